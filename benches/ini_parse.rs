@@ -0,0 +1,89 @@
+//! Measures the throughput of the `.ini` parser on a large machine-generated `.ini` config,
+//! both on its own (with an event handler which ignores all events) and when building a dynamic config.
+
+use {
+    criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput},
+    miniconfig::*,
+    std::fmt::Write,
+};
+
+const NUM_SECTIONS: u32 = 4096;
+const NUM_VALUES: u32 = 32;
+
+/// A large config with `NUM_SECTIONS` sections, each with `NUM_VALUES` values of mixed types and a comment.
+fn generate_ini() -> String {
+    let mut ini = String::new();
+
+    for section in 0..NUM_SECTIONS {
+        writeln!(ini, "; Generated section #{}", section).unwrap();
+        writeln!(ini, "[section_{}]", section).unwrap();
+
+        for value in 0..NUM_VALUES {
+            match value % 4 {
+                0 => writeln!(ini, "int_value_{} = {}", value, section * value),
+                1 => writeln!(ini, "float_value_{} = {}.5", value, value),
+                2 => writeln!(ini, "string_value_{} = unquoted_{}", value, value),
+                _ => writeln!(ini, "quoted_value_{} = \"quoted value {}\"", value, value),
+            }
+            .unwrap();
+        }
+
+        ini.push('\n');
+    }
+
+    ini
+}
+
+/// Ignores all events, so only the parser itself is measured.
+struct NullIniConfig;
+
+impl<'s> IniConfig<'s> for NullIniConfig {
+    fn contains_key(&self, _key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        None
+    }
+
+    fn add_value(
+        &mut self,
+        _key: NonEmptyIniStr<'s, '_>,
+        value: IniValue<'s, '_>,
+        _overwrite: bool,
+    ) {
+        black_box(value);
+    }
+
+    fn start_section(&mut self, _section: NonEmptyIniStr<'s, '_>, _overwrite: bool) {}
+
+    fn end_section(&mut self, _section: NonEmptyIniStr<'s, '_>) {}
+
+    fn start_array(&mut self, _array: NonEmptyIniStr<'s, '_>, _overwrite: bool) {}
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        black_box(value);
+    }
+
+    fn end_array(&mut self, _array: NonEmptyIniStr<'s, '_>) {}
+}
+
+fn ini_parse(c: &mut Criterion) {
+    let ini = generate_ini();
+
+    let mut group = c.benchmark_group("ini_parse");
+    group.throughput(Throughput::Bytes(ini.len() as u64));
+
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            IniParser::new(black_box(&ini))
+                .parse(&mut NullIniConfig)
+                .unwrap()
+        })
+    });
+
+    group.bench_function("DynConfig::from_ini", |b| {
+        b.iter(|| black_box(DynConfig::from_ini(IniParser::new(black_box(&ini))).unwrap()))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, ini_parse);
+criterion_main!(benches);
@@ -0,0 +1,78 @@
+//! Compares looking up a nested table directly in the binary config data blob (`BinConfig::table_at_path`)
+//! with converting the whole binary config to a dynamic config first.
+
+use {
+    criterion::{black_box, criterion_group, criterion_main, Criterion},
+    miniconfig::*,
+    std::num::NonZeroU32,
+};
+
+const NUM_SECTIONS: u32 = 512;
+const NUM_VALUES: u32 = 64;
+
+/// A large config with `NUM_SECTIONS` sections, each with a nested table of `NUM_VALUES` values.
+fn write_config(sort_keys: bool) -> BinConfig {
+    let mut writer = BinConfigWriter::new(NonZeroU32::new(NUM_SECTIONS).unwrap()).unwrap();
+    writer.sort_keys(sort_keys).unwrap();
+
+    for section in 0..NUM_SECTIONS {
+        writer
+            .table(format!("section_{}", section).as_str(), 1)
+            .unwrap();
+        writer.table("nested", NUM_VALUES).unwrap();
+
+        for value in 0..NUM_VALUES {
+            writer
+                .i64(format!("value_{}", value).as_str(), value as i64)
+                .unwrap();
+        }
+
+        writer.end().unwrap();
+        writer.end().unwrap();
+    }
+
+    BinConfig::new(writer.finish().unwrap()).unwrap()
+}
+
+fn table_at_path(c: &mut Criterion) {
+    let path: [ConfigKey; 2] = ["section_500".into(), "nested".into()];
+
+    for &sort_keys in [false, true].iter() {
+        let config = write_config(sort_keys);
+        let suffix = if sort_keys { " (sorted keys)" } else { "" };
+
+        c.bench_function(&format!("table_at_path{}", suffix), |b| {
+            b.iter(|| {
+                black_box(
+                    config
+                        .table_at_path(black_box(&path))
+                        .unwrap()
+                        .get_i64("value_63")
+                        .unwrap(),
+                )
+            })
+        });
+    }
+
+    #[cfg(feature = "dyn")]
+    {
+        let config = write_config(false);
+
+        c.bench_function("to_dyn_config + get_table_path", |b| {
+            b.iter(|| {
+                black_box(
+                    config
+                        .to_dyn_config()
+                        .root()
+                        .get_table_path(black_box(&path))
+                        .unwrap()
+                        .get_i64("value_63")
+                        .unwrap(),
+                )
+            })
+        });
+    }
+}
+
+criterion_group!(benches, table_at_path);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+use {super::util::to_array_error, crate::*, std::borrow::Cow};
+
+impl Config for BinConfig {
+    type Table<'a> = BinTable<'a>;
+
+    fn root(&self) -> Self::Table<'_> {
+        BinConfig::root(self)
+    }
+}
+
+impl<'t> TableAccess for BinTable<'t> {
+    type String = &'t str;
+    type Array = BinArray<'t>;
+
+    fn len(&self) -> u32 {
+        BinTable::len(self)
+    }
+
+    fn get_val(&self, key: &str) -> Option<BinConfigValue<'t>> {
+        BinTable::get_val(self, key)
+    }
+
+    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = (&'t str, BinConfigValue<'t>)> + 'i> {
+        Box::new(BinTable::iter(self).map(|(key, value)| (key.as_str(), value)))
+    }
+
+    fn get_str_cow(&self, key: &str) -> Result<Cow<'_, str>, TableError> {
+        BinTable::get_string(self, key).map(Cow::Borrowed)
+    }
+}
+
+impl<'a> ArrayAccess for BinArray<'a> {
+    type String = &'a str;
+    type Table = BinTable<'a>;
+
+    fn len(&self) -> u32 {
+        BinArray::len(self)
+    }
+
+    fn get_val(&self, index: u32) -> Result<BinConfigValue<'a>, ArrayError> {
+        BinArray::get_val(self, index).map_err(to_array_error)
+    }
+
+    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = BinConfigValue<'a>> + 'i> {
+        Box::new(BinArray::iter(self))
+    }
+
+    fn get_str_cow(&self, index: u32) -> Result<Cow<'_, str>, ArrayError> {
+        BinArray::get_string(self, index)
+            .map_err(to_array_error)
+            .map(Cow::Borrowed)
+    }
+}
@@ -1,10 +1,12 @@
 use {
-    super::{array_or_table::BinArrayOrTable, value::BinConfigUnpackedValue},
+    super::{array_or_table::BinArrayOrTable, util::u64_to_bin, value::BinConfigUnpackedValue},
     crate::{util::*, *},
     std::{
         borrow::Borrow,
         fmt::{Display, Formatter, Write},
         iter::Iterator,
+        mem::{align_of, size_of},
+        slice::from_raw_parts,
     },
 };
 
@@ -28,6 +30,55 @@ impl<'a> BinArray<'a> {
         self.len() == 0
     }
 
+    /// Returns the [`type`] of the elements of the [`array`], or `None` if the [`array`] is empty.
+    ///
+    /// NOTE - numeric [`arrays`] may contain both `i64` and `f64` elements,
+    /// in which case the [`type`] of the first element is returned.
+    ///
+    /// [`type`]: enum.ValueType.html
+    /// [`array`]: struct.BinArray.html
+    /// [`arrays`]: struct.BinArray.html
+    pub fn value_type(&self) -> Option<ValueType> {
+        if self.is_empty() {
+            None
+        } else if self.0.f32_elements {
+            Some(ValueType::F64)
+        } else {
+            // Safe to call - the array is not empty.
+            unsafe { self.0.packed_value(0) }.try_value_type()
+        }
+    }
+
+    /// Returns the elements of the [`array`] as a slice of `i64`'s without copying,
+    /// if all its elements are `i64`'s and the [`array`] has a typed copy in the data blob
+    /// (see [`BinConfigWriter::typed_arrays`]).
+    ///
+    /// Returns an empty slice if the [`array`] is empty.
+    ///
+    /// NOTE - does not call the [`audit hook`], if any.
+    ///
+    /// [`array`]: struct.BinArray.html
+    /// [`BinConfigWriter::typed_arrays`]: struct.BinConfigWriter.html#method.typed_arrays
+    /// [`audit hook`]: struct.BinConfig.html#method.set_audit_hook
+    pub fn as_i64_slice(&self) -> Option<&'a [i64]> {
+        self.as_typed_slice(ValueType::I64)
+    }
+
+    /// Returns the elements of the [`array`] as a slice of `f64`'s without copying,
+    /// if all its elements are `f64`'s and the [`array`] has a typed copy in the data blob
+    /// (see [`BinConfigWriter::typed_arrays`]).
+    ///
+    /// Returns an empty slice if the [`array`] is empty.
+    ///
+    /// NOTE - does not call the [`audit hook`], if any.
+    ///
+    /// [`array`]: struct.BinArray.html
+    /// [`BinConfigWriter::typed_arrays`]: struct.BinConfigWriter.html#method.typed_arrays
+    /// [`audit hook`]: struct.BinConfig.html#method.set_audit_hook
+    pub fn as_f64_slice(&self) -> Option<&'a [f64]> {
+        self.as_typed_slice(ValueType::F64)
+    }
+
     /// Tries to get a reference to a [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds.
@@ -36,7 +87,8 @@ impl<'a> BinArray<'a> {
     /// [`array`]: struct.BinArray.html
     /// [`error`]: enum.BinArrayError.html
     pub fn get_val(&self, index: u32) -> Result<BinConfigValue<'a>, BinArrayError> {
-        self.get_impl(index)
+        self.0
+            .audit_key(|| Some(index.into()), self.get_impl(index))
     }
 
     /// Tries to get an immutable reference to a [`value`] in the [`array`] at `index`,
@@ -51,7 +103,11 @@ impl<'a> BinArray<'a> {
         &self,
         index: u32,
     ) -> Result<V, BinArrayError> {
-        V::try_from(self.get_val(index)?).map_err(BinArrayError::IncorrectValueType)
+        let result = self
+            .get_impl(index)
+            .and_then(|value| V::try_from(value).map_err(BinArrayError::IncorrectValueType));
+
+        self.0.audit_key(|| Some(index.into()), result)
     }
 
     /// Tries to get an immutable reference to a [`value`] in the [`array`] at `path`.
@@ -75,9 +131,8 @@ impl<'a> BinArray<'a> {
         K: Borrow<ConfigKey<'k>>,
         P: IntoIterator<Item = K>,
     {
-        BinConfigValue::Array(BinArray(self.0.clone()))
-            .get_path(path.into_iter())
-            .map_err(GetPathError::reverse)
+        self.0
+            .audit_path(path.into_iter(), |path| self.get_val_path_impl(path))
     }
 
     /// Tries to get an immutable reference to a [`value`] in the [`array`] at `path`,
@@ -103,7 +158,9 @@ impl<'a> BinArray<'a> {
         P: IntoIterator<Item = K>,
         V: TryFromValue<&'a str, BinArray<'a>, BinTable<'a>>,
     {
-        V::try_from(self.get_val_path(path)?).map_err(GetPathError::IncorrectValueType)
+        self.0.audit_path(path.into_iter(), |path| {
+            V::try_from(self.get_val_path_impl(path)?).map_err(GetPathError::IncorrectValueType)
+        })
     }
 
     /// Tries to get a [`bool`] [`value`] in the [`array`] at `index`.
@@ -140,6 +197,46 @@ impl<'a> BinArray<'a> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`date/time`] [`value`] in the [`array`] at `index`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`date/time`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`error`]: enum.BinArrayError.html
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime(&self, index: u32) -> Result<DateTime, BinArrayError> {
+        self.get(index)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`array`] at `path`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`] or an [`array`](enum.Value.html#variant.Array) value.
+    /// The last key must correspond to a [`date/time`] [`value`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`table`]: enum.Value.html#variant.Table
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime_path<'k, K, P>(&self, path: P) -> Result<DateTime, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
     /// Tries to get an [`i64`] [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds or if value is not an [`i64`] / [`f64`].
@@ -176,6 +273,74 @@ impl<'a> BinArray<'a> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u32`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u32`].
+    ///
+    /// [`u32`]: https://doc.rust-lang.org/std/primitive.u32.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`error`]: enum.BinArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.BinArrayError.html#variant.ValueOutOfRange
+    pub fn get_u32(&self, index: u32) -> Result<u32, BinArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(BinArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get an [`i32`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of an [`i32`].
+    ///
+    /// [`i32`]: https://doc.rust-lang.org/std/primitive.i32.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`error`]: enum.BinArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.BinArrayError.html#variant.ValueOutOfRange
+    pub fn get_i32(&self, index: u32) -> Result<i32, BinArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(BinArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u16`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u16`].
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/std/primitive.u16.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`error`]: enum.BinArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.BinArrayError.html#variant.ValueOutOfRange
+    pub fn get_u16(&self, index: u32) -> Result<u16, BinArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(BinArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u8`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u8`].
+    ///
+    /// [`u8`]: https://doc.rust-lang.org/std/primitive.u8.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`error`]: enum.BinArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.BinArrayError.html#variant.ValueOutOfRange
+    pub fn get_u8(&self, index: u32) -> Result<u8, BinArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(BinArrayError::ValueOutOfRange))
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds or if value is not an [`f64`] / [`i64`].
@@ -323,7 +488,56 @@ impl<'a> BinArray<'a> {
         Self(array)
     }
 
-    fn get_impl(&self, index: u32) -> Result<BinConfigValue<'a>, BinArrayError> {
+    fn get_val_path_impl<'k, K, P>(&self, path: P) -> Result<BinConfigValue<'a>, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: Iterator<Item = K>,
+    {
+        BinConfigValue::Array(BinArray(self.0.clone()))
+            .get_path(path)
+            .map_err(GetPathError::reverse)
+    }
+
+    /// `T` must be `i64` / `f64` for `value_type` `I64` / `F64`.
+    fn as_typed_slice<T>(&self, value_type: ValueType) -> Option<&'a [T]> {
+        if self.is_empty() {
+            return Some(&[]);
+        }
+
+        // Typed copies are stored in data blob endianness.
+        // Arrays with elements stored as `f32`'s have no typed copies.
+        if u64_to_bin(1) != 1 || self.0.f32_elements {
+            return None;
+        }
+
+        // Safe to call - the array is not empty.
+        let first = unsafe { self.0.packed_value(0) };
+
+        // The offset to the typed copy of the array is stored in the key hash of its first element.
+        let typed_offset = first.key().hash;
+
+        // The array has no typed copy, or its elements are of a different type.
+        // NOTE - arrays with typed copies were validated to be homogeneous.
+        if typed_offset == 0 || first.try_value_type() != Some(value_type) {
+            return None;
+        }
+
+        // Safe to call - the typed copy was validated.
+        let typed = unsafe {
+            self.0
+                .slice(typed_offset, self.len() * size_of::<T>() as u32)
+                .as_ptr()
+        };
+
+        // The data blob itself might not be properly aligned.
+        if (typed as usize) % align_of::<T>() != 0 {
+            return None;
+        }
+
+        Some(unsafe { from_raw_parts(typed as *const T, self.len() as usize) })
+    }
+
+    pub(super) fn get_impl(&self, index: u32) -> Result<BinConfigValue<'a>, BinArrayError> {
         use BinArrayError::*;
 
         // Index out of bounds.
@@ -340,32 +554,43 @@ impl<'a> BinArray<'a> {
                 BinConfigUnpackedValue::String { offset, len } => {
                     Value::String(unsafe { self.0.string(offset, len) })
                 } // Safe to call - the string was validated.
-                Array { offset, len } => Value::Array(BinArray::new(BinArrayOrTable::new(
-                    self.0.base,
-                    self.0.key_table,
-                    offset,
-                    len,
-                ))),
-                Table { offset, len } => Value::Table(BinTable::new(BinArrayOrTable::new(
-                    self.0.base,
-                    self.0.key_table,
-                    offset,
-                    len,
-                ))),
+                Array { offset, len, f32 } => Value::Array(BinArray::new(
+                    self.0
+                        .nested(offset, len, || index.into())
+                        .f32_elements(f32),
+                )),
+                Table { offset, len } => {
+                    Value::Table(BinTable::new(self.0.nested(offset, len, || index.into())))
+                }
+                #[cfg(feature = "datetime")]
+                BinConfigUnpackedValue::DateTime { offset, len } => {
+                    // Safe to call - the date/time string was validated.
+                    Value::DateTime(unsafe { self.0.datetime(offset, len) })
+                }
             };
 
             Ok(value)
         }
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        // Arrays of `bool`'s / numbers may be serialized in compact form.
+        if is_compact_lua_array(self.iter().next().map(|value| value.get_type()), options) {
+            return write_compact_lua_array(w, self.iter(), self.len(), indent, options);
+        }
+
         writeln!(w, "{{")?;
 
         // Iterate the array.
         for (index, value) in self.iter().enumerate() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            options.formatter.write_indent(w, indent + 1)?;
 
-            value.fmt_lua(w, indent + 1)?;
+            value.fmt_lua(w, indent + 1, options)?;
 
             write!(w, ",")?;
 
@@ -378,7 +603,7 @@ impl<'a> BinArray<'a> {
             writeln!(w)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
+        options.formatter.write_indent(w, indent)?;
         write!(w, "}}")?;
 
         Ok(())
@@ -411,7 +636,7 @@ impl<'a> Iterator for BinArrayIter<'a> {
 
             // Must succeed - all indices are valid.
             Some(unwrap_unchecked(
-                self.array.get_val(index),
+                self.array.get_impl(index),
                 "invalid index in array iterator",
             ))
         } else {
@@ -421,14 +646,19 @@ impl<'a> Iterator for BinArrayIter<'a> {
 }
 
 impl<'a> DisplayLua for BinArray<'a> {
-    fn fmt_lua<W: Write>(&self, f: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(f, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        f: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(f, indent, options)
     }
 }
 
 impl<'a> Display for BinArray<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, Default::default())
     }
 }
 
@@ -451,7 +681,7 @@ mod tests {
         assert_eq!(
             config
                 .root()
-                .get_array("array".into())
+                .get_array("array")
                 .unwrap()
                 .get_val(1)
                 .err()
@@ -477,7 +707,7 @@ mod tests {
         assert_eq!(
             config
                 .root()
-                .get_array("array".into())
+                .get_array("array")
                 .unwrap()
                 .get_bool(0)
                 .unwrap(),
@@ -509,7 +739,7 @@ mod tests {
         let config = BinConfig::new(data).unwrap();
         let root = config.root();
 
-        let _ = root.get_array("array".into()).unwrap();
+        let _ = root.get_array("array").unwrap();
         let array = root.get_array(key!("array")).unwrap();
 
         assert_eq!(
@@ -563,4 +793,210 @@ mod tests {
         let val: f64 = array.get(0).unwrap();
         assert!(cmp_f64(val, 3.14));
     }
+
+    #[test]
+    fn BinArrayError_ValueOutOfRange() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.i64(None, 255).unwrap();
+        writer.i64(None, -1).unwrap();
+        writer.end().unwrap();
+
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+        let array = config.root().get_array("array").unwrap();
+
+        assert_eq!(array.get_u8(0).unwrap(), 255);
+        assert_eq!(array.get_i32(1).unwrap(), -1);
+        assert_eq!(
+            array.get_u8(1).err().unwrap(),
+            BinArrayError::ValueOutOfRange(-1)
+        );
+    }
+
+    #[test]
+    fn typed_arrays() {
+        let write = |typed_arrays: bool, sort_keys: bool, compress_keys: bool| {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(5).unwrap()).unwrap();
+            writer.typed_arrays(typed_arrays).unwrap();
+            writer.sort_keys(sort_keys).unwrap();
+            writer.compress_keys(compress_keys).unwrap();
+            writer.checksum(sort_keys).unwrap();
+
+            writer
+                .array_from_iter(nestr!("ints"), vec![1, 2, 3])
+                .unwrap();
+            writer.string(nestr!("name"), "foo").unwrap();
+            writer.table(nestr!("table"), 2).unwrap();
+            writer
+                .array_from_iter(nestr!("floats"), vec![0.5, 1.5])
+                .unwrap();
+            writer
+                .array_from_iter(nestr!("empty"), Vec::<i64>::new())
+                .unwrap();
+            writer.end().unwrap();
+            writer
+                .array_from_iter(nestr!("mixed"), vec![7.into(), Value::F64(3.5)])
+                .unwrap();
+            writer
+                .array_from_iter(nestr!("bools"), vec![true, false])
+                .unwrap();
+
+            writer.finish().unwrap()
+        };
+
+        for (sort_keys, compress_keys) in
+            [(false, false), (true, false), (false, true), (true, true)].iter()
+        {
+            let config = BinConfig::new(write(true, *sort_keys, *compress_keys)).unwrap();
+            let root = config.root();
+
+            let ints = root.get_array("ints").unwrap();
+            assert_eq!(ints.value_type(), Some(ValueType::I64));
+            assert_eq!(ints.as_i64_slice().unwrap(), &[1, 2, 3]);
+            assert!(ints.as_f64_slice().is_none());
+            // Typed arrays are still accessible as usual.
+            assert_eq!(ints.get_i64(2).unwrap(), 3);
+
+            let floats = root
+                .get_array_path(&["table".into(), "floats".into()])
+                .unwrap();
+            assert_eq!(floats.value_type(), Some(ValueType::F64));
+            assert_eq!(floats.as_f64_slice().unwrap(), &[0.5, 1.5]);
+            assert!(floats.as_i64_slice().is_none());
+
+            let empty = root
+                .get_array_path(&["table".into(), "empty".into()])
+                .unwrap();
+            assert_eq!(empty.value_type(), None);
+            assert!(empty.as_i64_slice().unwrap().is_empty());
+            assert!(empty.as_f64_slice().unwrap().is_empty());
+
+            let mixed = root.get_array("mixed").unwrap();
+            assert_eq!(mixed.value_type(), Some(ValueType::I64));
+            assert!(mixed.as_i64_slice().is_none());
+            assert!(mixed.as_f64_slice().is_none());
+            assert_eq!(mixed.get_f64(1).unwrap(), 3.5);
+
+            let bools = root.get_array("bools").unwrap();
+            assert_eq!(bools.value_type(), Some(ValueType::Bool));
+            assert!(bools.as_i64_slice().is_none());
+        }
+
+        // No typed copies are written by default.
+        let config = BinConfig::new(write(false, false, false)).unwrap();
+        let ints = config.root().get_array("ints").unwrap();
+        assert_eq!(ints.value_type(), Some(ValueType::I64));
+        assert!(ints.as_i64_slice().is_none());
+
+        // Typed copies must match the array elements.
+        let mut data = write(true, false, false).into_vec();
+        let ints = data
+            .windows(8 * 3)
+            .rposition(|window| {
+                window[..8] == 1i64.to_le_bytes() && window[16..] == 3i64.to_le_bytes()
+            })
+            .unwrap();
+        data[ints] = 2;
+        assert_eq!(
+            BinConfig::new(data.into_boxed_slice()).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+
+        // Only supported by `V3` data blobs.
+        let mut writer =
+            BinConfigWriter::with_version(NonZeroU32::new(1).unwrap(), BinConfigVersion::V2)
+                .unwrap();
+        assert_eq!(
+            writer.typed_arrays(true).err().unwrap(),
+            BinConfigWriterError::TypedArraysNotSupported(BinConfigVersion::V2)
+        );
+    }
+
+    #[test]
+    fn f32_arrays() {
+        let write = |f32_arrays: bool, typed_arrays: bool, embed_schema: bool| {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(5).unwrap()).unwrap();
+            writer.f32_arrays(f32_arrays).unwrap();
+            writer.typed_arrays(typed_arrays).unwrap();
+            writer.embed_schema(embed_schema).unwrap();
+            writer.sort_keys(true).unwrap();
+
+            writer
+                .array_from_iter(nestr!("curve"), vec![0.0, 0.25, 0.5, 1.0, -2.5])
+                .unwrap();
+            writer.array(nestr!("curves"), 2).unwrap();
+            writer.array_from_iter(None, vec![0.5]).unwrap();
+            writer.array_from_iter(None, vec![1.5, 2.5]).unwrap();
+            writer.end().unwrap();
+            writer.array_from_iter(nestr!("ints"), vec![1, 2]).unwrap();
+            writer
+                .array_from_iter(nestr!("mixed"), vec![Value::F64(0.5), 7.into()])
+                .unwrap();
+            writer.string(nestr!("name"), "foo").unwrap();
+
+            writer.finish().unwrap()
+        };
+
+        let full = write(false, false, false);
+        let packed = write(true, false, false);
+        assert!(packed.len() < full.len());
+
+        for (typed_arrays, embed_schema) in [(false, false), (true, false), (false, true)].iter() {
+            let config = BinConfig::new(write(true, *typed_arrays, *embed_schema)).unwrap();
+            let root = config.root();
+
+            let curve = root.get_array("curve").unwrap();
+            assert_eq!(curve.len(), 5);
+            assert_eq!(curve.value_type(), Some(ValueType::F64));
+            assert_eq!(
+                curve
+                    .iter()
+                    .map(|value| value.f64().unwrap())
+                    .collect::<Vec<_>>(),
+                vec![0.0, 0.25, 0.5, 1.0, -2.5]
+            );
+            assert_eq!(curve.get_f64(4).unwrap(), -2.5);
+            assert_eq!(curve.get_i64(1).unwrap(), 0);
+            assert!(curve.as_f64_slice().is_none());
+
+            assert_eq!(
+                root.get_f64_path(&["curves".into(), 1.into(), 1.into()])
+                    .unwrap(),
+                2.5
+            );
+
+            // Non-`f64` arrays are stored as usual.
+            let ints = root.get_array("ints").unwrap();
+            assert_eq!(ints.get_i64(1).unwrap(), 2);
+            assert_eq!(ints.as_i64_slice().is_some(), *typed_arrays);
+
+            let mixed = root.get_array("mixed").unwrap();
+            assert_eq!(mixed.get_i64(1).unwrap(), 7);
+
+            assert_eq!(root.get_string("name").unwrap(), "foo");
+
+            if *embed_schema {
+                let schema = config.schema().unwrap();
+                let curve = schema
+                    .children
+                    .iter()
+                    .find(|child| child.key.as_ref().unwrap().as_str() == "curve")
+                    .unwrap();
+                assert_eq!(curve.value_type, ValueType::Array);
+                assert_eq!(curve.children.len(), 5);
+                assert_eq!(curve.children[0].value_type, ValueType::F64);
+            }
+        }
+
+        // Only supported by `V3` data blobs.
+        let mut writer =
+            BinConfigWriter::with_version(NonZeroU32::new(1).unwrap(), BinConfigVersion::V2)
+                .unwrap();
+        assert_eq!(
+            writer.f32_arrays(true).err().unwrap(),
+            BinConfigWriterError::F32ArraysNotSupported(BinConfigVersion::V2)
+        );
+    }
 }
@@ -1,8 +1,12 @@
 use {
     super::{util::*, value::*},
-    std::{mem::size_of, slice::from_raw_parts, str::from_utf8_unchecked},
+    crate::*,
+    std::{borrow::Borrow, mem::size_of, slice::from_raw_parts, str::from_utf8_unchecked},
 };
 
+#[cfg(feature = "audit")]
+use super::audit::{Access, BinAudit};
+
 /// Represents an interned UTF-8 string in the string section of the binary config.
 ///
 /// Fields are in whatever endianness we use; see `super::util::__to_bin_bytes(), _from_bin()`.
@@ -39,6 +43,13 @@ pub(super) struct BinArrayOrTable<'at> {
     pub(super) offset: u32,
     /// Number of elements in this array/table.
     pub(super) len: u32,
+    /// Whether the values of all tables in the binary config data blob are sorted by key hash.
+    pub(super) sorted_keys: bool,
+    /// Whether this is an array with elements stored as `f32`'s (instead of packed values).
+    pub(super) f32_elements: bool,
+    /// Audit hook of the config and the path to this array/table, if the config has an audit hook.
+    #[cfg(feature = "audit")]
+    pub(super) audit: Option<BinAudit<'at>>,
 }
 
 impl<'at> BinArrayOrTable<'at> {
@@ -47,15 +58,93 @@ impl<'at> BinArrayOrTable<'at> {
         key_table: &'at [InternedString],
         offset: u32,
         len: u32,
+        sorted_keys: bool,
     ) -> Self {
         Self {
             base,
             key_table,
             offset,
             len,
+            sorted_keys,
+            f32_elements: false,
+            #[cfg(feature = "audit")]
+            audit: None,
         }
     }
 
+    /// Creates the nested array/table at `offset` with length `len`,
+    /// with the (lazily evaluated) `key` in this array/table.
+    pub(super) fn nested<F: FnOnce() -> OwnedConfigKey>(
+        &self,
+        offset: u32,
+        len: u32,
+        key: F,
+    ) -> Self {
+        #[cfg(not(feature = "audit"))]
+        let _ = key;
+
+        Self {
+            base: self.base,
+            key_table: self.key_table,
+            offset,
+            len,
+            sorted_keys: self.sorted_keys,
+            f32_elements: false,
+            #[cfg(feature = "audit")]
+            audit: self.audit.as_ref().map(|audit| audit.nested(key())),
+        }
+    }
+
+    /// Marks this array as an array with elements stored as `f32`'s, if `f32_elements` is `true`.
+    pub(super) fn f32_elements(mut self, f32_elements: bool) -> Self {
+        self.f32_elements = f32_elements;
+        self
+    }
+
+    /// Reports the access of the value with the (lazily evaluated) `key` in this array/table
+    /// to the audit hook, if any, and returns the accessor `result`.
+    #[cfg(feature = "audit")]
+    pub(super) fn audit_key<F: FnOnce() -> Option<OwnedConfigKey>, R: Access>(
+        &self,
+        key: F,
+        result: R,
+    ) -> R {
+        if let Some(audit) = &self.audit {
+            audit.report(key(), result.access());
+        }
+
+        result
+    }
+
+    #[cfg(not(feature = "audit"))]
+    pub(super) fn audit_key<F: FnOnce() -> Option<OwnedConfigKey>, R>(
+        &self,
+        _key: F,
+        result: R,
+    ) -> R {
+        result
+    }
+
+    /// Calls the accessor `f` with the `path` (relative to this array/table)
+    /// and reports the access to the audit hook, if any.
+    pub(super) fn audit_path<'k, K, P, V, F>(&self, path: P, f: F) -> Result<V, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: Iterator<Item = K>,
+        F: FnOnce(&mut dyn Iterator<Item = K>) -> Result<V, GetPathError>,
+    {
+        let mut path = path;
+
+        #[cfg(feature = "audit")]
+        {
+            if let Some(audit) = &self.audit {
+                return audit.report_path(path, f);
+            }
+        }
+
+        f(&mut path)
+    }
+
     /// Returns the range of bytes within the binary config data blob
     /// occupied by the packed values of this array / table.
     pub(super) fn offset_range(&self) -> std::ops::Range<u32> {
@@ -66,20 +155,35 @@ impl<'at> BinArrayOrTable<'at> {
         self.key_table
     }
 
-    pub(super) fn key_table_size(&self) -> u32 {
-        (self.key_table.len() * size_of::<InternedString>()) as u32
-    }
-
     /// Reads and returns an unpacked value at `index` of this array/table.
+    /// Elements of arrays stored as `f32`'s are widened to `f64`'s.
     /// NOTE - the caller ensures the array/table is not empty and `index` is in range.
     pub(super) unsafe fn value(&self, index: u32) -> BinConfigUnpackedValue {
-        self.packed_value(index).unpack()
+        if self.f32_elements {
+            BinConfigUnpackedValue::F64(self.f32_value(index) as f64)
+        } else {
+            self.packed_value(index).unpack()
+        }
+    }
+
+    /// Reads and returns the element at `index` of this array with elements stored as `f32`'s.
+    /// NOTE - the caller ensures the array is not empty and `index` is in range.
+    unsafe fn f32_value(&self, index: u32) -> f32 {
+        debug_assert!(index < self.len, "`index` must be in range.");
+
+        let offset = self.offset + index * size_of::<f32>() as u32;
+        let bits = (self.base.offset(offset as isize) as *const u32).read_unaligned();
+
+        f32::from_bits(u32_from_bin(bits))
     }
 
     /// Returns a reference to the packed value at `index` of this array/table
     /// in the binary config data blob.
-    /// NOTE - the caller ensures the array/table is not empty and `index` is in range.
+    /// NOTE - the caller ensures the array/table is not empty, has packed values (i.e. its elements are not stored as `f32`'s)
+    /// and `index` is in range.
     pub(super) unsafe fn packed_value(&self, index: u32) -> &'at BinConfigPackedValue {
+        debug_assert!(!self.f32_elements, "array elements are stored as `f32`'s.");
+
         &*(self.base.offset(self.packed_value_offset(index) as isize) as *const _)
     }
 
@@ -104,15 +208,20 @@ impl<'at> BinArrayOrTable<'at> {
         from_utf8_unchecked(self.slice(offset, len))
     }
 
+    /// Parses the RFC 3339 date/time string in the binary config data blob at `offset` with length `len`.
+    /// NOTE - the caller ensures `offset` and `len` are valid and that the string contains a valid date/time;
+    /// panics if it does not contain a valid date/time.
+    #[cfg(feature = "datetime")]
+    pub(super) unsafe fn datetime(&self, offset: u32, len: u32) -> crate::DateTime {
+        self.string(offset, len)
+            .parse::<crate::DateTime>()
+            .expect("invalid binary config date/time value")
+    }
+
     /// Looks up the key table with `index`.
-    /// NOTE - the caller ensures `index` is valid.
+    /// NOTE - the caller ensures `index` is valid; panics otherwise.
     pub(super) unsafe fn key_ofset_and_len(&self, index: u32) -> &InternedString {
-        let key_table = self.key_table();
-        let index = index as usize;
-
-        debug_assert!(index < self.key_table().len(), "`index` must be in range.");
-
-        key_table.get_unchecked(index)
+        &self.key_table()[index as usize]
     }
 
     /// Reads and returns a table key and an unpacked value at `index` of this array/table.
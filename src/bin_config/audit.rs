@@ -0,0 +1,164 @@
+use {
+    crate::*,
+    std::{borrow::Borrow, rc::Rc},
+};
+
+/// Result of a [`binary config`] value access, reported to the [`audit hook`].
+///
+/// [`binary config`]: struct.BinConfig.html
+/// [`audit hook`]: trait.ConfigAuditHook.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigAccess {
+    /// The value exists and is of the requested [`type`].
+    ///
+    /// [`type`]: enum.ValueType.html
+    Hit,
+    /// The value does not exist
+    /// (e.g. the table key is misspelled, or the array index is out of bounds).
+    Miss,
+    /// The value exists, but is of incorrect and incompatible [`type`].
+    /// Contains the actual value [`type`].
+    ///
+    /// [`type`]: enum.ValueType.html
+    TypeMismatch(ValueType),
+}
+
+/// A hook which is called on every value access in the [`binary config`] it is [`set`] on,
+/// e.g. to build runtime heat-maps of config usage,
+/// or to catch misspelled keys which silently fall back to defaults.
+///
+/// NOTE - requires `"audit"` feature.
+///
+/// Value accesses are calls to [`table`] / [`array`] `get*()` accessors,
+/// including the ones which return the `default` value.
+/// Iteration, [`contains`] and serialization are not reported.
+///
+/// [`binary config`]: struct.BinConfig.html
+/// [`set`]: struct.BinConfig.html#method.set_audit_hook
+/// [`table`]: struct.BinTable.html
+/// [`array`]: struct.BinArray.html
+/// [`contains`]: struct.BinTable.html#method.contains
+pub trait ConfigAuditHook: Send + Sync {
+    /// Called when the value at `path` is accessed.
+    ///
+    /// `path` is the full path to the accessed value from the root table of the config.
+    /// For [`path`](struct.BinTable.html#method.get_path) accessors which failed,
+    /// `path` is the path to the first invalid key.
+    fn on_get(&self, path: &ConfigPath, access: ConfigAccess);
+}
+
+/// Audit hook and the path from the root table of the config
+/// of the binary array / table it is attached to.
+#[derive(Clone)]
+pub(super) struct BinAudit<'h> {
+    hook: &'h dyn ConfigAuditHook,
+    path: Rc<ConfigPath>,
+}
+
+impl<'h> BinAudit<'h> {
+    /// Creates the audit context of the root table.
+    pub(super) fn new(hook: &'h dyn ConfigAuditHook) -> Self {
+        Self {
+            hook,
+            path: Rc::new(ConfigPath::new()),
+        }
+    }
+
+    /// Creates the audit context of the nested array / table with the `key`.
+    pub(super) fn nested(&self, key: OwnedConfigKey) -> Self {
+        let mut path = (*self.path).clone();
+        path.0.push(key);
+
+        Self {
+            hook: self.hook,
+            path: Rc::new(path),
+        }
+    }
+
+    /// Reports the `access` of the value at `keys` (relative to the array / table).
+    pub(super) fn report<I: IntoIterator<Item = OwnedConfigKey>>(
+        &self,
+        keys: I,
+        access: ConfigAccess,
+    ) {
+        let mut path = (*self.path).clone();
+        path.0.extend(keys);
+
+        self.hook.on_get(&path, access);
+    }
+
+    /// Calls `f` with the `path` (relative to the array / table),
+    /// recording the consumed config keys, and reports the access.
+    pub(super) fn report_path<'k, K, P, V, F>(&self, path: P, f: F) -> Result<V, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: Iterator<Item = K>,
+        F: FnOnce(&mut dyn Iterator<Item = K>) -> Result<V, GetPathError>,
+    {
+        let mut keys = Vec::new();
+
+        let result = f(&mut path.inspect(|key| match key.borrow() {
+            ConfigKey::Table(key) => {
+                if let Some(key) = NonEmptyStr::new(key.as_str()) {
+                    keys.push(key.into());
+                }
+            }
+            ConfigKey::Array(index) => keys.push((*index).into()),
+        }));
+
+        self.report(keys, result.access());
+
+        result
+    }
+}
+
+/// Helper trait to determine the [`access`](enum.ConfigAccess.html) result from the accessor return value.
+pub(super) trait Access {
+    fn access(&self) -> ConfigAccess;
+}
+
+impl<V> Access for Option<V> {
+    fn access(&self) -> ConfigAccess {
+        if self.is_some() {
+            ConfigAccess::Hit
+        } else {
+            ConfigAccess::Miss
+        }
+    }
+}
+
+impl<V> Access for Result<V, TableError> {
+    fn access(&self) -> ConfigAccess {
+        match self {
+            Ok(_) => ConfigAccess::Hit,
+            Err(TableError::IncorrectValueType(value_type)) => {
+                ConfigAccess::TypeMismatch(*value_type)
+            }
+            Err(_) => ConfigAccess::Miss,
+        }
+    }
+}
+
+impl<V> Access for Result<V, BinArrayError> {
+    fn access(&self) -> ConfigAccess {
+        match self {
+            Ok(_) => ConfigAccess::Hit,
+            Err(BinArrayError::IncorrectValueType(value_type)) => {
+                ConfigAccess::TypeMismatch(*value_type)
+            }
+            Err(_) => ConfigAccess::Miss,
+        }
+    }
+}
+
+impl<V> Access for Result<V, GetPathError> {
+    fn access(&self) -> ConfigAccess {
+        match self {
+            Ok(_) => ConfigAccess::Hit,
+            Err(GetPathError::IncorrectValueType(value_type)) => {
+                ConfigAccess::TypeMismatch(*value_type)
+            }
+            Err(_) => ConfigAccess::Miss,
+        }
+    }
+}
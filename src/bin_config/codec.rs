@@ -0,0 +1,117 @@
+//! User-supplied transform (e.g. encryption / obfuscation) of the binary config data blob payload.
+//!
+//! Encoded data blob layout (the data blob header has the `BIN_CONFIG_HEADER_FLAG_ENCODED` flag set):
+//!
+//! |- header -|- encoded payload -|
+//!
+//! - the header is the header of the decoded data blob (except the flags and the checksum),
+//!   i.e. all offsets are w.r.t. the decoded data blob,
+//! - the payload is the rest of the decoded data blob after the header, transformed by the user codec,
+//! - the checksum, if any, is calculated over the encoded data blob.
+//!
+//! The payload is decoded when the data blob is loaded.
+
+use {
+    super::config::*,
+    crate::{util::unwrap_unchecked, *},
+};
+
+/// A user-supplied transform (e.g. XOR obfuscation, AES encryption) applied to the binary config data blob payload
+/// (everything after the header - values, keys, strings, etc.).
+///
+/// The payload is [`encoded`] by the binary config [`writer`] (after the key table / payload compression, if any)
+/// and [`decoded`] when the data blob is [`loaded`].
+/// The data blob header is never transformed and is always validated by the crate.
+///
+/// [`encoded`]: #tymethod.encode
+/// [`decoded`]: #tymethod.decode
+/// [`writer`]: struct.BinConfigWriter.html#method.codec
+/// [`loaded`]: struct.BinConfig.html#method.with_codec
+pub trait BinConfigCodec: Send + Sync {
+    /// Transforms (e.g. encrypts) the binary config data blob `payload`.
+    /// The size of the returned encoded payload may differ from the size of the `payload`.
+    fn encode(&self, payload: &[u8]) -> Vec<u8>;
+
+    /// Reverses the transform applied by [`encode`] (e.g. decrypts) to the encoded binary config data blob `payload`.
+    ///
+    /// Returns `None` if the `payload` could not be decoded
+    /// (e.g. its authentication failed) - the data blob is then reported as [`failed to decode`].
+    ///
+    /// NOTE - the decoded payload is validated by the crate as usual.
+    ///
+    /// [`encode`]: #tymethod.encode
+    /// [`failed to decode`]: enum.BinConfigError.html#variant.DecodeFailed
+    fn decode(&self, payload: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// Encodes the payload of the valid unencoded binary config `data` blob using the `codec`.
+pub(super) fn encode(data: &[u8], codec: &dyn BinConfigCodec) -> Box<[u8]> {
+    let header = unwrap_unchecked(
+        BinConfigHeader::read(data).ok(),
+        "invalid binary config header",
+    );
+    debug_assert!(!header.encoded());
+    debug_assert!(header.checksum().is_none());
+
+    let header_size = header.version().header_size() as usize;
+
+    let payload = codec.encode(&data[header_size..]);
+
+    let mut result = Vec::with_capacity(header_size + payload.len());
+
+    result.extend_from_slice(&data[..header_size]);
+    result.extend_from_slice(&payload);
+
+    // Set the flag in the header.
+    BinConfigHeader::write_flags(&mut result, header.flags() | BIN_CONFIG_HEADER_FLAG_ENCODED);
+
+    result.into_boxed_slice()
+}
+
+/// Decodes the payload of the binary config `data` blob with the `header`
+/// (with the `BIN_CONFIG_HEADER_FLAG_ENCODED` flag set) using the `codec`.
+///
+/// NOTE - the caller verifies the checksum, if any, and validates the returned data blob.
+pub(super) fn decode(
+    data: &[u8],
+    header: &BinConfigHeader,
+    codec: &dyn BinConfigCodec,
+) -> Result<Box<[u8]>, BinConfigError> {
+    use BinConfigError::*;
+
+    debug_assert!(header.encoded());
+
+    // Empty binary config root tables are not supported.
+    if header.len() == 0 {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let header_size = header.version().header_size() as usize;
+
+    let payload = codec.decode(&data[header_size..]).ok_or(DecodeFailed)?;
+
+    // Make sure the decoded data blob is not too large.
+    if header_size + payload.len() > u32::MAX as usize {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let mut result = Vec::with_capacity(header_size + payload.len());
+
+    // Clear the flags in the header which do not apply to the decoded data blob
+    // (the checksum was verified).
+    BinConfigHeader::write(
+        &mut result,
+        header.version(),
+        header.flags() & !(BIN_CONFIG_HEADER_FLAG_ENCODED | BIN_CONFIG_HEADER_FLAG_CHECKSUM),
+        header.len(),
+        header.key_table_offset(),
+        header.key_table_len(),
+    )
+    .map_err(|_| InvalidBinaryConfigData)?;
+
+    debug_assert_eq!(result.len(), header_size);
+
+    result.extend_from_slice(&payload);
+
+    Ok(result.into_boxed_slice())
+}
@@ -0,0 +1,219 @@
+//! Compression of the binary config data blob payload.
+//!
+//! Compressed data blob layout (the data blob header has the `BIN_CONFIG_HEADER_FLAG_COMPRESSED` flag set):
+//!
+//! |- header -|- compression algorithm (u32) -|- payload size (u32) -|- compressed payload -|
+//!
+//! - the header is the header of the uncompressed data blob (except the flags and the checksum),
+//!   i.e. all offsets are w.r.t. the uncompressed data blob,
+//! - the payload is the rest of the uncompressed data blob after the header
+//!   (values, key table, strings, schema block, if any),
+//! - the checksum, if any, is calculated over the compressed data blob.
+//!
+//! The payload is decompressed when the data blob is loaded.
+
+use {
+    super::{config::*, util::*},
+    crate::*,
+    std::mem::size_of,
+};
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+use {
+    crate::util::unwrap_unchecked,
+    std::fmt::{Display, Formatter},
+};
+
+/// LZ4 (block format) compression algorithm.
+#[cfg(feature = "lz4")]
+const COMPRESSION_LZ4: u32 = 1;
+
+/// Zstandard compression algorithm.
+#[cfg(feature = "zstd")]
+const COMPRESSION_ZSTD: u32 = 2;
+
+/// Maximum ratio of the uncompressed payload size to the compressed payload size.
+///
+/// The LZ4 block format never exceeds this ratio, and Zstandard only does for degenerate payloads,
+/// which are recorded uncompressed instead.
+/// Data blobs with a larger recorded payload size are rejected before anything is decompressed,
+/// as the payload size may be forged to allocate an arbitrarily large buffer.
+const MAX_DECOMPRESSION_RATIO: usize = 255;
+
+/// Compression algorithm / level of the binary config data blob payload,
+/// used by the binary config [`writer`].
+///
+/// NOTE - requires `"lz4"` and / or `"zstd"` features.
+///
+/// [`writer`]: struct.BinConfigWriter.html#method.compression
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CompressionLevel {
+    /// The data blob is not compressed.
+    #[default]
+    None,
+    /// The data blob payload is compressed using LZ4 - fast compression and decompression, moderate compression ratio.
+    ///
+    /// NOTE - requires `"lz4"` feature.
+    #[cfg(feature = "lz4")]
+    Lz4,
+    /// The data blob payload is compressed using Zstandard - slower compression and decompression, better compression ratio.
+    /// Contains the Zstandard compression level (`1` ..= `22`; `0` means the default level).
+    ///
+    /// NOTE - requires `"zstd"` feature.
+    #[cfg(feature = "zstd")]
+    Zstd(i32),
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl Display for CompressionLevel {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CompressionLevel::None => "none".fmt(f),
+            #[cfg(feature = "lz4")]
+            CompressionLevel::Lz4 => "LZ4".fmt(f),
+            #[cfg(feature = "zstd")]
+            CompressionLevel::Zstd(level) => write!(f, "Zstandard (level {})", level),
+        }
+    }
+}
+
+/// Compresses the payload of the valid uncompressed binary config `data` blob
+/// using the `compression` algorithm / level.
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+pub(super) fn compress(
+    data: &[u8],
+    compression: CompressionLevel,
+) -> Result<Box<[u8]>, BinConfigWriterError> {
+    let header = unwrap_unchecked(
+        BinConfigHeader::read(data).ok(),
+        "invalid binary config header",
+    );
+    debug_assert!(!header.compressed());
+    debug_assert!(header.checksum().is_none());
+
+    let header_size = header.version().header_size() as usize;
+    let payload = &data[header_size..];
+
+    let (algorithm, compressed) = match compression {
+        CompressionLevel::None => return Ok(data.into()),
+        #[cfg(feature = "lz4")]
+        CompressionLevel::Lz4 => (COMPRESSION_LZ4, lz4_flex::block::compress(payload)),
+        #[cfg(feature = "zstd")]
+        CompressionLevel::Zstd(level) => {
+            (COMPRESSION_ZSTD, zstd_rs::bulk::compress(payload, level)?)
+        }
+    };
+
+    // Loading the data blob would fail - leave it uncompressed.
+    if payload.len() > compressed.len().saturating_mul(MAX_DECOMPRESSION_RATIO) {
+        return Ok(data.into());
+    }
+
+    let mut result = Vec::with_capacity(header_size + 2 * size_of::<u32>() + compressed.len());
+
+    result.extend_from_slice(&data[..header_size]);
+    result.extend_from_slice(&u32_to_bin_bytes(algorithm));
+    result.extend_from_slice(&u32_to_bin_bytes(payload.len() as u32));
+    result.extend_from_slice(&compressed);
+
+    // Set the flag in the header.
+    BinConfigHeader::write_flags(
+        &mut result,
+        header.flags() | BIN_CONFIG_HEADER_FLAG_COMPRESSED,
+    );
+
+    Ok(result.into_boxed_slice())
+}
+
+/// Decompresses the payload of the binary config `data` blob with the `header`
+/// (with the `BIN_CONFIG_HEADER_FLAG_COMPRESSED` flag set).
+///
+/// NOTE - the caller verifies the checksum, if any, and validates the returned data blob.
+pub(super) fn decompress(
+    data: &[u8],
+    header: &BinConfigHeader,
+) -> Result<Box<[u8]>, BinConfigError> {
+    use BinConfigError::*;
+
+    debug_assert!(header.compressed());
+
+    let header_size = header.version().header_size() as usize;
+
+    // Make sure the data is large enough to contain the compression algorithm and the payload size.
+    if data.len() < header_size + 2 * size_of::<u32>() {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let algorithm = read_u32(data, header_size);
+    let payload_size = read_u32(data, header_size + size_of::<u32>()) as usize;
+
+    // Empty binary config root tables are not supported.
+    if header.len() == 0 {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let compressed = &data[header_size + 2 * size_of::<u32>()..];
+
+    // Make sure the decompressed data blob is not too large before allocating it.
+    if header_size + payload_size > u32::MAX as usize
+        || payload_size > compressed.len().saturating_mul(MAX_DECOMPRESSION_RATIO)
+    {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let payload = decompress_payload(algorithm, compressed, payload_size)?;
+
+    if payload.len() != payload_size {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let mut result = Vec::with_capacity(header_size + payload_size);
+
+    // Clear the flags in the header which do not apply to the decompressed data blob
+    // (the checksum was verified).
+    BinConfigHeader::write(
+        &mut result,
+        header.version(),
+        header.flags() & !(BIN_CONFIG_HEADER_FLAG_COMPRESSED | BIN_CONFIG_HEADER_FLAG_CHECKSUM),
+        header.len(),
+        header.key_table_offset(),
+        header.key_table_len(),
+    )
+    .map_err(|_| InvalidBinaryConfigData)?;
+
+    debug_assert_eq!(result.len(), header_size);
+
+    result.extend_from_slice(&payload);
+
+    Ok(result.into_boxed_slice())
+}
+
+/// Decompresses the `compressed` payload of `payload_size` bytes using the `algorithm`.
+#[allow(unused_variables)]
+fn decompress_payload(
+    algorithm: u32,
+    compressed: &[u8],
+    payload_size: usize,
+) -> Result<Vec<u8>, BinConfigError> {
+    use BinConfigError::*;
+
+    match algorithm {
+        #[cfg(feature = "lz4")]
+        COMPRESSION_LZ4 => lz4_flex::block::decompress(compressed, payload_size)
+            .map_err(|_| InvalidBinaryConfigData),
+        #[cfg(feature = "zstd")]
+        COMPRESSION_ZSTD => {
+            zstd_rs::bulk::decompress(compressed, payload_size).map_err(|_| InvalidBinaryConfigData)
+        }
+        algorithm => Err(UnsupportedCompression(algorithm)),
+    }
+}
+
+/// Reads the `u32` at `offset` in bytes from the `data` blob.
+/// The caller ensures the data is large enough.
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&data[offset..offset + size_of::<u32>()]);
+    u32_from_bin(u32::from_ne_bytes(bytes))
+}
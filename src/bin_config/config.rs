@@ -1,11 +1,21 @@
 use {
     super::{
         array_or_table::{BinArrayOrTable, InternedString},
+        codec::decode,
+        compression::decompress,
+        keys::decompress_keys,
+        localization::{localize, string_table},
+        schema::*,
+        typed::{numeric_array_type, TYPED_ARRAY_ALIGNMENT},
         util::*,
-        value::BinConfigPackedValue,
+        value::{f32_array_size, BinConfigPackedValue},
+    },
+    crate::{
+        util::{unwrap_unchecked, DisplayLua},
+        *,
     },
-    crate::{util::DisplayLua, *},
     std::{
+        borrow::Borrow,
         fmt::{Display, Formatter, Write},
         mem::size_of,
         slice::from_raw_parts,
@@ -15,10 +25,24 @@ use {
 #[cfg(feature = "dyn")]
 use std::ops::DerefMut;
 
+#[cfg(feature = "audit")]
+use {super::audit::BinAudit, std::sync::Arc};
+
 /// Represents an immutable config with a root [`table`].
 ///
+/// The config is both [`Send`] and [`Sync`] and may be shared between threads (e.g. via an [`Arc`]).
+/// The [`tables`] / [`arrays`] borrowed from the config are neither.
+///
 /// [`table`]: struct.BinTable.html
-pub struct BinConfig(Box<[u8]>);
+/// [`tables`]: struct.BinTable.html
+/// [`arrays`]: struct.BinArray.html
+/// [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+pub struct BinConfig(
+    Box<[u8]>,
+    /// Audit hook called on value accesses, if any.
+    #[cfg(feature = "audit")]
+    Option<Arc<dyn ConfigAuditHook>>,
+);
 
 impl BinConfig {
     /// Tries to create a new [`config`] from the `data` binary blob.
@@ -27,15 +51,81 @@ impl BinConfig {
     /// if the `data` is not a valid binary config data blob,
     /// e.g. returned by the binary config [`writer`].
     ///
+    /// If the `data` contains a [`checksum`], it is verified first.
+    /// If the `data` payload is [`compressed`], it is decompressed into an internal buffer.
+    ///
+    /// Data blobs of previous supported [`versions`] are converted to the [`current`] version.
+    ///
     /// [`config`]: struct.BinConfig.html
     /// [`error`]: enum.BinConfigError.html
     /// [`writer`]: struct.BinConfigWriter.html
+    /// [`checksum`]: struct.BinConfigWriter.html#method.checksum
+    /// [`compressed`]: struct.BinConfigWriter.html#method.compression
+    /// [`versions`]: enum.BinConfigVersion.html
+    /// [`current`]: enum.BinConfigVersion.html#associatedconstant.CURRENT
     pub fn new(data: Box<[u8]>) -> Result<Self, BinConfigError> {
+        Self::new_impl(data, None, DEFAULT_MAX_DEPTH)
+    }
+
+    /// Like [`new`], but the [`arrays`] / [`tables`] in the `data` may be nested at most `max_depth` levels deep
+    /// (the [`arrays`] / [`tables`] in the root [`table`] are at depth `1`),
+    /// instead of [`DEFAULT_MAX_DEPTH`].
+    ///
+    /// Returns a [`MaxDepthExceeded`] error if the `data` contains [`arrays`] / [`tables`] nested deeper.
+    ///
+    /// [`new`]: #method.new
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`table`]: struct.BinTable.html
+    /// [`DEFAULT_MAX_DEPTH`]: constant.DEFAULT_MAX_DEPTH.html
+    /// [`MaxDepthExceeded`]: enum.BinConfigError.html#variant.MaxDepthExceeded
+    pub fn with_max_depth(data: Box<[u8]>, max_depth: u32) -> Result<Self, BinConfigError> {
+        Self::new_impl(data, None, max_depth)
+    }
+
+    /// Like [`new`], but the `data` payload may have been [`encoded`] (e.g. encrypted) by the `codec`;
+    /// it is decoded into an internal buffer.
+    ///
+    /// Data blobs with unencoded payloads are loaded as usual.
+    ///
+    /// [`new`]: #method.new
+    /// [`encoded`]: struct.BinConfigWriter.html#method.codec
+    pub fn with_codec(data: Box<[u8]>, codec: &dyn BinConfigCodec) -> Result<Self, BinConfigError> {
+        Self::new_impl(data, Some(codec), DEFAULT_MAX_DEPTH)
+    }
+
+    fn new_impl(
+        data: Box<[u8]>,
+        codec: Option<&dyn BinConfigCodec>,
+        max_depth: u32,
+    ) -> Result<Self, BinConfigError> {
+        // Verify the checksum, decode and decompress the payload / key table, if necessary.
+        let data = Self::unpack(&data, codec)?.unwrap_or(data);
+
         // Try to validate the data.
-        Self::validate_data(&data)?;
+        let header = Self::validate_data(&data, max_depth)?;
         // Seems to be fine?
 
-        Ok(Self(data))
+        if header.version() == BinConfigVersion::CURRENT {
+            Ok(Self::from_data(data))
+        } else {
+            Self::convert(&data, &header).map(Self::from_data)
+        }
+    }
+
+    /// Returns the [`version`] of the binary config `data` blob,
+    /// or an [`error`] if the `data` is not a binary config data blob of a supported [`version`].
+    ///
+    /// NOTE - does not validate the rest of the `data`.
+    ///
+    /// [`version`]: enum.BinConfigVersion.html
+    /// [`error`]: enum.BinConfigError.html
+    pub fn data_version(data: &[u8]) -> Result<BinConfigVersion, BinConfigError> {
+        if data.len() < Self::max_header_size() {
+            return Err(BinConfigError::InvalidBinaryConfigData);
+        }
+
+        BinConfigHeader::read(data).map(|header| header.version())
     }
 
     /// Attempts to validate the binary config `data` blob and returns an [`error`]
@@ -46,20 +136,107 @@ impl BinConfig {
     /// [`error`]: enum.BinConfigError.html
     /// [`writer`]: struct.BinConfigWriter.html
     pub fn validate(data: &Box<[u8]>) -> Result<(), BinConfigError> {
-        Self::validate_data(&data)
+        Self::validate_impl(data, None)
+    }
+
+    /// Like [`validate`], but the `data` payload may have been [`encoded`] (e.g. encrypted) by the `codec`.
+    ///
+    /// [`validate`]: #method.validate
+    /// [`encoded`]: struct.BinConfigWriter.html#method.codec
+    pub fn validate_with_codec(
+        data: &Box<[u8]>,
+        codec: &dyn BinConfigCodec,
+    ) -> Result<(), BinConfigError> {
+        Self::validate_impl(data, Some(codec))
+    }
+
+    fn validate_impl(
+        data: &[u8],
+        codec: Option<&dyn BinConfigCodec>,
+    ) -> Result<(), BinConfigError> {
+        if let Some(data) = Self::unpack(data, codec)? {
+            Self::validate_data(&data, DEFAULT_MAX_DEPTH)
+        } else {
+            Self::validate_data(data, DEFAULT_MAX_DEPTH)
+        }
+        .map(|_| ())
     }
 
     /// Like [`new`], but does not validate the `data` at all.
     ///
     /// # Safety
     ///
-    /// It's up to the user to ensure that `data` is a valid binary config data blob,
-    /// e.g. returned by the binary config [`writer`].
+    /// It's up to the user to ensure that `data` is a valid binary config data blob
+    /// of the [`current`] version, e.g. returned by the binary config [`writer`].
     ///
     /// [`new`]: #method.new
+    /// [`current`]: enum.BinConfigVersion.html#associatedconstant.CURRENT
     /// [`writer`]: struct.BinConfigWriter.html
     pub unsafe fn new_unchecked(data: Box<[u8]>) -> Self {
-        Self(data)
+        Self::from_data(data)
+    }
+
+    /// Like [`new`], but the string values of the config are remapped to the localized strings
+    /// in the `localization` table.
+    ///
+    /// See [`localized`].
+    ///
+    /// [`new`]: #method.new
+    /// [`localized`]: #method.localized
+    pub fn with_localization(
+        data: Box<[u8]>,
+        localization: &BinConfigLocalization,
+    ) -> Result<Self, BinConfigError> {
+        Self::new(data)?.localized(localization)
+    }
+
+    /// Returns the distinct string values of the [`config`], in string ID order
+    /// (i.e. the string ID of a string value is its index in the returned string table).
+    ///
+    /// The string ID of a string value is determined by the order of its first occurrence
+    /// when visiting the values depth-first, starting with the root [`table`] elements
+    /// (see [`BinTable::iter`] for the order of [`table`] elements).
+    /// Date/time values and [`table`] keys are not string values.
+    ///
+    /// String IDs only depend on the structure and the string values of the [`config`], not on its [`writer`] options,
+    /// so the string table may be exported for translation and [`localized`] strings may be mapped back
+    /// to the same (and any other language-independent) binary config data blob.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`table`]: struct.BinTable.html
+    /// [`BinTable::iter`]: struct.BinTable.html#method.iter
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`localized`]: #method.localized
+    pub fn string_table(&self) -> Vec<&str> {
+        // We ensured the data is validated.
+        let header = BinConfigHeader::read(&self.0).expect("invalid binary config header");
+
+        string_table(&self.0, &header)
+    }
+
+    /// Returns a copy of this [`config`] with its string values remapped to the localized strings
+    /// in the `localization` table by their [`string IDs`].
+    /// String values with no localized string in the `localization` table are unchanged.
+    ///
+    /// Structural data (values, keys, etc.) is shared by all localizations,
+    /// so that per-language configs don't need separate binary config data blobs.
+    ///
+    /// NOTE - the [`audit hook`] of this [`config`], if any, is not copied.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`string IDs`]: #method.string_table
+    /// [`audit hook`]: #method.set_audit_hook
+    pub fn localized(&self, localization: &BinConfigLocalization) -> Result<Self, BinConfigError> {
+        // We ensured the data is validated.
+        let header = BinConfigHeader::read(&self.0).expect("invalid binary config header");
+
+        localize(&self.0, &header, localization).map(|data| {
+            debug_assert!(
+                Self::validate_data(&data, u32::MAX).is_ok(),
+                "invalid localized binary config data blob"
+            );
+            Self::from_data(data)
+        })
     }
 
     /// Returns the immutable reference to the root [`table`] of the [`config`].
@@ -68,7 +245,95 @@ impl BinConfig {
     /// [`config`]: struct.BinConfig.html
     pub fn root(&self) -> BinTable<'_> {
         // We ensured the data is validated.
-        unsafe { Self::root_impl(&self.0) }
+        #[allow(unused_mut)]
+        let mut root = unsafe { Self::root_impl(&self.0) };
+
+        #[cfg(feature = "audit")]
+        {
+            root.0.audit = self.1.as_deref().map(BinAudit::new);
+        }
+
+        root
+    }
+
+    /// Tries to get a nested [`table`] at `path` in the root [`table`] of the [`config`]
+    /// directly from the data blob.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value;
+    /// the last key must correspond to a [`table`](enum.Value.html#variant.Table) value.
+    ///
+    /// Returns the root [`table`] itself if the `path` is empty.
+    ///
+    /// Only the packed values of the [`tables`] / [`arrays`] along the `path` (and the keys they are compared with) are read,
+    /// so the unrelated sections of large configs are never touched.
+    /// The lookup is `O(depth)` [`table`] lookups (each a linear scan, or a binary search if the [`keys are sorted`])
+    /// and does not allocate (unless an [`audit hook`] is set).
+    ///
+    /// [`table`]: struct.BinTable.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`config`]: struct.BinConfig.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`arrays`]: struct.BinArray.html
+    /// [`keys are sorted`]: struct.BinConfigWriter.html#method.sort_keys
+    /// [`audit hook`]: #method.set_audit_hook
+    pub fn table_at_path<'k, K, P>(&self, path: P) -> Result<BinTable<'_>, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.root().get_table_path(path)
+    }
+
+    /// Returns the [`schema`] of this [`config`] (i.e. the keys, [`types`] and nesting of all its values),
+    /// if the schema block was [`embedded`] in the data blob.
+    ///
+    /// Only reads the schema block (and the key table) of the data blob.
+    ///
+    /// [`schema`]: struct.BinConfigSchema.html
+    /// [`config`]: struct.BinConfig.html
+    /// [`types`]: enum.ValueType.html
+    /// [`embedded`]: struct.BinConfigWriter.html#method.embed_schema
+    pub fn schema(&self) -> Option<BinConfigSchema> {
+        // We ensured the data is validated.
+        let header = BinConfigHeader::read(&self.0).expect("invalid binary config header");
+        let (data, schema) =
+            split_schema(&self.0, &header).expect("invalid binary config schema block");
+
+        schema.map(|schema| read_schema(schema, data, &header))
+    }
+
+    /// Sets the [`audit hook`] called on every value access in this [`config`],
+    /// replacing the previous one, if any.
+    ///
+    /// NOTE - requires `"audit"` feature.
+    ///
+    /// NOTE - only affects the [`tables`] / [`arrays`] accessed via the [`root`] table
+    /// obtained after this call.
+    ///
+    /// [`audit hook`]: trait.ConfigAuditHook.html
+    /// [`config`]: struct.BinConfig.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`arrays`]: struct.BinArray.html
+    /// [`root`]: #method.root
+    #[cfg(feature = "audit")]
+    pub fn set_audit_hook(&mut self, hook: Arc<dyn ConfigAuditHook>) {
+        self.1.replace(hook);
+    }
+
+    /// Removes and returns the [`audit hook`] of this [`config`], if any.
+    ///
+    /// NOTE - requires `"audit"` feature.
+    ///
+    /// [`audit hook`]: trait.ConfigAuditHook.html
+    /// [`config`]: struct.BinConfig.html
+    #[cfg(feature = "audit")]
+    pub fn take_audit_hook(&mut self) -> Option<Arc<dyn ConfigAuditHook>> {
+        self.1.take()
     }
 
     /// Tries to serialize this [`config`] to a Lua script string.
@@ -92,7 +357,79 @@ impl BinConfig {
     ///
     /// [`config`]: struct.BinConfig.html
     pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), std::fmt::Error> {
-        self.root().fmt_lua(w, 0)
+        self.fmt_lua_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string using provided [`options`].
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`options`]: struct.ToLuaStringOptions.html
+    pub fn to_lua_string_opts(
+        &self,
+        options: ToLuaStringOptions,
+    ) -> Result<String, std::fmt::Error> {
+        let mut result = String::new();
+
+        self.fmt_lua_opts(options, &mut result)?;
+
+        result.shrink_to_fit();
+
+        Ok(result)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using provided [`options`].
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`options`]: struct.ToLuaStringOptions.html
+    pub fn fmt_lua_opts<W: Write>(
+        &self,
+        options: ToLuaStringOptions,
+        w: &mut W,
+    ) -> Result<(), std::fmt::Error> {
+        self.root()
+            .fmt_lua(&mut options.formatter.new_lines(w), 0, options)
+    }
+
+    /// Returns the 64-bit hash of the contents of this [`config`], calculated over its [`canonical`] serialization
+    /// to a Lua script string, so that logically equal configs (regardless of the config backend,
+    /// or the order in which their values were added) always have the same hash,
+    /// e.g. to be used as cache keys by build systems.
+    ///
+    /// NOTE - the hash is not a cryptographic hash, and is not guaranteed to be stable across versions of this crate.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`canonical`]: struct.ToLuaStringOptions.html#method.canonical
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = ContentHasher::new();
+
+        // Must succeed - the hasher never fails.
+        unwrap_unchecked(
+            self.fmt_lua_opts(ToLuaStringOptions::canonical(), &mut hasher),
+            "failed to hash a config",
+        );
+
+        hasher.finish()
+    }
+
+    /// Calls the methods on the `config` event handler with the sequence of events the [`.ini parser`]
+    /// would generate when parsing this [`config`] serialized to an `.ini` string,
+    /// so that the [`event handlers`] may consume both `.ini` config sources and in-memory configs.
+    ///
+    /// Tables are reported as (nested) sections, arrays of tables - as arrays of sections.
+    /// Section names / keys / string values are reported as [`owned`](enum.NonEmptyIniStr.html#variant.Owned).
+    ///
+    /// Returns an [`InvalidArrayType`] error if the [`config`] contains arrays of arrays.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`event handlers`]: trait.IniConfig.html
+    /// [`InvalidArrayType`]: enum.ToIniStringError.html#variant.InvalidArrayType
+    #[cfg(feature = "ini")]
+    pub fn replay_ini<'s, C: IniConfig<'s> + ?Sized>(
+        &self,
+        config: &mut C,
+    ) -> Result<(), ToIniStringError> {
+        replay_ini(&self.root(), config)
     }
 
     /// Tries to serialize this [`config`] to an `.ini` string using default [`options`].
@@ -143,7 +480,41 @@ impl BinConfig {
     ) -> Result<(), ToIniStringError> {
         let mut path = IniPath::new();
 
-        self.root().fmt_ini(w, 0, false, &mut path, options)
+        self.root().fmt_ini(
+            &mut options.formatter.new_lines(w),
+            0,
+            false,
+            &mut path,
+            options,
+        )
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string to the `std::io::Write` writer `w` (e.g. a file)
+    /// using default [`options`], without an intermediate string.
+    ///
+    /// NOTE - writes to `w` are not buffered - consider wrapping unbuffered writers in a `std::io::BufWriter`.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn write_ini<W: std::io::Write>(&self, w: &mut W) -> Result<(), ToIniStringError> {
+        self.write_ini_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string to the `std::io::Write` writer `w` (e.g. a file)
+    /// using provided [`options`], without an intermediate string.
+    ///
+    /// NOTE - writes to `w` are not buffered - consider wrapping unbuffered writers in a `std::io::BufWriter`.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn write_ini_opts<W: std::io::Write>(
+        &self,
+        options: ToIniStringOptions,
+        w: &mut W,
+    ) -> Result<(), ToIniStringError> {
+        self.fmt_ini_opts(options, &mut IoWriter(w))
     }
 
     /// Serializes this [`config`] to a [`dynamic config`].
@@ -159,6 +530,35 @@ impl BinConfig {
         result
     }
 
+    /// Tries to create a new [`config`] from the [`dynamic config`], copying all its tables / arrays / values.
+    ///
+    /// Equivalent to [`DynConfig::to_bin_config`], followed by [`new`].
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`dynamic config`]: struct.DynConfig.html
+    /// [`DynConfig::to_bin_config`]: struct.DynConfig.html#method.to_bin_config
+    /// [`new`]: #method.new
+    #[cfg(feature = "dyn")]
+    pub fn from_dyn_config(config: &DynConfig) -> Result<Self, BinConfigWriterError> {
+        let data = config.to_bin_config()?;
+
+        // The writer produces valid data blobs of the current version.
+        Ok(unsafe { Self::new_unchecked(data) })
+    }
+
+    fn from_data(data: Box<[u8]>) -> Self {
+        Self(
+            data,
+            #[cfg(feature = "audit")]
+            None,
+        )
+    }
+
+    /// Returns the (validated) binary config data blob.
+    pub(super) fn data(&self) -> &[u8] {
+        &self.0
+    }
+
     /// The caller ensures `key_table_offset` and `key_table_len` are valid and point to
     /// the actual key table in the `data` blob.
     unsafe fn key_table(
@@ -172,32 +572,37 @@ impl BinConfig {
         )
     }
 
-    /// The caller ensures the data is at least large enough for the header.
-    unsafe fn header(data: &[u8]) -> &BinConfigHeader {
-        &*(data.as_ptr() as *const _)
-    }
-
-    /// The caller ensures the data is at least large enough for the header.
+    /// The caller ensures the data is a valid binary config data blob of the current version.
     unsafe fn root_impl(data: &[u8]) -> BinTable<'_> {
-        BinTable::new(Self::root_raw_impl(data))
+        let header = BinConfigHeader::read(data).expect("invalid binary config header");
+
+        BinTable::new(Self::root_raw_impl(data, &header))
     }
 
     /// Constructs the root table from the binary config blob data.
-    /// NOTE - the caller ensures that data is a valid binary config header.
-    unsafe fn root_raw_impl(data: &[u8]) -> BinArrayOrTable<'_> {
-        let header = Self::header(data);
-
+    /// NOTE - the caller ensures that `header` was read from the `data` and is valid.
+    unsafe fn root_raw_impl<'d>(data: &'d [u8], header: &BinConfigHeader) -> BinArrayOrTable<'d> {
         BinArrayOrTable::new(
             data.as_ptr(), // Base address of the binary config.
             Self::key_table(data, header.key_table_offset(), header.key_table_len()),
-            size_of::<BinConfigHeader>() as u32, // Offset to the first value of the root table is the size of the header.
-            header.len(), // Config root table length as read from the header.
+            header.version().header_size(), // Offset to the first value of the root table is the size of the header.
+            header.len(),                   // Config root table length as read from the header.
+            header.sorted_keys(),
         )
     }
 
+    /// Size of the largest header of all supported versions.
+    /// Data blobs smaller than this are rejected before the header is read.
+    fn max_header_size() -> usize {
+        BinConfigVersion::V1
+            .header_size()
+            .max(BinConfigVersion::V2.header_size())
+            .max(BinConfigVersion::V3.header_size()) as _
+    }
+
     /// Header, one value, one key table entry and the shortest possible key.
-    const fn min_size() -> usize {
-        size_of::<BinConfigHeader>()
+    fn min_size(version: BinConfigVersion) -> usize {
+        version.header_size() as usize
             + size_of::<BinConfigPackedValue>()
             + size_of::<InternedString>()
             + Self::min_string_section_size()
@@ -208,8 +613,8 @@ impl BinConfig {
     }
 
     /// Key table comes after the header and at least one value.
-    const fn min_key_table_offset() -> usize {
-        size_of::<BinConfigHeader>() + size_of::<BinConfigPackedValue>()
+    fn min_key_table_offset(version: BinConfigVersion) -> usize {
+        version.header_size() as usize + size_of::<BinConfigPackedValue>()
     }
 
     /// 1 byte + terminating null char.
@@ -217,57 +622,209 @@ impl BinConfig {
         2
     }
 
-    fn validate_data(data: &[u8]) -> Result<(), BinConfigError> {
+    /// Verifies the [`checksum`] of the binary config `data` blob, if any.
+    /// If the binary config `data` blob has an [`encoded`] payload, decodes it using the `codec`.
+    /// If the binary config `data` blob has a [`compressed`] payload and / or a [`compressed`](struct.BinConfigWriter.html#method.compress_keys) key table,
+    /// returns the decompressed data blob.
+    ///
+    /// [`checksum`]: struct.BinConfigWriter.html#method.checksum
+    /// [`encoded`]: struct.BinConfigWriter.html#method.codec
+    /// [`compressed`]: struct.BinConfigWriter.html#method.compression
+    fn unpack(
+        data: &[u8],
+        codec: Option<&dyn BinConfigCodec>,
+    ) -> Result<Option<Box<[u8]>>, BinConfigError> {
+        // Let `validate_data()` handle the invalid data.
+        if data.len() < Self::max_header_size() {
+            return Ok(None);
+        }
+
+        let header = BinConfigHeader::read(data)?;
+
+        if let Some(checksum) = header.checksum() {
+            if BinConfigHeader::calculate_checksum(data) != checksum {
+                return Err(BinConfigError::ChecksumMismatch);
+            }
+        }
+
+        // Empty binary config root tables are not supported.
+        // Reject them before the header is rewritten for the unpacked data blob.
+        if (header.encoded() || header.compressed() || header.compressed_keys())
+            && header.len() == 0
+        {
+            return Err(BinConfigError::InvalidBinaryConfigData);
+        }
+
+        if header.encoded() {
+            let codec = codec.ok_or(BinConfigError::CodecRequired)?;
+
+            let data = decode(data, &header, codec)?;
+
+            Ok(Some(Self::unpack_decoded(&data)?.unwrap_or(data)))
+        } else {
+            Self::unpack_decoded(data)
+        }
+    }
+
+    /// If the decoded binary config `data` blob has a [`compressed`] payload and / or a [`compressed`](struct.BinConfigWriter.html#method.compress_keys) key table,
+    /// returns the decompressed data blob.
+    ///
+    /// [`compressed`]: struct.BinConfigWriter.html#method.compression
+    fn unpack_decoded(data: &[u8]) -> Result<Option<Box<[u8]>>, BinConfigError> {
+        // Let `validate_data()` handle the invalid data.
+        if data.len() < Self::max_header_size() {
+            return Ok(None);
+        }
+
+        let header = BinConfigHeader::read(data)?;
+
+        if header.compressed() {
+            let data = decompress(data, &header)?;
+
+            // Let `validate_data()` handle the invalid data.
+            if data.len() < Self::max_header_size() {
+                return Ok(Some(data));
+            }
+
+            let header = BinConfigHeader::read(&data)?;
+
+            Ok(Some(Self::unpack_keys(&data, &header)?.unwrap_or(data)))
+        } else {
+            Self::unpack_keys(data, &header)
+        }
+    }
+
+    /// If the binary config `data` blob with the `header` has a [`compressed`] key table,
+    /// returns the data blob with the decompressed key table.
+    ///
+    /// [`compressed`]: struct.BinConfigWriter.html#method.compress_keys
+    fn unpack_keys(
+        data: &[u8],
+        header: &BinConfigHeader,
+    ) -> Result<Option<Box<[u8]>>, BinConfigError> {
+        if header.compressed_keys() {
+            // The schema block, if any, follows the compressed key table.
+            let (data, schema) = split_schema(data, header)?;
+
+            let data = decompress_keys(data, header)?;
+
+            if let Some(schema) = schema {
+                let mut data = data.into_vec();
+
+                BinConfigHeader::write_flags(
+                    &mut data,
+                    (header.flags() & BIN_CONFIG_HEADER_VALUE_FLAGS)
+                        | BIN_CONFIG_HEADER_FLAG_SCHEMA,
+                );
+
+                data.extend_from_slice(schema);
+                data.extend_from_slice(&u32_to_bin_bytes(schema.len() as u32));
+
+                Ok(Some(data.into_boxed_slice()))
+            } else {
+                Ok(Some(data))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Validates the binary config `data` blob of any supported version, returns its header.
+    fn validate_data(data: &[u8], max_depth: u32) -> Result<BinConfigHeader, BinConfigError> {
         use BinConfigError::*;
 
-        // Make sure the data is large enough to contain at least the header, one value, one key table entry and the shortest possible key.
-        if data.len() < Self::min_size() {
+        // Make sure the data is large enough to contain the header of any supported version
+        // (the smallest valid data blob of any version is larger than that).
+        if data.len() < Self::max_header_size() {
             return Err(InvalidBinaryConfigData);
         }
 
-        // Make sure the data is not too large.
-        if data.len() > Self::max_size() {
+        // Read the header, check the header magic and the version.
+        let header = BinConfigHeader::read(data)?;
+        let version = header.version();
+
+        // The payload must have been decoded, the payload and the key table must have been decompressed.
+        if header.encoded() || header.compressed() || header.compressed_keys() {
             return Err(InvalidBinaryConfigData);
         }
 
-        // Read the header.
-        let header = unsafe { BinConfig::header(&data) };
+        // Validate the data blob without the schema block, if any.
+        let (data, schema) = split_schema(data, &header)?;
+
+        // Make sure the data is large enough to contain at least the header, one value, one key table entry and the shortest possible key.
+        if data.len() < Self::min_size(version) {
+            return Err(InvalidBinaryConfigData);
+        }
 
-        // Check the header magic.
-        if !header.check_magic() {
+        // Make sure the data is not too large.
+        if data.len() > Self::max_size() {
             return Err(InvalidBinaryConfigData);
         }
 
         // Check the key table - must contain at least one table key, as we don't allow empty root tables.
-        if header.key_table_len == 0 {
+        if header.key_table_len() == 0 {
             return Err(InvalidBinaryConfigData);
         }
 
-        // |---------- header (16b) --------|-------- root table (16b) ------|- key table 0 (8b) -|2b|
+        // |---------- header (16b / 24b) --------|-------- root table (16b) ------|- key table 0 (8b) -|2b|
 
         // Make sure the key table lies within the config data blob.
+        // Minus shortest string section length - one byte and a null terminator.
+        let key_table_range = Self::checked_range_bytes(
+            header.key_table_offset(),
+            header
+                .key_table_len()
+                .checked_mul(size_of::<InternedString>() as u32)
+                .ok_or(InvalidBinaryConfigData)?,
+        )?;
+
         Self::validate_range(
-            // Minus shortest string section length - one byte and a null terminator.
-            Self::min_key_table_offset() as u32
+            Self::min_key_table_offset(version) as u32
                 ..data.len() as u32 - Self::min_string_section_size() as u32,
-            header.key_table_range(),
+            key_table_range.clone(),
         )?;
 
+        // Make sure all key strings lie within the string section (which follows the key table), are null-terminated,
+        // non-empty and valid UTF-8.
+        Self::validate_key_table(data, &header, key_table_range.end)?;
+
         // Check the root table.
         if header.len() > 0 {
-            let root = unsafe { Self::root_raw_impl(data) };
+            let root = unsafe { Self::root_raw_impl(data, &header) };
 
-            // Make sure the root table values lie within the config data blob.
+            // All array / table values lie between the header and the key table.
             // Offset to the first value of the root table is the size of the header.
-            // Last value of the root table may be just before the key table and the shortest string section.
-            let valid_range = size_of::<BinConfigHeader>() as u32
-                ..data.len() as u32
-                    - Self::min_string_section_size() as u32
-                    - header.key_table_size();
+            let values_range = version.header_size()..key_table_range.start;
+
+            // Make sure the root table values lie within the config data blob.
+            Self::validate_range(
+                values_range.clone(),
+                Self::checked_range(root.offset, header.len())?,
+            )?;
 
-            Self::validate_range(valid_range.clone(), root.offset_range())?;
+            let mut validator = BinConfigValidator {
+                data,
+                values_start: values_range.start,
+                values_end: values_range.end,
+                strings_offset: key_table_range.end,
+                // Non-overlapping nested arrays / tables may contain at most this many values.
+                // NOTE - this guarantees validation time linear in the data blob size.
+                values_left: (values_range.end - values_range.start)
+                    / size_of::<BinConfigPackedValue>() as u32
+                    - header.len(),
+                depth: 0,
+                max_depth,
+                typed_arrays: header.typed_arrays(),
+                f32_arrays: header.f32_arrays(),
+            };
+
+            validator.validate_table(&root)?;
+
+            if let Some(schema) = schema {
+                validate_schema(schema, data, &header)?;
+            }
 
-            Self::validate_table(data, header.key_table_offset, &root)
+            Ok(header)
 
         // Empty binary config root tables are not supported.
         } else {
@@ -275,157 +832,167 @@ impl BinConfig {
         }
     }
 
-    fn validate_table(
-        data: &[u8],
-        key_table_offset: u32,
-        table: &BinArrayOrTable<'_>,
-    ) -> Result<(), BinConfigError> {
-        use BinConfigError::*;
-
-        // Empty tables must have no data offset.
-        if table.len == 0 && table.offset != 0 {
-            return Err(InvalidBinaryConfigData);
+    /// Converts the validated binary config `data` blob of a previous version with the `header`
+    /// to the current version.
+    ///
+    /// All sections of the data blob keep their relative layout;
+    /// all offsets w.r.t. the data blob are relocated by the difference in header sizes.
+    fn convert(data: &[u8], header: &BinConfigHeader) -> Result<Box<[u8]>, BinConfigError> {
+        let old_header_size = header.version().header_size();
+        let new_header_size = BinConfigVersion::CURRENT.header_size();
+
+        debug_assert!(new_header_size >= old_header_size);
+        let delta = new_header_size - old_header_size;
+
+        // Make sure the converted data is not too large.
+        if data.len() + delta as usize > Self::max_size() {
+            return Err(BinConfigError::InvalidBinaryConfigData);
         }
 
-        let key_table = unsafe { table.key_table() };
-        let key_table_size = table.key_table_size();
-
-        if table.len > 0 {
-            // Valid offset range for table values.
-            // Minimum offset to string/array/table values in the table is just past the table's packed values.
-            // Maximum offset is just before the key table and the shortest string section.
-            let mut valid_range = table.offset_range().end
-                ..data.len() as u32 - Self::min_string_section_size() as u32 - key_table_size;
+        let mut result = Vec::with_capacity(data.len() + delta as usize);
 
-            // Valid offset range for strings.
-            let valid_string_range = key_table_offset + key_table_size..data.len() as u32;
+        if BinConfigHeader::write(
+            &mut result,
+            BinConfigVersion::CURRENT,
+            0,
+            header.len(),
+            header.key_table_offset() + delta,
+            header.key_table_len(),
+        )
+        .is_err()
+        {
+            debug_unreachable!("writing to a `Vec` failed");
+        }
 
-            // For each table element.
-            for index in 0..table.len {
-                let value = unsafe { table.packed_value(index) };
+        result.extend_from_slice(&data[old_header_size as usize..]);
 
-                // All values in the table must have a key.
-                let key = value.key();
+        let base = result.as_mut_ptr();
 
-                // Validate the key.
-                //----------------------------------------------------------------------------------
-                // Key index must be in range.
-                if key.index as usize >= key_table.len() {
-                    return Err(InvalidBinaryConfigData);
-                }
+        // Relocate the key table.
+        let key_table = unsafe {
+            std::slice::from_raw_parts_mut(
+                base.add((header.key_table_offset() + delta) as _) as *mut InternedString,
+                header.key_table_len() as _,
+            )
+        };
 
-                let key_string = unsafe { key_table.get_unchecked(key.index as usize) };
+        for key in key_table.iter_mut() {
+            key.offset = u32_to_bin(key.offset() + delta);
+        }
 
-                // Key string must not be empty.
-                if key_string.len() == 0 {
-                    return Err(InvalidBinaryConfigData);
-                }
+        // Relocate the values.
+        let root = unsafe {
+            std::slice::from_raw_parts_mut(
+                base.add(new_header_size as _) as *mut BinConfigPackedValue,
+                header.len() as _,
+            )
+        };
 
-                // Make sure the key string and the null terminator lie within the config data blob (`+ 1`for null terminator).
-                Self::validate_range(
-                    valid_string_range.clone(),
-                    key_string.offset()..key_string.offset() + key_string.len() + 1,
-                )?;
+        Self::relocate_values(base, root, delta);
 
-                // Make sure the key string is null-terminated.
-                let null_terminator =
-                    unsafe { table.slice(key_string.offset() + key_string.len(), 1) };
+        Ok(result.into_boxed_slice())
+    }
 
-                if null_terminator[0] != b'\0' {
-                    return Err(InvalidBinaryConfigData);
+    /// Adds `delta` to all string / array / table offsets of the `values` and, recursively, their elements.
+    /// NOTE - the caller ensures the values are valid and `base` is the base address of the relocated data blob.
+    fn relocate_values(base: *mut u8, values: &mut [BinConfigPackedValue], delta: u32) {
+        for value in values.iter_mut() {
+            match value.value_type() {
+                // Empty strings have no data offset.
+                ValueType::String if value.len() > 0 => {
+                    value.set_offset(value.offset() + delta);
                 }
-
-                // Make sure the key string is valid UTF-8.
-                let key_slice = unsafe { table.slice(key_string.offset(), key_string.len()) };
-
-                let key_string =
-                    std::str::from_utf8(key_slice).map_err(|_| InvalidBinaryConfigData)?;
-
-                // Make sure the key hash matches the string.
-                if string_hash_fnv1a(key_string) != key.hash {
-                    return Err(InvalidBinaryConfigData);
+                // Date/time values are stored as strings.
+                #[cfg(feature = "datetime")]
+                ValueType::DateTime => {
+                    value.set_offset(value.offset() + delta);
                 }
-                //----------------------------------------------------------------------------------
-                // The key seems to be OK.
-
-                // Validate the value.
-                Self::validate_value(
-                    data,
-                    key_table_offset,
-                    key_table,
-                    &mut valid_range,
-                    valid_string_range.clone(),
-                    table,
-                    value,
-                )?;
-                // The value seems to be OK.
+                // Empty arrays / tables have no data offset.
+                ValueType::Array | ValueType::Table if value.len() > 0 => {
+                    let offset = value.offset() + delta;
+                    value.set_offset(offset);
+
+                    let values = unsafe {
+                        std::slice::from_raw_parts_mut(
+                            base.add(offset as _) as *mut BinConfigPackedValue,
+                            value.len() as _,
+                        )
+                    };
+
+                    Self::relocate_values(base, values, delta);
+                }
+                _ => {}
             }
         }
-
-        Ok(())
     }
 
-    fn validate_array(
+    /// Makes sure all key strings of the key table of the binary config `data` blob with the `header`
+    /// lie within the string section starting at `strings_offset`, are non-empty, null-terminated and valid UTF-8.
+    /// NOTE - the caller ensures the key table lies within the `data` blob.
+    fn validate_key_table(
         data: &[u8],
-        key_table_offset: u32,
-        valid_range_end: u32,
-        array: &BinArrayOrTable<'_>,
+        header: &BinConfigHeader,
+        strings_offset: u32,
     ) -> Result<(), BinConfigError> {
-        use BinConfigError::*;
-
-        // Empty arrays must have no data offset.
-        if array.len == 0 && array.offset != 0 {
-            return Err(InvalidBinaryConfigData);
-        }
+        let key_table =
+            unsafe { Self::key_table(data, header.key_table_offset(), header.key_table_len()) };
 
-        let key_table = unsafe { array.key_table() };
-        let key_table_size = array.key_table_size();
+        for key in key_table.iter() {
+            // Key string must not be empty.
+            if key.len() == 0 {
+                return Err(BinConfigError::InvalidBinaryConfigData);
+            }
 
-        // Valid offset range for array values.
-        // Minimum offset to string/array/table values in the array is just past the array's packed values.
-        // Maximum offset is just before the key table and the shortest string section.
-        let mut valid_range = array.offset_range().end..valid_range_end;
+            Self::validate_string(data, strings_offset, key.offset(), key.len())?;
+        }
 
-        // Valid offset range for strings.
-        let valid_string_range = key_table_offset + key_table_size..data.len() as u32;
+        Ok(())
+    }
 
-        let mut array_type: Option<ValueType> = None;
+    /// Makes sure the string at `offset` with `len` and its null terminator lie within the string section
+    /// of the `data` blob, starting at `strings_offset`, and that the string is valid UTF-8.
+    /// Returns the string.
+    fn validate_string(
+        data: &[u8],
+        strings_offset: u32,
+        offset: u32,
+        len: u32,
+    ) -> Result<&str, BinConfigError> {
+        use BinConfigError::*;
 
-        // For each array element.
-        for index in 0..array.len {
-            let value = unsafe { array.packed_value(index) };
+        // `+ 1` for null terminator.
+        let range =
+            Self::checked_range_bytes(offset, len.checked_add(1).ok_or(InvalidBinaryConfigData)?)?;
 
-            let value_type = value.value_type();
+        Self::validate_range(strings_offset..data.len() as u32, range)?;
 
-            if let Some(current_array_type) = array_type {
-                if !current_array_type.is_compatible(value_type) {
-                    return Err(InvalidBinaryConfigData);
-                } else {
-                    array_type.replace(value_type);
-                }
-            }
+        let string = &data[offset as usize..(offset + len) as usize];
 
-            // All values in the array must have no keys.
-            let key = value.key();
+        // Make sure the string is null-terminated.
+        if data[(offset + len) as usize] != b'\0' {
+            return Err(InvalidBinaryConfigData);
+        }
 
-            if key.hash != 0 || key.index != 0 {
-                return Err(InvalidBinaryConfigData);
-            }
+        // Make sure the string is valid UTF-8.
+        std::str::from_utf8(string).map_err(|_| InvalidBinaryConfigData)
+    }
 
-            // Validate the value.
-            Self::validate_value(
-                data,
-                key_table_offset,
-                key_table,
-                &mut valid_range,
-                valid_string_range.clone(),
-                array,
-                value,
-            )?;
-            // The value seems to be OK.
-        }
+    /// Returns the range of bytes occupied by `len` packed values at `offset`,
+    /// or an error if it overflows.
+    fn checked_range(offset: u32, len: u32) -> Result<std::ops::Range<u32>, BinConfigError> {
+        Self::checked_range_bytes(
+            offset,
+            len.checked_mul(size_of::<BinConfigPackedValue>() as u32)
+                .ok_or(BinConfigError::InvalidBinaryConfigData)?,
+        )
+    }
 
-        Ok(())
+    /// Returns the range of `size` bytes at `offset`, or an error if it overflows.
+    fn checked_range_bytes(offset: u32, size: u32) -> Result<std::ops::Range<u32>, BinConfigError> {
+        offset
+            .checked_add(size)
+            .map(|end| offset..end)
+            .ok_or(BinConfigError::InvalidBinaryConfigData)
     }
 
     fn validate_range(
@@ -445,92 +1012,6 @@ impl BinConfig {
         Ok(())
     }
 
-    fn validate_value(
-        data: &[u8],
-        key_table_offset: u32,
-        key_table: &[InternedString],
-        valid_range: &mut std::ops::Range<u32>, // Valid range of offsets within the binary data blob for this array/table value.
-        valid_string_range: std::ops::Range<u32>, // Valid range of offsets within the binary data blob for strings.
-        array_or_table: &BinArrayOrTable<'_>,     // Validated value's parent array/table.
-        value: &BinConfigPackedValue,
-    ) -> Result<(), BinConfigError> {
-        use BinConfigError::*;
-
-        // Make sure the value type is valid.
-        let value_type = value
-            .try_value_type()
-            .ok_or_else(|| InvalidBinaryConfigData)?;
-
-        match value_type {
-            // Only `0` and `1` are valid for `bool` values.
-            ValueType::Bool => {
-                value.try_bool().ok_or_else(|| InvalidBinaryConfigData)?;
-            }
-            ValueType::I64 | ValueType::F64 => {}
-            ValueType::String => {
-                // Non-empty strings have a positive offset to data.
-                if value.len() > 0 {
-                    // Make sure the string and the null terminator lie within the config data blob (`+ 1`for null terminator).
-                    Self::validate_range(
-                        valid_string_range,
-                        value.offset()..value.offset() + value.len() + 1,
-                    )?;
-
-                    // Make sure the value string is null-terminated.
-                    let null_terminator =
-                        unsafe { array_or_table.slice(value.offset() + value.len(), 1) };
-
-                    if null_terminator[0] != b'\0' {
-                        return Err(InvalidBinaryConfigData);
-                    }
-
-                    // Make sure the value string is valid UTF-8.
-                    let string_slice = unsafe { array_or_table.slice(value.offset(), value.len()) };
-
-                    std::str::from_utf8(string_slice).map_err(|_| InvalidBinaryConfigData)?;
-
-                // Empty value strings must have no offset.
-                } else if value.offset() != 0 {
-                    return Err(InvalidBinaryConfigData);
-                }
-            }
-            ValueType::Array | ValueType::Table => {
-                // Non-empty arrays/tables have a positive offset to data.
-                if value.len() > 0 {
-                    let array_or_table =
-                        BinArrayOrTable::new(data.as_ptr(), key_table, value.offset(), value.len());
-
-                    // Make sure the array/table slice lies within the config data blob.
-                    Self::validate_range(valid_range.clone(), array_or_table.offset_range())?;
-
-                    // Validate the array/table values.
-                    match value_type {
-                        ValueType::Array => {
-                            Self::validate_array(
-                                data,
-                                key_table_offset,
-                                valid_range.end,
-                                &array_or_table,
-                            )?;
-                        }
-                        ValueType::Table => {
-                            Self::validate_table(data, key_table_offset, &array_or_table)?;
-                        }
-                        _ => debug_unreachable!("value must be an array or table if we got here"),
-                    }
-
-                    valid_range.end += size_of::<BinConfigPackedValue>() as u32;
-
-                // Empty arrays/tables must have no offset.
-                } else if value.offset() != 0 {
-                    return Err(InvalidBinaryConfigData);
-                }
-            }
-        }
-
-        Ok(())
-    }
-
     #[cfg(feature = "dyn")]
     fn table_to_dyn_table<'t, T: DerefMut<Target = DynTable>>(
         table: BinTable<'_>,
@@ -571,6 +1052,8 @@ impl BinConfig {
                 Self::table_to_dyn_table(value, &mut table);
                 dyn_table.set(key, table)
             }
+            #[cfg(feature = "datetime")]
+            DateTime(value) => dyn_table.set(key, value),
         };
 
         // Must succeed - we are only adding values to the dyn table.
@@ -600,6 +1083,8 @@ impl BinConfig {
                 Self::table_to_dyn_table(value, &mut table);
                 dyn_array.push(Table(table))
             }
+            #[cfg(feature = "datetime")]
+            DateTime(value) => dyn_array.push(DateTime(value)),
         } {
             debug_unreachable!("pushing a value to the array failed")
         }
@@ -608,482 +1093,2247 @@ impl BinConfig {
 
 impl Display for BinConfig {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.root().fmt_lua(f, 0)
+        self.root().fmt_lua(f, 0, Default::default())
     }
 }
 
-const BIN_CONFIG_HEADER_MAGIC: u32 = 0x67666362; // `bcfg`, little endian.
-
-/// Binary config data blob header.
-///
-/// Fields are in whatever endianness we use; see `super::util::__to_bin_bytes(), _from_bin()`.
-#[repr(C, packed)]
-pub(super) struct BinConfigHeader {
-    /// Arbitrary magic value for a quick sanity check.
-    magic: u32,
-    /// Followed by the root table length.
-    len: u32,
-    /// Offset in bytes to the start of the key string table.
-    /// Each element is an `InternedString` - a key string's offset and length
-    /// in the string section.
-    key_table_offset: u32,
-    /// Length of the key string table in elements.
-    key_table_len: u32,
+/// Validates the array / table values of the binary config data blob with a validated header and key table.
+struct BinConfigValidator<'d> {
+    /// Binary config data blob (without the schema block).
+    data: &'d [u8],
+    /// Offset in bytes to the start of the array / table values (i.e. just past the header) w.r.t. the data blob.
+    values_start: u32,
+    /// Offset in bytes to the end of the array / table values (i.e. to the key table) w.r.t. the data blob.
+    values_end: u32,
+    /// Offset in bytes to the string section (i.e. just past the key table) w.r.t. the data blob.
+    strings_offset: u32,
+    /// Number of nested array / table values which may still be validated.
+    /// Bounded by the number of values between the header and the key table, so that maliciously overlapping
+    /// arrays / tables may not cause validation time exponential in the data blob size.
+    values_left: u32,
+    /// Nesting depth of the currently validated array / table (`0` for the root table).
+    depth: u32,
+    /// Maximum nesting depth of the arrays / tables.
+    max_depth: u32,
+    /// Whether the numeric arrays may have typed copies.
+    typed_arrays: bool,
+    /// Whether the arrays may have elements stored as `f32`'s.
+    f32_arrays: bool,
 }
 
-impl BinConfigHeader {
-    fn check_magic(&self) -> bool {
-        u32_from_bin(self.magic) == BIN_CONFIG_HEADER_MAGIC
-    }
+impl<'d> BinConfigValidator<'d> {
+    fn validate_table(&mut self, table: &BinArrayOrTable<'_>) -> Result<(), BinConfigError> {
+        use BinConfigError::*;
 
-    pub(super) fn len(&self) -> u32 {
-        u32_from_bin(self.len)
-    }
+        // Empty tables must have no data offset.
+        if table.len == 0 && table.offset != 0 {
+            return Err(InvalidBinaryConfigData);
+        }
 
-    pub(super) fn key_table_offset(&self) -> u32 {
-        u32_from_bin(self.key_table_offset)
-    }
+        let key_table = unsafe { table.key_table() };
 
-    pub(super) fn key_table_len(&self) -> u32 {
-        u32_from_bin(self.key_table_len)
-    }
+        // Key hash of the previous table element, if the table values must be sorted by key hash.
+        let mut previous_hash = 0;
 
-    pub(super) fn key_table_size(&self) -> u32 {
-        self.key_table_len() * size_of::<InternedString>() as u32
-    }
+        // For each table element.
+        for index in 0..table.len {
+            let value = unsafe { table.packed_value(index) };
 
-    /// Returns the range of bytes within the binary config data blob
-    /// occupied by the key table.
-    pub(super) fn key_table_range(&self) -> std::ops::Range<u32> {
-        let offset = self.key_table_offset();
-        offset..offset + self.key_table_size()
-    }
+            // All values in the table must have a key.
+            let key = value.key();
 
-    pub(super) fn write<W: std::io::Write>(
-        writer: &mut W,
-        len: u32,
-        key_table_offset: u32,
-        key_table_len: u32,
-    ) -> Result<u32, BinConfigWriterError> {
-        debug_assert!(len > 0);
+            // Table values must be sorted by key hash, if necessary.
+            if table.sorted_keys {
+                if key.hash < previous_hash {
+                    return Err(InvalidBinaryConfigData);
+                }
 
-        let mut written = 0;
+                previous_hash = key.hash;
+            }
 
-        // Magic.
-        written += writer.write(&u32_to_bin_bytes(BIN_CONFIG_HEADER_MAGIC))?;
+            // Key index must be in range.
+            let key_string = key_table
+                .get(key.index as usize)
+                .ok_or(InvalidBinaryConfigData)?;
 
-        // Root table length.
-        written += writer.write(&u32_to_bin_bytes(len))?;
+            // Safe to call - the key strings were validated.
+            let key_string = unsafe { table.string(key_string.offset(), key_string.len()) };
 
-        // Key table offset.
-        written += writer.write(&u32_to_bin_bytes(key_table_offset))?;
+            // Make sure the key hash matches the string.
+            if string_hash_fnv1a(key_string) != key.hash {
+                return Err(InvalidBinaryConfigData);
+            }
 
-        // Key table length.
-        written += writer.write(&u32_to_bin_bytes(key_table_len))?;
+            self.validate_value(table, value)?;
+        }
 
-        Ok(written as _)
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    #![allow(non_snake_case)]
+    fn validate_array(&mut self, array: &BinArrayOrTable<'_>) -> Result<(), BinConfigError> {
+        use BinConfigError::*;
 
-    use {crate::*, ministr_macro::nestr, std::num::NonZeroU32};
+        // Empty arrays must have no data offset.
+        if array.len == 0 && array.offset != 0 {
+            return Err(InvalidBinaryConfigData);
+        }
 
-    #[test]
-    fn GetPathError_PathDoesNotExist() {
-        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
-        writer.table(nestr!("foo"), 1).unwrap();
-        writer.array(nestr!("bar"), 1).unwrap();
-        writer.table(None, 1).unwrap();
-        writer.bool(nestr!("bob"), true).unwrap();
-        writer.end().unwrap();
-        writer.end().unwrap();
-        writer.end().unwrap();
-        let data = writer.finish().unwrap();
-        let config = BinConfig::new(data).unwrap();
+        // Type of the first array element.
+        let mut array_type: Option<ValueType> = None;
 
-        assert_eq!(
-            config.root().get_val_path(&["".into()]).err().unwrap(),
-            GetPathError::KeyDoesNotExist(ConfigPath::new())
-        );
-        assert_eq!(
-            config
-                .root()
-                .get_val_path(&["foo".into(), "".into()])
-                .err()
-                .unwrap(),
-            GetPathError::KeyDoesNotExist(vec![nestr!("foo").into()].into())
-        );
-        assert_eq!(
-            config
-                .root()
-                .get_val_path(&["foo".into(), "baz".into()])
-                .err()
-                .unwrap(),
-            GetPathError::KeyDoesNotExist(vec![nestr!("foo").into(), nestr!("baz").into()].into())
-        );
+        // For each array element.
+        for index in 0..array.len {
+            let value = unsafe { array.packed_value(index) };
 
-        assert_eq!(
-            config
-                .root()
-                .get_val_path(&["foo".into(), "bar".into(), 0.into(), "bill".into()])
-                .err()
-                .unwrap(),
-            GetPathError::KeyDoesNotExist(
-                vec![
-                    nestr!("foo").into(),
-                    nestr!("bar").into(),
-                    0.into(),
-                    nestr!("bill").into()
-                ]
-                .into()
-            )
-        );
+            let value_type = value.try_value_type().ok_or(InvalidBinaryConfigData)?;
 
-        // But this works.
+            // Array values must not be mixed.
+            if let Some(array_type) = array_type {
+                if !array_type.is_compatible(value_type) {
+                    return Err(InvalidBinaryConfigData);
+                }
+            } else {
+                array_type.replace(value_type);
+            }
 
-        assert_eq!(
-            config
-                .root()
-                .get_bool_path(&["foo".into(), "bar".into(), 0.into(), "bob".into()])
-                .unwrap(),
-            true
-        );
-        let val: bool = config
-            .root()
-            .get_path(&["foo".into(), "bar".into(), 0.into(), "bob".into()])
-            .unwrap();
-        assert_eq!(val, true);
-    }
+            // All values in the array must have no keys.
+            let key = value.key();
 
-    #[test]
-    fn GetPathError_IndexOutOfBounds() {
-        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
-        writer.array(nestr!("array"), 1).unwrap();
-        writer.bool(None, true).unwrap();
-        writer.end().unwrap();
-        let data = writer.finish().unwrap();
-        let config = BinConfig::new(data).unwrap();
+            if key.index != 0 {
+                return Err(InvalidBinaryConfigData);
+            }
 
-        assert_eq!(
-            config
-                .root()
-                .get_val_path(&["array".into(), 1.into()])
-                .err()
-                .unwrap(),
-            GetPathError::IndexOutOfBounds {
-                path: vec![nestr!("array").into(), 1.into()].into(),
-                len: 1
+            // Except the offset to the typed copy of the array in the first element, if any.
+            if key.hash != 0 {
+                if index == 0 && self.typed_arrays {
+                    self.validate_typed_array(array, key.hash)?;
+                } else {
+                    return Err(InvalidBinaryConfigData);
+                }
             }
-        );
 
-        // But this works.
+            self.validate_value(array, value)?;
+        }
 
-        assert_eq!(
-            config
-                .root()
-                .get_bool_path(&["array".into(), 0.into()])
-                .unwrap(),
-            true
-        );
+        Ok(())
     }
 
-    #[test]
-    fn GetPathError_ValueNotAnArray() {
-        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
-        writer.table(nestr!("table"), 1).unwrap();
+    /// Makes sure the typed copy of the numeric `array` at `typed_offset` is aligned,
+    /// lies between the values and the key table and matches the `array` elements.
+    fn validate_typed_array(
+        &self,
+        array: &BinArrayOrTable<'_>,
+        typed_offset: u32,
+    ) -> Result<(), BinConfigError> {
+        use BinConfigError::*;
+
+        if typed_offset % TYPED_ARRAY_ALIGNMENT != 0 {
+            return Err(InvalidBinaryConfigData);
+        }
+
+        let typed_range = BinConfig::checked_range_bytes(
+            typed_offset,
+            array
+                .len
+                .checked_mul(size_of::<u64>() as u32)
+                .ok_or(InvalidBinaryConfigData)?,
+        )?;
+
+        BinConfig::validate_range(self.values_start..self.values_end, typed_range.clone())?;
+
+        // NOTE - the array elements were validated to lie within the values by the parent array / table.
+        let values = (0..array.len).map(|index| unsafe { array.packed_value(index) });
+
+        numeric_array_type(values.clone()).ok_or(InvalidBinaryConfigData)?;
+
+        let typed = &self.data[typed_range.start as usize..typed_range.end as usize];
+
+        if values
+            .zip(typed.chunks_exact(size_of::<u64>()))
+            .all(|(value, typed)| u64_to_bin(value.bits()).to_ne_bytes() == typed)
+        {
+            Ok(())
+        } else {
+            Err(InvalidBinaryConfigData)
+        }
+    }
+
+    fn validate_value(
+        &mut self,
+        array_or_table: &BinArrayOrTable<'_>, // Validated value's parent array/table.
+        value: &BinConfigPackedValue,
+    ) -> Result<(), BinConfigError> {
+        use BinConfigError::*;
+
+        // Make sure the value type is valid.
+        let value_type = value.try_value_type().ok_or(InvalidBinaryConfigData)?;
+
+        match value_type {
+            // Only `0` and `1` are valid for `bool` values.
+            ValueType::Bool => {
+                value.try_bool().ok_or(InvalidBinaryConfigData)?;
+            }
+            ValueType::I64 | ValueType::F64 => {}
+            ValueType::String => {
+                // Non-empty strings have a positive offset to data.
+                if value.len() > 0 {
+                    BinConfig::validate_string(
+                        self.data,
+                        self.strings_offset,
+                        value.offset(),
+                        value.len(),
+                    )?;
+
+                // Empty value strings must have no offset.
+                } else if value.offset() != 0 {
+                    return Err(InvalidBinaryConfigData);
+                }
+            }
+            // Make sure the date/time string is a valid RFC 3339 date/time.
+            #[cfg(feature = "datetime")]
+            ValueType::DateTime => {
+                BinConfig::validate_string(
+                    self.data,
+                    self.strings_offset,
+                    value.offset(),
+                    value.len(),
+                )?
+                .parse::<crate::DateTime>()
+                .map_err(|_| InvalidBinaryConfigData)?;
+            }
+            // Non-empty arrays with elements stored as `f32`'s have a positive offset to data and no nested values.
+            ValueType::Array if value.is_f32_array() => {
+                if !self.f32_arrays || value.len() == 0 {
+                    return Err(InvalidBinaryConfigData);
+                }
+
+                let size = f32_array_size(value.len()).ok_or(InvalidBinaryConfigData)?;
+
+                // Make sure the array elements lie past the parent array's/table's values and before the key table.
+                BinConfig::validate_range(
+                    array_or_table.offset_range().end..self.values_end,
+                    BinConfig::checked_range_bytes(value.offset(), size)?,
+                )?;
+
+                // Make sure the total number of validated values does not exceed the number of values in the data blob.
+                self.values_left = self
+                    .values_left
+                    .checked_sub(size / size_of::<BinConfigPackedValue>() as u32)
+                    .ok_or(InvalidBinaryConfigData)?;
+            }
+            ValueType::Array | ValueType::Table => {
+                // Non-empty arrays/tables have a positive offset to data.
+                if value.len() > 0 {
+                    // Make sure the array/table values lie past the parent array's/table's values and before the key table.
+                    BinConfig::validate_range(
+                        array_or_table.offset_range().end..self.values_end,
+                        BinConfig::checked_range(value.offset(), value.len())?,
+                    )?;
+
+                    // Make sure the total number of validated values does not exceed the number of values in the data blob.
+                    self.values_left = self
+                        .values_left
+                        .checked_sub(value.len())
+                        .ok_or(InvalidBinaryConfigData)?;
+
+                    // Make sure the array/table does not exceed the maximum nesting depth.
+                    if self.depth >= self.max_depth {
+                        return Err(MaxDepthExceeded(self.max_depth));
+                    }
+
+                    let nested = BinArrayOrTable::new(
+                        self.data.as_ptr(),
+                        unsafe { array_or_table.key_table() },
+                        value.offset(),
+                        value.len(),
+                        array_or_table.sorted_keys,
+                    );
+
+                    self.depth += 1;
+
+                    if value_type == ValueType::Array {
+                        self.validate_array(&nested)?;
+                    } else {
+                        self.validate_table(&nested)?;
+                    }
+
+                    self.depth -= 1;
+
+                // Empty arrays/tables must have no offset.
+                } else if value.offset() != 0 {
+                    return Err(InvalidBinaryConfigData);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const BIN_CONFIG_HEADER_MAGIC: u32 = 0x67666362; // `bcfg`, little endian.
+
+/// Binary config data blob format version, as recorded in the binary config data blob header.
+///
+/// Binary config data blobs of all supported versions may be loaded by [`BinConfig::new`];
+/// blobs of previous versions are converted to the [`current`] version on load.
+///
+/// [`BinConfig::new`]: struct.BinConfig.html#method.new
+/// [`current`]: #associatedconstant.CURRENT
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BinConfigVersion {
+    /// Legacy layout, without an explicit version in the header.
+    V1,
+    /// Versioned layout.
+    V2,
+    /// Versioned layout with an optional [`checksum`](struct.BinConfigWriter.html#method.checksum).
+    V3,
+}
+
+impl BinConfigVersion {
+    /// The version of the binary config data blobs written by the [`writer`] by default
+    /// and used by the loaded [`binary configs`].
+    ///
+    /// [`writer`]: struct.BinConfigWriter.html#method.new
+    /// [`binary configs`]: struct.BinConfig.html
+    pub const CURRENT: BinConfigVersion = BinConfigVersion::V3;
+
+    /// Returns the version number as recorded in the binary config data blob header.
+    pub fn number(self) -> u32 {
+        match self {
+            BinConfigVersion::V1 => 1,
+            BinConfigVersion::V2 => 2,
+            BinConfigVersion::V3 => 3,
+        }
+    }
+
+    /// Returns the version with the `number` as recorded in the binary config data blob header, if it is supported.
+    pub fn from_number(number: u32) -> Option<Self> {
+        match number {
+            1 => Some(BinConfigVersion::V1),
+            2 => Some(BinConfigVersion::V2),
+            3 => Some(BinConfigVersion::V3),
+            _ => None,
+        }
+    }
+
+    /// Returns the magic value at the start of the binary config data blob header of this version.
+    fn magic(self) -> u32 {
+        match self {
+            BinConfigVersion::V1 => BIN_CONFIG_HEADER_MAGIC,
+            _ => BIN_CONFIG_VERSIONED_HEADER_MAGIC,
+        }
+    }
+
+    /// Returns the size in bytes of the binary config data blob header of this version.
+    pub(super) fn header_size(self) -> u32 {
+        match self {
+            // Magic, root table length, key table offset and length.
+            BinConfigVersion::V1 => 4 * size_of::<u32>() as u32,
+            // Magic, version, root table length, key table offset and length, flags.
+            BinConfigVersion::V2 => 6 * size_of::<u32>() as u32,
+            // Magic, version, root table length, key table offset and length, flags, checksum, padding.
+            BinConfigVersion::V3 => 8 * size_of::<u32>() as u32,
+        }
+    }
+
+    /// Returns the supported header flags of this version.
+    fn flags(self) -> u32 {
+        match self {
+            BinConfigVersion::V1 => 0,
+            BinConfigVersion::V2 => BIN_CONFIG_HEADER_FLAG_COMPRESSED_KEYS,
+            BinConfigVersion::V3 => {
+                BIN_CONFIG_HEADER_FLAG_COMPRESSED_KEYS
+                    | BIN_CONFIG_HEADER_FLAG_CHECKSUM
+                    | BIN_CONFIG_HEADER_FLAG_SCHEMA
+                    | BIN_CONFIG_HEADER_FLAG_SORTED_KEYS
+                    | BIN_CONFIG_HEADER_FLAG_COMPRESSED
+                    | BIN_CONFIG_HEADER_FLAG_ENCODED
+                    | BIN_CONFIG_HEADER_FLAG_TYPED_ARRAYS
+                    | BIN_CONFIG_HEADER_FLAG_F32_ARRAYS
+            }
+        }
+    }
+}
+
+impl Default for BinConfigVersion {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+impl Display for BinConfigVersion {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "v{}", self.number())
+    }
+}
+
+const BIN_CONFIG_VERSIONED_HEADER_MAGIC: u32 = 0x76666362; // `bcfv`, little endian.
+
+/// The key table of the data blob is compressed (see `super::keys`).
+pub(super) const BIN_CONFIG_HEADER_FLAG_COMPRESSED_KEYS: u32 = 0x1;
+
+/// The header contains the checksum of the data blob.
+pub(super) const BIN_CONFIG_HEADER_FLAG_CHECKSUM: u32 = 0x2;
+
+/// The data blob contains the schema block (see `super::schema`).
+pub(super) const BIN_CONFIG_HEADER_FLAG_SCHEMA: u32 = 0x4;
+
+/// The values of all tables in the data blob are sorted by key hash.
+pub(super) const BIN_CONFIG_HEADER_FLAG_SORTED_KEYS: u32 = 0x8;
+
+/// The payload of the data blob (everything after the header) is compressed (see `super::compression`).
+pub(super) const BIN_CONFIG_HEADER_FLAG_COMPRESSED: u32 = 0x10;
+
+/// The payload of the data blob (everything after the header) is encoded by the user codec (see `super::codec`).
+pub(super) const BIN_CONFIG_HEADER_FLAG_ENCODED: u32 = 0x20;
+
+/// The data blob contains the typed copies of the numeric arrays (see `super::typed`).
+pub(super) const BIN_CONFIG_HEADER_FLAG_TYPED_ARRAYS: u32 = 0x40;
+
+/// The data blob may contain arrays with elements stored as `f32`'s (see `BinConfigPackedValue::is_f32_array()`).
+pub(super) const BIN_CONFIG_HEADER_FLAG_F32_ARRAYS: u32 = 0x80;
+
+/// Flags which describe the layout of the values and are preserved when the data blob payload is transformed.
+pub(super) const BIN_CONFIG_HEADER_VALUE_FLAGS: u32 = BIN_CONFIG_HEADER_FLAG_SORTED_KEYS
+    | BIN_CONFIG_HEADER_FLAG_TYPED_ARRAYS
+    | BIN_CONFIG_HEADER_FLAG_F32_ARRAYS;
+
+/// Index of the flags field in the `V2` / `V3` header.
+const BIN_CONFIG_HEADER_FLAGS_INDEX: usize = 5;
+
+/// Index of the checksum field in the `V3` header.
+const BIN_CONFIG_HEADER_CHECKSUM_INDEX: usize = 6;
+
+/// Binary config data blob header, as read from the data blob.
+///
+/// Header fields are stored in whatever endianness we use; see `super::util::__to_bin_bytes(), _from_bin()`.
+/// Layout depends on the version (all fields are `u32`):
+/// - `V1`: `bcfg` magic, root table length, key table offset, key table length;
+/// - `V2`: `bcfv` magic, version, root table length, key table offset, key table length, flags;
+/// - `V3`: `V2` fields, checksum, padding (`0`).
+pub(super) struct BinConfigHeader {
+    /// Binary config data blob format version, determined by the magic value and the version field.
+    version: BinConfigVersion,
+    /// Combination of `BIN_CONFIG_HEADER_FLAG_...` flags. Always `0` for `V1`.
+    flags: u32,
+    /// CRC-32 checksum of the data blob (with the checksum field set to `0`),
+    /// if the `BIN_CONFIG_HEADER_FLAG_CHECKSUM` flag is set. Always `0` for `V1` / `V2`.
+    checksum: u32,
+    /// Root table length.
+    len: u32,
+    /// Offset in bytes to the start of the key string table.
+    /// Each element is an `InternedString` - a key string's offset and length
+    /// in the string section.
+    key_table_offset: u32,
+    /// Length of the key string table in elements.
+    key_table_len: u32,
+}
+
+impl BinConfigHeader {
+    /// Reads the header from the binary config `data` blob.
+    ///
+    /// Returns an error if the header magic is invalid or the version is not supported.
+    /// The caller ensures the data is at least large enough for the header of any supported version.
+    pub(super) fn read(data: &[u8]) -> Result<Self, BinConfigError> {
+        use BinConfigError::*;
+
+        let magic = Self::read_u32(data, 0);
+
+        let version = if magic == BIN_CONFIG_HEADER_MAGIC {
+            BinConfigVersion::V1
+        } else if magic == BIN_CONFIG_VERSIONED_HEADER_MAGIC {
+            let number = Self::read_u32(data, 1);
+            BinConfigVersion::from_number(number).ok_or(UnsupportedVersion(number))?
+        } else {
+            return Err(InvalidBinaryConfigData);
+        };
+
+        // Index of the root table length field, flags, checksum.
+        let (fields, flags, checksum) = match version {
+            BinConfigVersion::V1 => (1, 0, 0),
+            BinConfigVersion::V2 => (2, Self::read_u32(data, BIN_CONFIG_HEADER_FLAGS_INDEX), 0),
+            BinConfigVersion::V3 => {
+                // Padding must be `0`.
+                if Self::read_u32(data, 7) != 0 {
+                    return Err(InvalidBinaryConfigData);
+                }
+
+                (
+                    2,
+                    Self::read_u32(data, BIN_CONFIG_HEADER_FLAGS_INDEX),
+                    Self::read_u32(data, BIN_CONFIG_HEADER_CHECKSUM_INDEX),
+                )
+            }
+        };
+
+        // Unknown flags are not allowed.
+        if (flags & !version.flags()) != 0 {
+            return Err(InvalidBinaryConfigData);
+        }
+
+        // Checksum must be `0` if not used.
+        if (flags & BIN_CONFIG_HEADER_FLAG_CHECKSUM) == 0 && checksum != 0 {
+            return Err(InvalidBinaryConfigData);
+        }
+
+        Ok(Self {
+            version,
+            flags,
+            checksum,
+            len: Self::read_u32(data, fields),
+            key_table_offset: Self::read_u32(data, fields + 1),
+            key_table_len: Self::read_u32(data, fields + 2),
+        })
+    }
+
+    /// Reads the `u32` header field at `index`.
+    /// The caller ensures the data is large enough.
+    fn read_u32(data: &[u8], index: usize) -> u32 {
+        let offset = index * size_of::<u32>();
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&data[offset..offset + size_of::<u32>()]);
+        u32_from_bin(u32::from_ne_bytes(bytes))
+    }
+
+    pub(super) fn version(&self) -> BinConfigVersion {
+        self.version
+    }
+
+    /// Returns the combination of `BIN_CONFIG_HEADER_FLAG_...` flags.
+    pub(super) fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// Returns `true` if the data blob contains the schema block.
+    pub(super) fn has_schema(&self) -> bool {
+        (self.flags & BIN_CONFIG_HEADER_FLAG_SCHEMA) != 0
+    }
+
+    /// Returns `true` if the values of all tables in the data blob are sorted by key hash.
+    pub(super) fn sorted_keys(&self) -> bool {
+        (self.flags & BIN_CONFIG_HEADER_FLAG_SORTED_KEYS) != 0
+    }
+
+    /// Returns `true` if the key table of the data blob is compressed.
+    pub(super) fn compressed_keys(&self) -> bool {
+        (self.flags & BIN_CONFIG_HEADER_FLAG_COMPRESSED_KEYS) != 0
+    }
+
+    /// Returns `true` if the payload of the data blob is compressed.
+    pub(super) fn compressed(&self) -> bool {
+        (self.flags & BIN_CONFIG_HEADER_FLAG_COMPRESSED) != 0
+    }
+
+    /// Returns `true` if the data blob contains the typed copies of the numeric arrays.
+    pub(super) fn typed_arrays(&self) -> bool {
+        (self.flags & BIN_CONFIG_HEADER_FLAG_TYPED_ARRAYS) != 0
+    }
+
+    /// Returns `true` if the data blob may contain arrays with elements stored as `f32`'s.
+    pub(super) fn f32_arrays(&self) -> bool {
+        (self.flags & BIN_CONFIG_HEADER_FLAG_F32_ARRAYS) != 0
+    }
+
+    /// Returns `true` if the payload of the data blob is encoded by the user codec.
+    pub(super) fn encoded(&self) -> bool {
+        (self.flags & BIN_CONFIG_HEADER_FLAG_ENCODED) != 0
+    }
+
+    /// Returns the checksum of the data blob, if any.
+    pub(super) fn checksum(&self) -> Option<u32> {
+        if (self.flags & BIN_CONFIG_HEADER_FLAG_CHECKSUM) != 0 {
+            Some(self.checksum)
+        } else {
+            None
+        }
+    }
+
+    /// Calculates the checksum of the `V3` binary config `data` blob (with the checksum field set to `0`).
+    /// The caller ensures the data is large enough for the `V3` header.
+    pub(super) fn calculate_checksum(data: &[u8]) -> u32 {
+        let offset = BIN_CONFIG_HEADER_CHECKSUM_INDEX * size_of::<u32>();
+
+        let checksum = crc32(0, &data[..offset]);
+        let checksum = crc32(checksum, &[0; size_of::<u32>()]);
+        crc32(checksum, &data[offset + size_of::<u32>()..])
+    }
+
+    /// Sets the checksum flag in the header of the valid `V3` binary config `data` blob
+    /// and writes the checksum of the data blob to the header.
+    pub(super) fn write_checksum(data: &mut [u8]) {
+        let header = unwrap_unchecked(
+            BinConfigHeader::read(data).ok(),
+            "invalid binary config header",
+        );
+        debug_assert_eq!(header.version(), BinConfigVersion::V3);
+
+        Self::write_flags(data, header.flags | BIN_CONFIG_HEADER_FLAG_CHECKSUM);
+
+        let checksum = Self::calculate_checksum(data);
+        let offset = BIN_CONFIG_HEADER_CHECKSUM_INDEX * size_of::<u32>();
+        data[offset..offset + size_of::<u32>()].copy_from_slice(&u32_to_bin_bytes(checksum));
+    }
+
+    /// Clears the checksum flag in the header of the valid `V3` binary config `data` blob
+    /// and zeroes the checksum in the header.
+    pub(super) fn clear_checksum(data: &mut [u8]) {
+        let header = unwrap_unchecked(
+            BinConfigHeader::read(data).ok(),
+            "invalid binary config header",
+        );
+        debug_assert_eq!(header.version(), BinConfigVersion::V3);
+
+        Self::write_flags(data, header.flags & !BIN_CONFIG_HEADER_FLAG_CHECKSUM);
+
+        let offset = BIN_CONFIG_HEADER_CHECKSUM_INDEX * size_of::<u32>();
+        data[offset..offset + size_of::<u32>()].copy_from_slice(&u32_to_bin_bytes(0));
+    }
+
+    /// Overwrites the flags in the header of the valid `V2` / `V3` binary config `data` blob.
+    pub(super) fn write_flags(data: &mut [u8], flags: u32) {
+        let offset = BIN_CONFIG_HEADER_FLAGS_INDEX * size_of::<u32>();
+        data[offset..offset + size_of::<u32>()].copy_from_slice(&u32_to_bin_bytes(flags));
+    }
+
+    pub(super) fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub(super) fn key_table_offset(&self) -> u32 {
+        self.key_table_offset
+    }
+
+    pub(super) fn key_table_len(&self) -> u32 {
+        self.key_table_len
+    }
+
+    pub(super) fn key_table_size(&self) -> u32 {
+        self.key_table_len() * size_of::<InternedString>() as u32
+    }
+
+    pub(super) fn write<W: std::io::Write>(
+        writer: &mut W,
+        version: BinConfigVersion,
+        flags: u32,
+        len: u32,
+        key_table_offset: u32,
+        key_table_len: u32,
+    ) -> Result<u32, BinConfigWriterError> {
+        debug_assert!(len > 0);
+        debug_assert_eq!(flags & !version.flags(), 0);
+        debug_assert_eq!(flags & BIN_CONFIG_HEADER_FLAG_CHECKSUM, 0);
+
+        let mut written = 0;
+
+        // Magic.
+        written += writer.write(&u32_to_bin_bytes(version.magic()))?;
+
+        // Version.
+        if version != BinConfigVersion::V1 {
+            written += writer.write(&u32_to_bin_bytes(version.number()))?;
+        }
+
+        // Root table length.
+        written += writer.write(&u32_to_bin_bytes(len))?;
+
+        // Key table offset.
+        written += writer.write(&u32_to_bin_bytes(key_table_offset))?;
+
+        // Key table length.
+        written += writer.write(&u32_to_bin_bytes(key_table_len))?;
+
+        // Flags.
+        if version != BinConfigVersion::V1 {
+            written += writer.write(&u32_to_bin_bytes(flags))?;
+        }
+
+        // Checksum (NOTE - written separately, see `write_checksum()`), padding.
+        if version == BinConfigVersion::V3 {
+            written += writer.write(&u32_to_bin_bytes(0))?;
+            written += writer.write(&u32_to_bin_bytes(0))?;
+        }
+
+        debug_assert_eq!(written as u32, version.header_size());
+
+        Ok(written as _)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr, std::num::NonZeroU32};
+
+    #[test]
+    fn GetPathError_PathDoesNotExist() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.table(nestr!("foo"), 1).unwrap();
+        writer.array(nestr!("bar"), 1).unwrap();
+        writer.table(None, 1).unwrap();
+        writer.bool(nestr!("bob"), true).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+
+        assert_eq!(
+            config.root().get_val_path(&["".into()]).err().unwrap(),
+            GetPathError::KeyDoesNotExist(ConfigPath::new())
+        );
+        assert_eq!(
+            config
+                .root()
+                .get_val_path(&["foo".into(), "".into()])
+                .err()
+                .unwrap(),
+            GetPathError::KeyDoesNotExist(vec![nestr!("foo").into()].into())
+        );
+        assert_eq!(
+            config
+                .root()
+                .get_val_path(&["foo".into(), "baz".into()])
+                .err()
+                .unwrap(),
+            GetPathError::KeyDoesNotExist(vec![nestr!("foo").into(), nestr!("baz").into()].into())
+        );
+
+        assert_eq!(
+            config
+                .root()
+                .get_val_path(&["foo".into(), "bar".into(), 0.into(), "bill".into()])
+                .err()
+                .unwrap(),
+            GetPathError::KeyDoesNotExist(
+                vec![
+                    nestr!("foo").into(),
+                    nestr!("bar").into(),
+                    0.into(),
+                    nestr!("bill").into()
+                ]
+                .into()
+            )
+        );
+
+        // But this works.
+
+        assert_eq!(
+            config
+                .root()
+                .get_bool_path(&["foo".into(), "bar".into(), 0.into(), "bob".into()])
+                .unwrap(),
+            true
+        );
+        let val: bool = config
+            .root()
+            .get_path(&["foo".into(), "bar".into(), 0.into(), "bob".into()])
+            .unwrap();
+        assert_eq!(val, true);
+    }
+
+    #[test]
+    fn GetPathError_IndexOutOfBounds() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.array(nestr!("array"), 1).unwrap();
+        writer.bool(None, true).unwrap();
+        writer.end().unwrap();
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+
+        assert_eq!(
+            config
+                .root()
+                .get_val_path(&["array".into(), 1.into()])
+                .err()
+                .unwrap(),
+            GetPathError::IndexOutOfBounds {
+                path: vec![nestr!("array").into(), 1.into()].into(),
+                len: 1
+            }
+        );
+
+        // But this works.
+
+        assert_eq!(
+            config
+                .root()
+                .get_bool_path(&["array".into(), 0.into()])
+                .unwrap(),
+            true
+        );
+    }
+
+    #[test]
+    fn GetPathError_ValueNotAnArray() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.table(nestr!("table"), 1).unwrap();
         writer.bool(nestr!("array"), true).unwrap();
         writer.end().unwrap();
         let data = writer.finish().unwrap();
         let config = BinConfig::new(data).unwrap();
 
-        assert_eq!(
-            config
-                .root()
-                .get_val_path(&["table".into(), "array".into(), 1.into()])
-                .err()
-                .unwrap(),
-            GetPathError::ValueNotAnArray {
-                path: vec![nestr!("table").into(), nestr!("array").into()].into(),
-                value_type: ValueType::Bool
-            }
-        );
+        assert_eq!(
+            config
+                .root()
+                .get_val_path(&["table".into(), "array".into(), 1.into()])
+                .err()
+                .unwrap(),
+            GetPathError::ValueNotAnArray {
+                path: vec![nestr!("table").into(), nestr!("array").into()].into(),
+                value_type: ValueType::Bool
+            }
+        );
+
+        // But this works.
+
+        assert_eq!(
+            config
+                .root()
+                .get_bool_path(&["table".into(), "array".into()])
+                .unwrap(),
+            true,
+        );
+    }
+
+    #[test]
+    fn GetPathError_ValueNotATable() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.array(nestr!("array"), 1).unwrap();
+        writer.bool(None, true).unwrap();
+        writer.end().unwrap();
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+
+        assert_eq!(
+            config
+                .root()
+                .get_val_path(&["array".into(), 0.into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::ValueNotATable {
+                path: vec![nestr!("array").into(), 0.into()].into(),
+                value_type: ValueType::Bool
+            }
+        );
+
+        // But this works.
+
+        assert_eq!(
+            config
+                .root()
+                .get_bool_path(&["array".into(), 0.into()])
+                .unwrap(),
+            true,
+        );
+    }
+
+    #[test]
+    fn GetPathError_IncorrectValueType() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.table(nestr!("table"), 2).unwrap();
+        writer.bool(nestr!("foo"), true).unwrap();
+        writer.f64(nestr!("bar"), 3.14).unwrap();
+        writer.end().unwrap();
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+
+        assert_eq!(
+            config
+                .root()
+                .get_i64_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            config
+                .root()
+                .get_f64_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            config
+                .root()
+                .get_string_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            config
+                .root()
+                .get_array_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            config
+                .root()
+                .get_table_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+
+        // But this works.
+
+        assert_eq!(
+            config
+                .root()
+                .get_bool_path(&["table".into(), "foo".into()])
+                .unwrap(),
+            true
+        );
+
+        assert_eq!(
+            config
+                .root()
+                .get_i64_path(&["table".into(), "bar".into()])
+                .unwrap(),
+            3
+        );
+        assert!(cmp_f64(
+            config
+                .root()
+                .get_f64_path(&["table".into(), "bar".into()])
+                .unwrap(),
+            3.14
+        ));
+    }
+
+    #[test]
+    fn hash_collisions() {
+        // See `fnv1a_hash_collisions()`.
+
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+
+        writer.string(nestr!("costarring"), "declinate").unwrap();
+        writer.string(nestr!("liquid"), "macallums").unwrap();
+
+        let data = writer.finish().unwrap();
+
+        let config = BinConfig::new(data).unwrap();
+
+        assert_eq!(config.root().get_string("liquid").unwrap(), "macallums");
+        assert_eq!(config.root().get_string("costarring").unwrap(), "declinate");
+
+        #[cfg(feature = "str_hash")]
+        {
+            assert_eq!(
+                config.root().get_string(key!("liquid")).unwrap(),
+                "macallums"
+            );
+            assert_eq!(
+                config.root().get_string(key!("costarring")).unwrap(),
+                "declinate"
+            );
+        }
+    }
+
+    #[cfg(feature = "dyn")]
+    #[test]
+    fn to_dyn_config() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(6).unwrap()).unwrap();
+
+        writer.array(nestr!("array_value"), 3).unwrap();
+        writer.i64(None, 54).unwrap();
+        writer.i64(None, 12).unwrap();
+        writer.f64(None, 78.9).unwrap();
+        writer.end().unwrap();
+
+        writer.bool(nestr!("bool_value"), true).unwrap();
+        writer.f64(nestr!("float_value"), 3.14).unwrap();
+        writer.i64(nestr!("int_value"), 7).unwrap();
+        writer.string(nestr!("string_value"), "foo").unwrap();
+
+        writer.table(nestr!("table_value"), 3).unwrap();
+        writer.i64(nestr!("bar"), 2020).unwrap();
+        writer.string(nestr!("baz"), "hello").unwrap();
+        writer.bool(nestr!("foo"), false).unwrap();
+        writer.end().unwrap();
+
+        let data = writer.finish().unwrap();
+
+        let config = BinConfig::new(data).unwrap();
+
+        // Serialize to dynamic config.
+        let dyn_config = config.to_dyn_config();
+
+        let array_value = dyn_config.root().get_array("array_value").unwrap();
+
+        assert_eq!(array_value.len(), 3);
+        assert_eq!(array_value.get_i64(0).unwrap(), 54);
+        assert!(cmp_f64(array_value.get_f64(0).unwrap(), 54.0));
+        assert_eq!(array_value.get_i64(1).unwrap(), 12);
+        assert!(cmp_f64(array_value.get_f64(1).unwrap(), 12.0));
+        assert_eq!(array_value.get_i64(2).unwrap(), 78);
+        assert!(cmp_f64(array_value.get_f64(2).unwrap(), 78.9));
+
+        assert_eq!(dyn_config.root().get_bool("bool_value").unwrap(), true);
+
+        assert!(cmp_f64(
+            dyn_config.root().get_f64("float_value").unwrap(),
+            3.14
+        ));
+
+        assert_eq!(dyn_config.root().get_i64("int_value").unwrap(), 7);
+
+        assert_eq!(dyn_config.root().get_string("string_value").unwrap(), "foo");
+
+        let table_value = dyn_config.root().get_table("table_value").unwrap();
+
+        assert_eq!(table_value.len(), 3);
+        assert_eq!(table_value.get_i64("bar").unwrap(), 2020);
+        assert!(cmp_f64(table_value.get_f64("bar").unwrap(), 2020.0));
+        assert_eq!(table_value.get_string("baz").unwrap(), "hello");
+        assert_eq!(table_value.get_bool("foo").unwrap(), false);
+    }
+
+    #[cfg(feature = "dyn")]
+    #[test]
+    fn project() {
+        let config = dyn_config! {
+            render: {
+                window: { size: [640, 480], title: "foo" },
+                views: [{ fov: 90, name: "a" }, { fov: 60, name: "b" }],
+            },
+            audio: { volume: 0.5 },
+        };
+        let bin_config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+
+        let paths: Vec<ConfigPath> = ["render.window", "render.views[1].fov", "missing"]
+            .iter()
+            .map(|path| path.parse().unwrap())
+            .collect();
+
+        let projected = BinConfig::new(bin_config.project(&paths).unwrap()).unwrap();
+
+        // Same as projecting the dynamic config.
+        assert!(projected
+            .to_dyn_config()
+            .diff(&config.project(&paths))
+            .is_empty());
+        assert_eq!(projected.root().len(), 1);
+        assert_eq!(
+            projected
+                .root()
+                .get_i64_path(&"render.views[0].fov".parse::<ConfigPath>().unwrap())
+                .unwrap(),
+            60
+        );
+
+        // Whole config.
+        let projected = BinConfig::new(bin_config.project(&[ConfigPath::new()]).unwrap()).unwrap();
+        assert!(projected.to_dyn_config().diff(&config).is_empty());
+
+        // Nothing projected.
+        assert_eq!(
+            bin_config.project(&paths[2..]).err().unwrap(),
+            BinConfigWriterError::EmptyRootTable
+        );
+    }
+
+    #[cfg(all(feature = "dyn", feature = "ini"))]
+    #[test]
+    fn replay_ini() {
+        let config = dyn_config! {
+            name: "Game",
+            window: { width: 1920, monitor: { index: 1 } },
+            levels: ["intro", "boss"],
+            enemies: [{ name: "orc" }, { name: "troll" }],
+        };
+
+        let bin_config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+
+        let mut replayed = DynConfigIniConfig::new();
+        bin_config.replay_ini(&mut replayed).unwrap();
+
+        assert!(config.diff(&replayed.into_inner()).is_empty());
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn to_ini_string() {
+        let ini = r#"array = ["foo", "bar", "baz"]
+bool = true
+float = 3.14
+int = 7
+string = "foo"
+
+[other_section]
+other_bool = true
+other_float = 3.14
+other_int = 7
+other_string = "foo"
+
+[section]
+bool = false
+float = 7.62
+int = 9
+string = "bar""#;
+
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(7).unwrap()).unwrap();
+
+        writer.array(nestr!("array"), 3).unwrap();
+        writer.string(None, "foo").unwrap();
+        writer.string(None, "bar").unwrap();
+        writer.string(None, "baz").unwrap();
+        writer.end().unwrap();
+
+        writer.bool(nestr!("bool"), true).unwrap();
+        writer.f64(nestr!("float"), 3.14).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+
+        writer.table(nestr!("other_section"), 4).unwrap();
+        writer.bool(nestr!("other_bool"), true).unwrap();
+        writer.f64(nestr!("other_float"), 3.14).unwrap();
+        writer.i64(nestr!("other_int"), 7).unwrap();
+        writer.string(nestr!("other_string"), "foo").unwrap();
+        writer.end().unwrap();
+
+        writer.table(nestr!("section"), 4).unwrap();
+        writer.bool(nestr!("bool"), false).unwrap();
+        writer.f64(nestr!("float"), 7.62).unwrap();
+        writer.i64(nestr!("int"), 9).unwrap();
+        writer.string(nestr!("string"), "bar").unwrap();
+        writer.end().unwrap();
+
+        let data = writer.finish().unwrap();
+
+        let config = BinConfig::new(data).unwrap();
+
+        let string = config
+            .to_ini_string_opts(ToIniStringOptions {
+                arrays: true,
+                ..Default::default()
+            })
+            .unwrap();
 
-        // But this works.
+        assert_eq!(string, ini);
+
+        let mut bytes = Vec::new();
+
+        config
+            .write_ini_opts(
+                ToIniStringOptions {
+                    arrays: true,
+                    ..Default::default()
+                },
+                &mut bytes,
+            )
+            .unwrap();
+
+        assert_eq!(std::str::from_utf8(&bytes).unwrap(), ini);
+
+        // Keys in the order they were written in.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+
+        writer.table(nestr!("section"), 1).unwrap();
+        writer.i64(nestr!("int"), 9).unwrap();
+        writer.end().unwrap();
+
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.bool(nestr!("bool"), true).unwrap();
+
+        let config = BinConfig::new(writer.finish().unwrap()).unwrap();
 
         assert_eq!(
             config
-                .root()
-                .get_bool_path(&["table".into(), "array".into()])
+                .to_ini_string_opts(ToIniStringOptions {
+                    key_order: IniKeyOrder::Insertion,
+                    ..Default::default()
+                })
                 .unwrap(),
-            true,
+            "int = 7\nbool = true\n\n[section]\nint = 9"
         );
     }
 
+    #[cfg(feature = "ini")]
     #[test]
-    fn GetPathError_ValueNotATable() {
-        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+    fn write_ini() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+
         writer.array(nestr!("array"), 1).unwrap();
-        writer.bool(None, true).unwrap();
+        writer.i64(None, 7).unwrap();
         writer.end().unwrap();
-        let data = writer.finish().unwrap();
-        let config = BinConfig::new(data).unwrap();
 
+        writer.bool(nestr!("bool"), true).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+
+        let config = BinConfig::new(writer.finish().unwrap()).unwrap();
+
+        let options = ToIniStringOptions {
+            arrays: true,
+            ..Default::default()
+        };
+        let ini = "array = [7]\nbool = true\nstring = \"foo\"";
+
+        // `std::fmt::Write` sink.
+        let mut string = String::new();
+        config.fmt_ini_opts(options, &mut string).unwrap();
+        assert_eq!(string, ini);
+
+        // `std::io::Write` sink.
+        let mut bytes = Vec::new();
+        config.write_ini_opts(options, &mut bytes).unwrap();
+        assert_eq!(std::str::from_utf8(&bytes).unwrap(), ini);
+
+        // Serialization errors are returned as is.
+        assert_eq!(
+            config.write_ini(&mut Vec::new()).err().unwrap(),
+            ToIniStringError::ArraysNotAllowed
+        );
+
+        // Writer errors - the buffer is too small.
+        let mut buffer = [0u8; 8];
         assert_eq!(
             config
-                .root()
-                .get_val_path(&["array".into(), 0.into(), "foo".into()])
+                .write_ini_opts(options, &mut &mut buffer[..])
                 .err()
                 .unwrap(),
-            GetPathError::ValueNotATable {
-                path: vec![nestr!("array").into(), 0.into()].into(),
-                value_type: ValueType::Bool
+            ToIniStringError::WriteError
+        );
+        assert_eq!(&buffer, b"array = ");
+    }
+
+    #[test]
+    fn versions() {
+        fn write(version: BinConfigVersion) -> Box<[u8]> {
+            let mut writer =
+                BinConfigWriter::with_version(NonZeroU32::new(4).unwrap(), version).unwrap();
+            writer.string(nestr!("foo"), "bar").unwrap();
+            writer.array(nestr!("array"), 2).unwrap();
+            writer.i64(None, 7).unwrap();
+            writer.f64(None, 3.5).unwrap();
+            writer.end().unwrap();
+            writer.table(nestr!("table"), 1).unwrap();
+            writer.array(nestr!("nested"), 1).unwrap();
+            writer.string(None, "baz").unwrap();
+            writer.end().unwrap();
+            writer.end().unwrap();
+            writer.bool(nestr!("bool"), true).unwrap();
+            writer.finish().unwrap()
+        }
+
+        let v1 = write(BinConfigVersion::V1);
+        let v2 = write(BinConfigVersion::V2);
+        let v3 = write(BinConfigVersion::V3);
+
+        assert_eq!(BinConfig::data_version(&v1).unwrap(), BinConfigVersion::V1);
+        assert_eq!(BinConfig::data_version(&v2).unwrap(), BinConfigVersion::V2);
+        assert_eq!(BinConfig::data_version(&v3).unwrap(), BinConfigVersion::V3);
+        assert_eq!(
+            BinConfig::data_version(&write(Default::default())).unwrap(),
+            BinConfigVersion::CURRENT
+        );
+
+        // Only the header size differs.
+        assert_eq!(v1.len() + 8, v2.len());
+        assert_eq!(v2.len() + 8, v3.len());
+
+        BinConfig::validate(&v1).unwrap();
+        BinConfig::validate(&v2).unwrap();
+
+        // Legacy data is converted on load.
+        let v1 = BinConfig::new(v1).unwrap();
+        let v2 = BinConfig::new(v2).unwrap();
+        let v3 = BinConfig::new(v3).unwrap();
+
+        assert_eq!(v1.0, v3.0);
+        assert_eq!(v2.0, v3.0);
+        assert_eq!(v1.to_lua_string().unwrap(), v3.to_lua_string().unwrap());
+
+        assert_eq!(v1.root().get_string("foo").unwrap(), "bar");
+        assert_eq!(v1.root().get_array("array").unwrap().get_i64(0).unwrap(), 7);
+        assert_eq!(
+            v1.root()
+                .get_table("table")
+                .unwrap()
+                .get_array("nested")
+                .unwrap()
+                .get_string(0)
+                .unwrap(),
+            "baz"
+        );
+        assert!(v1.root().get_bool("bool").unwrap());
+
+        // Unsupported version.
+        let mut v4 = write(BinConfigVersion::V3);
+        v4[4] = 4;
+
+        assert_eq!(
+            BinConfig::data_version(&v4).err().unwrap(),
+            BinConfigError::UnsupportedVersion(4)
+        );
+        assert_eq!(
+            BinConfig::new(v4).err().unwrap(),
+            BinConfigError::UnsupportedVersion(4)
+        );
+
+        // Invalid magic.
+        let mut invalid = write(BinConfigVersion::V2);
+        invalid[0] = 0;
+
+        assert_eq!(
+            BinConfig::new(invalid).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+    }
+
+    #[test]
+    fn compress_keys() {
+        fn write(compress_keys: bool) -> Box<[u8]> {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(4).unwrap()).unwrap();
+            writer.compress_keys(compress_keys).unwrap();
+            writer
+                .string(nestr!("render.shadow.cascade.1.bias"), "render.shadow")
+                .unwrap();
+            writer
+                .i64(nestr!("render.shadow.cascade.0.bias"), 7)
+                .unwrap();
+            writer.table(nestr!("render.shadow"), 2).unwrap();
+            writer
+                .f64(nestr!("render.shadow.cascade.0.distance"), 3.5)
+                .unwrap();
+            writer.array(nestr!("render.shadow.cascades"), 2).unwrap();
+            writer.table(None, 1).unwrap();
+            writer
+                .bool(nestr!("render.shadow.cascade.0.bias"), true)
+                .unwrap();
+            writer.end().unwrap();
+            writer.table(None, 1).unwrap();
+            writer.string(nestr!("render"), "shadow").unwrap();
+            writer.end().unwrap();
+            writer.end().unwrap();
+            writer.end().unwrap();
+            writer.string(nestr!("render"), "render").unwrap();
+            writer.finish().unwrap()
+        }
+
+        let uncompressed = write(false);
+        let compressed = write(true);
+
+        assert!(compressed.len() < uncompressed.len());
+
+        BinConfig::validate(&compressed).unwrap();
+
+        let uncompressed = BinConfig::new(uncompressed).unwrap();
+        let compressed = BinConfig::new(compressed).unwrap();
+
+        assert_eq!(
+            uncompressed.to_lua_string().unwrap(),
+            compressed.to_lua_string().unwrap()
+        );
+
+        let root = compressed.root();
+
+        assert_eq!(
+            root.get_string("render.shadow.cascade.1.bias").unwrap(),
+            "render.shadow"
+        );
+        assert_eq!(root.get_i64("render.shadow.cascade.0.bias").unwrap(), 7);
+        assert_eq!(root.get_string("render").unwrap(), "render");
+
+        let shadow = root.get_table("render.shadow").unwrap();
+        assert!(cmp_f64(
+            shadow.get_f64("render.shadow.cascade.0.distance").unwrap(),
+            3.5
+        ));
+
+        let cascades = shadow.get_array("render.shadow.cascades").unwrap();
+        assert!(cascades
+            .get_table(0)
+            .unwrap()
+            .get_bool("render.shadow.cascade.0.bias")
+            .unwrap());
+        assert_eq!(
+            cascades.get_table(1).unwrap().get_string("render").unwrap(),
+            "shadow"
+        );
+
+        // Invalid shared prefix length.
+        let mut invalid = write(true);
+        // The first key has no shared prefix.
+        let key_table_offset =
+            u32::from_le_bytes([invalid[12], invalid[13], invalid[14], invalid[15]]) as usize;
+        invalid[key_table_offset] = 1;
+
+        assert_eq!(
+            BinConfig::new(invalid).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+
+        // Not supported by `V1` data blobs.
+        let mut writer =
+            BinConfigWriter::with_version(NonZeroU32::new(1).unwrap(), BinConfigVersion::V1)
+                .unwrap();
+        assert_eq!(
+            writer.compress_keys(true).err().unwrap(),
+            BinConfigWriterError::KeyCompressionNotSupported(BinConfigVersion::V1)
+        );
+    }
+
+    #[test]
+    fn compress_keys_quadratic_prefixes() {
+        const NUM_KEYS: u32 = 10_000;
+
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(NUM_KEYS).unwrap()).unwrap();
+        writer.compress_keys(true).unwrap();
+        for index in 0..NUM_KEYS {
+            let key = format!("{:05}", index);
+            writer.bool(NonEmptyStr::new(&key).unwrap(), true).unwrap();
+        }
+        let mut data = writer.finish().unwrap();
+
+        // Each key shares all of the previous key as its prefix and adds a one-byte suffix,
+        // so the keys would decompress to a quadratic number of bytes.
+        let key_table_offset =
+            u32::from_le_bytes([data[12], data[13], data[14], data[15]]) as usize;
+
+        for index in 0..NUM_KEYS as usize {
+            let entry = key_table_offset + index * 8;
+            data[entry..entry + 4].copy_from_slice(&(index as u32).to_le_bytes());
+            data[entry + 4..entry + 8].copy_from_slice(&1u32.to_le_bytes());
+        }
+
+        // Rejected before anything is decompressed.
+        let header = super::BinConfigHeader::read(&data).unwrap();
+        assert_eq!(
+            super::decompress_keys(&data, &header).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+
+        assert_eq!(
+            BinConfig::new(data).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+    }
+
+    #[test]
+    fn checksum() {
+        fn write(checksum: bool, compress_keys: bool) -> Box<[u8]> {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+            writer.checksum(checksum).unwrap();
+            writer.compress_keys(compress_keys).unwrap();
+            writer.string(nestr!("foo"), "bar").unwrap();
+            writer.table(nestr!("table"), 1).unwrap();
+            writer.i64(nestr!("baz"), 7).unwrap();
+            writer.end().unwrap();
+            writer.finish().unwrap()
+        }
+
+        for &compress_keys in [false, true].iter() {
+            let data = write(true, compress_keys);
+
+            // Checksum does not change the data blob size.
+            assert_eq!(data.len(), write(false, compress_keys).len());
+
+            BinConfig::validate(&data).unwrap();
+
+            let config = BinConfig::new(data.clone()).unwrap();
+            assert_eq!(config.root().get_string("foo").unwrap(), "bar");
+
+            // Corrupt each byte of the data blob past the header.
+            for index in BinConfigVersion::CURRENT.header_size() as usize..data.len() {
+                let mut corrupted = data.clone();
+                corrupted[index] ^= 0x10;
+
+                assert_eq!(
+                    BinConfig::validate(&corrupted).err().unwrap(),
+                    BinConfigError::ChecksumMismatch
+                );
+                assert_eq!(
+                    BinConfig::new(corrupted).err().unwrap(),
+                    BinConfigError::ChecksumMismatch
+                );
             }
+        }
+
+        // Not supported by `V1` / `V2` data blobs.
+        let mut writer =
+            BinConfigWriter::with_version(NonZeroU32::new(1).unwrap(), BinConfigVersion::V2)
+                .unwrap();
+        assert_eq!(
+            writer.checksum(true).err().unwrap(),
+            BinConfigWriterError::ChecksumNotSupported(BinConfigVersion::V2)
         );
+    }
 
-        // But this works.
+    #[cfg(any(feature = "lz4", feature = "zstd"))]
+    #[test]
+    fn compression() {
+        fn write(compression: CompressionLevel, compress_keys: bool, checksum: bool) -> Box<[u8]> {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+            writer.compression(compression).unwrap();
+            writer.compress_keys(compress_keys).unwrap();
+            writer.checksum(checksum).unwrap();
+            writer.embed_schema(true).unwrap();
+            writer.string(nestr!("foo"), "bar").unwrap();
+            writer.array(nestr!("array"), 64).unwrap();
+            for index in 0..64 {
+                writer.table(None, 2).unwrap();
+                writer.i64(nestr!("index"), index % 4).unwrap();
+                writer
+                    .string(nestr!("name"), "some repetitive string")
+                    .unwrap();
+                writer.end().unwrap();
+            }
+            writer.end().unwrap();
+            writer.finish().unwrap()
+        }
 
+        let mut levels = Vec::new();
+        #[cfg(feature = "lz4")]
+        levels.push(CompressionLevel::Lz4);
+        #[cfg(feature = "zstd")]
+        levels.push(CompressionLevel::Zstd(3));
+
+        let expected = BinConfig::new(write(CompressionLevel::None, false, false))
+            .unwrap()
+            .to_lua_string()
+            .unwrap();
+
+        for &level in levels.iter() {
+            for &compress_keys in [false, true].iter() {
+                for &checksum in [false, true].iter() {
+                    let uncompressed = write(CompressionLevel::None, compress_keys, checksum);
+                    let data = write(level, compress_keys, checksum);
+
+                    assert!(data.len() < uncompressed.len());
+
+                    BinConfig::validate(&data).unwrap();
+
+                    let config = BinConfig::new(data.clone()).unwrap();
+                    assert_eq!(config.to_lua_string().unwrap(), expected);
+                    assert!(config.schema().is_some());
+                    assert_eq!(config.root().get_string("foo").unwrap(), "bar");
+
+                    // Corrupt the compressed payload.
+                    if !checksum {
+                        let corrupted = data[..data.len() - 1].to_vec().into_boxed_slice();
+
+                        assert_eq!(
+                            BinConfig::new(corrupted).err().unwrap(),
+                            BinConfigError::InvalidBinaryConfigData
+                        );
+                    }
+                }
+            }
+
+            // Unknown compression algorithm.
+            let mut data = write(level, false, false);
+            let offset = BinConfigVersion::CURRENT.header_size() as usize;
+            data[offset..offset + 4].copy_from_slice(&7u32.to_le_bytes());
+
+            assert_eq!(
+                BinConfig::new(data).err().unwrap(),
+                BinConfigError::UnsupportedCompression(7)
+            );
+
+            // Forged payload size.
+            let data = write(level, false, false);
+            let compressed_len = data.len() - offset - 8;
+
+            for &payload_size in [compressed_len * 255 + 1, u32::MAX as usize - offset].iter() {
+                let mut data = data.clone();
+                data[offset + 4..offset + 8].copy_from_slice(&(payload_size as u32).to_le_bytes());
+
+                assert_eq!(
+                    BinConfig::new(data).err().unwrap(),
+                    BinConfigError::InvalidBinaryConfigData
+                );
+            }
+
+            // Not supported by `V1` / `V2` data blobs.
+            let mut writer =
+                BinConfigWriter::with_version(NonZeroU32::new(1).unwrap(), BinConfigVersion::V2)
+                    .unwrap();
+            assert_eq!(
+                writer.compression(level).err().unwrap(),
+                BinConfigWriterError::CompressionNotSupported(BinConfigVersion::V2)
+            );
+            writer.compression(CompressionLevel::None).unwrap();
+        }
+
+        // Payloads compressing better than the maximum ratio are recorded uncompressed.
+        #[cfg(feature = "zstd")]
+        {
+            let write = |compression| {
+                let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+                writer.compression(compression).unwrap();
+                writer.string(nestr!("foo"), &"a".repeat(1 << 20)).unwrap();
+                writer.finish().unwrap()
+            };
+
+            let data = write(CompressionLevel::Zstd(3));
+            assert_eq!(data, write(CompressionLevel::None));
+
+            let config = BinConfig::new(data).unwrap();
+            assert_eq!(config.root().get_string("foo").unwrap().len(), 1 << 20);
+        }
+    }
+
+    #[test]
+    fn table_at_path() {
+        for &sort_keys in [false, true].iter() {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+            writer.sort_keys(sort_keys).unwrap();
+            writer.i64(nestr!("foo"), 7).unwrap();
+            writer.table(nestr!("render"), 2).unwrap();
+            writer.bool(nestr!("vsync"), true).unwrap();
+            writer.table(nestr!("shadows"), 1).unwrap();
+            writer.i64(nestr!("resolution"), 2048).unwrap();
+            writer.end().unwrap();
+            writer.end().unwrap();
+            writer.array(nestr!("levels"), 1).unwrap();
+            writer.table(None, 1).unwrap();
+            writer.string(nestr!("name"), "intro").unwrap();
+            writer.end().unwrap();
+            writer.end().unwrap();
+            let config = BinConfig::new(writer.finish().unwrap()).unwrap();
+
+            let shadows = config
+                .table_at_path(&["render".into(), "shadows".into()])
+                .unwrap();
+            assert_eq!(shadows.get_i64("resolution").unwrap(), 2048);
+
+            let level = config.table_at_path(&["levels".into(), 0.into()]).unwrap();
+            assert_eq!(level.get_string("name").unwrap(), "intro");
+
+            // Empty path - the root table.
+            assert_eq!(
+                config
+                    .table_at_path(std::iter::empty::<ConfigKey>())
+                    .unwrap()
+                    .len(),
+                3
+            );
+
+            assert_eq!(
+                config
+                    .table_at_path(&["render".into(), "vsync".into()])
+                    .err()
+                    .unwrap(),
+                GetPathError::IncorrectValueType(ValueType::Bool)
+            );
+            assert_eq!(
+                config
+                    .table_at_path(&["render".into(), "missing".into()])
+                    .err()
+                    .unwrap(),
+                GetPathError::KeyDoesNotExist(
+                    vec![nestr!("render").into(), nestr!("missing").into()].into()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn stats() {
+        for &embed_schema in [false, true].iter() {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+            writer.embed_schema(embed_schema).unwrap();
+            writer.i64(nestr!("foo"), 7).unwrap();
+            writer.table(nestr!("render"), 2).unwrap();
+            writer.bool(nestr!("vsync"), true).unwrap();
+            writer.f64(nestr!("gamma"), 2.2).unwrap();
+            writer.end().unwrap();
+            writer.array(nestr!("levels"), 2).unwrap();
+            writer.string(None, "intro").unwrap();
+            writer.string(None, "outro").unwrap();
+            writer.end().unwrap();
+            let data = writer.finish().unwrap();
+            let len = data.len() as u32;
+            let config = BinConfig::new(data).unwrap();
+
+            let stats = config.stats();
+
+            assert_eq!(stats.config.num_tables, 2);
+            assert_eq!(stats.config.num_arrays, 1);
+            assert_eq!(stats.config.num_bools, 1);
+            assert_eq!(stats.config.num_i64s, 1);
+            assert_eq!(stats.config.num_f64s, 1);
+            assert_eq!(stats.config.num_strings, 2);
+            assert_eq!(stats.config.total_values(), 7);
+            assert_eq!(stats.config.max_depth, 2);
+            // "foo", "render", "vsync", "gamma", "levels".
+            assert_eq!(stats.config.key_bytes, 3 + 6 + 5 + 5 + 6);
+            // "intro", "outro".
+            assert_eq!(stats.config.string_bytes, 5 + 5);
+
+            assert_eq!(stats.header_size, BinConfigVersion::V3.header_size());
+            // 5 unique keys.
+            assert_eq!(
+                stats.key_table_size,
+                5 * std::mem::size_of::<super::InternedString>() as u32
+            );
+            assert!(stats.values_size > 0);
+            assert!(stats.strings_size > 0);
+            assert_eq!(stats.schema_size > 0, embed_schema);
+            assert_eq!(stats.total_size(), len);
+        }
+    }
+
+    #[test]
+    fn codec() {
+        /// XORs the payload with the key, prepends the key to detect mismatched keys.
+        struct XorCodec(u8);
+
+        impl BinConfigCodec for XorCodec {
+            fn encode(&self, payload: &[u8]) -> Vec<u8> {
+                std::iter::once(self.0)
+                    .chain(payload.iter().map(|byte| byte ^ self.0))
+                    .collect()
+            }
+
+            fn decode(&self, payload: &[u8]) -> Option<Vec<u8>> {
+                match payload.split_first() {
+                    Some((&key, payload)) if key == self.0 => {
+                        Some(payload.iter().map(|byte| byte ^ self.0).collect())
+                    }
+                    _ => None,
+                }
+            }
+        }
+
+        fn write(codec: Option<u8>, compress_keys: bool, checksum: bool) -> Box<[u8]> {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+            writer
+                .codec(codec.map(|key| std::sync::Arc::new(XorCodec(key)) as _))
+                .unwrap();
+            writer.compress_keys(compress_keys).unwrap();
+            writer.checksum(checksum).unwrap();
+            writer.embed_schema(true).unwrap();
+            writer.string(nestr!("secret"), "tuning data").unwrap();
+            writer.table(nestr!("table"), 1).unwrap();
+            writer.i64(nestr!("baz"), 7).unwrap();
+            writer.end().unwrap();
+            writer.finish().unwrap()
+        }
+
+        fn contains(data: &[u8], string: &str) -> bool {
+            data.windows(string.len())
+                .any(|window| window == string.as_bytes())
+        }
+
+        for &compress_keys in [false, true].iter() {
+            for &checksum in [false, true].iter() {
+                let plain = write(None, compress_keys, checksum);
+                assert!(contains(&plain, "tuning data"));
+
+                let data = write(Some(0x5a), compress_keys, checksum);
+                assert!(!contains(&data, "tuning data"));
+                assert!(!contains(&data, "secret"));
+
+                // The header is validated as usual.
+                assert_eq!(
+                    BinConfig::data_version(&data).unwrap(),
+                    BinConfigVersion::CURRENT
+                );
+
+                BinConfig::validate_with_codec(&data, &XorCodec(0x5a)).unwrap();
+
+                let config = BinConfig::with_codec(data.clone(), &XorCodec(0x5a)).unwrap();
+                assert_eq!(config.root().get_string("secret").unwrap(), "tuning data");
+                assert_eq!(
+                    config
+                        .root()
+                        .get_i64_path(&["table".into(), "baz".into()])
+                        .unwrap(),
+                    7
+                );
+                assert!(config.schema().is_some());
+
+                // Codec is required.
+                assert_eq!(
+                    BinConfig::validate(&data).err().unwrap(),
+                    BinConfigError::CodecRequired
+                );
+                assert_eq!(
+                    BinConfig::new(data.clone()).err().unwrap(),
+                    BinConfigError::CodecRequired
+                );
+
+                // Wrong codec.
+                assert_eq!(
+                    BinConfig::with_codec(data, &XorCodec(0x33)).err().unwrap(),
+                    BinConfigError::DecodeFailed
+                );
+
+                // Unencoded data blobs are loaded as usual.
+                BinConfig::with_codec(plain, &XorCodec(0x5a)).unwrap();
+            }
+        }
+
+        // Not supported by `V1` / `V2` data blobs.
+        let mut writer =
+            BinConfigWriter::with_version(NonZeroU32::new(1).unwrap(), BinConfigVersion::V2)
+                .unwrap();
         assert_eq!(
-            config
-                .root()
-                .get_bool_path(&["array".into(), 0.into()])
+            writer
+                .codec(Some(std::sync::Arc::new(XorCodec(0x5a))))
+                .err()
                 .unwrap(),
-            true,
+            BinConfigWriterError::CodecNotSupported(BinConfigVersion::V2)
         );
     }
 
     #[test]
-    fn GetPathError_IncorrectValueType() {
-        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
-        writer.table(nestr!("table"), 2).unwrap();
-        writer.bool(nestr!("foo"), true).unwrap();
-        writer.f64(nestr!("bar"), 3.14).unwrap();
-        writer.end().unwrap();
-        let data = writer.finish().unwrap();
-        let config = BinConfig::new(data).unwrap();
+    fn schema() {
+        fn write(embed_schema: bool, compress_keys: bool, checksum: bool) -> Box<[u8]> {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+            writer.embed_schema(embed_schema).unwrap();
+            writer.compress_keys(compress_keys).unwrap();
+            writer.checksum(checksum).unwrap();
+            writer.string(nestr!("foo"), "bar").unwrap();
+            writer.table(nestr!("table"), 1).unwrap();
+            writer.i64(nestr!("baz"), 7).unwrap();
+            writer.end().unwrap();
+            writer.array(nestr!("array"), 2).unwrap();
+            writer.bool(None, true).unwrap();
+            writer.bool(None, false).unwrap();
+            writer.end().unwrap();
+            writer.finish().unwrap()
+        }
 
+        fn value(key: &str, value_type: ValueType) -> BinConfigSchema {
+            BinConfigSchema {
+                key: NonEmptyStr::new(key).map(Into::into),
+                value_type,
+                children: Vec::new(),
+            }
+        }
+
+        let expected = BinConfigSchema {
+            children: vec![
+                value("foo", ValueType::String),
+                BinConfigSchema {
+                    children: vec![value("baz", ValueType::I64)],
+                    ..value("table", ValueType::Table)
+                },
+                BinConfigSchema {
+                    children: vec![value("", ValueType::Bool), value("", ValueType::Bool)],
+                    ..value("array", ValueType::Array)
+                },
+            ],
+            ..value("", ValueType::Table)
+        };
+
+        for &compress_keys in [false, true].iter() {
+            for &checksum in [false, true].iter() {
+                let data = write(true, compress_keys, checksum);
+                BinConfig::validate(&data).unwrap();
+
+                let config = BinConfig::new(data).unwrap();
+                assert_eq!(config.schema().unwrap(), expected);
+                assert_eq!(config.root().get_string("foo").unwrap(), "bar");
+                assert_eq!(
+                    config.root().get_i64_path(&["table".into()]).unwrap_err(),
+                    GetPathError::IncorrectValueType(ValueType::Table)
+                );
+                assert_eq!(
+                    config
+                        .root()
+                        .get_i64_path(&["table".into(), "baz".into()])
+                        .unwrap(),
+                    7
+                );
+
+                // No schema block.
+                let config = BinConfig::new(write(false, compress_keys, checksum)).unwrap();
+                assert!(config.schema().is_none());
+            }
+        }
+
+        // Schema block must match the values.
+        let data = write(true, false, false);
+        let schema_size_offset = data.len() - std::mem::size_of::<u32>();
+
+        // Corrupt the type of the last value.
+        let mut corrupted = data.clone();
+        corrupted[schema_size_offset - 1] ^= 0x1;
         assert_eq!(
-            config
-                .root()
-                .get_i64_path(&["table".into(), "foo".into()])
-                .err()
-                .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
-        );
-        assert_eq!(
-            config
-                .root()
-                .get_f64_path(&["table".into(), "foo".into()])
-                .err()
-                .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
-        );
-        assert_eq!(
-            config
-                .root()
-                .get_string_path(&["table".into(), "foo".into()])
-                .err()
-                .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
+            BinConfig::new(corrupted).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
         );
+
+        // Corrupt the schema block size.
+        let mut corrupted = data.clone();
+        corrupted[schema_size_offset] ^= 0x1;
         assert_eq!(
-            config
-                .root()
-                .get_array_path(&["table".into(), "foo".into()])
-                .err()
-                .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
+            BinConfig::new(corrupted).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
         );
+
+        // Not supported by `V1` / `V2` data blobs.
+        let mut writer =
+            BinConfigWriter::with_version(NonZeroU32::new(1).unwrap(), BinConfigVersion::V2)
+                .unwrap();
         assert_eq!(
-            config
-                .root()
-                .get_table_path(&["table".into(), "foo".into()])
-                .err()
-                .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
+            writer.embed_schema(true).err().unwrap(),
+            BinConfigWriterError::SchemaNotSupported(BinConfigVersion::V2)
         );
+    }
 
-        // But this works.
+    #[test]
+    fn sort_keys() {
+        const LEN: u32 = 256;
+
+        fn write(sort_keys: bool, compress_keys: bool, checksum: bool) -> Box<[u8]> {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(LEN + 1).unwrap()).unwrap();
+            writer.sort_keys(sort_keys).unwrap();
+            writer.compress_keys(compress_keys).unwrap();
+            writer.checksum(checksum).unwrap();
+
+            for index in 0..LEN {
+                writer
+                    .i64(
+                        NonEmptyStr::new(&format!("key{}", index)).unwrap(),
+                        index as _,
+                    )
+                    .unwrap();
+            }
+
+            writer.table(nestr!("table"), 2).unwrap();
+            writer.string(nestr!("foo"), "bar").unwrap();
+            writer.bool(nestr!("baz"), true).unwrap();
+            writer.end().unwrap();
+
+            writer.finish().unwrap()
+        }
+
+        for &compress_keys in [false, true].iter() {
+            for &checksum in [false, true].iter() {
+                let data = write(true, compress_keys, checksum);
+
+                // Sorting does not change the data blob size.
+                assert_eq!(data.len(), write(false, compress_keys, checksum).len());
+
+                BinConfig::validate(&data).unwrap();
+
+                let config = BinConfig::new(data).unwrap();
+                let root = config.root();
+
+                assert_eq!(root.len(), LEN + 1);
+
+                for index in 0..LEN {
+                    assert_eq!(
+                        root.get_i64(&format!("key{}", index)).unwrap(),
+                        index as i64
+                    );
+                }
+
+                assert_eq!(
+                    root.get_i64("missing").err().unwrap(),
+                    TableError::KeyDoesNotExist
+                );
+
+                let table = root.get_table("table").unwrap();
+                assert_eq!(table.get_string("foo").unwrap(), "bar");
+                assert!(table.get_bool("baz").unwrap());
+
+                // Values are iterated in key hash order.
+                let hashes: Vec<_> = root.iter().map(|(key, _)| string_hash_fnv1a(key)).collect();
+                let mut sorted = hashes.clone();
+                sorted.sort();
+                assert_eq!(hashes, sorted);
+            }
+        }
+
+        // Table values must be sorted.
+        let mut data = write(true, false, false);
+        let header_size = BinConfigVersion::CURRENT.header_size() as usize;
+        let value_size = std::mem::size_of::<super::super::value::BinConfigPackedValue>();
+
+        for index in 0..value_size {
+            data.swap(header_size + index, header_size + value_size + index);
+        }
 
         assert_eq!(
-            config
-                .root()
-                .get_bool_path(&["table".into(), "foo".into()])
-                .unwrap(),
-            true
+            BinConfig::new(data).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
         );
 
+        // Not supported by `V1` / `V2` data blobs.
+        let mut writer =
+            BinConfigWriter::with_version(NonZeroU32::new(1).unwrap(), BinConfigVersion::V2)
+                .unwrap();
         assert_eq!(
-            config
-                .root()
-                .get_i64_path(&["table".into(), "bar".into()])
-                .unwrap(),
-            3
+            writer.sort_keys(true).err().unwrap(),
+            BinConfigWriterError::KeySortingNotSupported(BinConfigVersion::V2)
         );
-        assert!(cmp_f64(
-            config
-                .root()
-                .get_f64_path(&["table".into(), "bar".into()])
-                .unwrap(),
-            3.14
-        ));
     }
 
+    #[cfg(feature = "audit")]
     #[test]
-    fn hash_collisions() {
-        // See `fnv1a_hash_collisions()`.
+    fn audit_hook() {
+        use std::sync::{Arc, Mutex};
 
-        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        #[derive(Default)]
+        struct Hook(Mutex<Vec<(ConfigPath, ConfigAccess)>>);
 
-        writer.string(nestr!("costarring"), "declinate").unwrap();
-        writer.string(nestr!("liquid"), "macallums").unwrap();
+        impl ConfigAuditHook for Hook {
+            fn on_get(&self, path: &ConfigPath, access: ConfigAccess) {
+                self.0.lock().unwrap().push((path.clone(), access));
+            }
+        }
 
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.array(nestr!("array"), 1).unwrap();
+        writer.table(None, 1).unwrap();
+        writer.i64(nestr!("foo"), 7).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.table(nestr!("table"), 1).unwrap();
+        writer.bool(nestr!("bar"), true).unwrap();
+        writer.end().unwrap();
         let data = writer.finish().unwrap();
 
-        let config = BinConfig::new(data).unwrap();
+        let mut config = BinConfig::new(data).unwrap();
+
+        let hook = Arc::new(Hook::default());
+        config.set_audit_hook(hook.clone());
 
+        let root = config.root();
+
+        // Hit.
+        assert!(root.get_val("table").is_some());
         assert_eq!(
-            config.root().get_string("liquid".into()).unwrap(),
-            "macallums"
+            root.get_array("array")
+                .unwrap()
+                .get_table(0)
+                .unwrap()
+                .get_i64("foo")
+                .unwrap(),
+            7
         );
+        assert!(root.get_bool_path(&["table".into(), "bar".into()]).unwrap());
+
+        // Miss.
+        assert!(!root.get_bool_or("baz", false));
+        assert!(root.get_array("array").unwrap().get_val(1).is_err());
+        assert!(root
+            .get_bool_path(&["table".into(), "baz".into(), "bob".into()])
+            .is_err());
+
+        // Type mismatch.
+        assert_eq!(root.get_i64_or("table", 9), 9);
+        assert!(root.get_i64_path(&["table".into(), "bar".into()]).is_err());
+
+        // Not reported.
+        assert!(root.contains("table"));
+        assert_eq!(root.iter().count(), 2);
+
+        let path = |keys: &[OwnedConfigKey]| ConfigPath(keys.to_vec());
+        let key = |key| OwnedConfigKey::from(NonEmptyStr::new(key).unwrap());
+
         assert_eq!(
-            config.root().get_string("costarring".into()).unwrap(),
-            "declinate"
+            *hook.0.lock().unwrap(),
+            vec![
+                (path(&[key("table")]), ConfigAccess::Hit),
+                (path(&[key("array")]), ConfigAccess::Hit),
+                (path(&[key("array"), 0.into()]), ConfigAccess::Hit),
+                (
+                    path(&[key("array"), 0.into(), key("foo")]),
+                    ConfigAccess::Hit
+                ),
+                (path(&[key("table"), key("bar")]), ConfigAccess::Hit),
+                (path(&[key("baz")]), ConfigAccess::Miss),
+                (path(&[key("array")]), ConfigAccess::Hit),
+                (path(&[key("array"), 1.into()]), ConfigAccess::Miss),
+                (path(&[key("table"), key("baz")]), ConfigAccess::Miss),
+                (
+                    path(&[key("table")]),
+                    ConfigAccess::TypeMismatch(ValueType::Table)
+                ),
+                (
+                    path(&[key("table"), key("bar")]),
+                    ConfigAccess::TypeMismatch(ValueType::Bool)
+                ),
+            ]
         );
 
-        #[cfg(feature = "str_hash")]
-        {
-            assert_eq!(
-                config.root().get_string(key!("liquid")).unwrap(),
-                "macallums"
-            );
-            assert_eq!(
-                config.root().get_string(key!("costarring")).unwrap(),
-                "declinate"
-            );
-        }
+        // Removed hook is no longer called.
+        assert!(config.take_audit_hook().is_some());
+        assert!(config.root().get_val("table").is_some());
+        assert_eq!(hook.0.lock().unwrap().len(), 11);
     }
 
-    #[cfg(feature = "dyn")]
     #[test]
-    fn to_dyn_config() {
-        let mut writer = BinConfigWriter::new(NonZeroU32::new(6).unwrap()).unwrap();
-
-        writer.array(nestr!("array_value"), 3).unwrap();
-        writer.i64(None, 54).unwrap();
-        writer.i64(None, 12).unwrap();
-        writer.f64(None, 78.9).unwrap();
+    fn untrusted_data() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.bool(None, true).unwrap();
+        writer.bool(None, false).unwrap();
         writer.end().unwrap();
+        writer.array(nestr!("int"), 2).unwrap();
+        writer.i64(None, 7).unwrap();
+        writer.i64(None, 8).unwrap();
+        writer.end().unwrap();
+        let data = writer.finish().unwrap();
 
-        writer.bool(nestr!("bool_value"), true).unwrap();
-        writer.f64(nestr!("float_value"), 3.14).unwrap();
-        writer.i64(nestr!("int_value"), 7).unwrap();
-        writer.string(nestr!("string_value"), "foo").unwrap();
+        assert!(BinConfig::new(data.clone()).is_ok());
 
-        writer.table(nestr!("table_value"), 3).unwrap();
-        writer.i64(nestr!("bar"), 2020).unwrap();
-        writer.string(nestr!("baz"), "hello").unwrap();
-        writer.bool(nestr!("foo"), false).unwrap();
-        writer.end().unwrap();
+        let write_u32 = |data: &mut Box<[u8]>, index: usize, val: u32| {
+            data[index * 4..index * 4 + 4].copy_from_slice(&val.to_le_bytes());
+        };
+
+        // Key table size overflows `u32`.
+        let mut invalid = data.clone();
+        write_u32(&mut invalid, 4, u32::MAX / 8 + 2);
+        assert_eq!(
+            BinConfig::new(invalid).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+
+        // Key table offset overflows `u32`.
+        let mut invalid = data.clone();
+        write_u32(&mut invalid, 3, u32::MAX - 4);
+        assert_eq!(
+            BinConfig::new(invalid).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+
+        // Key table overlaps the values.
+        let mut invalid = data.clone();
+        write_u32(&mut invalid, 3, 40);
+        assert_eq!(
+            BinConfig::new(invalid).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+
+        // Mixed array value types - copy the second `int` array element over the second `array` array element.
+        // |- header (32b) -|- root table (2 * 16b) -|- `array` (2 * 16b) -|- `int` (2 * 16b) -|
+        let mut invalid = data.clone();
+        invalid.copy_within(112..128, 80);
+        assert_eq!(
+            BinConfig::new(invalid).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
 
+        // Arbitrary corruption / truncation never panics.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(4).unwrap()).unwrap();
+        writer.string(nestr!("name"), "Game").unwrap();
+        writer.f64(nestr!("scale"), 1.5).unwrap();
+        writer.array(nestr!("levels"), 2).unwrap();
+        writer.string(None, "intro").unwrap();
+        writer.string(None, "boss").unwrap();
+        writer.end().unwrap();
+        writer.table(nestr!("window"), 2).unwrap();
+        writer.i64(nestr!("width"), 1920).unwrap();
+        writer.array(nestr!("modes"), 1).unwrap();
+        writer.table(None, 1).unwrap();
+        writer.bool(nestr!("fullscreen"), true).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
         let data = writer.finish().unwrap();
 
-        let config = BinConfig::new(data).unwrap();
+        // Deterministic xorshift PRNG.
+        let mut state = 0x2545_f491_u32;
+        let mut random = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
 
-        // Serialize to dynamic config.
-        let dyn_config = config.to_dyn_config();
+        for _ in 0..10_000 {
+            let mut corrupted = data.clone().into_vec();
 
-        let array_value = dyn_config.root().get_array("array_value").unwrap();
+            for _ in 0..1 + random() % 4 {
+                let index = random() as usize % corrupted.len();
+                corrupted[index] = random() as u8;
+            }
 
-        assert_eq!(array_value.len(), 3);
-        assert_eq!(array_value.get_i64(0).unwrap(), 54);
-        assert!(cmp_f64(array_value.get_f64(0).unwrap(), 54.0));
-        assert_eq!(array_value.get_i64(1).unwrap(), 12);
-        assert!(cmp_f64(array_value.get_f64(1).unwrap(), 12.0));
-        assert_eq!(array_value.get_i64(2).unwrap(), 78);
-        assert!(cmp_f64(array_value.get_f64(2).unwrap(), 78.9));
+            if random() % 8 == 0 {
+                corrupted.truncate(random() as usize % corrupted.len());
+            }
 
-        assert_eq!(dyn_config.root().get_bool("bool_value").unwrap(), true);
+            if let Ok(config) = BinConfig::new(corrupted.into_boxed_slice()) {
+                // Full traversal of the validated config.
+                let _ = config.to_string();
+            }
+        }
 
-        assert!(cmp_f64(
-            dyn_config.root().get_f64("float_value").unwrap(),
-            3.14
-        ));
+        // Zero-length root table in the header of packed data blobs (also must not panic in debug builds).
+        let write_packed = |configure: &dyn Fn(&mut BinConfigWriter)| {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+            configure(&mut writer);
+            writer.string(nestr!("name"), "Game").unwrap();
+            writer.finish().unwrap()
+        };
 
-        assert_eq!(dyn_config.root().get_i64("int_value").unwrap(), 7);
+        let mut packed = Vec::new();
+        packed.push(write_packed(&|writer| writer.compress_keys(true).unwrap()));
 
-        assert_eq!(dyn_config.root().get_string("string_value").unwrap(), "foo");
+        #[cfg(feature = "lz4")]
+        packed.push(write_packed(&|writer| {
+            writer.compression(CompressionLevel::Lz4).unwrap()
+        }));
 
-        let table_value = dyn_config.root().get_table("table_value").unwrap();
+        #[cfg(feature = "zstd")]
+        packed.push(write_packed(&|writer| {
+            writer.compression(CompressionLevel::Zstd(3)).unwrap()
+        }));
 
-        assert_eq!(table_value.len(), 3);
-        assert_eq!(table_value.get_i64("bar").unwrap(), 2020);
-        assert!(cmp_f64(table_value.get_f64("bar").unwrap(), 2020.0));
-        assert_eq!(table_value.get_string("baz").unwrap(), "hello");
-        assert_eq!(table_value.get_bool("foo").unwrap(), false);
+        for mut data in packed.into_iter() {
+            assert!(BinConfig::new(data.clone()).is_ok());
+
+            write_u32(&mut data, 2, 0);
+            assert_eq!(
+                BinConfig::new(data).err().unwrap(),
+                BinConfigError::InvalidBinaryConfigData
+            );
+        }
     }
 
-    #[cfg(feature = "ini")]
     #[test]
-    fn to_ini_string() {
-        let ini = r#"array = ["foo", "bar", "baz"]
-bool = true
-float = 3.14
-int = 7
-string = "foo"
+    fn max_depth() {
+        let write = |depth: u32| {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+            writer.max_depth(depth);
 
-[other_section]
-other_bool = true
-other_float = 3.14
-other_int = 7
-other_string = "foo"
+            for _ in 0..depth {
+                writer.table(nestr!("foo"), 1).unwrap();
+            }
 
-[section]
-bool = false
-float = 7.62
-int = 9
-string = "bar""#;
+            writer.i64(nestr!("bar"), 7).unwrap();
 
-        let mut writer = BinConfigWriter::new(NonZeroU32::new(7).unwrap()).unwrap();
+            for _ in 0..depth {
+                writer.end().unwrap();
+            }
 
-        writer.array(nestr!("array"), 3).unwrap();
-        writer.string(None, "foo").unwrap();
-        writer.string(None, "bar").unwrap();
-        writer.string(None, "baz").unwrap();
-        writer.end().unwrap();
+            writer.finish().unwrap()
+        };
 
-        writer.bool(nestr!("bool"), true).unwrap();
-        writer.f64(nestr!("float"), 3.14).unwrap();
-        writer.i64(nestr!("int"), 7).unwrap();
-        writer.string(nestr!("string"), "foo").unwrap();
+        let data = write(3);
 
-        writer.table(nestr!("other_section"), 4).unwrap();
-        writer.bool(nestr!("other_bool"), true).unwrap();
-        writer.f64(nestr!("other_float"), 3.14).unwrap();
-        writer.i64(nestr!("other_int"), 7).unwrap();
-        writer.string(nestr!("other_string"), "foo").unwrap();
-        writer.end().unwrap();
+        assert!(BinConfig::new(data.clone()).is_ok());
+        assert!(BinConfig::with_max_depth(data.clone(), 3).is_ok());
+        assert_eq!(
+            BinConfig::with_max_depth(data, 2).err().unwrap(),
+            BinConfigError::MaxDepthExceeded(2)
+        );
 
-        writer.table(nestr!("section"), 4).unwrap();
-        writer.bool(nestr!("bool"), false).unwrap();
-        writer.f64(nestr!("float"), 7.62).unwrap();
-        writer.i64(nestr!("int"), 9).unwrap();
-        writer.string(nestr!("string"), "bar").unwrap();
-        writer.end().unwrap();
+        // Deeply nested configs are rejected by default.
+        let data = write(DEFAULT_MAX_DEPTH + 1);
 
-        let data = writer.finish().unwrap();
+        assert_eq!(
+            BinConfig::new(data.clone()).err().unwrap(),
+            BinConfigError::MaxDepthExceeded(DEFAULT_MAX_DEPTH)
+        );
 
-        let config = BinConfig::new(data).unwrap();
+        let config = BinConfig::with_max_depth(data, DEFAULT_MAX_DEPTH + 1).unwrap();
 
-        let string = config
-            .to_ini_string_opts(ToIniStringOptions {
-                arrays: true,
-                ..Default::default()
-            })
-            .unwrap();
+        let mut path = vec!["foo".into(); DEFAULT_MAX_DEPTH as usize + 1];
+        path.push("bar".into());
 
-        assert_eq!(string, ini);
+        assert_eq!(config.root().get_i64_path(&path).unwrap(), 7);
     }
 }
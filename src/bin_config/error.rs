@@ -1,15 +1,52 @@
 use {
-    crate::ValueType,
+    crate::{BinConfigVersion, ConfigPath, EmptyKeyError, ValueType},
     std::{
         error::Error,
         fmt::{Display, Formatter},
     },
 };
 
+/// An error returned when creating a [`binary config`] from a data blob.
+///
+/// [`binary config`]: struct.BinConfig.html
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum BinConfigError {
     /// Binary config data blob is invalid.
     InvalidBinaryConfigData,
+    /// Binary config data blob [`version`] is not supported.
+    /// Contains the version number as recorded in the data blob header.
+    ///
+    /// [`version`]: enum.BinConfigVersion.html
+    UnsupportedVersion(u32),
+    /// Binary config data blob [`checksum`] does not match its contents
+    /// (e.g. the data blob was corrupted).
+    ///
+    /// [`checksum`]: struct.BinConfigWriter.html#method.checksum
+    ChecksumMismatch,
+    /// Binary config data blob payload is [`compressed`] with an unknown compression algorithm,
+    /// or with an algorithm whose support is not enabled (via `"lz4"` / `"zstd"` features).
+    /// Contains the compression algorithm identifier as recorded in the data blob.
+    ///
+    /// [`compressed`]: struct.BinConfigWriter.html#method.compression
+    UnsupportedCompression(u32),
+    /// Binary config data blob payload is [`encoded`], but no [`codec`] was provided to decode it.
+    ///
+    /// [`encoded`]: struct.BinConfigWriter.html#method.codec
+    /// [`codec`]: trait.BinConfigCodec.html
+    CodecRequired,
+    /// Binary config data blob payload could not be [`decoded`] by the [`codec`].
+    ///
+    /// [`decoded`]: trait.BinConfigCodec.html#tymethod.decode
+    /// [`codec`]: trait.BinConfigCodec.html
+    DecodeFailed,
+    /// Binary config data blob contains [`arrays`] / [`tables`] nested deeper than the [`maximum nesting depth`].
+    /// Contains the [`maximum nesting depth`].
+    ///
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`maximum nesting depth`]: struct.BinConfig.html#method.with_max_depth
+    MaxDepthExceeded(u32),
 }
 
 impl Error for BinConfigError {}
@@ -20,6 +57,28 @@ impl Display for BinConfigError {
 
         match self {
             InvalidBinaryConfigData => "binary config data blob is invalid".fmt(f),
+            UnsupportedVersion(version) => write!(
+                f,
+                "binary config data blob version ({}) is not supported",
+                version
+            ),
+            ChecksumMismatch => {
+                "binary config data blob checksum does not match its contents".fmt(f)
+            }
+            UnsupportedCompression(algorithm) => write!(
+                f,
+                "binary config data blob compression algorithm ({}) is not supported",
+                algorithm
+            ),
+            CodecRequired => {
+                "binary config data blob payload is encoded, but no codec was provided".fmt(f)
+            }
+            DecodeFailed => "binary config data blob payload could not be decoded".fmt(f),
+            MaxDepthExceeded(max_depth) => write!(
+                f,
+                "binary config data blob arrays / tables are nested deeper than the maximum nesting depth ({})",
+                max_depth
+            ),
         }
     }
 }
@@ -28,6 +87,7 @@ impl Display for BinConfigError {
 ///
 /// [`bin array`]: struct.BinArray.html
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum BinArrayError {
     /// [`Array`] index out of bounds.
     /// Contains the actual [`array`] length.
@@ -41,6 +101,12 @@ pub enum BinArrayError {
     /// [`Array`]: struct.BinArray.html
     /// [`type`]: enum.ValueType.html
     IncorrectValueType(ValueType),
+    /// [`Array`] [`i64`] value is out of range of the requested integer type.
+    /// Contains the actual value.
+    ///
+    /// [`Array`]: struct.BinArray.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    ValueOutOfRange(i64),
 }
 
 impl Error for BinArrayError {}
@@ -58,6 +124,11 @@ impl Display for BinArrayError {
                     invalid_type
                 )
             }
+            ValueOutOfRange(value) => write!(
+                f,
+                "array value {} is out of range of the requested integer type",
+                value
+            ),
         }
     }
 }
@@ -66,6 +137,7 @@ impl Display for BinArrayError {
 ///
 /// [`bin config writer`]: struct.BinConfigWriter.html
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum BinConfigWriterError {
     /// Empty [`binary config`] root tables are not supported.
     ///
@@ -98,6 +170,10 @@ pub enum BinConfigWriterError {
         /// [`array`]: struct.BinArray.html
         found: ValueType,
     },
+    /// An empty string key was provided for a [`table`] element.
+    ///
+    /// [`table`]: struct.BinTable.html
+    EmptyKey,
     /// A non-unique string key was provided for a [`table`] element.
     ///
     /// [`table`]: struct.BinTable.html
@@ -120,6 +196,61 @@ pub enum BinConfigWriterError {
     /// [`tables`]: struct.BinTable.html
     /// [`finish`]: struct.BinConfigWriter.html#method.finish
     UnfinishedArraysOrTables(u32),
+    /// [`Key compression`] is not supported by the binary config data blob [`version`].
+    /// Contains the binary config data blob [`version`].
+    ///
+    /// [`Key compression`]: struct.BinConfigWriter.html#method.compress_keys
+    /// [`version`]: enum.BinConfigVersion.html
+    KeyCompressionNotSupported(BinConfigVersion),
+    /// [`Checksum`] is not supported by the binary config data blob [`version`].
+    /// Contains the binary config data blob [`version`].
+    ///
+    /// [`Checksum`]: struct.BinConfigWriter.html#method.checksum
+    /// [`version`]: enum.BinConfigVersion.html
+    ChecksumNotSupported(BinConfigVersion),
+    /// [`Schema`] embedding is not supported by the binary config data blob [`version`].
+    /// Contains the binary config data blob [`version`].
+    ///
+    /// [`Schema`]: struct.BinConfigWriter.html#method.embed_schema
+    /// [`version`]: enum.BinConfigVersion.html
+    SchemaNotSupported(BinConfigVersion),
+    /// [`Key sorting`] is not supported by the binary config data blob [`version`].
+    /// Contains the binary config data blob [`version`].
+    ///
+    /// [`Key sorting`]: struct.BinConfigWriter.html#method.sort_keys
+    /// [`version`]: enum.BinConfigVersion.html
+    KeySortingNotSupported(BinConfigVersion),
+    /// [`Compression`] is not supported by the binary config data blob [`version`].
+    /// Contains the binary config data blob [`version`].
+    ///
+    /// [`Compression`]: struct.BinConfigWriter.html#method.compression
+    /// [`version`]: enum.BinConfigVersion.html
+    CompressionNotSupported(BinConfigVersion),
+    /// Payload [`encoding`] is not supported by the binary config data blob [`version`].
+    /// Contains the binary config data blob [`version`].
+    ///
+    /// [`encoding`]: struct.BinConfigWriter.html#method.codec
+    /// [`version`]: enum.BinConfigVersion.html
+    CodecNotSupported(BinConfigVersion),
+    /// [`Typed arrays`] are not supported by the binary config data blob [`version`].
+    /// Contains the binary config data blob [`version`].
+    ///
+    /// [`Typed arrays`]: struct.BinConfigWriter.html#method.typed_arrays
+    /// [`version`]: enum.BinConfigVersion.html
+    TypedArraysNotSupported(BinConfigVersion),
+    /// Storing the [`f64` arrays as `f32`'s] is not supported by the binary config data blob [`version`].
+    /// Contains the binary config data blob [`version`].
+    ///
+    /// [`f64` arrays as `f32`'s]: struct.BinConfigWriter.html#method.f32_arrays
+    /// [`version`]: enum.BinConfigVersion.html
+    F32ArraysNotSupported(BinConfigVersion),
+    /// An [`array`] / [`table`] would be nested deeper than the [`maximum nesting depth`].
+    /// Contains the [`maximum nesting depth`].
+    ///
+    /// [`array`]: struct.BinArray.html
+    /// [`table`]: struct.BinTable.html
+    /// [`maximum nesting depth`]: struct.BinConfigWriter.html#method.max_depth
+    MaxDepthExceeded(u32),
     /// General write error.
     WriteError,
 }
@@ -130,6 +261,12 @@ impl From<std::io::Error> for BinConfigWriterError {
     }
 }
 
+impl From<EmptyKeyError> for BinConfigWriterError {
+    fn from(_: EmptyKeyError) -> Self {
+        Self::EmptyKey
+    }
+}
+
 impl Error for BinConfigWriterError {}
 
 impl Display for BinConfigWriterError {
@@ -142,6 +279,7 @@ impl Display for BinConfigWriterError {
             TooManyKeys(max_num_keys) => write!(f, "too many unique table string keys in the config (current max is {})", max_num_keys),
             ArrayKeyNotRequired => "a string key is not required for an array element".fmt(f),
             MixedArray { expected, found } => write!(f, "mixed (and non-convertible) type values in the array: expected \"{}\", found \"{}\"", expected, found),
+            EmptyKey => "an empty string key was provided for a table element".fmt(f),
             NonUniqueKey => "a non-unique string key was provided for a table element".fmt(f),
             ArrayOrTableLengthMismatch { expected, found } => write!(
                 f,
@@ -151,7 +289,55 @@ impl Display for BinConfigWriterError {
             ),
             EndCallMismatch => "mismatched call to `end` (expected a previous call to `array`/`table`)".fmt(f),
             UnfinishedArraysOrTables(num) => write!(f, "{} unfinished array(s)/table(s) remain in the call to `finish`", num),
+            KeyCompressionNotSupported(version) => write!(f, "key compression is not supported by the binary config data blob version ({})", version),
+            ChecksumNotSupported(version) => write!(f, "checksum is not supported by the binary config data blob version ({})", version),
+            SchemaNotSupported(version) => write!(f, "schema embedding is not supported by the binary config data blob version ({})", version),
+            KeySortingNotSupported(version) => write!(f, "key sorting is not supported by the binary config data blob version ({})", version),
+            CompressionNotSupported(version) => write!(f, "compression is not supported by the binary config data blob version ({})", version),
+            CodecNotSupported(version) => write!(f, "payload encoding is not supported by the binary config data blob version ({})", version),
+            TypedArraysNotSupported(version) => write!(f, "typed arrays are not supported by the binary config data blob version ({})", version),
+            F32ArraysNotSupported(version) => write!(f, "`f32` arrays are not supported by the binary config data blob version ({})", version),
+            MaxDepthExceeded(max_depth) => write!(f, "array / table would be nested deeper than the maximum nesting depth ({})", max_depth),
             WriteError => "general write error".fmt(f),
         }
     }
 }
+
+/// A [`binary config writer error`] with the path to the config element which caused it,
+/// recorded by the [`writer`] when [`path validation`] is enabled.
+///
+/// [`binary config writer error`]: enum.BinConfigWriterError.html
+/// [`writer`]: struct.BinConfigWriter.html
+/// [`path validation`]: struct.BinConfigWriter.html#method.validate_paths
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub struct BinConfigWriterPathError {
+    /// Path to the config element which caused the error.
+    ///
+    /// For duplicate keys, mixed arrays and overflowing [`arrays`] / [`tables`] it is the path to the element being written;
+    /// for [`arrays`] / [`tables`] with too few elements it is the path to the [`array`] / [`table`] itself.
+    ///
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`array`]: struct.BinArray.html
+    /// [`table`]: struct.BinTable.html
+    pub path: ConfigPath,
+    /// The actual [`error`](enum.BinConfigWriterError.html).
+    pub error: BinConfigWriterError,
+}
+
+impl Error for BinConfigWriterPathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl Display for BinConfigWriterPathError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{} (at the root table)", self.error)
+        } else {
+            write!(f, "{} (at \"{}\")", self.error, self.path)
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use {
+    crate::*,
+    std::{borrow::Cow, collections::HashMap, num::NonZeroU32},
+};
+
+/// A value parsed from the `.ini` source, buffered by the [`event handler`](struct.BinConfigFromIni.html).
+enum BinIniValue<'s> {
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    String(Cow<'s, str>),
+    #[cfg(feature = "datetime")]
+    DateTime(crate::DateTime),
+    Array(Vec<BinIniValue<'s>>),
+    Table(BinIniTable<'s>),
+}
+
+impl<'s> BinIniValue<'s> {
+    fn new(value: IniValue<'s, '_>) -> Self {
+        match value {
+            IniValue::Bool(value) => BinIniValue::Bool(value),
+            IniValue::I64(value) => BinIniValue::I64(value),
+            IniValue::F64(value) => BinIniValue::F64(value),
+            IniValue::String(value) => BinIniValue::String(match value.as_borrowed() {
+                Some(value) => Cow::Borrowed(value.as_str()),
+                None => Cow::Owned(value.as_str().to_owned()),
+            }),
+            #[cfg(feature = "datetime")]
+            IniValue::DateTime(value) => BinIniValue::DateTime(value),
+        }
+    }
+
+    /// Writes the value with `key` (if it's a table element) recursively to the binary config `writer`.
+    fn write(
+        &self,
+        key: Option<&NonEmptyStr>,
+        writer: &mut BinConfigWriter,
+    ) -> Result<(), BinConfigWriterError> {
+        match self {
+            BinIniValue::Bool(value) => writer.bool(key, *value),
+            BinIniValue::I64(value) => writer.i64(key, *value),
+            BinIniValue::F64(value) => writer.f64(key, *value),
+            BinIniValue::String(value) => writer.string(key, value),
+            #[cfg(feature = "datetime")]
+            BinIniValue::DateTime(value) => writer.datetime(key, *value),
+            BinIniValue::Array(array) => {
+                writer.array(key, array.len() as _)?;
+
+                for value in array.iter() {
+                    value.write(None, writer)?;
+                }
+
+                writer.end()
+            }
+            BinIniValue::Table(table) => {
+                writer.table(key, table.len())?;
+                table.write(writer)?;
+                writer.end()
+            }
+        }
+    }
+}
+
+/// A section (or the root table) parsed from the `.ini` source, buffered by the [`event handler`](struct.BinConfigFromIni.html).
+/// Contains the values in the order they were parsed in, indexed by their keys.
+#[derive(Default)]
+struct BinIniTable<'s> {
+    // Removed values are `None` (until the key is set again), so that the indices remain valid.
+    values: Vec<(Cow<'s, NonEmptyStr>, Option<BinIniValue<'s>>)>,
+    // Indices of the `values` by their keys.
+    // Only the keys which could not be borrowed from the `.ini` source (i.e. contained escape sequences) are duplicated.
+    indices: HashMap<Cow<'s, NonEmptyStr>, usize>,
+    // Number of the values which are not removed.
+    len: u32,
+}
+
+impl<'s> BinIniTable<'s> {
+    fn len(&self) -> u32 {
+        self.len
+    }
+
+    fn get(&self, key: &NonEmptyStr) -> Option<&BinIniValue<'s>> {
+        let index = *self.indices.get(key)?;
+        self.values[index].1.as_ref()
+    }
+
+    fn remove(&mut self, key: &NonEmptyStr) -> Option<BinIniValue<'s>> {
+        let index = *self.indices.get(key)?;
+        let value = self.values[index].1.take()?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Adds the `key` / `value` pair to the table, replacing the previous value with the `key`, if any.
+    /// Returns `true` if the value with the `key` already existed.
+    fn set(&mut self, key: NonEmptyIniStr<'s, '_>, value: BinIniValue<'s>) -> bool {
+        let previous = if let Some(&index) = self.indices.get(key.as_ne_str()) {
+            self.values[index].1.replace(value)
+        } else {
+            let key = match key.as_borrowed() {
+                Some(key) => Cow::Borrowed(key),
+                None => Cow::Owned(key.as_ne_str().to_owned()),
+            };
+            self.indices.insert(key.clone(), self.values.len());
+            self.values.push((key, Some(value)));
+            None
+        };
+
+        if previous.is_none() {
+            self.len += 1;
+        }
+
+        previous.is_some()
+    }
+
+    /// Writes the table elements recursively to the binary config `writer`, in the order they were parsed in.
+    fn write(&self, writer: &mut BinConfigWriter) -> Result<(), BinConfigWriterError> {
+        for (key, value) in self.values.iter() {
+            if let Some(value) = value {
+                value.write(Some(key), writer)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An [`event handler`] which packs the `.ini` config to a [`binary config`] data blob
+/// in a single pass over the `.ini` source, without building a [`dynamic config`] in between.
+///
+/// As the [`binary config writer`] requires the number of elements of each [`table`] / [`array`] up front,
+/// the parsed values are buffered until the parsing is [`finished`], borrowing the section names / keys / string values
+/// directly from the `.ini` source where possible (see [`NonEmptyIniStr::as_borrowed`]).
+/// Values are recorded in the order they were parsed in, and overwritten values are discarded.
+///
+/// [`event handler`]: trait.IniConfig.html
+/// [`binary config`]: struct.BinConfig.html
+/// [`dynamic config`]: struct.DynConfig.html
+/// [`binary config writer`]: struct.BinConfigWriter.html
+/// [`table`]: struct.BinTable.html
+/// [`array`]: struct.BinArray.html
+/// [`finished`]: #method.finish
+/// [`NonEmptyIniStr::as_borrowed`]: enum.NonEmptyIniStr.html#method.as_borrowed
+#[derive(Default)]
+pub struct BinConfigFromIni<'s> {
+    root: BinIniTable<'s>,
+    current_section: Option<BinIniTable<'s>>,
+    // Never allocates if we don't support nested sections.
+    section_stack: Vec<BinIniTable<'s>>,
+    // Always `None` if we don't support arrays.
+    current_array: Option<Vec<BinIniValue<'s>>>,
+    // Array of sections which contains the current section, if any.
+    // Always `None` if we don't support arrays of sections.
+    section_array: Option<Vec<BinIniValue<'s>>>,
+}
+
+impl<'s> BinConfigFromIni<'s> {
+    /// Creates a new [`event handler`](struct.BinConfigFromIni.html) with an empty root table.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Consumes the [`event handler`](struct.BinConfigFromIni.html) and returns the recorded [`binary config`] data blob.
+    ///
+    /// NOTE - must only be called after the [`.ini parser`](struct.IniParser.html) successfully finished parsing.
+    ///
+    /// [`binary config`]: struct.BinConfig.html
+    pub fn finish(self) -> Result<Box<[u8]>, BinConfigWriterError> {
+        self.finish_with(|_| Ok(()))
+    }
+
+    /// Consumes the [`event handler`](struct.BinConfigFromIni.html) and returns the recorded [`binary config`] data blob.
+    /// Calls `configure` on the [`binary config writer`] before recording, e.g. to [`sort the keys`]
+    /// or to [`compress the keys`].
+    ///
+    /// NOTE - must only be called after the [`.ini parser`](struct.IniParser.html) successfully finished parsing.
+    ///
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`binary config writer`]: struct.BinConfigWriter.html
+    /// [`sort the keys`]: struct.BinConfigWriter.html#method.sort_keys
+    /// [`compress the keys`]: struct.BinConfigWriter.html#method.compress_keys
+    pub fn finish_with<F>(self, configure: F) -> Result<Box<[u8]>, BinConfigWriterError>
+    where
+        F: FnOnce(&mut BinConfigWriter) -> Result<(), BinConfigWriterError>,
+    {
+        debug_assert!(
+            self.current_section.is_none(),
+            "missing `end_section()` call"
+        );
+        debug_assert!(
+            self.section_stack.is_empty(),
+            "missing `end_section()` call"
+        );
+        debug_assert!(self.current_array.is_none(), "missing `end_array()` call");
+        debug_assert!(self.section_array.is_none(), "missing `end_section()` call");
+
+        let root_len =
+            NonZeroU32::new(self.root.len()).ok_or(BinConfigWriterError::EmptyRootTable)?;
+
+        let mut writer = BinConfigWriter::new(root_len)?;
+        configure(&mut writer)?;
+
+        self.root.write(&mut writer)?;
+
+        writer.finish()
+    }
+
+    fn current_section(&mut self) -> &mut BinIniTable<'s> {
+        self.current_section.as_mut().unwrap_or(&mut self.root)
+    }
+
+    /// Makes the `section` the current section, pushing the previous current section (if any) onto the section stack.
+    fn push_section(&mut self, section: BinIniTable<'s>) {
+        if let Some(current_section) = self.current_section.replace(section) {
+            self.section_stack.push(current_section);
+        }
+    }
+}
+
+impl<'s> IniConfig<'s> for BinConfigFromIni<'s> {
+    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        let table = self.current_section.as_ref().unwrap_or(&self.root);
+        table
+            .get(key.as_ne_str())
+            .map(|value| matches!(value, BinIniValue::Table(_)))
+    }
+
+    fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
+        let already_existed = self.current_section().set(key, BinIniValue::new(value));
+
+        debug_assert!(
+            overwrite == already_existed,
+            "overwrite flag mismatch when adding a value"
+        );
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        let previous = self.current_section().remove(section.as_ne_str());
+
+        debug_assert!(
+            !overwrite || previous.is_some(),
+            "overwrite flag mismatch when starting a section"
+        );
+
+        // Continue the previous section with this key in the parent section, unless we overwrite it.
+        // Else start a new section.
+        let section = match previous {
+            Some(BinIniValue::Table(previous)) if !overwrite => previous,
+            _ => BinIniTable::default(),
+        };
+
+        self.push_section(section);
+    }
+
+    fn contains_array_section(&self, key: NonEmptyIniStr<'s, '_>) -> bool {
+        let table = self.current_section.as_ref().unwrap_or(&self.root);
+        matches!(
+            table.get(key.as_ne_str()),
+            Some(BinIniValue::Array(array)) if matches!(array.first(), Some(BinIniValue::Table(_)))
+        )
+    }
+
+    fn start_array_section(&mut self, section: NonEmptyIniStr<'s, '_>, new: bool) {
+        debug_assert!(
+            self.section_array.is_none(),
+            "arrays of sections may not be nested"
+        );
+
+        // Take the previous array of sections with this key from the parent section, or start a new one.
+        let mut array = match self.current_section().remove(section.as_ne_str()) {
+            Some(BinIniValue::Array(array)) => array,
+            _ => Vec::new(),
+        };
+
+        // Start a new section or continue the last section in the array.
+        let table = if new {
+            None
+        } else {
+            match array.pop() {
+                Some(BinIniValue::Table(table)) => Some(table),
+                _ => None,
+            }
+        };
+
+        self.section_array.replace(array);
+        self.push_section(table.unwrap_or_default());
+    }
+
+    fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
+        if let Some(current_section) = self.current_section.take() {
+            // Add the current section to its array of sections, if any.
+            let current_section = match self.section_array.take() {
+                Some(mut section_array) => {
+                    section_array.push(BinIniValue::Table(current_section));
+                    BinIniValue::Array(section_array)
+                }
+                None => BinIniValue::Table(current_section),
+            };
+
+            self.current_section = self.section_stack.pop();
+
+            let already_existed = self.current_section().set(section, current_section);
+            debug_assert!(!already_existed);
+        } else {
+            debug_assert!(
+                false,
+                "`end_section()` call without a matching `start_section()`"
+            );
+        }
+    }
+
+    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        if overwrite {
+            let previous = self.current_section().remove(array.as_ne_str());
+            debug_assert!(
+                previous.is_some(),
+                "overwrite flag mismatch when starting an array"
+            );
+        }
+
+        debug_assert!(
+            self.current_array.is_none(),
+            "nested arrays are not supported"
+        );
+        self.current_array.replace(Vec::new());
+    }
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        if let Some(current_array) = self.current_array.as_mut() {
+            current_array.push(BinIniValue::new(value));
+        } else {
+            debug_assert!(
+                false,
+                "`add_array_value()` call without a matching `start_array()`"
+            );
+        }
+    }
+
+    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+        if let Some(current_array) = self.current_array.take() {
+            let already_existed = self
+                .current_section()
+                .set(array, BinIniValue::Array(current_array));
+            debug_assert!(!already_existed);
+        } else {
+            debug_assert!(
+                false,
+                "`end_array()` call without a matching `start_array()`"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::*;
+
+    #[test]
+    fn from_ini() {
+        let ini = r#"bool = true
+            int = 7
+            string = "foo"
+
+            [section]
+            float = 0.5
+            array = [1, 2, 3]
+            escaped = "bar\tbaz"
+
+            [other]
+            key = "value"
+
+            [section]
+            int = -9"#;
+
+        let mut config = BinConfigFromIni::new();
+        IniParser::new(ini)
+            .arrays(true)
+            .duplicate_sections(IniDuplicateSections::Merge)
+            .parse(&mut config)
+            .unwrap();
+        let config = BinConfig::new(config.finish().unwrap()).unwrap();
+
+        let root = config.root();
+        assert_eq!(root.len(), 5);
+        assert!(root.get_bool("bool").unwrap());
+        assert_eq!(root.get_i64("int").unwrap(), 7);
+        assert_eq!(root.get_string("string").unwrap(), "foo");
+
+        let section = root.get_table("section").unwrap();
+        assert_eq!(section.len(), 4);
+        assert_eq!(section.get_f64("float").unwrap(), 0.5);
+        let array = section.get_array("array").unwrap();
+        assert_eq!(array.len(), 3);
+        for index in 0..3 {
+            assert_eq!(array.get_i64(index).unwrap(), index as i64 + 1);
+        }
+        assert_eq!(section.get_string("escaped").unwrap(), "bar\tbaz");
+        assert_eq!(section.get_i64("int").unwrap(), -9);
+
+        let other = root.get_table("other").unwrap();
+        assert_eq!(other.get_string("key").unwrap(), "value");
+
+        // Empty root tables are not supported.
+        let mut config = BinConfigFromIni::new();
+        IniParser::new("; comment").parse(&mut config).unwrap();
+        assert_eq!(
+            config.finish().err().unwrap(),
+            BinConfigWriterError::EmptyRootTable
+        );
+    }
+
+    #[test]
+    fn from_ini_overwrite() {
+        let mut ini = String::new();
+
+        for index in 0..1000 {
+            ini.push_str(&format!("key{} = {}\n", index, index));
+        }
+
+        ini.push_str("key7 = \"seven\"\n[section]\na = 1\n[key9]\nb = 2\n[section]\nc = 3");
+
+        let mut config = BinConfigFromIni::new();
+        IniParser::new(&ini)
+            .duplicate_keys(IniDuplicateKeys::Last)
+            .duplicate_sections(IniDuplicateSections::Last)
+            .parse(&mut config)
+            .unwrap();
+        let config = BinConfig::new(config.finish().unwrap()).unwrap();
+
+        // Overwritten values / sections are discarded.
+        let root = config.root();
+        assert_eq!(root.len(), 1001);
+        assert_eq!(root.get_i64("key999").unwrap(), 999);
+        assert_eq!(root.get_string("key7").unwrap(), "seven");
+        assert_eq!(root.get_table("key9").unwrap().get_i64("b").unwrap(), 2);
+
+        let section = root.get_table("section").unwrap();
+        assert_eq!(section.len(), 1);
+        assert_eq!(section.get_i64("c").unwrap(), 3);
+    }
+}
@@ -0,0 +1,316 @@
+//! Compression of the binary config key table.
+//!
+//! Compressed key table layout (the data blob header has the `BIN_CONFIG_HEADER_FLAG_COMPRESSED_KEYS` flag set):
+//!
+//! |- header -|- values -|- key table -|- value strings -|- key suffixes -|
+//!
+//! - key strings in the key table are sorted (and table values' key indices are remapped accordingly),
+//! - each key table entry (same size as an `InternedString`) contains the length in bytes of the prefix
+//!   the key shares with the previous key in the key table, and the length in bytes of the rest of the key (its suffix),
+//! - key suffixes are stored, in key table order, at the very end of the data blob (not null-terminated),
+//! - the string section only contains the value strings.
+//!
+//! The key table is decompressed to the usual layout (the key strings are appended to the string section)
+//! when the data blob is loaded, so the rest of the data blob, including all value offsets, is unchanged.
+
+use {
+    super::{array_or_table::InternedString, config::*, util::*, value::BinConfigPackedValue},
+    crate::{util::unwrap_unchecked, *},
+    std::{collections::HashMap, mem::size_of},
+};
+
+/// Maximum ratio of the total size of the decompressed key strings (incl. the null terminators)
+/// to the size of the data blob with the compressed key table.
+///
+/// Prefix compression of sorted keys rarely achieves more than a fraction of this ratio;
+/// data blobs with larger key tables are rejected before anything is decompressed,
+/// as the key prefixes may be forged to decompress to a quadratic number of bytes.
+const MAX_KEY_DECOMPRESSION_RATIO: usize = 64;
+
+/// Compresses the key table of the valid uncompressed binary config `data` blob.
+pub(super) fn compress_keys(data: &[u8]) -> Box<[u8]> {
+    let header = unwrap_unchecked(
+        BinConfigHeader::read(data).ok(),
+        "invalid binary config header",
+    );
+    debug_assert!(!header.compressed_keys());
+
+    let key_table_offset = header.key_table_offset() as usize;
+    let key_table_len = header.key_table_len() as usize;
+    let string_offset = key_table_offset + header.key_table_size() as usize;
+
+    let keys: Vec<&[u8]> = (0..key_table_len)
+        .map(|index| {
+            let offset = read_u32(data, key_table_offset, 2 * index) as usize;
+            let len = read_u32(data, key_table_offset, 2 * index + 1) as usize;
+            &data[offset..offset + len]
+        })
+        .collect();
+
+    // Sort the keys, remap the key indices.
+    let mut order: Vec<u32> = (0..key_table_len as u32).collect();
+    order.sort_by_key(|&index| keys[index as usize]);
+
+    let mut remap = vec![0; key_table_len];
+
+    for (new_index, &old_index) in order.iter().enumerate() {
+        remap[old_index as usize] = new_index as u32;
+    }
+
+    // Header and values are unchanged, except the key indices and the value string offsets.
+    let mut result = Vec::with_capacity(data.len());
+    result.extend_from_slice(&data[..key_table_offset]);
+
+    // Key table.
+    let mut previous: &[u8] = &[];
+    let mut suffixes = Vec::new();
+
+    for &index in order.iter() {
+        let key = keys[index as usize];
+
+        let prefix_len = key
+            .iter()
+            .zip(previous.iter())
+            .take_while(|(l, r)| l == r)
+            .count();
+
+        result.extend_from_slice(&u32_to_bin_bytes(prefix_len as u32));
+        result.extend_from_slice(&u32_to_bin_bytes((key.len() - prefix_len) as u32));
+
+        suffixes.extend_from_slice(&key[prefix_len..]);
+
+        previous = key;
+    }
+
+    debug_assert_eq!(result.len(), string_offset);
+
+    // Value strings.
+    let mut strings = ValueStrings {
+        data,
+        string_offset: string_offset as u32,
+        strings: Vec::new(),
+        offsets: HashMap::new(),
+    };
+
+    let base = result.as_mut_ptr();
+
+    let root = unsafe {
+        std::slice::from_raw_parts_mut(
+            base.add(header.version().header_size() as _) as *mut BinConfigPackedValue,
+            header.len() as _,
+        )
+    };
+
+    compress_values(base, root, true, &remap, &mut strings);
+
+    result.append(&mut strings.strings);
+    result.append(&mut suffixes);
+
+    // Set the flag in the header.
+    if BinConfigHeader::write(
+        &mut &mut result[..],
+        header.version(),
+        header.flags() | BIN_CONFIG_HEADER_FLAG_COMPRESSED_KEYS,
+        header.len(),
+        header.key_table_offset(),
+        header.key_table_len(),
+    )
+    .is_err()
+    {
+        debug_unreachable!("writing to a slice failed");
+    }
+
+    result.into_boxed_slice()
+}
+
+/// Decompresses the key table of the binary config `data` blob with the `header`
+/// (with the `BIN_CONFIG_HEADER_FLAG_COMPRESSED_KEYS` flag set).
+///
+/// NOTE - only validates the compressed key table; the caller validates the rest of the returned data blob.
+pub(super) fn decompress_keys(
+    data: &[u8],
+    header: &BinConfigHeader,
+) -> Result<Box<[u8]>, BinConfigError> {
+    use BinConfigError::*;
+
+    debug_assert!(header.compressed_keys());
+
+    // Empty binary config root tables are not supported.
+    if header.len() == 0 {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let key_table_offset = header.key_table_offset() as usize;
+    let key_table_len = header.key_table_len() as usize;
+    let string_offset = key_table_offset
+        .checked_add(header.key_table_len() as usize * size_of::<InternedString>())
+        .ok_or(InvalidBinaryConfigData)?;
+
+    // Make sure the key table lies within the config data blob.
+    if key_table_offset < header.version().header_size() as usize || string_offset > data.len() {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    // Make sure the key suffixes lie within the config data blob.
+    let suffixes_len = (0..key_table_len).try_fold(0usize, |len, index| {
+        len.checked_add(read_u32(data, key_table_offset, 2 * index + 1) as usize)
+            .ok_or(InvalidBinaryConfigData)
+    })?;
+
+    if suffixes_len > data.len() - string_offset {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let mut suffixes_offset = data.len() - suffixes_len;
+
+    // Make sure the decompressed keys are not too large before allocating them.
+    let keys_len = (0..key_table_len).try_fold(0usize, |len, index| {
+        let prefix_len = read_u32(data, key_table_offset, 2 * index) as usize;
+        let suffix_len = read_u32(data, key_table_offset, 2 * index + 1) as usize;
+
+        len.checked_add(prefix_len + suffix_len + 1)
+            .ok_or(InvalidBinaryConfigData)
+    })?;
+
+    if keys_len > data.len().saturating_mul(MAX_KEY_DECOMPRESSION_RATIO)
+        || suffixes_offset + keys_len > u32::MAX as usize
+    {
+        return Err(InvalidBinaryConfigData);
+    }
+
+    let mut result = Vec::with_capacity(suffixes_offset + keys_len);
+    result.extend_from_slice(&data[..suffixes_offset]);
+
+    // Decompress the keys, append them to the string section.
+    let mut previous = 0..0;
+
+    for index in 0..key_table_len {
+        let prefix_len = read_u32(data, key_table_offset, 2 * index) as usize;
+        let suffix_len = read_u32(data, key_table_offset, 2 * index + 1) as usize;
+
+        // Prefix must be shared with the previous key.
+        if prefix_len > previous.len() {
+            return Err(InvalidBinaryConfigData);
+        }
+
+        let offset = result.len();
+
+        for prefix_index in previous.start..previous.start + prefix_len {
+            let byte = result[prefix_index];
+            result.push(byte);
+        }
+        result.extend_from_slice(&data[suffixes_offset..suffixes_offset + suffix_len]);
+        result.push(b'\0');
+
+        suffixes_offset += suffix_len;
+
+        let len = prefix_len + suffix_len;
+        previous = offset..offset + len;
+
+        let entry = key_table_offset + index * size_of::<InternedString>();
+        result[entry..entry + 4].copy_from_slice(&u32_to_bin_bytes(offset as u32));
+        result[entry + 4..entry + 8].copy_from_slice(&u32_to_bin_bytes(len as u32));
+    }
+
+    // Clear the flags in the header which do not apply to the decompressed data blob
+    // (the checksum was verified, the schema block, if any, was split off by the caller).
+    if BinConfigHeader::write(
+        &mut &mut result[..],
+        header.version(),
+        header.flags() & BIN_CONFIG_HEADER_VALUE_FLAGS,
+        header.len(),
+        header.key_table_offset(),
+        header.key_table_len(),
+    )
+    .is_err()
+    {
+        debug_unreachable!("writing to a slice failed");
+    }
+
+    Ok(result.into_boxed_slice())
+}
+
+/// Value strings of the compressed data blob.
+struct ValueStrings<'d> {
+    /// Uncompressed data blob.
+    data: &'d [u8],
+    /// Offset in bytes to the string section of the compressed data blob.
+    string_offset: u32,
+    /// String section of the compressed data blob (null-terminated value strings).
+    strings: Vec<u8>,
+    /// Maps value string offsets in the uncompressed data blob to offsets in the compressed data blob.
+    offsets: HashMap<u32, u32>,
+}
+
+impl<'d> ValueStrings<'d> {
+    /// Returns the offset in the compressed data blob of the value string
+    /// at `offset` with `len` in the uncompressed data blob.
+    fn relocate(&mut self, offset: u32, len: u32) -> u32 {
+        let Self {
+            data,
+            string_offset,
+            strings,
+            offsets,
+        } = self;
+
+        *offsets.entry(offset).or_insert_with(|| {
+            let new_offset = *string_offset + strings.len() as u32;
+
+            // Copy the string with the null terminator.
+            strings.extend_from_slice(&data[offset as usize..(offset + len + 1) as usize]);
+
+            new_offset
+        })
+    }
+}
+
+/// Remaps the key indices of the table `values` and relocates the value strings of the `values`
+/// and, recursively, their elements.
+fn compress_values(
+    base: *mut u8,
+    values: &mut [BinConfigPackedValue],
+    table: bool,
+    remap: &[u32],
+    strings: &mut ValueStrings<'_>,
+) {
+    for value in values.iter_mut() {
+        if table {
+            value.set_key_index(remap[value.key().index as usize]);
+        }
+
+        match value.value_type() {
+            // Empty strings have no data offset.
+            ValueType::String if value.len() > 0 => {
+                value.set_offset(strings.relocate(value.offset(), value.len()));
+            }
+            #[cfg(feature = "datetime")]
+            ValueType::DateTime => {
+                value.set_offset(strings.relocate(value.offset(), value.len()));
+            }
+            // Empty arrays / tables have no data offset.
+            // Arrays with elements stored as `f32`'s have no nested values.
+            value_type @ ValueType::Array | value_type @ ValueType::Table
+                if value.len() > 0 && !value.is_f32_array() =>
+            {
+                let values = unsafe {
+                    std::slice::from_raw_parts_mut(
+                        base.add(value.offset() as _) as *mut BinConfigPackedValue,
+                        value.len() as _,
+                    )
+                };
+
+                compress_values(base, values, value_type == ValueType::Table, remap, strings);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads the `u32` at `index` from the `data` blob at `offset`.
+/// The caller ensures the data is large enough.
+fn read_u32(data: &[u8], offset: usize, index: usize) -> u32 {
+    let offset = offset + index * size_of::<u32>();
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&data[offset..offset + size_of::<u32>()]);
+    u32_from_bin(u32::from_ne_bytes(bytes))
+}
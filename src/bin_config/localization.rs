@@ -0,0 +1,268 @@
+//! Localization of the binary config string values.
+//!
+//! Each distinct string value of the binary config data blob has a string ID -
+//! its index in the order of first occurrence when visiting the values depth-first, starting with the root table elements
+//! (table elements are visited in data blob order).
+//! Date/time values and table keys are not string values and have no string IDs.
+//!
+//! String IDs only depend on the structure and the string values of the data blob,
+//! so that a single data blob may be localized at load time by remapping the string IDs to localized strings.
+//! Localized strings are appended to the string section, and the string values' offsets are relocated to point to them.
+
+use {
+    super::{config::*, schema::*, util::*, value::BinConfigPackedValue},
+    crate::{value::*, *},
+    std::{collections::HashMap, iter::FromIterator, mem::size_of, str::from_utf8_unchecked},
+};
+
+/// Maps the string IDs of the [`binary config`] string values to localized strings.
+///
+/// See [`BinConfig::string_table`] for the description of the string IDs.
+///
+/// [`binary config`]: struct.BinConfig.html
+/// [`BinConfig::string_table`]: struct.BinConfig.html#method.string_table
+#[derive(Clone, Default, Debug)]
+pub struct BinConfigLocalization(HashMap<u32, String>);
+
+impl BinConfigLocalization {
+    /// Creates a new empty [`localization table`](struct.BinConfigLocalization.html).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the localized `string` for the string value with string `id`.
+    /// Returns the previous localized string for the string `id`, if any.
+    pub fn set<S: Into<String>>(&mut self, id: u32, string: S) -> Option<String> {
+        self.0.insert(id, string.into())
+    }
+
+    /// Returns the localized string for the string value with string `id`, if any.
+    pub fn get(&self, id: u32) -> Option<&str> {
+        self.0.get(&id).map(String::as_str)
+    }
+
+    /// Returns the number of localized strings in the [`localization table`](struct.BinConfigLocalization.html).
+    pub fn len(&self) -> u32 {
+        self.0.len() as _
+    }
+
+    /// Returns `true` if the [`localization table`](struct.BinConfigLocalization.html) contains no localized strings.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Creates a [`localization table`](struct.BinConfigLocalization.html) from the localized strings in string ID order,
+/// e.g. a translated [`string table`](struct.BinConfig.html#method.string_table).
+impl<S: Into<String>> FromIterator<S> for BinConfigLocalization {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .enumerate()
+                .map(|(id, string)| (id as u32, string.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Returns the distinct string values of the valid binary config `data` blob with the `header`, in string ID order.
+pub(super) fn string_table<'d>(data: &'d [u8], header: &BinConfigHeader) -> Vec<&'d str> {
+    let mut strings = StringIds::default();
+
+    visit_strings(
+        data,
+        header.version().header_size(),
+        header.len(),
+        &mut |string, _| {
+            strings.id(string);
+        },
+    );
+
+    strings.strings
+}
+
+/// Returns the valid binary config `data` blob with the `header`,
+/// with the string values remapped to the localized strings in the `localization` table.
+pub(super) fn localize(
+    data: &[u8],
+    header: &BinConfigHeader,
+    localization: &BinConfigLocalization,
+) -> Result<Box<[u8]>, BinConfigError> {
+    // Localized strings are appended to the string section, which is followed by the schema block, if any.
+    let (data, schema) = split_schema(data, header)?;
+
+    let mut strings = StringIds::default();
+    // Localized strings' offsets and lengths, by string ID.
+    let mut localized = HashMap::<u32, (u32, u32)>::new();
+    let mut result = data.to_vec();
+
+    let mut relocations = Vec::new();
+
+    visit_strings(
+        data,
+        header.version().header_size(),
+        header.len(),
+        &mut |string, value_offset| {
+            let id = strings.id(string);
+
+            if let Some(string) = localization.get(id) {
+                let offset_and_len = *localized.entry(id).or_insert_with(|| {
+                    if string.is_empty() {
+                        (0, 0)
+                    } else {
+                        let offset = result.len() as u32;
+                        result.extend_from_slice(string.as_bytes());
+                        // NOTE - strings are null-terminated just in case.
+                        result.push(b'\0');
+                        (offset, string.len() as u32)
+                    }
+                });
+
+                relocations.push((value_offset, offset_and_len));
+            }
+        },
+    );
+
+    if result.len() + schema.map_or(0, |schema| schema.len() + size_of::<u32>()) > u32::MAX as usize
+    {
+        return Err(BinConfigError::InvalidBinaryConfigData);
+    }
+
+    for (value_offset, (offset, len)) in relocations.into_iter() {
+        let value = unsafe {
+            &mut *(result.as_mut_ptr().add(value_offset as _) as *mut BinConfigPackedValue)
+        };
+        value.set_offset_and_len(offset, len);
+    }
+
+    if let Some(schema) = schema {
+        result.extend_from_slice(schema);
+        result.extend_from_slice(&u32_to_bin_bytes(schema.len() as u32));
+    }
+
+    // The checksum of the original data blob, if any, does not match the localized data blob.
+    BinConfigHeader::clear_checksum(&mut result);
+
+    Ok(result.into_boxed_slice())
+}
+
+/// Assigns string IDs to the distinct strings.
+#[derive(Default)]
+struct StringIds<'d> {
+    ids: HashMap<&'d str, u32>,
+    strings: Vec<&'d str>,
+}
+
+impl<'d> StringIds<'d> {
+    /// Returns the string ID of the `string`, assigning the next string ID to it if it was not seen before.
+    fn id(&mut self, string: &'d str) -> u32 {
+        let strings = &mut self.strings;
+
+        *self.ids.entry(string).or_insert_with(|| {
+            strings.push(string);
+            strings.len() as u32 - 1
+        })
+    }
+}
+
+/// Calls `f` with each string value and its packed value offset in the valid binary config `data` blob,
+/// recursively visiting the `len` array / table values at `offset` depth-first.
+fn visit_strings<'d, F: FnMut(&'d str, u32)>(data: &'d [u8], offset: u32, len: u32, f: &mut F) {
+    for (index, value) in values(data, offset, len).iter().enumerate() {
+        match value.value_type() {
+            ValueType::String => {
+                let start = value.offset() as usize;
+                let end = start + value.len() as usize;
+
+                // Safe to call - the strings were validated.
+                f(
+                    unsafe { from_utf8_unchecked(&data[start..end]) },
+                    offset + (index * size_of::<BinConfigPackedValue>()) as u32,
+                );
+            }
+            // Arrays with elements stored as `f32`'s have no nested values.
+            ValueType::Array | ValueType::Table if !value.is_f32_array() => {
+                visit_strings(data, value.offset(), value.len(), f);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(all(test, feature = "dyn"))]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr, std::num::NonZeroU32};
+
+    #[test]
+    fn localize() {
+        let config = dyn_config! {
+            title: "Hello",
+            menu: { start: "Start", quit: "Quit", font: "arial.ttf" },
+            buttons: ["Start", "Quit"],
+            width: 1920,
+        };
+
+        let config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+
+        let string_table = config.string_table();
+        assert_eq!(string_table.len(), 4);
+
+        let id = |string: &str| string_table.iter().position(|s| *s == string).unwrap() as u32;
+
+        let mut localization = BinConfigLocalization::new();
+        assert!(localization.set(id("Hello"), "Bonjour").is_none());
+        assert!(localization.set(id("Start"), "Démarrer").is_none());
+        assert!(localization.set(id("Quit"), "").is_none());
+        assert_eq!(localization.len(), 3);
+
+        let localized = config.localized(&localization).unwrap();
+
+        let root = localized.root();
+        assert_eq!(root.get_string("title").unwrap(), "Bonjour");
+        assert_eq!(
+            root.get_string_path(&["menu".into(), "start".into()])
+                .unwrap(),
+            "Démarrer"
+        );
+        assert_eq!(
+            root.get_string_path(&["menu".into(), "quit".into()])
+                .unwrap(),
+            ""
+        );
+        assert_eq!(
+            root.get_string_path(&["menu".into(), "font".into()])
+                .unwrap(),
+            "arial.ttf"
+        );
+        assert_eq!(
+            root.get_string_path(&["buttons".into(), 0.into()]).unwrap(),
+            "Démarrer"
+        );
+        assert_eq!(
+            root.get_string_path(&["buttons".into(), 1.into()]).unwrap(),
+            ""
+        );
+        assert_eq!(root.get_i64("width").unwrap(), 1920);
+
+        // Localize at load time; the checksum of the original data blob is cleared.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.checksum(true).unwrap();
+        writer.embed_schema(true).unwrap();
+        writer.string(nestr!("title"), "Hello").unwrap();
+        let data = writer.finish().unwrap();
+
+        let localization: BinConfigLocalization = BinConfig::new(data.clone())
+            .unwrap()
+            .string_table()
+            .iter()
+            .map(|string| string.to_uppercase())
+            .collect();
+
+        let localized = BinConfig::with_localization(data, &localization).unwrap();
+        assert_eq!(localized.root().get_string("title").unwrap(), "HELLO");
+        assert!(localized.schema().is_some());
+        assert_eq!(localized.string_table(), vec!["HELLO"]);
+    }
+}
@@ -0,0 +1,210 @@
+use {
+    super::util::to_array_error,
+    crate::{util::*, *},
+};
+
+impl<'t> BinTable<'t> {
+    /// Tries to get a 2-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `2` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec2<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<[f32; 2], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get a 3-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `3` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec3<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<[f32; 3], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get a 4-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `4` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec4<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<[f32; 4], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get an RGBA color [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// The color is either a `#rrggbb` / `#rrggbbaa` hex [`string`],
+    /// or a numeric [`array`] with `3` (RGB, alpha is `1.0`) or `4` (RGBA) elements.
+    /// Channels of hex [`strings`] are converted to `[0.0, 1.0]` range; [`array`] elements are returned as is.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`] or an [`array`],
+    /// or if it is not a valid color.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`strings`]: enum.Value.html#variant.String
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_color<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<[f32; 4], GetVecError> {
+        match self.get_val(key).ok_or(TableError::KeyDoesNotExist)? {
+            Value::String(color) => parse_color(color),
+            Value::Array(array) => color_from_values(array.len(), array.iter()),
+            value => Err(TableError::IncorrectValueType(value.get_type()).into()),
+        }
+    }
+
+    fn get_vec<'k, K: IntoTableKey<'k>, const N: usize>(
+        &self,
+        key: K,
+    ) -> Result<[f32; N], GetVecError> {
+        let array = self.get_array(key)?;
+        vec_from_values(array.len(), array.iter())
+    }
+}
+
+impl<'a> BinArray<'a> {
+    /// Tries to get a 2-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `2` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.BinArray.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec2(&self, index: u32) -> Result<[f32; 2], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get a 3-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `3` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.BinArray.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec3(&self, index: u32) -> Result<[f32; 3], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get a 4-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `4` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.BinArray.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec4(&self, index: u32) -> Result<[f32; 4], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get an RGBA color [`value`] in the [`array`] at `index`.
+    ///
+    /// See [`BinTable::get_color`] for supported color representations.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`] or an [`array`],
+    /// or if it is not a valid color.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`BinTable::get_color`]: struct.BinTable.html#method.get_color
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_color(&self, index: u32) -> Result<[f32; 4], GetVecError> {
+        match self.get_val(index).map_err(to_array_error)? {
+            Value::String(color) => parse_color(color),
+            Value::Array(array) => color_from_values(array.len(), array.iter()),
+            value => Err(ArrayError::IncorrectValueType(value.get_type()).into()),
+        }
+    }
+
+    fn get_vec<const N: usize>(&self, index: u32) -> Result<[f32; N], GetVecError> {
+        let array = self.get_array(index).map_err(to_array_error)?;
+        vec_from_values(array.len(), array.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr, std::num::NonZeroU32};
+
+    #[test]
+    fn get_vec() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(4).unwrap()).unwrap();
+
+        writer.array(nestr!("position"), 3).unwrap();
+        writer.f64(None, 1.0).unwrap();
+        writer.i64(None, 2).unwrap();
+        writer.f64(None, -3.5).unwrap();
+        writer.end().unwrap();
+
+        writer.string(nestr!("hex"), "#ff8000").unwrap();
+        writer.bool(nestr!("bool"), true).unwrap();
+
+        writer.array(nestr!("positions"), 1).unwrap();
+        writer.array(None, 2).unwrap();
+        writer.f64(None, 0.5).unwrap();
+        writer.f64(None, 0.25).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+        let root = config.root();
+
+        assert_eq!(root.get_vec3("position").unwrap(), [1.0, 2.0, -3.5]);
+        assert_eq!(
+            root.get_vec4("position").err().unwrap(),
+            GetVecError::InvalidLength(3)
+        );
+        assert_eq!(root.get_color("position").unwrap(), [1.0, 2.0, -3.5, 1.0]);
+        assert_eq!(
+            root.get_color("hex").unwrap(),
+            [1.0, 128.0 / 255.0, 0.0, 1.0]
+        );
+        assert_eq!(
+            root.get_color("bool").err().unwrap(),
+            GetVecError::Table(TableError::IncorrectValueType(ValueType::Bool))
+        );
+
+        let positions = root.get_array("positions").unwrap();
+
+        assert_eq!(positions.get_vec2(0).unwrap(), [0.5, 0.25]);
+        assert_eq!(
+            positions.get_vec2(1).err().unwrap(),
+            GetVecError::Array(ArrayError::IndexOutOfBounds(1))
+        );
+    }
+}
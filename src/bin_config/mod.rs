@@ -1,12 +1,40 @@
+mod access;
 mod array;
 mod array_or_table;
+#[cfg(feature = "audit")]
+mod audit;
+mod codec;
+mod compression;
 mod config;
 mod error;
+#[cfg(feature = "ini")]
+mod from_ini;
+mod keys;
+mod localization;
+#[cfg(feature = "math")]
+mod math;
+mod project;
+mod schema;
+mod stats;
 mod table;
+mod typed;
+mod units;
 mod util;
 mod value;
+mod walk;
 mod writer;
 
-pub(crate) use util::string_hash_fnv1a;
+pub use {
+    array::*, codec::BinConfigCodec, config::*, error::*, localization::BinConfigLocalization,
+    schema::BinConfigSchema, stats::BinConfigStats, table::*, value::*, walk::BinConfigWalk,
+    writer::*,
+};
 
-pub use {array::*, config::*, error::*, table::*, value::*, writer::*};
+#[cfg(feature = "ini")]
+pub use from_ini::BinConfigFromIni;
+
+#[cfg(feature = "audit")]
+pub use audit::{ConfigAccess, ConfigAuditHook};
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+pub use compression::CompressionLevel;
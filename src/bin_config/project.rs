@@ -0,0 +1,178 @@
+use {
+    crate::{util::*, *},
+    std::num::NonZeroU32,
+};
+
+impl BinConfig {
+    /// Serializes the elements of this [`config`] at the [`paths`], and their parent [`tables`] / [`arrays`],
+    /// to a new [`binary config`] data blob, using the default [`writer`] options.
+    /// Elements at the [`paths`] are copied with all their contents; [`paths`] which do not exist in this [`config`] are ignored.
+    ///
+    /// Same as [`DynConfig::project`], without converting the [`config`] to a [`dynamic config`].
+    ///
+    /// NOTE - [`array`] elements not at any of the [`paths`] are skipped,
+    /// i.e. the indices of the copied [`array`] elements may change.
+    ///
+    /// Returns an [`EmptyRootTable`] error if none of the [`paths`] exist in this [`config`].
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`dynamic config`]: struct.DynConfig.html
+    /// [`paths`]: struct.ConfigPath.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`arrays`]: struct.BinArray.html
+    /// [`array`]: struct.BinArray.html
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`DynConfig::project`]: struct.DynConfig.html#method.project
+    /// [`EmptyRootTable`]: enum.BinConfigWriterError.html#variant.EmptyRootTable
+    pub fn project(&self, paths: &[ConfigPath]) -> Result<Box<[u8]>, BinConfigWriterError> {
+        let paths: Vec<_> = paths.iter().map(|path| path.0.as_slice()).collect();
+
+        // The root table itself was projected.
+        let paths = if paths.iter().any(|path| path.is_empty()) {
+            vec![&[][..]]
+        } else {
+            paths
+        };
+
+        let root = project_table(self.root(), &paths);
+
+        let root_len =
+            NonZeroU32::new(root.len() as u32).ok_or(BinConfigWriterError::EmptyRootTable)?;
+
+        let mut writer = BinConfigWriter::new(root_len)?;
+
+        write_table(root, &mut writer)?;
+
+        writer.finish()
+    }
+}
+
+/// (Remaining) paths to the projected elements of a value.
+/// An empty path means the whole value is projected.
+type Paths<'p> = Vec<&'p [OwnedConfigKey]>;
+
+/// Returns the elements of the `table` (with their remaining paths) which contain any of the projected elements.
+fn project_table<'t, 'p>(
+    table: BinTable<'t>,
+    paths: &[&'p [OwnedConfigKey]],
+) -> Vec<(&'t NonEmptyStr, BinConfigValue<'t>, Paths<'p>)> {
+    table
+        .iter()
+        .filter_map(|(key, value)| {
+            let paths = sub_paths(paths, |path| path.matches_key(key.as_str()));
+
+            if is_projected(&value, &paths) {
+                Some((key, value, paths))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Returns the elements of the `array` (with their remaining paths) which contain any of the projected elements.
+fn project_array<'a, 'p>(
+    array: BinArray<'a>,
+    paths: &[&'p [OwnedConfigKey]],
+) -> Vec<(BinConfigValue<'a>, Paths<'p>)> {
+    array
+        .iter()
+        .enumerate()
+        .filter_map(|(index, value)| {
+            let paths = sub_paths(paths, |path| path.matches_index(index as u32));
+
+            if is_projected(&value, &paths) {
+                Some((value, paths))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Returns the remaining `paths` of the element of a table / array matched by `matches`.
+/// Elements of the whole projected tables / arrays are projected as a whole too.
+fn sub_paths<'p, F>(paths: &[&'p [OwnedConfigKey]], matches: F) -> Paths<'p>
+where
+    F: Fn(&OwnedConfigKey) -> bool,
+{
+    if paths.iter().any(|path| path.is_empty()) {
+        vec![&[][..]]
+    } else {
+        sub_patterns(paths, matches)
+    }
+}
+
+/// Returns `true` if the `value` is, or contains, any of the projected elements.
+fn is_projected(value: &BinConfigValue<'_>, paths: &[&[OwnedConfigKey]]) -> bool {
+    if paths.is_empty() {
+        return false;
+    }
+
+    if paths.iter().any(|path| path.is_empty()) {
+        return true;
+    }
+
+    match value {
+        Value::Table(table) => table.iter().any(|(key, value)| {
+            is_projected(
+                &value,
+                &sub_patterns(paths, |path| path.matches_key(key.as_str())),
+            )
+        }),
+        Value::Array(array) => array.iter().enumerate().any(|(index, value)| {
+            is_projected(
+                &value,
+                &sub_patterns(paths, |path| path.matches_index(index as u32)),
+            )
+        }),
+        _ => false,
+    }
+}
+
+fn write_table(
+    elements: Vec<(&NonEmptyStr, BinConfigValue<'_>, Paths<'_>)>,
+    writer: &mut BinConfigWriter,
+) -> Result<(), BinConfigWriterError> {
+    for (key, value, paths) in elements {
+        write_value(Some(key), value, &paths, writer)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the projected elements of the `value` with `key` recursively to the binary config writer.
+fn write_value(
+    key: Option<&NonEmptyStr>,
+    value: BinConfigValue<'_>,
+    paths: &[&[OwnedConfigKey]],
+    writer: &mut BinConfigWriter,
+) -> Result<(), BinConfigWriterError> {
+    match value {
+        Value::Bool(value) => writer.bool(key, value),
+        Value::I64(value) => writer.i64(key, value),
+        Value::F64(value) => writer.f64(key, value),
+        Value::String(value) => writer.string(key, value),
+        #[cfg(feature = "datetime")]
+        Value::DateTime(value) => writer.datetime(key, value),
+        Value::Array(array) => {
+            let elements = project_array(array, paths);
+
+            writer.array(key, elements.len() as u32)?;
+
+            for (value, paths) in elements {
+                write_value(None, value, &paths, writer)?;
+            }
+
+            writer.end()
+        }
+        Value::Table(table) => {
+            let elements = project_table(table, paths);
+
+            writer.table(key, elements.len() as u32)?;
+            write_table(elements, writer)?;
+            writer.end()
+        }
+    }
+}
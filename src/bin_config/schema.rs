@@ -0,0 +1,281 @@
+//! Schema block of the binary config data blob.
+//!
+//! Data blob layout with the schema block (the data blob header has the `BIN_CONFIG_HEADER_FLAG_SCHEMA` flag set):
+//!
+//! |- header -|- values -|- key table -|- strings -|- schema block -|- schema block size (u32) -|
+//!
+//! The schema block describes each value of the config in depth-first order, starting with the root table elements:
+//!
+//! |- value type (u8) -|- key index (u32), table elements only -|- length (u32), arrays / tables only -|
+//!
+//! Array / table descriptors are followed by the descriptors of their elements.
+//! Key indices refer to the key table of the data blob.
+
+use {
+    super::{array_or_table::InternedString, config::*, util::*, value::BinConfigPackedValue},
+    crate::{util::unwrap_unchecked, value::*, *},
+    std::{mem::size_of, str::from_utf8_unchecked},
+};
+
+/// Describes the structure of a [`binary config`] value - its key, [`type`] and nested values,
+/// as recorded in the schema block [`embedded`] in the binary config data blob.
+///
+/// [`binary config`]: struct.BinConfig.html
+/// [`type`]: enum.ValueType.html
+/// [`embedded`]: struct.BinConfigWriter.html#method.embed_schema
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BinConfigSchema {
+    /// Key of the value in its parent table.
+    /// `None` for array elements and the root table.
+    pub key: Option<NonEmptyString>,
+    /// Value [`type`](enum.ValueType.html).
+    pub value_type: ValueType,
+    /// Schemas of the array / table elements, in order. Empty for other value types.
+    pub children: Vec<BinConfigSchema>,
+}
+
+/// Appends the schema block to the valid `V3` binary config `data` blob (possibly with a compressed key table)
+/// and sets the `BIN_CONFIG_HEADER_FLAG_SCHEMA` flag in its header.
+pub(super) fn write_schema(data: &[u8]) -> Box<[u8]> {
+    let header = unwrap_unchecked(
+        BinConfigHeader::read(data).ok(),
+        "invalid binary config header",
+    );
+    debug_assert!(!header.has_schema());
+
+    let mut result = data.to_vec();
+
+    write_values(data, root_values(data, &header), true, &mut result);
+
+    let schema_size = (result.len() - data.len()) as u32;
+    result.extend_from_slice(&u32_to_bin_bytes(schema_size));
+
+    BinConfigHeader::write_flags(&mut result, header.flags() | BIN_CONFIG_HEADER_FLAG_SCHEMA);
+
+    result.into_boxed_slice()
+}
+
+/// Splits the binary config `data` blob with the `header` into the data blob without the schema block and the schema block, if any.
+pub(super) fn split_schema<'d>(
+    data: &'d [u8],
+    header: &BinConfigHeader,
+) -> Result<(&'d [u8], Option<&'d [u8]>), BinConfigError> {
+    if !header.has_schema() {
+        return Ok((data, None));
+    }
+
+    let header_size = header.version().header_size() as usize;
+
+    if data.len() < header_size + size_of::<u32>() {
+        return Err(BinConfigError::InvalidBinaryConfigData);
+    }
+
+    let schema_end = data.len() - size_of::<u32>();
+    let schema_size = read_u32(&data[schema_end..]) as usize;
+
+    if schema_size > schema_end - header_size {
+        return Err(BinConfigError::InvalidBinaryConfigData);
+    }
+
+    let schema_start = schema_end - schema_size;
+
+    Ok((&data[..schema_start], Some(&data[schema_start..schema_end])))
+}
+
+/// Makes sure the `schema` block matches the values of the validated binary config `data` blob (without the schema block) with the `header`.
+pub(super) fn validate_schema(
+    schema: &[u8],
+    data: &[u8],
+    header: &BinConfigHeader,
+) -> Result<(), BinConfigError> {
+    let mut reader = SchemaReader(schema);
+
+    if validate_values(&mut reader, data, root_values(data, header), true) && reader.0.is_empty() {
+        Ok(())
+    } else {
+        Err(BinConfigError::InvalidBinaryConfigData)
+    }
+}
+
+/// Reads the validated `schema` block of the validated binary config `data` blob (without the schema block) with the `header`.
+pub(super) fn read_schema(schema: &[u8], data: &[u8], header: &BinConfigHeader) -> BinConfigSchema {
+    let mut reader = SchemaReader(schema);
+
+    let children = (0..header.len())
+        .map(|_| read_value(&mut reader, data, header, true))
+        .collect();
+
+    debug_assert!(reader.0.is_empty());
+
+    BinConfigSchema {
+        key: None,
+        value_type: ValueType::Table,
+        children,
+    }
+}
+
+/// Returns the root table values of the valid binary config `data` blob with the `header`.
+fn root_values<'d>(data: &'d [u8], header: &BinConfigHeader) -> &'d [BinConfigPackedValue] {
+    values(data, header.version().header_size(), header.len())
+}
+
+/// Returns the `len` array / table values at `offset` in the valid binary config `data` blob.
+pub(super) fn values(data: &[u8], offset: u32, len: u32) -> &[BinConfigPackedValue] {
+    if len == 0 {
+        return &[];
+    }
+
+    unsafe {
+        std::slice::from_raw_parts(
+            data.as_ptr().add(offset as _) as *const BinConfigPackedValue,
+            len as _,
+        )
+    }
+}
+
+/// Whether the value of `value_type` has nested values.
+fn is_array_or_table(value_type: ValueType) -> bool {
+    matches!(value_type, ValueType::Array | ValueType::Table)
+}
+
+/// Appends the descriptors of the (table, if `table` is `true`) `values` and, recursively, their elements to the `schema` block.
+fn write_values(data: &[u8], values: &[BinConfigPackedValue], table: bool, schema: &mut Vec<u8>) {
+    for value in values.iter() {
+        let value_type = value.value_type();
+
+        schema.push(value_type_to_u32(value_type) as u8);
+
+        if table {
+            schema.extend_from_slice(&u32_to_bin_bytes(value.key().index));
+        }
+
+        if is_array_or_table(value_type) {
+            schema.extend_from_slice(&u32_to_bin_bytes(value.len()));
+
+            // Elements stored as `f32`'s are described as `f64`'s.
+            if value.is_f32_array() {
+                for _ in 0..value.len() {
+                    schema.push(value_type_to_u32(ValueType::F64) as u8);
+                }
+
+                continue;
+            }
+
+            write_values(
+                data,
+                self::values(data, value.offset(), value.len()),
+                value_type == ValueType::Table,
+                schema,
+            );
+        }
+    }
+}
+
+/// Returns `true` if the descriptors in the schema `reader` match the (table, if `table` is `true`) `values` and, recursively, their elements.
+fn validate_values(
+    reader: &mut SchemaReader<'_>,
+    data: &[u8],
+    values: &[BinConfigPackedValue],
+    table: bool,
+) -> bool {
+    values.iter().all(|value| {
+        let value_type = value.value_type();
+
+        if reader.value_type() != Some(value_type) {
+            return false;
+        }
+
+        if table && reader.u32() != Some(value.key().index) {
+            return false;
+        }
+
+        if value.is_f32_array() {
+            reader.u32() == Some(value.len())
+                && (0..value.len()).all(|_| reader.value_type() == Some(ValueType::F64))
+        } else if is_array_or_table(value_type) {
+            reader.u32() == Some(value.len())
+                && validate_values(
+                    reader,
+                    data,
+                    self::values(data, value.offset(), value.len()),
+                    value_type == ValueType::Table,
+                )
+        } else {
+            true
+        }
+    })
+}
+
+/// Reads the descriptor of the (table element, if `table` is `true`) value and, recursively, its elements from the validated schema `reader`.
+fn read_value(
+    reader: &mut SchemaReader<'_>,
+    data: &[u8],
+    header: &BinConfigHeader,
+    table: bool,
+) -> BinConfigSchema {
+    let value_type = reader.value_type().expect("invalid binary config schema");
+
+    let key = if table {
+        let index = reader.u32().expect("invalid binary config schema");
+        Some(key_string(data, header, index).into())
+    } else {
+        None
+    };
+
+    let children = if is_array_or_table(value_type) {
+        let len = reader.u32().expect("invalid binary config schema");
+
+        (0..len)
+            .map(|_| read_value(reader, data, header, value_type == ValueType::Table))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    BinConfigSchema {
+        key,
+        value_type,
+        children,
+    }
+}
+
+/// Returns the key string at `index` in the key table of the validated binary config `data` blob with the `header`.
+fn key_string<'d>(data: &'d [u8], header: &BinConfigHeader, index: u32) -> &'d NonEmptyStr {
+    let entry = header.key_table_offset() as usize + index as usize * size_of::<InternedString>();
+
+    let offset = read_u32(&data[entry..]) as usize;
+    let len = read_u32(&data[entry + size_of::<u32>()..]) as usize;
+
+    // Safe to call - the key strings were validated.
+    NonEmptyStr::new(unsafe { from_utf8_unchecked(&data[offset..offset + len]) })
+        .expect("empty key")
+}
+
+/// Reads the `u32` at the start of the `data`.
+/// The caller ensures the data is large enough.
+fn read_u32(data: &[u8]) -> u32 {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&data[..size_of::<u32>()]);
+    u32_from_bin(u32::from_ne_bytes(bytes))
+}
+
+/// Reads the value descriptors from the schema block.
+struct SchemaReader<'s>(&'s [u8]);
+
+impl<'s> SchemaReader<'s> {
+    fn value_type(&mut self) -> Option<ValueType> {
+        let (&value_type, rest) = self.0.split_first()?;
+        self.0 = rest;
+        value_type_from_u32(value_type as u32)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        if self.0.len() < size_of::<u32>() {
+            return None;
+        }
+
+        let (value, rest) = self.0.split_at(size_of::<u32>());
+        self.0 = rest;
+        Some(read_u32(value))
+    }
+}
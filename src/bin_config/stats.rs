@@ -0,0 +1,88 @@
+use {
+    super::{config::*, schema::split_schema},
+    crate::*,
+};
+
+/// Statistics of the contents of a binary config - see [`ConfigStats`] -
+/// and the breakdown of the packed size of its data blob per section.
+///
+/// Returned by [`BinConfig::stats`].
+///
+/// NOTE - sizes refer to the loaded binary config data blob,
+/// i.e. after the payload is decoded / decompressed and the key table is unpacked, if necessary.
+///
+/// [`ConfigStats`]: struct.ConfigStats.html
+/// [`BinConfig::stats`]: struct.BinConfig.html#method.stats
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BinConfigStats {
+    /// Statistics of the contents of the binary config.
+    pub config: ConfigStats,
+    /// Size in bytes of the data blob header.
+    pub header_size: u32,
+    /// Size in bytes of the packed values (including the packed nested tables / arrays
+    /// and the typed copies of the numeric arrays, if any).
+    pub values_size: u32,
+    /// Size in bytes of the key table.
+    pub key_table_size: u32,
+    /// Size in bytes of the string section (key and string value strings, including their null terminators).
+    pub strings_size: u32,
+    /// Size in bytes of the embedded [`schema`] (including its size), or `0` if none.
+    ///
+    /// [`schema`]: struct.BinConfigSchema.html
+    pub schema_size: u32,
+}
+
+impl BinConfigStats {
+    /// Returns the total size in bytes of the binary config data blob.
+    pub fn total_size(&self) -> u32 {
+        self.header_size
+            + self.values_size
+            + self.key_table_size
+            + self.strings_size
+            + self.schema_size
+    }
+}
+
+impl BinConfig {
+    /// Collects the [`statistics`] of the contents of the [`config`]
+    /// and the breakdown of the packed size of its data blob.
+    ///
+    /// NOTE - visits all values in the [`config`] - see [`walk`].
+    ///
+    /// [`statistics`]: struct.BinConfigStats.html
+    /// [`config`]: struct.BinConfig.html
+    /// [`walk`]: #method.walk
+    pub fn stats(&self) -> BinConfigStats {
+        let mut config = ConfigStats::new();
+
+        self.walk(|path, value| {
+            let key = match path.0.last() {
+                Some(OwnedConfigKey::Table(key)) => Some(key.as_str()),
+                _ => None,
+            };
+
+            config.add(key, &value, path.0.len() as u32);
+        });
+
+        let data = self.data();
+
+        // The data blob was validated on load.
+        let header = BinConfigHeader::read(data).expect("invalid binary config header");
+        let (data, schema) = split_schema(data, &header).expect("invalid binary config schema");
+
+        let header_size = header.version().header_size();
+        let key_table_offset = header.key_table_offset();
+        let key_table_size = header.key_table_size();
+
+        BinConfigStats {
+            config,
+            header_size,
+            values_size: key_table_offset - header_size,
+            key_table_size,
+            strings_size: data.len() as u32 - key_table_offset - key_table_size,
+            schema_size: schema.map_or(0, |schema| {
+                (schema.len() + std::mem::size_of::<u32>()) as u32
+            }),
+        }
+    }
+}
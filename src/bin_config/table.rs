@@ -3,9 +3,9 @@ use {
     crate::{util::*, *},
     std::{
         borrow::Borrow,
-        convert::TryInto,
         fmt::{Display, Formatter, Write},
         iter::Iterator,
+        str::FromStr,
     },
 };
 
@@ -30,11 +30,12 @@ impl<'t> BinTable<'t> {
     }
 
     /// Returns `true` if the [`table`] contains a [`value`] with the (non-empty) string `key`.
+    /// Returns `false` if the `key` is empty.
     ///
     /// [`table`]: struct.BinTable.html
     /// [`value`]: type.BinConfigValue.html
-    pub fn contains(&self, key: TableKey<'_>) -> bool {
-        self.get_val(key).is_some()
+    pub fn contains<'k, K: IntoTableKey<'k>>(&self, key: K) -> bool {
+        self.get_val_impl(key).1.is_some()
     }
 
     /// Tries to get a reference to a [`value`] in the [`table`] with the (non-empty) string `key`.
@@ -42,26 +43,46 @@ impl<'t> BinTable<'t> {
     /// [`value`]: type.BinConfigValue.html
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
-    pub fn get_val(&self, key: TableKey<'_>) -> Option<BinConfigValue<'t>> {
-        self.get_impl(key.as_str().try_into().ok()?, key.key_hash())
+    pub fn get_val<'k, K: IntoTableKey<'k>>(&self, key: K) -> Option<BinConfigValue<'t>> {
+        let (key, value) = self.get_val_impl(key);
+        self.0.audit_key(|| key.map(OwnedConfigKey::from), value)
     }
 
     /// Tries to get a reference to a [`value`] in the [`table`] with the (non-empty) string `key`,
     /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
     ///
-    /// Returns an [`error`] if the [`table`] does not contain the `key`,
+    /// Returns an [`error`] if the [`table`] does not contain the `key` (or the `key` is empty),
     /// or if the [`value`] is of incorrect and incompatible [`type`].
     ///
     /// [`value`]: type.BinConfigValue.html
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
     /// [`type`]: enum.ValueType.html
-    pub fn get<V: TryFromValue<&'t str, BinArray<'t>, BinTable<'t>>>(
+    pub fn get<'k, K: IntoTableKey<'k>, V: TryFromValue<&'t str, BinArray<'t>, BinTable<'t>>>(
         &self,
-        key: TableKey<'_>,
+        key: K,
     ) -> Result<V, TableError> {
-        use TableError::*;
-        V::try_from(self.get_val(key).ok_or_else(|| KeyDoesNotExist)?).map_err(IncorrectValueType)
+        self.get_with_key(key).1
+    }
+
+    /// Tries to get a reference to a [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key`,
+    /// or if the [`value`] is of incorrect and incompatible [`type`].
+    ///
+    /// NOTE - use [`Lenient`] as the requested type to apply lenient type coercion rules.
+    ///
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`type`]: enum.ValueType.html
+    /// [`Lenient`]: struct.Lenient.html
+    pub fn get_or<'k, K: IntoTableKey<'k>, V: TryFromValue<&'t str, BinArray<'t>, BinTable<'t>>>(
+        &self,
+        key: K,
+        default: V,
+    ) -> V {
+        self.get(key).unwrap_or(default)
     }
 
     /// Tries to get a reference to a [`value`] in the [`table`] at `path`.
@@ -85,9 +106,8 @@ impl<'t> BinTable<'t> {
         K: Borrow<ConfigKey<'k>>,
         P: IntoIterator<Item = K>,
     {
-        BinConfigValue::Table(BinTable(self.0.clone()))
-            .get_path(path.into_iter())
-            .map_err(GetPathError::reverse)
+        self.0
+            .audit_path(path.into_iter(), |path| self.get_val_path_impl(path))
     }
 
     /// Tries to get a reference to a [`value`] in the [`table`] at `path`,
@@ -113,7 +133,36 @@ impl<'t> BinTable<'t> {
         P: IntoIterator<Item = K>,
         V: TryFromValue<&'t str, BinArray<'t>, BinTable<'t>>,
     {
-        V::try_from(self.get_val_path(path)?).map_err(GetPathError::IncorrectValueType)
+        self.0.audit_path(path.into_iter(), |path| {
+            V::try_from(self.get_val_path_impl(path)?).map_err(GetPathError::IncorrectValueType)
+        })
+    }
+
+    /// Tries to get a reference to a [`value`] in the [`table`] at `path`,
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    ///
+    /// Returns the `default` value if the `path` is invalid,
+    /// or if the [`value`] is of incorrect and incompatible [`type`].
+    ///
+    /// NOTE - use [`Lenient`] as the requested type to apply lenient type coercion rules.
+    ///
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`type`]: enum.ValueType.html
+    /// [`Lenient`]: struct.Lenient.html
+    pub fn get_path_or<'k, K, P, V>(&self, path: P, default: V) -> V
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+        V: TryFromValue<&'t str, BinArray<'t>, BinTable<'t>>,
+    {
+        self.get_path(path).unwrap_or(default)
     }
 
     /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
@@ -124,7 +173,7 @@ impl<'t> BinTable<'t> {
     /// [`value`]: type.BinConfigValue.html
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
-    pub fn get_bool(&self, key: TableKey<'_>) -> Result<bool, TableError> {
+    pub fn get_bool<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<bool, TableError> {
         self.get(key)
     }
 
@@ -150,6 +199,110 @@ impl<'t> BinTable<'t> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not a [`bool`].
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    pub fn get_bool_or<'k, K: IntoTableKey<'k>>(&self, key: K, default: bool) -> bool {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`bool`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_bool<'k, 'd, K: IntoTableKey<'k>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<bool> {
+        let (key, result) = self.get_with_key(key);
+        ConfigDiagnostics::try_get(diagnostics.into(), key, ValueType::Bool, result)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`date/time`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.TableError.html
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<DateTime, TableError> {
+        self.get(key)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] at `path`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value.
+    /// The last key must correspond to a [`date/time`] [`value`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: struct.BinArray.html
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime_path<'k, K, P>(&self, path: P) -> Result<DateTime, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not a [`date/time`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime_or<'k, K: IntoTableKey<'k>>(&self, key: K, default: DateTime) -> DateTime {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`date/time`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    #[cfg(feature = "datetime")]
+    pub fn try_get_datetime<'k, 'd, K: IntoTableKey<'k>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<DateTime> {
+        let (key, result) = self.get_with_key(key);
+        ConfigDiagnostics::try_get(diagnostics.into(), key, ValueType::DateTime, result)
+    }
+
     /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`].
@@ -159,7 +312,7 @@ impl<'t> BinTable<'t> {
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
     /// [`f64`]: enum.Value.html#variant.F64
-    pub fn get_i64(&self, key: TableKey<'_>) -> Result<i64, TableError> {
+    pub fn get_i64<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<i64, TableError> {
         self.get(key)
     }
 
@@ -186,6 +339,105 @@ impl<'t> BinTable<'t> {
         self.get_path(path)
     }
 
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`].
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_i64_or<'k, K: IntoTableKey<'k>>(&self, key: K, default: i64) -> i64 {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_i64<'k, 'd, K: IntoTableKey<'k>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<i64> {
+        let (key, result) = self.get_with_key(key);
+        ConfigDiagnostics::try_get(diagnostics.into(), key, ValueType::I64, result)
+    }
+
+    /// Tries to get a [`u32`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u32`].
+    ///
+    /// [`u32`]: https://doc.rust-lang.org/std/primitive.u32.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u32<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<u32, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get an [`i32`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of an [`i32`].
+    ///
+    /// [`i32`]: https://doc.rust-lang.org/std/primitive.i32.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_i32<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<i32, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u16`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u16`].
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/std/primitive.u16.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u16<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<u16, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u8`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u8`].
+    ///
+    /// [`u8`]: https://doc.rust-lang.org/std/primitive.u8.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u8<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<u8, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
@@ -195,7 +447,7 @@ impl<'t> BinTable<'t> {
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
     /// [`i64`]: enum.Value.html#variant.I64
-    pub fn get_f64(&self, key: TableKey<'_>) -> Result<f64, TableError> {
+    pub fn get_f64<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<f64, TableError> {
         self.get(key)
     }
 
@@ -222,6 +474,37 @@ impl<'t> BinTable<'t> {
         self.get_path(path)
     }
 
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn get_f64_or<'k, K: IntoTableKey<'k>>(&self, key: K, default: f64) -> f64 {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_f64<'k, 'd, K: IntoTableKey<'k>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<f64> {
+        let (key, result) = self.get_with_key(key);
+        ConfigDiagnostics::try_get(diagnostics.into(), key, ValueType::F64, result)
+    }
+
     /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`string`].
@@ -230,7 +513,7 @@ impl<'t> BinTable<'t> {
     /// [`value`]: type.BinConfigValue.html
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
-    pub fn get_string(&self, key: TableKey<'_>) -> Result<&str, TableError> {
+    pub fn get_string<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<&str, TableError> {
         self.get(key)
     }
 
@@ -256,6 +539,74 @@ impl<'t> BinTable<'t> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not a [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    pub fn get_string_or<'k, 'a, K: IntoTableKey<'k>>(
+        &'a self,
+        key: K,
+        default: &'a str,
+    ) -> &'a str {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`string`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_string<'k, 'd, K: IntoTableKey<'k>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<&str> {
+        let (key, result) = self.get_with_key(key);
+        ConfigDiagnostics::try_get(diagnostics.into(), key, ValueType::String, result)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as an enum value of type `T` via its [`FromStr`] implementation.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    /// [`error`]: enum.GetEnumError.html
+    pub fn get_enum<'k, K: IntoTableKey<'k>, T: FromStr>(&self, key: K) -> Result<T, GetEnumError> {
+        let (key, value) = self.get_with_key(key);
+        GetEnumError::parse(key, value)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and map it to an enum value of type `T` using the valid `variants` - pairs of strings and corresponding enum values.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it does not match any of the `variants` (the error then lists all valid `variants`).
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.GetEnumError.html
+    pub fn get_enum_with<'k, K: IntoTableKey<'k>, T: Clone>(
+        &self,
+        key: K,
+        variants: &[(&str, T)],
+    ) -> Result<T, GetEnumError> {
+        let (key, value) = self.get_with_key(key);
+        GetEnumError::map(key, value, variants)
+    }
+
     /// Tries to get an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`array`].
@@ -264,7 +615,7 @@ impl<'t> BinTable<'t> {
     /// [`value`]: type.BinConfigValue.html
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
-    pub fn get_array<'k>(&self, key: TableKey<'k>) -> Result<BinArray<'t>, TableError> {
+    pub fn get_array<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<BinArray<'t>, TableError> {
         self.get(key)
     }
 
@@ -289,6 +640,24 @@ impl<'t> BinTable<'t> {
         self.get_path(path)
     }
 
+    /// Tries to get an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`array`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_array<'k, 'd, K: IntoTableKey<'k>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<BinArray<'t>> {
+        let (key, result) = self.get_with_key(key);
+        ConfigDiagnostics::try_get(diagnostics.into(), key, ValueType::Array, result)
+    }
+
     /// Tries to get a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`table`](enum.Value.html#variant.Table).
@@ -296,7 +665,7 @@ impl<'t> BinTable<'t> {
     /// [`value`]: type.BinConfigValue.html
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
-    pub fn get_table(&self, key: TableKey<'_>) -> Result<BinTable<'t>, TableError> {
+    pub fn get_table<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<BinTable<'t>, TableError> {
         self.get(key)
     }
 
@@ -321,6 +690,23 @@ impl<'t> BinTable<'t> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`table`](enum.Value.html#variant.Table),
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_table<'k, 'd, K: IntoTableKey<'k>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<BinTable<'t>> {
+        let (key, result) = self.get_with_key(key);
+        ConfigDiagnostics::try_get(diagnostics.into(), key, ValueType::Table, result)
+    }
+
     /// Returns an iterator over (`key`, [`value`]) pairs of the [`table`], in unspecified order.
     ///
     /// [`value`]: type.BinConfigValue.html
@@ -333,8 +719,80 @@ impl<'t> BinTable<'t> {
         Self(table)
     }
 
+    /// Returns the (`key`, [`value`]) pair of the [`table`] at `index` in iteration order.
+    /// `index` must be less than the [`table`] length.
+    ///
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    pub(super) fn entry(&self, index: u32) -> (&'t NonEmptyStr, BinConfigValue<'t>) {
+        debug_assert!(index < self.len());
+
+        // Safe to call - the config was validated.
+        let (key, value) = unsafe { self.0.key_and_value(index) };
+
+        // Safe to call - the config was validated.
+        let key = unsafe { self.0.key_ofset_and_len(key.index) };
+
+        // Safe to call - the key string was validated.
+        let key =
+            NonEmptyStr::new(unsafe { self.0.string(key.offset(), key.len()) }).expect("empty key");
+
+        let value = self.get_value(key, value);
+
+        (key, value)
+    }
+
+    /// Returns the (non-empty) string `key`, if it is valid, and the [`value`](type.BinConfigValue.html) with it, if any.
+    fn get_val_impl<'k, K: IntoTableKey<'k>>(
+        &self,
+        key: K,
+    ) -> (Option<&'k NonEmptyStr>, Option<BinConfigValue<'t>>) {
+        let hash = key.precomputed_hash();
+
+        match key.into_table_key() {
+            Ok(key) => (
+                Some(key),
+                self.get_impl(key, hash.unwrap_or_else(|| string_hash_fnv1a(key))),
+            ),
+            Err(_) => (None, None),
+        }
+    }
+
+    /// Like [`get`](#method.get), but also returns the `key` string (empty if the `key` is empty) for error reporting.
+    fn get_with_key<
+        'k,
+        K: IntoTableKey<'k>,
+        V: TryFromValue<&'t str, BinArray<'t>, BinTable<'t>>,
+    >(
+        &self,
+        key: K,
+    ) -> (&'k str, Result<V, TableError>) {
+        use TableError::*;
+
+        let (key, value) = self.get_val_impl(key);
+
+        let result = value
+            .ok_or(KeyDoesNotExist)
+            .and_then(|value| V::try_from(value).map_err(IncorrectValueType));
+
+        (
+            key.map_or("", |key| key.as_str()),
+            self.0.audit_key(|| key.map(OwnedConfigKey::from), result),
+        )
+    }
+
+    fn get_val_path_impl<'k, K, P>(&self, path: P) -> Result<BinConfigValue<'t>, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: Iterator<Item = K>,
+    {
+        BinConfigValue::Table(BinTable(self.0.clone()))
+            .get_path(path)
+            .map_err(GetPathError::reverse)
+    }
+
     pub(super) fn get_impl(&self, key: &NonEmptyStr, hash: u32) -> Option<BinConfigValue<'t>> {
-        (0..self.len()).find_map(|idx| {
+        let find = |idx| {
             // Safe to call - the config was validated.
             let (table_key, value) = unsafe { self.0.key_and_value(idx) };
 
@@ -347,17 +805,52 @@ impl<'t> BinTable<'t> {
 
                 // Safe to call - the key string was validated.
                 if key == unsafe { self.0.string(table_key.offset(), table_key.len()) } {
-                    Some(self.get_value(value))
+                    Some(self.get_value(key, value))
                 } else {
                     None
                 }
             } else {
                 None
             }
-        })
+        };
+
+        if self.0.sorted_keys {
+            // Values are sorted by key hash - binary search for the first value with the key hash,
+            // then compare the strings of all values with the key hash.
+            (self.lower_bound(hash)..self.len())
+                .take_while(|&idx| self.key_hash(idx) == hash)
+                .find_map(find)
+        } else {
+            (0..self.len()).find_map(find)
+        }
+    }
+
+    /// Returns the index of the first value in the table (sorted by key hash) with the key hash not less than `hash`.
+    fn lower_bound(&self, hash: u32) -> u32 {
+        let mut start = 0;
+        let mut end = self.len();
+
+        while start < end {
+            let middle = start + (end - start) / 2;
+
+            if self.key_hash(middle) < hash {
+                start = middle + 1;
+            } else {
+                end = middle;
+            }
+        }
+
+        start
     }
 
-    fn get_value(&self, value: BinConfigUnpackedValue) -> BinConfigValue<'t> {
+    /// Returns the key hash of the value at `idx`.
+    /// NOTE - the caller ensures `idx` is in range.
+    fn key_hash(&self, idx: u32) -> u32 {
+        // Safe to call - the config was validated.
+        unsafe { self.0.packed_value(idx) }.key().hash
+    }
+
+    fn get_value(&self, key: &NonEmptyStr, value: BinConfigUnpackedValue) -> BinConfigValue<'t> {
         use BinConfigUnpackedValue::*;
 
         match value {
@@ -368,22 +861,26 @@ impl<'t> BinTable<'t> {
                 // Safe to call - the string was validated.
                 Value::String(unsafe { self.0.string(offset, len) })
             }
-            Array { offset, len } => Value::Array(BinArray::new(BinArrayOrTable::new(
-                self.0.base,
-                self.0.key_table,
-                offset,
-                len,
-            ))),
-            Table { offset, len } => Value::Table(BinTable::new(BinArrayOrTable::new(
-                self.0.base,
-                self.0.key_table,
-                offset,
-                len,
-            ))),
+            Array { offset, len, f32 } => Value::Array(BinArray::new(
+                self.0.nested(offset, len, || key.into()).f32_elements(f32),
+            )),
+            Table { offset, len } => {
+                Value::Table(BinTable::new(self.0.nested(offset, len, || key.into())))
+            }
+            #[cfg(feature = "datetime")]
+            BinConfigUnpackedValue::DateTime { offset, len } => {
+                // Safe to call - the date/time string was validated.
+                Value::DateTime(unsafe { self.0.datetime(offset, len) })
+            }
         }
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
         writeln!(w, "{{")?;
 
         // Gather the keys.
@@ -392,22 +889,25 @@ impl<'t> BinTable<'t> {
         // Sort the keys in alphabetical order.
         keys.sort();
 
+        // Width to pad the keys to, if aligned.
+        let key_width = lua_keys_width(keys.iter(), options);
+
         // Iterate the table using the sorted keys.
         for key in keys.into_iter() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            options.formatter.write_indent(w, indent + 1)?;
 
-            write_lua_key(w, key)?;
+            write_lua_key(w, key, key_width)?;
             write!(w, " = ")?;
 
             // Must succeed - all keys are valid.
             let value = unwrap_unchecked(
-                self.get_val(TableKey::String(key.into())),
+                self.get_val(key),
                 "failed to get a value from a bin config table with a valid key",
             );
 
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            value.fmt_lua(w, indent + 1)?;
+            value.fmt_lua(w, indent + 1, options)?;
 
             write!(w, ",")?;
 
@@ -418,7 +918,7 @@ impl<'t> BinTable<'t> {
             writeln!(w)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
+        options.formatter.write_indent(w, indent)?;
         write!(w, "}}")?;
 
         Ok(())
@@ -433,35 +933,55 @@ impl<'t> BinTable<'t> {
         path: &mut IniPath,
         options: ToIniStringOptions,
     ) -> Result<(), ToIniStringError> {
-        debug_assert!(options.nested_sections() || level < 2);
+        debug_assert!(
+            options.nested_sections() || options.flatten_separator().is_some() || level < 2
+        );
+
+        // Tables and arrays of tables serialized as arrays of sections are written after all other values.
+        let is_section = |value: &BinConfigValue<'_>| match value {
+            Value::Table(_) => true,
+            Value::Array(value) => {
+                is_ini_array_sections(value.get_val(0).ok().map(|value| value.get_type()), options)
+            }
+            _ => false,
+        };
 
         // Gather the keys.
         let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
 
-        // Sort the keys in alphabetical order, non-tables first.
+        // Sort the keys in the configured order, non-tables first.
         keys.sort_by(|&l, &r| {
             // Must succeed - all keys are valid.
             let l_val = unwrap_unchecked(
-                self.get_val(l.into()),
+                self.get_val(l),
                 "failed to get a value from a bin config table with a valid key",
             );
             let r_val = unwrap_unchecked(
-                self.get_val(r.into()),
+                self.get_val(r),
                 "failed to get a value from a bin config table with a valid key",
             );
 
-            let l_is_a_table = l_val.get_type() == ValueType::Table;
-            let r_is_a_table = r_val.get_type() == ValueType::Table;
+            let l_is_a_table = is_section(&l_val);
+            let r_is_a_table = is_section(&r_val);
 
             if !l_is_a_table && r_is_a_table {
                 std::cmp::Ordering::Less
             } else if l_is_a_table && !r_is_a_table {
                 std::cmp::Ordering::Greater
             } else {
-                l.cmp(r)
+                options.cmp_keys(l, r)
             }
         });
 
+        // Width to pad the keys of the non-table values to, if aligned.
+        let key_width = ini_keys_width(
+            self.iter()
+                .filter(|(_, value)| !is_section(value))
+                .map(|(key, _)| key),
+            path,
+            options,
+        );
+
         let len = self.len() as usize;
 
         // Iterate the table using the sorted keys.
@@ -470,15 +990,40 @@ impl<'t> BinTable<'t> {
 
             // Must succeed - all keys are valid.
             let value = unwrap_unchecked(
-                self.get_val(TableKey::String(key.into())),
+                self.get_val(key),
                 "failed to get a value from a bin config table with a valid key",
             );
 
             match value {
+                Value::Array(value)
+                    if is_ini_array_sections(
+                        value.get_val(0).ok().map(|value| value.get_type()),
+                        options,
+                    ) =>
+                {
+                    write_ini_array_sections(
+                        w,
+                        key,
+                        None,
+                        key_index as u32,
+                        value.iter().filter_map(Value::table).map(|table| {
+                            let len = table.len();
+                            (table, len)
+                        }),
+                        value.len() as usize,
+                        last,
+                        level,
+                        array,
+                        path,
+                        options,
+                    )?;
+                }
                 Value::Array(value) => {
                     write_ini_array(
                         w,
                         key,
+                        None,
+                        key_width,
                         value.iter(),
                         value.len() as usize,
                         last,
@@ -488,25 +1033,27 @@ impl<'t> BinTable<'t> {
                     )?;
                 }
                 Value::Table(value) => {
-                    let has_non_tables = value
-                        .iter()
-                        .any(|(_, val)| val.get_type() != ValueType::Table);
+                    let has_non_tables = value.iter().any(|(_, val)| !is_section(&val));
 
                     write_ini_table(
                         w,
                         key,
+                        None,
                         key_index as u32,
                         &value,
                         value.len(),
                         has_non_tables,
                         last,
                         level,
+                        array,
                         path,
                         options,
                     )?;
                 }
                 value => {
-                    write_ini_value(w, key, &value, last, level, array, path, options)?;
+                    write_ini_value(
+                        w, key, None, key_width, &value, last, level, array, path, options,
+                    )?;
                 }
             }
         }
@@ -539,21 +1086,7 @@ impl<'i, 't> Iterator for BinTableIter<'i, 't> {
         if index < self.table.len() {
             self.index += 1;
 
-            // Safe to call - the config was validated.
-            let (key, value) = unsafe { self.table.0.key_and_value(index) };
-
-            // Safe to call - the config was validated.
-            let key = unsafe { self.table.0.key_ofset_and_len(key.index) };
-
-            // Safe to call - the key string was validated.
-            let key = unwrap_unchecked(
-                NonEmptyStr::new(unsafe { self.table.0.string(key.offset(), key.len()) }),
-                "empty key",
-            );
-
-            let value = self.table.get_value(value);
-
-            Some((key, value))
+            Some(self.table.entry(index))
         } else {
             None
         }
@@ -561,8 +1094,13 @@ impl<'i, 't> Iterator for BinTableIter<'i, 't> {
 }
 
 impl<'t> DisplayLua for BinTable<'t> {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
@@ -582,7 +1120,7 @@ impl<'t> DisplayIni for BinTable<'t> {
 
 impl<'t> Display for BinTable<'t> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, Default::default())
     }
 }
 
@@ -600,11 +1138,11 @@ mod tests {
         let config = BinConfig::new(data).unwrap();
 
         assert_eq!(
-            config.root().get_bool("".into()).err().unwrap(),
+            config.root().get_bool("").err().unwrap(),
             TableError::KeyDoesNotExist
         );
         assert_eq!(
-            config.root().get_bool("missing".into()).err().unwrap(),
+            config.root().get_bool("missing").err().unwrap(),
             TableError::KeyDoesNotExist
         );
         #[cfg(feature = "str_hash")]
@@ -617,7 +1155,7 @@ mod tests {
 
         // But this works.
 
-        assert_eq!(config.root().get_bool("bool".into()).unwrap(), true);
+        assert_eq!(config.root().get_bool("bool").unwrap(), true);
 
         #[cfg(feature = "str_hash")]
         {
@@ -625,6 +1163,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn empty_key() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.bool(nestr!("bool"), true).unwrap();
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+        let root = config.root();
+
+        // Empty keys are never found, same as in the dynamic config.
+        assert!(!root.contains(""));
+        assert!(root.get_val("").is_none());
+        assert_eq!(
+            root.get_bool("").err().unwrap(),
+            TableError::KeyDoesNotExist
+        );
+        assert_eq!(
+            root.get_bool(&String::new()).err().unwrap(),
+            TableError::KeyDoesNotExist
+        );
+        assert_eq!(
+            root.get_bool(&TableKey::from("")).err().unwrap(),
+            TableError::KeyDoesNotExist
+        );
+        assert!(!root.get_bool_or("", false));
+
+        #[cfg(feature = "dyn")]
+        assert_eq!(
+            config.to_dyn_config().root().get_bool("").err().unwrap(),
+            root.get_bool("").err().unwrap()
+        );
+
+        // All key types are accepted.
+        assert!(root.get_bool("bool").unwrap());
+        assert!(root.get_bool(&String::from("bool")).unwrap());
+        assert!(root.get_bool(nestr!("bool")).unwrap());
+        assert!(root.get_bool(&TableKey::from("bool")).unwrap());
+
+        #[cfg(feature = "str_hash")]
+        assert!(root.get_bool(key!("bool")).unwrap());
+    }
+
     #[test]
     fn BinTableError_IncorrectValueType() {
         let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
@@ -637,10 +1216,10 @@ mod tests {
         let root = config.root();
 
         assert_eq!(
-            config.root().get_bool("f64".into()).err().unwrap(),
+            config.root().get_bool("f64").err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
         );
-        let val: Result<bool, _> = config.root().get("f64".into());
+        let val: Result<bool, _> = config.root().get("f64");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
@@ -653,15 +1232,15 @@ mod tests {
             );
         }
         assert_eq!(
-            config.root().get_string("f64".into()).err().unwrap(),
+            config.root().get_string("f64").err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
         );
-        let val: Result<&str, _> = root.get("f64".into());
+        let val: Result<&str, _> = root.get("f64");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
         );
-        let val: Result<String, _> = config.root().get("f64".into());
+        let val: Result<String, _> = config.root().get("f64");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
@@ -674,10 +1253,10 @@ mod tests {
             );
         }
         assert_eq!(
-            config.root().get_array("f64".into()).err().unwrap(),
+            config.root().get_array("f64").err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
         );
-        let val: Result<BinArray<'_>, _> = root.get("f64".into());
+        let val: Result<BinArray<'_>, _> = root.get("f64");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
@@ -690,10 +1269,10 @@ mod tests {
             );
         }
         assert_eq!(
-            config.root().get_table("f64".into()).err().unwrap(),
+            config.root().get_table("f64").err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
         );
-        let val: Result<BinTable<'_>, _> = root.get("f64".into());
+        let val: Result<BinTable<'_>, _> = root.get("f64");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::F64)
@@ -707,10 +1286,10 @@ mod tests {
         }
 
         assert_eq!(
-            config.root().get_bool("string".into()).err().unwrap(),
+            config.root().get_bool("string").err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
         );
-        let val: Result<bool, _> = config.root().get("string".into());
+        let val: Result<bool, _> = config.root().get("string");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
@@ -723,10 +1302,10 @@ mod tests {
             );
         }
         assert_eq!(
-            config.root().get_f64("string".into()).err().unwrap(),
+            config.root().get_f64("string").err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
         );
-        let val: Result<f64, _> = root.get("string".into());
+        let val: Result<f64, _> = root.get("string");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
@@ -739,10 +1318,10 @@ mod tests {
             );
         }
         assert_eq!(
-            config.root().get_i64("string".into()).err().unwrap(),
+            config.root().get_i64("string").err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
         );
-        let val: Result<i64, _> = root.get("string".into());
+        let val: Result<i64, _> = root.get("string");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
@@ -755,10 +1334,10 @@ mod tests {
             );
         }
         assert_eq!(
-            config.root().get_array("string".into()).err().unwrap(),
+            config.root().get_array("string").err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
         );
-        let val: Result<BinArray<'_>, _> = root.get("string".into());
+        let val: Result<BinArray<'_>, _> = root.get("string");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
@@ -771,10 +1350,10 @@ mod tests {
             );
         }
         assert_eq!(
-            config.root().get_table("string".into()).err().unwrap(),
+            config.root().get_table("string").err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
         );
-        let val: Result<BinTable<'_>, _> = root.get("string".into());
+        let val: Result<BinTable<'_>, _> = root.get("string");
         assert_eq!(
             val.err().unwrap(),
             TableError::IncorrectValueType(ValueType::String)
@@ -789,20 +1368,160 @@ mod tests {
 
         // But this works.
 
-        assert!(cmp_f64(config.root().get_f64("f64".into()).unwrap(), 3.14));
+        assert!(cmp_f64(config.root().get_f64("f64").unwrap(), 3.14));
         #[cfg(feature = "str_hash")]
         {
             assert!(cmp_f64(config.root().get_f64(key!("f64")).unwrap(), 3.14));
         }
-        assert_eq!(config.root().get_i64("f64".into()).unwrap(), 3);
+        assert_eq!(config.root().get_i64("f64").unwrap(), 3);
         #[cfg(feature = "str_hash")]
         {
             assert_eq!(config.root().get_i64(key!("f64")).unwrap(), 3);
         }
-        assert_eq!(config.root().get_string("string".into()).unwrap(), "foo");
-        let string: &str = root.get("string".into()).unwrap();
+        assert_eq!(config.root().get_string("string").unwrap(), "foo");
+        let string: &str = root.get("string").unwrap();
         assert_eq!(string, "foo");
-        let string: String = root.get("string".into()).unwrap();
+        let string: String = root.get("string").unwrap();
         assert_eq!(string, "foo");
     }
+
+    #[test]
+    fn BinTableError_ValueOutOfRange() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+
+        writer.i64(nestr!("foo"), -7).unwrap();
+        writer.i64(nestr!("bar"), 70_000).unwrap();
+
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+        let root = config.root();
+
+        assert_eq!(root.get_i32("foo").unwrap(), -7);
+        assert_eq!(
+            root.get_u32("foo").err().unwrap(),
+            TableError::ValueOutOfRange(-7)
+        );
+        assert_eq!(root.get_u32("bar").unwrap(), 70_000);
+        assert_eq!(
+            root.get_u16("bar").err().unwrap(),
+            TableError::ValueOutOfRange(70_000)
+        );
+    }
+
+    #[test]
+    fn get_enum() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+
+        writer.string(nestr!("foo"), "high").unwrap();
+        writer.string(nestr!("bar"), "ultra").unwrap();
+
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+        let root = config.root();
+
+        let variants = [("low", 0), ("high", 1)];
+
+        assert_eq!(root.get_enum_with("foo", &variants).unwrap(), 1);
+        assert_eq!(
+            root.get_enum_with("bar", &variants).err().unwrap(),
+            GetEnumError::InvalidVariant {
+                path: vec![nestr!("bar").into()].into(),
+                value: "ultra".to_owned(),
+                variants: vec!["low".to_owned(), "high".to_owned()],
+            }
+        );
+        assert_eq!(
+            root.get_enum::<_, i64>("foo").err().unwrap(),
+            GetEnumError::InvalidVariant {
+                path: vec![nestr!("foo").into()].into(),
+                value: "high".to_owned(),
+                variants: Vec::new(),
+            }
+        );
+        assert_eq!(
+            root.get_enum_with("missing", &variants).err().unwrap(),
+            GetEnumError::Table(TableError::KeyDoesNotExist)
+        );
+    }
+
+    #[test]
+    fn get_or() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(4).unwrap()).unwrap();
+
+        writer.bool(nestr!("bool"), true).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.string(nestr!("string"), "false").unwrap();
+        writer.table(nestr!("table"), 1).unwrap();
+        writer.f64(nestr!("float"), 3.14).unwrap();
+        writer.end().unwrap();
+
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+        let root = config.root();
+
+        assert_eq!(root.get_bool_or("bool", false), true);
+        assert_eq!(root.get_bool_or("missing", true), true);
+        assert_eq!(root.get_bool_or("int", true), true);
+
+        assert_eq!(root.get_i64_or("int", 9), 7);
+        assert!(cmp_f64(root.get_f64_or("int", 9.0), 7.0));
+        assert_eq!(root.get_i64_or("string", 9), 9);
+
+        assert_eq!(root.get_string_or("string", "foo"), "false");
+        assert_eq!(root.get_string_or("bool", "foo"), "foo");
+
+        assert_eq!(root.get_or("int", 9), 7);
+        assert_eq!(root.get_or("missing", 9), 9);
+
+        assert!(cmp_f64(
+            root.get_path_or(&["table".into(), "float".into()], 7.62),
+            3.14
+        ));
+        assert!(cmp_f64(
+            root.get_path_or(&["table".into(), "missing".into()], 7.62),
+            7.62
+        ));
+
+        // Lenient coercion.
+        assert_eq!(root.get_bool_or("string", true), true);
+        assert_eq!(root.get_or("string", Lenient(true)), Lenient(false));
+        assert_eq!(root.get_or("int", Lenient(false)), Lenient(false));
+    }
+
+    #[test]
+    fn try_get() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+
+        writer.bool(nestr!("bool"), true).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+        let root = config.root();
+
+        let diagnostics = ConfigDiagnostics::new();
+
+        assert_eq!(root.try_get_bool("bool", &diagnostics), Some(true));
+        assert_eq!(root.try_get_i64("int", &diagnostics), Some(7));
+        assert_eq!(root.try_get_string("string", &diagnostics), Some("foo"));
+        assert!(diagnostics.is_empty());
+
+        assert_eq!(root.try_get_f64("missing", &diagnostics), None);
+        assert!(root.try_get_table("bool", &diagnostics).is_none());
+        // No diagnostics sink.
+        assert_eq!(root.try_get_bool("missing", None), None);
+
+        let diagnostics = diagnostics.take();
+
+        assert_eq!(diagnostics.len(), 2);
+
+        assert_eq!(diagnostics[0].path, vec![nestr!("missing").into()].into());
+        assert_eq!(diagnostics[0].expected, ValueType::F64);
+        assert_eq!(diagnostics[0].found, None);
+
+        assert_eq!(diagnostics[1].path, vec![nestr!("bool").into()].into());
+        assert_eq!(diagnostics[1].expected, ValueType::Table);
+        assert_eq!(diagnostics[1].found, Some(ValueType::Bool));
+    }
 }
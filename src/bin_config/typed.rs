@@ -0,0 +1,109 @@
+//! Typed copies of the numeric arrays of the binary config.
+//!
+//! Data blob layout with typed arrays (the data blob header has the `BIN_CONFIG_HEADER_FLAG_TYPED_ARRAYS` flag set):
+//!
+//! |- header -|- values -|- typed arrays -|- key table -|- strings -|
+//!
+//! - the elements of each non-empty array whose elements are all `i64`'s or all `f64`'s are also stored contiguously
+//!   as `i64`'s / `f64`'s (in whatever endianness we use), aligned to `TYPED_ARRAY_ALIGNMENT` w.r.t. the data blob,
+//!   after all packed values,
+//! - the offset in bytes to the typed copy of the array w.r.t. the data blob is stored
+//!   in the key hash field of the array's first element (which is `0` for the other array elements).
+//!
+//! Typed copies allow zero-copy access to the numeric arrays as slices (see `BinArray::as_i64_slice()` / `as_f64_slice()`).
+
+use {
+    super::{config::*, util::*, value::BinConfigPackedValue},
+    crate::*,
+    std::mem::size_of,
+};
+
+/// Alignment in bytes of the typed array copies w.r.t. the data blob.
+pub(super) const TYPED_ARRAY_ALIGNMENT: u32 = 8;
+
+/// Appends the typed copies of the numeric arrays to the binary config `data` blob of the `version`
+/// with the root table with `len` values, which must only contain the header and the packed values,
+/// and writes the offsets to the typed copies to the arrays' first elements.
+pub(super) fn write_typed_arrays(data: &mut Vec<u8>, version: BinConfigVersion, len: u32) {
+    let mut arrays = Vec::new();
+    numeric_arrays(data, version.header_size(), len, &mut arrays);
+
+    for (offset, len) in arrays.into_iter() {
+        while data.len() % TYPED_ARRAY_ALIGNMENT as usize != 0 {
+            data.push(0);
+        }
+
+        let typed_offset = data.len() as u32;
+
+        for index in 0..len {
+            let value = *packed_value(data, offset, index);
+            data.extend_from_slice(&u64_to_bin(value.bits()).to_ne_bytes());
+        }
+
+        packed_value_mut(data, offset, 0).set_key_hash(typed_offset);
+    }
+}
+
+/// Returns the type of the elements of the non-empty array with `len` `values`
+/// if the elements are all `i64`'s or all `f64`'s.
+pub(super) fn numeric_array_type<'v, V>(values: V) -> Option<ValueType>
+where
+    V: Iterator<Item = &'v BinConfigPackedValue>,
+{
+    let mut array_type = None;
+
+    for value in values {
+        let value_type = value.try_value_type()?;
+
+        if !matches!(value_type, ValueType::I64 | ValueType::F64)
+            || matches!(array_type, Some(array_type) if array_type != value_type)
+        {
+            return None;
+        }
+
+        array_type.replace(value_type);
+    }
+
+    array_type
+}
+
+/// Collects the offsets and lengths of the non-empty numeric arrays (with all `i64` or all `f64` elements)
+/// among the `len` table values at `offset` in the binary config `data` blob and, recursively, their elements.
+fn numeric_arrays(data: &[u8], offset: u32, len: u32, arrays: &mut Vec<(u32, u32)>) {
+    for index in 0..len {
+        let value = packed_value(data, offset, index);
+
+        match value.value_type() {
+            // Arrays with elements stored as `f32`'s have no typed copies.
+            value_type @ ValueType::Array | value_type @ ValueType::Table
+                if value.len() > 0 && !value.is_f32_array() =>
+            {
+                if value_type == ValueType::Array
+                    && numeric_array_type(
+                        (0..value.len()).map(|index| packed_value(data, value.offset(), index)),
+                    )
+                    .is_some()
+                {
+                    arrays.push((value.offset(), value.len()));
+                } else {
+                    numeric_arrays(data, value.offset(), value.len(), arrays);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn packed_value(data: &[u8], offset: u32, index: u32) -> &BinConfigPackedValue {
+    let offset = offset as usize + index as usize * size_of::<BinConfigPackedValue>();
+    debug_assert!(offset + size_of::<BinConfigPackedValue>() <= data.len());
+
+    unsafe { &*(data.as_ptr().add(offset) as *const BinConfigPackedValue) }
+}
+
+fn packed_value_mut(data: &mut [u8], offset: u32, index: u32) -> &mut BinConfigPackedValue {
+    let offset = offset as usize + index as usize * size_of::<BinConfigPackedValue>();
+    debug_assert!(offset + size_of::<BinConfigPackedValue>() <= data.len());
+
+    unsafe { &mut *(data.as_mut_ptr().add(offset) as *mut BinConfigPackedValue) }
+}
@@ -0,0 +1,115 @@
+use {
+    super::util::to_array_error,
+    crate::{util::*, *},
+    std::time::Duration,
+};
+
+impl<'t> BinTable<'t> {
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as a duration - a non-negative integer or fractional number followed by a unit suffix
+    /// (`ns`, `us` / `µs`, `ms`, `s`, `m` / `min`, `h` or `d`), e.g. `"5s"`, `"250ms"`, `"1.5h"`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_duration<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<Duration, GetUnitError> {
+        parse_duration(self.get_string(key)?)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as a byte size - a non-negative integer or fractional number followed by an optional unit suffix
+    /// (`B`, decimal `kB` / `KB`, `MB`, `GB`, `TB`, or binary `KiB`, `MiB`, `GiB`, `TiB`), e.g. `"512"`, `"64MiB"`, `"1.5GB"`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_bytes<'k, K: IntoTableKey<'k>>(&self, key: K) -> Result<u64, GetUnitError> {
+        parse_bytes(self.get_string(key)?)
+    }
+}
+
+impl<'a> BinArray<'a> {
+    /// Tries to get a [`string`] [`value`] in the [`array`] at `index` and parse it as a duration.
+    ///
+    /// See [`BinTable::get_duration`] for the supported format.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`], or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`BinTable::get_duration`]: struct.BinTable.html#method.get_duration
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_duration(&self, index: u32) -> Result<Duration, GetUnitError> {
+        parse_duration(self.get_string(index).map_err(to_array_error)?)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`array`] at `index` and parse it as a byte size.
+    ///
+    /// See [`BinTable::get_bytes`] for the supported format.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`], or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`BinTable::get_bytes`]: struct.BinTable.html#method.get_bytes
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_bytes(&self, index: u32) -> Result<u64, GetUnitError> {
+        parse_bytes(self.get_string(index).map_err(to_array_error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr, std::num::NonZeroU32, std::time::Duration};
+
+    #[test]
+    fn get_duration_bytes() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+
+        writer.string(nestr!("timeout"), "250ms").unwrap();
+        writer.string(nestr!("cache"), "64MiB").unwrap();
+
+        writer.array(nestr!("sizes"), 2).unwrap();
+        writer.string(None, "1.5KB").unwrap();
+        writer.string(None, "1XB").unwrap();
+        writer.end().unwrap();
+
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+        let root = config.root();
+
+        assert_eq!(
+            root.get_duration("timeout").unwrap(),
+            Duration::from_millis(250)
+        );
+        assert_eq!(root.get_bytes("cache").unwrap(), 64 * 1024 * 1024);
+        assert_eq!(
+            root.get_duration("cache").err().unwrap(),
+            GetUnitError::InvalidSuffix("MiB".to_owned())
+        );
+
+        let sizes = root.get_array("sizes").unwrap();
+
+        assert_eq!(sizes.get_bytes(0).unwrap(), 1_500);
+        assert_eq!(
+            sizes.get_bytes(1).err().unwrap(),
+            GetUnitError::InvalidSuffix("XB".to_owned())
+        );
+        assert_eq!(
+            sizes.get_bytes(2).err().unwrap(),
+            GetUnitError::Array(ArrayError::IndexOutOfBounds(2))
+        );
+    }
+}
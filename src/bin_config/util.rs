@@ -1,17 +1,4 @@
-pub(crate) type StringHash = u32;
-
-pub(crate) fn string_hash_fnv1a(string: &str) -> StringHash {
-    const FNV1A_PRIME: StringHash = 0x0100_0193;
-    const FNV1A_SEED: StringHash = 0x811C_9DC5;
-
-    let mut hash = FNV1A_SEED;
-
-    for byte in string.as_bytes() {
-        hash = (hash ^ *byte as StringHash).wrapping_mul(FNV1A_PRIME);
-    }
-
-    hash
-}
+use crate::{ArrayError, BinArrayError};
 
 pub(super) fn u32_to_bin(val: u32) -> u32 {
     u32::to_le(val)
@@ -38,25 +25,64 @@ pub(super) fn u64_from_bin(bin: u64) -> u64 {
     //u64::from_be(bin)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Converts the binary config array error to the equivalent generic array error.
+pub(super) fn to_array_error(err: BinArrayError) -> ArrayError {
+    match err {
+        BinArrayError::IndexOutOfBounds(len) => ArrayError::IndexOutOfBounds(len),
+        BinArrayError::IncorrectValueType(value_type) => ArrayError::IncorrectValueType(value_type),
+        BinArrayError::ValueOutOfRange(value) => ArrayError::ValueOutOfRange(value),
+    }
+}
 
-    #[test]
-    fn fnv1a_hash_collisions() {
-        // https://softwareengineering.stackexchange.com/questions/49550/which-hashing-algorithm-is-best-for-uniqueness-and-speed
+/// CRC-32 (IEEE 802.3) lookup table.
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320; // Reversed.
 
-        assert_eq!(string_hash_fnv1a("costarring"), string_hash_fnv1a("liquid"),);
+    let mut table = [0; 256];
+    let mut index = 0;
 
-        assert_eq!(
-            string_hash_fnv1a("declinate"),
-            string_hash_fnv1a("macallums"),
-        );
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
 
-        assert_eq!(string_hash_fnv1a("altarage"), string_hash_fnv1a("zinke"),);
+        while bit < 8 {
+            crc = if (crc & 1) != 0 {
+                (crc >> 1) ^ CRC32_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
 
-        assert_eq!(string_hash_fnv1a("altarages"), string_hash_fnv1a("zinkes"),);
+        table[index] = crc;
+        index += 1;
+    }
+
+    table
+}
+
+/// Updates the CRC-32 (IEEE 802.3) checksum `crc` of the previous bytes with the `bytes`.
+/// Pass `0` as the `crc` for the first bytes.
+pub(super) fn crc32(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
 
-        assert_ne!(string_hash_fnv1a("foo"), string_hash_fnv1a("bar"),);
+    for byte in bytes {
+        crc = (crc >> 8) ^ CRC32_TABLE[((crc as u8) ^ *byte) as usize];
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(0, b""), 0);
+        assert_eq!(crc32(0, b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(crc32(0, b"1234"), b"56789"), 0xCBF4_3926);
     }
 }
@@ -1,6 +1,6 @@
 use {
     super::{array::BinArray, table::BinTable, util::*},
-    crate::{util::unwrap_unchecked, value::*, *},
+    crate::{value::*, *},
     static_assertions::const_assert,
     std::{
         borrow::Borrow,
@@ -35,6 +35,13 @@ const TYPE_MASK: u32 = ((1 << TYPE_BITS) - 1) << TYPE_OFFSET;
 
 const_assert!(KEY_INDEX_BITS + TYPE_BITS == (std::mem::size_of::<u32>() as u32) * 8);
 
+/// Value type bits of the arrays with elements stored as `f32`'s (see `BinConfigWriter::f32_arrays()`).
+/// Unpacked as `ValueType::Array`.
+///
+/// Elements of such arrays are stored contiguously as `f32`'s (in whatever endianness we use) at the array's offset,
+/// padded with zeros to a multiple of the packed value size.
+const F32_ARRAY_TYPE: u32 = (1 << TYPE_BITS) - 1;
+
 // |--   offset   --|--   length   --|
 // |--   32 bits  --|--   32 bits  --|
 
@@ -128,6 +135,20 @@ impl BinConfigPackedValue {
         result
     }
 
+    /// Create a new packed value representing a date/time.
+    /// Date/time values are stored as RFC 3339 strings in the string section.
+    #[cfg(feature = "datetime")]
+    pub(super) fn new_datetime(key: BinTableKey, offset: u32, len: u32) -> Self {
+        let mut result = Self::default();
+
+        result.set_value_type_and_key_index(ValueType::DateTime, key.index);
+        result.key_hash = u32_to_bin(key.hash);
+
+        result.set_offset_and_len(offset, len);
+
+        result
+    }
+
     /// Create a new packed value representing an array / table.
     pub(super) fn new_array_or_table(key: BinTableKey, offset: u32, len: u32, table: bool) -> Self {
         let mut result = Self::default();
@@ -187,11 +208,17 @@ impl BinConfigPackedValue {
             ValueType::Array => Array {
                 offset: self.offset(),
                 len: self.len(),
+                f32: self.is_f32_array(),
             },
             ValueType::Table => Table {
                 offset: self.offset(),
                 len: self.len(),
             },
+            #[cfg(feature = "datetime")]
+            ValueType::DateTime => DateTime {
+                offset: self.offset(),
+                len: self.len(),
+            },
         }
     }
 
@@ -200,13 +227,41 @@ impl BinConfigPackedValue {
     pub(super) fn try_value_type(&self) -> Option<ValueType> {
         let value_type = (self.type_and_key_index() & TYPE_MASK) >> TYPE_OFFSET;
 
-        value_type_from_u32(value_type)
+        if value_type == F32_ARRAY_TYPE {
+            Some(ValueType::Array)
+        } else {
+            value_type_from_u32(value_type)
+        }
+    }
+
+    /// Returns `true` if this value is an array with elements stored as `f32`'s.
+    pub(super) fn is_f32_array(&self) -> bool {
+        (self.type_and_key_index() & TYPE_MASK) >> TYPE_OFFSET == F32_ARRAY_TYPE
+    }
+
+    /// Marks this array value as an array with elements stored as `f32`'s, keeps the key index.
+    /// NOTE - the caller ensures the value is an array.
+    pub(super) fn set_f32_array(&mut self) {
+        let type_and_key_index =
+            ((F32_ARRAY_TYPE << TYPE_OFFSET) & TYPE_MASK) | (self.key_index() & KEY_INDEX_MASK);
+
+        self.set_type_and_key_index(type_and_key_index);
     }
 
     /// Unpacks this value's type.
-    /// NOTE - the caller guarantees the value type is valid.
+    /// NOTE - the caller guarantees the value type is valid (i.e. the data blob was validated);
+    /// panics otherwise, never causes undefined behaviour.
     pub(super) fn value_type(&self) -> ValueType {
-        unwrap_unchecked(self.try_value_type(), "invalid binary config value type")
+        self.try_value_type()
+            .expect("invalid binary config value type")
+    }
+
+    /// Packs the table element's key index in the key table, keeps the value type.
+    pub(super) fn set_key_index(&mut self, key_index: u32) {
+        let type_and_key_index =
+            (self.type_and_key_index() & TYPE_MASK) | (key_index & KEY_INDEX_MASK);
+
+        self.set_type_and_key_index(type_and_key_index);
     }
 
     fn set_value_type_and_key_index(&mut self, value_type: ValueType, key_index: u32) {
@@ -229,9 +284,11 @@ impl BinConfigPackedValue {
     }
 
     /// Unpacks and interprets this value as a `bool`.
-    /// NOTE - the caller guarantees the value is `0` or `1`.
+    /// NOTE - the caller guarantees the value is `0` or `1` (i.e. the data blob was validated);
+    /// panics otherwise, never causes undefined behaviour.
     fn bool(&self) -> bool {
-        unwrap_unchecked(self.try_bool(), "invalid binary config boolean value")
+        self.try_bool()
+            .expect("invalid binary config boolean value")
     }
 
     /// Unpacks and interprets this value as an `i64`.
@@ -247,8 +304,8 @@ impl BinConfigPackedValue {
     }
 
     /// Unpacks and returns the string/array/table length.
-    /// String length is in bytes; array/table length is in elements.
-    /// NOTE - the caller ensures this value is a string/array/table.
+    /// String (and date/time) length is in bytes; array/table length is in elements.
+    /// NOTE - the caller ensures this value is a string/array/table (or a date/time).
     pub(super) fn len(&self) -> u32 {
         ((self.value_or_offset_and_len() & VALUE_LEN_MASK) >> VALUE_LEN_OFFSET) as u32
     }
@@ -282,6 +339,17 @@ impl BinConfigPackedValue {
         self.set_value_or_offset_and_len(offset_and_len);
     }
 
+    /// Unpacks this value's value/offset and length to `u64` - the raw bits of `bool` / `i64` / `f64` values.
+    pub(super) fn bits(&self) -> u64 {
+        self.value_or_offset_and_len()
+    }
+
+    /// Packs the key hash.
+    /// NOTE - for the first element of an array with a typed copy, the offset to the typed copy (see `super::typed`).
+    pub(super) fn set_key_hash(&mut self, key_hash: u32) {
+        self.key_hash = u32_to_bin(key_hash);
+    }
+
     /// Unpacks this value's type/key index to `u32`.
     fn type_and_key_index(&self) -> u32 {
         u32_from_bin(self.type_and_key_index)
@@ -313,6 +381,16 @@ impl BinConfigPackedValue {
     }
 }
 
+/// Returns the size in bytes of the elements of the array of `len` elements stored as `f32`'s,
+/// padded to a multiple of the packed value size, or `None` on overflow.
+pub(super) fn f32_array_size(len: u32) -> Option<u32> {
+    let value_size = std::mem::size_of::<BinConfigPackedValue>() as u32;
+
+    len.checked_mul(std::mem::size_of::<f32>() as u32)?
+        .checked_add(value_size - 1)
+        .map(|size| size / value_size * value_size)
+}
+
 /// Each value in the binary config table has a hashed non-empty UTF-8 string key,
 /// described by this struct.
 pub(super) struct BinTableKey {
@@ -349,9 +427,26 @@ pub(super) enum BinConfigUnpackedValue {
     Bool(bool),
     I64(i64),
     F64(f64),
-    String { offset: u32, len: u32 },
-    Array { offset: u32, len: u32 },
-    Table { offset: u32, len: u32 },
+    String {
+        offset: u32,
+        len: u32,
+    },
+    Array {
+        offset: u32,
+        len: u32,
+        /// Whether the array elements are stored as `f32`'s.
+        f32: bool,
+    },
+    Table {
+        offset: u32,
+        len: u32,
+    },
+    /// Offset and length of the RFC 3339 date/time string in the string section.
+    #[cfg(feature = "datetime")]
+    DateTime {
+        offset: u32,
+        len: u32,
+    },
 }
 
 /// A [`value`] returned when accessing a binary [`array`] or [`table`].
@@ -373,15 +468,16 @@ impl<'at> BinConfigValue<'at> {
             match key {
                 ConfigKey::Array(index) => match self {
                     Value::Array(array) => {
-                        let value = array.get_val(*index).map_err(|err| match err {
+                        let value = array.get_impl(*index).map_err(|err| match err {
                             BinArrayError::IndexOutOfBounds(len) => {
                                 GetPathError::IndexOutOfBounds {
                                     path: vec![(*index).into()].into(),
                                     len,
                                 }
                             }
-                            BinArrayError::IncorrectValueType(_) => debug_unreachable!(
-                                "`get()` does not return `IncorrectValueType(_)`"
+                            BinArrayError::IncorrectValueType(_)
+                            | BinArrayError::ValueOutOfRange(_) => debug_unreachable!(
+                                "`get()` does not return `IncorrectValueType(_)` or `ValueOutOfRange(_)`"
                             ),
                         })?;
 
@@ -417,7 +513,7 @@ impl<'at> BinConfigValue<'at> {
 
 impl<'a> Display for BinConfigValue<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, Default::default())
     }
 }
 
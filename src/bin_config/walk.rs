@@ -0,0 +1,307 @@
+use crate::{util::*, *};
+
+impl BinConfig {
+    /// Recursively visits all [`values`] in the root [`table`] of the [`config`] in depth-first order,
+    /// calling `f` with the [`path`] of each [`value`] and the [`value`] itself.
+    ///
+    /// See [`BinTable::walk`].
+    ///
+    /// [`values`]: type.BinConfigValue.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`config`]: struct.BinConfig.html
+    /// [`path`]: struct.ConfigPath.html
+    /// [`BinTable::walk`]: struct.BinTable.html#method.walk
+    pub fn walk<'t, F: FnMut(&ConfigPath, BinConfigValue<'t>)>(&'t self, f: F) {
+        self.root().walk(f)
+    }
+
+    /// Returns a depth-first iterator over the [`paths`] and [`values`] of all values in the root [`table`] of the [`config`].
+    ///
+    /// See [`BinTable::walk_iter`].
+    ///
+    /// [`paths`]: struct.ConfigPath.html
+    /// [`values`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`config`]: struct.BinConfig.html
+    /// [`BinTable::walk_iter`]: struct.BinTable.html#method.walk_iter
+    pub fn walk_iter(&self) -> BinConfigWalk<'_> {
+        self.root().walk_iter()
+    }
+}
+
+impl<'t> BinTable<'t> {
+    /// Recursively visits all [`values`] in the [`table`] in depth-first order,
+    /// calling `f` with the [`path`] of each [`value`] (relative to this [`table`]) and the [`value`] itself.
+    ///
+    /// Nested [`tables`] / [`arrays`] are visited before their [`values`].
+    /// [`Table`] values are visited in unspecified order, [`array`] elements are visited in order.
+    ///
+    /// [`values`]: type.BinConfigValue.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`Table`]: struct.BinTable.html
+    /// [`arrays`]: struct.BinArray.html
+    /// [`array`]: struct.BinArray.html
+    /// [`path`]: struct.ConfigPath.html
+    pub fn walk<F: FnMut(&ConfigPath, BinConfigValue<'t>)>(&self, mut f: F) {
+        walk_table(self, &mut ConfigPath::new(), &mut f);
+    }
+
+    /// Returns a depth-first iterator over the [`paths`] (relative to this [`table`]) and [`values`] of all values in the [`table`],
+    /// in the same order as [`walk`].
+    ///
+    /// [`paths`]: struct.ConfigPath.html
+    /// [`values`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`walk`]: #method.walk
+    pub fn walk_iter(&self) -> BinConfigWalk<'t> {
+        BinConfigWalk::new(BinConfigWalkLevel::Table(BinTable::new(self.0.clone()), 0))
+    }
+}
+
+impl<'a> BinArray<'a> {
+    /// Recursively visits all [`values`] in the [`array`] in depth-first order,
+    /// calling `f` with the [`path`] of each [`value`] (relative to this [`array`]) and the [`value`] itself.
+    ///
+    /// See [`BinTable::walk`].
+    ///
+    /// [`values`]: type.BinConfigValue.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`path`]: struct.ConfigPath.html
+    /// [`BinTable::walk`]: struct.BinTable.html#method.walk
+    pub fn walk<F: FnMut(&ConfigPath, BinConfigValue<'a>)>(&self, mut f: F) {
+        walk_array(self, &mut ConfigPath::new(), &mut f);
+    }
+
+    /// Returns a depth-first iterator over the [`paths`] (relative to this [`array`]) and [`values`] of all values in the [`array`],
+    /// in the same order as [`walk`].
+    ///
+    /// [`paths`]: struct.ConfigPath.html
+    /// [`values`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`walk`]: #method.walk
+    pub fn walk_iter(&self) -> BinConfigWalk<'a> {
+        BinConfigWalk::new(BinConfigWalkLevel::Array(BinArray::new(self.0.clone()), 0))
+    }
+}
+
+fn walk_table<'t, F: FnMut(&ConfigPath, BinConfigValue<'t>)>(
+    table: &BinTable<'t>,
+    path: &mut ConfigPath,
+    f: &mut F,
+) {
+    for index in 0..table.len() {
+        let (key, value) = table.entry(index);
+
+        path.push(key);
+        walk_value(value, path, f);
+        path.pop();
+    }
+}
+
+fn walk_array<'a, F: FnMut(&ConfigPath, BinConfigValue<'a>)>(
+    array: &BinArray<'a>,
+    path: &mut ConfigPath,
+    f: &mut F,
+) {
+    for (index, value) in array.iter().enumerate() {
+        path.push(index as u32);
+        walk_value(value, path, f);
+        path.pop();
+    }
+}
+
+fn walk_value<'at, F: FnMut(&ConfigPath, BinConfigValue<'at>)>(
+    value: BinConfigValue<'at>,
+    path: &mut ConfigPath,
+    f: &mut F,
+) {
+    match value {
+        Value::Table(table) => {
+            f(path, Value::Table(BinTable::new(table.0.clone())));
+            walk_table(&table, path, f);
+        }
+        Value::Array(array) => {
+            f(path, Value::Array(BinArray::new(array.0.clone())));
+            walk_array(&array, path, f);
+        }
+        value => f(path, value),
+    }
+}
+
+/// A depth-first iterator over the [`paths`] and [`values`] of all values
+/// nested in a binary config [`table`] / [`array`].
+///
+/// Returned by [`BinConfig::walk_iter`], [`BinTable::walk_iter`] and [`BinArray::walk_iter`].
+///
+/// [`paths`]: struct.ConfigPath.html
+/// [`values`]: type.BinConfigValue.html
+/// [`table`]: struct.BinTable.html
+/// [`array`]: struct.BinArray.html
+/// [`BinConfig::walk_iter`]: struct.BinConfig.html#method.walk_iter
+/// [`BinTable::walk_iter`]: struct.BinTable.html#method.walk_iter
+/// [`BinArray::walk_iter`]: struct.BinArray.html#method.walk_iter
+pub struct BinConfigWalk<'at> {
+    /// Stack of the currently visited tables / arrays, innermost last.
+    stack: Vec<BinConfigWalkLevel<'at>>,
+    /// Path to the innermost currently visited table / array.
+    path: ConfigPath,
+}
+
+/// A currently visited table / array and the index of its next value.
+enum BinConfigWalkLevel<'at> {
+    Table(BinTable<'at>, u32),
+    Array(BinArray<'at>, u32),
+}
+
+impl<'at> BinConfigWalk<'at> {
+    fn new(level: BinConfigWalkLevel<'at>) -> Self {
+        Self {
+            stack: vec![level],
+            path: ConfigPath::new(),
+        }
+    }
+}
+
+impl<'at> Iterator for BinConfigWalk<'at> {
+    type Item = (ConfigPath, BinConfigValue<'at>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (key, value): (OwnedConfigKey, _) = match self.stack.last_mut()? {
+                BinConfigWalkLevel::Table(table, index) if *index < table.len() => {
+                    let (key, value) = table.entry(*index);
+                    *index += 1;
+                    (key.into(), value)
+                }
+                BinConfigWalkLevel::Array(array, index) if *index < array.len() => {
+                    let key = *index;
+                    *index += 1;
+
+                    // Must succeed - the index is valid.
+                    let value =
+                        unwrap_unchecked(array.get_impl(key), "invalid index in array iterator");
+                    (key.into(), value)
+                }
+                // The innermost table / array is finished - continue with its parent.
+                _ => {
+                    self.stack.pop();
+                    self.path.pop();
+                    continue;
+                }
+            };
+
+            let mut path = self.path.clone();
+            path.push(key);
+
+            match &value {
+                Value::Table(table) => {
+                    self.stack
+                        .push(BinConfigWalkLevel::Table(BinTable::new(table.0.clone()), 0));
+                    self.path = path.clone();
+                }
+                Value::Array(array) => {
+                    self.stack
+                        .push(BinConfigWalkLevel::Array(BinArray::new(array.0.clone()), 0));
+                    self.path = path.clone();
+                }
+                _ => {}
+            }
+
+            return Some((path, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr, std::num::NonZeroU32};
+
+    #[test]
+    fn walk() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.table(None, 1).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.end().unwrap();
+        writer.table(None, 1).unwrap();
+        writer.bool(nestr!("bool"), true).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.table(nestr!("table"), 1).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        writer.end().unwrap();
+        writer.f64(nestr!("float"), 0.5).unwrap();
+        let config = BinConfig::new(writer.finish().unwrap()).unwrap();
+
+        let mut visited = Vec::new();
+
+        config.walk(|path, value| visited.push((path.to_string(), value.get_type())));
+
+        visited.sort_by(|(l, _), (r, _)| l.cmp(r));
+
+        assert_eq!(
+            visited,
+            vec![
+                ("array".to_owned(), ValueType::Array),
+                ("array[0]".to_owned(), ValueType::Table),
+                ("array[0].int".to_owned(), ValueType::I64),
+                ("array[1]".to_owned(), ValueType::Table),
+                ("array[1].bool".to_owned(), ValueType::Bool),
+                ("float".to_owned(), ValueType::F64),
+                ("table".to_owned(), ValueType::Table),
+                ("table.string".to_owned(), ValueType::String),
+            ]
+        );
+
+        // The iterator visits the values in the same order.
+        let mut walked = Vec::new();
+        config.walk(|path, value| walked.push((path.clone(), value.get_type())));
+
+        assert_eq!(
+            config
+                .walk_iter()
+                .map(|(path, value)| (path, value.get_type()))
+                .collect::<Vec<_>>(),
+            walked
+        );
+
+        // Nested values are visited after their parents.
+        let position = |path: &str| {
+            let path = path.parse::<ConfigPath>().unwrap();
+            walked
+                .iter()
+                .position(|(walked, _)| *walked == path)
+                .unwrap()
+        };
+        assert!(position("array") < position("array[1]"));
+        assert!(position("array[1]") < position("array[1].bool"));
+        assert!(position("table") < position("table.string"));
+
+        // Paths are relative to the walked table / array.
+        let array = config.root().get_array("array").unwrap();
+        assert_eq!(
+            array
+                .walk_iter()
+                .map(|(path, _)| path.to_string())
+                .collect::<Vec<_>>(),
+            vec!["[0]", "[0].int", "[1]", "[1].bool"]
+        );
+
+        assert_eq!(
+            config
+                .root()
+                .get_table("table")
+                .unwrap()
+                .walk_iter()
+                .map(|(path, value)| (path.to_string(), value.string().unwrap().to_owned()))
+                .collect::<Vec<_>>(),
+            vec![("string".to_owned(), "foo".to_owned())]
+        );
+    }
+}
@@ -1,15 +1,23 @@
 use {
-    super::{array_or_table::*, config::*, util::*, value::*},
+    super::{
+        array_or_table::*, codec::encode, config::*, keys::compress_keys, schema::write_schema,
+        typed::write_typed_arrays, util::*, value::*,
+    },
     crate::*,
     std::{
         collections::{hash_map::Entry, HashMap},
+        convert::Infallible,
         io::{Cursor, Seek, SeekFrom, Write},
         mem::size_of,
         num::NonZeroU32,
         str::from_utf8_unchecked,
+        sync::Arc,
     },
 };
 
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+use super::compression::compress;
+
 /// Represents a UTF-8 string interned by the binary config writer.
 /// Looked up by its hash.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -24,8 +32,32 @@ struct BinConfigWriterString {
 
 /// Provides an interface for recording of [`binary configs`].
 ///
+/// [`Table`] element keys may be passed as `&NonEmptyStr` or as plain `&str`;
+/// empty `&str` keys result in an [`EmptyKey`] error.
+///
 /// [`binary configs`]: struct.BinConfig.html
+/// [`Table`]: struct.BinTable.html
+/// [`EmptyKey`]: enum.BinConfigWriterError.html#variant.EmptyKey
 pub struct BinConfigWriter {
+    /// Version of the recorded binary config data blob.
+    version: BinConfigVersion,
+    /// Whether the key table of the recorded binary config data blob is compressed.
+    compress_keys: bool,
+    /// Whether the checksum of the recorded binary config data blob is written to its header.
+    checksum: bool,
+    /// Whether the schema block is embedded in the recorded binary config data blob.
+    embed_schema: bool,
+    /// Whether the values of all tables in the recorded binary config data blob are sorted by key hash.
+    sort_keys: bool,
+    /// Whether the typed copies of the numeric arrays are written to the recorded binary config data blob.
+    typed_arrays: bool,
+    /// Whether the elements of the `f64` arrays are stored as `f32`'s in the recorded binary config data blob.
+    f32_arrays: bool,
+    /// Compression algorithm / level of the recorded binary config data blob payload.
+    #[cfg(any(feature = "lz4", feature = "zstd"))]
+    compression: CompressionLevel,
+    /// User codec used to encode the recorded binary config data blob payload, if any.
+    codec: Option<Arc<dyn BinConfigCodec>>,
     /// Offset in bytes to the string section of the binary config data blob.
     data_offset: u32,
     /// Binary config data blob writer.
@@ -42,6 +74,12 @@ pub struct BinConfigWriter {
     string_writer: Vec<u8>,
     /// LIFO stack which contains the root table and any nested arrays/tables during recording.
     stack: Vec<BinConfigArrayOrTable>,
+    /// Whether the paths to the recorded arrays/tables are tracked for error reporting.
+    validate_paths: bool,
+    /// Last error (with its path) returned by the writer with path validation enabled.
+    last_error: Option<BinConfigWriterPathError>,
+    /// Maximum nesting depth of the recorded arrays/tables.
+    max_depth: u32,
 }
 
 impl BinConfigWriter {
@@ -51,13 +89,41 @@ impl BinConfigWriter {
     /// [`writer`]: struct.BinConfigWriter.html
     /// [`table`]: struct.BinTable.html
     pub fn new(len: std::num::NonZeroU32) -> Result<Self, BinConfigWriterError> {
+        Self::with_version(len, BinConfigVersion::CURRENT)
+    }
+
+    /// Creates a new [`binary config`] [`writer`] with the root [`table`] with `len` elements,
+    /// which records a binary config data blob of the [`version`],
+    /// e.g. to be loaded by previously shipped versions of the binary config reader.
+    ///
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`table`]: struct.BinTable.html
+    /// [`version`]: enum.BinConfigVersion.html
+    pub fn with_version(
+        len: std::num::NonZeroU32,
+        version: BinConfigVersion,
+    ) -> Result<Self, BinConfigWriterError> {
         let mut writer = Self {
+            version,
+            compress_keys: false,
+            checksum: false,
+            embed_schema: false,
+            sort_keys: false,
+            typed_arrays: false,
+            f32_arrays: false,
+            #[cfg(any(feature = "lz4", feature = "zstd"))]
+            compression: CompressionLevel::None,
+            codec: None,
             data_offset: 0,
             config_writer: Cursor::new(Vec::new()),
             strings: HashMap::new(),
             key_table: Vec::new(),
             string_writer: Vec::new(),
             stack: Vec::new(),
+            validate_paths: false,
+            last_error: None,
+            max_depth: DEFAULT_MAX_DEPTH,
         };
 
         // Write the config header / root table length, prepare to receive root table elements.
@@ -66,19 +132,306 @@ impl BinConfigWriter {
         Ok(writer)
     }
 
+    /// Sets whether the key table of the recorded binary config data blob is compressed.
+    ///
+    /// If `true`, the keys are sorted and the common prefixes of the keys (e.g. `render.shadow.cascade.0.` in
+    /// `render.shadow.cascade.0.bias` / `render.shadow.cascade.0.distance`) are stored only once.
+    /// Reduces the size of the data blob for configs with many long keys with common prefixes.
+    /// The key table is decompressed when the data blob is [`loaded`].
+    ///
+    /// Not supported by [`V1`] data blobs.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`loaded`]: struct.BinConfig.html#method.new
+    /// [`V1`]: enum.BinConfigVersion.html#variant.V1
+    pub fn compress_keys(&mut self, compress_keys: bool) -> Result<(), BinConfigWriterError> {
+        if compress_keys && self.version == BinConfigVersion::V1 {
+            return Err(BinConfigWriterError::KeyCompressionNotSupported(
+                self.version,
+            ));
+        }
+
+        self.compress_keys = compress_keys;
+
+        Ok(())
+    }
+
+    /// Sets whether the checksum (CRC-32) of the recorded binary config data blob is written to its header.
+    ///
+    /// If `true`, the checksum is verified when the data blob is [`loaded`],
+    /// so that corrupted data blobs (e.g. after an incomplete download) are reported as a [`checksum mismatch`].
+    ///
+    /// Only supported by [`V3`] data blobs.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`loaded`]: struct.BinConfig.html#method.new
+    /// [`checksum mismatch`]: enum.BinConfigError.html#variant.ChecksumMismatch
+    /// [`V3`]: enum.BinConfigVersion.html#variant.V3
+    pub fn checksum(&mut self, checksum: bool) -> Result<(), BinConfigWriterError> {
+        if checksum && self.version != BinConfigVersion::V3 {
+            return Err(BinConfigWriterError::ChecksumNotSupported(self.version));
+        }
+
+        self.checksum = checksum;
+
+        Ok(())
+    }
+
+    /// Sets whether the schema block (the keys, [`types`] and nesting of all values, but not the values themselves)
+    /// is embedded in the recorded binary config data blob.
+    ///
+    /// If `true`, the [`schema`] may be read from the [`loaded`] data blob without accessing the values,
+    /// e.g. by tooling which inspects / validates binary configs without knowing their expected contents.
+    ///
+    /// Only supported by [`V3`] data blobs.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`types`]: enum.ValueType.html
+    /// [`schema`]: struct.BinConfig.html#method.schema
+    /// [`loaded`]: struct.BinConfig.html#method.new
+    /// [`V3`]: enum.BinConfigVersion.html#variant.V3
+    pub fn embed_schema(&mut self, embed_schema: bool) -> Result<(), BinConfigWriterError> {
+        if embed_schema && self.version != BinConfigVersion::V3 {
+            return Err(BinConfigWriterError::SchemaNotSupported(self.version));
+        }
+
+        self.embed_schema = embed_schema;
+
+        Ok(())
+    }
+
+    /// Sets the compression algorithm / level of the recorded binary config data blob payload
+    /// (everything after the header - values, keys, strings, etc.).
+    ///
+    /// Binary configs are usually highly compressible. The payload is decompressed when the data blob is [`loaded`],
+    /// so access performance of the loaded config is unaffected.
+    /// May be combined with key table [`compression`], which is applied first.
+    ///
+    /// NOTE - requires `"lz4"` and / or `"zstd"` features.
+    /// Loading the data blob requires the same feature as the one used to record it.
+    ///
+    /// NOTE - payloads which compress better than `255:1` (e.g. consisting of a single huge repetitive string)
+    /// are recorded uncompressed, as larger payload sizes are rejected when the data blob is [`loaded`].
+    ///
+    /// Only supported by [`V3`] data blobs.
+    ///
+    /// Default: [`None`].
+    ///
+    /// [`loaded`]: struct.BinConfig.html#method.new
+    /// [`compression`]: #method.compress_keys
+    /// [`V3`]: enum.BinConfigVersion.html#variant.V3
+    /// [`None`]: enum.CompressionLevel.html#variant.None
+    #[cfg(any(feature = "lz4", feature = "zstd"))]
+    pub fn compression(
+        &mut self,
+        compression: CompressionLevel,
+    ) -> Result<(), BinConfigWriterError> {
+        if compression != CompressionLevel::None && self.version != BinConfigVersion::V3 {
+            return Err(BinConfigWriterError::CompressionNotSupported(self.version));
+        }
+
+        self.compression = compression;
+
+        Ok(())
+    }
+
+    /// Sets the user [`codec`] used to encode (e.g. encrypt) the recorded binary config data blob payload
+    /// (everything after the header - values, keys, strings, etc.), or `None` to store the payload as is.
+    ///
+    /// The payload is encoded after the key table / payload compression, if any.
+    /// The data blob must then be [`loaded`] with the same [`codec`].
+    ///
+    /// Only supported by [`V3`] data blobs.
+    ///
+    /// Default: `None`.
+    ///
+    /// [`codec`]: trait.BinConfigCodec.html
+    /// [`loaded`]: struct.BinConfig.html#method.with_codec
+    /// [`V3`]: enum.BinConfigVersion.html#variant.V3
+    pub fn codec(
+        &mut self,
+        codec: Option<Arc<dyn BinConfigCodec>>,
+    ) -> Result<(), BinConfigWriterError> {
+        if codec.is_some() && self.version != BinConfigVersion::V3 {
+            return Err(BinConfigWriterError::CodecNotSupported(self.version));
+        }
+
+        self.codec = codec;
+
+        Ok(())
+    }
+
+    /// Sets whether the values of all [`tables`] in the recorded binary config data blob are sorted by key hash.
+    ///
+    /// If `true`, [`table`] value lookups by key use binary search instead of a linear scan,
+    /// which is faster for large [`tables`] (with hundreds of elements or more).
+    /// [`Table`] iteration order is changed accordingly.
+    ///
+    /// Only supported by [`V3`] data blobs.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`tables`]: struct.BinTable.html
+    /// [`table`]: struct.BinTable.html#method.get
+    /// [`Table`]: struct.BinTable.html#method.iter
+    /// [`V3`]: enum.BinConfigVersion.html#variant.V3
+    pub fn sort_keys(&mut self, sort_keys: bool) -> Result<(), BinConfigWriterError> {
+        if sort_keys && self.version != BinConfigVersion::V3 {
+            return Err(BinConfigWriterError::KeySortingNotSupported(self.version));
+        }
+
+        self.sort_keys = sort_keys;
+
+        Ok(())
+    }
+
+    /// Sets whether the [`writer`] writes contiguous, aligned copies of the elements of the numeric [`arrays`]
+    /// (non-empty [`arrays`] whose elements are all `i64`'s or all `f64`'s) to the recorded binary config data blob.
+    ///
+    /// If `true`, such [`arrays`] may be accessed as slices via [`as_i64_slice`] / [`as_f64_slice`],
+    /// at the cost of increased data blob size.
+    ///
+    /// Only supported by [`V3`] data blobs.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`arrays`]: struct.BinArray.html
+    /// [`as_i64_slice`]: struct.BinArray.html#method.as_i64_slice
+    /// [`as_f64_slice`]: struct.BinArray.html#method.as_f64_slice
+    /// [`V3`]: enum.BinConfigVersion.html#variant.V3
+    pub fn typed_arrays(&mut self, typed_arrays: bool) -> Result<(), BinConfigWriterError> {
+        if typed_arrays && self.version != BinConfigVersion::V3 {
+            return Err(BinConfigWriterError::TypedArraysNotSupported(self.version));
+        }
+
+        self.typed_arrays = typed_arrays;
+
+        Ok(())
+    }
+
+    /// Sets whether the [`writer`] stores the elements of the [`arrays`] whose elements are all `f64`'s
+    /// as `f32`'s in the recorded binary config data blob, reducing the data blob size for large float [`arrays`].
+    ///
+    /// The elements are transparently widened to `f64`'s when accessed (e.g. via [`get_f64`]);
+    /// the loss of precision is the user's responsibility.
+    ///
+    /// NOTE - [`arrays`] written with this option enabled never have [`typed copies`].
+    ///
+    /// Only supported by [`V3`] data blobs.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`arrays`]: struct.BinArray.html
+    /// [`get_f64`]: struct.BinArray.html#method.get_f64
+    /// [`typed copies`]: #method.typed_arrays
+    /// [`V3`]: enum.BinConfigVersion.html#variant.V3
+    pub fn f32_arrays(&mut self, f32_arrays: bool) -> Result<(), BinConfigWriterError> {
+        if f32_arrays && self.version != BinConfigVersion::V3 {
+            return Err(BinConfigWriterError::F32ArraysNotSupported(self.version));
+        }
+
+        self.f32_arrays = f32_arrays;
+
+        Ok(())
+    }
+
+    /// Sets whether the [`writer`] tracks the paths to the recorded [`arrays`] / [`tables`]
+    /// to report the [`path`] to the config element which caused the last error returned by the [`writer`]
+    /// (e.g. a duplicate key, or an [`array`] / [`table`] [`end`]ed with the wrong number of elements).
+    ///
+    /// The last error and its path are returned by [`last_error`].
+    ///
+    /// NOTE - must be enabled before any [`arrays`] / [`tables`] are written,
+    /// otherwise the paths to the [`arrays`] / [`tables`] written before are not tracked.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`path`]: struct.ConfigPath.html
+    /// [`array`]: struct.BinArray.html
+    /// [`table`]: struct.BinTable.html
+    /// [`end`]: #method.end
+    /// [`last_error`]: #method.last_error
+    pub fn validate_paths(&mut self, validate_paths: bool) {
+        self.validate_paths = validate_paths;
+    }
+
+    /// Sets the maximum nesting depth of the recorded [`arrays`] / [`tables`]
+    /// (the [`arrays`] / [`tables`] in the root [`table`] are at depth `1`).
+    ///
+    /// Writing an [`array`] / [`table`] nested deeper returns a [`MaxDepthExceeded`] error,
+    /// so that the recorded data blob is never rejected by the [`binary config`] loaded with the same maximum nesting depth.
+    ///
+    /// Default: [`DEFAULT_MAX_DEPTH`].
+    ///
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`table`]: struct.BinTable.html
+    /// [`array`]: struct.BinArray.html
+    /// [`MaxDepthExceeded`]: enum.BinConfigWriterError.html#variant.MaxDepthExceeded
+    /// [`binary config`]: struct.BinConfig.html#method.with_max_depth
+    /// [`DEFAULT_MAX_DEPTH`]: constant.DEFAULT_MAX_DEPTH.html
+    pub fn max_depth(&mut self, max_depth: u32) {
+        self.max_depth = max_depth;
+    }
+
+    /// Returns the last error returned by the [`writer`] with the [`path`] to the config element which caused it,
+    /// if [`path validation`] is enabled and any errors occured.
+    ///
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`path`]: struct.ConfigPath.html
+    /// [`path validation`]: #method.validate_paths
+    pub fn last_error(&self) -> Option<&BinConfigWriterPathError> {
+        self.last_error.as_ref()
+    }
+
+    /// Returns the [`path`] to the currently recorded [`array`] / [`table`]
+    /// (empty for the root [`table`]), if [`path validation`] is enabled.
+    ///
+    /// E.g. may be called before [`finish`] to find the [`arrays`] / [`tables`] which were not [`end`]ed.
+    ///
+    /// [`path`]: struct.ConfigPath.html
+    /// [`array`]: struct.BinArray.html
+    /// [`table`]: struct.BinTable.html
+    /// [`path validation`]: #method.validate_paths
+    /// [`finish`]: #method.finish
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`end`]: #method.end
+    pub fn path(&self) -> Option<ConfigPath> {
+        if self.validate_paths {
+            Some(ConfigPath(
+                self.stack
+                    .iter()
+                    .filter_map(|array_or_table| array_or_table.key.clone())
+                    .collect(),
+            ))
+        } else {
+            None
+        }
+    }
+
     /// Writes a `bool` value to the current [`array`] / [`table`] (including the root [`table`]).
     ///
     /// NOTE - a non-empty UTF-8 string `key` is required for a [`table`] element (including the root [`table`]).
     ///
     /// [`array`]: struct.BinArray.html
     /// [`table`]: struct.BinTable.html
-    pub fn bool<'k, K: Into<Option<&'k NonEmptyStr>>>(
+    pub fn bool<'k, K: IntoOptionalTableKey<'k>>(
         &mut self,
         key: K,
         value: bool,
     ) -> Result<(), BinConfigWriterError> {
         // Value's key and its offset in bytes.
-        let (key, value_offset) = self.key_and_value_offset(key.into(), ValueType::Bool)?;
+        let (key, value_offset) =
+            self.key_and_value_offset(key.into_optional_table_key()?, ValueType::Bool)?;
 
         // Write the packed value.
         Self::write_value(
@@ -97,13 +450,14 @@ impl BinConfigWriter {
     ///
     /// [`array`]: struct.BinArray.html
     /// [`table`]: struct.BinTable.html
-    pub fn i64<'k, K: Into<Option<&'k NonEmptyStr>>>(
+    pub fn i64<'k, K: IntoOptionalTableKey<'k>>(
         &mut self,
         key: K,
         value: i64,
     ) -> Result<(), BinConfigWriterError> {
         // Value's key and its offset in bytes.
-        let (key, value_offset) = self.key_and_value_offset(key.into(), ValueType::I64)?;
+        let (key, value_offset) =
+            self.key_and_value_offset(key.into_optional_table_key()?, ValueType::I64)?;
 
         // Write the packed value.
         Self::write_value(
@@ -122,13 +476,14 @@ impl BinConfigWriter {
     ///
     /// [`array`]: struct.BinArray.html
     /// [`table`]: struct.BinTable.html
-    pub fn f64<'k, K: Into<Option<&'k NonEmptyStr>>>(
+    pub fn f64<'k, K: IntoOptionalTableKey<'k>>(
         &mut self,
         key: K,
         value: f64,
     ) -> Result<(), BinConfigWriterError> {
         // Value's key and its offset in bytes.
-        let (key, value_offset) = self.key_and_value_offset(key.into(), ValueType::F64)?;
+        let (key, value_offset) =
+            self.key_and_value_offset(key.into_optional_table_key()?, ValueType::F64)?;
 
         // Write the packed value.
         Self::write_value(
@@ -147,13 +502,14 @@ impl BinConfigWriter {
     ///
     /// [`array`]: struct.BinArray.html
     /// [`table`]: struct.BinTable.html
-    pub fn string<'k, K: Into<Option<&'k NonEmptyStr>>>(
+    pub fn string<'k, K: IntoOptionalTableKey<'k>>(
         &mut self,
         key: K,
         value: &str,
     ) -> Result<(), BinConfigWriterError> {
         // Value's key and its offset in bytes.
-        let (key, value_offset) = self.key_and_value_offset(key.into(), ValueType::String)?;
+        let (key, value_offset) =
+            self.key_and_value_offset(key.into_optional_table_key()?, ValueType::String)?;
 
         // Lookup or intern the string.
         let (_, string) =
@@ -174,6 +530,48 @@ impl BinConfigWriter {
         Ok(())
     }
 
+    /// Writes a [`date/time`] value to the current [`array`] / [`table`] (including the root [`table`]).
+    ///
+    /// NOTE - a non-empty string `key` is required for a [`table`] element (including the root [`table`]).
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// [`date/time`]: struct.DateTime.html
+    /// [`array`]: struct.BinArray.html
+    /// [`table`]: struct.BinTable.html
+    #[cfg(feature = "datetime")]
+    pub fn datetime<'k, K: IntoOptionalTableKey<'k>>(
+        &mut self,
+        key: K,
+        value: DateTime,
+    ) -> Result<(), BinConfigWriterError> {
+        // Value's key and its offset in bytes.
+        let (key, value_offset) =
+            self.key_and_value_offset(key.into_optional_table_key()?, ValueType::DateTime)?;
+
+        // Date/time values are stored as RFC 3339 strings.
+        // Lookup or intern the string.
+        let (_, string) = Self::intern_string(
+            &mut self.strings,
+            None,
+            &mut self.string_writer,
+            &value.to_string(),
+        )?;
+
+        // Write the packed value.
+        Self::write_value(
+            &mut self.config_writer,
+            &mut self.stack,
+            BinConfigPackedValue::new_datetime(
+                key,
+                string.offset_and_len.offset,
+                string.offset_and_len.len,
+            ),
+            value_offset,
+        )?;
+
+        Ok(())
+    }
+
     /// Writes an array value with `len` elements to the current [`array`] / [`table`] (including the root [`table`])
     /// and makes it the active array for the next `len` calls to this [`writer`]'s methods.
     ///
@@ -184,12 +582,54 @@ impl BinConfigWriter {
     /// [`table`]: struct.BinTable.html
     /// [`writer`]: struct.BinConfigWriter.html
     /// [`end`]: #method.end
-    pub fn array<'k, K: Into<Option<&'k NonEmptyStr>>>(
+    pub fn array<'k, K: IntoOptionalTableKey<'k>>(
         &mut self,
         key: K,
         len: u32,
     ) -> Result<(), BinConfigWriterError> {
-        self.array_or_table(key.into(), len, false)
+        self.array_or_table(key.into_optional_table_key()?, len, false)
+    }
+
+    /// Writes an array value with all primitive `values` as its elements to the current [`array`] / [`table`]
+    /// (including the root [`table`]).
+    ///
+    /// Equivalent to calling [`array`](#method.array) with the number of `values`,
+    /// writing each of the `values` in order and calling [`end`].
+    ///
+    /// NOTE - a non-empty string `key` is required for a [`table`] element (including the root [`table`]).
+    /// NOTE - nested arrays / tables must be written with [`array`](#method.array) / [`table`](#method.table) and [`end`].
+    ///
+    /// [`array`]: struct.BinArray.html
+    /// [`table`]: struct.BinTable.html
+    /// [`end`]: #method.end
+    pub fn array_from_iter<'k, 's, K, I, V>(
+        &mut self,
+        key: K,
+        values: I,
+    ) -> Result<(), BinConfigWriterError>
+    where
+        K: IntoOptionalTableKey<'k>,
+        I: IntoIterator<Item = V>,
+        I::IntoIter: ExactSizeIterator,
+        V: Into<Value<&'s str, Infallible, Infallible>>,
+    {
+        let values = values.into_iter();
+
+        self.array(key, values.len() as _)?;
+
+        for value in values {
+            match value.into() {
+                Value::Bool(value) => self.bool(None, value)?,
+                Value::I64(value) => self.i64(None, value)?,
+                Value::F64(value) => self.f64(None, value)?,
+                Value::String(value) => self.string(None, value)?,
+                #[cfg(feature = "datetime")]
+                Value::DateTime(value) => self.datetime(None, value)?,
+                Value::Array(never) | Value::Table(never) => match never {},
+            }
+        }
+
+        self.end()
     }
 
     /// Writes a table value with `len` elements to the current [`array`] / [`table`] (including the root [`table`])
@@ -202,12 +642,12 @@ impl BinConfigWriter {
     /// [`table`]: struct.BinTable.html
     /// [`writer`]: struct.BinConfigWriter.html
     /// [`end`]: #method.end
-    pub fn table<'k, K: Into<Option<&'k NonEmptyStr>>>(
+    pub fn table<'k, K: IntoOptionalTableKey<'k>>(
         &mut self,
         key: K,
         len: u32,
     ) -> Result<(), BinConfigWriterError> {
-        self.array_or_table(key.into(), len, true)
+        self.array_or_table(key.into_optional_table_key()?, len, true)
     }
 
     /// Ends the recording of the previous [`array`] / [`table`].
@@ -215,6 +655,17 @@ impl BinConfigWriter {
     /// [`array`]: #method.array
     /// [`table`]: #method.table
     pub fn end(&mut self) -> Result<(), BinConfigWriterError> {
+        // Both for mismatched calls and for length mismatches the error path is the current array's/table's path.
+        let result = self.end_impl();
+
+        if let Err(error) = result {
+            self.record_error(None, error);
+        }
+
+        result
+    }
+
+    fn end_impl(&mut self) -> Result<(), BinConfigWriterError> {
         use BinConfigWriterError::*;
 
         // Must have an array/non-root table on the stack (excluding the root table).
@@ -240,6 +691,11 @@ impl BinConfigWriter {
                 });
             }
 
+            // Store the array elements as `f32`'s, if necessary.
+            if self.f32_arrays && !parent.table {
+                self.pack_f32_array(&parent);
+            }
+
             Ok(())
 
         // Shouldn't get here, case handled above.
@@ -273,6 +729,21 @@ impl BinConfigWriter {
             });
         };
 
+        let mut flags = 0;
+
+        // Append the typed copies of the numeric arrays, if necessary.
+        if self.typed_arrays {
+            let data = self.config_writer.get_mut();
+            write_typed_arrays(data, self.version, root.len);
+            self.data_offset = data.len() as u32;
+
+            flags |= BIN_CONFIG_HEADER_FLAG_TYPED_ARRAYS;
+        }
+
+        if self.f32_arrays {
+            flags |= BIN_CONFIG_HEADER_FLAG_F32_ARRAYS;
+        }
+
         // Fixup the header with correct key table offset and length.
         let key_table_offset = self.data_offset;
         let key_table_len = self.key_table.len() as u32;
@@ -281,6 +752,8 @@ impl BinConfigWriter {
 
         BinConfigHeader::write(
             &mut self.config_writer,
+            self.version,
+            flags,
             root.len,
             key_table_offset,
             key_table_len,
@@ -314,7 +787,40 @@ impl BinConfigWriter {
         // via incrementing them by the now-known data offset.
         let mut data = config_writer.into_boxed_slice();
 
-        Self::fixup_string_offsets(&mut data, string_offset);
+        Self::fixup_string_offsets(&mut data, self.version, root.len, string_offset);
+
+        // Sort the table values by key hash, if necessary.
+        if self.sort_keys {
+            Self::sort_table_values(&mut data, self.version, root.len);
+        }
+
+        // Compress the key table, if necessary.
+        if self.compress_keys {
+            data = compress_keys(&data);
+        }
+
+        // Embed the schema block, if necessary.
+        if self.embed_schema {
+            data = write_schema(&data);
+        }
+
+        // Compress the payload, if necessary.
+        #[cfg(any(feature = "lz4", feature = "zstd"))]
+        {
+            if self.compression != CompressionLevel::None {
+                data = compress(&data, self.compression)?;
+            }
+        }
+
+        // Encode the payload, if necessary.
+        if let Some(codec) = self.codec.as_ref() {
+            data = encode(&data, codec.as_ref());
+        }
+
+        // Write the checksum, if necessary.
+        if self.checksum {
+            BinConfigHeader::write_checksum(&mut data);
+        }
 
         Ok(data)
     }
@@ -328,6 +834,8 @@ impl BinConfigWriter {
         // Write the header.
         self.data_offset += BinConfigHeader::write(
             &mut self.config_writer,
+            self.version,
+            0,
             len.get(),
             0, // NOTE - fixed up when the recording is finished.
             0, // NOTE - fixed up when the recording is finished.
@@ -352,6 +860,9 @@ impl BinConfigWriter {
         len: u32,
         table: bool,
     ) -> Result<(), BinConfigWriterError> {
+        // Must get the element key before the parent array's/table's length is incremented.
+        let path_key = self.element_key(key);
+
         // Offset to the array's/table's packed value is the parent array's/table's value offset.
         let (key, value_offset) = self.key_and_value_offset(
             key,
@@ -372,8 +883,9 @@ impl BinConfigWriter {
         )?;
 
         // Push the array/table on the stack.
-        self.stack
-            .push(BinConfigArrayOrTable::new(table, len, self.data_offset));
+        let mut array_or_table = BinConfigArrayOrTable::new(table, len, self.data_offset);
+        array_or_table.key = path_key;
+        self.stack.push(array_or_table);
 
         // Bump the data offset by the combined value length.
         self.data_offset += len * size_of::<BinConfigPackedValue>() as u32;
@@ -568,9 +1080,32 @@ impl BinConfigWriter {
         &mut self,
         key: Option<&NonEmptyStr>,
         value_type: ValueType,
+    ) -> Result<(BinTableKey, u32), BinConfigWriterError> {
+        let result = self.key_and_value_offset_impl(key, value_type);
+
+        if let Err(error) = result {
+            let key = self.element_key(key);
+            self.record_error(key, error);
+        }
+
+        result
+    }
+
+    fn key_and_value_offset_impl(
+        &mut self,
+        key: Option<&NonEmptyStr>,
+        value_type: ValueType,
     ) -> Result<(BinTableKey, u32), BinConfigWriterError> {
         use BinConfigWriterError::*;
 
+        // Make sure nested arrays/tables do not exceed the maximum nesting depth.
+        // NOTE - the stack contains the root table.
+        if matches!(value_type, ValueType::Array | ValueType::Table)
+            && self.stack.len() as u32 > self.max_depth
+        {
+            return Err(MaxDepthExceeded(self.max_depth));
+        }
+
         // Parent array/table; offset to current value.
         let (parent, value_offset) = Self::parent_and_value_offset(&mut self.stack)?;
 
@@ -600,6 +1135,32 @@ impl BinConfigWriter {
         Ok((key, value_offset))
     }
 
+    /// Returns the key of the next element of the current array/table, if path validation is enabled:
+    /// the `key` for table elements (if any) or the current length for array elements.
+    fn element_key(&self, key: Option<&NonEmptyStr>) -> Option<OwnedConfigKey> {
+        if !self.validate_paths {
+            return None;
+        }
+
+        let parent = self.stack.last()?;
+
+        if parent.table {
+            key.map(OwnedConfigKey::from)
+        } else {
+            Some(OwnedConfigKey::Array(parent.current_len))
+        }
+    }
+
+    /// If path validation is enabled, records the `error` at the path to the current array/table,
+    /// optionally followed by the element `key`.
+    fn record_error(&mut self, key: Option<OwnedConfigKey>, error: BinConfigWriterError) {
+        if let Some(mut path) = self.path() {
+            path.0.extend(key);
+            self.last_error
+                .replace(BinConfigWriterPathError { path, error });
+        }
+    }
+
     /// Returns the current parent array/table.
     /// and the offset in bytes w.r.t. config data blob to its current value.
     /// Checks if the current parent array/table is full.
@@ -647,13 +1208,16 @@ impl BinConfigWriter {
 
     /// Adds `string_offset` to all string offsets in the binary config `data` blob
     /// to transform the from offset w.r.t. the string section to offsets w.r.t. the data blob.
-    fn fixup_string_offsets(data: &mut [u8], string_offset: u32) {
-        let header = unsafe { &mut *(data.as_mut_ptr() as *mut BinConfigHeader) };
-        let len = header.len();
-
+    /// NOTE - the root table with `len` elements immediately follows the header of the `version`.
+    fn fixup_string_offsets(
+        data: &mut [u8],
+        version: BinConfigVersion,
+        len: u32,
+        string_offset: u32,
+    ) {
         let base = data.as_mut_ptr() as *mut u8;
 
-        let begin = unsafe { base.add(size_of::<BinConfigHeader>()) as *mut BinConfigPackedValue };
+        let begin = unsafe { base.add(version.header_size() as _) as *mut BinConfigPackedValue };
         let values = unsafe { std::slice::from_raw_parts_mut(begin, len as _) };
 
         Self::fixup_string_offsets_impl(base, values, string_offset);
@@ -670,8 +1234,14 @@ impl BinConfigWriter {
                 ValueType::String => {
                     value.set_offset(value.offset() + string_offset);
                 }
+                // Date/time values are stored as strings.
+                #[cfg(feature = "datetime")]
+                ValueType::DateTime => {
+                    value.set_offset(value.offset() + string_offset);
+                }
                 // If the value is an array/table, process its elements recursively.
-                ValueType::Array | ValueType::Table => {
+                // Arrays with elements stored as `f32`'s have no nested values.
+                ValueType::Array | ValueType::Table if !value.is_f32_array() => {
                     let begin = unsafe {
                         base.offset(value.offset() as isize) as *mut BinConfigPackedValue
                     };
@@ -684,6 +1254,98 @@ impl BinConfigWriter {
         }
     }
 
+    /// If the just finished non-empty `array` elements are all `f64`'s, replaces its packed values
+    /// (which are the last values written) with the `f32` elements and marks the array's packed value in its parent array / table.
+    fn pack_f32_array(&mut self, array: &BinConfigArrayOrTable) {
+        let value_size = size_of::<BinConfigPackedValue>() as u32;
+
+        // Arrays with nested arrays / tables are never stored as `f32`'s.
+        if array.len == 0 || self.data_offset != array.value_offset {
+            return;
+        }
+
+        let offset = array.value_offset - array.len * value_size;
+        let data = self.config_writer.get_mut();
+
+        let mut elements = Vec::with_capacity(array.len as usize);
+
+        for value in
+            data[offset as usize..array.value_offset as usize].chunks_exact(value_size as _)
+        {
+            let value = unsafe { &*(value.as_ptr() as *const BinConfigPackedValue) };
+
+            match value.unpack() {
+                BinConfigUnpackedValue::F64(value) => elements.push(value as f32),
+                _ => return,
+            }
+        }
+
+        data.truncate(offset as _);
+
+        for element in elements.into_iter() {
+            data.extend_from_slice(&u32_to_bin_bytes(element.to_bits()));
+        }
+
+        // Must succeed - the `f32` elements take less space than the packed values.
+        let size = f32_array_size(array.len).expect("invalid `f32` array size");
+        data.resize((offset + size) as _, 0);
+
+        self.data_offset = offset + size;
+
+        // The array's packed value is the last value written to its parent array / table.
+        // Must succeed - the array has a parent array / table.
+        let parent = self.stack.last().expect("empty binary config writer stack");
+        let value_offset = parent.value_offset - value_size;
+
+        let value = unsafe {
+            &mut *(data.as_mut_ptr().add(value_offset as _) as *mut BinConfigPackedValue)
+        };
+        value.set_f32_array();
+    }
+
+    /// Sorts the values of all tables in the binary config `data` blob by key hash
+    /// and sets the `BIN_CONFIG_HEADER_FLAG_SORTED_KEYS` flag in its header.
+    fn sort_table_values(data: &mut [u8], version: BinConfigVersion, len: u32) {
+        let base = data.as_mut_ptr();
+
+        let begin = unsafe { base.add(version.header_size() as _) as *mut BinConfigPackedValue };
+        let values = unsafe { std::slice::from_raw_parts_mut(begin, len as _) };
+
+        Self::sort_table_values_impl(base, values, true);
+
+        // The data blob was just written.
+        let flags = BinConfigHeader::read(data)
+            .expect("invalid binary config header")
+            .flags();
+
+        BinConfigHeader::write_flags(data, flags | BIN_CONFIG_HEADER_FLAG_SORTED_KEYS);
+    }
+
+    fn sort_table_values_impl(base: *mut u8, values: &mut [BinConfigPackedValue], table: bool) {
+        // Nested array/table values are not moved, so their offsets remain valid.
+        if table {
+            values.sort_by_key(|value| value.key().hash);
+        }
+
+        for value in values.iter_mut() {
+            match value.value_type() {
+                // If the value is a non-empty array/table, process its elements recursively.
+                // Arrays with elements stored as `f32`'s have no nested values.
+                value_type @ ValueType::Array | value_type @ ValueType::Table
+                    if value.len() > 0 && !value.is_f32_array() =>
+                {
+                    let begin = unsafe {
+                        base.offset(value.offset() as isize) as *mut BinConfigPackedValue
+                    };
+                    let values = unsafe { std::slice::from_raw_parts_mut(begin, value.len() as _) };
+
+                    Self::sort_table_values_impl(base, values, value_type == ValueType::Table);
+                }
+                _ => {}
+            }
+        }
+    }
+
     fn write_value(
         config_writer: &mut Cursor<Vec<u8>>,
         stack: &mut Vec<BinConfigArrayOrTable>,
@@ -715,6 +1377,9 @@ struct BinConfigArrayOrTable {
     keys: HashMap<StringHash, Vec<StringIndex>>,
     // For arrays must keep track of value type to ensure no mixed arrays.
     array_type: Option<ValueType>,
+    // Key / index of the array/table in its parent array/table, if path validation is enabled.
+    // `None` for the root table.
+    key: Option<OwnedConfigKey>,
 }
 
 impl BinConfigArrayOrTable {
@@ -726,6 +1391,7 @@ impl BinConfigArrayOrTable {
             value_offset,
             keys: HashMap::new(),
             array_type: None,
+            key: None,
         }
     }
 }
@@ -781,6 +1447,26 @@ mod tests {
         writer.bool(None, false).unwrap();
     }
 
+    #[test]
+    fn EmptyKey() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+        assert_eq!(
+            writer.bool("", true).err().unwrap(),
+            BinConfigWriterError::EmptyKey
+        );
+
+        // But this works.
+
+        writer.bool("bool", true).unwrap();
+        writer.i64(&String::from("int"), 7).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+
+        let config = BinConfig::new(writer.finish().unwrap()).unwrap();
+
+        assert!(config.root().get_bool("bool").unwrap());
+        assert_eq!(config.root().get_i64("int").unwrap(), 7);
+    }
+
     #[test]
     fn NonUniqueKey() {
         let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
@@ -948,6 +1634,208 @@ mod tests {
         writer.finish().unwrap();
     }
 
+    #[test]
+    fn validate_paths() {
+        let path = |path: &str| path.parse::<ConfigPath>().unwrap();
+
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.validate_paths(true);
+
+        assert_eq!(writer.path().unwrap(), ConfigPath::new());
+
+        writer.table(nestr!("player"), 3).unwrap();
+        writer.array(nestr!("items"), 2).unwrap();
+        writer.table(None, 1).unwrap();
+
+        assert_eq!(writer.path().unwrap(), path("player.items[0]"));
+
+        // Too many elements.
+        writer.string(nestr!("name"), "sword").unwrap();
+        assert_eq!(
+            writer.string(nestr!("name"), "shield").err().unwrap(),
+            BinConfigWriterError::ArrayOrTableLengthMismatch {
+                expected: 1,
+                found: 2
+            }
+        );
+        assert_eq!(
+            writer.last_error().unwrap(),
+            &BinConfigWriterPathError {
+                path: path("player.items[0].name"),
+                error: BinConfigWriterError::ArrayOrTableLengthMismatch {
+                    expected: 1,
+                    found: 2
+                },
+            }
+        );
+        // The writer error is exposed as the error source.
+        assert_eq!(
+            std::error::Error::source(writer.last_error().unwrap())
+                .unwrap()
+                .downcast_ref::<BinConfigWriterError>()
+                .unwrap(),
+            &BinConfigWriterError::ArrayOrTableLengthMismatch {
+                expected: 1,
+                found: 2
+            }
+        );
+        writer.end().unwrap();
+
+        // Mixed array.
+        assert_eq!(
+            writer.bool(None, true).err().unwrap(),
+            BinConfigWriterError::MixedArray {
+                expected: ValueType::Table,
+                found: ValueType::Bool
+            }
+        );
+        assert_eq!(writer.last_error().unwrap().path, path("player.items[1]"));
+
+        // Too few elements.
+        assert_eq!(
+            writer.end().err().unwrap(),
+            BinConfigWriterError::ArrayOrTableLengthMismatch {
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            writer.last_error().unwrap().to_string(),
+            "mismatch between declared array/table length (2) and actual number of elements provided (1) (at \"player.items\")"
+        );
+
+        writer.table(None, 0).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+
+        // Duplicate key.
+        writer.i64(nestr!("level"), 7).unwrap();
+        assert_eq!(
+            writer.i64(nestr!("level"), 8).err().unwrap(),
+            BinConfigWriterError::NonUniqueKey
+        );
+        assert_eq!(writer.last_error().unwrap().path, path("player.level"));
+        writer.string(nestr!("name"), "hero").unwrap();
+        writer.end().unwrap();
+
+        // Unbalanced `end`.
+        assert_eq!(
+            writer.end().err().unwrap(),
+            BinConfigWriterError::EndCallMismatch
+        );
+        assert_eq!(
+            writer.last_error().unwrap().to_string(),
+            "mismatched call to `end` (expected a previous call to `array`/`table`) (at the root table)"
+        );
+
+        writer.bool(nestr!("bool"), true).unwrap();
+        writer.finish().unwrap();
+
+        // Paths are not tracked by default.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.end().err().unwrap();
+        assert!(writer.path().is_none());
+        assert!(writer.last_error().is_none());
+    }
+
+    #[test]
+    fn max_depth() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+        writer.max_depth(2);
+        writer.validate_paths(true);
+
+        writer.table(nestr!("foo"), 1).unwrap();
+        writer.array(nestr!("bar"), 1).unwrap();
+        assert_eq!(
+            writer.table(None, 0).err().unwrap(),
+            BinConfigWriterError::MaxDepthExceeded(2)
+        );
+        assert_eq!(
+            writer.last_error().unwrap().path,
+            "foo.bar[0]".parse::<ConfigPath>().unwrap()
+        );
+
+        // Primitive values are fine.
+        writer.i64(None, 7).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn array_from_iter() {
+        let tags = [String::from("foo"), String::from("bar")];
+
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(4).unwrap()).unwrap();
+        writer
+            .array_from_iter(nestr!("tags"), tags.iter().map(String::as_str))
+            .unwrap();
+        writer
+            .array_from_iter(nestr!("numbers"), vec![1.0, 2.5, 3.0])
+            .unwrap();
+        writer
+            .array_from_iter(nestr!("empty"), Vec::<bool>::new())
+            .unwrap();
+        assert_eq!(
+            writer.array_from_iter(None, vec![7]).err().unwrap(),
+            BinConfigWriterError::TableKeyRequired
+        );
+        writer.bool(nestr!("bool"), true).unwrap();
+
+        let config = BinConfig::new(writer.finish().unwrap()).unwrap();
+
+        let array = config.root().get_array("tags").unwrap();
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.get_string(0).unwrap(), "foo");
+        assert_eq!(array.get_string(1).unwrap(), "bar");
+
+        let array = config.root().get_array("numbers").unwrap();
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get_f64(1).unwrap(), 2.5);
+
+        assert!(config.root().get_array("empty").unwrap().is_empty());
+    }
+
+    #[cfg(feature = "datetime")]
+    #[test]
+    fn datetime() {
+        let datetime: DateTime = "2024-01-01T10:00:00Z".parse().unwrap();
+
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.datetime(nestr!("datetime"), datetime).unwrap();
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.datetime(None, datetime).unwrap();
+        assert_eq!(
+            writer.string(None, "2024-01-01T10:00:00Z").err().unwrap(),
+            BinConfigWriterError::MixedArray {
+                expected: ValueType::DateTime,
+                found: ValueType::String
+            }
+        );
+        writer
+            .datetime(None, "1985-04-12T23:20:50.52-05:00".parse().unwrap())
+            .unwrap();
+        writer.end().unwrap();
+
+        let config = BinConfig::new(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(
+            config.root().get::<_, DateTime>("datetime").unwrap(),
+            datetime
+        );
+        assert_eq!(
+            config.root().get_string("datetime").err().unwrap(),
+            TableError::IncorrectValueType(ValueType::DateTime)
+        );
+
+        let array = config.root().get_array("array").unwrap();
+        assert_eq!(array.get::<DateTime>(0).unwrap(), datetime);
+        assert_eq!(
+            array.get::<DateTime>(1).unwrap().to_string(),
+            "1985-04-12T23:20:50.52-05:00"
+        );
+    }
+
     // "array_value = { 54, 12, 78.9 } -- array_value
     // bool_value = true
     // float_value = 3.14
@@ -988,19 +1876,19 @@ mod tests {
 
         let config = BinConfig::new(data).unwrap();
 
-        assert!(!config.root().contains("missing_value".into()));
+        assert!(!config.root().contains("missing_value"));
         #[cfg(feature = "str_hash")]
         {
             assert!(!config.root().contains(key!("missing_value")));
         }
 
-        assert!(config.root().contains("array_value".into()));
+        assert!(config.root().contains("array_value"));
         #[cfg(feature = "str_hash")]
         {
             assert!(config.root().contains(key!("array_value")));
         }
 
-        let array_value = config.root().get_array("array_value".into()).unwrap();
+        let array_value = config.root().get_array("array_value").unwrap();
 
         assert_eq!(array_value.len(), 3);
         assert_eq!(array_value.get_i64(0).unwrap(), 54);
@@ -1010,26 +1898,23 @@ mod tests {
         assert_eq!(array_value.get_i64(2).unwrap(), 78);
         assert!(cmp_f64(array_value.get_f64(2).unwrap(), 78.9));
 
-        assert!(config.root().contains("bool_value".into()));
+        assert!(config.root().contains("bool_value"));
         #[cfg(feature = "str_hash")]
         {
             assert!(config.root().contains(key!("bool_value")));
         }
-        assert_eq!(config.root().get_bool("bool_value".into()).unwrap(), true);
+        assert_eq!(config.root().get_bool("bool_value").unwrap(), true);
         #[cfg(feature = "str_hash")]
         {
             assert_eq!(config.root().get_bool(key!("bool_value")).unwrap(), true);
         }
 
-        assert!(config.root().contains("float_value".into()));
+        assert!(config.root().contains("float_value"));
         #[cfg(feature = "str_hash")]
         {
             assert!(config.root().contains(key!("float_value")));
         }
-        assert!(cmp_f64(
-            config.root().get_f64("float_value".into()).unwrap(),
-            3.14
-        ));
+        assert!(cmp_f64(config.root().get_f64("float_value").unwrap(), 3.14));
         #[cfg(feature = "str_hash")]
         {
             assert!(cmp_f64(
@@ -1038,28 +1923,25 @@ mod tests {
             ));
         }
 
-        assert!(config.root().contains("int_value".into()));
+        assert!(config.root().contains("int_value"));
         #[cfg(feature = "str_hash")]
         {
             assert!(config.root().contains(key!("int_value")));
         }
 
-        assert_eq!(config.root().get_i64("int_value".into()).unwrap(), 7);
+        assert_eq!(config.root().get_i64("int_value").unwrap(), 7);
         #[cfg(feature = "str_hash")]
         {
             assert_eq!(config.root().get_i64(key!("int_value")).unwrap(), 7);
         }
 
-        assert!(config.root().contains("string_value".into()));
+        assert!(config.root().contains("string_value"));
         #[cfg(feature = "str_hash")]
         {
             assert!(config.root().contains(key!("string_value")));
         }
 
-        assert_eq!(
-            config.root().get_string("string_value".into()).unwrap(),
-            "foo"
-        );
+        assert_eq!(config.root().get_string("string_value").unwrap(), "foo");
         #[cfg(feature = "str_hash")]
         {
             assert_eq!(
@@ -1068,51 +1950,51 @@ mod tests {
             );
         }
 
-        assert!(config.root().contains("table_value".into()));
+        assert!(config.root().contains("table_value"));
         #[cfg(feature = "str_hash")]
         {
             assert!(config.root().contains(key!("table_value")));
         }
 
-        let table_value = config.root().get_table("table_value".into()).unwrap();
+        let table_value = config.root().get_table("table_value").unwrap();
 
         assert_eq!(table_value.len(), 3);
-        assert!(table_value.contains("bar".into()));
+        assert!(table_value.contains("bar"));
         #[cfg(feature = "str_hash")]
         {
             assert!(table_value.contains(key!("bar")));
         }
-        assert_eq!(table_value.get_i64("bar".into()).unwrap(), 2020);
+        assert_eq!(table_value.get_i64("bar").unwrap(), 2020);
         #[cfg(feature = "str_hash")]
         {
             assert_eq!(table_value.get_i64(key!("bar")).unwrap(), 2020);
         }
-        assert!(cmp_f64(table_value.get_f64("bar".into()).unwrap(), 2020.0));
+        assert!(cmp_f64(table_value.get_f64("bar").unwrap(), 2020.0));
         #[cfg(feature = "str_hash")]
         {
             assert!(cmp_f64(table_value.get_f64(key!("bar")).unwrap(), 2020.0));
         }
-        assert!(table_value.contains("baz".into()));
+        assert!(table_value.contains("baz"));
         #[cfg(feature = "str_hash")]
         {
             assert!(table_value.contains(key!("baz")));
         }
-        assert_eq!(table_value.get_string("baz".into()).unwrap(), "hello");
+        assert_eq!(table_value.get_string("baz").unwrap(), "hello");
         #[cfg(feature = "str_hash")]
         {
             assert_eq!(table_value.get_string(key!("baz")).unwrap(), "hello");
         }
-        assert!(table_value.contains("foo".into()));
+        assert!(table_value.contains("foo"));
         #[cfg(feature = "str_hash")]
         {
             assert!(table_value.contains(key!("foo")));
         }
-        assert_eq!(table_value.get_bool("foo".into()).unwrap(), false);
+        assert_eq!(table_value.get_bool("foo").unwrap(), false);
         #[cfg(feature = "str_hash")]
         {
             assert_eq!(table_value.get_bool(key!("foo")).unwrap(), false);
         }
-        assert!(!table_value.contains("bob".into()));
+        assert!(!table_value.contains("bob"));
         #[cfg(feature = "str_hash")]
         {
             assert!(!table_value.contains(key!("bob")));
@@ -0,0 +1,449 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// Represents an [`RFC 3339`](https://tools.ietf.org/html/rfc3339) date and time with a UTC offset,
+/// e.g. `2024-01-01T10:00:00Z` or `1985-04-12T23:20:50.52-05:00`.
+///
+/// The date and time are stored as written, i.e. in the local time of the UTC offset.
+/// No time zone conversions are performed.
+///
+/// NOTE - requires `"datetime"` feature.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+    /// UTC offset in minutes, `[-1439 .. 1439]`.
+    offset: i16,
+}
+
+impl DateTime {
+    /// Tries to create a new [`date/time`] value from its components.
+    ///
+    /// `year` is in range `[0 .. 9999]`, `month` - in range `[1 .. 12]`, `day` - in range `[1 .. 31]`
+    /// (and must be valid for the `month` and `year`),
+    /// `hour` - in range `[0 .. 23]`, `minute` - in range `[0 .. 59]`,
+    /// `second` - in range `[0 .. 60]` (to allow for leap seconds), `nanosecond` - in range `[0 .. 999_999_999]`,
+    /// `offset` (UTC offset in minutes) - in range `[-1439 .. 1439]`.
+    ///
+    /// Returns `None` if any of the components are out of range.
+    ///
+    /// [`date/time`]: struct.DateTime.html
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+        offset: i16,
+    ) -> Option<Self> {
+        if year > 9999
+            || !(1..=12).contains(&month)
+            || !(1..=days_in_month(year, month)).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 60
+            || nanosecond > 999_999_999
+            || !(-1439..=1439).contains(&offset)
+        {
+            return None;
+        }
+
+        Some(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+            offset,
+        })
+    }
+
+    /// Returns the year, `[0 .. 9999]`.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month, `[1 .. 12]`.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, `[1 .. 31]`.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the hour, `[0 .. 23]`.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute, `[0 .. 59]`.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns the second, `[0 .. 60]` (`60` for leap seconds).
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Returns the fractional part of the second in nanoseconds, `[0 .. 999_999_999]`.
+    pub fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    /// Returns the UTC offset in minutes, `[-1439 .. 1439]`.
+    /// `0` for UTC (`Z`).
+    pub fn offset(&self) -> i16 {
+        self.offset
+    }
+
+    /// Returns the number of non-leap seconds since the Unix epoch (`1970-01-01T00:00:00Z`),
+    /// taking the UTC offset into account.
+    ///
+    /// Leap seconds (second `60`) are treated as the first second of the next minute.
+    pub fn unix_timestamp(&self) -> i64 {
+        let days = days_from_civil(self.year as i64, self.month as i64, self.day as i64);
+
+        days * 86_400 + self.hour as i64 * 3_600 + self.minute as i64 * 60 + self.second as i64
+            - self.offset as i64 * 60
+    }
+
+    fn parse_impl(string: &str) -> Option<Self> {
+        let mut parser = DateTimeParser(string.as_bytes());
+
+        // `full-date`.
+        let year = parser.digits(4)? as u16;
+        parser.char(b'-')?;
+        let month = parser.digits(2)? as u8;
+        parser.char(b'-')?;
+        let day = parser.digits(2)? as u8;
+
+        // Date / time separator.
+        parser.one_of(b"Tt")?;
+
+        // `partial-time`.
+        let hour = parser.digits(2)? as u8;
+        parser.char(b':')?;
+        let minute = parser.digits(2)? as u8;
+        parser.char(b':')?;
+        let second = parser.digits(2)? as u8;
+
+        // Optional `time-secfrac`.
+        let nanosecond = if parser.char(b'.').is_some() {
+            parser.fraction()?
+        } else {
+            0
+        };
+
+        // `time-offset`.
+        let offset = if parser.one_of(b"Zz").is_some() {
+            0
+        } else {
+            let sign = if parser.one_of(b"+-")? == b'+' { 1 } else { -1 };
+            let offset_hour = parser.digits(2)? as i16;
+            parser.char(b':')?;
+            let offset_minute = parser.digits(2)? as i16;
+
+            if offset_hour > 23 || offset_minute > 59 {
+                return None;
+            }
+
+            sign * (offset_hour * 60 + offset_minute)
+        };
+
+        // Must have consumed the whole string.
+        if !parser.0.is_empty() {
+            return None;
+        }
+
+        Self::new(year, month, day, hour, minute, second, nanosecond, offset)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = DateTimeParseError;
+
+    /// Parses an [`RFC 3339`](https://tools.ietf.org/html/rfc3339) `date-time` string,
+    /// e.g. `2024-01-01T10:00:00Z` or `1985-04-12T23:20:50.52-05:00`.
+    ///
+    /// Fractional seconds with more than `9` digits are truncated to nanoseconds.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Self::parse_impl(string).ok_or(DateTimeParseError)
+    }
+}
+
+impl Display for DateTime {
+    /// Formats the [`date/time`] value as an [`RFC 3339`](https://tools.ietf.org/html/rfc3339) `date-time` string.
+    ///
+    /// Fractional seconds are omitted if zero, trailing zeros are trimmed.
+    /// Zero UTC offsets are written as `Z`.
+    ///
+    /// [`date/time`]: struct.DateTime.html
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+
+        if self.nanosecond > 0 {
+            let mut nanosecond = self.nanosecond;
+            let mut width = 9;
+
+            while nanosecond % 10 == 0 {
+                nanosecond /= 10;
+                width -= 1;
+            }
+
+            write!(f, ".{:0width$}", nanosecond, width = width)?;
+        }
+
+        if self.offset == 0 {
+            write!(f, "Z")
+        } else {
+            let sign = if self.offset > 0 { '+' } else { '-' };
+            let offset = self.offset.abs();
+
+            write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+        }
+    }
+}
+
+/// An error returned when parsing a [`date/time`] value from an invalid string.
+///
+/// [`date/time`]: struct.DateTime.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DateTimeParseError;
+
+impl Error for DateTimeParseError {}
+
+impl Display for DateTimeParseError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        "invalid RFC 3339 date/time string".fmt(f)
+    }
+}
+
+/// A trivial cursor over the (remaining) bytes of a parsed date/time string.
+struct DateTimeParser<'a>(&'a [u8]);
+
+impl<'a> DateTimeParser<'a> {
+    fn next(&mut self) -> Option<u8> {
+        let (first, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(*first)
+    }
+
+    fn char(&mut self, c: u8) -> Option<()> {
+        self.one_of(&[c]).map(|_| ())
+    }
+
+    fn one_of(&mut self, chars: &[u8]) -> Option<u8> {
+        let c = *self.0.first()?;
+
+        if chars.contains(&c) {
+            self.0 = &self.0[1..];
+            Some(c)
+        } else {
+            None
+        }
+    }
+
+    /// Parses exactly `num` ASCII decimal digits.
+    fn digits(&mut self, num: u32) -> Option<u32> {
+        let mut result = 0;
+
+        for _ in 0..num {
+            let c = self.next()?;
+
+            if !c.is_ascii_digit() {
+                return None;
+            }
+
+            result = result * 10 + (c - b'0') as u32;
+        }
+
+        Some(result)
+    }
+
+    /// Parses at least one ASCII decimal digit of a fraction of a second, returns nanoseconds.
+    fn fraction(&mut self) -> Option<u32> {
+        let mut result = 0;
+        let mut num_digits = 0;
+
+        while let Some(c) = self.0.first().filter(|c| c.is_ascii_digit()) {
+            if num_digits < 9 {
+                result = result * 10 + (c - b'0') as u32;
+            }
+
+            num_digits += 1;
+            self.0 = &self.0[1..];
+        }
+
+        if num_digits == 0 {
+            return None;
+        }
+
+        for _ in num_digits..9 {
+            result *= 10;
+        }
+
+        Some(result)
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0) && ((year % 100 != 0) || (year % 400 == 0))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the number of days since `1970-01-01` for the proleptic Gregorian calendar date.
+/// See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let datetime: DateTime = "2024-01-01T10:00:00Z".parse().unwrap();
+
+        assert_eq!(datetime.year(), 2024);
+        assert_eq!(datetime.month(), 1);
+        assert_eq!(datetime.day(), 1);
+        assert_eq!(datetime.hour(), 10);
+        assert_eq!(datetime.minute(), 0);
+        assert_eq!(datetime.second(), 0);
+        assert_eq!(datetime.nanosecond(), 0);
+        assert_eq!(datetime.offset(), 0);
+
+        let datetime: DateTime = "1985-04-12t23:20:50.52-05:30".parse().unwrap();
+
+        assert_eq!(datetime.year(), 1985);
+        assert_eq!(datetime.month(), 4);
+        assert_eq!(datetime.day(), 12);
+        assert_eq!(datetime.hour(), 23);
+        assert_eq!(datetime.minute(), 20);
+        assert_eq!(datetime.second(), 50);
+        assert_eq!(datetime.nanosecond(), 520_000_000);
+        assert_eq!(datetime.offset(), -330);
+
+        // Leap second.
+        assert!("1990-12-31T23:59:60Z".parse::<DateTime>().is_ok());
+        // Leap year.
+        assert!("2000-02-29T00:00:00Z".parse::<DateTime>().is_ok());
+
+        for invalid in &[
+            "",
+            "2024-01-01",
+            "2024-01-01T10:00:00",
+            "2024-01-01 10:00:00Z",
+            "2024-1-01T10:00:00Z",
+            "2024-13-01T10:00:00Z",
+            "2023-02-29T00:00:00Z",
+            "1900-02-29T00:00:00Z",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T10:60:00Z",
+            "2024-01-01T10:00:61Z",
+            "2024-01-01T10:00:00.Z",
+            "2024-01-01T10:00:00+24:00",
+            "2024-01-01T10:00:00+0100",
+            "2024-01-01T10:00:00Zfoo",
+        ] {
+            assert_eq!(invalid.parse::<DateTime>(), Err(DateTimeParseError));
+        }
+    }
+
+    #[test]
+    fn to_string_and_back() {
+        for (string, expected) in &[
+            ("2024-01-01T10:00:00Z", "2024-01-01T10:00:00Z"),
+            ("2024-01-01T10:00:00+00:00", "2024-01-01T10:00:00Z"),
+            (
+                "1985-04-12t23:20:50.52-05:00",
+                "1985-04-12T23:20:50.52-05:00",
+            ),
+            (
+                "1985-04-12T23:20:50.000000001z",
+                "1985-04-12T23:20:50.000000001Z",
+            ),
+            (
+                "1985-04-12T23:20:50.1234567891+01:30",
+                "1985-04-12T23:20:50.123456789+01:30",
+            ),
+        ] {
+            let datetime: DateTime = string.parse().unwrap();
+            assert_eq!(datetime.to_string(), *expected);
+            assert_eq!(datetime.to_string().parse::<DateTime>().unwrap(), datetime);
+        }
+    }
+
+    #[test]
+    fn unix_timestamp() {
+        assert_eq!(
+            "1970-01-01T00:00:00Z"
+                .parse::<DateTime>()
+                .unwrap()
+                .unix_timestamp(),
+            0
+        );
+        assert_eq!(
+            "2024-01-01T10:00:00Z"
+                .parse::<DateTime>()
+                .unwrap()
+                .unix_timestamp(),
+            1_704_103_200
+        );
+        assert_eq!(
+            "2024-01-01T11:30:00+01:30"
+                .parse::<DateTime>()
+                .unwrap()
+                .unix_timestamp(),
+            1_704_103_200
+        );
+        assert_eq!(
+            "1969-12-31T23:59:59Z"
+                .parse::<DateTime>()
+                .unwrap()
+                .unix_timestamp(),
+            -1
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use {crate::*, std::borrow::Cow};
+
+impl Config for DynConfig {
+    type Table<'a> = &'a DynTable;
+
+    fn root(&self) -> Self::Table<'_> {
+        DynConfig::root(self)
+    }
+}
+
+impl<'a> TableAccess for &'a DynTable {
+    type String = &'a str;
+    type Array = &'a DynArray;
+
+    fn len(&self) -> u32 {
+        DynTable::len(self)
+    }
+
+    fn get_val(&self, key: &str) -> Option<DynConfigValueRef<'a>> {
+        DynTable::get_val(self, key)
+    }
+
+    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = (&'a str, DynConfigValueRef<'a>)> + 'i> {
+        Box::new(DynTable::iter(self).map(|(key, value)| (key.as_str(), value)))
+    }
+
+    fn get_str_cow(&self, key: &str) -> Result<Cow<'_, str>, TableError> {
+        DynTable::get_string(self, key).map(Cow::Borrowed)
+    }
+}
+
+impl<'a> ArrayAccess for &'a DynArray {
+    type String = &'a str;
+    type Table = &'a DynTable;
+
+    fn len(&self) -> u32 {
+        DynArray::len(self)
+    }
+
+    fn get_val(&self, index: u32) -> Result<DynConfigValueRef<'a>, ArrayError> {
+        DynArray::get_val(self, index)
+    }
+
+    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = DynConfigValueRef<'a>> + 'i> {
+        Box::new(DynArray::iter(self))
+    }
+
+    fn get_str_cow(&self, index: u32) -> Result<Cow<'_, str>, ArrayError> {
+        DynArray::get_string(self, index).map(Cow::Borrowed)
+    }
+}
@@ -0,0 +1,234 @@
+use crate::*;
+
+#[cfg(feature = "ini")]
+use super::hashed_key::DynTableKeyRef;
+
+/// A pool of recycled [`table`], [`array`], string value and table key allocations,
+/// owned by a [`config`] created with [`DynConfig::with_arena`].
+///
+/// When the [`config`] is [`cleared`] (or [`re-parsed`]), its [`tables`], [`arrays`], string values and table keys
+/// are cleared (keeping their capacity) and returned to the pool,
+/// and the [`.ini parser`] reuses them for the next config instead of allocating new ones,
+/// which dramatically reduces allocator traffic when parsing thousands of small configs in a row
+/// (e.g. during asset baking).
+///
+/// NOTE - this is a pool rather than a bump arena: the [`tables`], [`arrays`] and strings
+/// of the [`config`] are standard library collections, which cannot be allocated from a custom allocator
+/// on stable Rust without changing their types. Once the pool is warmed up by the first few configs,
+/// it has the same effect - parsing a config of a similar shape makes (almost) no allocations,
+/// while the [`config`] values remain the regular [`tables`] / [`arrays`] / strings.
+///
+/// [`table`]: struct.DynTable.html
+/// [`array`]: struct.DynArray.html
+/// [`config`]: struct.DynConfig.html
+/// [`DynConfig::with_arena`]: struct.DynConfig.html#method.with_arena
+/// [`cleared`]: struct.DynConfig.html#method.clear
+/// [`re-parsed`]: struct.DynConfig.html#method.parse_ini
+/// [`tables`]: struct.DynTable.html
+/// [`arrays`]: struct.DynArray.html
+/// [`.ini parser`]: struct.IniParser.html
+#[derive(Default)]
+pub struct DynConfigArena {
+    tables: Vec<DynTable>,
+    arrays: Vec<DynArray>,
+    strings: Vec<String>,
+}
+
+impl DynConfigArena {
+    /// Creates a new empty [`arena`](struct.DynConfigArena.html).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of pooled [`table`], [`array`] and string / key allocations in the [`arena`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    /// [`arena`]: struct.DynConfigArena.html
+    pub fn len(&self) -> u32 {
+        (self.tables.len() + self.arrays.len() + self.strings.len()) as _
+    }
+
+    /// Returns `true` if the [`arena`](struct.DynConfigArena.html) contains no pooled allocations.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Frees all pooled allocations in the [`arena`](struct.DynConfigArena.html).
+    pub fn clear(&mut self) {
+        self.tables.clear();
+        self.arrays.clear();
+        self.strings.clear();
+    }
+
+    /// Returns an empty [`table`](struct.DynTable.html), reusing a pooled allocation, if any.
+    pub(super) fn table(&mut self) -> DynTable {
+        self.tables.pop().unwrap_or_else(DynTable::new)
+    }
+
+    /// Returns an empty [`array`](struct.DynArray.html), reusing a pooled allocation, if any.
+    #[cfg(feature = "ini")]
+    pub(super) fn array(&mut self) -> DynArray {
+        self.arrays.pop().unwrap_or_else(DynArray::new)
+    }
+
+    /// Returns a copy of the `string`, reusing a pooled allocation, if any.
+    #[cfg(feature = "ini")]
+    pub(super) fn string(&mut self, string: &str) -> String {
+        if string.is_empty() {
+            return String::new();
+        }
+
+        let mut result = self.strings.pop().unwrap_or_default();
+        result.push_str(string);
+        result
+    }
+
+    /// Clears the `string` and returns it to the pool, unless it has no allocation.
+    fn recycle_string(&mut self, mut string: String) {
+        if string.capacity() > 0 {
+            string.clear();
+            self.strings.push(string);
+        }
+    }
+
+    /// Clears the `table` and returns it, with all its keys and nested [`tables`], [`arrays`] and string values, to the pool.
+    ///
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    pub(super) fn recycle_table(&mut self, mut table: DynTable) {
+        for (key, value) in table.0.drain() {
+            self.recycle_string(key.into_key().into_inner());
+            self.recycle_value(value);
+        }
+
+        self.tables.push(table);
+    }
+
+    /// Clears the `array` and returns it, with all its nested [`tables`], [`arrays`] and string values, to the pool.
+    ///
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    pub(super) fn recycle_array(&mut self, mut array: DynArray) {
+        for value in array.0.drain(..) {
+            self.recycle_value(value);
+        }
+
+        self.arrays.push(array);
+    }
+
+    /// Returns the allocations of the `value`, if any, to the pool.
+    pub(super) fn recycle_value(&mut self, value: DynConfigValue) {
+        match value {
+            Value::String(string) => self.recycle_string(string),
+            Value::Array(array) => self.recycle_array(array),
+            Value::Table(table) => self.recycle_table(table),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "ini")]
+impl DynTable {
+    /// Inserts or changes the `value` at `key`, like `set_impl()`.
+    /// The `key` is copied to a pooled allocation from the `arena`, if any, if the `value` is added,
+    /// and the allocations of the previous value are returned to the `arena` if it is changed.
+    pub(super) fn set_in_arena(
+        &mut self,
+        key: &NonEmptyStr,
+        value: DynConfigValue,
+        arena: &mut DynConfigArena,
+    ) -> bool {
+        let key = DynTableKeyRef::from(key);
+
+        // Modify.
+        if let Some(cur_value) = self.0.get_mut(key.as_lookup()) {
+            arena.recycle_value(std::mem::replace(cur_value, value));
+            true
+
+        // Add.
+        } else {
+            let buffer = arena.strings.pop().unwrap_or_default();
+            self.0.insert(key.into_owned_in(buffer), value);
+            false
+        }
+    }
+
+    /// Tries to remove the value at `key`, like `remove_impl()`,
+    /// returning the allocation of the `key` to the `arena`.
+    pub(super) fn remove_in_arena(
+        &mut self,
+        key: &NonEmptyStr,
+        arena: &mut DynConfigArena,
+    ) -> Option<DynConfigValue> {
+        let (key, value) = self.0.remove_entry(DynTableKeyRef::from(key).as_lookup())?;
+        arena.recycle_string(key.into_key().into_inner());
+        Some(value)
+    }
+}
+
+#[cfg(all(test, feature = "ini"))]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::*;
+
+    #[test]
+    fn arena() {
+        let mut config = DynConfig::with_arena(DynConfigArena::new());
+
+        config
+            .parse_ini(
+                IniParser::new(
+                    "name = \"foo\"\n[section]\nvalue = \"bar\"\narray = [\"baz\", \"qux\"]",
+                )
+                .arrays(true),
+            )
+            .unwrap();
+
+        assert_eq!(config.root().get_string("name").unwrap(), "foo");
+        assert_eq!(
+            config
+                .root()
+                .get_string_path(&["section".into(), "value".into()])
+                .unwrap(),
+            "bar"
+        );
+
+        // Previous values' allocations are reused.
+        config
+            .parse_ini(IniParser::new(
+                "other = \"value\"\n[other_section]\nnumber = 7",
+            ))
+            .unwrap();
+
+        assert_eq!(config.root().len(), 2);
+        assert_eq!(config.root().get_string("other").unwrap(), "value");
+        assert!(config.root().get_string("name").is_err());
+        assert_eq!(
+            config
+                .root()
+                .get_i64_path(&["other_section".into(), "number".into()])
+                .unwrap(),
+            7
+        );
+
+        // Two tables, one array, four strings and four keys of the first config,
+        // some of which were reused by the second config and returned to the arena again.
+        let arena = config.into_arena();
+        assert_eq!(arena.len(), 2 + 1 + 4 + 4);
+
+        // Allocations of the partially parsed config are returned to the arena on errors.
+        let mut config = DynConfig::with_arena(arena);
+        assert!(config
+            .parse_ini(IniParser::new("name = \"foo\"\n[section"))
+            .is_err());
+        assert!(config.root().is_empty());
+
+        let mut arena = config.into_arena();
+        assert_eq!(arena.len(), 2 + 1 + 4 + 4);
+
+        arena.clear();
+        assert!(arena.is_empty());
+    }
+}
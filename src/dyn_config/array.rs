@@ -1,9 +1,16 @@
 use {
-    crate::{util::DisplayLua, *},
+    crate::{
+        util::{narrow_i64, DisplayLua},
+        *,
+    },
     std::{
         borrow::Borrow,
+        cmp::Ordering,
         fmt::{Display, Formatter, Write},
+        iter::FromIterator,
+        ops::{Bound, Index, IndexMut, RangeBounds},
         slice::Iter as VecIter,
+        vec::IntoIter as VecIntoIter,
     },
 };
 
@@ -11,7 +18,7 @@ use {
 ///
 /// [`Value`]: struct.Value.html
 #[derive(Clone)]
-pub struct DynArray(Vec<DynConfigValue>);
+pub struct DynArray(pub(super) Vec<DynConfigValue>);
 
 impl DynArray {
     /// Creates a new empty [`array`].
@@ -154,6 +161,46 @@ impl DynArray {
         self.get_path(path)
     }
 
+    /// Tries to get a [`date/time`] [`value`] in the [`array`] at `index`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`date/time`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime(&self, index: u32) -> Result<DateTime, ArrayError> {
+        self.get(index)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`array`] at `path`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`] or an [`array`](enum.Value.html#variant.Array) value.
+    /// The last key must correspond to a [`date/time`] [`value`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`table`]: enum.Value.html#variant.Table
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime_path<'k, K, P>(&self, path: P) -> Result<DateTime, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
     /// Tries to get an [`i64`] [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds or if value is not an [`i64`] / [`f64`].
@@ -190,6 +237,74 @@ impl DynArray {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u32`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u32`].
+    ///
+    /// [`u32`]: https://doc.rust-lang.org/std/primitive.u32.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.ArrayError.html#variant.ValueOutOfRange
+    pub fn get_u32(&self, index: u32) -> Result<u32, ArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(ArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get an [`i32`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of an [`i32`].
+    ///
+    /// [`i32`]: https://doc.rust-lang.org/std/primitive.i32.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.ArrayError.html#variant.ValueOutOfRange
+    pub fn get_i32(&self, index: u32) -> Result<i32, ArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(ArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u16`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u16`].
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/std/primitive.u16.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.ArrayError.html#variant.ValueOutOfRange
+    pub fn get_u16(&self, index: u32) -> Result<u16, ArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(ArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u8`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u8`].
+    ///
+    /// [`u8`]: https://doc.rust-lang.org/std/primitive.u8.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.ArrayError.html#variant.ValueOutOfRange
+    pub fn get_u8(&self, index: u32) -> Result<u8, ArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(ArrayError::ValueOutOfRange))
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds or if value is not an [`f64`] / [`i64`].
@@ -329,7 +444,7 @@ impl DynArray {
     ///
     /// [`values`]: enum.Value.html
     /// [`array`]: struct.DynArray.html
-    pub fn iter(&self) -> impl Iterator<Item = DynConfigValueRef<'_>> {
+    pub fn iter(&self) -> DynArrayIter<'_> {
         DynArrayIter(self.0.iter())
     }
 
@@ -572,6 +687,217 @@ impl DynArray {
         self.pop_impl()
     }
 
+    /// Inserts the [`value`] into the [`array`] at `index`, shifting all elements after it to the right.
+    ///
+    /// Returns an [`error`] if `index` is greater than the [`array`] length or if `value` is of invalid type.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    pub fn insert(&mut self, index: u32, value: DynConfigValue) -> Result<(), ArrayError> {
+        let len = self.len();
+
+        if index > len {
+            return Err(ArrayError::IndexOutOfBounds(len));
+        }
+
+        self.validate_value_type(&value)?;
+
+        self.0.insert(index as usize, value);
+
+        Ok(())
+    }
+
+    /// Pushes all [`values`] to the back of the [`array`], in order.
+    ///
+    /// Returns an [`error`] if any of the `values` is of invalid type;
+    /// the [`array`] is left unchanged in this case.
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    pub fn extend<I, V>(&mut self, values: I) -> Result<(), ArrayError>
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<DynConfigValue>,
+    {
+        let len = self.0.len();
+
+        for value in values.into_iter() {
+            if let Err(err) = self.push_impl(value.into()) {
+                self.0.truncate(len);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shortens the [`array`], keeping the first `len` [`values`] and dropping the rest.
+    ///
+    /// Has no effect if `len` is greater than or equal to the [`array`] length.
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`values`]: type.DynConfigValue.html
+    pub fn truncate(&mut self, len: u32) {
+        self.0.truncate(len as usize)
+    }
+
+    /// Retains only the [`values`] in the [`array`] for which the predicate `f` returns `true`,
+    /// removing all others in place and preserving the order of the retained [`values`].
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    pub fn retain<F: FnMut(&DynConfigValue) -> bool>(&mut self, f: F) {
+        self.0.retain(f)
+    }
+
+    /// Sorts the [`values`] in the [`array`] in place with the `compare` function.
+    ///
+    /// The sort is stable, i.e. the order of equal [`values`] is preserved.
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    pub fn sort_by<F: FnMut(&DynConfigValue, &DynConfigValue) -> Ordering>(&mut self, compare: F) {
+        self.0.sort_by(compare)
+    }
+
+    /// Sorts the [`values`] in the [`array`] in place using a total ordering of [`values`] of all types.
+    ///
+    /// The sort is unstable, i.e. the order of equal [`values`] is not preserved.
+    ///
+    /// [`Values`](enum.Value.html) of different types are ordered as follows:
+    /// [`booleans`] < [`numbers`] < [`strings`] < [`date/times`] < [`arrays`] < [`tables`].
+    ///
+    /// - [`booleans`] - `false` < `true`.
+    /// - [`numbers`] - by numeric value, integers and floats compared with each other;
+    ///   an integer is ordered before a float with the same numeric value; `NaN`'s are ordered after all other floats.
+    /// - [`strings`] - lexicographically.
+    /// - [`date/times`] - by the instant in time they represent.
+    /// - [`arrays`] - lexicographically by their elements, then by length.
+    /// - [`tables`] - lexicographically by their (key, value) pairs sorted by key, then by length.
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`booleans`]: enum.Value.html#variant.Bool
+    /// [`numbers`]: enum.Value.html#variant.F64
+    /// [`strings`]: enum.Value.html#variant.String
+    /// [`date/times`]: enum.Value.html#variant.DateTime
+    /// [`arrays`]: enum.Value.html#variant.Array
+    /// [`tables`]: enum.Value.html#variant.Table
+    pub fn sort_unstable_values(&mut self) {
+        self.0
+            .sort_unstable_by(|l, r| cmp_values(l.into(), r.into()))
+    }
+
+    /// Removes consecutive equal [`values`] in the [`array`], keeping the first one.
+    /// If the [`array`] is sorted (e.g. with [`sort_unstable_values`]), this removes all duplicate [`values`].
+    ///
+    /// [`Values`](enum.Value.html) are equal if they are of the same type and are equal in the ordering
+    /// used by [`sort_unstable_values`], i.e. an integer is never equal to a float.
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`sort_unstable_values`]: #method.sort_unstable_values
+    pub fn dedup(&mut self) {
+        self.0
+            .dedup_by(|r, l| cmp_values((&*l).into(), (&*r).into()) == Ordering::Equal)
+    }
+
+    /// Removes the [`values`] in the `range` of indices from the [`array`], shifting all elements after it to the left,
+    /// and returns an iterator over the removed [`values`].
+    ///
+    /// NOTE - the [`values`] are removed even if the iterator is dropped before it is fully consumed.
+    ///
+    /// Returns an [`error`] if the `range` is out of bounds.
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    pub fn drain<R: RangeBounds<u32>>(
+        &mut self,
+        range: R,
+    ) -> Result<impl Iterator<Item = DynConfigValue> + '_, ArrayError> {
+        let len = self.len();
+
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+
+        if start > end || end > len {
+            Err(ArrayError::IndexOutOfBounds(len))
+        } else {
+            Ok(self.0.drain(start as usize..end as usize))
+        }
+    }
+
+    /// Converts the [`array`] of [`tables`] into a [`table`] of [`tables`], keyed by the value of the `field` of each element,
+    /// e.g. `[{ id = "foo", a = 7 }, { id = "bar", a = 9 }]` into `{ bar = { id = "bar", a = 9 }, foo = { id = "foo", a = 7 } }`
+    /// for the `field` `"id"`.
+    ///
+    /// The `field` value must be a non-empty string or an integer (which is converted to a string).
+    /// The `field` is kept in the element [`tables`].
+    /// Duplicate keys are handled according to the `duplicates` policy.
+    ///
+    /// This is the inverse of [`DynTable::into_array_by_key`].
+    ///
+    /// Returns an [`error`] if any element is not a [`table`](enum.Value.html#variant.Table),
+    /// if its `field` is missing or invalid, or if its key is a duplicate and duplicate keys are [`forbidden`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`table`]: struct.DynTable.html
+    /// [`DynTable::into_array_by_key`]: struct.DynTable.html#method.into_array_by_key
+    /// [`error`]: enum.DynRekeyError.html
+    /// [`forbidden`]: enum.DynRekeyDuplicates.html#variant.Forbid
+    pub fn into_table_by_key<K: AsRef<str>>(
+        self,
+        field: K,
+        duplicates: DynRekeyDuplicates,
+    ) -> Result<DynTable, DynRekeyError> {
+        use DynRekeyError::*;
+
+        let mut table = DynTable::new();
+
+        for (index, value) in self.0.into_iter().enumerate() {
+            let index = index as u32;
+
+            let element = match value {
+                Value::Table(element) => element,
+                value => return Err(ElementNotATable(index, value.get_type())),
+            };
+
+            let key = match element.get_val(field.as_ref()) {
+                Some(Value::String(key)) => key.to_owned(),
+                Some(Value::I64(key)) => key.to_string(),
+                Some(value) => return Err(InvalidKeyField(index, value.get_type())),
+                None => return Err(MissingKeyField(index)),
+            };
+
+            let key = NonEmptyStr::new(&key).ok_or(InvalidKeyField(index, ValueType::String))?;
+
+            if table.contains(key) {
+                match duplicates {
+                    DynRekeyDuplicates::Forbid => return Err(DuplicateKey(index, key.to_owned())),
+                    DynRekeyDuplicates::First => continue,
+                    DynRekeyDuplicates::Last => {}
+                }
+            }
+
+            table.set(key, Value::Table(element));
+        }
+
+        Ok(table)
+    }
+
     fn len_impl(&self) -> u32 {
         self.0.len() as u32
     }
@@ -592,6 +918,8 @@ impl DynArray {
                 Value::String(value) => Value::String(value.as_str()),
                 Value::Array(value) => Value::Array(value),
                 Value::Table(value) => Value::Table(value),
+                #[cfg(feature = "datetime")]
+                Value::DateTime(value) => Value::DateTime(*value),
             };
 
             Ok(value)
@@ -614,12 +942,71 @@ impl DynArray {
                 Value::String(value) => Value::String(value.as_str()),
                 Value::Array(value) => Value::Array(value),
                 Value::Table(value) => Value::Table(value),
+                #[cfg(feature = "datetime")]
+                Value::DateTime(value) => Value::DateTime(*value),
             };
 
             Ok(value)
         }
     }
 
+    pub(crate) fn remove_impl(&mut self, index: u32) -> Result<DynConfigValue, ArrayError> {
+        use ArrayError::*;
+
+        let len = self.len();
+
+        // Index out of bounds.
+        if index >= len {
+            Err(IndexOutOfBounds(len))
+        } else {
+            Ok(self.0.remove(index as usize))
+        }
+    }
+
+    pub(crate) fn extract_impl<K: ConfigPathMatch>(&self, patterns: &[&[K]]) -> Option<DynArray> {
+        // Array values are homogeneous, so any subset of them is too.
+        let result: Vec<_> = self
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| {
+                let patterns =
+                    sub_patterns(patterns, |pattern| pattern.matches_index(index as u32));
+
+                if patterns.is_empty() {
+                    None
+                } else {
+                    extract_value(value, &patterns)
+                }
+            })
+            .collect();
+
+        if result.is_empty() {
+            None
+        } else {
+            Some(DynArray(result))
+        }
+    }
+
+    pub(crate) fn retain_impl(&mut self, patterns: &[&[ConfigPathPatternKey]]) -> bool {
+        self.0 = std::mem::take(&mut self.0)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, mut value)| {
+                let patterns =
+                    sub_patterns(patterns, |pattern| pattern.matches_index(index as u32));
+
+                if !patterns.is_empty() && retain_value(&mut value, &patterns) {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        !self.is_empty()
+    }
+
     fn validate_value_type<S: Into<String>>(
         &self,
         value: &Value<S, DynArray, DynTable>,
@@ -670,6 +1057,8 @@ impl DynArray {
             Value::String(value) => *dst = Value::String(value),
             Value::Array(value) => *dst = Value::Array(value),
             Value::Table(value) => *dst = Value::Table(value),
+            #[cfg(feature = "datetime")]
+            Value::DateTime(value) => *dst = Value::DateTime(value),
         }
 
         Ok(())
@@ -688,14 +1077,24 @@ impl DynArray {
         self.0.pop().ok_or_else(|| ArrayError::ArrayEmpty)
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        writeln!(w, "{{ ")?;
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        // Arrays of `bool`'s / numbers may be serialized in compact form.
+        if is_compact_lua_array(self.iter().next().map(|value| value.get_type()), options) {
+            return write_compact_lua_array(w, self.iter(), self.len(), indent, options);
+        }
+
+        writeln!(w, "{{")?;
 
         // Iterate the array.
         for (index, value) in self.iter().enumerate() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            options.formatter.write_indent(w, indent + 1)?;
 
-            value.fmt_lua(w, indent + 1)?;
+            value.fmt_lua(w, indent + 1, options)?;
 
             write!(w, ",")?;
 
@@ -708,7 +1107,7 @@ impl DynArray {
             writeln!(w)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
+        options.formatter.write_indent(w, indent)?;
         write!(w, "}}")?;
 
         Ok(())
@@ -719,7 +1118,14 @@ impl DynArray {
 ///
 /// [`values`]: type.DynConfigValue.html
 /// [`array`]: struct.DynArray.html
-struct DynArrayIter<'a>(VecIter<'a, DynConfigValue>);
+/// An in-order iterator over the [`values`] of an [`array`].
+///
+/// Returned by [`DynArray::iter`] and by iterating over an [`array`] reference.
+///
+/// [`values`]: type.DynConfigValueRef.html
+/// [`array`]: struct.DynArray.html
+/// [`DynArray::iter`]: struct.DynArray.html#method.iter
+pub struct DynArrayIter<'a>(VecIter<'a, DynConfigValue>);
 
 impl<'a> Iterator for DynArrayIter<'a> {
     type Item = DynConfigValueRef<'a>;
@@ -733,6 +1139,8 @@ impl<'a> Iterator for DynArrayIter<'a> {
                 Value::String(value) => Value::String(value.as_str()),
                 Value::Array(value) => Value::Array(value),
                 Value::Table(value) => Value::Table(value),
+                #[cfg(feature = "datetime")]
+                Value::DateTime(value) => Value::DateTime(*value),
             };
 
             Some(value)
@@ -742,27 +1150,140 @@ impl<'a> Iterator for DynArrayIter<'a> {
     }
 }
 
+/// Returns a reference to the [`value`] in the [`array`] at `index`.
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds.
+/// Use [`DynArray::get_val`] to handle this case.
+///
+/// [`value`]: type.DynConfigValue.html
+/// [`array`]: struct.DynArray.html
+/// [`DynArray::get_val`]: struct.DynArray.html#method.get_val
+impl Index<u32> for DynArray {
+    type Output = DynConfigValue;
+
+    fn index(&self, index: u32) -> &Self::Output {
+        &self.0[index as usize]
+    }
+}
+
+/// Returns a mutable reference to the [`value`] in the [`array`] at `index`.
+///
+/// NOTE - the [`array`] must stay homogeneous - the type of the [`value`] must not be changed
+/// to a type incompatible with the other [`values`] in the [`array`].
+/// Use [`DynArray::set`] to change the [`value`] with a type check.
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds.
+///
+/// [`value`]: type.DynConfigValue.html
+/// [`values`]: type.DynConfigValue.html
+/// [`array`]: struct.DynArray.html
+/// [`DynArray::set`]: struct.DynArray.html#method.set
+impl IndexMut<u32> for DynArray {
+    fn index_mut(&mut self, index: u32) -> &mut Self::Output {
+        &mut self.0[index as usize]
+    }
+}
+
+impl<'a> IntoIterator for &'a DynArray {
+    type Item = DynConfigValueRef<'a>;
+    type IntoIter = DynArrayIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DynArrayIter(self.0.iter())
+    }
+}
+
+/// An in-order iterator over the owned [`values`] of an [`array`].
+///
+/// Returned by iterating over an [`array`] by value.
+///
+/// [`values`]: type.DynConfigValue.html
+/// [`array`]: struct.DynArray.html
+pub struct DynArrayIntoIter(VecIntoIter<DynConfigValue>);
+
+impl Iterator for DynArrayIntoIter {
+    type Item = DynConfigValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl IntoIterator for DynArray {
+    type Item = DynConfigValue;
+    type IntoIter = DynArrayIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DynArrayIntoIter(self.0.into_iter())
+    }
+}
+
+/// Creates an [`array`] from the [`values`], in order.
+///
+/// # Panics
+///
+/// Panics if the [`values`] are of incompatible types.
+/// Use [`DynArray::extend`] to handle this case.
+///
+/// [`array`]: struct.DynArray.html
+/// [`values`]: type.DynConfigValue.html
+/// [`DynArray::extend`]: struct.DynArray.html#method.extend
+impl<V: Into<DynConfigValue>> FromIterator<V> for DynArray {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        let mut array = DynArray::new();
+
+        if let Err(err) = array.extend(iter) {
+            panic!("failed to collect an array: {}", err);
+        }
+
+        array
+    }
+}
+
 impl DisplayLua for DynArray {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl<'a> DisplayLua for &'a DynArray {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl<'a> DisplayLua for &'a mut DynArray {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl Display for DynArray {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, Default::default())
     }
 }
 
@@ -795,6 +1316,237 @@ mod tests {
         assert!(array.is_empty());
     }
 
+    #[test]
+    fn iterators() {
+        let array: DynArray = (0..4).map(|value| value * 2).collect();
+
+        assert_eq!(array.len(), 4);
+
+        let mut sum = 0;
+
+        for value in &array {
+            sum += value.i64().unwrap();
+        }
+
+        assert_eq!(sum, 12);
+
+        let strings: DynArray = array
+            .into_iter()
+            .map(|value| value.i64().unwrap().to_string())
+            .collect();
+
+        assert_eq!(strings.get_string(3).unwrap(), "6");
+    }
+
+    #[test]
+    fn index() {
+        let mut array: DynArray = vec![1, 2, 3].into_iter().collect();
+
+        assert_eq!(array[1].i64().unwrap(), 2);
+
+        array[1] = 7.into();
+        assert_eq!(array.get_i64(1).unwrap(), 7);
+
+        let mut nested = DynArray::new();
+        nested.push(Value::Array(array)).unwrap();
+
+        if let Value::Array(array) = &mut nested[0] {
+            array.push(4.into()).unwrap();
+        }
+        assert_eq!(nested.get_array(0).unwrap().len(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let array: DynArray = vec![1, 2, 3].into_iter().collect();
+        let _ = &array[3];
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_iter_mixed() {
+        let _: DynArray = vec![DynConfigValue::from(7), "foo".into()]
+            .into_iter()
+            .collect();
+    }
+
+    #[test]
+    fn insert_extend_truncate() {
+        let mut array = DynArray::new();
+
+        array.extend(vec![1, 2, 3]).unwrap();
+        array.extend(vec![4.5]).unwrap();
+
+        assert_eq!(array.len(), 4);
+
+        assert_eq!(
+            array
+                .extend(vec![DynConfigValue::from(5), "foo".into()])
+                .err()
+                .unwrap(),
+            ArrayError::IncorrectValueType(ValueType::I64)
+        );
+        // Unchanged.
+        assert_eq!(array.len(), 4);
+
+        array.insert(0, 0.into()).unwrap();
+        array.insert(5, 5.into()).unwrap();
+        array.insert(2, 7.into()).unwrap();
+
+        assert_eq!(
+            array.insert(8, 8.into()).err().unwrap(),
+            ArrayError::IndexOutOfBounds(7)
+        );
+        assert_eq!(
+            array.insert(0, true.into()).err().unwrap(),
+            ArrayError::IncorrectValueType(ValueType::I64)
+        );
+
+        assert_eq!(
+            array
+                .iter()
+                .map(|value| value.f64().unwrap())
+                .collect::<Vec<_>>(),
+            vec![0.0, 1.0, 7.0, 2.0, 3.0, 4.5, 5.0]
+        );
+
+        array.truncate(9);
+        assert_eq!(array.len(), 7);
+
+        array.truncate(2);
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.get_i64(1).unwrap(), 1);
+
+        array.truncate(0);
+        assert!(array.is_empty());
+
+        array.extend(vec!["foo", "bar"]).unwrap();
+        assert_eq!(array.get_string(1).unwrap(), "bar");
+    }
+
+    #[test]
+    fn retain_drain() {
+        let mut array = DynArray::new();
+
+        for value in 0..10 {
+            array.push(value.into()).unwrap();
+        }
+
+        array.retain(|value| value.i64().unwrap() % 2 == 0);
+
+        assert_eq!(array.len(), 5);
+        assert_eq!(
+            array
+                .iter()
+                .map(|value| value.i64().unwrap())
+                .collect::<Vec<_>>(),
+            vec![0, 2, 4, 6, 8]
+        );
+
+        assert_eq!(
+            array
+                .drain(1..3)
+                .unwrap()
+                .map(|value| value.i64().unwrap())
+                .collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get_i64(1).unwrap(), 6);
+
+        assert_eq!(
+            array.drain(2..=3).err().unwrap(),
+            ArrayError::IndexOutOfBounds(3)
+        );
+        assert_eq!(array.len(), 3);
+
+        // Dropped iterator still removes the values.
+        std::mem::drop(array.drain(..2).unwrap());
+        assert_eq!(array.len(), 1);
+        assert_eq!(array.get_i64(0).unwrap(), 8);
+
+        assert_eq!(array.drain(..).unwrap().count(), 1);
+        assert!(array.is_empty());
+
+        // Empty array accepts values of any type.
+        array.push("foo".into()).unwrap();
+    }
+
+    #[test]
+    fn sort_dedup() {
+        let mut array = DynArray::new();
+
+        for value in ["foo", "bar", "baz", "bar", "foo", "bob"].iter() {
+            array.push((*value).into()).unwrap();
+        }
+
+        array.sort_unstable_values();
+        array.dedup();
+
+        assert_eq!(
+            array
+                .iter()
+                .map(|value| value.string().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["bar", "baz", "bob", "foo"]
+        );
+
+        let len = |value: &DynConfigValue| match value {
+            Value::String(value) => value.len(),
+            _ => 0,
+        };
+        array.sort_by(|l, r| len(r).cmp(&len(l)));
+        assert_eq!(array.get_string(0).unwrap(), "bar");
+
+        // Integers and floats.
+        let mut array = DynArray::new();
+
+        for value in [3.5, f64::NAN, -1.0, 2.0].iter() {
+            array.push((*value).into()).unwrap();
+        }
+        for value in [2, 7, -3, 2].iter() {
+            array.push((*value).into()).unwrap();
+        }
+
+        array.sort_unstable_values();
+        array.dedup();
+
+        assert_eq!(array.len(), 7);
+        assert_eq!(array.get_i64(0).unwrap(), -3);
+        assert_eq!(array.get_f64(1).unwrap(), -1.0);
+        assert!(matches!(array.get_val(2).unwrap(), Value::I64(2)));
+        assert!(matches!(array.get_val(3).unwrap(), Value::F64(_)));
+        assert_eq!(array.get_f64(4).unwrap(), 3.5);
+        assert_eq!(array.get_i64(5).unwrap(), 7);
+        assert!(array.get_f64(6).unwrap().is_nan());
+
+        // Nested arrays.
+        let nested = |values: &[i64]| {
+            let mut array = DynArray::new();
+
+            for value in values.iter() {
+                array.push((*value).into()).unwrap();
+            }
+
+            Value::Array(array)
+        };
+
+        let mut array = DynArray::new();
+        array.push(nested(&[1, 2])).unwrap();
+        array.push(nested(&[1])).unwrap();
+        array.push(nested(&[0, 9])).unwrap();
+        array.push(nested(&[1, 2])).unwrap();
+
+        array.sort_unstable_values();
+        array.dedup();
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get_i64_path(&[0.into(), 0.into()]).unwrap(), 0);
+        assert_eq!(array.get_array(1).unwrap().len(), 1);
+        assert_eq!(array.get_array(2).unwrap().len(), 2);
+    }
+
     #[test]
     fn DynArrayError_IndexOutOfBounds() {
         let mut array = DynArray::new();
@@ -988,6 +1740,25 @@ mod tests {
         assert!(cmp_f64(val, 3.14));
     }
 
+    #[test]
+    fn DynArrayError_ValueOutOfRange() {
+        let mut array = DynArray::new();
+
+        array.push(255u8.into()).unwrap();
+        array.push(256.into()).unwrap();
+
+        assert_eq!(array.get_u8(0).unwrap(), 255);
+        assert_eq!(array.get_u16(1).unwrap(), 256);
+        assert_eq!(
+            array.get_u8(1).err().unwrap(),
+            ArrayError::ValueOutOfRange(256)
+        );
+        assert_eq!(
+            array.get_u32(2).err().unwrap(),
+            ArrayError::IndexOutOfBounds(2)
+        );
+    }
+
     #[test]
     fn basic() {
         // Create an empty array.
@@ -1097,4 +1868,109 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn rekey() {
+        use ministr_macro::nestr;
+
+        fn element(id: DynConfigValue, value: i64) -> DynConfigValue {
+            let mut element = DynTable::new();
+            element.set(nestr!("id"), id);
+            element.set(nestr!("value"), value);
+            Value::Table(element)
+        }
+
+        let mut array = DynArray::new();
+        array.push(element(Value::String("foo".into()), 7)).unwrap();
+        array.push(element(9.into(), 9)).unwrap();
+        array
+            .push(element(Value::String("foo".into()), 11))
+            .unwrap();
+
+        // Duplicate keys.
+        assert_eq!(
+            array
+                .clone()
+                .into_table_by_key(nestr!("id"), DynRekeyDuplicates::Forbid)
+                .err()
+                .unwrap(),
+            DynRekeyError::DuplicateKey(2, nestr!("foo").to_owned())
+        );
+
+        let table = array
+            .clone()
+            .into_table_by_key(nestr!("id"), DynRekeyDuplicates::First)
+            .unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(
+            table.get_i64_path(&["foo".into(), "value".into()]).unwrap(),
+            7
+        );
+        assert_eq!(
+            table.get_i64_path(&["9".into(), "value".into()]).unwrap(),
+            9
+        );
+        // Key field is kept.
+        assert_eq!(table.get_i64_path(&["9".into(), "id".into()]).unwrap(), 9);
+
+        let table = array
+            .into_table_by_key(nestr!("id"), DynRekeyDuplicates::Last)
+            .unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(
+            table.get_i64_path(&["foo".into(), "value".into()]).unwrap(),
+            11
+        );
+
+        // And back.
+        let array = table.into_array_by_key(nestr!("key")).unwrap();
+        assert_eq!(array.len(), 2);
+        // Sorted by key.
+        assert_eq!(
+            array.get_string_path(&[0.into(), "key".into()]).unwrap(),
+            "9"
+        );
+        assert_eq!(array.get_i64_path(&[0.into(), "value".into()]).unwrap(), 9);
+        assert_eq!(
+            array.get_string_path(&[1.into(), "key".into()]).unwrap(),
+            "foo"
+        );
+        assert_eq!(array.get_i64_path(&[1.into(), "value".into()]).unwrap(), 11);
+
+        // Invalid elements / key fields.
+        let mut array = DynArray::new();
+        array.push(7.into()).unwrap();
+        assert_eq!(
+            array
+                .into_table_by_key(nestr!("id"), DynRekeyDuplicates::Forbid)
+                .err()
+                .unwrap(),
+            DynRekeyError::ElementNotATable(0, ValueType::I64)
+        );
+
+        let mut array = DynArray::new();
+        array.push(element(true.into(), 7)).unwrap();
+        assert_eq!(
+            array
+                .clone()
+                .into_table_by_key(nestr!("id"), DynRekeyDuplicates::Forbid)
+                .err()
+                .unwrap(),
+            DynRekeyError::InvalidKeyField(0, ValueType::Bool)
+        );
+        assert_eq!(
+            array
+                .into_table_by_key(nestr!("missing"), DynRekeyDuplicates::Forbid)
+                .err()
+                .unwrap(),
+            DynRekeyError::MissingKeyField(0)
+        );
+
+        let mut table = DynTable::new();
+        table.set(nestr!("foo"), 7);
+        assert_eq!(
+            table.into_array_by_key(nestr!("id")).err().unwrap(),
+            DynRekeyError::ValueNotATable(nestr!("foo").to_owned(), ValueType::I64)
+        );
+    }
 }
@@ -0,0 +1,93 @@
+use {crate::*, std::collections::HashMap};
+
+/// Description comments of the values of a [`dynamic config`](struct.DynConfig.html), keyed by their paths.
+#[derive(Default)]
+pub(super) struct DynConfigComments(HashMap<ConfigPath, String>);
+
+impl DynConfigComments {
+    fn set(&mut self, path: ConfigPath, comment: String) -> Option<String> {
+        self.0.insert(path, comment)
+    }
+
+    fn get(&self, path: &ConfigPath) -> Option<&str> {
+        self.0.get(path).map(String::as_str)
+    }
+
+    fn remove(&mut self, path: &ConfigPath) -> Option<String> {
+        self.0.remove(path)
+    }
+
+    /// Returns the comment of the value with the `key` in the `.ini` section / flattened table at `path`, if any.
+    #[cfg(feature = "ini")]
+    pub(super) fn get_ini(&self, path: &IniPath, key: &NonEmptyStr) -> Option<&str> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let path: ConfigPath = path
+            .iter()
+            .map(|section| OwnedConfigKey::from(section.as_ne_str()))
+            .chain(std::iter::once(key.into()))
+            .collect::<Vec<_>>()
+            .into();
+
+        self.get(&path)
+    }
+
+    /// Returns the comment of the root table, if any.
+    #[cfg(feature = "ini")]
+    pub(super) fn root(&self) -> Option<&str> {
+        if self.0.is_empty() {
+            None
+        } else {
+            self.get(&ConfigPath::new())
+        }
+    }
+}
+
+impl DynConfig {
+    /// Attaches the description `comment` to the [`value`] at `path` of this [`config`],
+    /// replacing the previous one, if any.
+    /// Returns the previous comment of the [`value`] at `path`, if any.
+    ///
+    /// The comments are emitted by [`to_ini_string`] (and other `.ini` serialization methods)
+    /// as `;` comment lines, one per line of the comment, above the key / section header of the [`value`],
+    /// so that generated settings files are self-documenting.
+    /// The comment of the root [`table`] (i.e. the one with an empty `path`) is emitted at the top of the file.
+    ///
+    /// The [`value`] at `path` does not need to exist - comments are not affected by the changes to the [`config`]
+    /// (incl. [`clear`]), and comments of [`values`] which don't exist are ignored.
+    /// Comments of [`array`] elements and of [`values`] in arrays of sections are not emitted.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`to_ini_string`]: #method.to_ini_string
+    /// [`table`]: struct.DynTable.html
+    /// [`clear`]: #method.clear
+    /// [`array`]: struct.DynArray.html
+    pub fn set_comment<P, C>(&mut self, path: P, comment: C) -> Option<String>
+    where
+        P: Into<ConfigPath>,
+        C: Into<String>,
+    {
+        self.3.set(path.into(), comment.into())
+    }
+
+    /// Returns the description comment attached to the [`value`] at `path` by [`set_comment`], if any.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`set_comment`]: #method.set_comment
+    pub fn comment(&self, path: &ConfigPath) -> Option<&str> {
+        self.3.get(path)
+    }
+
+    /// Removes the description comment attached to the [`value`] at `path` by [`set_comment`].
+    /// Returns the removed comment, if any.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`set_comment`]: #method.set_comment
+    pub fn remove_comment(&mut self, path: &ConfigPath) -> Option<String> {
+        self.3.remove(path)
+    }
+}
@@ -1,15 +1,37 @@
 use {
+    super::{
+        arena::DynConfigArena, comments::DynConfigComments, subscribe::DynConfigSubscriptions,
+    },
     crate::{util::DisplayLua, *},
     std::{
+        borrow::Borrow,
         fmt::{Display, Formatter, Write},
         num::NonZeroU32,
     },
 };
 
+#[cfg(feature = "ini")]
+use super::{
+    interpolation::interpolate_ini,
+    memory::{
+        ini_value_memory_usage, table_entry_memory_usage, value_memory_usage, ARRAY_VALUE_SIZE,
+    },
+};
+
 /// Represents a mutable config with a root hashmap [`table`].
 ///
+/// The config is both `Send` and `Sync` (including its value change [`subscriptions`]);
+/// use [`SharedConfig`] to share a versioned config between threads.
+///
 /// [`table`]: struct.DynTable.html
-pub struct DynConfig(DynTable);
+/// [`subscriptions`]: #method.subscribe
+/// [`SharedConfig`]: struct.SharedConfig.html
+pub struct DynConfig(
+    pub(super) DynTable,
+    pub(super) DynConfigSubscriptions,
+    pub(super) DynConfigArena,
+    pub(super) DynConfigComments,
+);
 
 impl DynConfig {
     /// Creates a new [`config`] with an empty root [`table`].
@@ -17,7 +39,45 @@ impl DynConfig {
     /// [`config`]: struct.DynConfig.html
     /// [`table`]: struct.DynTable.html
     pub fn new() -> Self {
-        Self(DynTable::new())
+        Self(
+            DynTable::new(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )
+    }
+
+    /// Creates a new [`config`] with an empty root [`table`], which owns the [`arena`]
+    /// of pooled allocations reused by [`parse_ini`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`table`]: struct.DynTable.html
+    /// [`arena`]: struct.DynConfigArena.html
+    /// [`parse_ini`]: #method.parse_ini
+    pub fn with_arena(mut arena: DynConfigArena) -> Self {
+        Self(arena.table(), Default::default(), arena, Default::default())
+    }
+
+    /// Removes all values from the [`config`], returning their allocations to its [`arena`].
+    ///
+    /// NOTE - does not notify the [`subscribers`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`arena`]: struct.DynConfigArena.html
+    /// [`subscribers`]: #method.subscribe
+    pub fn clear(&mut self) {
+        let root = std::mem::replace(&mut self.0, self.2.table());
+        self.2.recycle_table(root);
+    }
+
+    /// Consumes the [`config`] and returns its [`arena`], with the allocations of all its values returned to it,
+    /// e.g. to be reused by another [`config`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`arena`]: struct.DynConfigArena.html
+    pub fn into_arena(mut self) -> DynConfigArena {
+        self.clear();
+        self.2
     }
 
     /// Returns the immutable reference to the root [`table`] of the [`config`].
@@ -37,6 +97,111 @@ impl DynConfig {
         &mut self.0
     }
 
+    /// Returns a new [`config`] which contains copies of all elements of this [`config`]
+    /// matched by any of the path [`patterns`], with all their parent [`tables`] / [`arrays`].
+    /// Elements matched by a pattern are copied with all their contents.
+    ///
+    /// NOTE - [`array`] elements not matched by any pattern are skipped,
+    /// i.e. the indices of the copied [`array`] elements may change.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`patterns`]: struct.ConfigPathPattern.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`array`]: struct.DynArray.html
+    pub fn extract<I, P>(&self, patterns: I) -> DynConfig
+    where
+        I: IntoIterator<Item = P>,
+        P: Borrow<ConfigPathPattern>,
+    {
+        let patterns: Vec<_> = patterns.into_iter().collect();
+        let patterns: Vec<_> = patterns
+            .iter()
+            .map(|pattern| pattern.borrow().0.as_slice())
+            .collect();
+
+        // The root table itself was matched.
+        if patterns.iter().any(|pattern| pattern.is_empty()) {
+            Self(
+                self.0.clone(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            )
+        } else {
+            Self(
+                self.0.extract_impl(&patterns).unwrap_or_else(DynTable::new),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            )
+        }
+    }
+
+    /// Returns a new [`config`] which only contains the copies of the elements of this [`config`] at the [`paths`],
+    /// and their parent [`tables`] / [`arrays`].
+    /// Elements at the [`paths`] are copied with all their contents; [`paths`] which do not exist in this [`config`] are ignored.
+    ///
+    /// Same as [`extract`](#method.extract), but the table keys of the [`paths`] are matched exactly.
+    ///
+    /// NOTE - [`array`] elements not at any of the [`paths`] are skipped,
+    /// i.e. the indices of the copied [`array`] elements may change.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`paths`]: struct.ConfigPath.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`array`]: struct.DynArray.html
+    pub fn project(&self, paths: &[ConfigPath]) -> DynConfig {
+        let paths: Vec<_> = paths.iter().map(|path| path.0.as_slice()).collect();
+
+        // The root table itself was projected.
+        if paths.iter().any(|path| path.is_empty()) {
+            Self(
+                self.0.clone(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            )
+        } else {
+            Self(
+                self.0.extract_impl(&paths).unwrap_or_else(DynTable::new),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            )
+        }
+    }
+
+    /// Removes all elements of this [`config`] not matched by any of the path [`patterns`],
+    /// except the parent [`tables`] / [`arrays`] of the matched elements.
+    /// Elements matched by a pattern are retained with all their contents.
+    ///
+    /// NOTE - [`array`] elements not matched by any pattern are removed,
+    /// i.e. the indices of the retained [`array`] elements may change.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`patterns`]: struct.ConfigPathPattern.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`array`]: struct.DynArray.html
+    pub fn retain_paths<I, P>(&mut self, patterns: I)
+    where
+        I: IntoIterator<Item = P>,
+        P: Borrow<ConfigPathPattern>,
+    {
+        let patterns: Vec<_> = patterns.into_iter().collect();
+        let patterns: Vec<_> = patterns
+            .iter()
+            .map(|pattern| pattern.borrow().0.as_slice())
+            .collect();
+
+        // The root table itself was matched.
+        if !patterns.iter().any(|pattern| pattern.is_empty()) {
+            self.0.retain_impl(&patterns);
+        }
+    }
+
     /// Tries to serialize this [`config`] to a Lua script string.
     ///
     /// NOTE: you may also call `to_string` via the [`config`]'s `Display` implementation.
@@ -58,7 +223,74 @@ impl DynConfig {
     ///
     /// [`config`]: struct.DynConfig.html
     pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), std::fmt::Error> {
-        self.root().fmt_lua(w, 0)
+        self.fmt_lua_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string using provided [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToLuaStringOptions.html
+    pub fn to_lua_string_opts(
+        &self,
+        options: ToLuaStringOptions,
+    ) -> Result<String, std::fmt::Error> {
+        let mut result = String::new();
+
+        self.fmt_lua_opts(options, &mut result)?;
+
+        result.shrink_to_fit();
+
+        Ok(result)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using provided [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToLuaStringOptions.html
+    pub fn fmt_lua_opts<W: Write>(
+        &self,
+        options: ToLuaStringOptions,
+        w: &mut W,
+    ) -> Result<(), std::fmt::Error> {
+        self.root()
+            .fmt_lua(&mut options.formatter.new_lines(w), 0, options)
+    }
+
+    /// Returns the 64-bit hash of the contents of this [`config`], calculated over its [`canonical`] serialization
+    /// to a Lua script string, so that logically equal configs (regardless of the config backend,
+    /// or the order in which their values were added) always have the same hash,
+    /// e.g. to be used as cache keys by build systems.
+    ///
+    /// NOTE - the hash is not a cryptographic hash, and is not guaranteed to be stable across versions of this crate.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`canonical`]: struct.ToLuaStringOptions.html#method.canonical
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = ContentHasher::new();
+
+        // Must succeed - the hasher never fails.
+        unwrap_unchecked(
+            self.fmt_lua_opts(ToLuaStringOptions::canonical(), &mut hasher),
+            "failed to hash a config",
+        );
+
+        hasher.finish()
+    }
+
+    /// Creates a new [`Lua table`] in the [`Lua context`] with the contents of this [`config`]'s root table,
+    /// so that it may be used by Lua scripts directly, without serializing the [`config`] to a Lua script string.
+    ///
+    /// See also [`LuaTable::update_from_dyn`] to update an existing [`Lua table`].
+    ///
+    /// [`Lua table`]: struct.LuaTable.html
+    /// [`Lua context`]: https://docs.rs/rlua/*/rlua/struct.Context.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`LuaTable::update_from_dyn`]: struct.LuaTable.html#method.update_from_dyn
+    #[cfg(feature = "lua_runtime")]
+    pub fn to_lua<'lua>(&self, lua: rlua::Context<'lua>) -> LuaTable<'lua> {
+        let mut table = LuaTable::new(lua);
+        table.update_from_dyn(lua, self.root());
+        table
     }
 
     /// Tries to serialize this [`config`] to a [`binary config`].
@@ -84,15 +316,248 @@ impl DynConfig {
         }
     }
 
+    /// Creates a new [`config`] from the [`binary config`], copying all its tables / arrays / values.
+    ///
+    /// Equivalent to [`BinConfig::to_dyn_config`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`BinConfig::to_dyn_config`]: struct.BinConfig.html#method.to_dyn_config
+    #[cfg(feature = "bin")]
+    pub fn from_bin_config(config: &BinConfig) -> Self {
+        config.to_dyn_config()
+    }
+
     /// Creates a new [`config`] from the [`.ini parser`].
     ///
     /// [`config`]: struct.DynConfig.html
     /// [`.ini parser`]: struct.IniParser.html
     #[cfg(feature = "ini")]
     pub fn from_ini(parser: IniParser) -> Result<Self, IniError> {
-        let mut config = DynConfigIniConfig::new();
-        parser.parse(&mut config)?;
-        Ok(config.into_inner())
+        Self::from_ini_impl(
+            parser,
+            &mut Default::default(),
+            &mut IniDependencyGraph::new(),
+            |parser, config, _| parser.parse(config),
+        )
+    }
+
+    /// Replaces the contents of this [`config`] with the config parsed by the [`.ini parser`],
+    /// reusing the allocations in its [`arena`] (see [`with_arena`]).
+    /// The allocations of the previous values are returned to the [`arena`] first.
+    ///
+    /// If parsing fails, the [`config`] is left empty.
+    ///
+    /// NOTE - does not notify the [`subscribers`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`arena`]: struct.DynConfigArena.html
+    /// [`with_arena`]: #method.with_arena
+    /// [`subscribers`]: #method.subscribe
+    #[cfg(feature = "ini")]
+    pub fn parse_ini(&mut self, parser: IniParser) -> Result<(), IniError> {
+        let root = std::mem::replace(&mut self.0, DynTable::new());
+        self.2.recycle_table(root);
+
+        let config = Self::from_ini_impl(
+            parser,
+            &mut self.2,
+            &mut IniDependencyGraph::new(),
+            |parser, config, _| parser.parse(config),
+        )?;
+
+        self.0 = config.0;
+        self.2 = config.2;
+
+        Ok(())
+    }
+
+    /// Creates a new [`config`] from the [`.ini parser`],
+    /// reusing the helper buffers in the passed [`scratch`] state.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`scratch`]: struct.IniParserScratch.html
+    #[cfg(feature = "ini")]
+    pub fn from_ini_with_scratch(
+        parser: IniParser,
+        scratch: &mut IniParserScratch,
+    ) -> Result<Self, IniError> {
+        Self::from_ini_impl(
+            parser,
+            &mut Default::default(),
+            &mut IniDependencyGraph::new(),
+            |parser, config, _| parser.parse_with_scratch(config, scratch),
+        )
+    }
+
+    /// Creates a new [`config`] from the [`.ini parser`],
+    /// resolving the `!include` directives with the `resolver`
+    /// and recording the included sources in the [`dependency graph`],
+    /// followed by the resolved [`value references`] / [`interpolations`] (if the parser is so configured).
+    ///
+    /// See [`parse_with_includes`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`dependency graph`]: struct.IniDependencyGraph.html
+    /// [`parse_with_includes`]: struct.IniParser.html#method.parse_with_includes
+    /// [`value references`]: struct.IniParser.html#method.value_references
+    /// [`interpolations`]: struct.IniParser.html#method.interpolation
+    #[cfg(feature = "ini")]
+    pub fn from_ini_with_includes<'s, R: IniIncludeResolver<'s>>(
+        parser: IniParser<'s>,
+        resolver: R,
+        dependencies: &mut IniDependencyGraph,
+    ) -> Result<Self, IniError> {
+        Self::from_ini_impl(
+            parser,
+            &mut Default::default(),
+            dependencies,
+            |parser, config, dependencies| {
+                parser.parse_with_includes(config, resolver, dependencies)
+            },
+        )
+    }
+
+    /// Creates a new [`config`] from the [`.ini parser`],
+    /// additionally returning the source [`spans`] of all its values (except [`array`] elements) and their keys,
+    /// e.g. to report semantic errors at the location of the offending value in the `.ini` source.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`spans`]: struct.IniSpans.html
+    /// [`array`]: struct.DynArray.html
+    #[cfg(feature = "ini")]
+    pub fn from_ini_with_spans(parser: IniParser) -> Result<(Self, IniSpans), IniError> {
+        let mut config = DynConfigIniSpans::new(parser.options());
+        parser.spans(true).parse(&mut config)?;
+        config.into_inner(&mut IniDependencyGraph::new())
+    }
+
+    /// Creates a new [`config`] from the [`.ini parser`],
+    /// additionally returning the [`warnings`] for the registered [`deprecated`] values present in the [`config`],
+    /// with the source locations of their keys (sections and [`array`] elements have no source location).
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`warnings`]: struct.ConfigWarning.html
+    /// [`deprecated`]: struct.ConfigDeprecations.html
+    /// [`array`]: struct.DynArray.html
+    #[cfg(feature = "ini")]
+    pub fn from_ini_with_warnings(
+        parser: IniParser,
+        deprecations: &ConfigDeprecations,
+    ) -> Result<(Self, Vec<ConfigWarning>), IniError> {
+        let (config, spans) = Self::from_ini_with_spans(parser)?;
+
+        let mut warnings = Vec::new();
+
+        deprecations.check_with(&config, |mut warning| {
+            warning.location = spans
+                .get(&warning.path)
+                .map(|spans| (spans.key.line, spans.key.column));
+            warnings.push(warning);
+        });
+
+        Ok((config, warnings))
+    }
+
+    /// Parses the [`.ini parser`]'s source with the `parse` function,
+    /// reusing the allocations in the `arena`, which is then owned by the returned config.
+    /// If the parser is [`configured`] to support value references / interpolation, tracks and resolves them,
+    /// recording them in the [`dependency graph`] (which is also passed to the `parse` function).
+    ///
+    /// If parsing fails, the allocations of the partially parsed config are returned to the `arena`.
+    ///
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`configured`]: struct.IniParser.html#method.value_references
+    /// [`dependency graph`]: struct.IniDependencyGraph.html
+    #[cfg(feature = "ini")]
+    fn from_ini_impl<'s, F>(
+        parser: IniParser<'s>,
+        arena: &mut DynConfigArena,
+        dependencies: &mut IniDependencyGraph,
+        parse: F,
+    ) -> Result<Self, IniError>
+    where
+        F: FnOnce(
+            IniParser<'s>,
+            &mut dyn IniConfig<'s>,
+            &mut IniDependencyGraph,
+        ) -> Result<(), IniError>,
+    {
+        let config_arena = std::mem::take(arena);
+
+        let options = *parser.options();
+
+        if options.value_references || (options.interpolation != IniInterpolation::None) {
+            let mut config = DynConfigIniSpans::with_arena(config_arena, &options);
+
+            // Interpolation errors are reported at the locations of the interpolated values.
+            let parser = if options.interpolation != IniInterpolation::None {
+                parser.spans(true)
+            } else {
+                parser
+            };
+
+            if let Err(err) = parse(parser, &mut config, dependencies) {
+                *arena = config.config.into_arena();
+                return Err(err);
+            }
+
+            match config.into_config(dependencies) {
+                (config, Ok(_)) => Ok(config),
+                (config, Err(err)) => {
+                    *arena = config.into_arena();
+                    Err(err)
+                }
+            }
+        } else {
+            let mut config = DynConfigIniConfig::with_arena(config_arena)
+                .max_memory_usage(options.max_memory_usage);
+
+            if let Err(err) = parse(parser, &mut config, dependencies) {
+                *arena = config.into_arena();
+                return Err(err);
+            }
+
+            Ok(config.into_inner())
+        }
+    }
+
+    /// Renames the (lowercase) keys in this [`config`] to their original spellings
+    /// recorded by the [`.ini parser`] for [`case-insensitive`] section names / keys,
+    /// e.g. to restore the preferred case before serializing the config back to `.ini`.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html#method.parse_with_key_spellings
+    /// [`case-insensitive`]: struct.IniParser.html#method.case_insensitive_keys
+    #[cfg(feature = "ini")]
+    pub fn restore_key_spellings(&mut self, spellings: &IniKeySpellings) {
+        restore_key_spellings(&mut self.0, spellings);
+    }
+
+    /// Calls the methods on the `config` event handler with the sequence of events the [`.ini parser`]
+    /// would generate when parsing this [`config`] serialized to an `.ini` string,
+    /// so that the [`event handlers`] may consume both `.ini` config sources and in-memory configs.
+    ///
+    /// Tables are reported as (nested) sections, arrays of tables - as arrays of sections.
+    /// Section names / keys / string values are reported as [`owned`](enum.NonEmptyIniStr.html#variant.Owned).
+    ///
+    /// Returns an [`InvalidArrayType`] error if the [`config`] contains arrays of arrays.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`event handlers`]: trait.IniConfig.html
+    /// [`InvalidArrayType`]: enum.ToIniStringError.html#variant.InvalidArrayType
+    #[cfg(feature = "ini")]
+    pub fn replay_ini<'s, C: IniConfig<'s> + ?Sized>(
+        &self,
+        config: &mut C,
+    ) -> Result<(), ToIniStringError> {
+        replay_ini(&self.root(), config)
     }
 
     /// Tries to serialize this [`config`] to an `.ini` string.
@@ -140,40 +605,173 @@ impl DynConfig {
         options: ToIniStringOptions,
         w: &mut W,
     ) -> Result<(), ToIniStringError> {
-        let mut path = IniPath::new();
+        let mut w = options.formatter.new_lines(w);
+
+        // The comment of the root table is the file header.
+        if let Some(comment) = self.3.root() {
+            write_ini_comment(&mut w, Some(comment))?;
+
+            if !self.root().is_empty() {
+                writeln!(w)?;
+            }
+        }
+
+        self.root().fmt_ini_impl(
+            &mut w,
+            0,
+            false,
+            &mut IniPath::new(),
+            Some(&self.3),
+            options,
+        )
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string to the `std::io::Write` writer `w` (e.g. a file)
+    /// using default [`options`], without an intermediate string.
+    ///
+    /// NOTE - writes to `w` are not buffered - consider wrapping unbuffered writers in a `std::io::BufWriter`.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn write_ini<W: std::io::Write>(&self, w: &mut W) -> Result<(), ToIniStringError> {
+        self.write_ini_opts(Default::default(), w)
+    }
 
-        self.root().fmt_ini(w, 0, false, &mut path, options)
+    /// Tries to serialize this [`config`] to an `.ini` string to the `std::io::Write` writer `w` (e.g. a file)
+    /// using provided [`options`], without an intermediate string.
+    ///
+    /// NOTE - writes to `w` are not buffered - consider wrapping unbuffered writers in a `std::io::BufWriter`.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn write_ini_opts<W: std::io::Write>(
+        &self,
+        options: ToIniStringOptions,
+        w: &mut W,
+    ) -> Result<(), ToIniStringError> {
+        self.fmt_ini_opts(options, &mut IoWriter(w))
     }
 }
 
 impl Display for DynConfig {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.root().fmt_lua(f, 0)
+        self.root().fmt_lua(f, 0, Default::default())
+    }
+}
+
+#[cfg(feature = "ini")]
+fn restore_key_spellings(table: &mut DynTable, spellings: &IniKeySpellings) {
+    for (key, spelling) in spellings.iter() {
+        let value = NonEmptyStr::new(key).and_then(|key| table.remove_impl(key));
+
+        if let Some(mut value) = value {
+            if let Value::Table(table) = &mut value {
+                restore_key_spellings(table, spelling.children());
+            }
+
+            if let Some(spelling) = NonEmptyStr::new(spelling.spelling()) {
+                table.set_impl(spelling, value);
+            }
+        }
     }
 }
 
-/// Implements the `IniConfig` `.ini` parser event handler for the `DynConfig`.
+/// Implements the [`.ini parser`] [`event handler`] which builds a [`config`].
+///
+/// Use with the [`stream parser`]; otherwise prefer [`DynConfig::from_ini`].
+///
+/// [`.ini parser`]: struct.IniParser.html
+/// [`event handler`]: trait.IniConfig.html
+/// [`config`]: struct.DynConfig.html
+/// [`stream parser`]: struct.IniStreamParser.html
+/// [`DynConfig::from_ini`]: struct.DynConfig.html#method.from_ini
 #[cfg(feature = "ini")]
-pub(crate) struct DynConfigIniConfig {
+pub struct DynConfigIniConfig {
     root: DynTable,
     current_section: Option<DynTable>,
     // Never allocates if we don't support nested sections.
     section_stack: Vec<DynTable>,
     // Always `None` if we don't support arrays.
     current_array: Option<DynArray>,
+    // Array of sections which contains the current section, if any.
+    // Always `None` if we don't support arrays of sections.
+    section_array: Option<DynArray>,
+    // Pooled allocations reused for the tables / arrays / strings / keys of the built config, owned by the built config.
+    arena: DynConfigArena,
+    // Estimated memory usage of the built config, see `DynConfig::memory_usage()`.
+    // Overwritten values / array elements are not subtracted.
+    memory_usage: usize,
+    // Values / array elements are no longer added once the estimated memory usage exceeds the limit, if any.
+    max_memory_usage: Option<usize>,
 }
 
 #[cfg(feature = "ini")]
 impl DynConfigIniConfig {
+    /// Creates a new [`event handler`](struct.DynConfigIniConfig.html) with an empty root table.
     pub fn new() -> Self {
+        Self::with_arena(Default::default())
+    }
+
+    /// Creates a new [`event handler`](struct.DynConfigIniConfig.html) with an empty root table,
+    /// which reuses the allocations in the [`arena`] for the tables / arrays / string values / keys of the built [`config`],
+    /// which then owns the [`arena`].
+    ///
+    /// [`arena`]: struct.DynConfigArena.html
+    /// [`config`]: struct.DynConfig.html
+    pub fn with_arena(mut arena: DynConfigArena) -> Self {
         Self {
-            root: DynTable::new(),
+            root: arena.table(),
             current_section: None,
             section_stack: Vec::new(),
             current_array: None,
+            section_array: None,
+            arena,
+            memory_usage: 0,
+            max_memory_usage: None,
+        }
+    }
+
+    /// Sets the [`memory usage`](struct.DynConfig.html#method.memory_usage) limit of the built config, if any.
+    fn max_memory_usage(mut self, max_memory_usage: Option<usize>) -> Self {
+        self.max_memory_usage = max_memory_usage;
+        self
+    }
+
+    /// Adds `size` bytes to the estimated memory usage of the built config.
+    /// Returns `false` if the estimated memory usage exceeds the limit.
+    fn add_memory_usage(&mut self, size: usize) -> bool {
+        self.memory_usage = self.memory_usage.saturating_add(size);
+        !self.memory_limit_exceeded()
+    }
+
+    /// Returns `true` if the estimated memory usage of the built config exceeds the limit, if any.
+    fn memory_limit_exceeded(&self) -> bool {
+        matches!(self.max_memory_usage, Some(max_memory_usage) if self.memory_usage > max_memory_usage)
+    }
+
+    /// Consumes the [`event handler`](struct.DynConfigIniConfig.html) and returns its arena
+    /// with the allocations of the partially built config returned to it, e.g. if parsing failed.
+    fn into_arena(mut self) -> DynConfigArena {
+        let arena = &mut self.arena;
+
+        arena.recycle_table(self.root);
+
+        for table in self.current_section.into_iter().chain(self.section_stack) {
+            arena.recycle_table(table);
+        }
+
+        for array in self.current_array.into_iter().chain(self.section_array) {
+            arena.recycle_array(array);
         }
+
+        self.arena
     }
 
+    /// Consumes the [`event handler`](struct.DynConfigIniConfig.html) and returns the built [`config`](struct.DynConfig.html).
+    ///
+    /// NOTE - must only be called after the [`.ini parser`](struct.IniParser.html) successfully finished parsing.
     pub fn into_inner(self) -> DynConfig {
         debug_assert!(
             self.current_section.is_none(),
@@ -184,8 +782,21 @@ impl DynConfigIniConfig {
             "missing `end_section()` call"
         );
         debug_assert!(self.current_array.is_none(), "missing `end_array()` call");
+        debug_assert!(self.section_array.is_none(), "missing `end_section()` call");
+
+        DynConfig(
+            self.root,
+            Default::default(),
+            self.arena,
+            Default::default(),
+        )
+    }
+}
 
-        DynConfig(self.root)
+#[cfg(feature = "ini")]
+impl Default for DynConfigIniConfig {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -199,17 +810,30 @@ impl<'s> IniConfig<'s> for DynConfigIniConfig {
     }
 
     fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
-        let table = self.current_section.as_mut().unwrap_or(&mut self.root);
-
         let key = key.as_ne_str();
 
-        let already_existed = match value {
-            IniValue::Bool(value) => table.set(key, value),
-            IniValue::I64(value) => table.set(key, value),
-            IniValue::F64(value) => table.set(key, value),
-            IniValue::String(value) => table.set(key, value.as_str()),
+        let entry_size = if overwrite {
+            0
+        } else {
+            table_entry_memory_usage(key)
+        };
+
+        if !self.add_memory_usage(entry_size + ini_value_memory_usage(&value)) {
+            return;
+        }
+
+        let value = match value {
+            IniValue::Bool(value) => Value::Bool(value),
+            IniValue::I64(value) => Value::I64(value),
+            IniValue::F64(value) => Value::F64(value),
+            IniValue::String(value) => Value::String(self.arena.string(value.as_str())),
+            #[cfg(feature = "datetime")]
+            IniValue::DateTime(value) => Value::DateTime(value),
         };
 
+        let table = self.current_section.as_mut().unwrap_or(&mut self.root);
+        let already_existed = table.set_in_arena(key, value, &mut self.arena);
+
         debug_assert!(
             overwrite == already_existed,
             "overwrite flag mismatch when adding a value"
@@ -217,22 +841,38 @@ impl<'s> IniConfig<'s> for DynConfigIniConfig {
     }
 
     fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        let entry_size = if self.contains_key(section).is_none() {
+            table_entry_memory_usage(section.as_ne_str())
+        } else {
+            0
+        };
+
+        // The parser aborts after this event - don't start the section.
+        if !self.add_memory_usage(entry_size) {
+            return;
+        }
+
         let start_section_in_section =
-            |parent: &mut DynTable, current_section: &mut Option<DynTable>| {
+            |parent: &mut DynTable,
+             current_section: &mut Option<DynTable>,
+             arena: &mut DynConfigArena| {
                 // Overwrite the previous value / section with this key in the parent section.
                 if overwrite {
-                    let already_existed = parent.remove(section.as_ne_str());
+                    let previous = parent.remove_in_arena(section.as_ne_str(), arena);
                     debug_assert!(
-                        already_existed.is_some(),
+                        previous.is_some(),
                         "overwrite flag mismatch when starting a section"
                     );
-                    current_section.replace(DynTable::new());
+                    if let Some(previous) = previous {
+                        arena.recycle_value(previous);
+                    }
+                    current_section.replace(arena.table());
 
                 // Add a new section or continue the previous section with this key in the parent section.
                 } else {
                     // Previous value at this key was a section - continue it.
                     if let Some(previous) = parent
-                        .remove_impl(section.as_ne_str())
+                        .remove_in_arena(section.as_ne_str(), arena)
                         .map(Value::table)
                         .flatten()
                     {
@@ -240,28 +880,119 @@ impl<'s> IniConfig<'s> for DynConfigIniConfig {
 
                     // Else it was a value and we will overwrite it.
                     } else {
-                        current_section.replace(DynTable::new());
+                        current_section.replace(arena.table());
                     }
                 }
             };
 
         if let Some(mut current_section) = self.current_section.take() {
-            start_section_in_section(&mut current_section, &mut self.current_section);
+            start_section_in_section(
+                &mut current_section,
+                &mut self.current_section,
+                &mut self.arena,
+            );
+
+            self.section_stack.push(current_section);
+        } else {
+            start_section_in_section(&mut self.root, &mut self.current_section, &mut self.arena);
+        }
+    }
+
+    fn contains_array_section(&self, key: NonEmptyIniStr<'s, '_>) -> bool {
+        let table = self.current_section.as_ref().unwrap_or(&self.root);
+        matches!(
+            table
+                .get_impl(key.as_ne_str())
+                .and_then(Value::array)
+                .and_then(|array| array.get_val(0).ok()),
+            Some(Value::Table(_))
+        )
+    }
+
+    fn start_array_section(&mut self, section: NonEmptyIniStr<'s, '_>, new: bool) {
+        debug_assert!(
+            self.section_array.is_none(),
+            "arrays of sections may not be nested"
+        );
+
+        let entry_size = if self.contains_key(section).is_none() {
+            table_entry_memory_usage(section.as_ne_str())
+        } else {
+            0
+        };
+
+        let element_size = if new { ARRAY_VALUE_SIZE } else { 0 };
+
+        // The parser aborts after this event - don't start the section.
+        if !self.add_memory_usage(entry_size + element_size) {
+            return;
+        }
+
+        let start_array_section_in_section =
+            |parent: &mut DynTable,
+             current_section: &mut Option<DynTable>,
+             section_array: &mut Option<DynArray>,
+             arena: &mut DynConfigArena| {
+                // Take the previous array of sections with this key from the parent section, or start a new one.
+                let mut array = parent
+                    .remove_in_arena(section.as_ne_str(), arena)
+                    .and_then(Value::array)
+                    .unwrap_or_else(|| arena.array());
+
+                // Start a new section or continue the last section in the array.
+                let table = if new {
+                    None
+                } else {
+                    array.pop().ok().and_then(Value::table)
+                };
+
+                current_section.replace(table.unwrap_or_else(|| arena.table()));
+                section_array.replace(array);
+            };
+
+        if let Some(mut current_section) = self.current_section.take() {
+            start_array_section_in_section(
+                &mut current_section,
+                &mut self.current_section,
+                &mut self.section_array,
+                &mut self.arena,
+            );
 
             self.section_stack.push(current_section);
         } else {
-            start_section_in_section(&mut self.root, &mut self.current_section);
+            start_array_section_in_section(
+                &mut self.root,
+                &mut self.current_section,
+                &mut self.section_array,
+                &mut self.arena,
+            );
         }
     }
 
     fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
         if let Some(current_section) = self.current_section.take() {
+            // Add the current section to its array of sections, if any.
+            let current_section: DynConfigValue = match self.section_array.take() {
+                Some(mut section_array) => {
+                    let result = section_array.push(current_section.into());
+                    debug_assert!(result.is_ok(), "incorrect array of sections value type");
+                    section_array.into()
+                }
+                None => current_section.into(),
+            };
+
             if let Some(mut parent_section) = self.section_stack.pop() {
-                let already_existed = parent_section.set(section.as_ne_str(), current_section);
+                let already_existed = parent_section.set_in_arena(
+                    section.as_ne_str(),
+                    current_section,
+                    &mut self.arena,
+                );
                 debug_assert!(!already_existed);
                 self.current_section.replace(parent_section);
             } else {
-                let already_existed = self.root.set(section.as_ne_str(), current_section);
+                let already_existed =
+                    self.root
+                        .set_in_arena(section.as_ne_str(), current_section, &mut self.arena);
                 debug_assert!(!already_existed);
             }
         } else {
@@ -273,30 +1004,50 @@ impl<'s> IniConfig<'s> for DynConfigIniConfig {
     }
 
     fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        let entry_size = if overwrite {
+            0
+        } else {
+            table_entry_memory_usage(array.as_ne_str())
+        };
+
+        // The parser aborts after this event - don't start the array.
+        if !self.add_memory_usage(entry_size) {
+            return;
+        }
+
         let table = self.current_section.as_mut().unwrap_or(&mut self.root);
 
         if overwrite {
-            let previous = table.remove(array.as_ne_str());
+            let previous = table.remove_in_arena(array.as_ne_str(), &mut self.arena);
             debug_assert!(
                 previous.is_some(),
                 "overwrite flag mismatch when starting an array"
             );
+            if let Some(previous) = previous {
+                self.arena.recycle_value(previous);
+            }
         }
 
         debug_assert!(
             self.current_array.is_none(),
             "nested arrays are not supported"
         );
-        self.current_array.replace(DynArray::new());
+        self.current_array.replace(self.arena.array());
     }
 
     fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        if !self.add_memory_usage(ARRAY_VALUE_SIZE + ini_value_memory_usage(&value)) {
+            return;
+        }
+
         if let Some(current_array) = self.current_array.as_mut() {
             let result = current_array.push(match value {
                 IniValue::Bool(value) => Value::Bool(value),
                 IniValue::I64(value) => Value::I64(value),
                 IniValue::F64(value) => Value::F64(value),
-                IniValue::String(value) => Value::String(value.into()),
+                IniValue::String(value) => Value::String(self.arena.string(value.as_str())),
+                #[cfg(feature = "datetime")]
+                IniValue::DateTime(value) => Value::DateTime(value),
             });
             debug_assert!(result.is_ok(), "incorrect array value type");
         } else {
@@ -311,7 +1062,8 @@ impl<'s> IniConfig<'s> for DynConfigIniConfig {
         if let Some(current_array) = self.current_array.take() {
             let root = &mut self.root;
             let table = self.current_section.as_mut().unwrap_or(root);
-            let existed = table.set(array.as_ne_str(), current_array);
+            let existed =
+                table.set_in_arena(array.as_ne_str(), current_array.into(), &mut self.arena);
             debug_assert!(!existed);
         } else {
             debug_assert!(
@@ -320,6 +1072,14 @@ impl<'s> IniConfig<'s> for DynConfigIniConfig {
             );
         }
     }
+
+    fn abort(&self) -> Option<IniErrorKind> {
+        if self.memory_limit_exceeded() {
+            self.max_memory_usage.map(IniErrorKind::MemoryLimitExceeded)
+        } else {
+            None
+        }
+    }
 }
 
 #[cfg(feature = "bin")]
@@ -394,11 +1154,383 @@ fn value_to_bin_config(
             table_to_bin_config(value, writer)?;
             writer.end()?;
         }
+        #[cfg(feature = "datetime")]
+        DateTime(value) => {
+            writer.datetime(key, value)?;
+        }
     }
 
     Ok(())
 }
 
+/// A [`value reference`](struct.IniParser.html#method.value_references) recorded by the [`.ini parser`](struct.IniParser.html),
+/// resolved after parsing.
+#[cfg(feature = "ini")]
+struct IniValueReference {
+    // Path to the referencing value.
+    path: ConfigPath,
+    // Path to the referenced value.
+    reference: ConfigPath,
+    // Source span of the referencing value.
+    span: IniSpan,
+}
+
+/// Wraps the [`.ini parser`] [`event handler`] which builds a [`config`],
+/// recording the source spans of the values and the value references at their paths in the config.
+///
+/// [`.ini parser`]: struct.IniParser.html
+/// [`event handler`]: trait.IniConfig.html
+/// [`config`]: struct.DynConfig.html
+#[cfg(feature = "ini")]
+struct DynConfigIniSpans {
+    config: DynConfigIniConfig,
+    // Path to the current section.
+    // Ends with the array index of the current section if it is in an array of sections.
+    path: ConfigPath,
+    // Whether the current section is in an array of sections.
+    array_section: bool,
+    spans: IniSpans,
+    references: Vec<IniValueReference>,
+    // Interpolation syntax of the string values, resolved after the value references.
+    interpolation: IniInterpolation,
+    case_insensitive_keys: bool,
+}
+
+#[cfg(feature = "ini")]
+impl DynConfigIniSpans {
+    fn new(options: &IniOptions) -> Self {
+        Self::with_arena(Default::default(), options)
+    }
+
+    fn with_arena(arena: DynConfigArena, options: &IniOptions) -> Self {
+        Self {
+            config: DynConfigIniConfig::with_arena(arena)
+                .max_memory_usage(options.max_memory_usage),
+            path: ConfigPath::new(),
+            array_section: false,
+            spans: IniSpans::new(),
+            references: Vec::new(),
+            interpolation: options.interpolation,
+            case_insensitive_keys: options.case_insensitive_keys,
+        }
+    }
+
+    /// Removes the spans / value references of the overwritten value / section at `key` in the current section.
+    fn remove(&mut self, key: &NonEmptyStr) {
+        self.path.push(key);
+        self.spans.remove(&self.path);
+
+        let path = &self.path;
+        self.references
+            .retain(|reference| !reference.path.starts_with(path));
+
+        self.path.pop();
+    }
+
+    /// Resolves the value references / interpolations, recording them in the `dependencies` graph,
+    /// and returns the built config and the recorded spans.
+    fn into_inner(
+        self,
+        dependencies: &mut IniDependencyGraph,
+    ) -> Result<(DynConfig, IniSpans), IniError> {
+        let (config, spans) = self.into_config(dependencies);
+        spans.map(|spans| (config, spans))
+    }
+
+    /// Resolves the value references / interpolations, recording them in the `dependencies` graph,
+    /// and returns the built config and the recorded spans,
+    /// or the built config (with unresolved value references / interpolations) and the resolution error.
+    fn into_config(
+        self,
+        dependencies: &mut IniDependencyGraph,
+    ) -> (DynConfig, Result<IniSpans, IniError>) {
+        // Resolved value references / interpolations may increase the memory usage.
+        let mut memory_limit = IniMemoryLimit {
+            memory_usage: self.config.memory_usage,
+            max_memory_usage: self.config.max_memory_usage,
+        };
+
+        let mut config = self.config.into_inner();
+
+        let mut resolved = vec![false; self.references.len()];
+
+        for index in 0..self.references.len() {
+            if resolved[index] {
+                continue;
+            }
+
+            if let Err(err) = resolve_ini_value_reference(
+                &mut config.0,
+                &self.references,
+                &mut resolved,
+                index,
+                &mut memory_limit,
+                dependencies,
+            ) {
+                return (config, Err(err));
+            }
+        }
+
+        if let Err(err) = interpolate_ini(
+            &mut config.0,
+            &self.spans,
+            self.interpolation,
+            self.case_insensitive_keys,
+            &mut memory_limit,
+            dependencies,
+        ) {
+            return (config, Err(err));
+        }
+
+        (config, Ok(self.spans))
+    }
+}
+
+/// Tracks the estimated memory usage of the [`config`](struct.DynConfig.html) built from an `.ini` source
+/// while resolving its value references / interpolations.
+#[cfg(feature = "ini")]
+pub(super) struct IniMemoryLimit {
+    memory_usage: usize,
+    max_memory_usage: Option<usize>,
+}
+
+#[cfg(feature = "ini")]
+impl IniMemoryLimit {
+    /// Replaces a string value of `old_size` bytes with a value of `new_size` bytes.
+    /// Returns a [`MemoryLimitExceeded`](enum.IniErrorKind.html#variant.MemoryLimitExceeded) error
+    /// if the estimated memory usage exceeds the limit, if any.
+    pub(super) fn replace(&mut self, old_size: usize, new_size: usize) -> Result<(), IniErrorKind> {
+        self.memory_usage = self
+            .memory_usage
+            .saturating_sub(old_size)
+            .saturating_add(new_size);
+
+        match self.max_memory_usage {
+            Some(max_memory_usage) if self.memory_usage > max_memory_usage => {
+                Err(IniErrorKind::MemoryLimitExceeded(max_memory_usage))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Resolves the value reference at `index` in `references` (unless it is already `resolved`),
+/// after resolving all value references to / within the value it references.
+///
+/// Records the referencing value (and the referenced value, unless it is a value reference itself)
+/// as [`Reference`] nodes in the `dependencies` graph, detecting the cyclic value references.
+/// Already `resolved` value references are recorded as well, but not resolved again.
+///
+/// [`Reference`]: enum.IniDependencyKind.html#variant.Reference
+#[cfg(feature = "ini")]
+fn resolve_ini_value_reference(
+    root: &mut DynTable,
+    references: &[IniValueReference],
+    resolved: &mut [bool],
+    index: usize,
+    memory_limit: &mut IniMemoryLimit,
+    dependencies: &mut IniDependencyGraph,
+) -> Result<(), IniError> {
+    let reference = &references[index];
+
+    let error = |error| IniError {
+        line: reference.span.line,
+        column: reference.span.column,
+        offset: reference.span.start,
+        path: reference.path.clone(),
+        error,
+    };
+
+    let cyclic_reference = || {
+        error(IniErrorKind::CyclicValueReference(
+            reference.reference.clone(),
+        ))
+    };
+
+    if !dependencies.enter(IniDependencyKind::Reference, reference.path.to_string()) {
+        return Err(cyclic_reference());
+    }
+
+    if resolved[index] {
+        dependencies.exit();
+        return Ok(());
+    }
+
+    // Value references are entered when they are resolved below.
+    let is_reference = references
+        .iter()
+        .any(|other| other.path == reference.reference);
+
+    if !is_reference
+        && !dependencies.enter(
+            IniDependencyKind::Reference,
+            reference.reference.to_string(),
+        )
+    {
+        return Err(cyclic_reference());
+    }
+
+    for other in 0..references.len() {
+        if references[other].path.starts_with(&reference.reference) {
+            resolve_ini_value_reference(
+                root,
+                references,
+                resolved,
+                other,
+                memory_limit,
+                dependencies,
+            )?;
+        }
+    }
+
+    if !is_reference {
+        dependencies.exit();
+    }
+
+    let value = root.get_val_path(&reference.reference).map_err(|_| {
+        error(IniErrorKind::UnresolvedValueReference(
+            reference.reference.clone(),
+        ))
+    })?;
+
+    // Must succeed - the referencing value is the unresolved value reference string.
+    let unresolved = unwrap_unchecked(
+        root.get_string_path(&reference.path),
+        "failed to get an unresolved value reference string",
+    );
+
+    memory_limit
+        .replace(unresolved.len(), value_memory_usage(value.clone()))
+        .map_err(error)?;
+
+    let value = super::diff::to_owned_value(value);
+
+    // Must succeed - the referencing value exists.
+    let result = root.set_path(&reference.path, value);
+    debug_assert!(result.is_ok(), "failed to set a referencing value");
+
+    resolved[index] = true;
+
+    dependencies.exit();
+
+    Ok(())
+}
+
+#[cfg(feature = "ini")]
+impl<'s> IniConfig<'s> for DynConfigIniSpans {
+    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        self.config.contains_key(key)
+    }
+
+    fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
+        if overwrite {
+            self.remove(key.as_ne_str());
+        }
+
+        self.config.add_value(key, value, overwrite);
+    }
+
+    fn add_value_with_spans(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        value: IniValue<'s, '_>,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        if overwrite {
+            self.remove(key.as_ne_str());
+        }
+
+        self.path.push(key.as_ne_str());
+        self.spans.set(self.path.clone(), spans);
+        self.path.pop();
+
+        self.config.add_value(key, value, overwrite);
+    }
+
+    fn add_value_reference(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        reference: &ConfigPath,
+        value: IniStr<'s, '_>,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        // The unresolved value is overwritten when the reference is resolved.
+        self.add_value_with_spans(key, IniValue::String(value), overwrite, spans);
+
+        let mut path = self.path.clone();
+        path.push(key.as_ne_str());
+
+        self.references.push(IniValueReference {
+            path,
+            reference: reference.clone(),
+            span: spans.value,
+        });
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        // Sections overwrite the previous sections (if so configured) and values.
+        if overwrite || (self.config.contains_key(section) == Some(false)) {
+            self.remove(section.as_ne_str());
+        }
+
+        self.path.push(section.as_ne_str());
+
+        self.config.start_section(section, overwrite);
+    }
+
+    fn contains_array_section(&self, key: NonEmptyIniStr<'s, '_>) -> bool {
+        self.config.contains_array_section(key)
+    }
+
+    fn start_array_section(&mut self, section: NonEmptyIniStr<'s, '_>, new: bool) {
+        self.config.start_array_section(section, new);
+
+        // The current section is not in the array yet - its index is the array length.
+        let index = self
+            .config
+            .section_array
+            .as_ref()
+            .map_or(0, |section_array| section_array.len());
+
+        self.path.push(section.as_ne_str());
+        self.path.push(index);
+        self.array_section = true;
+    }
+
+    fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
+        if self.array_section {
+            self.path.pop();
+            self.array_section = false;
+        }
+
+        self.path.pop();
+
+        self.config.end_section(section);
+    }
+
+    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        if overwrite {
+            self.remove(array.as_ne_str());
+        }
+
+        self.config.start_array(array, overwrite);
+    }
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        self.config.add_array_value(value);
+    }
+
+    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+        self.config.end_array(array);
+    }
+
+    fn abort(&self) -> Option<IniErrorKind> {
+        self.config.abort()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(non_snake_case)]
@@ -614,6 +1746,71 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn GetPathError_segment() {
+        let mut table = DynTable::new();
+
+        let mut array = DynArray::new();
+        array.push(true.into()).unwrap();
+
+        let mut other_table = DynTable::new();
+        assert!(!other_table.set(nestr!("array"), array));
+
+        assert!(!table.set(nestr!("table"), other_table));
+
+        let get_err = |path: &[ConfigKey]| table.get_i64_path(path).err().unwrap();
+
+        let err = get_err(&["table".into(), "foo".into()]);
+        let foo = nestr!("foo").into();
+        assert_eq!(
+            err.segment().unwrap(),
+            GetPathSegment {
+                index: 1,
+                parent: &[nestr!("table").into()],
+                key: Some(&foo),
+                reason: GetPathSegmentReason::KeyDoesNotExist,
+            }
+        );
+
+        let err = get_err(&["table".into(), "array".into(), 1.into()]);
+        assert_eq!(
+            err.segment().unwrap(),
+            GetPathSegment {
+                index: 2,
+                parent: &[nestr!("table").into(), nestr!("array").into()],
+                key: Some(&1.into()),
+                reason: GetPathSegmentReason::IndexOutOfBounds(1),
+            }
+        );
+
+        let err = get_err(&["table".into(), "array".into(), 0.into(), 0.into()]);
+        assert_eq!(
+            err.segment().unwrap(),
+            GetPathSegment {
+                index: 3,
+                parent: &[nestr!("table").into(), nestr!("array").into(), 0.into()],
+                key: None,
+                reason: GetPathSegmentReason::ValueNotAnArray(ValueType::Bool),
+            }
+        );
+
+        let err = get_err(&["table".into(), "array".into(), "foo".into()]);
+        assert_eq!(
+            err.segment().unwrap(),
+            GetPathSegment {
+                index: 2,
+                parent: &[nestr!("table").into(), nestr!("array").into()],
+                key: None,
+                reason: GetPathSegmentReason::ValueNotATable(ValueType::Array),
+            }
+        );
+
+        // Not a path key error.
+        let err = get_err(&["table".into(), "array".into(), 0.into()]);
+        assert_eq!(err, GetPathError::IncorrectValueType(ValueType::Bool));
+        assert!(err.segment().is_none());
+    }
+
     // "array_value = { 54, 12, 78.9 } -- array_value
     // bool_value = true
     // float_value = 3.14
@@ -657,7 +1854,7 @@ mod tests {
         // Load the binary config.
         let config = BinConfig::new(data).unwrap();
 
-        let array_value = config.root().get_array("array_value".into()).unwrap();
+        let array_value = config.root().get_array("array_value").unwrap();
 
         assert_eq!(array_value.len(), 3);
         assert_eq!(array_value.get_i64(0).unwrap(), 54);
@@ -667,27 +1864,577 @@ mod tests {
         assert_eq!(array_value.get_i64(2).unwrap(), 78);
         assert!(cmp_f64(array_value.get_f64(2).unwrap(), 78.9));
 
-        assert_eq!(config.root().get_bool("bool_value".into()).unwrap(), true);
+        assert_eq!(config.root().get_bool("bool_value").unwrap(), true);
+
+        assert!(cmp_f64(config.root().get_f64("float_value").unwrap(), 3.14));
+
+        assert_eq!(config.root().get_i64("int_value").unwrap(), 7);
+
+        assert_eq!(config.root().get_string("string_value").unwrap(), "foo");
+
+        let table_value = config.root().get_table("table_value").unwrap();
+
+        assert_eq!(table_value.len(), 3);
+        assert_eq!(table_value.get_i64("bar").unwrap(), 2020);
+        assert!(cmp_f64(table_value.get_f64("bar").unwrap(), 2020.0));
+        assert_eq!(table_value.get_string("baz").unwrap(), "hello");
+        assert_eq!(table_value.get_bool("foo").unwrap(), false);
+    }
+
+    #[cfg(feature = "bin")]
+    #[test]
+    fn bin_config_and_back() {
+        let mut config = DynConfig::new();
+
+        let root = config.root_mut();
+
+        let mut array_value = DynArray::new();
+
+        array_value.push(Value::String("foo".into())).unwrap();
+        array_value.push(Value::String("bar".into())).unwrap();
+
+        assert!(!root.set(nestr!("array_value"), array_value));
+        assert!(!root.set(nestr!("int_value"), 7));
+
+        let mut table_value = DynTable::new();
+
+        assert!(!table_value.set(nestr!("bar"), 3.14));
+
+        let mut nested_table = DynTable::new();
+        assert!(!nested_table.set(nestr!("baz"), true));
+        assert!(!table_value.set(nestr!("nested_table"), nested_table));
+
+        assert!(!root.set(nestr!("table_value"), table_value));
+
+        // Convert to binary config.
+        let bin_config = BinConfig::from_dyn_config(&config).unwrap();
 
+        assert_eq!(bin_config.root().len(), 3);
+        assert_eq!(
+            bin_config
+                .root()
+                .get_string_path(&["array_value".into(), 1.into()])
+                .unwrap(),
+            "bar"
+        );
+        assert_eq!(bin_config.root().get_i64("int_value").unwrap(), 7);
+        assert!(bin_config
+            .root()
+            .get_bool_path(&["table_value".into(), "nested_table".into(), "baz".into()])
+            .unwrap());
+
+        // And back.
+        let config = DynConfig::from_bin_config(&bin_config);
+
+        assert_eq!(config.root().len(), 3);
+        assert_eq!(
+            config
+                .root()
+                .get_string_path(&["array_value".into(), 0.into()])
+                .unwrap(),
+            "foo"
+        );
+        assert_eq!(config.root().get_i64("int_value").unwrap(), 7);
         assert!(cmp_f64(
-            config.root().get_f64("float_value".into()).unwrap(),
+            config
+                .root()
+                .get_f64_path(&["table_value".into(), "bar".into()])
+                .unwrap(),
             3.14
         ));
+        assert!(config
+            .root()
+            .get_bool_path(&["table_value".into(), "nested_table".into(), "baz".into()])
+            .unwrap());
 
-        assert_eq!(config.root().get_i64("int_value".into()).unwrap(), 7);
+        // An empty config cannot be converted to binary config.
+        assert_eq!(
+            BinConfig::from_dyn_config(&DynConfig::new()).err().unwrap(),
+            BinConfigWriterError::EmptyRootTable
+        );
+    }
+
+    #[cfg(all(feature = "bin", feature = "datetime"))]
+    #[test]
+    fn datetime_to_bin_config_and_back() {
+        let datetime: DateTime = "1985-04-12T23:20:50.52-05:00".parse().unwrap();
+
+        let mut config = DynConfig::new();
+
+        let mut array_value = DynArray::new();
+        array_value.push(datetime.into()).unwrap();
+
+        assert!(!config.root_mut().set(nestr!("array_value"), array_value));
+        assert!(!config.root_mut().set(nestr!("datetime_value"), datetime));
 
         assert_eq!(
-            config.root().get_string("string_value".into()).unwrap(),
-            "foo"
+            config.to_lua_string().unwrap(),
+            "{\n\tarray_value = {\n\t\t\"1985-04-12T23:20:50.52-05:00\",\n\t}, -- array_value\n\tdatetime_value = \"1985-04-12T23:20:50.52-05:00\",\n}"
         );
 
-        let table_value = config.root().get_table("table_value".into()).unwrap();
+        let config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
 
-        assert_eq!(table_value.len(), 3);
-        assert_eq!(table_value.get_i64("bar".into()).unwrap(), 2020);
-        assert!(cmp_f64(table_value.get_f64("bar".into()).unwrap(), 2020.0));
-        assert_eq!(table_value.get_string("baz".into()).unwrap(), "hello");
-        assert_eq!(table_value.get_bool("foo".into()).unwrap(), false);
+        assert_eq!(
+            config.root().get_datetime("datetime_value").unwrap(),
+            datetime
+        );
+
+        let config = config.to_dyn_config();
+
+        assert_eq!(
+            config.root().get_datetime("datetime_value").unwrap(),
+            datetime
+        );
+        assert_eq!(
+            config
+                .root()
+                .get_datetime_path(&["array_value".into(), 0.into()])
+                .unwrap(),
+            datetime
+        );
+    }
+
+    #[test]
+    fn extract_retain_paths() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        let mut size = DynArray::new();
+        size.push(640.into()).unwrap();
+        size.push(480.into()).unwrap();
+        root.set_path(&["render".into(), "window".into(), "size".into()], size)
+            .unwrap();
+        root.set_path(&["render".into(), "window".into(), "title".into()], "foo")
+            .unwrap();
+
+        let mut views = DynArray::new();
+
+        for (fov, name) in [(90, "a"), (60, "b")].iter() {
+            let mut view = DynTable::new();
+            view.set(nestr!("fov"), *fov);
+            view.set(nestr!("name"), *name);
+            views.push(view.into()).unwrap();
+        }
+
+        root.set_path(&["render".into(), "views".into()], views)
+            .unwrap();
+        root.set_path(&["audio".into(), "volume".into()], 0.5)
+            .unwrap();
+        root.set_path(&["physics".into(), "gravity".into()], 9.8)
+            .unwrap();
+
+        let patterns = |patterns: &[&str]| -> Vec<ConfigPathPattern> {
+            patterns
+                .iter()
+                .map(|pattern| pattern.parse().unwrap())
+                .collect()
+        };
+
+        // Extract.
+        let extracted = config.extract(patterns(&[
+            "render.window.size",
+            "render.views[*].fov",
+            "a*",
+        ]));
+        let root = extracted.root();
+
+        assert_eq!(root.len(), 2);
+        assert_eq!(root.get_table("render").unwrap().len(), 2);
+        assert_eq!(
+            root.get_table_path(&["render".into(), "window".into()])
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            root.get_i64_path(&"render.window.size[1]".parse::<ConfigPath>().unwrap())
+                .unwrap(),
+            480
+        );
+        assert_eq!(
+            root.get_i64_path(&"render.views[1].fov".parse::<ConfigPath>().unwrap())
+                .unwrap(),
+            60
+        );
+        assert!(!root
+            .get_table_path(&"render.views[0]".parse::<ConfigPath>().unwrap())
+            .unwrap()
+            .contains("name"));
+        assert!(cmp_f64(
+            root.get_f64_path(&["audio".into(), "volume".into()])
+                .unwrap(),
+            0.5
+        ));
+
+        // Unmatched array elements are skipped.
+        let extracted = config.extract(patterns(&["render.views[1]"]));
+        let views = extracted
+            .root()
+            .get_array_path(&["render".into(), "views".into()])
+            .unwrap();
+
+        assert_eq!(views.len(), 1);
+        assert_eq!(
+            views.get_string_path(&[0.into(), "name".into()]).unwrap(),
+            "b"
+        );
+
+        assert!(config.extract(patterns(&["missing"])).root().is_empty());
+        assert!(config
+            .extract(patterns(&["render.window.title.foo"]))
+            .root()
+            .is_empty());
+        assert_eq!(config.extract(patterns(&[""])).root().len(), 3);
+
+        // Retain.
+        config.retain_paths(patterns(&[""]));
+        assert_eq!(config.root().len(), 3);
+
+        config.retain_paths(patterns(&["render.window.*", "physics", "render.views[7]"]));
+        let root = config.root();
+
+        assert_eq!(root.len(), 2);
+        assert_eq!(root.get_table("render").unwrap().len(), 1);
+        assert_eq!(
+            root.get_table_path(&["render".into(), "window".into()])
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(cmp_f64(
+            root.get_f64_path(&["physics".into(), "gravity".into()])
+                .unwrap(),
+            9.8
+        ));
+    }
+
+    #[test]
+    fn project() {
+        let config = dyn_config! {
+            render: {
+                window: { size: [640, 480], title: "foo" },
+                views: [{ fov: 90, name: "a" }, { fov: 60, name: "b" }],
+            },
+            "a*": 7,
+            audio: { volume: 0.5 },
+        };
+
+        let paths = |paths: &[&str]| -> Vec<ConfigPath> {
+            paths.iter().map(|path| path.parse().unwrap()).collect()
+        };
+
+        let projected = config.project(&paths(&[
+            "render.window.size",
+            "render.views[1].fov",
+            "a*",
+            "missing.path",
+        ]));
+        let root = projected.root();
+
+        // Keys are matched exactly.
+        assert_eq!(root.len(), 2);
+        assert_eq!(root.get_i64("a*").unwrap(), 7);
+
+        let render = root.get_table("render").unwrap();
+        assert_eq!(render.len(), 2);
+        assert_eq!(render.get_table("window").unwrap().len(), 1);
+        assert_eq!(
+            render
+                .get_i64_path(&"window.size[1]".parse::<ConfigPath>().unwrap())
+                .unwrap(),
+            480
+        );
+
+        // Array elements not at any of the paths are skipped.
+        let views = render.get_array("views").unwrap();
+        assert_eq!(views.len(), 1);
+        assert_eq!(views.get_table(0).unwrap().len(), 1);
+        assert_eq!(views.get_i64_path(&[0.into(), "fov".into()]).unwrap(), 60);
+
+        assert!(config.project(&paths(&["render.*"])).root().is_empty());
+        assert!(config.project(&[]).root().is_empty());
+        assert_eq!(config.project(&[ConfigPath::new()]).root().len(), 3);
+    }
+
+    #[test]
+    fn to_lua_string_opts() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        let mut ints = DynArray::new();
+        ints.push(1.into()).unwrap();
+        ints.push(2.into()).unwrap();
+        ints.push(3.into()).unwrap();
+        assert!(!root.set(nestr!("ints"), ints));
+
+        let mut floats = DynArray::new();
+        floats.push(0.5.into()).unwrap();
+        floats.push(1.into()).unwrap();
+        floats.push(1.5.into()).unwrap();
+        floats.push(2.into()).unwrap();
+        floats.push(2.5.into()).unwrap();
+        assert!(!root.set(nestr!("floats"), floats));
+
+        let mut strings = DynArray::new();
+        strings.push("foo".into()).unwrap();
+        strings.push("bar".into()).unwrap();
+        assert!(!root.set(nestr!("strings"), strings));
+
+        let mut table = DynTable::new();
+        let mut bools = DynArray::new();
+        bools.push(true.into()).unwrap();
+        bools.push(false.into()).unwrap();
+        assert!(!table.set(nestr!("bools"), bools));
+        assert!(!table.set(nestr!("empty"), DynArray::new()));
+        assert!(!root.set(nestr!("table"), table));
+
+        assert_eq!(
+            config
+                .to_lua_string_opts(ToLuaStringOptions {
+                    compact_arrays: true,
+                    compact_array_wrap: 4,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "{\n\tfloats = {\n\t\t0.5, 1, 1.5, 2,\n\t\t2.5,\n\t}, -- floats\n\tints = { 1, 2, 3 }, -- ints\n\tstrings = {\n\t\t\"foo\",\n\t\t\"bar\",\n\t}, -- strings\n\ttable = {\n\t\tbools = { true, false }, -- bools\n\t\tempty = {\n\t\t}, -- empty\n\t}, -- table\n}"
+        );
+
+        // Compact arrays are disabled by default.
+        assert_eq!(
+            config.to_lua_string().unwrap(),
+            config
+                .to_lua_string_opts(ToLuaStringOptions {
+                    compact_arrays: false,
+                    compact_array_wrap: 4,
+                    ..Default::default()
+                })
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn formatter() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        assert!(!root.set(nestr!("a"), 1.5));
+        assert!(!root.set(nestr!("bb"), "it's"));
+
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("c"), true));
+        assert!(!root.set(nestr!("table"), table));
+
+        let formatter = ConfigFormatter {
+            indent: ConfigIndent::Spaces(2),
+            quotes: ConfigQuotes::Single,
+            float_format: ConfigFloatFormat::Fixed(2),
+            new_line: ConfigNewLine::CrLf,
+            align_values: true,
+        };
+
+        assert_eq!(
+            config
+                .to_lua_string_opts(ToLuaStringOptions {
+                    formatter,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "{\r\n  a     = 1.50,\r\n  bb    = 'it\\'s',\r\n  table = {\r\n    c = true,\r\n  }, -- table\r\n}"
+        );
+
+        #[cfg(feature = "ini")]
+        {
+            let string = config
+                .to_ini_string_opts(ToIniStringOptions {
+                    formatter,
+                    ..Default::default()
+                })
+                .unwrap();
+
+            assert_eq!(
+                string,
+                "a  = 1.50\r\nbb = 'it\\'s'\r\n\r\n[table]\r\nc = true"
+            );
+
+            let config =
+                DynConfig::from_ini(IniParser::new(&string).string_quotes(IniStringQuote::Single))
+                    .unwrap();
+
+            assert!(cmp_f64(config.root().get_f64("a").unwrap(), 1.5));
+            assert_eq!(config.root().get_string("bb").unwrap(), "it's");
+        }
+    }
+
+    #[test]
+    fn float_round_trip() {
+        let values = [
+            0.1,
+            1.0,
+            -0.0,
+            0.1 + 0.2,
+            1e300,
+            5e-324,
+            f64::MAX,
+            std::f64::consts::PI,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+
+        let mut config = DynConfig::new();
+
+        let mut array = DynArray::new();
+
+        for value in values.iter() {
+            array.push((*value).into()).unwrap();
+        }
+
+        assert!(!config.root_mut().set(nestr!("array"), array));
+
+        assert_eq!(
+            config
+                .to_lua_string_opts(ToLuaStringOptions {
+                    compact_arrays: true,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "{\n\tarray = { 0.1, 1.0, -0.0, 0.30000000000000004, 1e300, 5e-324, 1.7976931348623157e308, 3.141592653589793, (1/0), (-1/0) }, -- array\n}"
+        );
+
+        #[cfg(feature = "ini")]
+        {
+            let string = config
+                .to_ini_string_opts(ToIniStringOptions {
+                    arrays: true,
+                    ..Default::default()
+                })
+                .unwrap();
+
+            assert_eq!(
+                string,
+                "array = [0.1, 1.0, -0.0, 0.30000000000000004, 1e300, 5e-324, 1.7976931348623157e308, 3.141592653589793, inf, -inf]"
+            );
+
+            let parsed = DynConfig::from_ini(IniParser::new(&string).arrays(true)).unwrap();
+            let array = parsed.root().get_array("array").unwrap();
+
+            assert_eq!(array.len(), values.len() as u32);
+
+            for (index, value) in values.iter().enumerate() {
+                assert_eq!(
+                    array.get_f64(index as u32).unwrap().to_bits(),
+                    value.to_bits()
+                );
+            }
+
+            // Fixed precision still serializes a float.
+            let mut config = DynConfig::new();
+            assert!(!config.root_mut().set(nestr!("a"), 1.25));
+
+            let string = config
+                .to_ini_string_opts(ToIniStringOptions {
+                    formatter: ConfigFormatter {
+                        float_format: ConfigFloatFormat::Fixed(0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .unwrap();
+
+            assert_eq!(string, "a = 1.0");
+
+            let parsed = DynConfig::from_ini(IniParser::new(&string)).unwrap();
+            assert!(cmp_f64(parsed.root().get_f64("a").unwrap(), 1.0));
+        }
+    }
+
+    #[test]
+    fn content_hash() {
+        let config = dyn_config! {
+            name: "Game",
+            scale: 1.5,
+            window: { width: 1920, height: 1080, title: "foo\nbar" },
+            levels: [{ name: "intro" }, { name: "boss" }],
+        };
+
+        // Same contents added in a different order.
+        let mut other = DynConfig::new();
+        let mut window = DynTable::new();
+        window.set(nestr!("title"), "foo\nbar");
+        window.set(nestr!("height"), 1080);
+        window.set(nestr!("width"), 1920);
+        other.root_mut().set(nestr!("window"), window);
+        other.root_mut().set(
+            nestr!("levels"),
+            dyn_config! { levels: [{ name: "intro" }, { name: "boss" }] }
+                .root()
+                .get_array("levels")
+                .unwrap()
+                .clone(),
+        );
+        other.root_mut().set(nestr!("scale"), 1.5);
+        other.root_mut().set(nestr!("name"), "Game");
+
+        assert_eq!(config.content_hash(), other.content_hash());
+        assert_eq!(
+            config.to_lua_string_opts(ToLuaStringOptions::canonical()),
+            other.to_lua_string_opts(ToLuaStringOptions::canonical())
+        );
+
+        // Different contents.
+        other.root_mut().set(nestr!("scale"), 1.25);
+        assert_ne!(config.content_hash(), other.content_hash());
+
+        // Different types.
+        other.root_mut().set(nestr!("scale"), 1);
+        let mut float = other.project(&[ConfigPath::new()]);
+        float.root_mut().set(nestr!("scale"), 1.0);
+        assert_ne!(float.content_hash(), other.content_hash());
+
+        // Same hash regardless of the config backend.
+        #[cfg(feature = "bin")]
+        {
+            let bin_config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+            assert_eq!(
+                bin_config.to_lua_string_opts(ToLuaStringOptions::canonical()),
+                config.to_lua_string_opts(ToLuaStringOptions::canonical())
+            );
+            assert_eq!(bin_config.content_hash(), config.content_hash());
+        }
+
+        // Canonical `.ini` serialization.
+        #[cfg(feature = "ini")]
+        {
+            let string = config
+                .to_ini_string_opts(ToIniStringOptions::canonical())
+                .unwrap();
+
+            let parsed = DynConfig::from_ini(
+                IniParser::new(&string)
+                    .arrays(true)
+                    .array_sections(true)
+                    .nested_section_depth(u32::MAX),
+            )
+            .unwrap();
+
+            assert_eq!(parsed.content_hash(), config.content_hash());
+            assert_eq!(
+                parsed
+                    .to_ini_string_opts(ToIniStringOptions::canonical())
+                    .unwrap(),
+                string
+            );
+
+            #[cfg(feature = "bin")]
+            {
+                let bin_config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+                assert_eq!(
+                    bin_config
+                        .to_ini_string_opts(ToIniStringOptions::canonical())
+                        .unwrap(),
+                    string
+                );
+            }
+        }
     }
 
     #[cfg(feature = "ini")]
@@ -754,5 +2501,281 @@ string = "bar""#;
             .unwrap();
 
         assert_eq!(string, ini);
+
+        let mut bytes = Vec::new();
+
+        config
+            .write_ini_opts(
+                ToIniStringOptions {
+                    arrays: true,
+                    ..Default::default()
+                },
+                &mut bytes,
+            )
+            .unwrap();
+
+        assert_eq!(std::str::from_utf8(&bytes).unwrap(), ini);
+
+        // Writer errors.
+        let mut buffer = [0u8; 16];
+
+        assert_eq!(
+            config
+                .write_ini_opts(
+                    ToIniStringOptions {
+                        arrays: true,
+                        ..Default::default()
+                    },
+                    &mut &mut buffer[..],
+                )
+                .err()
+                .unwrap(),
+            ToIniStringError::WriteError
+        );
+        assert_eq!(&buffer, b"array = [\"foo\", ");
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn write_ini() {
+        let mut array = DynArray::new();
+        array.push(7.into()).unwrap();
+
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+        root.set(nestr!("array"), array);
+        root.set(nestr!("bool"), true);
+        root.set(nestr!("string"), "foo");
+
+        let options = ToIniStringOptions {
+            arrays: true,
+            ..Default::default()
+        };
+        let ini = "array = [7]\nbool = true\nstring = \"foo\"";
+
+        // `std::fmt::Write` sink.
+        let mut string = String::new();
+        config.fmt_ini_opts(options, &mut string).unwrap();
+        assert_eq!(string, ini);
+
+        // `std::io::Write` sink.
+        let mut bytes = Vec::new();
+        config.write_ini_opts(options, &mut bytes).unwrap();
+        assert_eq!(std::str::from_utf8(&bytes).unwrap(), ini);
+
+        // Serialization errors are returned as is.
+        assert_eq!(
+            config.write_ini(&mut Vec::new()).err().unwrap(),
+            ToIniStringError::ArraysNotAllowed
+        );
+
+        // Writer errors - the buffer is too small.
+        let mut buffer = [0u8; 8];
+        assert_eq!(
+            config
+                .write_ini_opts(options, &mut &mut buffer[..])
+                .err()
+                .unwrap(),
+            ToIniStringError::WriteError
+        );
+        assert_eq!(&buffer, b"array = ");
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn to_ini_string_layout() {
+        let config = dyn_config! {
+            a: 1,
+            b: [true],
+            c: { d: "foo", e: { f: 2.5 } },
+            g: { h: 3 },
+        };
+
+        let to_ini_string = |options| config.to_ini_string_opts(options).unwrap();
+
+        assert_eq!(
+            to_ini_string(ToIniStringOptions {
+                arrays: true,
+                nested_section_depth: 2,
+                key_order: IniKeyOrder::Custom(|l, r| r.cmp(l)),
+                blank_lines_between_sections: 2,
+                space_around_separator: false,
+                ..Default::default()
+            }),
+            "b=[true]\na=1\n\n\n[g]\nh=3\n\n\n[c]\nd=\"foo\"\n\n\n[c/e]\nf=2.5"
+        );
+
+        assert_eq!(
+            to_ini_string(ToIniStringOptions {
+                arrays: true,
+                blank_lines_between_sections: 0,
+                nested_tables: IniNestedTables::Flatten('.'),
+                ..Default::default()
+            }),
+            "a = 1\nb = [true]\n[c]\nd = \"foo\"\ne.f = 2.5\n[g]\nh = 3"
+        );
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn to_ini_string_comments() {
+        let mut config = dyn_config! {
+            a: 1,
+            c: { d: "foo", e: { f: 2.5 } },
+            g: [{ h: 3 }],
+        };
+
+        assert!(config.set_comment(ConfigPath::new(), "Settings.").is_none());
+        config.set_comment(
+            "a".parse::<ConfigPath>().unwrap(),
+            "First line.\n\nThird line.",
+        );
+        config.set_comment("c".parse::<ConfigPath>().unwrap(), "Section.");
+        config.set_comment("c.e".parse::<ConfigPath>().unwrap(), "Nested table.");
+        config.set_comment("c.e.f".parse::<ConfigPath>().unwrap(), "Nested value.");
+        config.set_comment("g".parse::<ConfigPath>().unwrap(), "Array of sections.");
+        // Ignored.
+        config.set_comment(
+            "g[0].h".parse::<ConfigPath>().unwrap(),
+            "Array element value.",
+        );
+        config.set_comment("missing".parse::<ConfigPath>().unwrap(), "Missing.");
+
+        assert_eq!(
+            config
+                .set_comment("c".parse::<ConfigPath>().unwrap(), "A section.")
+                .unwrap(),
+            "Section."
+        );
+        assert_eq!(config.comment(&"c".parse().unwrap()).unwrap(), "A section.");
+
+        let string = config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_section_depth: 2,
+                array_sections: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            string,
+            "; Settings.\n\
+            \n\
+            ; First line.\n\
+            ;\n\
+            ; Third line.\n\
+            a = 1\n\
+            \n\
+            ; A section.\n\
+            [c]\n\
+            d = \"foo\"\n\
+            \n\
+            ; Nested table.\n\
+            [c/e]\n\
+            ; Nested value.\n\
+            f = 2.5\n\
+            \n\
+            ; Array of sections.\n\
+            [[g]]\n\
+            h = 3"
+        );
+
+        // Comments are skipped by the parser.
+        let parsed = DynConfig::from_ini(
+            IniParser::new(&string)
+                .nested_section_depth(2)
+                .array_sections(true),
+        )
+        .unwrap();
+        assert!(parsed.diff(&config).is_empty());
+
+        // Flattened tables.
+        assert_eq!(
+            config
+                .to_ini_string_opts(ToIniStringOptions {
+                    nested_tables: IniNestedTables::Flatten('.'),
+                    array_sections: true,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "; Settings.\n\
+            \n\
+            ; First line.\n\
+            ;\n\
+            ; Third line.\n\
+            a = 1\n\
+            \n\
+            ; A section.\n\
+            [c]\n\
+            d = \"foo\"\n\
+            ; Nested table.\n\
+            ; Nested value.\n\
+            e.f = 2.5\n\
+            \n\
+            ; Array of sections.\n\
+            [[g]]\n\
+            h = 3"
+        );
+
+        // Comments are kept on clear.
+        config.clear();
+        assert_eq!(config.to_ini_string().unwrap(), "; Settings.\n");
+
+        assert_eq!(
+            config.remove_comment(&ConfigPath::new()).unwrap(),
+            "Settings."
+        );
+        assert!(config.comment(&ConfigPath::new()).is_none());
+        assert_eq!(config.to_ini_string().unwrap(), "");
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn replay_ini() {
+        let config = dyn_config! {
+            name: "Game",
+            empty: "",
+            version: 3,
+            scale: 1.5,
+            window: {
+                width: 1920,
+                fullscreen: false,
+                monitor: { index: 1 },
+            },
+            levels: ["intro", "boss"],
+            enemies: [
+                { name: "orc", hp: 10 },
+                { name: "troll", hp: 25 },
+            ],
+            none: [],
+        };
+
+        let mut replayed = DynConfigIniConfig::new();
+        config.replay_ini(&mut replayed).unwrap();
+        let replayed = replayed.into_inner();
+
+        assert!(config.diff(&replayed).is_empty());
+        assert_eq!(
+            replayed
+                .root()
+                .get_array("enemies")
+                .unwrap()
+                .get_table(1)
+                .unwrap()
+                .get_i64("hp")
+                .unwrap(),
+            25
+        );
+
+        // Arrays of arrays have no `.ini` representation.
+        let config = dyn_config! { matrix: [[1, 2], [3, 4]] };
+
+        assert_eq!(
+            config
+                .replay_ini(&mut DynConfigIniConfig::new())
+                .err()
+                .unwrap(),
+            ToIniStringError::InvalidArrayType
+        );
     }
 }
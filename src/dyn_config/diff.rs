@@ -0,0 +1,370 @@
+use {
+    crate::*,
+    std::fmt::{Display, Formatter},
+};
+
+/// A single difference between two [`dynamic configs`] / [`tables`],
+/// as returned by [`DynConfig::diff`] / [`DynTable::diff`].
+///
+/// [`dynamic configs`]: struct.DynConfig.html
+/// [`tables`]: struct.DynTable.html
+/// [`DynConfig::diff`]: struct.DynConfig.html#method.diff
+/// [`DynTable::diff`]: struct.DynTable.html#method.diff
+#[derive(Clone)]
+pub enum ConfigChange {
+    /// The [`value`] at `path` only exists in the new config.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    Added {
+        path: ConfigPath,
+        value: DynConfigValue,
+    },
+    /// The [`value`] at `path` only exists in the old config.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    Removed {
+        path: ConfigPath,
+        value: DynConfigValue,
+    },
+    /// The [`value`] at `path` exists in both configs, but is different.
+    ///
+    /// Only reported for non-[`array`] / [`table`] values,
+    /// or if the value [`type`] changed;
+    /// differences in nested [`arrays`] / [`tables`] are reported per nested value.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`table`]: struct.DynTable.html
+    /// [`type`]: enum.ValueType.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`tables`]: struct.DynTable.html
+    Modified {
+        path: ConfigPath,
+        old: DynConfigValue,
+        new: DynConfigValue,
+    },
+}
+
+impl ConfigChange {
+    /// Returns the path to the changed value from the root of the diffed configs / tables.
+    pub fn path(&self) -> &ConfigPath {
+        match self {
+            ConfigChange::Added { path, .. } => path,
+            ConfigChange::Removed { path, .. } => path,
+            ConfigChange::Modified { path, .. } => path,
+        }
+    }
+}
+
+impl Display for ConfigChange {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ConfigChange::Added { path, value } => write!(f, "+ {}: {}", path, value),
+            ConfigChange::Removed { path, value } => write!(f, "- {}: {}", path, value),
+            ConfigChange::Modified { path, old, new } => {
+                write!(f, "~ {}: {} -> {}", path, old, new)
+            }
+        }
+    }
+}
+
+/// Differences between two [`dynamic configs`] / [`tables`],
+/// as returned by [`DynConfig::diff`] / [`DynTable::diff`].
+///
+/// [`Changes`] are ordered by path, [`table`] keys in alphabetical order.
+///
+/// [`dynamic configs`]: struct.DynConfig.html
+/// [`tables`]: struct.DynTable.html
+/// [`DynConfig::diff`]: struct.DynConfig.html#method.diff
+/// [`DynTable::diff`]: struct.DynTable.html#method.diff
+/// [`Changes`]: enum.ConfigChange.html
+/// [`table`]: struct.DynTable.html
+#[derive(Clone, Default)]
+pub struct ConfigDiff(Vec<ConfigChange>);
+
+impl ConfigDiff {
+    /// Returns the number of [`changes`](enum.ConfigChange.html).
+    pub fn len(&self) -> u32 {
+        self.0.len() as _
+    }
+
+    /// Returns `true` if the diffed configs / tables are equal.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the [`changes`](enum.ConfigChange.html), ordered by path.
+    pub fn changes(&self) -> &[ConfigChange] {
+        &self.0
+    }
+
+    /// Returns an iterator over the [`changes`](enum.ConfigChange.html), ordered by path.
+    pub fn iter(&self) -> impl Iterator<Item = &ConfigChange> {
+        self.0.iter()
+    }
+
+    /// Consumes the diff and returns the [`changes`](enum.ConfigChange.html), ordered by path.
+    pub fn into_changes(self) -> Vec<ConfigChange> {
+        self.0
+    }
+}
+
+impl Display for ConfigDiff {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for change in self.0.iter() {
+            writeln!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DynConfig {
+    /// Returns the differences between this (old) [`config`] and the `new` [`config`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    pub fn diff(&self, new: &DynConfig) -> ConfigDiff {
+        self.root().diff(new.root())
+    }
+}
+
+impl DynTable {
+    /// Returns the differences between this (old) [`table`] and the `new` [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn diff(&self, new: &DynTable) -> ConfigDiff {
+        let mut changes = Vec::new();
+        diff_tables(self, new, &mut Vec::new(), &mut changes);
+        ConfigDiff(changes)
+    }
+}
+
+fn diff_tables(
+    old: &DynTable,
+    new: &DynTable,
+    path: &mut Vec<OwnedConfigKey>,
+    changes: &mut Vec<ConfigChange>,
+) {
+    let mut keys: Vec<_> = old
+        .iter()
+        .map(|(key, _)| key)
+        .chain(new.iter().map(|(key, _)| key))
+        .collect();
+    keys.sort_by(|l, r| l.as_str().cmp(r.as_str()));
+    keys.dedup();
+
+    for key in keys.into_iter() {
+        path.push(key.into());
+
+        match (old.get_val(key), new.get_val(key)) {
+            (Some(old), Some(new)) => diff_values(old, new, path, changes),
+            (Some(old), None) => changes.push(ConfigChange::Removed {
+                path: path.clone().into(),
+                value: to_owned_value(old),
+            }),
+            (None, Some(new)) => changes.push(ConfigChange::Added {
+                path: path.clone().into(),
+                value: to_owned_value(new),
+            }),
+            (None, None) => debug_unreachable!("key must exist in at least one of the tables"),
+        }
+
+        path.pop();
+    }
+}
+
+fn diff_arrays(
+    old: &DynArray,
+    new: &DynArray,
+    path: &mut Vec<OwnedConfigKey>,
+    changes: &mut Vec<ConfigChange>,
+) {
+    for index in 0..old.len().max(new.len()) {
+        path.push(index.into());
+
+        match (old.get_val(index).ok(), new.get_val(index).ok()) {
+            (Some(old), Some(new)) => diff_values(old, new, path, changes),
+            (Some(old), None) => changes.push(ConfigChange::Removed {
+                path: path.clone().into(),
+                value: to_owned_value(old),
+            }),
+            (None, Some(new)) => changes.push(ConfigChange::Added {
+                path: path.clone().into(),
+                value: to_owned_value(new),
+            }),
+            (None, None) => debug_unreachable!("index must be valid in at least one of the arrays"),
+        }
+
+        path.pop();
+    }
+}
+
+fn diff_values(
+    old: DynConfigValueRef<'_>,
+    new: DynConfigValueRef<'_>,
+    path: &mut Vec<OwnedConfigKey>,
+    changes: &mut Vec<ConfigChange>,
+) {
+    let equal = match (&old, &new) {
+        (Value::Table(old), Value::Table(new)) => return diff_tables(old, new, path, changes),
+        (Value::Array(old), Value::Array(new)) => return diff_arrays(old, new, path, changes),
+        (Value::Bool(old), Value::Bool(new)) => old == new,
+        (Value::I64(old), Value::I64(new)) => old == new,
+        (Value::F64(old), Value::F64(new)) => old == new || (old.is_nan() && new.is_nan()),
+        (Value::String(old), Value::String(new)) => old == new,
+        #[cfg(feature = "datetime")]
+        (Value::DateTime(old), Value::DateTime(new)) => old == new,
+        _ => false,
+    };
+
+    if !equal {
+        changes.push(ConfigChange::Modified {
+            path: path.clone().into(),
+            old: to_owned_value(old),
+            new: to_owned_value(new),
+        });
+    }
+}
+
+pub(super) fn to_owned_value(value: DynConfigValueRef<'_>) -> DynConfigValue {
+    match value {
+        Value::Bool(value) => Value::Bool(value),
+        Value::I64(value) => Value::I64(value),
+        Value::F64(value) => Value::F64(value),
+        Value::String(value) => Value::String(value.to_owned()),
+        Value::Array(value) => Value::Array(value.clone()),
+        Value::Table(value) => Value::Table(value.clone()),
+        #[cfg(feature = "datetime")]
+        Value::DateTime(value) => Value::DateTime(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn diff() {
+        let mut old = DynConfig::new();
+        let mut new = DynConfig::new();
+
+        // Equal.
+        old.root_mut().set(nestr!("same"), 7);
+        new.root_mut().set(nestr!("same"), 7);
+
+        // Modified.
+        old.root_mut().set(nestr!("modified"), 7);
+        new.root_mut().set(nestr!("modified"), 9);
+
+        // Type changed.
+        old.root_mut().set(nestr!("retyped"), 7);
+        new.root_mut().set(nestr!("retyped"), "7");
+
+        // Removed / added.
+        old.root_mut().set(nestr!("removed"), true);
+        new.root_mut().set(nestr!("added"), 3.5);
+
+        // Nested.
+        let mut old_table = DynTable::new();
+        old_table.set(nestr!("foo"), 1);
+        old_table.set(nestr!("bar"), 2);
+
+        let mut new_table = DynTable::new();
+        new_table.set(nestr!("foo"), 1);
+        new_table.set(nestr!("bar"), 3);
+
+        let mut old_array = DynArray::new();
+        old_array.push(1.into()).unwrap();
+        old_array.push(2.into()).unwrap();
+
+        let mut new_array = DynArray::new();
+        new_array.push(1.into()).unwrap();
+        new_array.push(5.into()).unwrap();
+        new_array.push(6.into()).unwrap();
+
+        old_table.set(nestr!("array"), old_array);
+        new_table.set(nestr!("array"), new_array);
+
+        old.root_mut().set(nestr!("table"), old_table);
+        new.root_mut().set(nestr!("table"), new_table);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.len(), 7);
+
+        let paths: Vec<_> = diff.iter().map(|change| change.path().clone()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "added".parse().unwrap(),
+                "modified".parse().unwrap(),
+                "removed".parse().unwrap(),
+                "retyped".parse().unwrap(),
+                "table.array[1]".parse().unwrap(),
+                "table.array[2]".parse().unwrap(),
+                "table.bar".parse::<ConfigPath>().unwrap(),
+            ]
+        );
+
+        let changes = diff.changes();
+
+        assert!(matches!(
+            changes[0],
+            ConfigChange::Added {
+                value: Value::F64(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            changes[1],
+            ConfigChange::Modified {
+                old: Value::I64(7),
+                new: Value::I64(9),
+                ..
+            }
+        ));
+        assert!(matches!(
+            changes[2],
+            ConfigChange::Removed {
+                value: Value::Bool(true),
+                ..
+            }
+        ));
+        assert!(matches!(
+            changes[3],
+            ConfigChange::Modified {
+                old: Value::I64(7),
+                new: Value::String(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            changes[4],
+            ConfigChange::Modified {
+                old: Value::I64(2),
+                new: Value::I64(5),
+                ..
+            }
+        ));
+        assert!(matches!(
+            changes[5],
+            ConfigChange::Added {
+                value: Value::I64(6),
+                ..
+            }
+        ));
+        assert!(matches!(
+            changes[6],
+            ConfigChange::Modified {
+                old: Value::I64(2),
+                new: Value::I64(3),
+                ..
+            }
+        ));
+
+        assert!(new.diff(&new).is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+use {super::hashed_key::*, crate::*, std::collections::hash_map::Entry};
+
+/// A view into a single entry in a [`table`] with a (non-empty) string key, which may either be occupied or vacant.
+///
+/// Returned by [`DynTable::entry`].
+///
+/// [`table`]: struct.DynTable.html
+/// [`DynTable::entry`]: struct.DynTable.html#method.entry
+pub struct DynTableEntry<'t>(pub(super) Entry<'t, DynTableKey, DynConfigValue>);
+
+impl<'t> DynTableEntry<'t> {
+    /// Returns the (non-empty) string key of the entry.
+    pub fn key(&self) -> &NonEmptyStr {
+        self.0.key().key()
+    }
+
+    /// Returns `true` if the [`table`] contains a [`value`] with the entry's key.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`value`]: type.DynConfigValue.html
+    pub fn is_occupied(&self) -> bool {
+        matches!(self.0, Entry::Occupied(_))
+    }
+
+    /// Inserts the `default` [`value`] if the entry is vacant,
+    /// and returns a mutable reference to the [`value`] in the entry.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    pub fn or_insert<V: Into<DynConfigValue>>(self, default: V) -> &'t mut DynConfigValue {
+        self.0.or_insert_with(|| default.into())
+    }
+
+    /// Inserts the [`value`] returned by the `default` function if the entry is vacant,
+    /// and returns a mutable reference to the [`value`] in the entry.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    pub fn or_insert_with<V: Into<DynConfigValue>, F: FnOnce() -> V>(
+        self,
+        default: F,
+    ) -> &'t mut DynConfigValue {
+        self.0.or_insert_with(|| default().into())
+    }
+
+    /// Calls the function `f` with a mutable reference to the [`value`] in the entry if it is occupied.
+    /// Returns the entry.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    pub fn and_modify<F: FnOnce(&mut DynConfigValue)>(self, f: F) -> Self {
+        Self(self.0.and_modify(f))
+    }
+}
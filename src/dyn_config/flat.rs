@@ -0,0 +1,617 @@
+use {
+    crate::*,
+    std::{
+        cmp::Ordering,
+        collections::HashMap,
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// An [`array`] value in a [`flat config map`].
+///
+/// The [`array`] elements are stored in the [`flat config map`] under their own paths (e.g. `"array[0]"`).
+///
+/// [`array`]: struct.DynArray.html
+/// [`flat config map`]: type.FlatConfigMap.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FlatArray {
+    /// Number of elements in the [`array`](struct.DynArray.html).
+    pub len: u32,
+}
+
+/// A [`value`] in a [`flat config map`].
+///
+/// [`Tables`] / [`arrays`] only mark the presence of the (possibly empty) [`table`] / [`array`];
+/// their nested [`values`] are stored in the [`flat config map`] under their own paths.
+///
+/// [`value`]: enum.Value.html
+/// [`values`]: enum.Value.html
+/// [`flat config map`]: type.FlatConfigMap.html
+/// [`Tables`]: struct.DynTable.html
+/// [`table`]: struct.DynTable.html
+/// [`arrays`]: struct.FlatArray.html
+/// [`array`]: struct.FlatArray.html
+pub type FlatConfigValue = Value<String, FlatArray, ()>;
+
+/// A flat map of all [`values`] of a [`config`], keyed by their full [`path`] strings
+/// (e.g. `"window.size[1]"`), as returned by [`DynConfig::to_flat_map`].
+///
+/// [`values`]: type.FlatConfigValue.html
+/// [`config`]: struct.DynConfig.html
+/// [`path`]: struct.ConfigPath.html
+/// [`DynConfig::to_flat_map`]: struct.DynConfig.html#method.to_flat_map
+pub type FlatConfigMap = HashMap<String, FlatConfigValue>;
+
+/// An error returned when creating a [`config`] from a [`flat config map`].
+///
+/// [`config`]: struct.DynConfig.html
+/// [`flat config map`]: type.FlatConfigMap.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum DynFlatMapError {
+    /// The key of a [`flat config map`] value is not a valid non-empty [`path`] string.
+    /// Contains the key and the path parsing error, if any (`None` if the path is empty).
+    ///
+    /// [`flat config map`]: type.FlatConfigMap.html
+    /// [`path`]: struct.ConfigPath.html
+    InvalidPath(String, Option<ParseConfigPathError>),
+    /// Multiple keys of the [`flat config map`] values correspond to the same [`path`] (e.g. `"[1]"` and `"[01]"`).
+    /// Contains the path.
+    ///
+    /// [`flat config map`]: type.FlatConfigMap.html
+    /// [`path`]: struct.ConfigPath.html
+    DuplicatePath(ConfigPath),
+    /// A value with a [`table key`] is nested in a value which is not a [`table`].
+    /// Contains the path to the value and its actual value [`type`].
+    ///
+    /// [`table key`]: enum.ConfigKey.html#variant.Table
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`type`]: enum.ValueType.html
+    ValueNotATable {
+        path: ConfigPath,
+        value_type: ValueType,
+    },
+    /// A value with an [`array index`] is nested in a value which is not an [`array`].
+    /// Contains the path to the value and its actual value [`type`].
+    ///
+    /// [`array index`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`type`]: enum.ValueType.html
+    ValueNotAnArray {
+        path: ConfigPath,
+        value_type: ValueType,
+    },
+    /// An [`array`] element index is out of bounds of the [`array`] length.
+    /// Contains the path to the element and the [`array`] length.
+    ///
+    /// [`array`]: struct.FlatArray.html
+    IndexOutOfBounds { path: ConfigPath, len: u32 },
+    /// An [`array`] element is missing.
+    /// Contains the path to the element.
+    ///
+    /// [`array`]: struct.FlatArray.html
+    MissingArrayElement(ConfigPath),
+    /// An [`array`] element is of incorrect and incompatible [`type`].
+    /// Contains the path to the element and its value [`type`].
+    ///
+    /// [`array`]: struct.FlatArray.html
+    /// [`type`]: enum.ValueType.html
+    MixedArray {
+        path: ConfigPath,
+        value_type: ValueType,
+    },
+}
+
+impl Error for DynFlatMapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DynFlatMapError::InvalidPath(_, Some(err)) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DynFlatMapError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DynFlatMapError::*;
+
+        match self {
+            InvalidPath(key, Some(err)) => write!(f, "invalid path \"{}\": {}", key, err),
+            InvalidPath(key, None) => write!(f, "invalid path \"{}\": empty path", key),
+            DuplicatePath(path) => write!(f, "duplicate path \"{}\"", path),
+            ValueNotATable { path, value_type } => {
+                write!(
+                    f,
+                    "value at \"{}\" is not a table (found {})",
+                    path, value_type
+                )
+            }
+            ValueNotAnArray { path, value_type } => write!(
+                f,
+                "value at \"{}\" is not an array (found {})",
+                path, value_type
+            ),
+            IndexOutOfBounds { path, len } => write!(
+                f,
+                "array element \"{}\" is out of bounds (array length is {})",
+                path, len
+            ),
+            MissingArrayElement(path) => write!(f, "missing array element \"{}\"", path),
+            MixedArray { path, value_type } => write!(
+                f,
+                "array element \"{}\" is of incorrect type (found {})",
+                path, value_type
+            ),
+        }
+    }
+}
+
+impl DynConfig {
+    /// Returns a deep copy of all [`values`] of this [`config`] as a [`flat config map`],
+    /// keyed by their full [`path`] strings (e.g. `"window.size[1]"`).
+    ///
+    /// Nested [`tables`] / [`arrays`] are included as [`table`] / [`array`] marker values
+    /// (so that the empty [`tables`] / [`arrays`] are preserved),
+    /// followed by all their nested [`values`] under their own paths.
+    ///
+    /// This is the inverse of [`from_flat_map`].
+    ///
+    /// [`values`]: type.FlatConfigValue.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`flat config map`]: type.FlatConfigMap.html
+    /// [`path`]: struct.ConfigPath.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`array`]: struct.FlatArray.html
+    /// [`from_flat_map`]: #method.from_flat_map
+    pub fn to_flat_map(&self) -> FlatConfigMap {
+        let mut map = FlatConfigMap::new();
+        flatten_table(self.root(), &mut ConfigPath::new(), &mut map);
+        map
+    }
+
+    /// Creates a new [`config`] from the [`flat config map`], e.g. returned by [`to_flat_map`].
+    ///
+    /// Missing intermediate [`tables`] are created on demand (e.g. for the `"window.title"` key without the `"window"` key).
+    /// [`Arrays`] must be explicitly present in the map, along with all their elements.
+    ///
+    /// Returns an [`error`] if any of the map keys is not a valid non-empty [`path`] string,
+    /// or if the map values do not form a valid [`config`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`flat config map`]: type.FlatConfigMap.html
+    /// [`to_flat_map`]: #method.to_flat_map
+    /// [`tables`]: struct.DynTable.html
+    /// [`Arrays`]: struct.FlatArray.html
+    /// [`error`]: enum.DynFlatMapError.html
+    /// [`path`]: struct.ConfigPath.html
+    pub fn from_flat_map(map: &FlatConfigMap) -> Result<Self, DynFlatMapError> {
+        let mut entries = map
+            .iter()
+            .map(|(key, value)| match key.parse::<ConfigPath>() {
+                Ok(path) if !path.is_empty() => Ok((path, value)),
+                Ok(_) => Err(DynFlatMapError::InvalidPath(key.clone(), None)),
+                Err(err) => Err(DynFlatMapError::InvalidPath(key.clone(), Some(err))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Sorted paths place all nested values right after their parent table / array.
+        entries.sort_by(|(l, _), (r, _)| cmp_paths(l, r));
+
+        if let Some(entries) = entries
+            .windows(2)
+            .find(|entries| entries[0].0 == entries[1].0)
+        {
+            return Err(DynFlatMapError::DuplicatePath(entries[0].0.clone()));
+        }
+
+        let mut reader = FlatMapReader { entries, index: 0 };
+
+        let mut config = DynConfig::new();
+        *config.root_mut() = reader.table(&mut ConfigPath::new())?;
+
+        Ok(config)
+    }
+}
+
+fn flatten_table(table: &DynTable, path: &mut ConfigPath, map: &mut FlatConfigMap) {
+    for (key, value) in table.iter() {
+        path.push(key);
+        flatten_value(value, path, map);
+        path.pop();
+    }
+}
+
+fn flatten_array(array: &DynArray, path: &mut ConfigPath, map: &mut FlatConfigMap) {
+    for (index, value) in array.iter().enumerate() {
+        path.push(index as u32);
+        flatten_value(value, path, map);
+        path.pop();
+    }
+}
+
+fn flatten_value(value: DynConfigValueRef<'_>, path: &mut ConfigPath, map: &mut FlatConfigMap) {
+    let flat_value = match value {
+        Value::Bool(value) => Value::Bool(value),
+        Value::I64(value) => Value::I64(value),
+        Value::F64(value) => Value::F64(value),
+        Value::String(value) => Value::String(value.to_owned()),
+        Value::Array(array) => Value::Array(FlatArray { len: array.len() }),
+        Value::Table(_) => Value::Table(()),
+        #[cfg(feature = "datetime")]
+        Value::DateTime(value) => Value::DateTime(value),
+    };
+
+    map.insert(path.to_string(), flat_value);
+
+    match value {
+        Value::Table(table) => flatten_table(table, path, map),
+        Value::Array(array) => flatten_array(array, path, map),
+        _ => {}
+    }
+}
+
+/// Orders the paths by their keys - table keys by string, array indices by value,
+/// so that the nested paths directly follow their parent path.
+fn cmp_paths(l: &ConfigPath, r: &ConfigPath) -> Ordering {
+    for (l, r) in l.0.iter().zip(r.0.iter()) {
+        let ordering = match (l, r) {
+            (OwnedConfigKey::Table(l), OwnedConfigKey::Table(r)) => l.as_str().cmp(r.as_str()),
+            (OwnedConfigKey::Array(l), OwnedConfigKey::Array(r)) => l.cmp(r),
+            (OwnedConfigKey::Table(_), OwnedConfigKey::Array(_)) => Ordering::Less,
+            (OwnedConfigKey::Array(_), OwnedConfigKey::Table(_)) => Ordering::Greater,
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    l.len().cmp(&r.len())
+}
+
+/// Reads the [`flat config map`](type.FlatConfigMap.html) entries, sorted by path, into the tables / arrays.
+struct FlatMapReader<'m> {
+    entries: Vec<(ConfigPath, &'m FlatConfigValue)>,
+    /// Index of the next entry to read.
+    index: usize,
+}
+
+impl<'m> FlatMapReader<'m> {
+    /// Returns the key of the next entry relative to `path`, if the entry is nested in the table / array at `path`.
+    fn next_nested_key(&self, path: &ConfigPath) -> Option<&OwnedConfigKey> {
+        let (entry_path, _) = self.entries.get(self.index)?;
+
+        if entry_path.starts_with(path) {
+            entry_path.0.get(path.0.len())
+        } else {
+            None
+        }
+    }
+
+    /// Reads all entries nested in the table at `path`.
+    fn table(&mut self, path: &mut ConfigPath) -> Result<DynTable, DynFlatMapError> {
+        let mut table = DynTable::new();
+
+        while let Some(key) = self.next_nested_key(path) {
+            let key = match key {
+                OwnedConfigKey::Table(key) => key.clone(),
+                OwnedConfigKey::Array(_) => {
+                    return Err(DynFlatMapError::ValueNotAnArray {
+                        path: path.clone(),
+                        value_type: ValueType::Table,
+                    })
+                }
+            };
+
+            path.push(key.clone());
+            let value = self.value(path)?;
+            path.pop();
+
+            table.set(key, value);
+        }
+
+        Ok(table)
+    }
+
+    /// Reads all `len` elements of the array at `path`.
+    fn array(&mut self, path: &mut ConfigPath, len: u32) -> Result<DynArray, DynFlatMapError> {
+        let mut array = DynArray::new();
+
+        while let Some(key) = self.next_nested_key(path) {
+            let index = match key {
+                OwnedConfigKey::Array(index) => *index,
+                OwnedConfigKey::Table(_) => {
+                    return Err(DynFlatMapError::ValueNotATable {
+                        path: path.clone(),
+                        value_type: ValueType::Array,
+                    })
+                }
+            };
+
+            if index >= len {
+                path.push(index);
+                return Err(DynFlatMapError::IndexOutOfBounds {
+                    path: path.clone(),
+                    len,
+                });
+            }
+
+            path.push(array.len());
+
+            if index != array.len() {
+                return Err(DynFlatMapError::MissingArrayElement(path.clone()));
+            }
+
+            let value = self.value(path)?;
+            let value_type = value.get_type();
+
+            array.push(value).map_err(|_| DynFlatMapError::MixedArray {
+                path: path.clone(),
+                value_type,
+            })?;
+
+            path.pop();
+        }
+
+        if array.len() < len {
+            path.push(array.len());
+            return Err(DynFlatMapError::MissingArrayElement(path.clone()));
+        }
+
+        Ok(array)
+    }
+
+    /// Reads the value at `path` (the path of the next entry or of its missing parent table)
+    /// with all its nested values.
+    fn value(&mut self, path: &mut ConfigPath) -> Result<DynConfigValue, DynFlatMapError> {
+        let (entry_path, value) = &self.entries[self.index];
+
+        // Missing intermediate table.
+        if entry_path.len() > path.len() {
+            return self.table(path).map(Value::Table);
+        }
+
+        let value = *value;
+        self.index += 1;
+
+        let value = match value {
+            Value::Bool(value) => Value::Bool(*value),
+            Value::I64(value) => Value::I64(*value),
+            Value::F64(value) => Value::F64(*value),
+            Value::String(value) => Value::String(value.clone()),
+            Value::Array(array) => return self.array(path, array.len).map(Value::Array),
+            Value::Table(_) => return self.table(path).map(Value::Table),
+            #[cfg(feature = "datetime")]
+            Value::DateTime(value) => Value::DateTime(*value),
+        };
+
+        // Values may not be nested in non-table / array values.
+        match self.next_nested_key(path) {
+            Some(OwnedConfigKey::Table(_)) => Err(DynFlatMapError::ValueNotATable {
+                path: path.clone(),
+                value_type: value.get_type(),
+            }),
+            Some(OwnedConfigKey::Array(_)) => Err(DynFlatMapError::ValueNotAnArray {
+                path: path.clone(),
+                value_type: value.get_type(),
+            }),
+            None => Ok(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn to_flat_map() {
+        let mut config = DynConfig::new();
+
+        let path = |path: &str| path.parse::<ConfigPath>().unwrap();
+
+        config.root_mut().set(nestr!("bool"), true);
+        config.root_mut().set(nestr!("string"), "foo");
+        config
+            .root_mut()
+            .set_path(&path("window.title"), "bar")
+            .unwrap();
+        config
+            .root_mut()
+            .set(nestr!("empty_table"), DynTable::new());
+        config
+            .root_mut()
+            .set(nestr!("empty_array"), DynArray::new());
+
+        let mut size = DynArray::new();
+        size.push(640.into()).unwrap();
+        size.push(480.into()).unwrap();
+        config
+            .root_mut()
+            .set_path(&path("window.size"), size)
+            .unwrap();
+
+        let mut views = DynArray::new();
+        let mut view = DynTable::new();
+        view.set(nestr!("fov"), 90.0);
+        views.push(view.into()).unwrap();
+        config.root_mut().set(nestr!("views"), views);
+
+        let map = config.to_flat_map();
+
+        let mut keys: Vec<_> = map.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+
+        assert_eq!(
+            keys,
+            vec![
+                "bool",
+                "empty_array",
+                "empty_table",
+                "string",
+                "views",
+                "views[0]",
+                "views[0].fov",
+                "window",
+                "window.size",
+                "window.size[0]",
+                "window.size[1]",
+                "window.title",
+            ]
+        );
+
+        assert!(map["bool"].bool().unwrap());
+        assert_eq!(map["string"].as_str().unwrap(), "foo");
+        assert_eq!(map["window.size[1]"].i64().unwrap(), 480);
+        assert_eq!(map["views[0].fov"].f64().unwrap(), 90.0);
+        assert_eq!(map["window.size"].clone().array().unwrap().len, 2);
+        assert_eq!(map["empty_array"].clone().array().unwrap().len, 0);
+        assert!(map["window"].clone().table().is_some());
+
+        // Round trip.
+        let round_trip = DynConfig::from_flat_map(&map).unwrap();
+
+        assert!(config.diff(&round_trip).is_empty());
+    }
+
+    #[test]
+    fn from_flat_map() {
+        let path = |path: &str| path.parse::<ConfigPath>().unwrap();
+
+        let from_flat_map = |entries: Vec<(&str, FlatConfigValue)>| {
+            DynConfig::from_flat_map(
+                &entries
+                    .into_iter()
+                    .map(|(key, value)| (key.to_owned(), value))
+                    .collect(),
+            )
+        };
+
+        // Missing intermediate tables are created on demand.
+        let config = from_flat_map(vec![
+            ("window.title", Value::String("foo".to_owned())),
+            ("window.size", Value::Array(FlatArray { len: 1 })),
+            ("window.size[0]", Value::I64(640)),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            config
+                .root()
+                .get_string_path(&path("window.title"))
+                .unwrap(),
+            "foo"
+        );
+        assert_eq!(
+            config.root().get_i64_path(&path("window.size[0]")).unwrap(),
+            640
+        );
+
+        assert_eq!(
+            from_flat_map(vec![("foo..bar", Value::Bool(true))])
+                .err()
+                .unwrap(),
+            DynFlatMapError::InvalidPath(
+                "foo..bar".to_owned(),
+                Some(ParseConfigPathError::EmptyKey(4))
+            )
+        );
+        assert_eq!(
+            from_flat_map(vec![("", Value::Bool(true))]).err().unwrap(),
+            DynFlatMapError::InvalidPath("".to_owned(), None)
+        );
+
+        // The path parsing error, if any, is exposed as the error source.
+        assert_eq!(
+            std::error::Error::source(
+                &from_flat_map(vec![("foo..bar", Value::Bool(true))])
+                    .err()
+                    .unwrap()
+            )
+            .unwrap()
+            .downcast_ref::<ParseConfigPathError>()
+            .unwrap(),
+            &ParseConfigPathError::EmptyKey(4)
+        );
+        assert!(std::error::Error::source(
+            &from_flat_map(vec![("", Value::Bool(true))]).err().unwrap()
+        )
+        .is_none());
+        assert_eq!(
+            from_flat_map(vec![
+                ("array", Value::Array(FlatArray { len: 2 })),
+                ("array[1]", Value::Bool(true)),
+                ("array[01]", Value::Bool(true)),
+            ])
+            .err()
+            .unwrap(),
+            DynFlatMapError::DuplicatePath(path("array[1]"))
+        );
+        assert_eq!(
+            from_flat_map(vec![("foo", Value::I64(7)), ("foo.bar", Value::Bool(true)),])
+                .err()
+                .unwrap(),
+            DynFlatMapError::ValueNotATable {
+                path: path("foo"),
+                value_type: ValueType::I64
+            }
+        );
+        assert_eq!(
+            from_flat_map(vec![("foo[0]", Value::Bool(true))])
+                .err()
+                .unwrap(),
+            DynFlatMapError::ValueNotAnArray {
+                path: path("foo"),
+                value_type: ValueType::Table
+            }
+        );
+        assert_eq!(
+            from_flat_map(vec![
+                ("array", Value::Array(FlatArray { len: 1 })),
+                ("array[1]", Value::Bool(true)),
+            ])
+            .err()
+            .unwrap(),
+            DynFlatMapError::IndexOutOfBounds {
+                path: path("array[1]"),
+                len: 1
+            }
+        );
+        assert_eq!(
+            from_flat_map(vec![
+                ("array", Value::Array(FlatArray { len: 2 })),
+                ("array[1]", Value::Bool(true)),
+            ])
+            .err()
+            .unwrap(),
+            DynFlatMapError::MissingArrayElement(path("array[0]"))
+        );
+        assert_eq!(
+            from_flat_map(vec![
+                ("array", Value::Array(FlatArray { len: 2 })),
+                ("array[0]", Value::Bool(true)),
+            ])
+            .err()
+            .unwrap(),
+            DynFlatMapError::MissingArrayElement(path("array[1]"))
+        );
+        assert_eq!(
+            from_flat_map(vec![
+                ("array", Value::Array(FlatArray { len: 2 })),
+                ("array[0]", Value::Bool(true)),
+                ("array[1]", Value::String("foo".to_owned())),
+            ])
+            .err()
+            .unwrap(),
+            DynFlatMapError::MixedArray {
+                path: path("array[1]"),
+                value_type: ValueType::String
+            }
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! (Non-empty) string keys of the dynamic config tables, stored together with their FNV1-a hashes,
+//! so that the table lookups with precomputed key hashes (e.g. via the `key!` macro) avoid runtime string hashing.
+
+use {
+    crate::{util::*, *},
+    std::{
+        borrow::Borrow,
+        collections::HashMap,
+        hash::{BuildHasherDefault, Hash, Hasher},
+    },
+};
+
+/// Hashmap of (non-empty) string keys and their FNV1-a hashes to the [`values`](type.DynConfigValue.html) of the table.
+pub(super) type DynTableMap = HashMap<DynTableKey, DynConfigValue, BuildHasherDefault<KeyHasher>>;
+
+/// Owned (non-empty) string table key and its FNV1-a hash.
+#[derive(Clone)]
+pub(super) struct DynTableKey {
+    key: NonEmptyString,
+    hash: StringHash,
+}
+
+impl DynTableKey {
+    pub(super) fn new(key: &NonEmptyStr, hash: StringHash) -> Self {
+        debug_assert_eq!(string_hash_fnv1a(key), hash, "string and hash mismatch");
+
+        Self {
+            key: key.into(),
+            hash,
+        }
+    }
+
+    pub(super) fn key(&self) -> &NonEmptyStr {
+        self.key.as_ne_str()
+    }
+
+    pub(super) fn into_key(self) -> NonEmptyString {
+        self.key
+    }
+}
+
+/// Borrowed (non-empty) string table key and its FNV1-a hash, used for table lookups.
+#[derive(Clone, Copy)]
+pub(super) struct DynTableKeyRef<'k> {
+    key: &'k NonEmptyStr,
+    hash: StringHash,
+}
+
+impl<'k> DynTableKeyRef<'k> {
+    pub(super) fn new(key: &'k NonEmptyStr, hash: StringHash) -> Self {
+        debug_assert_eq!(string_hash_fnv1a(key), hash, "string and hash mismatch");
+
+        Self { key, hash }
+    }
+
+    pub(super) fn as_lookup(&self) -> &(dyn LookupKey + 'k) {
+        self
+    }
+
+    /// Copies the key to the `buffer`, reusing its allocation.
+    #[cfg(feature = "ini")]
+    pub(super) fn into_owned_in(self, mut buffer: String) -> DynTableKey {
+        buffer.clear();
+        buffer.push_str(self.key);
+
+        DynTableKey {
+            // Must succeed - the key is not empty.
+            key: unwrap_unchecked(NonEmptyString::new(buffer), "empty key"),
+            hash: self.hash,
+        }
+    }
+}
+
+impl<'k> From<&'k NonEmptyStr> for DynTableKeyRef<'k> {
+    fn from(key: &'k NonEmptyStr) -> Self {
+        Self::new(key, string_hash_fnv1a(key))
+    }
+}
+
+impl<'k> From<DynTableKeyRef<'k>> for DynTableKey {
+    fn from(key: DynTableKeyRef<'k>) -> Self {
+        Self::new(key.key, key.hash)
+    }
+}
+
+/// Common interface of owned and borrowed table keys,
+/// which allows to look up the owned keys in the table hashmap by borrowed keys.
+pub(super) trait LookupKey {
+    fn key(&self) -> &str;
+    fn hash(&self) -> StringHash;
+}
+
+impl LookupKey for DynTableKey {
+    fn key(&self) -> &str {
+        self.key.as_str()
+    }
+
+    fn hash(&self) -> StringHash {
+        self.hash
+    }
+}
+
+impl<'k> LookupKey for DynTableKeyRef<'k> {
+    fn key(&self) -> &str {
+        self.key.as_str()
+    }
+
+    fn hash(&self) -> StringHash {
+        self.hash
+    }
+}
+
+impl<'k> Borrow<dyn LookupKey + 'k> for DynTableKey {
+    fn borrow(&self) -> &(dyn LookupKey + 'k) {
+        self
+    }
+}
+
+impl<'k> Hash for dyn LookupKey + 'k {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(LookupKey::hash(self));
+    }
+}
+
+impl<'k> PartialEq for dyn LookupKey + 'k {
+    fn eq(&self, other: &Self) -> bool {
+        LookupKey::hash(self) == LookupKey::hash(other) && self.key() == other.key()
+    }
+}
+
+impl<'k> Eq for dyn LookupKey + 'k {}
+
+// Must be consistent with the `dyn LookupKey` implementations above.
+impl Hash for DynTableKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.hash);
+    }
+}
+
+impl PartialEq for DynTableKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.key == other.key
+    }
+}
+
+impl Eq for DynTableKey {}
+
+/// Hasher which uses the precomputed FNV1-a key hash,
+/// spread over the full 64 bits by the Fibonacci hashing multiplier.
+#[derive(Default)]
+pub(super) struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, _: &[u8]) {
+        debug_unreachable!("table keys are only hashed via `write_u32()`")
+    }
+
+    fn write_u32(&mut self, hash: u32) {
+        self.0 = (hash as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    }
+}
@@ -0,0 +1,392 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter, Write},
+        ops::Range,
+    },
+};
+
+/// An error returned by [`DynConfig::save_ini_updates`].
+///
+/// [`DynConfig::save_ini_updates`]: struct.DynConfig.html#method.save_ini_updates
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum IniUpdateError {
+    /// Failed to parse the original `.ini` source.
+    /// Contains the parse error.
+    Parse(IniError),
+    /// Failed to serialize an updated value to `.ini`.
+    /// Contains the serialization error.
+    ToIniString(ToIniStringError),
+    /// The change of the value at the path cannot be applied to the original `.ini` source in place -
+    /// e.g. an [`array`] was modified, a section was removed, a value type changed to / from a [`table`],
+    /// or a value was added to a section without values.
+    /// Contains the path to the changed value.
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`table`]: struct.DynTable.html
+    UnsupportedChange(ConfigPath),
+}
+
+impl Error for IniUpdateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use IniUpdateError::*;
+
+        match self {
+            Parse(err) => Some(err),
+            ToIniString(err) => Some(err),
+            UnsupportedChange(_) => None,
+        }
+    }
+}
+
+impl Display for IniUpdateError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use IniUpdateError::*;
+
+        match self {
+            Parse(err) => write!(f, "failed to parse the original `.ini` source: {}", err),
+            ToIniString(err) => write!(f, "failed to serialize an updated value: {}", err),
+            UnsupportedChange(path) => write!(
+                f,
+                "change of the value at \"{}\" cannot be applied to the original `.ini` source in place",
+                path
+            ),
+        }
+    }
+}
+
+impl From<ToIniStringError> for IniUpdateError {
+    fn from(err: ToIniStringError) -> Self {
+        Self::ToIniString(err)
+    }
+}
+
+impl From<std::fmt::Error> for IniUpdateError {
+    fn from(err: std::fmt::Error) -> Self {
+        Self::ToIniString(err.into())
+    }
+}
+
+impl DynConfig {
+    /// Applies the values of this [`config`] which differ from the values parsed from the `original_source` `.ini` string
+    /// back onto the `original_source`, using default parsing and serialization [`options`],
+    /// and returns the updated `.ini` string.
+    ///
+    /// See [`save_ini_updates_opts`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    /// [`save_ini_updates_opts`]: #method.save_ini_updates_opts
+    pub fn save_ini_updates(&self, original_source: &str) -> Result<String, IniUpdateError> {
+        self.save_ini_updates_opts(IniParser::new(original_source), Default::default())
+    }
+
+    /// Applies the values of this [`config`] which differ from the values parsed by the [`.ini parser`]
+    /// back onto its source `.ini` string and returns the updated `.ini` string.
+    ///
+    /// Only the changed values are touched - all other lines, comments and their order are preserved:
+    /// - modified values are replaced in place, serialized using the [`options`],
+    /// - removed values are removed with their lines,
+    /// - values added to the root section / existing sections are inserted after the last value in the section,
+    /// - sections added to the root table are appended to the end of the string.
+    ///
+    /// Returns an [`error`] if the source failed to parse, or if some change cannot be applied in place
+    /// (e.g. an [`array`] was modified or a section was removed).
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    /// [`error`]: enum.IniUpdateError.html
+    /// [`array`]: struct.DynArray.html
+    pub fn save_ini_updates_opts(
+        &self,
+        parser: IniParser<'_>,
+        options: ToIniStringOptions,
+    ) -> Result<String, IniUpdateError> {
+        use IniUpdateError::*;
+
+        let source = parser.source();
+        let (original, spans) = DynConfig::from_ini_with_spans(parser).map_err(Parse)?;
+
+        // Byte ranges in the source and their replacements.
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+        // Values added to the root table / sections, keyed by the path to their parent table.
+        let mut added: Vec<(ConfigPath, DynTable)> = Vec::new();
+        // Sections added to the root table.
+        let mut added_sections = DynConfig::new();
+
+        for change in original.diff(self).into_changes().into_iter() {
+            let path = change.path();
+
+            // No spans are recorded for arrays and their elements.
+            if path
+                .0
+                .iter()
+                .any(|key| matches!(key, OwnedConfigKey::Array(_)))
+            {
+                return Err(UnsupportedChange(path.clone()));
+            }
+
+            match change {
+                ConfigChange::Modified { path, old, new } => {
+                    if is_array_or_table(&old) || is_array_or_table(&new) {
+                        return Err(UnsupportedChange(path));
+                    }
+
+                    let spans = spans
+                        .get(&path)
+                        .ok_or_else(|| UnsupportedChange(path.clone()))?;
+
+                    let mut value = String::new();
+                    new.fmt_ini(&mut value, 0, false, &mut IniPath::new(), options)?;
+
+                    edits.push((spans.value.range(), value));
+                }
+                ConfigChange::Removed { path, value } => {
+                    if is_array_or_table(&value) {
+                        return Err(UnsupportedChange(path));
+                    }
+
+                    let spans = spans
+                        .get(&path)
+                        .ok_or_else(|| UnsupportedChange(path.clone()))?;
+
+                    edits.push((
+                        line_start(source, spans.key.start)..line_end(source, spans.value.end),
+                        String::new(),
+                    ));
+                }
+                ConfigChange::Added { mut path, value } => {
+                    let key = match path.0.pop() {
+                        Some(OwnedConfigKey::Table(key)) => key,
+                        _ => debug_unreachable!("added table values have non-empty string keys"),
+                    };
+
+                    match value {
+                        Value::Table(_) if path.0.is_empty() => {
+                            added_sections.root_mut().set(&key, value);
+                        }
+                        Value::Table(_) => {
+                            path.0.push(key.into());
+                            return Err(UnsupportedChange(path));
+                        }
+                        value => {
+                            let index = match added.iter().position(|(parent, _)| *parent == path) {
+                                Some(index) => index,
+                                None => {
+                                    added.push((path, DynTable::new()));
+                                    added.len() - 1
+                                }
+                            };
+
+                            added[index].1.set(&key, value);
+                        }
+                    }
+                }
+            }
+        }
+
+        for (parent, table) in added.into_iter() {
+            // Insert after the last value in the section, or at the start of the source for the root section.
+            let last_value = spans
+                .iter()
+                .filter(|(path, _)| path.0.len() == parent.0.len() + 1 && path.starts_with(&parent))
+                .map(|(_, spans)| spans.value.end)
+                .max();
+
+            let offset = match last_value {
+                Some(end) => line_end(source, end),
+                None if parent.0.is_empty() => 0,
+                None => {
+                    let mut path = parent;
+                    let (key, _) = table.iter().next().unwrap();
+                    path.0.push(key.into());
+                    return Err(UnsupportedChange(path));
+                }
+            };
+
+            let mut ini_path = IniPath::new();
+
+            for key in parent.0.iter() {
+                match key {
+                    OwnedConfigKey::Table(key) => ini_path.push(NonEmptyIniStr::Owned(key)),
+                    OwnedConfigKey::Array(_) => {
+                        debug_unreachable!("array element paths are not supported")
+                    }
+                }
+            }
+
+            let mut values = String::new();
+
+            if offset > 0 && !source[..offset].ends_with('\n') {
+                options.formatter.new_lines(&mut values).write_char('\n')?;
+            }
+
+            {
+                let mut w = options.formatter.new_lines(&mut values);
+                table.fmt_ini(&mut w, parent.0.len() as _, false, &mut ini_path, options)?;
+                w.write_char('\n')?;
+            }
+
+            edits.push((offset..offset, values));
+        }
+
+        if !added_sections.root().is_empty() {
+            let mut sections = String::new();
+
+            {
+                let mut w = options.formatter.new_lines(&mut sections);
+
+                if !source.is_empty() {
+                    if !source.ends_with('\n') {
+                        w.write_char('\n')?;
+                    }
+                    w.write_char('\n')?;
+                }
+            }
+
+            added_sections.fmt_ini_opts(options, &mut sections)?;
+            options
+                .formatter
+                .new_lines(&mut sections)
+                .write_char('\n')?;
+
+            edits.push((source.len()..source.len(), sections));
+        }
+
+        // Insertions sort before the removals / replacements which start at the same offset.
+        edits.sort_by_key(|(range, _)| (range.start, range.end));
+
+        let mut result = String::with_capacity(source.len());
+        let mut offset = 0;
+
+        for (range, replacement) in edits.into_iter() {
+            debug_assert!(range.start >= offset);
+
+            result.push_str(&source[offset..range.start]);
+            result.push_str(&replacement);
+
+            offset = range.end;
+        }
+
+        result.push_str(&source[offset..]);
+
+        Ok(result)
+    }
+}
+
+fn is_array_or_table(value: &DynConfigValue) -> bool {
+    matches!(value, Value::Array(_) | Value::Table(_))
+}
+
+/// Returns the byte offset in the `source` of the start of the line which contains the byte at `offset`.
+fn line_start(source: &str, offset: usize) -> usize {
+    source[..offset].rfind('\n').map_or(0, |index| index + 1)
+}
+
+/// Returns the byte offset in the `source` past the end of the line (including the new line sequence, if any)
+/// which contains the byte at `offset`.
+fn line_end(source: &str, offset: usize) -> usize {
+    source[offset..]
+        .find('\n')
+        .map_or(source.len(), |index| offset + index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn save_ini_updates() {
+        let source = "; Settings.\n\
+            volume = 50\n\
+            name = \"Player\"\n\
+            \n\
+            [render]\n\
+            ; Vertical sync.\n\
+            vsync = true\n\
+            fov = 90\n\
+            \n\
+            [audio]\n\
+            muted = false";
+
+        let mut config = DynConfig::from_ini(IniParser::new(source)).unwrap();
+
+        // No changes.
+        assert_eq!(config.save_ini_updates(source).unwrap(), source);
+
+        config.root_mut().set(nestr!("volume"), 75);
+        config.root_mut().remove("name").unwrap();
+        config.root_mut().set(nestr!("difficulty"), "hard");
+
+        let render = config.root_mut().get_table_mut("render").unwrap();
+        render.set(nestr!("fov"), 110);
+        render.set(nestr!("gamma"), 2.2);
+
+        let mut network = DynTable::new();
+        network.set(nestr!("port"), 7777);
+        config
+            .root_mut()
+            .set(nestr!("network"), Value::Table(network));
+
+        config
+            .root_mut()
+            .get_table_mut("audio")
+            .unwrap()
+            .set(nestr!("muted"), true);
+
+        let updated = config.save_ini_updates(source).unwrap();
+
+        assert_eq!(
+            updated,
+            "; Settings.\n\
+            volume = 75\n\
+            difficulty = \"hard\"\n\
+            \n\
+            [render]\n\
+            ; Vertical sync.\n\
+            vsync = true\n\
+            fov = 110\n\
+            gamma = 2.2\n\
+            \n\
+            [audio]\n\
+            muted = true\n\
+            \n\
+            [network]\n\
+            port = 7777\n"
+        );
+
+        // The updated source parses back to the updated config.
+        assert!(DynConfig::from_ini(IniParser::new(&updated))
+            .unwrap()
+            .diff(&config)
+            .is_empty());
+
+        // Removed sections cannot be applied in place.
+        let mut config = DynConfig::from_ini(IniParser::new(source)).unwrap();
+        config.root_mut().remove("audio").unwrap();
+
+        assert_eq!(
+            config.save_ini_updates(source).err().unwrap(),
+            IniUpdateError::UnsupportedChange(vec![nestr!("audio").into()].into())
+        );
+
+        // Parse errors are exposed as the error source.
+        let error = config.save_ini_updates("[audio").err().unwrap();
+        assert_eq!(
+            std::error::Error::source(&error)
+                .unwrap()
+                .downcast_ref::<IniError>()
+                .unwrap()
+                .error,
+            IniErrorKind::UnexpectedEndOfFileInSectionName
+        );
+        assert!(
+            std::error::Error::source(&config.save_ini_updates(source).err().unwrap()).is_none()
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use {super::config::IniMemoryLimit, crate::*, std::borrow::Cow};
+
+/// Maximum depth of the nested interpolations, same as in Python `configparser`.
+const MAX_INTERPOLATION_DEPTH: u32 = 10;
+
+/// Name of the section which contains the default values, same as in Python `configparser`.
+const DEFAULT_SECTION: &str = "DEFAULT";
+
+/// Interpolates all string values (except [`array`] elements) in the `root` table
+/// according to the [`interpolation`] syntax, tracking the estimated memory usage of the interpolated values in `memory_limit`,
+/// reporting the errors at the locations of the interpolated values in the `spans`.
+///
+/// Records the interpolated values and the values they reference as [`Interpolation`] nodes in the `dependencies` graph,
+/// detecting the cyclic interpolations.
+///
+/// [`array`]: struct.DynArray.html
+/// [`interpolation`]: enum.IniInterpolation.html
+/// [`Interpolation`]: enum.IniDependencyKind.html#variant.Interpolation
+pub(super) fn interpolate_ini(
+    root: &mut DynTable,
+    spans: &IniSpans,
+    interpolation: IniInterpolation,
+    case_insensitive_keys: bool,
+    memory_limit: &mut IniMemoryLimit,
+    dependencies: &mut IniDependencyGraph,
+) -> Result<(), IniError> {
+    let delimiter = match interpolation {
+        IniInterpolation::None => return Ok(()),
+        IniInterpolation::Basic => '%',
+        IniInterpolation::Extended => '$',
+    };
+
+    let mut paths = Vec::new();
+    gather_string_paths(root, &mut ConfigPath::new(), &mut paths);
+
+    let interpolator = Interpolator {
+        root,
+        interpolation,
+        delimiter,
+        case_insensitive_keys,
+    };
+
+    // Interpolate all values first - referenced values are always interpolated from their raw strings.
+    let mut interpolated = Vec::new();
+
+    for path in paths.into_iter() {
+        // Must succeed - the path is valid.
+        let raw = unwrap_unchecked(
+            interpolator.root.get_string_path(&path),
+            "failed to get a string value at a valid path",
+        );
+
+        if !raw.contains(delimiter) {
+            continue;
+        }
+
+        let section = &path.0[..path.0.len() - 1];
+        let mut value = String::new();
+
+        // Must succeed - no other nodes are being resolved.
+        let entered = dependencies.enter(IniDependencyKind::Interpolation, path.to_string());
+        debug_assert!(entered, "interpolated value entered twice");
+
+        let result = interpolator
+            .interpolate(&mut value, raw, section, 1, dependencies)
+            .and_then(|_| memory_limit.replace(raw.len(), value.len()));
+
+        if result.is_ok() {
+            dependencies.exit();
+        }
+
+        match result {
+            Ok(_) => interpolated.push((path, value)),
+            Err(error) => {
+                let span = spans.get(&path).map(|spans| spans.value);
+
+                return Err(IniError {
+                    line: span.map_or(0, |span| span.line),
+                    column: span.map_or(0, |span| span.column),
+                    offset: span.map_or(0, |span| span.start),
+                    path,
+                    error,
+                });
+            }
+        }
+    }
+
+    for (path, value) in interpolated.into_iter() {
+        // Must succeed - the interpolated value exists.
+        let result = root.set_path(&path, value);
+        debug_assert!(result.is_ok(), "failed to set an interpolated value");
+    }
+
+    Ok(())
+}
+
+/// Gathers the paths to all string values in the `table` at `path`, and in its nested tables, to `paths`.
+fn gather_string_paths(table: &DynTable, path: &mut ConfigPath, paths: &mut Vec<ConfigPath>) {
+    for (key, value) in table.iter() {
+        match value {
+            Value::String(_) => {
+                let mut path = path.clone();
+                path.push(key);
+                paths.push(path);
+            }
+            Value::Table(table) => {
+                path.push(key);
+                gather_string_paths(table, path, paths);
+                path.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Interpolator<'t> {
+    root: &'t DynTable,
+    interpolation: IniInterpolation,
+    delimiter: char,
+    case_insensitive_keys: bool,
+}
+
+impl<'t> Interpolator<'t> {
+    /// Interpolates the `raw` string value in the `section` and appends the result to `result`.
+    /// `depth` is the current interpolation depth, starting at `1`.
+    /// Records the referenced values in the `dependencies` graph.
+    fn interpolate(
+        &self,
+        result: &mut String,
+        raw: &str,
+        section: &[OwnedConfigKey],
+        depth: u32,
+        dependencies: &mut IniDependencyGraph,
+    ) -> Result<(), IniErrorKind> {
+        use IniErrorKind::*;
+
+        if depth > MAX_INTERPOLATION_DEPTH {
+            return Err(InterpolationDepthExceeded);
+        }
+
+        let mut rest = raw;
+
+        while let Some(index) = rest.find(self.delimiter) {
+            result.push_str(&rest[..index]);
+            rest = &rest[index + self.delimiter.len_utf8()..];
+
+            // Escaped delimiter.
+            if let Some(after) = rest.strip_prefix(self.delimiter) {
+                result.push(self.delimiter);
+                rest = after;
+                continue;
+            }
+
+            let (reference, after) = self.parse_reference(rest).ok_or(InvalidInterpolation)?;
+            rest = after;
+
+            let (reference_section, key) = match reference.rsplit_once(':') {
+                Some((reference_section, key))
+                    if self.interpolation == IniInterpolation::Extended =>
+                {
+                    if reference_section.contains(':') {
+                        return Err(InvalidInterpolation);
+                    }
+
+                    let mut path = Vec::new();
+
+                    for section in reference_section.split('/') {
+                        path.push(self.key(section)?);
+                    }
+
+                    (Cow::Owned(path), key)
+                }
+                _ => (Cow::Borrowed(section), reference),
+            };
+
+            let key = self.key(key)?;
+
+            let (path, value) = self.get(&reference_section, &key).ok_or_else(|| {
+                let mut path: ConfigPath = reference_section.to_vec().into();
+                path.0.push(key.clone());
+                UnresolvedInterpolation(path)
+            })?;
+
+            // Cyclic interpolations would exceed the maximum depth anyway.
+            if !dependencies.enter(IniDependencyKind::Interpolation, path.to_string()) {
+                return Err(InterpolationDepthExceeded);
+            }
+
+            if value.contains(self.delimiter) {
+                self.interpolate(result, &value, &reference_section, depth + 1, dependencies)?;
+            } else {
+                result.push_str(&value);
+            }
+
+            dependencies.exit();
+        }
+
+        result.push_str(rest);
+
+        Ok(())
+    }
+
+    /// Parses the reference (`(key)s` / `{[section:]key}`) at the start of the `string`
+    /// (which follows the delimiter).
+    /// Returns the contents of the reference and the rest of the `string`,
+    /// or `None` if the `string` does not start with a valid reference.
+    fn parse_reference<'s>(&self, string: &'s str) -> Option<(&'s str, &'s str)> {
+        let (open, close, suffix) = match self.interpolation {
+            IniInterpolation::Basic => ('(', ')', "s"),
+            _ => ('{', '}', ""),
+        };
+
+        let string = string.strip_prefix(open)?;
+        let end = string.find(close)?;
+
+        if end == 0 {
+            return None;
+        }
+
+        let rest = string[end + close.len_utf8()..].strip_prefix(suffix)?;
+
+        Some((&string[..end], rest))
+    }
+
+    /// Converts the section name / `key` of the reference to a config key,
+    /// in lowercase if the section names / keys are case-insensitive.
+    fn key(&self, key: &str) -> Result<OwnedConfigKey, IniErrorKind> {
+        let key = NonEmptyStr::new(key).ok_or(IniErrorKind::InvalidInterpolation)?;
+
+        Ok(if self.case_insensitive_keys {
+            let key: String = key.as_str().chars().flat_map(char::to_lowercase).collect();
+            // Must succeed - the key is not empty.
+            unwrap_unchecked(NonEmptyStr::new(&key), "empty interpolation key").into()
+        } else {
+            key.into()
+        })
+    }
+
+    /// Returns the path to and the raw string representation of the value at `key` in the `section`,
+    /// or in the default section if it does not exist.
+    fn get(
+        &self,
+        section: &[OwnedConfigKey],
+        key: &OwnedConfigKey,
+    ) -> Option<(ConfigPath, Cow<'t, str>)> {
+        let mut path: ConfigPath = section.to_vec().into();
+        path.0.push(key.clone());
+
+        if let Some(value) = self.get_value(&path) {
+            return Some((path, value));
+        }
+
+        let default_section = self.key(DEFAULT_SECTION).ok()?;
+        let path: ConfigPath = vec![default_section, key.clone()].into();
+        self.get_value(&path).map(|value| (path, value))
+    }
+
+    fn get_value(&self, path: &ConfigPath) -> Option<Cow<'t, str>> {
+        Some(match self.root.get_val_path(path).ok()? {
+            Value::String(value) => Cow::Borrowed(value),
+            Value::Bool(value) => Cow::Borrowed(if value { "true" } else { "false" }),
+            Value::I64(value) => Cow::Owned(value.to_string()),
+            Value::F64(value) => {
+                let mut string = String::new();
+                ConfigFormatter::default()
+                    .write_f64(&mut string, value)
+                    .ok()?;
+                Cow::Owned(string)
+            }
+            #[cfg(feature = "datetime")]
+            Value::DateTime(value) => Cow::Owned(value.to_string()),
+            Value::Array(_) | Value::Table(_) => return None,
+        })
+    }
+}
@@ -0,0 +1,277 @@
+use {super::diff::to_owned_value, crate::*, std::borrow::Borrow};
+
+/// A stack of named [`config`] layers (e.g. defaults, user settings, runtime overrides)
+/// which resolves value lookups through the stack, from the top (last pushed) layer to the bottom (first pushed) one.
+///
+/// Nested [`tables`] are merged across layers:
+/// a value at a path is supplied by the topmost layer which contains a value at that path.
+///
+/// [`config`]: struct.DynConfig.html
+/// [`tables`]: struct.DynTable.html
+#[derive(Default)]
+pub struct LayeredConfig {
+    layers: Vec<(String, DynConfig)>,
+}
+
+impl LayeredConfig {
+    /// Creates a new empty layered [`config`].
+    ///
+    /// [`config`]: struct.LayeredConfig.html
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Pushes the `config` on top of the stack as a layer with `name`,
+    /// overriding the values in all previously pushed layers.
+    ///
+    /// Returns the index of the layer (`0` for the bottom layer).
+    pub fn push<N: Into<String>>(&mut self, name: N, config: DynConfig) -> usize {
+        self.layers.push((name.into(), config));
+        self.layers.len() - 1
+    }
+
+    /// Removes the top layer from the stack and returns its name and [`config`], if any.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    pub fn pop(&mut self) -> Option<(String, DynConfig)> {
+        self.layers.pop()
+    }
+
+    /// Returns the number of layers in the stack.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns `true` if the stack contains no layers.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Returns the [`config`] of the layer at `index` (`0` for the bottom layer), if any.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    pub fn layer(&self, index: usize) -> Option<&DynConfig> {
+        self.layers.get(index).map(|(_, config)| config)
+    }
+
+    /// Returns the mutable [`config`] of the layer at `index` (`0` for the bottom layer), if any.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut DynConfig> {
+        self.layers.get_mut(index).map(|(_, config)| config)
+    }
+
+    /// Returns the name of the layer at `index` (`0` for the bottom layer), if any.
+    pub fn layer_name(&self, index: usize) -> Option<&str> {
+        self.layers.get(index).map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the index of the topmost layer with `name`, if any.
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers
+            .iter()
+            .rposition(|(layer_name, _)| layer_name == name)
+    }
+
+    /// Tries to get an immutable reference to a [`value`] at `path`, resolved through the layer stack,
+    /// and the index of the layer which supplied it.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - see [`DynTable::get_val_path`].
+    ///
+    /// Layers which do not contain a value at `path` are skipped.
+    /// Returns the [`error`] for the bottom layer if no layer contains a value at `path`,
+    /// or [`KeyDoesNotExist`] if the stack is empty.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`DynTable::get_val_path`]: struct.DynTable.html#method.get_val_path
+    /// [`error`]: enum.GetPathError.html
+    /// [`KeyDoesNotExist`]: enum.GetPathError.html#variant.KeyDoesNotExist
+    pub fn get_val_path_and_source<'k, K, P>(
+        &self,
+        path: P,
+    ) -> Result<(DynConfigValueRef<'_>, usize), GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        let path: Vec<_> = path.into_iter().collect();
+
+        let mut error = GetPathError::KeyDoesNotExist(ConfigPath::new());
+
+        for (index, (_, config)) in self.layers.iter().enumerate().rev() {
+            match config
+                .root()
+                .get_val_path(path.iter().map(Borrow::<ConfigKey<'k>>::borrow))
+            {
+                Ok(value) => return Ok((value, index)),
+                Err(err) => error = err,
+            }
+        }
+
+        Err(error)
+    }
+
+    /// Tries to get an immutable reference to a [`value`] at `path`, resolved through the layer stack.
+    ///
+    /// See [`get_val_path_and_source`].
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`get_val_path_and_source`]: #method.get_val_path_and_source
+    pub fn get_val_path<'k, K, P>(&self, path: P) -> Result<DynConfigValueRef<'_>, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_val_path_and_source(path).map(|(value, _)| value)
+    }
+
+    /// Tries to get an immutable reference to a [`value`] at `path`, resolved through the layer stack,
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// NOTE - the value is not looked up in the lower layers if it is of incorrect and incompatible [`type`].
+    ///
+    /// See [`get_val_path_and_source`].
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`type`]: enum.ValueType.html
+    /// [`get_val_path_and_source`]: #method.get_val_path_and_source
+    pub fn get_path<'t, 'k, K, P, V>(&'t self, path: P) -> Result<V, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+        V: TryFromValue<&'t str, &'t DynArray, &'t DynTable>,
+    {
+        V::try_from(self.get_val_path(path)?).map_err(GetPathError::IncorrectValueType)
+    }
+
+    /// Returns the index of the topmost layer which contains a value at `path`, if any,
+    /// i.e. the layer which supplies the value at `path`.
+    ///
+    /// Use [`layer_name`] to get the name of the layer.
+    ///
+    /// [`layer_name`]: #method.layer_name
+    pub fn source<'k, K, P>(&self, path: P) -> Option<usize>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_val_path_and_source(path)
+            .ok()
+            .map(|(_, index)| index)
+    }
+
+    /// Merges all layers into a single [`config`], with the values of the upper layers
+    /// overriding the values of the lower ones, and nested [`tables`] merged recursively.
+    ///
+    /// NOTE - [`arrays`] are not merged - an [`array`] in an upper layer replaces the [`array`] in the lower layers.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`array`]: struct.DynArray.html
+    pub fn flatten(&self) -> DynConfig {
+        let mut result = DynConfig::new();
+
+        for (_, config) in self.layers.iter() {
+            merge_table(result.root_mut(), config.root());
+        }
+
+        result
+    }
+}
+
+fn merge_table(dst: &mut DynTable, src: &DynTable) {
+    for (key, value) in src.iter() {
+        if let Value::Table(src) = value {
+            if let Ok(dst) = dst.get_table_mut(key) {
+                merge_table(dst, src);
+                continue;
+            }
+        }
+
+        dst.set(key, to_owned_value(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn layered_config() {
+        let mut defaults = DynConfig::new();
+        defaults.root_mut().set(nestr!("volume"), 50);
+        let mut render = DynTable::new();
+        render.set(nestr!("vsync"), true);
+        render.set(nestr!("fov"), 90.0);
+        defaults
+            .root_mut()
+            .set(nestr!("render"), Value::Table(render));
+
+        let mut user = DynConfig::new();
+        let mut render = DynTable::new();
+        render.set(nestr!("fov"), 110.0);
+        user.root_mut().set(nestr!("render"), Value::Table(render));
+
+        let mut overrides = DynConfig::new();
+        overrides.root_mut().set(nestr!("volume"), 0);
+
+        let mut config = LayeredConfig::new();
+        assert!(config.is_empty());
+        assert_eq!(
+            config.get_val_path(&["volume".into()]).err().unwrap(),
+            GetPathError::KeyDoesNotExist(ConfigPath::new())
+        );
+
+        assert_eq!(config.push("defaults", defaults), 0);
+        assert_eq!(config.push("user", user), 1);
+        assert_eq!(config.push("overrides", overrides), 2);
+        assert_eq!(config.len(), 3);
+        assert_eq!(config.layer_index("user"), Some(1));
+
+        let volume: [ConfigKey; 1] = ["volume".into()];
+        let fov: [ConfigKey; 2] = ["render".into(), "fov".into()];
+        let vsync: [ConfigKey; 2] = ["render".into(), "vsync".into()];
+        let missing: [ConfigKey; 2] = ["render".into(), "missing".into()];
+
+        assert_eq!(config.get_path::<_, _, i64>(&volume).unwrap(), 0);
+        assert_eq!(config.source(&volume), Some(2));
+        assert_eq!(config.layer_name(2), Some("overrides"));
+
+        assert_eq!(config.get_path::<_, _, f64>(&fov).unwrap(), 110.0);
+        assert_eq!(config.source(&fov), Some(1));
+
+        assert!(config.get_path::<_, _, bool>(&vsync).unwrap());
+        assert_eq!(config.source(&vsync), Some(0));
+
+        assert_eq!(config.source(&missing), None);
+        assert_eq!(
+            config.get_val_path(&missing).err().unwrap(),
+            GetPathError::KeyDoesNotExist(
+                vec![nestr!("render").into(), nestr!("missing").into()].into()
+            )
+        );
+
+        let flat = config.flatten();
+        assert_eq!(flat.root().get_i64("volume").unwrap(), 0);
+        let render = flat.root().get_table("render").unwrap();
+        assert_eq!(render.get_f64("fov").unwrap(), 110.0);
+        assert!(render.get_bool("vsync").unwrap());
+
+        // Modifying a layer affects the lookups.
+        config
+            .layer_mut(2)
+            .unwrap()
+            .root_mut()
+            .remove("volume")
+            .unwrap();
+        assert_eq!(config.get_path::<_, _, i64>(&volume).unwrap(), 50);
+        assert_eq!(config.source(&volume), Some(0));
+
+        assert_eq!(config.pop().unwrap().0, "overrides");
+        assert_eq!(config.len(), 2);
+    }
+}
@@ -0,0 +1,380 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        ffi::OsStr,
+        fmt::{Display, Formatter},
+        fs, io,
+        path::Path,
+        str::Utf8Error,
+    },
+};
+
+/// Source format of a config [`loaded`] into a [`dynamic config`].
+///
+/// [`loaded`]: struct.DynConfig.html#method.load
+/// [`dynamic config`]: struct.DynConfig.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ConfigFormat {
+    /// [`Binary config`](struct.BinConfig.html) data blob.
+    ///
+    /// NOTE - requires `"bin"` feature.
+    #[cfg(feature = "bin")]
+    Bin,
+    /// `.ini` config, parsed with default [`parser`](struct.IniParser.html) options.
+    ///
+    /// NOTE - requires `"ini"` feature.
+    #[cfg(feature = "ini")]
+    Ini,
+    /// Lua script config, executed in a new Lua state.
+    ///
+    /// NOTE - requires `"lua"` feature.
+    #[cfg(feature = "lua_runtime")]
+    Lua,
+}
+
+impl ConfigFormat {
+    /// Returns the config format corresponding to the `path` file extension
+    /// (`.bin`, `.ini` or `.lua`, case-sensitive), if it is known and its support is enabled.
+    pub fn from_extension<P: AsRef<Path>>(path: P) -> Option<Self> {
+        match path.as_ref().extension().and_then(OsStr::to_str) {
+            #[cfg(feature = "bin")]
+            Some("bin") => Some(ConfigFormat::Bin),
+            #[cfg(feature = "ini")]
+            Some("ini") => Some(ConfigFormat::Ini),
+            #[cfg(feature = "lua_runtime")]
+            Some("lua") => Some(ConfigFormat::Lua),
+            _ => None,
+        }
+    }
+
+    /// Determines the config format by the contents of the `data`:
+    /// - data starting with the [`binary config`] data blob header is a binary config,
+    /// - data starting with a Lua table constructor (`{`), optionally preceded by whitespace
+    ///   and Lua line comments (`--`), is a Lua script config,
+    /// - anything else is an `.ini` config.
+    ///
+    /// Returns `None` if the support for the detected format is not enabled.
+    ///
+    /// NOTE - the data is not validated beyond the checks above.
+    ///
+    /// [`binary config`]: struct.BinConfig.html
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if is_bin_config(data) {
+            Self::BIN
+        } else if is_lua_script(data) {
+            Self::LUA
+        } else {
+            Self::INI
+        }
+    }
+
+    #[cfg(feature = "bin")]
+    const BIN: Option<Self> = Some(ConfigFormat::Bin);
+    #[cfg(not(feature = "bin"))]
+    const BIN: Option<Self> = None;
+
+    #[cfg(feature = "ini")]
+    const INI: Option<Self> = Some(ConfigFormat::Ini);
+    #[cfg(not(feature = "ini"))]
+    const INI: Option<Self> = None;
+
+    #[cfg(feature = "lua_runtime")]
+    const LUA: Option<Self> = Some(ConfigFormat::Lua);
+    #[cfg(not(feature = "lua_runtime"))]
+    const LUA: Option<Self> = None;
+}
+
+/// An error returned when [`loading`] a [`dynamic config`] in the [`detected`] source format.
+///
+/// [`loading`]: struct.DynConfig.html#method.load
+/// [`dynamic config`]: struct.DynConfig.html
+/// [`detected`]: enum.ConfigFormat.html#method.detect
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigLoadError {
+    /// Failed to read the config file.
+    /// Contains the I/O error.
+    Io(io::Error),
+    /// The config format is unknown or its support is not enabled.
+    UnknownFormat,
+    /// The `.ini` / Lua script config source is not valid UTF-8.
+    /// Contains the UTF-8 error.
+    InvalidUtf8(Utf8Error),
+    /// Failed to load the binary config data blob.
+    /// Contains the [`binary config error`](enum.BinConfigError.html).
+    ///
+    /// NOTE - requires `"bin"` feature.
+    #[cfg(feature = "bin")]
+    Bin(BinConfigError),
+    /// Failed to parse the `.ini` config.
+    /// Contains the `.ini` [`parser error`](struct.IniError.html).
+    ///
+    /// NOTE - requires `"ini"` feature.
+    #[cfg(feature = "ini")]
+    Ini(IniError),
+    /// Failed to execute the Lua config script.
+    /// Contains the Lua [`config error`](enum.LuaConfigError.html).
+    ///
+    /// NOTE - requires `"lua"` feature.
+    #[cfg(feature = "lua_runtime")]
+    Lua(LuaConfigError),
+}
+
+impl Error for ConfigLoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use ConfigLoadError::*;
+
+        match self {
+            Io(err) => Some(err),
+            UnknownFormat => None,
+            InvalidUtf8(err) => Some(err),
+            #[cfg(feature = "bin")]
+            Bin(err) => Some(err),
+            #[cfg(feature = "ini")]
+            Ini(err) => Some(err),
+            #[cfg(feature = "lua_runtime")]
+            Lua(err) => Some(err),
+        }
+    }
+}
+
+impl Display for ConfigLoadError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use ConfigLoadError::*;
+
+        match self {
+            Io(err) => write!(f, "failed to read the config file: {}", err),
+            UnknownFormat => "unknown config format".fmt(f),
+            InvalidUtf8(err) => write!(f, "config source is not valid UTF-8: {}", err),
+            #[cfg(feature = "bin")]
+            Bin(err) => write!(f, "failed to load the binary config: {}", err),
+            #[cfg(feature = "ini")]
+            Ini(err) => write!(f, "failed to parse the `.ini` config: {}", err),
+            #[cfg(feature = "lua_runtime")]
+            Lua(err) => write!(f, "failed to execute the Lua config script: {}", err),
+        }
+    }
+}
+
+impl From<io::Error> for ConfigLoadError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl DynConfig {
+    /// Loads a new [`config`] from the file at `path`.
+    ///
+    /// The source [`format`] is determined by the file [`extension`];
+    /// if the extension is missing or unknown, by the file [`contents`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`format`]: enum.ConfigFormat.html
+    /// [`extension`]: enum.ConfigFormat.html#method.from_extension
+    /// [`contents`]: enum.ConfigFormat.html#method.detect
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigLoadError> {
+        let path = path.as_ref();
+        let data = fs::read(path)?;
+
+        let format = ConfigFormat::from_extension(path)
+            .or_else(|| ConfigFormat::detect(&data))
+            .ok_or(ConfigLoadError::UnknownFormat)?;
+
+        Self::from_bytes_format(data, format)
+    }
+
+    /// Creates a new [`config`] from the binary config data blob / `.ini` / Lua script config source `data`,
+    /// with the source format [`detected`] by its contents.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`detected`]: enum.ConfigFormat.html#method.detect
+    pub fn from_bytes_auto(data: &[u8]) -> Result<Self, ConfigLoadError> {
+        let format = ConfigFormat::detect(data).ok_or(ConfigLoadError::UnknownFormat)?;
+
+        Self::from_bytes_format(data, format)
+    }
+
+    /// Creates a new [`config`] from the `data` in the source [`format`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`format`]: enum.ConfigFormat.html
+    pub fn from_bytes_format<D: AsRef<[u8]> + Into<Box<[u8]>>>(
+        data: D,
+        format: ConfigFormat,
+    ) -> Result<Self, ConfigLoadError> {
+        use ConfigLoadError::*;
+
+        #[cfg(any(feature = "ini", feature = "lua_runtime"))]
+        let source = || std::str::from_utf8(data.as_ref()).map_err(InvalidUtf8);
+
+        match format {
+            #[cfg(feature = "bin")]
+            ConfigFormat::Bin => BinConfig::new(data.into())
+                .map(|config| config.to_dyn_config())
+                .map_err(Bin),
+            #[cfg(feature = "ini")]
+            ConfigFormat::Ini => DynConfig::from_ini(IniParser::new(source()?)).map_err(Ini),
+            #[cfg(feature = "lua_runtime")]
+            ConfigFormat::Lua => {
+                let script = source()?;
+                let lua = rlua::Lua::new();
+
+                lua.context(|lua| {
+                    LuaConfig::from_script(lua, script)
+                        .map(|config| config.to_dyn_config())
+                        .map_err(Lua)
+                })
+            }
+        }
+    }
+}
+
+/// Returns `true` if the `data` starts with the header of a binary config data blob
+/// (of a supported or an unsupported version).
+fn is_bin_config(data: &[u8]) -> bool {
+    #[cfg(feature = "bin")]
+    {
+        matches!(
+            BinConfig::data_version(data),
+            Ok(_) | Err(BinConfigError::UnsupportedVersion(_))
+        )
+    }
+    // Not loaded anyway - only make sure binary data blobs are not mistaken for `.ini` configs.
+    #[cfg(not(feature = "bin"))]
+    {
+        data.starts_with(b"bcfg") || data.starts_with(b"bcfv")
+    }
+}
+
+/// Returns `true` if the first token in the `data`, skipping whitespace and Lua line comments,
+/// is a Lua table constructor (`{`).
+fn is_lua_script(mut data: &[u8]) -> bool {
+    loop {
+        data = skip_ascii_whitespace(data);
+
+        if data.starts_with(b"--") {
+            let line_end = data.iter().position(|&b| b == b'\n').unwrap_or(data.len());
+            data = &data[line_end..];
+        } else {
+            return data.first() == Some(&b'{');
+        }
+    }
+}
+
+fn skip_ascii_whitespace(data: &[u8]) -> &[u8] {
+    let start = data
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    &data[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::*;
+
+    #[test]
+    fn detect() {
+        assert!(ConfigFormat::from_extension("config.txt").is_none());
+        assert!(ConfigFormat::from_extension("config").is_none());
+
+        #[cfg(feature = "ini")]
+        {
+            assert_eq!(
+                ConfigFormat::from_extension("assets/config.ini"),
+                Some(ConfigFormat::Ini)
+            );
+            assert_eq!(
+                ConfigFormat::detect(b"; comment\nkey = 7"),
+                Some(ConfigFormat::Ini)
+            );
+            assert_eq!(ConfigFormat::detect(b""), Some(ConfigFormat::Ini));
+        }
+
+        #[cfg(feature = "lua_runtime")]
+        assert_eq!(
+            ConfigFormat::detect(b"-- comment\n  { key = 7 }"),
+            Some(ConfigFormat::Lua)
+        );
+        #[cfg(not(feature = "lua_runtime"))]
+        assert!(ConfigFormat::detect(b"-- comment\n  { key = 7 }").is_none());
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn from_bytes_auto() {
+        let config = DynConfig::from_bytes_auto(b"key = 7\n[section]\nvalue = \"foo\"").unwrap();
+        assert_eq!(config.root().get_i64("key").unwrap(), 7);
+
+        match DynConfig::from_bytes_auto(b"key").err().unwrap() {
+            ConfigLoadError::Ini(err) => {
+                assert_eq!(
+                    err.error,
+                    IniErrorKind::UnexpectedEndOfFileBeforeKeyValueSeparator
+                )
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+
+        assert!(matches!(
+            DynConfig::from_bytes_auto(b"key = \xff").err().unwrap(),
+            ConfigLoadError::InvalidUtf8(_)
+        ));
+
+        #[cfg(feature = "bin")]
+        {
+            let data = config.to_bin_config().unwrap();
+            assert_eq!(ConfigFormat::detect(&data), Some(ConfigFormat::Bin));
+
+            let bin_config = DynConfig::from_bytes_auto(&data).unwrap();
+            assert!(bin_config.diff(&config).is_empty());
+
+            // Data blobs with unsupported versions are detected as binary configs.
+            let mut data = data.into_vec();
+            data[4..8].copy_from_slice(&u32::to_le_bytes(u32::MAX));
+            assert!(matches!(
+                DynConfig::from_bytes_auto(&data).err().unwrap(),
+                ConfigLoadError::Bin(BinConfigError::UnsupportedVersion(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn load() {
+        let dir = std::env::temp_dir();
+        let path = |name: &str| {
+            dir.join(format!(
+                "miniconfig_load_test_{}{}",
+                std::process::id(),
+                name
+            ))
+        };
+
+        // Format is determined by the extension ...
+        let ini_path = path(".ini");
+        std::fs::write(&ini_path, "key = 7").unwrap();
+
+        let config = DynConfig::load(&ini_path).unwrap();
+        assert_eq!(config.root().get_i64("key").unwrap(), 7);
+
+        // ... or by the contents.
+        let cfg_path = path(".cfg");
+        std::fs::write(&cfg_path, "key = 9").unwrap();
+
+        let config = DynConfig::load(&cfg_path).unwrap();
+        assert_eq!(config.root().get_i64("key").unwrap(), 9);
+
+        std::fs::remove_file(&ini_path).unwrap();
+        std::fs::remove_file(&cfg_path).unwrap();
+
+        assert!(matches!(
+            DynConfig::load(&ini_path).err().unwrap(),
+            ConfigLoadError::Io(_)
+        ));
+    }
+}
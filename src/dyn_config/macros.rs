@@ -0,0 +1,185 @@
+/// Builds a [`dynamic config`] from a literal description of its root [`table`],
+/// e.g. `dyn_config!{ name: "Game", window: { width: 1920, height: 1080 }, levels: ["intro", "boss"] }`.
+///
+/// The root [`table`] elements may optionally be enclosed in braces, e.g. `dyn_config!({ "name": "Game" })`.
+///
+/// [`Table`] keys are identifiers or (non-empty) string literals.
+/// Values are nested [`tables`] (`{ ... }`), nested [`arrays`] (`[ ... ]`),
+/// or any expressions convertible to a [`value`] (`bool`, integers, `f64`, strings, [`date/times`]).
+/// Trailing commas are allowed.
+///
+/// # Panics
+///
+/// Panics if a [`table`] key is an empty string, or if an [`array`] contains values of mixed (and non-convertible) types.
+///
+/// [`dynamic config`]: struct.DynConfig.html
+/// [`table`]: struct.DynTable.html
+/// [`Table`]: struct.DynTable.html
+/// [`tables`]: struct.DynTable.html
+/// [`arrays`]: struct.DynArray.html
+/// [`array`]: struct.DynArray.html
+/// [`value`]: type.DynConfigValue.html
+/// [`date/times`]: struct.DateTime.html
+#[macro_export]
+macro_rules! dyn_config {
+    // Table elements.
+    (@table $table:ident ()) => {};
+    (@table $table:ident ($key:ident : $($rest:tt)*)) => {
+        $crate::dyn_config!(@entry $table (stringify!($key)) ($($rest)*));
+    };
+    (@table $table:ident ($key:literal : $($rest:tt)*)) => {
+        $crate::dyn_config!(@entry $table ($key) ($($rest)*));
+    };
+
+    // Table element values.
+    (@entry $table:ident $key:tt ({ $($value:tt)* } $(, $($rest:tt)*)?)) => {
+        $crate::dyn_config!(@set $table $key ($crate::dyn_config!(@table_value $($value)*)));
+        $crate::dyn_config!(@table $table ($($($rest)*)?));
+    };
+    (@entry $table:ident $key:tt ([ $($value:tt)* ] $(, $($rest:tt)*)?)) => {
+        $crate::dyn_config!(@set $table $key ($crate::dyn_config!(@array_value $($value)*)));
+        $crate::dyn_config!(@table $table ($($($rest)*)?));
+    };
+    (@entry $table:ident $key:tt ($value:expr $(, $($rest:tt)*)?)) => {
+        $crate::dyn_config!(@set $table $key ($crate::DynConfigValue::from($value)));
+        $crate::dyn_config!(@table $table ($($($rest)*)?));
+    };
+    (@set $table:ident $key:tt $value:tt) => {
+        $table.set(
+            $crate::NonEmptyStr::new($key).expect("empty `dyn_config!` table key"),
+            $value,
+        );
+    };
+
+    // Array elements.
+    (@array $array:ident ()) => {};
+    (@array $array:ident ({ $($value:tt)* } $(, $($rest:tt)*)?)) => {
+        $crate::dyn_config!(@push $array ($crate::dyn_config!(@table_value $($value)*)));
+        $crate::dyn_config!(@array $array ($($($rest)*)?));
+    };
+    (@array $array:ident ([ $($value:tt)* ] $(, $($rest:tt)*)?)) => {
+        $crate::dyn_config!(@push $array ($crate::dyn_config!(@array_value $($value)*)));
+        $crate::dyn_config!(@array $array ($($($rest)*)?));
+    };
+    (@array $array:ident ($value:expr $(, $($rest:tt)*)?)) => {
+        $crate::dyn_config!(@push $array ($crate::DynConfigValue::from($value)));
+        $crate::dyn_config!(@array $array ($($($rest)*)?));
+    };
+    (@push $array:ident $value:tt) => {
+        $array
+            .push($value)
+            .expect("mixed `dyn_config!` array value types");
+    };
+
+    // Nested tables / arrays.
+    (@table_value) => {
+        $crate::DynConfigValue::from($crate::DynTable::new())
+    };
+    (@table_value $($value:tt)+) => {{
+        let mut table = $crate::DynTable::new();
+        $crate::dyn_config!(@table table ($($value)*));
+        $crate::DynConfigValue::from(table)
+    }};
+    (@array_value) => {
+        $crate::DynConfigValue::from($crate::DynArray::new())
+    };
+    (@array_value $($value:tt)+) => {{
+        let mut array = $crate::DynArray::new();
+        $crate::dyn_config!(@array array ($($value)*));
+        $crate::DynConfigValue::from(array)
+    }};
+
+    // Root table.
+    ({ $($table:tt)* }) => {
+        $crate::dyn_config!($($table)*)
+    };
+    () => {
+        $crate::DynConfig::new()
+    };
+    ($($table:tt)+) => {{
+        let mut config = $crate::DynConfig::new();
+        {
+            let table = config.root_mut();
+            $crate::dyn_config!(@table table ($($table)*));
+        }
+        config
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    #[test]
+    fn dyn_config() {
+        let width = 1920;
+
+        let config = dyn_config! {
+            name: "Game",
+            "version": 3,
+            scale: -1.5,
+            fullscreen: false,
+            window: {
+                width: width,
+                "height": width / 16 * 9,
+                title: String::from("Main"),
+            },
+            levels: ["intro", "boss"],
+            matrix: [[1, 2], [3, 4.5], []],
+            enemies: [
+                { name: "orc", hp: 10 },
+                { name: "troll", hp: 25 },
+            ],
+            empty: {},
+        };
+
+        let root = config.root();
+
+        assert_eq!(root.len(), 9);
+        assert_eq!(root.get_string("name").unwrap(), "Game");
+        assert_eq!(root.get_i64("version").unwrap(), 3);
+        assert_eq!(root.get_f64("scale").unwrap(), -1.5);
+        assert!(!root.get_bool("fullscreen").unwrap());
+
+        let window = root.get_table("window").unwrap();
+        assert_eq!(window.get_i64("width").unwrap(), 1920);
+        assert_eq!(window.get_i64("height").unwrap(), 1080);
+        assert_eq!(window.get_string("title").unwrap(), "Main");
+
+        let levels = root.get_array("levels").unwrap();
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels.get_string(1).unwrap(), "boss");
+
+        let matrix = root.get_array("matrix").unwrap();
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix.get_array(1).unwrap().get_f64(1).unwrap(), 4.5);
+        assert!(matrix.get_array(2).unwrap().is_empty());
+
+        let enemies = root.get_array("enemies").unwrap();
+        assert_eq!(
+            enemies.get_table(1).unwrap().get_string("name").unwrap(),
+            "troll"
+        );
+
+        assert!(root.get_table("empty").unwrap().is_empty());
+
+        // Braces around the root table.
+        let config = dyn_config!({ "a": 1, b: [true], });
+        assert_eq!(config.root().get_i64("a").unwrap(), 1);
+        assert!(config.root().get_array("b").unwrap().get_bool(0).unwrap());
+
+        assert!(dyn_config! {}.root().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "mixed `dyn_config!` array value types")]
+    fn dyn_config_mixed_array() {
+        dyn_config! { array: [1, "two"] };
+    }
+
+    #[test]
+    #[should_panic(expected = "empty `dyn_config!` table key")]
+    fn dyn_config_empty_key() {
+        dyn_config! { "": 1 };
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{util::*, *};
+
+impl DynTable {
+    /// Tries to get a 2-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `2` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec2<K: AsRef<str>>(&self, key: K) -> Result<[f32; 2], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get a 3-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `3` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec3<K: AsRef<str>>(&self, key: K) -> Result<[f32; 3], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get a 4-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `4` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec4<K: AsRef<str>>(&self, key: K) -> Result<[f32; 4], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get an RGBA color [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// The color is either a `#rrggbb` / `#rrggbbaa` hex [`string`],
+    /// or a numeric [`array`] with `3` (RGB, alpha is `1.0`) or `4` (RGBA) elements.
+    /// Channels of hex [`strings`] are converted to `[0.0, 1.0]` range; [`array`] elements are returned as is.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`] or an [`array`],
+    /// or if it is not a valid color.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`strings`]: enum.Value.html#variant.String
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_color<K: AsRef<str>>(&self, key: K) -> Result<[f32; 4], GetVecError> {
+        match self.get_val(key).ok_or(TableError::KeyDoesNotExist)? {
+            Value::String(color) => parse_color(color),
+            Value::Array(array) => color_from_values(array.len(), array.iter()),
+            value => Err(TableError::IncorrectValueType(value.get_type()).into()),
+        }
+    }
+
+    fn get_vec<K: AsRef<str>, const N: usize>(&self, key: K) -> Result<[f32; N], GetVecError> {
+        let array = self.get_array(key)?;
+        vec_from_values(array.len(), array.iter())
+    }
+}
+
+impl DynArray {
+    /// Tries to get a 2-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `2` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec2(&self, index: u32) -> Result<[f32; 2], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get a 3-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `3` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec3(&self, index: u32) -> Result<[f32; 3], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get a 4-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `4` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec4(&self, index: u32) -> Result<[f32; 4], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get an RGBA color [`value`] in the [`array`] at `index`.
+    ///
+    /// See [`DynTable::get_color`] for supported color representations.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`] or an [`array`],
+    /// or if it is not a valid color.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`DynTable::get_color`]: struct.DynTable.html#method.get_color
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_color(&self, index: u32) -> Result<[f32; 4], GetVecError> {
+        match self.get_val(index)? {
+            Value::String(color) => parse_color(color),
+            Value::Array(array) => color_from_values(array.len(), array.iter()),
+            value => Err(ArrayError::IncorrectValueType(value.get_type()).into()),
+        }
+    }
+
+    fn get_vec<const N: usize>(&self, index: u32) -> Result<[f32; N], GetVecError> {
+        let array = self.get_array(index)?;
+        vec_from_values(array.len(), array.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn get_vec() {
+        let mut position = DynArray::new();
+        position.push(1.0.into()).unwrap();
+        position.push(2.into()).unwrap();
+        position.push((-3.5).into()).unwrap();
+
+        let mut color = DynArray::new();
+        color.push(0.5.into()).unwrap();
+        color.push(0.25.into()).unwrap();
+        color.push(1.0.into()).unwrap();
+
+        let mut invalid = DynArray::new();
+        invalid.push("1.0".into()).unwrap();
+        invalid.push("2.0".into()).unwrap();
+
+        let mut table = DynTable::new();
+        table.set(nestr!("position"), Value::Array(position.clone()));
+        table.set(nestr!("color"), Value::Array(color));
+        table.set(nestr!("hex"), "#ff800040");
+        table.set(nestr!("invalid"), Value::Array(invalid));
+        table.set(nestr!("bool"), true);
+
+        assert_eq!(table.get_vec3("position").unwrap(), [1.0, 2.0, -3.5]);
+        assert_eq!(
+            table.get_vec2("position").err().unwrap(),
+            GetVecError::InvalidLength(3)
+        );
+        assert_eq!(
+            table.get_vec4("position").err().unwrap(),
+            GetVecError::InvalidLength(3)
+        );
+        assert_eq!(
+            table.get_vec2("invalid").err().unwrap(),
+            GetVecError::IncorrectElementType {
+                index: 0,
+                value_type: ValueType::String
+            }
+        );
+        assert_eq!(
+            table.get_vec2("bool").err().unwrap(),
+            GetVecError::Table(TableError::IncorrectValueType(ValueType::Bool))
+        );
+        assert_eq!(
+            table.get_vec2("missing").err().unwrap(),
+            GetVecError::Table(TableError::KeyDoesNotExist)
+        );
+
+        assert_eq!(table.get_color("color").unwrap(), [0.5, 0.25, 1.0, 1.0]);
+        assert_eq!(
+            table.get_color("hex").unwrap(),
+            [1.0, 128.0 / 255.0, 0.0, 64.0 / 255.0]
+        );
+        assert_eq!(table.get_color("position").unwrap(), [1.0, 2.0, -3.5, 1.0]);
+        assert_eq!(
+            table.get_color("bool").err().unwrap(),
+            GetVecError::Table(TableError::IncorrectValueType(ValueType::Bool))
+        );
+
+        let mut array = DynArray::new();
+        array.push(Value::Array(position)).unwrap();
+
+        assert_eq!(array.get_vec3(0).unwrap(), [1.0, 2.0, -3.5]);
+        assert_eq!(
+            array.get_vec3(1).err().unwrap(),
+            GetVecError::Array(ArrayError::IndexOutOfBounds(1))
+        );
+
+        let mut array = DynArray::new();
+        for color in ["#00ff00", "#00ff0", "00ff00", "#00ff0g", "#00ff00f"].iter() {
+            array.push((*color).into()).unwrap();
+        }
+
+        assert_eq!(array.get_color(0).unwrap(), [0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(
+            array.get_vec3(0).err().unwrap(),
+            GetVecError::Array(ArrayError::IncorrectValueType(ValueType::String))
+        );
+
+        for index in 1..array.len() {
+            assert_eq!(
+                array.get_color(index).err().unwrap(),
+                GetVecError::InvalidColor(array.get_string(index).unwrap().to_owned())
+            );
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use {
+    super::hashed_key::DynTableKey,
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+        mem::size_of,
+    },
+};
+
+/// Estimated heap size in bytes of a [`table`](struct.DynTable.html) entry, excluding the key string.
+/// Includes the hash map control byte.
+const TABLE_ENTRY_SIZE: usize = size_of::<DynTableKey>() + size_of::<DynConfigValue>() + 1;
+
+/// Estimated heap size in bytes of an [`array`](struct.DynArray.html) element.
+pub(super) const ARRAY_VALUE_SIZE: usize = size_of::<DynConfigValue>();
+
+/// An error returned when the estimated [`memory usage`] of a [`config`] exceeds the limit.
+///
+/// [`memory usage`]: struct.DynConfig.html#method.memory_usage
+/// [`config`]: struct.DynConfig.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub struct DynMemoryLimitError {
+    /// The memory usage limit in bytes.
+    pub limit: usize,
+    /// The estimated memory usage in bytes of the config.
+    pub usage: usize,
+}
+
+impl Error for DynMemoryLimitError {}
+
+impl Display for DynMemoryLimitError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "estimated memory usage of the config ({} bytes) exceeds the limit ({} bytes)",
+            self.usage, self.limit
+        )
+    }
+}
+
+impl DynConfig {
+    /// Returns the estimated heap memory usage in bytes of the contents of the [`config`]:
+    /// its (nested) [`tables`], [`arrays`], string values and keys.
+    ///
+    /// NOTE - the estimate is based on the number and the lengths of the contained elements
+    /// and does not include the unused capacity of the allocations, nor the [`arena`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`arena`]: struct.DynConfigArena.html
+    pub fn memory_usage(&self) -> usize {
+        self.root().memory_usage()
+    }
+
+    /// Creates a new [`config`] from the [`binary config`], copying all its tables / arrays / values,
+    /// if its estimated [`memory usage`] does not exceed `max_memory_usage` bytes.
+    ///
+    /// The [`binary config`] is checked before any allocations are made.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`memory usage`]: #method.memory_usage
+    #[cfg(feature = "bin")]
+    pub fn from_bin_config_with_memory_limit(
+        config: &BinConfig,
+        max_memory_usage: usize,
+    ) -> Result<Self, DynMemoryLimitError> {
+        let usage = table_memory_usage(&config.root());
+
+        if usage > max_memory_usage {
+            Err(DynMemoryLimitError {
+                limit: max_memory_usage,
+                usage,
+            })
+        } else {
+            Ok(config.to_dyn_config())
+        }
+    }
+}
+
+impl DynTable {
+    /// Returns the estimated heap memory usage in bytes of the contents of the [`table`],
+    /// as if it was the root table of a config (see [`DynConfig::memory_usage`]).
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`DynConfig::memory_usage`]: struct.DynConfig.html#method.memory_usage
+    pub fn memory_usage(&self) -> usize {
+        table_memory_usage(&self)
+    }
+}
+
+impl DynArray {
+    /// Returns the estimated heap memory usage in bytes of the contents of the [`array`]
+    /// (see [`DynConfig::memory_usage`]).
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`DynConfig::memory_usage`]: struct.DynConfig.html#method.memory_usage
+    pub fn memory_usage(&self) -> usize {
+        array_memory_usage(&self)
+    }
+}
+
+/// Returns the estimated heap memory usage in bytes of a [`dynamic config`](struct.DynConfig.html) string value.
+fn string_memory_usage(string: &str) -> usize {
+    string.len()
+}
+
+/// Returns the estimated heap memory usage in bytes of a [`dynamic config`](struct.DynConfig.html) table entry
+/// with the `key`, excluding the value.
+pub(super) fn table_entry_memory_usage(key: &str) -> usize {
+    TABLE_ENTRY_SIZE + key.len()
+}
+
+/// Returns the estimated heap memory usage in bytes of the `.ini` `value`
+/// when added to a [`dynamic config`](struct.DynConfig.html), excluding its table entry / array element.
+#[cfg(feature = "ini")]
+pub(super) fn ini_value_memory_usage(value: &IniValue<'_, '_>) -> usize {
+    match value {
+        IniValue::String(value) => string_memory_usage(value.as_str()),
+        _ => 0,
+    }
+}
+
+/// Returns the estimated heap memory usage in bytes of the `table` contents,
+/// if it was a [`dynamic config`](struct.DynConfig.html) table.
+fn table_memory_usage<T: TableAccess>(table: &T) -> usize {
+    table
+        .iter()
+        .map(|(key, value)| table_entry_memory_usage(key.as_ref()) + value_memory_usage(value))
+        .sum()
+}
+
+/// Returns the estimated heap memory usage in bytes of the `array` contents,
+/// if it was a [`dynamic config`](struct.DynConfig.html) array.
+fn array_memory_usage<A: ArrayAccess>(array: &A) -> usize {
+    array
+        .iter()
+        .map(|value| ARRAY_VALUE_SIZE + value_memory_usage(value))
+        .sum()
+}
+
+/// Returns the estimated heap memory usage in bytes of the `value`, excluding its table entry / array element.
+pub(super) fn value_memory_usage<S, A, T>(value: Value<S, A, T>) -> usize
+where
+    S: AsRef<str>,
+    A: ArrayAccess,
+    T: TableAccess,
+{
+    match value {
+        Value::String(value) => string_memory_usage(value.as_ref()),
+        Value::Array(value) => array_memory_usage(&value),
+        Value::Table(value) => table_memory_usage(&value),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn memory_usage() {
+        let mut config = DynConfig::new();
+
+        assert_eq!(config.memory_usage(), 0);
+
+        config.root_mut().set(nestr!("bool"), true);
+        let usage = super::TABLE_ENTRY_SIZE + "bool".len();
+        assert_eq!(config.memory_usage(), usage);
+
+        config.root_mut().set(nestr!("string"), "hello");
+        let usage = usage + super::TABLE_ENTRY_SIZE + "string".len() + "hello".len();
+        assert_eq!(config.memory_usage(), usage);
+
+        let mut array = DynArray::new();
+        array.push("foo".into()).unwrap();
+        array.push("bar".into()).unwrap();
+        let array_usage = 2 * super::ARRAY_VALUE_SIZE + "foo".len() + "bar".len();
+        assert_eq!(array.memory_usage(), array_usage);
+
+        let mut table = DynTable::new();
+        table.set(nestr!("array"), array);
+        let table_usage = super::TABLE_ENTRY_SIZE + "array".len() + array_usage;
+        assert_eq!(table.memory_usage(), table_usage);
+
+        config.root_mut().set(nestr!("table"), table);
+        let usage = usage + super::TABLE_ENTRY_SIZE + "table".len() + table_usage;
+        assert_eq!(config.memory_usage(), usage);
+
+        // Overwritten values are not counted.
+        config.root_mut().set(nestr!("string"), 7);
+        let usage = usage - "hello".len();
+        assert_eq!(config.memory_usage(), usage);
+    }
+
+    #[cfg(feature = "bin")]
+    #[test]
+    fn from_bin_config_with_memory_limit() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+        root.set(nestr!("string"), "hello");
+        root.set(nestr!("int"), 7);
+
+        let usage = config.memory_usage();
+
+        let config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+
+        let dyn_config = DynConfig::from_bin_config_with_memory_limit(&config, usage).unwrap();
+        assert_eq!(dyn_config.memory_usage(), usage);
+        assert_eq!(dyn_config.root().get_string("string").unwrap(), "hello");
+
+        assert_eq!(
+            DynConfig::from_bin_config_with_memory_limit(&config, usage - 1)
+                .err()
+                .unwrap(),
+            DynMemoryLimitError {
+                limit: usage - 1,
+                usage
+            }
+        );
+    }
+}
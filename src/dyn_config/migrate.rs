@@ -0,0 +1,297 @@
+use {
+    crate::*,
+    std::{
+        collections::BTreeMap,
+        error::Error,
+        fmt::{Display, Formatter},
+        ops::Bound,
+    },
+};
+
+/// A migration step which upgrades the [`config`] from the previous version
+/// to the version it was [`registered`] with.
+///
+/// NOTE - the step must not modify the version value in the root table of the [`config`] -
+/// it is updated by the [`migration pipeline`] after the step is applied.
+///
+/// [`config`]: struct.DynConfig.html
+/// [`registered`]: struct.DynConfigMigrations.html#method.add
+/// [`migration pipeline`]: struct.DynConfig.html#method.migrate_to
+pub type DynConfigMigrationStep = fn(&mut DynConfig);
+
+/// A registry of versioned [`migration steps`] applied to a [`config`] by [`DynConfig::migrate_to`].
+///
+/// The current version of the [`config`] is an [`i64`] value with the user-provided key in its root table.
+/// A [`config`] without the version value is considered to be of version `0`.
+///
+/// [`migration steps`]: type.DynConfigMigrationStep.html
+/// [`config`]: struct.DynConfig.html
+/// [`DynConfig::migrate_to`]: struct.DynConfig.html#method.migrate_to
+/// [`i64`]: enum.Value.html#variant.I64
+pub struct DynConfigMigrations {
+    version_key: NonEmptyString,
+    steps: BTreeMap<i64, DynConfigMigrationStep>,
+}
+
+impl DynConfigMigrations {
+    /// Creates a new empty migration registry for [`configs`]
+    /// which store their version at the (non-empty) string `version_key` in their root table.
+    ///
+    /// [`configs`]: struct.DynConfig.html
+    pub fn new<K: AsRef<NonEmptyStr>>(version_key: K) -> Self {
+        Self {
+            version_key: version_key.as_ref().into(),
+            steps: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the key of the version value in the root table of migrated [`configs`].
+    ///
+    /// [`configs`]: struct.DynConfig.html
+    pub fn version_key(&self) -> &NonEmptyStr {
+        &self.version_key
+    }
+
+    /// Registers the migration `step` which upgrades the [`config`] from the previous version to `version`.
+    ///
+    /// Returns an [`error`] if `version` is not positive, or if a step for `version` was already registered.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`error`]: enum.DynMigrateError.html
+    pub fn add(
+        &mut self,
+        version: i64,
+        step: DynConfigMigrationStep,
+    ) -> Result<(), DynMigrateError> {
+        use DynMigrateError::*;
+
+        if version <= 0 {
+            return Err(InvalidVersion(version));
+        }
+
+        if self.steps.contains_key(&version) {
+            return Err(DuplicateStep(version));
+        }
+
+        self.steps.insert(version, step);
+
+        Ok(())
+    }
+
+    /// Returns the latest version registered with a migration step, or `0` if none were registered.
+    pub fn latest(&self) -> i64 {
+        self.steps.keys().next_back().copied().unwrap_or(0)
+    }
+
+    /// Returns the current version of the `config`, or `0` if it has no version value.
+    ///
+    /// Returns an [`error`] if the version value is not an [`i64`] or is negative.
+    ///
+    /// [`error`]: enum.DynMigrateError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn version(&self, config: &DynConfig) -> Result<i64, DynMigrateError> {
+        use DynMigrateError::*;
+
+        match config.root().get_val(&self.version_key) {
+            None => Ok(0),
+            Some(Value::I64(version)) => {
+                if version < 0 {
+                    Err(InvalidVersion(version))
+                } else {
+                    Ok(version)
+                }
+            }
+            Some(value) => Err(InvalidVersionType(value.get_type())),
+        }
+    }
+}
+
+impl DynConfig {
+    /// Upgrades the [`config`] to the `target` version by applying all migration steps
+    /// registered in the `migrations` for versions after the current version of the [`config`], up to and including `target`,
+    /// in order of their versions.
+    ///
+    /// The version value in the root table of the [`config`] is set to the version of each step after it is applied,
+    /// and to `target` when done.
+    ///
+    /// Returns the number of applied steps.
+    ///
+    /// Returns an [`error`] if the current version of the [`config`] is invalid or newer than `target`,
+    /// or if `target` is invalid. The [`config`] is not modified in this case.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`error`]: enum.DynMigrateError.html
+    pub fn migrate_to(
+        &mut self,
+        migrations: &DynConfigMigrations,
+        target: i64,
+    ) -> Result<u32, DynMigrateError> {
+        use DynMigrateError::*;
+
+        if target < 0 {
+            return Err(InvalidVersion(target));
+        }
+
+        let version = migrations.version(self)?;
+
+        if version > target {
+            return Err(NewerVersion { version, target });
+        }
+
+        let mut num_steps = 0;
+
+        for (&step_version, step) in migrations
+            .steps
+            .range((Bound::Excluded(version), Bound::Included(target)))
+        {
+            step(self);
+            self.root_mut().set(&migrations.version_key, step_version);
+            num_steps += 1;
+        }
+
+        self.root_mut().set(&migrations.version_key, target);
+
+        Ok(num_steps)
+    }
+}
+
+/// An error returned when registering [`migration steps`] or [`migrating`] a [`config`].
+///
+/// [`migration steps`]: struct.DynConfigMigrations.html#method.add
+/// [`migrating`]: struct.DynConfig.html#method.migrate_to
+/// [`config`]: struct.DynConfig.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum DynMigrateError {
+    /// Config version is negative, or a migration step version is not positive.
+    /// Contains the invalid version.
+    InvalidVersion(i64),
+    /// A migration step was already registered for the version.
+    /// Contains the version.
+    DuplicateStep(i64),
+    /// Config version value is not an [`i64`].
+    /// Contains the actual value [`type`].
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`type`]: enum.ValueType.html
+    InvalidVersionType(ValueType),
+    /// Config version is newer than the migration target version.
+    NewerVersion {
+        /// Current config version.
+        version: i64,
+        /// Migration target version.
+        target: i64,
+    },
+}
+
+impl Error for DynMigrateError {}
+
+impl Display for DynMigrateError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DynMigrateError::*;
+
+        match self {
+            InvalidVersion(version) => write!(f, "invalid config version {}", version),
+            DuplicateStep(version) => write!(
+                f,
+                "migration step for config version {} is already registered",
+                version
+            ),
+            InvalidVersionType(value_type) => write!(
+                f,
+                "config version value is not an integer (found {})",
+                value_type
+            ),
+            NewerVersion { version, target } => write!(
+                f,
+                "config version {} is newer than the migration target version {}",
+                version, target
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    fn rename_resolution(config: &mut DynConfig) {
+        let root = config.root_mut();
+
+        if let Some(Value::I64(width)) = root.get_val("width") {
+            root.remove(nestr!("width"));
+            root.set(nestr!("resolution_x"), width);
+        }
+    }
+
+    fn add_vsync(config: &mut DynConfig) {
+        config.root_mut().set(nestr!("vsync"), true);
+    }
+
+    fn migrations() -> DynConfigMigrations {
+        let mut migrations = DynConfigMigrations::new(nestr!("version"));
+
+        // Registration order does not matter.
+        migrations.add(2, add_vsync).unwrap();
+        migrations.add(1, rename_resolution).unwrap();
+
+        assert_eq!(
+            migrations.add(1, add_vsync).err().unwrap(),
+            DynMigrateError::DuplicateStep(1)
+        );
+        assert_eq!(
+            migrations.add(0, add_vsync).err().unwrap(),
+            DynMigrateError::InvalidVersion(0)
+        );
+
+        migrations
+    }
+
+    #[test]
+    fn migrate_to() {
+        let migrations = migrations();
+        assert_eq!(migrations.latest(), 2);
+
+        // No version value - version `0`.
+        let mut config = DynConfig::new();
+        config.root_mut().set(nestr!("width"), 1920);
+
+        assert_eq!(migrations.version(&config).unwrap(), 0);
+
+        assert_eq!(config.migrate_to(&migrations, 1).unwrap(), 1);
+        assert_eq!(migrations.version(&config).unwrap(), 1);
+        assert_eq!(config.root().get_i64("resolution_x").unwrap(), 1920);
+        assert!(config.root().get_val("width").is_none());
+        assert!(config.root().get_val("vsync").is_none());
+
+        assert_eq!(
+            config.migrate_to(&migrations, migrations.latest()).unwrap(),
+            1
+        );
+        assert_eq!(migrations.version(&config).unwrap(), 2);
+        assert!(config.root().get_bool("vsync").unwrap());
+
+        // Already up to date.
+        assert_eq!(config.migrate_to(&migrations, 2).unwrap(), 0);
+
+        // Target version without a step.
+        assert_eq!(config.migrate_to(&migrations, 3).unwrap(), 0);
+        assert_eq!(migrations.version(&config).unwrap(), 3);
+
+        assert_eq!(
+            config.migrate_to(&migrations, 2).err().unwrap(),
+            DynMigrateError::NewerVersion {
+                version: 3,
+                target: 2
+            }
+        );
+
+        config.root_mut().set(nestr!("version"), "2");
+        assert_eq!(
+            config.migrate_to(&migrations, 2).err().unwrap(),
+            DynMigrateError::InvalidVersionType(ValueType::String)
+        );
+    }
+}
@@ -1,6 +1,41 @@
+mod access;
+mod arena;
 mod array;
+mod comments;
 mod config;
+mod diff;
+mod entry;
+mod flat;
+mod hashed_key;
+#[cfg(feature = "ini")]
+mod ini_update;
+#[cfg(feature = "ini")]
+mod interpolation;
+mod layered;
+#[cfg(any(feature = "bin", feature = "ini", feature = "lua_runtime"))]
+mod load;
+mod macros;
+#[cfg(feature = "math")]
+mod math;
+mod memory;
+mod migrate;
+mod rekey;
+mod shared;
+mod stats;
+mod subscribe;
 mod table;
+mod template;
+mod units;
 mod value;
 
-pub use {array::*, config::*, table::*, value::*};
+pub use {
+    arena::DynConfigArena, array::*, config::*, diff::*, entry::DynTableEntry, flat::*,
+    layered::LayeredConfig, memory::DynMemoryLimitError, migrate::*, rekey::*, shared::SharedConfig,
+    subscribe::DynConfigSubscription, table::*, template::*, value::*,
+};
+
+#[cfg(feature = "ini")]
+pub use ini_update::IniUpdateError;
+
+#[cfg(any(feature = "bin", feature = "ini", feature = "lua_runtime"))]
+pub use load::{ConfigFormat, ConfigLoadError};
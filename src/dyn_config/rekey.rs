@@ -0,0 +1,107 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// Controls how duplicate keys, if any, are handled when an [`array`] of [`tables`]
+/// is [`re-keyed`] into a [`table`].
+///
+/// [`array`]: struct.DynArray.html
+/// [`tables`]: struct.DynTable.html
+/// [`re-keyed`]: struct.DynArray.html#method.into_table_by_key
+/// [`table`]: struct.DynTable.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DynRekeyDuplicates {
+    /// Do not allow duplicate keys.
+    Forbid,
+    /// Use the first [`array`](struct.DynArray.html) element with the key,
+    /// skip all following ones.
+    First,
+    /// Use the last [`array`](struct.DynArray.html) element with the key,
+    /// overwriting all prior, if any.
+    Last,
+}
+
+/// An error returned when re-keying an [`array`] of [`tables`] into a [`table`] and vice versa.
+///
+/// [`array`]: struct.DynArray.html
+/// [`tables`]: struct.DynTable.html
+/// [`table`]: struct.DynTable.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum DynRekeyError {
+    /// [`Array`] element is not a [`table`].
+    /// Contains the element index and its actual value [`type`].
+    ///
+    /// [`Array`]: struct.DynArray.html
+    /// [`table`]: struct.DynTable.html
+    /// [`type`]: enum.ValueType.html
+    ElementNotATable(u32, ValueType),
+    /// [`Array`] element [`table`] does not contain the key field.
+    /// Contains the element index.
+    ///
+    /// [`Array`]: struct.DynArray.html
+    /// [`table`]: struct.DynTable.html
+    MissingKeyField(u32),
+    /// [`Array`] element key field value is not a non-empty string or an integer.
+    /// Contains the element index and the key field value [`type`].
+    ///
+    /// [`Array`]: struct.DynArray.html
+    /// [`type`]: enum.ValueType.html
+    InvalidKeyField(u32, ValueType),
+    /// [`Array`] element key is a duplicate and duplicate keys are [`forbidden`].
+    /// Contains the element index and the key.
+    ///
+    /// [`Array`]: struct.DynArray.html
+    /// [`forbidden`]: enum.DynRekeyDuplicates.html#variant.Forbid
+    DuplicateKey(u32, NonEmptyString),
+    /// [`Table`] value is not a [`table`].
+    /// Contains the value key and its actual value [`type`].
+    ///
+    /// [`Table`]: struct.DynTable.html
+    /// [`table`]: struct.DynTable.html
+    /// [`type`]: enum.ValueType.html
+    ValueNotATable(NonEmptyString, ValueType),
+    /// The key field is empty.
+    EmptyKeyField,
+}
+
+impl Error for DynRekeyError {}
+
+impl Display for DynRekeyError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DynRekeyError::*;
+
+        match self {
+            ElementNotATable(index, value_type) => write!(
+                f,
+                "array element at index {} is not a table (found {})",
+                index, value_type
+            ),
+            MissingKeyField(index) => write!(
+                f,
+                "array element at index {} does not contain the key field",
+                index
+            ),
+            InvalidKeyField(index, value_type) => write!(
+                f,
+                "key field of the array element at index {} is not a non-empty string or an integer (found {})",
+                index, value_type
+            ),
+            DuplicateKey(index, key) => write!(
+                f,
+                "duplicate key \"{}\" of the array element at index {}",
+                key, index
+            ),
+            ValueNotATable(key, value_type) => write!(
+                f,
+                "table value with key \"{}\" is not a table (found {})",
+                key, value_type
+            ),
+            EmptyKeyField => "key field is empty".fmt(f),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use {
+    crate::*,
+    std::sync::{Arc, PoisonError, RwLock},
+};
+
+/// A thread-safe handle to a shared, versioned [`dynamic config`] root [`table`].
+///
+/// Multiple threads may [`load`] the current version of the config concurrently and keep reading it
+/// without holding any locks, while a loader thread [`stores`] new versions of the config
+/// (e.g. reloaded from disk) or [`updates`] it in place.
+/// Threads which still hold the previous version keep reading it until they [`load`] the config again.
+///
+/// Cloning the handle is cheap; all clones refer to the same shared config.
+///
+/// NOTE - the shared config only keeps the root [`table`] of the [`DynConfig`],
+/// so that the loaded versions may be read without locking; its value change [`subscriptions`] are dropped.
+///
+/// [`dynamic config`]: struct.DynConfig.html
+/// [`DynConfig`]: struct.DynConfig.html
+/// [`table`]: struct.DynTable.html
+/// [`load`]: #method.load
+/// [`stores`]: #method.store
+/// [`updates`]: #method.update
+/// [`subscriptions`]: struct.DynConfig.html#method.subscribe
+#[derive(Clone)]
+pub struct SharedConfig(Arc<RwLock<SharedConfigState>>);
+
+struct SharedConfigState {
+    root: Arc<DynTable>,
+    version: u64,
+}
+
+impl SharedConfig {
+    /// Creates a new shared config from the root [`table`] of the [`config`], with version `0`.
+    ///
+    /// NOTE - the value change [`subscriptions`] of the [`config`], if any, are dropped.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`subscriptions`]: struct.DynConfig.html#method.subscribe
+    pub fn new(config: DynConfig) -> Self {
+        Self(Arc::new(RwLock::new(SharedConfigState {
+            root: Arc::new(config.0),
+            version: 0,
+        })))
+    }
+
+    /// Returns the current version of the shared config root [`table`].
+    ///
+    /// The returned [`table`] is immutable and is not affected by the following [`stores`] / [`updates`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`stores`]: #method.store
+    /// [`updates`]: #method.update
+    pub fn load(&self) -> Arc<DynTable> {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .root
+            .clone()
+    }
+
+    /// Returns the version number of the shared config, incremented on every [`store`] / [`update`].
+    ///
+    /// May be used to cheaply check whether the config changed since it was last [`loaded`].
+    ///
+    /// [`store`]: #method.store
+    /// [`update`]: #method.update
+    /// [`loaded`]: #method.load
+    pub fn version(&self) -> u64 {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .version
+    }
+
+    /// Atomically replaces the shared config root [`table`] with the root [`table`] of the new [`config`].
+    /// Returns the previous version of the root [`table`].
+    ///
+    /// NOTE - the value change [`subscriptions`] of the [`config`], if any, are dropped.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`subscriptions`]: struct.DynConfig.html#method.subscribe
+    pub fn store(&self, config: DynConfig) -> Arc<DynTable> {
+        let mut state = self.0.write().unwrap_or_else(PoisonError::into_inner);
+
+        state.version += 1;
+        std::mem::replace(&mut state.root, Arc::new(config.0))
+    }
+
+    /// Atomically modifies the shared config root [`table`] by calling `f` with the mutable reference to it.
+    /// Returns the result of `f`.
+    ///
+    /// Copy-on-write: if any thread still holds the current version of the root [`table`] returned by [`load`],
+    /// `f` modifies a copy of the root [`table`], which then replaces the current version.
+    /// Concurrent [`loads`] are blocked while `f` is running.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`load`]: #method.load
+    /// [`loads`]: #method.load
+    pub fn update<F: FnOnce(&mut DynTable) -> R, R>(&self, f: F) -> R {
+        let mut state = self.0.write().unwrap_or_else(PoisonError::into_inner);
+
+        state.version += 1;
+        f(Arc::make_mut(&mut state.root))
+    }
+}
+
+impl From<DynConfig> for SharedConfig {
+    fn from(config: DynConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr, std::thread};
+
+    fn is_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_sync() {
+        is_send_sync::<SharedConfig>();
+        is_send_sync::<DynConfig>();
+        is_send_sync::<DynTable>();
+        is_send_sync::<DynArray>();
+        #[cfg(feature = "bin")]
+        is_send_sync::<BinConfig>();
+    }
+
+    #[test]
+    fn shared_config() {
+        let mut config = DynConfig::new();
+        config.root_mut().set(nestr!("foo"), 7);
+
+        let shared = SharedConfig::new(config);
+        assert_eq!(shared.version(), 0);
+
+        let snapshot = shared.load();
+        assert_eq!(snapshot.get_i64("foo").unwrap(), 7);
+
+        // Copy-on-write - the snapshot is not affected.
+        assert!(shared.update(|root| root.set(nestr!("foo"), 9)));
+        assert_eq!(shared.version(), 1);
+        assert_eq!(snapshot.get_i64("foo").unwrap(), 7);
+        assert_eq!(shared.load().get_i64("foo").unwrap(), 9);
+
+        // Concurrent readers.
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+
+                thread::spawn(move || {
+                    let foo = shared.load().get_i64("foo").unwrap();
+                    assert!(foo == 9 || foo == 11);
+                })
+            })
+            .collect();
+
+        let mut config = DynConfig::new();
+        config.root_mut().set(nestr!("foo"), 11);
+
+        let previous = shared.store(config);
+
+        assert_eq!(previous.get_i64("foo").unwrap(), 9);
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        assert_eq!(shared.version(), 2);
+        assert_eq!(shared.load().get_i64("foo").unwrap(), 11);
+    }
+}
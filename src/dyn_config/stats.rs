@@ -0,0 +1,101 @@
+use crate::*;
+
+impl DynConfig {
+    /// Collects the [`statistics`] of the contents of the [`config`].
+    ///
+    /// [`statistics`]: struct.ConfigStats.html
+    /// [`config`]: struct.DynConfig.html
+    pub fn stats(&self) -> ConfigStats {
+        self.root().stats()
+    }
+}
+
+impl DynTable {
+    /// Collects the [`statistics`] of the contents of the [`table`],
+    /// as if it was the root table of a config.
+    ///
+    /// [`statistics`]: struct.ConfigStats.html
+    /// [`table`]: struct.DynTable.html
+    pub fn stats(&self) -> ConfigStats {
+        let mut stats = ConfigStats::new();
+
+        table_stats(self, 1, &mut stats);
+
+        stats
+    }
+}
+
+fn table_stats(table: &DynTable, depth: u32, stats: &mut ConfigStats) {
+    for (key, value) in table.iter() {
+        value_stats(Some(key.as_str()), value, depth, stats);
+    }
+}
+
+fn array_stats(array: &DynArray, depth: u32, stats: &mut ConfigStats) {
+    for value in array.iter() {
+        value_stats(None, value, depth, stats);
+    }
+}
+
+fn value_stats(
+    key: Option<&str>,
+    value: DynConfigValueRef<'_>,
+    depth: u32,
+    stats: &mut ConfigStats,
+) {
+    stats.add(key, &value, depth);
+
+    match value {
+        Value::Table(table) => table_stats(table, depth + 1, stats),
+        Value::Array(array) => array_stats(array, depth + 1, stats),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn stats() {
+        let config = DynConfig::new();
+
+        assert_eq!(config.stats(), ConfigStats::default());
+        assert_eq!(config.stats().total_values(), 0);
+
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        root.set(nestr!("foo"), 7);
+        root.set(nestr!("bar"), "hello");
+
+        let mut table = DynTable::new();
+        table.set(nestr!("baz"), 3.5);
+        table.set(nestr!("bob"), true);
+
+        let mut array = DynArray::new();
+        array.push("world".into()).unwrap();
+        array.push("!".into()).unwrap();
+        table.set(nestr!("array"), Value::Array(array));
+
+        root.set(nestr!("table"), Value::Table(table));
+
+        let stats = config.stats();
+
+        assert_eq!(stats.num_tables, 2);
+        assert_eq!(stats.num_arrays, 1);
+        assert_eq!(stats.num_bools, 1);
+        assert_eq!(stats.num_i64s, 1);
+        assert_eq!(stats.num_f64s, 1);
+        assert_eq!(stats.num_strings, 3);
+        assert_eq!(stats.num_values(ValueType::String), 3);
+        assert_eq!(stats.total_values(), 8);
+        assert_eq!(stats.max_depth, 3);
+        // "foo", "bar", "table", "baz", "bob", "array".
+        assert_eq!(stats.key_bytes, 3 + 3 + 5 + 3 + 3 + 5);
+        // "hello", "world", "!".
+        assert_eq!(stats.string_bytes, 5 + 5 + 1);
+    }
+}
@@ -0,0 +1,291 @@
+use {
+    crate::{util::*, *},
+    std::borrow::Borrow,
+};
+
+/// A token identifying a [`dynamic config`] value change subscription,
+/// returned by [`DynConfig::subscribe`] and used to [`unsubscribe`].
+///
+/// [`dynamic config`]: struct.DynConfig.html
+/// [`DynConfig::subscribe`]: struct.DynConfig.html#method.subscribe
+/// [`unsubscribe`]: struct.DynConfig.html#method.unsubscribe
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DynConfigSubscription(u32);
+
+// `Send + Sync` so that the config remains `Send + Sync`.
+type Callback = Box<dyn FnMut(&ConfigPath, Option<DynConfigValueRef<'_>>) + Send + Sync>;
+
+struct Subscription {
+    token: DynConfigSubscription,
+    path: ConfigPath,
+    callback: Callback,
+}
+
+/// Value change subscriptions of a [`dynamic config`](struct.DynConfig.html).
+#[derive(Default)]
+pub(super) struct DynConfigSubscriptions {
+    next_token: u32,
+    subscriptions: Vec<Subscription>,
+}
+
+impl DynConfigSubscriptions {
+    fn subscribe(&mut self, path: ConfigPath, callback: Callback) -> DynConfigSubscription {
+        let token = DynConfigSubscription(self.next_token);
+        self.next_token += 1;
+
+        self.subscriptions.push(Subscription {
+            token,
+            path,
+            callback,
+        });
+
+        token
+    }
+
+    fn unsubscribe(&mut self, token: DynConfigSubscription) -> bool {
+        let len = self.subscriptions.len();
+        self.subscriptions
+            .retain(|subscription| subscription.token != token);
+        self.subscriptions.len() != len
+    }
+
+    /// Calls the callbacks of all subscriptions affected by the change of the value at the `changed` path
+    /// in the `root` table, in subscription order.
+    ///
+    /// If `removed_element` is `true`, the `changed` path ends with an array index
+    /// and the array element was removed, shifting all following elements.
+    fn notify(&mut self, root: &DynTable, changed: &ConfigPath, removed_element: bool) {
+        for subscription in self.subscriptions.iter_mut() {
+            if is_affected(&subscription.path, changed, removed_element) {
+                let value = root.get_val_path(&subscription.path).ok();
+                (subscription.callback)(changed, value);
+            }
+        }
+    }
+}
+
+/// Whether the value at the subscribed `path` is affected by the change of the value at the `changed` path.
+fn is_affected(path: &ConfigPath, changed: &ConfigPath, removed_element: bool) -> bool {
+    // The value itself, its parent or its child changed.
+    if path.starts_with(changed) || changed.starts_with(path) {
+        return true;
+    }
+
+    // The value is an (element of an) array element shifted by the element removal.
+    if removed_element {
+        if let Some((OwnedConfigKey::Array(removed), array)) = changed.0.split_last() {
+            if path.0.starts_with(array) {
+                if let Some(OwnedConfigKey::Array(index)) = path.0.get(array.len()) {
+                    return index >= removed;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Converts the valid (i.e. successfully accessed) config `path` to a [`config path`](struct.ConfigPath.html).
+fn owned_path<'k, K: Borrow<ConfigKey<'k>>>(path: &[K]) -> ConfigPath {
+    path.iter()
+        .map(|key| match key.borrow() {
+            // Safe to call - the path was valid.
+            ConfigKey::Table(key) => {
+                unwrap_unchecked(NonEmptyStr::new(key.as_str()), "empty key").into()
+            }
+            ConfigKey::Array(index) => (*index).into(),
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+impl DynConfig {
+    /// Subscribes to the changes of the [`value`] at `path` made via [`set_path`] / [`remove_path`]
+    /// of this [`config`].
+    ///
+    /// The `callback` is called after the [`value`] at `path`, any of its parent [`tables`] / [`arrays`],
+    /// or any of its nested [`values`] is inserted, changed or removed,
+    /// as well as when the [`value`] is an [`array`] element (or nested in one) shifted by the removal of a preceding element.
+    /// It is passed the path of the changed [`value`] and the current [`value`] at `path`, if any.
+    ///
+    /// Returns the [`token`] which may be used to [`unsubscribe`].
+    ///
+    /// NOTE - changes made directly via the [`root table`] accessors are not reported.
+    ///
+    /// NOTE - the `callback` must be `Send + Sync`, so that the [`config`] may be sent / shared between threads.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`set_path`]: #method.set_path
+    /// [`remove_path`]: #method.remove_path
+    /// [`config`]: struct.DynConfig.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`array`]: struct.DynArray.html
+    /// [`token`]: struct.DynConfigSubscription.html
+    /// [`unsubscribe`]: #method.unsubscribe
+    /// [`root table`]: #method.root_mut
+    pub fn subscribe<P, F>(&mut self, path: P, callback: F) -> DynConfigSubscription
+    where
+        P: Into<ConfigPath>,
+        F: FnMut(&ConfigPath, Option<DynConfigValueRef<'_>>) + Send + Sync + 'static,
+    {
+        self.1.subscribe(path.into(), Box::new(callback))
+    }
+
+    /// Removes the value change subscription with the `token` returned by [`subscribe`].
+    ///
+    /// Returns `true` if the subscription existed and was removed.
+    ///
+    /// [`subscribe`]: #method.subscribe
+    pub fn unsubscribe(&mut self, token: DynConfigSubscription) -> bool {
+        self.1.unsubscribe(token)
+    }
+
+    /// Inserts or changes the [`value`] in the root [`table`] of the [`config`] at `path`
+    /// and notifies the affected [`subscribers`].
+    ///
+    /// See [`DynTable::set_path`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`subscribers`]: #method.subscribe
+    /// [`DynTable::set_path`]: struct.DynTable.html#method.set_path
+    pub fn set_path<'k, K, P, V>(&mut self, path: P, value: V) -> Result<bool, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+        V: Into<DynConfigValue>,
+    {
+        let path: Vec<_> = path.into_iter().collect();
+
+        let result = self
+            .0
+            .set_path(path.iter().map(Borrow::<ConfigKey<'k>>::borrow), value)?;
+
+        self.1.notify(&self.0, &owned_path(&path), false);
+
+        Ok(result)
+    }
+
+    /// Tries to remove the [`value`] in the root [`table`] of the [`config`] at `path`
+    /// and notifies the affected [`subscribers`].
+    /// Returns the now-removed [`value`] at `path` if it existed.
+    ///
+    /// See [`DynTable::remove_path`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`subscribers`]: #method.subscribe
+    /// [`DynTable::remove_path`]: struct.DynTable.html#method.remove_path
+    pub fn remove_path<'k, K, P>(&mut self, path: P) -> Result<DynConfigValue, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        let path: Vec<_> = path.into_iter().collect();
+
+        let value = self
+            .0
+            .remove_path(path.iter().map(Borrow::<ConfigKey<'k>>::borrow))?;
+
+        self.1.notify(&self.0, &owned_path(&path), true);
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {
+        crate::*,
+        std::sync::{Arc, Mutex},
+    };
+
+    fn is_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_sync() {
+        is_send_sync::<DynConfig>();
+        is_send_sync::<DynConfigSubscription>();
+    }
+
+    #[test]
+    fn subscribe() {
+        let mut config = DynConfig::new();
+
+        let path = |path: &str| path.parse::<ConfigPath>().unwrap();
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let mut subscribe = |subscribed: &'static str| {
+            let calls = calls.clone();
+
+            config.subscribe(
+                path(subscribed),
+                move |changed: &ConfigPath, value: Option<DynConfigValueRef<'_>>| {
+                    calls.lock().unwrap().push((
+                        subscribed,
+                        changed.clone(),
+                        value.map(|value| value.get_type()),
+                    ));
+                },
+            )
+        };
+
+        let title = subscribe("window.title");
+        subscribe("window");
+        subscribe("sizes[1]");
+
+        // Inserted value, and the parent table created on demand.
+        assert!(!config.set_path(&path("window.title"), "foo").unwrap());
+        // Unrelated value.
+        assert!(!config.set_path(&path("volume"), 0.5).unwrap());
+
+        let mut sizes = DynArray::new();
+        sizes.push(1.into()).unwrap();
+        sizes.push(2.into()).unwrap();
+        sizes.push(3.into()).unwrap();
+        assert!(!config.set_path(&path("sizes"), sizes).unwrap());
+
+        // Shifted array element.
+        assert!(matches!(
+            config.remove_path(&path("sizes[0]")).unwrap(),
+            Value::I64(1)
+        ));
+        assert_eq!(config.root().get_i64_path(&path("sizes[1]")).unwrap(), 3);
+
+        // Following array element.
+        config.remove_path(&path("sizes[1]")).unwrap();
+
+        // Unsubscribed.
+        assert!(config.unsubscribe(title));
+        assert!(!config.unsubscribe(title));
+
+        // Removed parent table.
+        config.remove_path(&path("window")).unwrap();
+
+        // Failed access.
+        assert!(config.remove_path(&path("window")).is_err());
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                (
+                    "window.title",
+                    path("window.title"),
+                    Some(ValueType::String)
+                ),
+                ("window", path("window.title"), Some(ValueType::Table)),
+                ("sizes[1]", path("sizes"), Some(ValueType::I64)),
+                ("sizes[1]", path("sizes[0]"), Some(ValueType::I64)),
+                ("sizes[1]", path("sizes[1]"), None),
+                ("window", path("window"), None),
+            ]
+        );
+    }
+}
@@ -1,26 +1,32 @@
 use {
+    super::{entry::*, hashed_key::*},
     crate::{util::*, *},
     std::{
         borrow::Borrow,
-        collections::{hash_map::Iter as HashMapIter, HashMap},
+        collections::hash_map::{IntoIter as HashMapIntoIter, Iter as HashMapIter},
         convert::TryInto,
         fmt::{Display, Formatter, Write},
-        iter::{IntoIterator, Iterator},
+        iter::{FromIterator, IntoIterator, Iterator},
+        ops::{Index, IndexMut},
+        str::FromStr,
     },
 };
 
+#[cfg(feature = "ini")]
+use super::comments::DynConfigComments;
+
 /// Represents a mutable hashmap of [`Value`]'s with (non-empty) string keys.
 ///
 /// [`Value`]: enum.Value.html
 #[derive(Clone)]
-pub struct DynTable(HashMap<NonEmptyString, DynConfigValue>);
+pub struct DynTable(pub(super) DynTableMap);
 
 impl DynTable {
     /// Creates a new empty [`table`].
     ///
     /// [`table`]: struct.DynTable.html
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self(DynTableMap::default())
     }
 
     /// Returns the number of entries in the [`table`].
@@ -82,6 +88,83 @@ impl DynTable {
         .map_err(TableError::IncorrectValueType)
     }
 
+    /// Returns `true` if the [`table`] contains a [`value`] with the string `key` and its precomputed hash
+    /// (created via the [`key!`] macro).
+    ///
+    /// Avoids runtime string hashing used internally by the [`table`] accessors.
+    ///
+    /// NOTE - requires `"str_hash"` feature.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`key!`]: macro.key.html
+    #[cfg(feature = "str_hash")]
+    pub fn contains_by_hash(&self, key: StringAndHash) -> bool {
+        self.get_val_by_hash(key).is_some()
+    }
+
+    /// Tries to get an immutable reference to a [`value`] in the [`table`] with the string `key` and its precomputed hash
+    /// (created via the [`key!`] macro).
+    ///
+    /// Avoids runtime string hashing used internally by the [`table`] accessors.
+    ///
+    /// NOTE - requires `"str_hash"` feature.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`key!`]: macro.key.html
+    #[cfg(feature = "str_hash")]
+    pub fn get_val_by_hash(&self, key: StringAndHash) -> Option<DynConfigValueRef<'_>> {
+        self.get_hashed_impl(DynTableKeyRef::new(key.string, key.hash))
+    }
+
+    /// Tries to get an immutable reference to a [`value`] in the [`table`] with the string `key` and its precomputed hash
+    /// (created via the [`key!`] macro),
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// Avoids runtime string hashing used internally by the [`table`] accessors.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`,
+    /// or if the [`value`] is of incorrect and incompatible type.
+    ///
+    /// NOTE - requires `"str_hash"` feature.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`key!`]: macro.key.html
+    /// [`error`]: enum.TableError.html
+    #[cfg(feature = "str_hash")]
+    pub fn get_by_hash<'t, V: TryFromValue<&'t str, &'t DynArray, &'t DynTable>>(
+        &'t self,
+        key: StringAndHash,
+    ) -> Result<V, TableError> {
+        V::try_from(
+            self.get_val_by_hash(key)
+                .ok_or(TableError::KeyDoesNotExist)?,
+        )
+        .map_err(TableError::IncorrectValueType)
+    }
+
+    /// Tries to get a reference to a [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key`,
+    /// or if the [`value`] is of incorrect and incompatible [`type`].
+    ///
+    /// NOTE - use [`Lenient`] as the requested type to apply lenient type coercion rules.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`type`]: enum.ValueType.html
+    /// [`Lenient`]: struct.Lenient.html
+    pub fn get_or<'t, K: AsRef<str>, V: TryFromValue<&'t str, &'t DynArray, &'t DynTable>>(
+        &'t self,
+        key: K,
+        default: V,
+    ) -> V {
+        self.get(key).unwrap_or(default)
+    }
+
     /// Tries to get an immutable reference to a [`value`] in the [`table`] at `path`.
     ///
     /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
@@ -134,6 +217,92 @@ impl DynTable {
         V::try_from(self.get_val_path(path)?).map_err(GetPathError::IncorrectValueType)
     }
 
+    /// Tries to get a reference to a [`value`] in the [`table`] at `path`,
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    ///
+    /// Returns the `default` value if the `path` is invalid,
+    /// or if the [`value`] is of incorrect and incompatible [`type`].
+    ///
+    /// NOTE - use [`Lenient`] as the requested type to apply lenient type coercion rules.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`type`]: enum.ValueType.html
+    /// [`Lenient`]: struct.Lenient.html
+    pub fn get_path_or<'t, 'k, K, P, V>(&'t self, path: P, default: V) -> V
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+        V: TryFromValue<&'t str, &'t DynArray, &'t DynTable>,
+    {
+        self.get_path(path).unwrap_or(default)
+    }
+
+    /// Tries to get an immutable reference to a [`value`] in the [`table`] at the dotted `path` string
+    /// (e.g. `"render.window.size[2]"`), [`parsed`] to a [`config path`].
+    ///
+    /// Returns an [`error`] if the `path` string could not be parsed, or if the `path` is invalid.
+    ///
+    /// See [`get_val_path`](#method.get_val_path).
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`parsed`]: struct.ConfigPath.html#impl-FromStr
+    /// [`config path`]: struct.ConfigPath.html
+    /// [`error`]: enum.GetPathError.html
+    pub fn get_val_path_str(&self, path: &str) -> Result<DynConfigValueRef<'_>, GetPathError> {
+        self.get_val_path(&path.parse::<ConfigPath>()?)
+    }
+
+    /// Tries to get an immutable reference to a [`value`] in the [`table`] at the dotted `path` string
+    /// (e.g. `"render.window.size[2]"`), [`parsed`] to a [`config path`],
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// Returns an [`error`] if the `path` string could not be parsed, if the `path` is invalid,
+    /// or if the [`value`] is of incorrect and incompatible [`type`].
+    ///
+    /// See [`get_path`](#method.get_path).
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`parsed`]: struct.ConfigPath.html#impl-FromStr
+    /// [`config path`]: struct.ConfigPath.html
+    /// [`error`]: enum.GetPathError.html
+    /// [`type`]: enum.ValueType.html
+    pub fn get_path_str<'t, V>(&'t self, path: &str) -> Result<V, GetPathError>
+    where
+        V: TryFromValue<&'t str, &'t DynArray, &'t DynTable>,
+    {
+        self.get_path(&path.parse::<ConfigPath>()?)
+    }
+
+    /// Tries to get a reference to a [`value`] in the [`table`] at the dotted `path` string
+    /// (e.g. `"render.window.size[2]"`), [`parsed`] to a [`config path`],
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// Returns the `default` value if the `path` string could not be parsed, if the `path` is invalid,
+    /// or if the [`value`] is of incorrect and incompatible [`type`].
+    ///
+    /// See [`get_path_or`](#method.get_path_or).
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`parsed`]: struct.ConfigPath.html#impl-FromStr
+    /// [`config path`]: struct.ConfigPath.html
+    /// [`type`]: enum.ValueType.html
+    pub fn get_path_str_or<'t, V>(&'t self, path: &str, default: V) -> V
+    where
+        V: TryFromValue<&'t str, &'t DynArray, &'t DynTable>,
+    {
+        self.get_path_str(path).unwrap_or(default)
+    }
+
     /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`bool`].
@@ -168,6 +337,118 @@ impl DynTable {
         self.get_path(path)
     }
 
+    /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not a [`bool`].
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    pub fn get_bool_or<K: AsRef<str>>(&self, key: K, default: bool) -> bool {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`bool`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_bool<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<bool> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::Bool,
+            self.get(key.as_ref()),
+        )
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`date/time`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime<K: AsRef<str>>(&self, key: K) -> Result<DateTime, TableError> {
+        self.get(key)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] at `path`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value.
+    /// The last key must correspond to a [`date/time`] [`value`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime_path<'k, K, P>(&self, path: P) -> Result<DateTime, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not a [`date/time`].
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    #[cfg(feature = "datetime")]
+    pub fn get_datetime_or<K: AsRef<str>>(&self, key: K, default: DateTime) -> DateTime {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`date/time`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    #[cfg(feature = "datetime")]
+    pub fn try_get_datetime<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<DateTime> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::DateTime,
+            self.get(key.as_ref()),
+        )
+    }
+
     /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`].
@@ -204,6 +485,109 @@ impl DynTable {
         self.get_path(path)
     }
 
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`].
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_i64_or<K: AsRef<str>>(&self, key: K, default: i64) -> i64 {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_i64<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<i64> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::I64,
+            self.get(key.as_ref()),
+        )
+    }
+
+    /// Tries to get a [`u32`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u32`].
+    ///
+    /// [`u32`]: https://doc.rust-lang.org/std/primitive.u32.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u32<K: AsRef<str>>(&self, key: K) -> Result<u32, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get an [`i32`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of an [`i32`].
+    ///
+    /// [`i32`]: https://doc.rust-lang.org/std/primitive.i32.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_i32<K: AsRef<str>>(&self, key: K) -> Result<i32, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u16`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u16`].
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/std/primitive.u16.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u16<K: AsRef<str>>(&self, key: K) -> Result<u16, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u8`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u8`].
+    ///
+    /// [`u8`]: https://doc.rust-lang.org/std/primitive.u8.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u8<K: AsRef<str>>(&self, key: K) -> Result<u8, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
@@ -240,6 +624,41 @@ impl DynTable {
         self.get_path(path)
     }
 
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn get_f64_or<K: AsRef<str>>(&self, key: K, default: f64) -> f64 {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_f64<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<f64> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::F64,
+            self.get(key.as_ref()),
+        )
+    }
+
     /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`string`].
@@ -274,31 +693,99 @@ impl DynTable {
         self.get_path(path)
     }
 
-    /// Tries to get an immutable reference to an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
-    /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`array`].
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not a [`string`].
     ///
-    /// [`array`]: enum.Value.html#variant.Array
+    /// [`string`]: enum.Value.html#variant.String
     /// [`value`]: type.DynConfigValue.html
     /// [`table`]: struct.DynTable.html
-    /// [`error`]: enum.TableError.html
-    pub fn get_array<K: AsRef<str>>(&self, key: K) -> Result<&DynArray, TableError> {
-        self.get(key)
+    pub fn get_string_or<'a, K: AsRef<str>>(&'a self, key: K, default: &'a str) -> &'a str {
+        self.get(key).unwrap_or(default)
     }
 
-    /// Tries to get an immutable reference to an [`array`] [`value`] in the [`table`] at `path`.
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
-    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
-    /// or (`0`-based) [`array indices`].
-    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value.
-    /// The last key must correspond to an [`array`] [`value`].
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`string`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
     ///
-    /// [`array`]: enum.Value.html#variant.Array
+    /// [`string`]: enum.Value.html#variant.String
     /// [`value`]: type.DynConfigValue.html
     /// [`table`]: struct.DynTable.html
-    /// [`config keys`]: enum.ConfigKey.html
-    /// [`table keys`]: enum.ConfigKey.html#variant.Table
-    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_string<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<&str> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::String,
+            self.get(key.as_ref()),
+        )
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as an enum value of type `T` via its [`FromStr`] implementation.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    /// [`error`]: enum.GetEnumError.html
+    pub fn get_enum<K: AsRef<str>, T: FromStr>(&self, key: K) -> Result<T, GetEnumError> {
+        let value = self.get_string(key.as_ref());
+        GetEnumError::parse(key.as_ref(), value)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and map it to an enum value of type `T` using the valid `variants` - pairs of strings and corresponding enum values.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it does not match any of the `variants` (the error then lists all valid `variants`).
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.GetEnumError.html
+    pub fn get_enum_with<K: AsRef<str>, T: Clone>(
+        &self,
+        key: K,
+        variants: &[(&str, T)],
+    ) -> Result<T, GetEnumError> {
+        let value = self.get_string(key.as_ref());
+        GetEnumError::map(key.as_ref(), value, variants)
+    }
+
+    /// Tries to get an immutable reference to an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`array`].
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    pub fn get_array<K: AsRef<str>>(&self, key: K) -> Result<&DynArray, TableError> {
+        self.get(key)
+    }
+
+    /// Tries to get an immutable reference to an [`array`] [`value`] in the [`table`] at `path`.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value.
+    /// The last key must correspond to an [`array`] [`value`].
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
     pub fn get_array_path<'k, K, P>(&self, path: P) -> Result<&DynArray, GetPathError>
     where
         K: Borrow<ConfigKey<'k>>,
@@ -307,6 +794,28 @@ impl DynTable {
         self.get_path(path)
     }
 
+    /// Tries to get an immutable reference to an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`array`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_array<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<&DynArray> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::Array,
+            self.get(key.as_ref()),
+        )
+    }
+
     /// Tries to get an immutable reference to a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`table`](enum.Value.html#variant.Table).
@@ -339,11 +848,32 @@ impl DynTable {
         self.get_path(path)
     }
 
+    /// Tries to get an immutable reference to a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`table`](enum.Value.html#variant.Table),
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_table<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<&DynTable> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::Table,
+            self.get(key.as_ref()),
+        )
+    }
+
     /// Returns an iterator over (`key`, [`value`]) pairs of the [`table`], in unspecified order.
     ///
     /// [`value`]: type.DynConfigValueRef.html
     /// [`table`]: struct.DynTable.html
-    pub fn iter(&self) -> impl Iterator<Item = (&NonEmptyStr, DynConfigValueRef<'_>)> {
+    pub fn iter(&self) -> DynTableIter<'_> {
         DynTableIter(self.0.iter())
     }
 
@@ -536,6 +1066,35 @@ impl DynTable {
         self.get_path_mut(path)
     }
 
+    /// Tries to get a mutable reference to a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// inserting a new empty [`table`](enum.Value.html#variant.Table) at `key` if the [`table`] does not contain the `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] contains the `key`, but the value is not a [`table`](enum.Value.html#variant.Table).
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    pub fn get_table_mut_or_create<K: AsRef<NonEmptyStr>>(
+        &mut self,
+        key: K,
+    ) -> Result<&mut DynTable, TableError> {
+        match self.entry(key).or_insert_with(DynTable::new) {
+            Value::Table(table) => Ok(table),
+            value => Err(TableError::IncorrectValueType(value.get_type())),
+        }
+    }
+
+    /// Returns the [`entry`] for the (non-empty) string `key` in the [`table`] for in-place manipulation,
+    /// e.g. to insert a default [`value`] at `key` if it does not exist, or to modify an existing one,
+    /// with a single lookup.
+    ///
+    /// [`entry`]: struct.DynTableEntry.html
+    /// [`table`]: struct.DynTable.html
+    /// [`value`]: type.DynConfigValue.html
+    pub fn entry<K: AsRef<NonEmptyStr>>(&mut self, key: K) -> DynTableEntry<'_> {
+        DynTableEntry(self.0.entry(DynTableKeyRef::from(key.as_ref()).into()))
+    }
+
     /// Inserts or changes the [`value`] at (non-empty) string `key`.
     /// Returns `true` if the [`value`] at `key` already existed and was modified.
     /// Returns `false` if the [`value`] at `key` did not exist and was added.
@@ -549,6 +1108,22 @@ impl DynTable {
         self.set_impl(key.as_ref(), value.into())
     }
 
+    /// Inserts or changes the [`value`] at string `key`, which may be a (non-empty) `&NonEmptyStr` or a plain `&str`.
+    /// Returns `true` if the [`value`] at `key` already existed and was modified.
+    /// Returns `false` if the [`value`] at `key` did not exist and was added.
+    ///
+    /// Returns an [`error`] if the `key` is empty.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`error`]: struct.EmptyKeyError.html
+    pub fn try_set<'k, K, V>(&mut self, key: K, value: V) -> Result<bool, EmptyKeyError>
+    where
+        K: IntoTableKey<'k>,
+        V: Into<DynConfigValue>,
+    {
+        Ok(self.set_impl(key.into_table_key()?, value.into()))
+    }
+
     /// Tries to remove the [`value`] at (non-empty) string `key`.
     /// Returns the now-removed [`value`] at `key` if it existed,
     /// otherwise returns `None`.
@@ -558,17 +1133,182 @@ impl DynTable {
         self.remove_impl(key.as_ref().try_into().ok()?)
     }
 
+    /// Retains only the [`values`] in the [`table`] for which the predicate `f`,
+    /// called with the (non-empty) string key and the [`value`], returns `true`,
+    /// removing all others in place.
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    pub fn retain<F: FnMut(&NonEmptyStr, &DynConfigValue) -> bool>(&mut self, mut f: F) {
+        self.0.retain(|key, value| f(key.key(), value))
+    }
+
+    /// Inserts or changes the [`value`] in the [`table`] at `path`.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value;
+    /// missing intermediate [`tables`] are created on demand for all [`table keys`] followed by another [`table key`](enum.ConfigKey.html#variant.Table).
+    /// The last key may be a [`table key`](enum.ConfigKey.html#variant.Table) (the [`value`] is inserted or changed),
+    /// or an [`array index`](enum.ConfigKey.html#variant.Array) (the existing [`value`] is changed).
+    ///
+    /// Returns `true` if the [`value`] at `path` already existed and was modified.
+    /// Returns `false` if the [`value`] at `path` did not exist and was added.
+    ///
+    /// Returns an [`error`] if the `path` is empty or otherwise invalid, or if the [`value`] is of invalid type for the [`array`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`tables`]: enum.Value.html#variant.Table
+    /// [`error`]: enum.GetPathError.html
+    pub fn set_path<'k, K, P, V>(&mut self, path: P, value: V) -> Result<bool, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+        V: Into<DynConfigValue>,
+    {
+        DynConfigValueMut::Table(self)
+            .set_path(path.into_iter().peekable(), value.into())
+            .map_err(GetPathError::reverse)
+    }
+
+    /// Tries to remove the [`value`] in the [`table`] at `path`.
+    /// Returns the now-removed [`value`] at `path` if it existed.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value.
+    /// Removing an [`array`] element shifts all elements after it to the left.
+    ///
+    /// Returns an [`error`] if the `path` is empty or otherwise invalid.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.GetPathError.html
+    pub fn remove_path<'k, K, P>(&mut self, path: P) -> Result<DynConfigValue, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        DynConfigValueMut::Table(self)
+            .remove_path(path.into_iter().peekable())
+            .map_err(GetPathError::reverse)
+    }
+
+    /// Inserts or changes the [`value`] in the [`table`] at the dotted `path` string
+    /// (e.g. `"render.window.size[2]"`), [`parsed`] to a [`config path`].
+    ///
+    /// Returns an [`error`] if the `path` string could not be parsed.
+    ///
+    /// See [`set_path`](#method.set_path).
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`parsed`]: struct.ConfigPath.html#impl-FromStr
+    /// [`config path`]: struct.ConfigPath.html
+    /// [`error`]: enum.GetPathError.html
+    pub fn set_path_str<V: Into<DynConfigValue>>(
+        &mut self,
+        path: &str,
+        value: V,
+    ) -> Result<bool, GetPathError> {
+        self.set_path(&path.parse::<ConfigPath>()?, value)
+    }
+
+    /// Tries to remove the [`value`] in the [`table`] at the dotted `path` string
+    /// (e.g. `"render.window.size[2]"`), [`parsed`] to a [`config path`].
+    /// Returns the now-removed [`value`] at `path` if it existed.
+    ///
+    /// Returns an [`error`] if the `path` string could not be parsed.
+    ///
+    /// See [`remove_path`](#method.remove_path).
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`parsed`]: struct.ConfigPath.html#impl-FromStr
+    /// [`config path`]: struct.ConfigPath.html
+    /// [`error`]: enum.GetPathError.html
+    pub fn remove_path_str(&mut self, path: &str) -> Result<DynConfigValue, GetPathError> {
+        self.remove_path(&path.parse::<ConfigPath>()?)
+    }
+
+    /// Converts the [`table`] of [`tables`] into an [`array`] of [`tables`], storing the key of each value
+    /// as a string `field` of the element [`table`],
+    /// e.g. `{ foo = { a = 7 }, bar = { a = 9 } }` into `[{ a = 9, id = "bar" }, { a = 7, id = "foo" }]` for the `field` `"id"`.
+    ///
+    /// This is the inverse of [`DynArray::into_table_by_key`].
+    ///
+    /// [`Array`] elements are sorted by key.
+    /// The `field` in the element [`tables`] is overwritten, if it exists.
+    ///
+    /// Returns an [`error`] if the `field` is empty or if any value is not a [`table`](enum.Value.html#variant.Table).
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    /// [`Array`]: struct.DynArray.html
+    /// [`DynArray::into_table_by_key`]: struct.DynArray.html#method.into_table_by_key
+    /// [`error`]: enum.DynRekeyError.html
+    pub fn into_array_by_key<'k, K: IntoTableKey<'k>>(
+        self,
+        field: K,
+    ) -> Result<DynArray, DynRekeyError> {
+        let field = field
+            .into_table_key()
+            .map_err(|_| DynRekeyError::EmptyKeyField)?;
+
+        let mut values: Vec<_> = self
+            .0
+            .into_iter()
+            .map(|(key, value)| (key.into_key(), value))
+            .collect();
+        values.sort_by(|(l, _), (r, _)| l.as_str().cmp(r.as_str()));
+
+        let mut array = DynArray::new();
+
+        for (key, value) in values.into_iter() {
+            let mut element = match value {
+                Value::Table(element) => element,
+                value => return Err(DynRekeyError::ValueNotATable(key, value.get_type())),
+            };
+
+            element.set(field, Value::String(key.as_str().to_owned()));
+
+            // Must succeed - all elements are tables.
+            if array.push(Value::Table(element)).is_err() {
+                debug_unreachable!("failed to push a table to an array of tables");
+            }
+        }
+
+        Ok(array)
+    }
+
     fn len_impl(&self) -> u32 {
         self.0.len() as u32
     }
 
     pub(crate) fn get_impl(&self, key: &NonEmptyStr) -> Option<DynConfigValueRef<'_>> {
-        self.0.get(key).map(|val| val.into())
+        self.get_hashed_impl(key.into())
     }
 
-    fn set_impl(&mut self, key: &NonEmptyStr, value: DynConfigValue) -> bool {
+    pub(super) fn get_hashed_impl(&self, key: DynTableKeyRef<'_>) -> Option<DynConfigValueRef<'_>> {
+        self.0.get(key.as_lookup()).map(|val| val.into())
+    }
+
+    pub(crate) fn set_impl(&mut self, key: &NonEmptyStr, value: DynConfigValue) -> bool {
+        let key = DynTableKeyRef::from(key);
+
         // Modify.
-        if let Some(cur_value) = self.0.get_mut(key) {
+        if let Some(cur_value) = self.0.get_mut(key.as_lookup()) {
             *cur_value = value;
             true
 
@@ -580,14 +1320,59 @@ impl DynTable {
     }
 
     pub(crate) fn remove_impl(&mut self, key: &NonEmptyStr) -> Option<DynConfigValue> {
-        self.0.remove(key)
+        self.0.remove(DynTableKeyRef::from(key).as_lookup())
     }
 
     pub(crate) fn get_mut_impl(&mut self, key: &NonEmptyStr) -> Option<DynConfigValueMut<'_>> {
-        self.0.get_mut(key).map(|val| val.into())
+        self.get_mut_hashed_impl(key.into())
+    }
+
+    pub(super) fn get_mut_hashed_impl(
+        &mut self,
+        key: DynTableKeyRef<'_>,
+    ) -> Option<DynConfigValueMut<'_>> {
+        self.0.get_mut(key.as_lookup()).map(|val| val.into())
+    }
+
+    pub(crate) fn extract_impl<K: ConfigPathMatch>(&self, patterns: &[&[K]]) -> Option<DynTable> {
+        let mut result = DynTable::new();
+
+        for (key, value) in self.0.iter() {
+            let patterns =
+                sub_patterns(patterns, |pattern| pattern.matches_key(key.key().as_str()));
+
+            if patterns.is_empty() {
+                continue;
+            }
+
+            if let Some(value) = extract_value(value, &patterns) {
+                result.0.insert(key.clone(), value);
+            }
+        }
+
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    pub(crate) fn retain_impl(&mut self, patterns: &[&[ConfigPathPatternKey]]) -> bool {
+        self.0.retain(|key, value| {
+            let patterns =
+                sub_patterns(patterns, |pattern| pattern.matches_key(key.key().as_str()));
+            !patterns.is_empty() && retain_value(value, &patterns)
+        });
+
+        !self.is_empty()
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
         writeln!(w, "{{")?;
 
         // Gather the keys.
@@ -596,11 +1381,14 @@ impl DynTable {
         // Sort the keys in alphabetical order.
         keys.sort();
 
+        // Width to pad the keys to, if aligned.
+        let key_width = lua_keys_width(keys.iter(), options);
+
         // Iterate the table using the sorted keys.
         for key in keys.into_iter() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            options.formatter.write_indent(w, indent + 1)?;
 
-            write_lua_key(w, key)?;
+            write_lua_key(w, key, key_width)?;
             write!(w, " = ")?;
 
             // Must succeed - all keys are valid.
@@ -611,7 +1399,7 @@ impl DynTable {
 
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            value.fmt_lua(w, indent + 1)?;
+            value.fmt_lua(w, indent + 1, options)?;
 
             write!(w, ",")?;
 
@@ -622,27 +1410,43 @@ impl DynTable {
             writeln!(w)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
+        options.formatter.write_indent(w, indent)?;
         write!(w, "}}")?;
 
         Ok(())
     }
 
+    /// Serializes the table to an `.ini` string, emitting the description `comments` of its values, if any.
     #[cfg(feature = "ini")]
-    fn fmt_ini_impl<W: Write>(
+    pub(super) fn fmt_ini_impl<W: Write>(
         &self,
         w: &mut W,
         level: u32,
-        _array: bool,
+        array: bool,
         path: &mut IniPath,
+        comments: Option<&DynConfigComments>,
         options: ToIniStringOptions,
     ) -> Result<(), ToIniStringError> {
-        debug_assert!(options.nested_sections() || level < 2);
+        debug_assert!(
+            options.nested_sections() || options.flatten_separator().is_some() || level < 2
+        );
+
+        // Comments of the values in arrays of sections are not supported.
+        let comments = if array { None } else { comments };
+
+        // Tables and arrays of tables serialized as arrays of sections are written after all other values.
+        let is_section = |value: &DynConfigValueRef<'_>| match value {
+            Value::Table(_) => true,
+            Value::Array(value) => {
+                is_ini_array_sections(value.get_val(0).ok().map(|value| value.get_type()), options)
+            }
+            _ => false,
+        };
 
         // Gather the keys.
         let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
 
-        // Sort the keys in alphabetical order, non-tables first.
+        // Sort the keys in the configured order, non-tables first.
         keys.sort_by(|l, r| {
             // Must succeed - all keys are valid.
             let l_val = unwrap_unchecked(
@@ -654,18 +1458,27 @@ impl DynTable {
                 "failed to get a value from a dyn config table with a valid key",
             );
 
-            let l_is_a_table = l_val.get_type() == ValueType::Table;
-            let r_is_a_table = r_val.get_type() == ValueType::Table;
+            let l_is_a_table = is_section(&l_val);
+            let r_is_a_table = is_section(&r_val);
 
             if !l_is_a_table && r_is_a_table {
                 std::cmp::Ordering::Less
             } else if l_is_a_table && !r_is_a_table {
                 std::cmp::Ordering::Greater
             } else {
-                l.cmp(r)
+                options.cmp_keys(l.as_str(), r.as_str())
             }
         });
 
+        // Width to pad the keys of the non-table values to, if aligned.
+        let key_width = ini_keys_width(
+            self.iter()
+                .filter(|(_, value)| !is_section(value))
+                .map(|(key, _)| key),
+            path,
+            options,
+        );
+
         let len = self.len() as usize;
 
         // Iterate the table using the sorted keys.
@@ -678,11 +1491,38 @@ impl DynTable {
                 "failed to get a value from a dyn config table with a valid key",
             );
 
+            let comment = comments.and_then(|comments| comments.get_ini(path, key));
+
             match value {
+                Value::Array(value)
+                    if is_ini_array_sections(
+                        value.get_val(0).ok().map(|value| value.get_type()),
+                        options,
+                    ) =>
+                {
+                    write_ini_array_sections(
+                        w,
+                        key,
+                        comment,
+                        key_index as u32,
+                        value.iter().filter_map(Value::table).map(|table| {
+                            let len = table.len();
+                            (table, len)
+                        }),
+                        value.len() as usize,
+                        last,
+                        level,
+                        array,
+                        path,
+                        options,
+                    )?;
+                }
                 Value::Array(value) => {
                     write_ini_array(
                         w,
                         key,
+                        comment,
+                        key_width,
                         value.iter(),
                         value.len() as usize,
                         last,
@@ -692,25 +1532,30 @@ impl DynTable {
                     )?;
                 }
                 Value::Table(value) => {
-                    let has_non_tables = value
-                        .iter()
-                        .any(|(_, val)| val.get_type() != ValueType::Table);
+                    let has_non_tables = value.iter().any(|(_, val)| !is_section(&val));
 
                     write_ini_table(
                         w,
                         key,
+                        comment,
                         key_index as u32,
-                        value,
+                        &DynTableIni {
+                            table: value,
+                            comments,
+                        },
                         value.len(),
                         has_non_tables,
                         last,
                         level,
+                        array,
                         path,
                         options,
                     )?;
                 }
                 value => {
-                    write_ini_value(w, key, &value, last, level, false, path, options)?;
+                    write_ini_value(
+                        w, key, comment, key_width, &value, last, level, false, path, options,
+                    )?;
                 }
             }
         }
@@ -723,7 +1568,14 @@ impl DynTable {
 ///
 /// [`value`]: type.DynConfigValue.html
 /// [`table`]: struct.DynTable.html
-struct DynTableIter<'t>(HashMapIter<'t, NonEmptyString, DynConfigValue>);
+/// An iterator over the (non-empty) string keys and [`values`] of a [`table`], in unspecified order.
+///
+/// Returned by [`DynTable::iter`] and by iterating over a [`table`] reference.
+///
+/// [`values`]: type.DynConfigValueRef.html
+/// [`table`]: struct.DynTable.html
+/// [`DynTable::iter`]: struct.DynTable.html#method.iter
+pub struct DynTableIter<'t>(HashMapIter<'t, DynTableKey, DynConfigValue>);
 
 impl<'t> Iterator for DynTableIter<'t> {
     type Item = (&'t NonEmptyStr, DynConfigValueRef<'t>);
@@ -737,54 +1589,195 @@ impl<'t> Iterator for DynTableIter<'t> {
                 Value::String(value) => Value::String(value.as_str()),
                 Value::Array(value) => Value::Array(value),
                 Value::Table(value) => Value::Table(value),
+                #[cfg(feature = "datetime")]
+                Value::DateTime(value) => Value::DateTime(*value),
             };
 
-            // Safe to call - we validated the key.
-            Some((
-                unwrap_unchecked(NonEmptyStr::new(key.as_ref()), "empty key"),
-                value,
-            ))
+            Some((key.key(), value))
         } else {
             None
         }
     }
 }
 
-impl DisplayLua for DynTable {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+/// Returns a reference to the [`value`] in the [`table`] with the (non-empty) string `key`.
+///
+/// # Panics
+///
+/// Panics if the [`table`] does not contain the `key`.
+/// Use [`DynTable::get_val`] to handle this case.
+///
+/// [`value`]: type.DynConfigValue.html
+/// [`table`]: struct.DynTable.html
+/// [`DynTable::get_val`]: struct.DynTable.html#method.get_val
+impl<K: AsRef<str>> Index<K> for DynTable {
+    type Output = DynConfigValue;
+
+    fn index(&self, key: K) -> &Self::Output {
+        let key = key.as_ref();
+
+        NonEmptyStr::new(key)
+            .and_then(|key| self.0.get(DynTableKeyRef::from(key).as_lookup()))
+            .unwrap_or_else(|| panic!("key \"{}\" does not exist in the table", key))
     }
 }
 
-impl<'t> DisplayLua for &'t DynTable {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+/// Returns a mutable reference to the [`value`] in the [`table`] with the (non-empty) string `key`.
+///
+/// # Panics
+///
+/// Panics if the [`table`] does not contain the `key`.
+/// Use [`DynTable::entry`] / [`DynTable::set`] to insert new [`values`].
+///
+/// [`value`]: type.DynConfigValue.html
+/// [`values`]: type.DynConfigValue.html
+/// [`table`]: struct.DynTable.html
+/// [`DynTable::entry`]: struct.DynTable.html#method.entry
+/// [`DynTable::set`]: struct.DynTable.html#method.set
+impl<K: AsRef<str>> IndexMut<K> for DynTable {
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        let key = key.as_ref();
+
+        match NonEmptyStr::new(key)
+            .and_then(move |key| self.0.get_mut(DynTableKeyRef::from(key).as_lookup()))
+        {
+            Some(value) => value,
+            None => panic!("key \"{}\" does not exist in the table", key),
+        }
     }
 }
 
-impl<'t> DisplayLua for &'t mut DynTable {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+impl<'t> IntoIterator for &'t DynTable {
+    type Item = (&'t NonEmptyStr, DynConfigValueRef<'t>);
+    type IntoIter = DynTableIter<'t>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DynTableIter(self.0.iter())
     }
 }
 
-#[cfg(feature = "ini")]
-impl DisplayIni for DynTable {
-    fn fmt_ini<W: Write>(
-        &self,
-        w: &mut W,
-        level: u32,
-        array: bool,
-        path: &mut IniPath,
-        options: ToIniStringOptions,
-    ) -> Result<(), ToIniStringError> {
-        self.fmt_ini_impl(w, level, array, path, options)
+/// An iterator over the owned (non-empty) string keys and [`values`] of a [`table`], in unspecified order.
+///
+/// Returned by iterating over a [`table`] by value.
+///
+/// [`values`]: type.DynConfigValue.html
+/// [`table`]: struct.DynTable.html
+pub struct DynTableIntoIter(HashMapIntoIter<DynTableKey, DynConfigValue>);
+
+impl Iterator for DynTableIntoIter {
+    type Item = (NonEmptyString, DynConfigValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key.into_key(), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
     }
 }
 
-#[cfg(feature = "ini")]
-impl<'t> DisplayIni for &'t DynTable {
-    fn fmt_ini<W: Write>(
+impl IntoIterator for DynTable {
+    type Item = (NonEmptyString, DynConfigValue);
+    type IntoIter = DynTableIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DynTableIntoIter(self.0.into_iter())
+    }
+}
+
+/// Creates a [`table`] from (non-empty) string keys and [`values`].
+/// If a key is repeated, the last [`value`] with this key is kept.
+///
+/// [`table`]: struct.DynTable.html
+/// [`values`]: type.DynConfigValue.html
+/// [`value`]: type.DynConfigValue.html
+impl<K, V> FromIterator<(K, V)> for DynTable
+where
+    K: AsRef<NonEmptyStr>,
+    V: Into<DynConfigValue>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut table = DynTable::new();
+
+        for (key, value) in iter {
+            table.set(key, value);
+        }
+
+        table
+    }
+}
+
+impl DisplayLua for DynTable {
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
+    }
+}
+
+impl<'t> DisplayLua for &'t DynTable {
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
+    }
+}
+
+impl<'t> DisplayLua for &'t mut DynTable {
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "ini")]
+impl DisplayIni for DynTable {
+    fn fmt_ini<W: Write>(
+        &self,
+        w: &mut W,
+        level: u32,
+        array: bool,
+        path: &mut IniPath,
+        options: ToIniStringOptions,
+    ) -> Result<(), ToIniStringError> {
+        self.fmt_ini_impl(w, level, array, path, None, options)
+    }
+}
+
+#[cfg(feature = "ini")]
+impl<'t> DisplayIni for &'t DynTable {
+    fn fmt_ini<W: Write>(
+        &self,
+        w: &mut W,
+        level: u32,
+        array: bool,
+        path: &mut IniPath,
+        options: ToIniStringOptions,
+    ) -> Result<(), ToIniStringError> {
+        self.fmt_ini_impl(w, level, array, path, None, options)
+    }
+}
+
+/// A [`table`](struct.DynTable.html) serialized to an `.ini` string with the description comments of its config.
+#[cfg(feature = "ini")]
+pub(super) struct DynTableIni<'t> {
+    pub(super) table: &'t DynTable,
+    pub(super) comments: Option<&'t DynConfigComments>,
+}
+
+#[cfg(feature = "ini")]
+impl<'t> DisplayIni for DynTableIni<'t> {
+    fn fmt_ini<W: Write>(
         &self,
         w: &mut W,
         level: u32,
@@ -792,13 +1785,14 @@ impl<'t> DisplayIni for &'t DynTable {
         path: &mut IniPath,
         options: ToIniStringOptions,
     ) -> Result<(), ToIniStringError> {
-        self.fmt_ini_impl(w, level, array, path, options)
+        self.table
+            .fmt_ini_impl(w, level, array, path, self.comments, options)
     }
 }
 
 impl Display for DynTable {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, Default::default())
     }
 }
 
@@ -855,6 +1849,556 @@ mod tests {
         assert!(!table.contains("bar"));
     }
 
+    #[test]
+    fn get_or() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set(nestr!("bool"), true));
+        assert!(!table.set(nestr!("int"), 7));
+        assert!(!table.set(nestr!("string"), Value::String("false".into())));
+
+        let mut nested = DynTable::new();
+        assert!(!nested.set(nestr!("float"), 3.14));
+        assert!(!table.set(nestr!("table"), Value::Table(nested)));
+
+        assert_eq!(table.get_bool_or("bool", false), true);
+        assert_eq!(table.get_bool_or("missing", true), true);
+        assert_eq!(table.get_bool_or("int", true), true);
+
+        assert_eq!(table.get_i64_or("int", 9), 7);
+        assert!(cmp_f64(table.get_f64_or("int", 9.0), 7.0));
+        assert_eq!(table.get_i64_or("string", 9), 9);
+
+        assert_eq!(table.get_string_or("string", "foo"), "false");
+        assert_eq!(table.get_string_or("bool", "foo"), "foo");
+
+        assert_eq!(table.get_or("int", 9), 7);
+        assert_eq!(table.get_or("missing", 9), 9);
+
+        assert!(cmp_f64(
+            table.get_path_or(&["table".into(), "float".into()], 7.62),
+            3.14
+        ));
+        assert!(cmp_f64(
+            table.get_path_or(&["table".into(), "missing".into()], 7.62),
+            7.62
+        ));
+        assert_eq!(table.get_path_or(&["bool".into(), "bool".into()], 9), 9);
+
+        // Lenient coercion.
+        assert_eq!(table.get_bool_or("string", true), true);
+        assert_eq!(table.get_or("string", Lenient(true)), Lenient(false));
+        assert_eq!(table.get_or("bool", Lenient(false)), Lenient(true));
+        assert_eq!(table.get_or("int", Lenient(false)), Lenient(false));
+        assert_eq!(table.get_or("int", Lenient(3.14)), Lenient(7.0));
+    }
+
+    #[test]
+    fn try_get() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set(nestr!("bool"), true));
+        assert!(!table.set(nestr!("int"), 7));
+        assert!(!table.set(nestr!("string"), Value::String("foo".into())));
+        assert!(!table.set(nestr!("table"), Value::Table(DynTable::new())));
+
+        let diagnostics = ConfigDiagnostics::new();
+
+        assert_eq!(table.try_get_bool("bool", &diagnostics), Some(true));
+        assert!(cmp_f64(
+            table.try_get_f64("int", &diagnostics).unwrap(),
+            7.0
+        ));
+        assert_eq!(table.try_get_string("string", &diagnostics), Some("foo"));
+        assert!(table
+            .try_get_table("table", &diagnostics)
+            .unwrap()
+            .is_empty());
+        assert!(diagnostics.is_empty());
+
+        assert_eq!(table.try_get_i64("missing", &diagnostics), None);
+        assert_eq!(table.try_get_i64("string", &diagnostics), None);
+        assert!(table.try_get_array("table", &diagnostics).is_none());
+        // No diagnostics sink.
+        assert_eq!(table.try_get_bool("missing", None), None);
+
+        assert_eq!(diagnostics.len(), 3);
+
+        let diagnostics = diagnostics.take();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                ConfigDiagnostic {
+                    path: vec![nestr!("missing").into()].into(),
+                    expected: ValueType::I64,
+                    found: None,
+                },
+                ConfigDiagnostic {
+                    path: vec![nestr!("string").into()].into(),
+                    expected: ValueType::I64,
+                    found: Some(ValueType::String),
+                },
+                ConfigDiagnostic {
+                    path: vec![nestr!("table").into()].into(),
+                    expected: ValueType::Array,
+                    found: Some(ValueType::Table),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn try_set() {
+        let mut table = DynTable::new();
+
+        assert_eq!(table.try_set("foo", true), Ok(false));
+        assert_eq!(table.try_set(&String::from("foo"), 7), Ok(true));
+        assert_eq!(table.try_set(nestr!("bar"), 3.5), Ok(false));
+        assert_eq!(table.try_set("", 9), Err(EmptyKeyError));
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get_i64("foo").unwrap(), 7);
+    }
+
+    #[test]
+    fn iterators() {
+        let mut array = DynArray::new();
+        array.push(7.into()).unwrap();
+
+        let table: DynTable = vec![
+            (nestr!("foo").to_owned(), DynConfigValue::from(true)),
+            (nestr!("bar").to_owned(), "bar".into()),
+            (nestr!("baz").to_owned(), Value::Array(array)),
+            (nestr!("foo").to_owned(), false.into()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(table.len(), 3);
+        assert!(!table.get_bool("foo").unwrap());
+
+        let mut keys = Vec::new();
+
+        for (key, value) in &table {
+            keys.push(key.as_str());
+
+            if key == "baz" {
+                assert_eq!(value.array().unwrap().len(), 1);
+            }
+        }
+
+        keys.sort_unstable();
+        assert_eq!(keys, vec!["bar", "baz", "foo"]);
+
+        let copy: DynTable = table
+            .clone()
+            .into_iter()
+            .filter(|(key, _)| key.as_str() != "bar")
+            .collect();
+
+        assert_eq!(copy.len(), 2);
+        assert!(!copy.contains("bar"));
+        assert_eq!(copy.get_array("baz").unwrap().get_i64(0).unwrap(), 7);
+    }
+
+    #[test]
+    fn index() {
+        let mut table = DynTable::new();
+        table.set(nestr!("foo"), 7);
+        table.set(nestr!("bar"), Value::Table(DynTable::new()));
+
+        assert_eq!(table["foo"].i64().unwrap(), 7);
+        assert_eq!(table[nestr!("foo")].i64().unwrap(), 7);
+
+        table["foo"] = "foo".into();
+        assert_eq!(table.get_string("foo").unwrap(), "foo");
+
+        if let Value::Table(bar) = &mut table["bar"] {
+            bar.set(nestr!("baz"), true);
+        }
+        assert!(table.get_bool_path(&["bar".into(), "baz".into()]).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_missing_key() {
+        let table = DynTable::new();
+        let _ = &table["foo"];
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_empty_key() {
+        let mut table = DynTable::new();
+        table[""] = 7.into();
+    }
+
+    #[test]
+    fn retain() {
+        let mut table = DynTable::new();
+
+        table.set(nestr!("foo"), 7);
+        table.set(nestr!("bar"), 9);
+        table.set(nestr!("editor_only"), true);
+        table.set(nestr!("editor_grid"), Value::Table(DynTable::new()));
+
+        table.retain(|key, _| !key.as_str().starts_with("editor_"));
+
+        assert_eq!(table.len(), 2);
+        assert!(table.contains("foo"));
+        assert!(table.contains("bar"));
+
+        table.retain(|_, value| value.i64().unwrap() > 7);
+
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get_i64("bar").unwrap(), 9);
+    }
+
+    #[test]
+    fn entry() {
+        let mut table = DynTable::new();
+
+        let entry = table.entry(nestr!("foo"));
+        assert_eq!(entry.key(), nestr!("foo"));
+        assert!(!entry.is_occupied());
+        *entry.or_insert(7) = Value::I64(9);
+        assert_eq!(table.get_i64("foo").unwrap(), 9);
+
+        // Occupied entries are not overwritten.
+        table.entry(nestr!("foo")).or_insert_with(|| 3.5);
+        assert_eq!(table.get_i64("foo").unwrap(), 9);
+
+        for _ in 0..3 {
+            table
+                .entry(nestr!("count"))
+                .and_modify(|value| *value = Value::I64(value.i64().unwrap() + 1))
+                .or_insert(1);
+        }
+        assert_eq!(table.get_i64("count").unwrap(), 3);
+
+        table
+            .get_table_mut_or_create(nestr!("bar"))
+            .unwrap()
+            .get_table_mut_or_create(nestr!("baz"))
+            .unwrap()
+            .set(nestr!("bob"), true);
+        table
+            .get_table_mut_or_create(nestr!("bar"))
+            .unwrap()
+            .set(nestr!("bill"), "bill");
+
+        let bar = table.get_table("bar").unwrap();
+        assert_eq!(bar.len(), 2);
+        assert_eq!(bar.get_string("bill").unwrap(), "bill");
+        assert!(bar.get_table("baz").unwrap().get_bool("bob").unwrap());
+
+        assert_eq!(
+            table.get_table_mut_or_create(nestr!("foo")).err().unwrap(),
+            TableError::IncorrectValueType(ValueType::I64)
+        );
+    }
+
+    #[cfg(feature = "str_hash")]
+    #[test]
+    fn get_by_hash() {
+        let mut table = DynTable::new();
+
+        let mut nested = DynTable::new();
+        nested.set(nestr!("bar"), 7);
+
+        table.set(nestr!("foo"), nested);
+        table.set(nestr!("baz"), "qux");
+
+        assert!(table.contains_by_hash(key!("foo")));
+        assert!(!table.contains_by_hash(key!("bar")));
+
+        assert_eq!(table.get_by_hash::<&str>(key!("baz")).unwrap(), "qux");
+        assert_eq!(
+            table.get_by_hash::<bool>(key!("baz")).err().unwrap(),
+            TableError::IncorrectValueType(ValueType::String)
+        );
+        assert_eq!(
+            table.get_by_hash::<bool>(key!("bar")).err().unwrap(),
+            TableError::KeyDoesNotExist
+        );
+
+        // Precomputed hashes are used by the path accessors.
+        assert_eq!(
+            table
+                .get_i64_path(&[key!("foo").into(), key!("bar").into()])
+                .unwrap(),
+            7
+        );
+        assert_eq!(
+            table
+                .get_i64_path(&[ConfigKey::from("foo"), ConfigKey::from("bar")])
+                .unwrap(),
+            7
+        );
+
+        // Keys are still found after removal / reinsertion.
+        table.remove("baz").unwrap();
+        assert!(!table.contains_by_hash(key!("baz")));
+        table.set(nestr!("baz"), true);
+        assert!(table.get_by_hash::<bool>(key!("baz")).unwrap());
+    }
+
+    #[test]
+    fn set_path_remove_path() {
+        let mut table = DynTable::new();
+
+        // Intermediate tables are created on demand.
+        assert!(!table
+            .set_path(&"render.window.title".parse::<ConfigPath>().unwrap(), "foo")
+            .unwrap());
+        assert_eq!(
+            table
+                .get_string_path(&"render.window.title".parse::<ConfigPath>().unwrap())
+                .unwrap(),
+            "foo"
+        );
+
+        let mut size = DynArray::new();
+        size.push(Value::I64(640)).unwrap();
+        size.push(Value::I64(480)).unwrap();
+
+        assert!(!table
+            .set_path(&["render".into(), "window".into(), "size".into()], size)
+            .unwrap());
+
+        // Modify an array element.
+        let path: ConfigPath = "render.window.size[1]".parse().unwrap();
+
+        assert_eq!(table.get_i64_path(&path).unwrap(), 480);
+        assert!(table.set_path(&path, 720).unwrap());
+        assert_eq!(table.get_i64_path(&path).unwrap(), 720);
+
+        assert_eq!(
+            table
+                .set_path(&"render.window.size[2]".parse::<ConfigPath>().unwrap(), 7)
+                .err()
+                .unwrap(),
+            GetPathError::IndexOutOfBounds {
+                path: vec![
+                    nestr!("render").into(),
+                    nestr!("window").into(),
+                    nestr!("size").into(),
+                    2.into()
+                ]
+                .into(),
+                len: 2
+            }
+        );
+        assert_eq!(
+            table.set_path(&path, true).err().unwrap(),
+            GetPathError::IncorrectValueType(ValueType::I64)
+        );
+
+        // Missing intermediate arrays are not created.
+        assert_eq!(
+            table
+                .set_path(&"render.views[0].fov".parse::<ConfigPath>().unwrap(), 90)
+                .err()
+                .unwrap(),
+            GetPathError::KeyDoesNotExist(
+                vec![nestr!("render").into(), nestr!("views").into()].into()
+            )
+        );
+        assert_eq!(
+            table
+                .set_path(&"render.window.title.foo".parse::<ConfigPath>().unwrap(), 7)
+                .err()
+                .unwrap(),
+            GetPathError::ValueNotATable {
+                path: vec![
+                    nestr!("render").into(),
+                    nestr!("window").into(),
+                    nestr!("title").into()
+                ]
+                .into(),
+                value_type: ValueType::String
+            }
+        );
+        assert_eq!(
+            table.set_path(&ConfigPath::new(), 7).err().unwrap(),
+            GetPathError::EmptyPath
+        );
+
+        // Remove an array element.
+        assert_eq!(
+            table
+                .remove_path(&"render.window.size[0]".parse::<ConfigPath>().unwrap())
+                .unwrap()
+                .i64()
+                .unwrap(),
+            640
+        );
+        assert_eq!(
+            table
+                .get_i64_path(&"render.window.size[0]".parse::<ConfigPath>().unwrap())
+                .unwrap(),
+            720
+        );
+        assert_eq!(
+            table
+                .remove_path(&"render.window.size[1]".parse::<ConfigPath>().unwrap())
+                .err()
+                .unwrap(),
+            GetPathError::IndexOutOfBounds {
+                path: vec![
+                    nestr!("render").into(),
+                    nestr!("window").into(),
+                    nestr!("size").into(),
+                    1.into()
+                ]
+                .into(),
+                len: 1
+            }
+        );
+
+        // Remove a table value.
+        assert_eq!(
+            table
+                .remove_path(&["render".into(), "window".into(), "title".into()])
+                .unwrap()
+                .string()
+                .unwrap(),
+            "foo"
+        );
+        assert_eq!(
+            table
+                .remove_path(&["render".into(), "window".into(), "title".into()])
+                .err()
+                .unwrap(),
+            GetPathError::KeyDoesNotExist(
+                vec![
+                    nestr!("render").into(),
+                    nestr!("window").into(),
+                    nestr!("title").into()
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            table.remove_path(&ConfigPath::new()).err().unwrap(),
+            GetPathError::EmptyPath
+        );
+
+        assert!(table
+            .remove_path(&["render".into()])
+            .unwrap()
+            .table()
+            .is_some());
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn path_str() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set_path_str("render.window.title", "foo").unwrap());
+        assert!(!table
+            .set_path_str("render.window.size", DynArray::new())
+            .unwrap());
+        table
+            .get_val_path_str("render.window.size")
+            .unwrap()
+            .array()
+            .unwrap();
+
+        let mut size = DynArray::new();
+        size.push(Value::I64(640)).unwrap();
+        size.push(Value::I64(480)).unwrap();
+        assert!(table.set_path_str("render.window.size", size).unwrap());
+
+        assert_eq!(
+            table.get_path_str::<&str>("render.window.title").unwrap(),
+            "foo"
+        );
+        assert_eq!(
+            table.get_path_str::<i64>("render.window.size[1]").unwrap(),
+            480
+        );
+        assert!(table.set_path_str("render.window.size[1]", 720).unwrap());
+        assert_eq!(table.get_path_str_or("render.window.size[1]", 0), 720);
+        assert_eq!(table.get_path_str_or("render.window.size[2]", 0), 0);
+        assert_eq!(table.get_path_str_or("render.window.size[", 0), 0);
+
+        // Path errors.
+        assert_eq!(
+            table
+                .get_path_str::<i64>("render.window.size[2]")
+                .err()
+                .unwrap(),
+            GetPathError::IndexOutOfBounds {
+                path: vec![
+                    nestr!("render").into(),
+                    nestr!("window").into(),
+                    nestr!("size").into(),
+                    2.into()
+                ]
+                .into(),
+                len: 2
+            }
+        );
+        assert_eq!(
+            table
+                .get_path_str::<bool>("render.window.title")
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::String)
+        );
+
+        // Parse errors.
+        assert_eq!(
+            table.get_val_path_str("render..window").err().unwrap(),
+            GetPathError::InvalidPath(ParseConfigPathError::EmptyKey(7))
+        );
+        assert_eq!(
+            table
+                .get_path_str::<i64>("render.window.size[x]")
+                .err()
+                .unwrap(),
+            GetPathError::InvalidPath(ParseConfigPathError::InvalidIndex(18))
+        );
+        assert_eq!(
+            table.set_path_str("render.window]", 7).err().unwrap(),
+            GetPathError::InvalidPath(ParseConfigPathError::UnexpectedCharacter(13, ']'))
+        );
+        assert_eq!(
+            table.remove_path_str("render\\\\").err().unwrap(),
+            GetPathError::KeyDoesNotExist(vec![nestr!("render\\").into()].into())
+        );
+        assert_eq!(
+            table.remove_path_str("render\\x").err().unwrap(),
+            GetPathError::InvalidPath(ParseConfigPathError::InvalidEscapeSequence(6))
+        );
+
+        // The empty string is the empty path.
+        assert!(table.get_val_path_str("").unwrap().table().is_some());
+        assert_eq!(
+            table.remove_path_str("").err().unwrap(),
+            GetPathError::EmptyPath
+        );
+
+        assert_eq!(
+            table
+                .remove_path_str("render.window.size[0]")
+                .unwrap()
+                .i64()
+                .unwrap(),
+            640
+        );
+        assert_eq!(
+            table.get_path_str::<i64>("render.window.size[0]").unwrap(),
+            720
+        );
+
+        assert!(table.remove_path_str("render").unwrap().table().is_some());
+        assert!(table.is_empty());
+    }
+
     #[test]
     fn DynTableError_KeyDoesNotExist() {
         let mut table = DynTable::new();
@@ -1065,6 +2609,102 @@ mod tests {
         assert_eq!(arne.get_i64(3).unwrap(), 4);
     }
 
+    #[test]
+    fn DynTableError_ValueOutOfRange() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set(nestr!("foo"), 7u8));
+        assert!(!table.set(nestr!("bar"), -1));
+        assert!(!table.set(nestr!("baz"), u32::MAX));
+        assert!(!table.set(nestr!("bob"), 3.0));
+
+        assert_eq!(table.get_u8("foo").unwrap(), 7);
+        assert_eq!(table.get_u16("foo").unwrap(), 7);
+        assert_eq!(table.get_i32("foo").unwrap(), 7);
+        assert_eq!(table.get_u32("foo").unwrap(), 7);
+
+        assert_eq!(table.get_i32("bar").unwrap(), -1);
+        assert_eq!(
+            table.get_u32("bar").err().unwrap(),
+            TableError::ValueOutOfRange(-1)
+        );
+
+        assert_eq!(table.get_u32("baz").unwrap(), u32::MAX);
+        assert_eq!(
+            table.get_i32("baz").err().unwrap(),
+            TableError::ValueOutOfRange(u32::MAX as i64)
+        );
+        assert_eq!(
+            table.get_u16("baz").err().unwrap(),
+            TableError::ValueOutOfRange(u32::MAX as i64)
+        );
+
+        // Integral floats are converted.
+        assert_eq!(table.get_u8("bob").unwrap(), 3);
+
+        assert_eq!(
+            table.get_u8("missing").err().unwrap(),
+            TableError::KeyDoesNotExist
+        );
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    enum Quality {
+        Low,
+        High,
+    }
+
+    #[test]
+    fn get_enum() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set(nestr!("foo"), "high"));
+        assert!(!table.set(nestr!("bar"), "ultra"));
+        assert!(!table.set(nestr!("baz"), 7));
+        assert!(!table.set(nestr!("bob"), "true"));
+
+        let variants = [("low", Quality::Low), ("high", Quality::High)];
+
+        assert_eq!(
+            table.get_enum_with("foo", &variants).unwrap(),
+            Quality::High
+        );
+
+        let err = table.get_enum_with("bar", &variants).err().unwrap();
+        assert_eq!(
+            err,
+            GetEnumError::InvalidVariant {
+                path: vec![nestr!("bar").into()].into(),
+                value: "ultra".to_owned(),
+                variants: vec!["low".to_owned(), "high".to_owned()],
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "value \"ultra\" at bar is not a valid enum variant (expected one of \"low\", \"high\")"
+        );
+
+        assert_eq!(
+            table.get_enum_with("baz", &variants).err().unwrap(),
+            GetEnumError::Table(TableError::IncorrectValueType(ValueType::I64))
+        );
+        assert_eq!(
+            table.get_enum_with("missing", &variants).err().unwrap(),
+            GetEnumError::Table(TableError::KeyDoesNotExist)
+        );
+
+        // `FromStr`.
+        assert!(table.get_enum::<_, bool>("bob").unwrap());
+        assert_eq!(
+            table.get_enum::<_, bool>("foo").err().unwrap(),
+            GetEnumError::InvalidVariant {
+                path: vec![nestr!("foo").into()].into(),
+                value: "high".to_owned(),
+                variants: Vec::new(),
+            }
+        );
+    }
+
     #[test]
     fn basic() {
         // Create an empty table.
@@ -0,0 +1,513 @@
+use {
+    super::diff::to_owned_value,
+    crate::*,
+    std::{
+        collections::{BTreeMap, BTreeSet},
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// A map of named parameter values a [`config template`] is [`instantiated`] with.
+///
+/// [`config template`]: struct.DynConfig.html#method.template_params
+/// [`instantiated`]: struct.DynConfig.html#method.instantiate
+#[derive(Clone, Default)]
+pub struct DynTemplateParams(BTreeMap<String, DynConfigValue>);
+
+impl DynTemplateParams {
+    /// Creates a new empty parameter map.
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Returns the number of parameters in the map.
+    pub fn len(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    /// Returns `true` if the map contains no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Inserts or changes the [`value`] of the parameter with (non-empty) string `name`.
+    /// Returns `true` if the parameter already existed and was modified.
+    /// Returns `false` if the parameter did not exist and was added.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    pub fn set<N, V>(&mut self, name: N, value: V) -> bool
+    where
+        N: AsRef<NonEmptyStr>,
+        V: Into<DynConfigValue>,
+    {
+        self.0
+            .insert(name.as_ref().as_str().to_owned(), value.into())
+            .is_some()
+    }
+
+    /// Returns the [`value`] of the parameter with string `name`, if any.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    pub fn get<N: AsRef<str>>(&self, name: N) -> Option<DynConfigValueRef<'_>> {
+        self.0.get(name.as_ref()).map(value_ref)
+    }
+
+    /// Tries to remove the parameter with string `name`.
+    /// Returns its now-removed [`value`] if it existed, otherwise returns `None`.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    pub fn remove<N: AsRef<str>>(&mut self, name: N) -> Option<DynConfigValue> {
+        self.0.remove(name.as_ref())
+    }
+}
+
+impl DynConfig {
+    /// Returns the names of all parameters declared by this [`config`] used as a template,
+    /// i.e. the parameter names of all placeholders in its string values.
+    ///
+    /// A placeholder is a `$` followed by the parameter name (ASCII alphanumeric characters and underscores),
+    /// or a parameter name enclosed in `${` and `}`. `$$` is a literal `$`;
+    /// a `$` not followed by a parameter name / `{` / `$` is a literal `$` as well.
+    ///
+    /// Returns an [`InvalidPlaceholder`] error if a string value contains a `${` without the closing `}`,
+    /// or with an empty or invalid parameter name.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`InvalidPlaceholder`]: enum.DynTemplateError.html#variant.InvalidPlaceholder
+    pub fn template_params(&self) -> Result<BTreeSet<NonEmptyString>, DynTemplateError> {
+        let mut params = BTreeSet::new();
+        let mut path = ConfigPath::new();
+
+        template_params_table(&self.0, &mut path, &mut params)?;
+
+        Ok(params)
+    }
+
+    /// Uses this [`config`] as a template and returns a new concrete [`config`],
+    /// with all [`placeholders`] in its string values substituted with the values of the `params`.
+    ///
+    /// A string value which consists of a single placeholder is replaced with the parameter value of any type,
+    /// including [`tables`] and [`arrays`]. Placeholders embedded in longer strings
+    /// are replaced with the string representation of the parameter value,
+    /// which must not be a [`table`] / [`array`] in this case.
+    ///
+    /// Parameter values substituted as [`array`] elements must be of the same type as the other elements.
+    ///
+    /// Parameters not used by the template are ignored.
+    ///
+    /// Returns an [`error`] if a placeholder is invalid, if a parameter is missing from the `params`,
+    /// or if a parameter value type is invalid.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`placeholders`]: #method.template_params
+    /// [`tables`]: struct.DynTable.html
+    /// [`table`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.DynTemplateError.html
+    pub fn instantiate(&self, params: &DynTemplateParams) -> Result<DynConfig, DynTemplateError> {
+        let mut path = ConfigPath::new();
+
+        Ok(Self(
+            instantiate_table(&self.0, params, &mut path)?,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        ))
+    }
+}
+
+/// An error returned when [`instantiating`] a [`config`] template.
+///
+/// [`instantiating`]: struct.DynConfig.html#method.instantiate
+/// [`config`]: struct.DynConfig.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum DynTemplateError {
+    /// A string value contains an invalid placeholder.
+    /// Contains the path to the string value.
+    InvalidPlaceholder(ConfigPath),
+    /// A parameter used by the template is missing.
+    MissingParam {
+        /// Path to the string value which contains the placeholder.
+        path: ConfigPath,
+        /// Parameter name.
+        name: NonEmptyString,
+    },
+    /// A parameter value is of invalid type for its placeholder.
+    InvalidParamType {
+        /// Path to the string value which contains the placeholder.
+        path: ConfigPath,
+        /// Parameter name.
+        name: NonEmptyString,
+        /// Actual parameter value [`type`](enum.ValueType.html).
+        value_type: ValueType,
+    },
+}
+
+impl Error for DynTemplateError {}
+
+impl Display for DynTemplateError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DynTemplateError::*;
+
+        match self {
+            InvalidPlaceholder(path) => write!(f, "invalid placeholder in string at \"{}\"", path),
+            MissingParam { path, name } => write!(
+                f,
+                "missing template parameter \"{}\" used at \"{}\"",
+                name, path
+            ),
+            InvalidParamType {
+                path,
+                name,
+                value_type,
+            } => write!(
+                f,
+                "template parameter \"{}\" used at \"{}\" is of invalid type (found {})",
+                name, path, value_type
+            ),
+        }
+    }
+}
+
+/// A part of a template string value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum TemplateSegment<'s> {
+    /// Literal text.
+    Text(&'s str),
+    /// Parameter placeholder with the parameter name.
+    Param(&'s NonEmptyStr),
+}
+
+fn is_param_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Splits the template `string` into literal text and parameter placeholders.
+/// Returns `None` if the `string` contains an invalid placeholder.
+fn parse_template(string: &str) -> Option<Vec<TemplateSegment<'_>>> {
+    let mut segments = Vec::new();
+    let mut rest = string;
+
+    while let Some(offset) = rest.find('$') {
+        if offset > 0 {
+            segments.push(TemplateSegment::Text(&rest[..offset]));
+        }
+
+        let after = &rest[offset + 1..];
+
+        if let Some(after) = after.strip_prefix('$') {
+            segments.push(TemplateSegment::Text("$"));
+            rest = after;
+        } else if let Some(after) = after.strip_prefix('{') {
+            let end = after.find('}')?;
+            let name = &after[..end];
+
+            if !name.chars().all(is_param_char) {
+                return None;
+            }
+
+            segments.push(TemplateSegment::Param(NonEmptyStr::new(name)?));
+            rest = &after[end + 1..];
+        } else {
+            let end = after.find(|c| !is_param_char(c)).unwrap_or(after.len());
+
+            if let Some(name) = NonEmptyStr::new(&after[..end]) {
+                segments.push(TemplateSegment::Param(name));
+            } else {
+                segments.push(TemplateSegment::Text("$"));
+            }
+
+            rest = &after[end..];
+        }
+    }
+
+    if !rest.is_empty() {
+        segments.push(TemplateSegment::Text(rest));
+    }
+
+    Some(segments)
+}
+
+fn template_params_table(
+    table: &DynTable,
+    path: &mut ConfigPath,
+    params: &mut BTreeSet<NonEmptyString>,
+) -> Result<(), DynTemplateError> {
+    for (key, value) in table.iter() {
+        path.push(key);
+        template_params_value(value, path, params)?;
+        path.pop();
+    }
+
+    Ok(())
+}
+
+fn template_params_value(
+    value: DynConfigValueRef<'_>,
+    path: &mut ConfigPath,
+    params: &mut BTreeSet<NonEmptyString>,
+) -> Result<(), DynTemplateError> {
+    match value {
+        Value::String(string) => {
+            let segments = parse_template(string)
+                .ok_or_else(|| DynTemplateError::InvalidPlaceholder(path.clone()))?;
+
+            for segment in segments {
+                if let TemplateSegment::Param(name) = segment {
+                    params.insert(name.into());
+                }
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.iter().enumerate() {
+                path.push(index as u32);
+                template_params_value(value, path, params)?;
+                path.pop();
+            }
+        }
+        Value::Table(table) => template_params_table(table, path, params)?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn instantiate_table(
+    table: &DynTable,
+    params: &DynTemplateParams,
+    path: &mut ConfigPath,
+) -> Result<DynTable, DynTemplateError> {
+    let mut result = DynTable::new();
+
+    for (key, value) in table.iter() {
+        path.push(key);
+        result.set(key, instantiate_value(value, params, path)?.0);
+        path.pop();
+    }
+
+    Ok(result)
+}
+
+fn instantiate_array(
+    array: &DynArray,
+    params: &DynTemplateParams,
+    path: &mut ConfigPath,
+) -> Result<DynArray, DynTemplateError> {
+    let mut elements = Vec::with_capacity(array.len() as usize);
+
+    for (index, value) in array.iter().enumerate() {
+        path.push(index as u32);
+        elements.push(instantiate_value(value, params, path)?);
+        path.pop();
+    }
+
+    // Elements which are not substituted parameter values determine the array type;
+    // otherwise the first substituted parameter value does.
+    let array_type = elements
+        .iter()
+        .find(|(_, param)| param.is_none())
+        .or_else(|| elements.first())
+        .map(|(value, _)| value.get_type());
+
+    let mut result = DynArray::new();
+
+    for (index, (value, param)) in elements.into_iter().enumerate() {
+        if let Some(array_type) = array_type {
+            if !array_type.is_compatible(value.get_type()) {
+                // Must succeed - only substituted parameter values may be of incompatible type.
+                let name = unwrap_unchecked(param, "incompatible template array element type");
+
+                path.push(index as u32);
+
+                return Err(DynTemplateError::InvalidParamType {
+                    path: path.clone(),
+                    name,
+                    value_type: value.get_type(),
+                });
+            }
+        }
+
+        // Must succeed - the value type was validated above.
+        unwrap_unchecked(
+            result.push(value),
+            "incompatible template array element type",
+        );
+    }
+
+    Ok(result)
+}
+
+/// Returns the instantiated `value`, and the name of the parameter if the `value` is the substituted parameter value.
+fn instantiate_value(
+    value: DynConfigValueRef<'_>,
+    params: &DynTemplateParams,
+    path: &mut ConfigPath,
+) -> Result<(DynConfigValue, Option<NonEmptyString>), DynTemplateError> {
+    use DynTemplateError::*;
+
+    Ok(match value {
+        Value::String(string) => {
+            let segments =
+                parse_template(string).ok_or_else(|| InvalidPlaceholder(path.clone()))?;
+
+            let param = |name: &NonEmptyStr| {
+                params.get(name).ok_or_else(|| MissingParam {
+                    path: path.clone(),
+                    name: name.into(),
+                })
+            };
+
+            // A single placeholder - substitute the parameter value of any type.
+            if let [TemplateSegment::Param(name)] = segments.as_slice() {
+                (to_owned_value(param(name)?), Some((*name).into()))
+            } else {
+                let mut result = String::with_capacity(string.len());
+
+                for segment in segments {
+                    match segment {
+                        TemplateSegment::Text(text) => result.push_str(text),
+                        TemplateSegment::Param(name) => match param(name)? {
+                            Value::Bool(value) => result.push_str(&value.to_string()),
+                            Value::I64(value) => result.push_str(&value.to_string()),
+                            Value::F64(value) => result.push_str(&value.to_string()),
+                            Value::String(value) => result.push_str(value),
+                            #[cfg(feature = "datetime")]
+                            Value::DateTime(value) => result.push_str(&value.to_string()),
+                            value => {
+                                return Err(InvalidParamType {
+                                    path: path.clone(),
+                                    name: name.into(),
+                                    value_type: value.get_type(),
+                                })
+                            }
+                        },
+                    }
+                }
+
+                (Value::String(result), None)
+            }
+        }
+        Value::Array(array) => (Value::Array(instantiate_array(array, params, path)?), None),
+        Value::Table(table) => (Value::Table(instantiate_table(table, params, path)?), None),
+        value => (to_owned_value(value), None),
+    })
+}
+
+fn value_ref(value: &DynConfigValue) -> DynConfigValueRef<'_> {
+    match value {
+        Value::Bool(value) => Value::Bool(*value),
+        Value::I64(value) => Value::I64(*value),
+        Value::F64(value) => Value::F64(*value),
+        Value::String(value) => Value::String(value.as_str()),
+        Value::Array(value) => Value::Array(value),
+        Value::Table(value) => Value::Table(value),
+        #[cfg(feature = "datetime")]
+        Value::DateTime(value) => Value::DateTime(*value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    fn path(path: &str) -> ConfigPath {
+        path.parse().unwrap()
+    }
+
+    #[test]
+    fn instantiate() {
+        let template = dyn_config! {
+            name: "$name",
+            file: "levels/${name}_$index.dat",
+            price: "$$5 or $",
+            enemies: "$enemies",
+            spawn: { count: "$count", points: ["$start", "$start"] },
+            tags: ["boss", "$tag"],
+        };
+
+        assert_eq!(
+            template
+                .template_params()
+                .unwrap()
+                .iter()
+                .map(NonEmptyString::as_str)
+                .collect::<Vec<_>>(),
+            vec!["count", "enemies", "index", "name", "start", "tag"]
+        );
+
+        let mut params = DynTemplateParams::new();
+        assert!(!params.set(nestr!("name"), "forest"));
+        params.set(nestr!("index"), 3);
+        params.set(nestr!("enemies"), dyn_config! { wolf: 4 }.root().clone());
+        params.set(nestr!("count"), 7);
+        params.set(nestr!("tag"), "night");
+        params.set(nestr!("unused"), true);
+
+        let mut start = DynArray::new();
+        start.push(1.into()).unwrap();
+        start.push(2.into()).unwrap();
+        params.set(nestr!("start"), start);
+
+        assert_eq!(params.len(), 7);
+        assert_eq!(params.get("index").unwrap().i64().unwrap(), 3);
+
+        let config = template.instantiate(&params).unwrap();
+
+        let expected = dyn_config! {
+            name: "forest",
+            file: "levels/forest_3.dat",
+            price: "$5 or $",
+            enemies: { wolf: 4 },
+            spawn: { count: 7, points: [[1, 2], [1, 2]] },
+            tags: ["boss", "night"],
+        };
+        assert!(expected.diff(&config).is_empty());
+
+        // Errors.
+        params.remove("count");
+        assert_eq!(
+            template.instantiate(&params).err().unwrap(),
+            DynTemplateError::MissingParam {
+                path: path("spawn.count"),
+                name: nestr!("count").into(),
+            }
+        );
+        params.set(nestr!("count"), 7);
+
+        params.set(nestr!("index"), DynArray::new());
+        assert_eq!(
+            template.instantiate(&params).err().unwrap(),
+            DynTemplateError::InvalidParamType {
+                path: path("file"),
+                name: nestr!("index").into(),
+                value_type: ValueType::Array,
+            }
+        );
+        params.set(nestr!("index"), 3);
+
+        params.set(nestr!("tag"), 9);
+        assert_eq!(
+            template.instantiate(&params).err().unwrap(),
+            DynTemplateError::InvalidParamType {
+                path: path("tags[1]"),
+                name: nestr!("tag").into(),
+                value_type: ValueType::I64,
+            }
+        );
+
+        let template = dyn_config! { a: "${a" };
+        assert_eq!(
+            template.instantiate(&params).err().unwrap(),
+            DynTemplateError::InvalidPlaceholder(path("a"))
+        );
+        assert_eq!(
+            template.template_params().err().unwrap(),
+            DynTemplateError::InvalidPlaceholder(path("a"))
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use {
+    crate::{util::*, *},
+    std::time::Duration,
+};
+
+impl DynTable {
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as a duration - a non-negative integer or fractional number followed by a unit suffix
+    /// (`ns`, `us` / `µs`, `ms`, `s`, `m` / `min`, `h` or `d`), e.g. `"5s"`, `"250ms"`, `"1.5h"`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_duration<K: AsRef<str>>(&self, key: K) -> Result<Duration, GetUnitError> {
+        parse_duration(self.get_string(key)?)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as a byte size - a non-negative integer or fractional number followed by an optional unit suffix
+    /// (`B`, decimal `kB` / `KB`, `MB`, `GB`, `TB`, or binary `KiB`, `MiB`, `GiB`, `TiB`), e.g. `"512"`, `"64MiB"`, `"1.5GB"`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_bytes<K: AsRef<str>>(&self, key: K) -> Result<u64, GetUnitError> {
+        parse_bytes(self.get_string(key)?)
+    }
+}
+
+impl DynArray {
+    /// Tries to get a [`string`] [`value`] in the [`array`] at `index` and parse it as a duration.
+    ///
+    /// See [`DynTable::get_duration`] for the supported format.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`], or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`DynTable::get_duration`]: struct.DynTable.html#method.get_duration
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_duration(&self, index: u32) -> Result<Duration, GetUnitError> {
+        parse_duration(self.get_string(index)?)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`array`] at `index` and parse it as a byte size.
+    ///
+    /// See [`DynTable::get_bytes`] for the supported format.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`], or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`DynTable::get_bytes`]: struct.DynTable.html#method.get_bytes
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_bytes(&self, index: u32) -> Result<u64, GetUnitError> {
+        parse_bytes(self.get_string(index)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr, std::time::Duration};
+
+    #[test]
+    fn get_duration() {
+        let mut table = DynTable::new();
+
+        for (key, value) in [
+            ("ns", "100ns"),
+            ("us", "250us"),
+            ("ms", "250ms"),
+            ("s", "5s"),
+            ("spaces", " 5 s "),
+            ("min", "2min"),
+            ("h", "1.5h"),
+            ("d", "1d"),
+        ]
+        .iter()
+        {
+            table.set(NonEmptyStr::new(key).unwrap(), *value);
+        }
+
+        assert_eq!(table.get_duration("ns").unwrap(), Duration::from_nanos(100));
+        assert_eq!(
+            table.get_duration("us").unwrap(),
+            Duration::from_micros(250)
+        );
+        assert_eq!(
+            table.get_duration("ms").unwrap(),
+            Duration::from_millis(250)
+        );
+        assert_eq!(table.get_duration("s").unwrap(), Duration::from_secs(5));
+        assert_eq!(
+            table.get_duration("spaces").unwrap(),
+            Duration::from_secs(5)
+        );
+        assert_eq!(table.get_duration("min").unwrap(), Duration::from_secs(120));
+        assert_eq!(table.get_duration("h").unwrap(), Duration::from_secs(5400));
+        assert_eq!(table.get_duration("d").unwrap(), Duration::from_secs(86400));
+
+        let mut array = DynArray::new();
+
+        for value in ["5", "5sec", "-5s", "s", "99999999999999999999d"].iter() {
+            array.push((*value).into()).unwrap();
+        }
+
+        assert_eq!(
+            array.get_duration(0).err().unwrap(),
+            GetUnitError::InvalidSuffix(String::new())
+        );
+        assert_eq!(
+            array.get_duration(1).err().unwrap(),
+            GetUnitError::InvalidSuffix("sec".to_owned())
+        );
+        assert_eq!(
+            array.get_duration(2).err().unwrap(),
+            GetUnitError::InvalidNumber("-5s".to_owned())
+        );
+        assert_eq!(
+            array.get_duration(3).err().unwrap(),
+            GetUnitError::InvalidNumber("s".to_owned())
+        );
+        assert_eq!(
+            array.get_duration(4).err().unwrap(),
+            GetUnitError::ValueOutOfRange("99999999999999999999d".to_owned())
+        );
+        assert_eq!(
+            array.get_duration(5).err().unwrap(),
+            GetUnitError::Array(ArrayError::IndexOutOfBounds(5))
+        );
+
+        table.set(nestr!("int"), 5);
+        assert_eq!(
+            table.get_duration("int").err().unwrap(),
+            GetUnitError::Table(TableError::IncorrectValueType(ValueType::I64))
+        );
+    }
+
+    #[test]
+    fn get_bytes() {
+        let mut table = DynTable::new();
+
+        for (key, value) in [
+            ("none", "512"),
+            ("B", "512B"),
+            ("KB", "2KB"),
+            ("MiB", "64MiB"),
+            ("GB", "1.5 GB"),
+            ("TiB", "1TiB"),
+            ("MB", "64mb"),
+            ("overflow", "20000000TiB"),
+        ]
+        .iter()
+        {
+            table.set(NonEmptyStr::new(key).unwrap(), *value);
+        }
+
+        assert_eq!(table.get_bytes("none").unwrap(), 512);
+        assert_eq!(table.get_bytes("B").unwrap(), 512);
+        assert_eq!(table.get_bytes("KB").unwrap(), 2_000);
+        assert_eq!(table.get_bytes("MiB").unwrap(), 64 * 1024 * 1024);
+        assert_eq!(table.get_bytes("GB").unwrap(), 1_500_000_000);
+        assert_eq!(table.get_bytes("TiB").unwrap(), 1 << 40);
+        assert_eq!(
+            table.get_bytes("MB").err().unwrap(),
+            GetUnitError::InvalidSuffix("mb".to_owned())
+        );
+        assert_eq!(
+            table.get_bytes("overflow").err().unwrap(),
+            GetUnitError::ValueOutOfRange("20000000TiB".to_owned())
+        );
+        assert_eq!(
+            table.get_bytes("missing").err().unwrap(),
+            GetUnitError::Table(TableError::KeyDoesNotExist)
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use {
+    super::hashed_key::DynTableKeyRef,
     crate::{util::DisplayLua, *},
     std::{
         borrow::Borrow,
+        cmp::Ordering,
         fmt::{Display, Formatter},
+        iter::Peekable,
     },
 };
 
@@ -39,7 +42,7 @@ impl From<DynTable> for DynConfigValue {
 
 impl Display for DynConfigValue {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, Default::default())
     }
 }
 
@@ -59,6 +62,8 @@ impl<'at> From<&'at Value<String, DynArray, DynTable>> for DynConfigValueRef<'at
             Value::String(value) => Value::String(value.as_str()),
             Value::Array(value) => Value::Array(value),
             Value::Table(value) => Value::Table(value),
+            #[cfg(feature = "datetime")]
+            Value::DateTime(value) => Value::DateTime(*value),
         }
     }
 }
@@ -79,8 +84,10 @@ impl<'at> DynConfigValueRef<'at> {
                                 path: vec![(*index).into()].into(),
                                 len,
                             },
-                            ArrayError::ArrayEmpty | ArrayError::IncorrectValueType(_) => {
-                                debug_unreachable!("`get()` does not return `ArrayEmpty` or `IncorrectValueType(_)`")
+                            ArrayError::ArrayEmpty
+                            | ArrayError::IncorrectValueType(_)
+                            | ArrayError::ValueOutOfRange(_) => {
+                                debug_unreachable!("`get()` does not return `ArrayEmpty`, `IncorrectValueType(_)` or `ValueOutOfRange(_)`")
                             }
                         })?;
 
@@ -95,9 +102,11 @@ impl<'at> DynConfigValueRef<'at> {
                     Value::Table(table) => {
                         let key = NonEmptyStr::new(table_key.as_str())
                             .ok_or_else(|| GetPathError::KeyDoesNotExist(ConfigPath::new()))?;
-                        let value = table.get_impl(key).ok_or_else(|| {
-                            GetPathError::KeyDoesNotExist(vec![key.into()].into())
-                        })?;
+                        let value = table
+                            .get_hashed_impl(DynTableKeyRef::new(key, table_key.key_hash()))
+                            .ok_or_else(|| {
+                                GetPathError::KeyDoesNotExist(vec![key.into()].into())
+                            })?;
                         value.get_path(path).map_err(|err| err.push_key(key))
                     }
                     _ => Err(GetPathError::ValueNotATable {
@@ -114,7 +123,7 @@ impl<'at> DynConfigValueRef<'at> {
 
 impl<'a> Display for DynConfigValueRef<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, Default::default())
     }
 }
 
@@ -134,6 +143,8 @@ impl<'at> From<&'at mut Value<String, DynArray, DynTable>> for DynConfigValueMut
             Value::String(value) => Value::String(value.as_str()),
             Value::Array(value) => Value::Array(value),
             Value::Table(value) => Value::Table(value),
+            #[cfg(feature = "datetime")]
+            Value::DateTime(value) => Value::DateTime(*value),
         }
     }
 }
@@ -154,8 +165,10 @@ impl<'at> DynConfigValueMut<'at> {
                                 path: vec![(*index).into()].into(),
                                 len,
                             },
-                            ArrayError::ArrayEmpty | ArrayError::IncorrectValueType(_) => {
-                                debug_unreachable!("`get_mut()` does not return `ArrayEmpty` or `IncorrectValueType(_)`")
+                            ArrayError::ArrayEmpty
+                            | ArrayError::IncorrectValueType(_)
+                            | ArrayError::ValueOutOfRange(_) => {
+                                debug_unreachable!("`get_mut()` does not return `ArrayEmpty`, `IncorrectValueType(_)` or `ValueOutOfRange(_)`")
                             }
                         })?;
 
@@ -170,9 +183,11 @@ impl<'at> DynConfigValueMut<'at> {
                     Value::Table(table) => {
                         let key = NonEmptyStr::new(table_key.as_str())
                             .ok_or_else(|| GetPathError::KeyDoesNotExist(ConfigPath::new()))?;
-                        let value = table.get_mut_impl(key).ok_or_else(|| {
-                            GetPathError::KeyDoesNotExist(vec![key.into()].into())
-                        })?;
+                        let value = table
+                            .get_mut_hashed_impl(DynTableKeyRef::new(key, table_key.key_hash()))
+                            .ok_or_else(|| {
+                                GetPathError::KeyDoesNotExist(vec![key.into()].into())
+                            })?;
                         value.get_path(path).map_err(|err| err.push_key(key))
                     }
                     _ => Err(GetPathError::ValueNotATable {
@@ -187,9 +202,269 @@ impl<'at> DynConfigValueMut<'at> {
     }
 }
 
+impl<'at> DynConfigValueMut<'at> {
+    /// Sets the `value` at the last key in the `path`,
+    /// creating the missing intermediate tables for all other (string) keys.
+    pub(crate) fn set_path<'k, K, P>(
+        self,
+        mut path: Peekable<P>,
+        value: DynConfigValue,
+    ) -> Result<bool, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: Iterator<Item = K>,
+    {
+        let key = path.next().ok_or(GetPathError::EmptyPath)?;
+        let key = key.borrow();
+
+        match key {
+            ConfigKey::Array(index) => match self {
+                Value::Array(array) => {
+                    if path.peek().is_none() {
+                        array
+                            .set(*index, value)
+                            .map(|_| true)
+                            .map_err(|err| array_error_to_path_error(*index, err))
+                    } else {
+                        array
+                            .get_val_mut(*index)
+                            .map_err(|err| array_error_to_path_error(*index, err))?
+                            .set_path(path, value)
+                            .map_err(|err| err.push_index(*index))
+                    }
+                }
+                _ => Err(GetPathError::ValueNotAnArray {
+                    path: ConfigPath::new(),
+                    value_type: self.get_type(),
+                }),
+            },
+            ConfigKey::Table(ref table_key) => match self {
+                Value::Table(table) => {
+                    let key = NonEmptyStr::new(table_key.as_str())
+                        .ok_or_else(|| GetPathError::KeyDoesNotExist(ConfigPath::new()))?;
+
+                    match path.peek().map(Borrow::borrow) {
+                        None => Ok(table.set_impl(key, value)),
+                        Some(next_key) => {
+                            // Only create the missing intermediate table if it is going to be indexed with a string key.
+                            if table.get_impl(key).is_none() {
+                                if let ConfigKey::Table(_) = next_key {
+                                    table.set_impl(key, DynTable::new().into());
+                                } else {
+                                    return Err(GetPathError::KeyDoesNotExist(
+                                        vec![key.into()].into(),
+                                    ));
+                                }
+                            }
+
+                            let nested_value = unwrap_unchecked(
+                                table.get_mut_impl(key),
+                                "failed to get a value from a dyn config table with a valid key",
+                            );
+
+                            nested_value
+                                .set_path(path, value)
+                                .map_err(|err| err.push_key(key))
+                        }
+                    }
+                }
+                _ => Err(GetPathError::ValueNotATable {
+                    path: ConfigPath::new(),
+                    value_type: self.get_type(),
+                }),
+            },
+        }
+    }
+
+    /// Removes the value at the last key in the `path`.
+    pub(crate) fn remove_path<'k, K, P>(
+        self,
+        mut path: Peekable<P>,
+    ) -> Result<DynConfigValue, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: Iterator<Item = K>,
+    {
+        let key = path.next().ok_or(GetPathError::EmptyPath)?;
+        let key = key.borrow();
+
+        match key {
+            ConfigKey::Array(index) => match self {
+                Value::Array(array) => {
+                    if path.peek().is_none() {
+                        array
+                            .remove_impl(*index)
+                            .map_err(|err| array_error_to_path_error(*index, err))
+                    } else {
+                        array
+                            .get_val_mut(*index)
+                            .map_err(|err| array_error_to_path_error(*index, err))?
+                            .remove_path(path)
+                            .map_err(|err| err.push_index(*index))
+                    }
+                }
+                _ => Err(GetPathError::ValueNotAnArray {
+                    path: ConfigPath::new(),
+                    value_type: self.get_type(),
+                }),
+            },
+            ConfigKey::Table(ref table_key) => match self {
+                Value::Table(table) => {
+                    let key = NonEmptyStr::new(table_key.as_str())
+                        .ok_or_else(|| GetPathError::KeyDoesNotExist(ConfigPath::new()))?;
+
+                    let key_does_not_exist =
+                        || GetPathError::KeyDoesNotExist(vec![key.into()].into());
+
+                    if path.peek().is_none() {
+                        table.remove_impl(key).ok_or_else(key_does_not_exist)
+                    } else {
+                        table
+                            .get_mut_impl(key)
+                            .ok_or_else(key_does_not_exist)?
+                            .remove_path(path)
+                            .map_err(|err| err.push_key(key))
+                    }
+                }
+                _ => Err(GetPathError::ValueNotATable {
+                    path: ConfigPath::new(),
+                    value_type: self.get_type(),
+                }),
+            },
+        }
+    }
+}
+
+/// Returns a copy of the parts of the `value` matched by the (remaining) path `patterns`,
+/// or `None` if nothing was matched.
+pub(crate) fn extract_value<K: ConfigPathMatch>(
+    value: &DynConfigValue,
+    patterns: &[&[K]],
+) -> Option<DynConfigValue> {
+    // The whole value was matched.
+    if patterns.iter().any(|pattern| pattern.is_empty()) {
+        return Some(value.clone());
+    }
+
+    match value {
+        Value::Array(array) => array.extract_impl(patterns).map(Value::Array),
+        Value::Table(table) => table.extract_impl(patterns).map(Value::Table),
+        _ => None,
+    }
+}
+
+/// Removes all parts of the `value` not matched by the (remaining) path `patterns`.
+/// Returns `false` if nothing was matched and the `value` must be removed.
+pub(crate) fn retain_value(
+    value: &mut DynConfigValue,
+    patterns: &[&[ConfigPathPatternKey]],
+) -> bool {
+    // The whole value was matched.
+    if patterns.iter().any(|pattern| pattern.is_empty()) {
+        return true;
+    }
+
+    match value {
+        Value::Array(array) => array.retain_impl(patterns),
+        Value::Table(table) => table.retain_impl(patterns),
+        _ => false,
+    }
+}
+
+/// Compares the [`values`] using a total cross-type ordering (see [`DynArray::sort_unstable_values`]).
+///
+/// [`values`]: type.DynConfigValue.html
+/// [`DynArray::sort_unstable_values`]: struct.DynArray.html#method.sort_unstable_values
+pub(crate) fn cmp_values(l: DynConfigValueRef<'_>, r: DynConfigValueRef<'_>) -> Ordering {
+    match (l, r) {
+        (Value::Bool(l), Value::Bool(r)) => l.cmp(&r),
+        (Value::I64(l), Value::I64(r)) => l.cmp(&r),
+        (Value::F64(l), Value::F64(r)) => cmp_f64(l, r),
+        // Integers sort before equal floats.
+        (Value::I64(l), Value::F64(r)) => cmp_f64(l as f64, r).then(Ordering::Less),
+        (Value::F64(l), Value::I64(r)) => cmp_f64(l, r as f64).then(Ordering::Greater),
+        (Value::String(l), Value::String(r)) => l.cmp(r),
+        #[cfg(feature = "datetime")]
+        (Value::DateTime(l), Value::DateTime(r)) => {
+            (l.unix_timestamp(), l.nanosecond()).cmp(&(r.unix_timestamp(), r.nanosecond()))
+        }
+        (Value::Array(l), Value::Array(r)) => {
+            for (l, r) in l.iter().zip(r.iter()) {
+                let ordering = cmp_values(l, r);
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+
+            l.len().cmp(&r.len())
+        }
+        (Value::Table(l), Value::Table(r)) => {
+            let (l, r) = (sorted_by_key(l), sorted_by_key(r));
+
+            for ((l_key, l), (r_key, r)) in l.iter().zip(r.iter()) {
+                let ordering = l_key
+                    .as_str()
+                    .cmp(r_key.as_str())
+                    .then_with(|| cmp_values(l.clone(), r.clone()));
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+
+            l.len().cmp(&r.len())
+        }
+        (l, r) => type_order(&l).cmp(&type_order(&r)),
+    }
+}
+
+/// Returns the position of the `value` type in the cross-type ordering of [`values`].
+///
+/// [`values`]: type.DynConfigValue.html
+fn type_order(value: &DynConfigValueRef<'_>) -> u8 {
+    match value {
+        Value::Bool(_) => 0,
+        Value::I64(_) | Value::F64(_) => 1,
+        Value::String(_) => 2,
+        #[cfg(feature = "datetime")]
+        Value::DateTime(_) => 3,
+        Value::Array(_) => 4,
+        Value::Table(_) => 5,
+    }
+}
+
+/// Returns the (key, value) pairs of the `table` sorted by key.
+fn sorted_by_key(table: &DynTable) -> Vec<(&NonEmptyStr, DynConfigValueRef<'_>)> {
+    let mut values: Vec<_> = table.iter().collect();
+    values.sort_by(|(l, _), (r, _)| l.as_str().cmp(r.as_str()));
+    values
+}
+
+/// Compares the floats, treating `NaN`'s as equal to each other and greater than all other floats.
+fn cmp_f64(l: f64, r: f64) -> Ordering {
+    l.partial_cmp(&r)
+        .unwrap_or_else(|| l.is_nan().cmp(&r.is_nan()))
+}
+
+fn array_error_to_path_error(index: u32, err: ArrayError) -> GetPathError {
+    match err {
+        ArrayError::IndexOutOfBounds(len) => GetPathError::IndexOutOfBounds {
+            path: vec![index.into()].into(),
+            len,
+        },
+        ArrayError::IncorrectValueType(value_type) => GetPathError::IncorrectValueType(value_type),
+        ArrayError::ArrayEmpty | ArrayError::ValueOutOfRange(_) => {
+            debug_unreachable!(
+                "`set()` / `remove()` do not return `ArrayEmpty` or `ValueOutOfRange(_)`"
+            )
+        }
+    }
+}
+
 impl<'a> Display for DynConfigValueMut<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, Default::default())
     }
 }
 
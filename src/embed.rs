@@ -0,0 +1,347 @@
+use {
+    crate::*,
+    std::{
+        env,
+        error::Error,
+        ffi::OsStr,
+        fmt::{Display, Formatter},
+        fs, io,
+        path::{Component, Path, PathBuf},
+    },
+};
+
+/// An error returned by [`build_bin_config`](fn.build_bin_config.html).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EmbedBinConfigError {
+    /// The `OUT_DIR` environment variable is not set
+    /// (i.e. [`build_bin_config`](fn.build_bin_config.html) was not called from a build script).
+    OutDirNotSet,
+    /// The `CARGO_MANIFEST_DIR` environment variable is not set
+    /// (i.e. [`build_bin_config`](fn.build_bin_config.html) was not called from a build script).
+    ManifestDirNotSet,
+    /// The config source path is not a relative path within the package.
+    /// Contains the config source path.
+    InvalidPath(PathBuf),
+    /// The config source file extension is not `.ini` (requires `"ini"` feature) or `.lua` (requires `"lua"` feature).
+    /// Contains the config source path.
+    UnknownFormat(PathBuf),
+    /// Failed to read the config source file or to write the binary config data blob.
+    /// Contains the I/O error.
+    Io(io::Error),
+    /// Failed to parse the `.ini` config source file.
+    ///
+    /// NOTE - requires `"ini"` feature.
+    #[cfg(feature = "ini")]
+    Ini {
+        /// Path to the config source file.
+        path: PathBuf,
+        /// The `.ini` [`parser error`](struct.IniError.html).
+        error: IniError,
+    },
+    /// Failed to execute the Lua config source script.
+    ///
+    /// NOTE - requires `"lua"` feature.
+    #[cfg(feature = "lua_runtime")]
+    Lua {
+        /// Path to the config source file.
+        path: PathBuf,
+        /// The Lua [`config error`](enum.LuaConfigError.html).
+        error: LuaConfigError,
+    },
+    /// Failed to serialize the config to the binary config data blob.
+    /// Contains the binary config [`writer error`](enum.BinConfigWriterError.html).
+    Writer(BinConfigWriterError),
+}
+
+impl Error for EmbedBinConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use EmbedBinConfigError::*;
+
+        match self {
+            Io(err) => Some(err),
+            #[cfg(feature = "ini")]
+            Ini { error, .. } => Some(error),
+            #[cfg(feature = "lua_runtime")]
+            Lua { error, .. } => Some(error),
+            Writer(err) => Some(err),
+            OutDirNotSet | ManifestDirNotSet | InvalidPath(_) | UnknownFormat(_) => None,
+        }
+    }
+}
+
+impl Display for EmbedBinConfigError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use EmbedBinConfigError::*;
+
+        match self {
+            OutDirNotSet => {
+                "`OUT_DIR` environment variable is not set (must be called from a build script)"
+                    .fmt(f)
+            }
+            ManifestDirNotSet => {
+                "`CARGO_MANIFEST_DIR` environment variable is not set (must be called from a build script)"
+                    .fmt(f)
+            }
+            InvalidPath(path) => write!(
+                f,
+                "config source path \"{}\" is not a relative path within the package",
+                path.display()
+            ),
+            UnknownFormat(path) => write!(
+                f,
+                "unknown config source format of \"{}\" (expected an `.ini` or a `.lua` file)",
+                path.display()
+            ),
+            Io(err) => write!(f, "I/O error: {}", err),
+            // `<path>:<line>:<column>: <error>`, as reported by compilers.
+            #[cfg(feature = "ini")]
+            Ini { path, error } => write!(
+                f,
+                "{}:{}:{}: {}",
+                path.display(),
+                error.line,
+                error.column,
+                error.error
+            ),
+            #[cfg(feature = "lua_runtime")]
+            Lua { path, error } => write!(f, "{}: {}", path.display(), error),
+            Writer(err) => write!(f, "failed to serialize the binary config: {}", err),
+        }
+    }
+}
+
+impl From<io::Error> for EmbedBinConfigError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<BinConfigWriterError> for EmbedBinConfigError {
+    fn from(err: BinConfigWriterError) -> Self {
+        Self::Writer(err)
+    }
+}
+
+/// Parses the `.ini` (requires `"ini"` feature) or Lua (requires `"lua"` feature) config `source` file
+/// (format is determined by the file extension) and writes the resulting [`binary config`] data blob
+/// to the `OUT_DIR` directory, to be embedded in the binary by [`embed_bin_config`].
+///
+/// Must be called from a build script. `source` is the path to the config source file relative to the package root
+/// (i.e. to the `CARGO_MANIFEST_DIR` directory, e.g. `"assets/defaults.ini"`);
+/// the same path must be passed to [`embed_bin_config`].
+/// Also instructs Cargo to rerun the build script when the `source` file changes.
+///
+/// Returns the path to the written [`binary config`] data blob.
+///
+/// Returns an [`error`] if the `source` file could not be read or parsed.
+/// `.ini` parse errors are formatted as `<source>:<line>:<column>: <error>`,
+/// so failing the build script with the error reports the location of the error in the `source` file.
+///
+/// NOTE - requires `"embed"` feature.
+///
+/// [`binary config`]: struct.BinConfig.html
+/// [`embed_bin_config`]: macro.embed_bin_config.html
+/// [`error`]: enum.EmbedBinConfigError.html
+pub fn build_bin_config<P: AsRef<Path>>(source: P) -> Result<PathBuf, EmbedBinConfigError> {
+    let source = source.as_ref();
+
+    let out_dir = env::var_os("OUT_DIR").ok_or(EmbedBinConfigError::OutDirNotSet)?;
+    let package_dir =
+        env::var_os("CARGO_MANIFEST_DIR").ok_or(EmbedBinConfigError::ManifestDirNotSet)?;
+
+    println!("cargo:rerun-if-changed={}", source.display());
+
+    build_bin_config_impl(source, Path::new(&package_dir), Path::new(&out_dir))
+}
+
+/// Embeds the [`binary config`] data blob, written by [`build_bin_config`] in the build script
+/// for the config source file at `path` (relative to the package root), in the binary,
+/// and evaluates to the result of loading it as a [`binary config`].
+///
+/// Loading the embedded [`binary config`] only validates the data blob, without parsing the config source.
+/// It may only fail if the versions of the crate used by the build script and by the binary do not match.
+///
+/// NOTE - requires `"embed"` feature.
+///
+/// [`binary config`]: struct.BinConfig.html
+/// [`build_bin_config`]: fn.build_bin_config.html
+#[macro_export]
+macro_rules! embed_bin_config {
+    ($path:literal) => {
+        $crate::BinConfig::new(
+            include_bytes!(concat!(env!("OUT_DIR"), "/miniconfig/", $path, ".bin"))
+                .to_vec()
+                .into_boxed_slice(),
+        )
+    };
+}
+
+/// `source` is resolved relative to the `package_dir`.
+fn build_bin_config_impl(
+    source: &Path,
+    package_dir: &Path,
+    out_dir: &Path,
+) -> Result<PathBuf, EmbedBinConfigError> {
+    // The path is mirrored in the output directory - it must not escape it.
+    if !source
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(EmbedBinConfigError::InvalidPath(source.to_owned()));
+    }
+
+    let source_path = package_dir.join(source);
+
+    let data = match source.extension().and_then(OsStr::to_str) {
+        #[cfg(feature = "ini")]
+        Some("ini") => {
+            let config = DynConfig::from_ini(IniParser::new(&fs::read_to_string(&source_path)?))
+                .map_err(|error| EmbedBinConfigError::Ini {
+                    path: source.to_owned(),
+                    error,
+                })?;
+
+            config.to_bin_config()?
+        }
+        #[cfg(feature = "lua_runtime")]
+        Some("lua") => {
+            let script = fs::read_to_string(&source_path)?;
+
+            let lua = rlua::Lua::new();
+
+            lua.context(|lua| {
+                LuaConfig::from_script(lua, &script)
+                    .map_err(|error| EmbedBinConfigError::Lua {
+                        path: source.to_owned(),
+                        error,
+                    })?
+                    .to_bin_config()
+                    .map_err(EmbedBinConfigError::Writer)
+            })?
+        }
+        _ => return Err(EmbedBinConfigError::UnknownFormat(source.to_owned())),
+    };
+
+    let mut path = out_dir.join("miniconfig").join(source).into_os_string();
+    path.push(".bin");
+    let path = PathBuf::from(path);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&path, &data)?;
+
+    Ok(path)
+}
+
+#[cfg(all(test, feature = "ini"))]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {
+        super::*,
+        std::{env, fs},
+    };
+
+    /// Temporary directory, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let dir = env::temp_dir().join(format!("miniconfig_embed_test_{}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn build_bin_config() {
+        let dir = TempDir::new();
+        let package_dir = dir.0.join("package");
+        let out_dir = dir.0.join("out");
+
+        // Relative to the package directory.
+        let source = Path::new("assets").join("defaults.ini");
+
+        fs::create_dir_all(package_dir.join("assets")).unwrap();
+        fs::write(
+            package_dir.join(&source),
+            "name = \"Game\"\n[window]\nwidth = 1920",
+        )
+        .unwrap();
+
+        let path = build_bin_config_impl(&source, &package_dir, &out_dir).unwrap();
+        assert_eq!(
+            path,
+            out_dir
+                .join("miniconfig")
+                .join("assets")
+                .join("defaults.ini.bin")
+        );
+
+        let config = BinConfig::new(fs::read(&path).unwrap().into_boxed_slice()).unwrap();
+        assert_eq!(config.root().get_string("name").unwrap(), "Game");
+        assert_eq!(
+            config
+                .root()
+                .get_table("window")
+                .unwrap()
+                .get_i64("width")
+                .unwrap(),
+            1920
+        );
+
+        // Parse errors report the source location.
+        fs::write(
+            package_dir.join(&source),
+            "name = \"Game\"\n[window\nwidth = 1920",
+        )
+        .unwrap();
+
+        let err = build_bin_config_impl(&source, &package_dir, &out_dir)
+            .err()
+            .unwrap();
+        assert!(matches!(err, EmbedBinConfigError::Ini { .. }));
+        // The parse error is exposed as the error source.
+        assert_eq!(
+            Error::source(&err)
+                .unwrap()
+                .downcast_ref::<IniError>()
+                .unwrap()
+                .line,
+            2
+        );
+        assert!(err
+            .to_string()
+            .starts_with(&format!("{}:2:", source.display())));
+
+        // Missing source file.
+        assert!(matches!(
+            build_bin_config_impl(Path::new("missing.ini"), &package_dir, &out_dir)
+                .err()
+                .unwrap(),
+            EmbedBinConfigError::Io(_)
+        ));
+
+        // Invalid paths / formats.
+        let err = build_bin_config_impl(Path::new("../defaults.ini"), &package_dir, &out_dir)
+            .err()
+            .unwrap();
+        assert!(matches!(err, EmbedBinConfigError::InvalidPath(_)));
+        assert!(Error::source(&err).is_none());
+        assert!(matches!(
+            build_bin_config_impl(Path::new("defaults.json"), &package_dir, &out_dir)
+                .err()
+                .unwrap(),
+            EmbedBinConfigError::UnknownFormat(_)
+        ));
+    }
+}
@@ -1,8 +1,9 @@
 use {
-    crate::*,
+    crate::{util::PathOrRoot, *},
     std::{
         error::Error,
         fmt::{Display, Formatter},
+        str::FromStr,
     },
 };
 
@@ -10,6 +11,7 @@ use {
 ///
 /// [`table`]: enum.Value.html#variant.Table
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum TableError {
     /// Provided key does not exist in the [`table`], or is empty.
     ///
@@ -21,6 +23,12 @@ pub enum TableError {
     /// [`Table`]: enum.Value.html#variant.Table
     /// [`type`]: enum.Value.html#variant.Table
     IncorrectValueType(ValueType),
+    /// [`Table`] [`i64`] value is out of range of the requested integer type.
+    /// Contains the actual value.
+    ///
+    /// [`Table`]: enum.Value.html#variant.Table
+    /// [`i64`]: enum.Value.html#variant.I64
+    ValueOutOfRange(i64),
 }
 
 impl Error for TableError {}
@@ -38,6 +46,11 @@ impl Display for TableError {
                     actual_type
                 )
             }
+            ValueOutOfRange(value) => write!(
+                f,
+                "table value {} is out of range of the requested integer type",
+                value
+            ),
         }
     }
 }
@@ -46,6 +59,7 @@ impl Display for TableError {
 ///
 /// [`array`]: enum.Value.html#variant.Array
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum ArrayError {
     /// [`Array`] index out of bounds.
     /// Contains the actual [`array`] length.
@@ -63,6 +77,12 @@ pub enum ArrayError {
     /// [`Array`]: enum.Value.html#variant.Array
     /// [`type`]: enum.Value.html#variant.Array
     IncorrectValueType(ValueType),
+    /// [`Array`] [`i64`] value is out of range of the requested integer type.
+    /// Contains the actual value.
+    ///
+    /// [`Array`]: enum.Value.html#variant.Array
+    /// [`i64`]: enum.Value.html#variant.I64
+    ValueOutOfRange(i64),
 }
 
 impl Error for ArrayError {}
@@ -81,6 +101,139 @@ impl Display for ArrayError {
                     actual_type
                 )
             }
+            ValueOutOfRange(value) => write!(
+                f,
+                "array value {} is out of range of the requested integer type",
+                value
+            ),
+        }
+    }
+}
+
+/// An error returned by [`table`] enum value accessors (e.g. [`DynTable::get_enum`]).
+///
+/// [`table`]: enum.Value.html#variant.Table
+/// [`DynTable::get_enum`]: struct.DynTable.html#method.get_enum
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum GetEnumError {
+    /// Failed to get the [`string`] value from the [`table`].
+    /// Contains the [`table error`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`table error`]: enum.TableError.html
+    Table(TableError),
+    /// The [`string`] value is not a valid enum variant.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    InvalidVariant {
+        /// Path to the value.
+        path: ConfigPath,
+        /// Actual value.
+        value: String,
+        /// Valid enum variants, if known.
+        variants: Vec<String>,
+    },
+}
+
+impl From<TableError> for GetEnumError {
+    fn from(err: TableError) -> Self {
+        Self::Table(err)
+    }
+}
+
+impl GetEnumError {
+    /// Parses the string `value` at table `key` as an enum value of type `T`.
+    pub(crate) fn parse<T: FromStr>(
+        key: &str,
+        value: Result<&str, TableError>,
+    ) -> Result<T, GetEnumError> {
+        let value = value?;
+
+        value.parse().map_err(|_| GetEnumError::InvalidVariant {
+            path: Self::path(key),
+            value: value.to_owned(),
+            variants: Vec::new(),
+        })
+    }
+
+    /// Maps the string `value` at table `key` to an enum value of type `T` using the valid `variants`.
+    pub(crate) fn map<T: Clone>(
+        key: &str,
+        value: Result<&str, TableError>,
+        variants: &[(&str, T)],
+    ) -> Result<T, GetEnumError> {
+        let value = value?;
+
+        variants
+            .iter()
+            .find(|(variant, _)| *variant == value)
+            .map(|(_, variant)| variant.clone())
+            .ok_or_else(|| GetEnumError::InvalidVariant {
+                path: Self::path(key),
+                value: value.to_owned(),
+                variants: variants
+                    .iter()
+                    .map(|(variant, _)| (*variant).to_owned())
+                    .collect(),
+            })
+    }
+
+    fn path(key: &str) -> ConfigPath {
+        NonEmptyStr::new(key)
+            .map(|key| vec![key.into()])
+            .unwrap_or_default()
+            .into()
+    }
+}
+
+impl Error for GetEnumError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use GetEnumError::*;
+
+        match self {
+            // Transparent - `Display` is forwarded to the table error.
+            Table(err) => err.source(),
+            InvalidVariant { .. } => None,
+        }
+    }
+}
+
+impl Display for GetEnumError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use GetEnumError::*;
+
+        match self {
+            Table(err) => err.fmt(f),
+            InvalidVariant {
+                path,
+                value,
+                variants,
+            } => {
+                write!(
+                    f,
+                    "value \"{}\" at {} is not a valid enum variant",
+                    value,
+                    PathOrRoot(path)
+                )?;
+
+                if !variants.is_empty() {
+                    " (expected one of ".fmt(f)?;
+
+                    for (index, variant) in variants.iter().enumerate() {
+                        if index > 0 {
+                            ", ".fmt(f)?;
+                        }
+
+                        write!(f, "\"{}\"", variant)?;
+                    }
+
+                    ")".fmt(f)?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
@@ -90,6 +243,7 @@ impl Display for ArrayError {
 /// [`table`]: enum.Value.html#variant.Table
 /// [`array`]: enum.Value.html#variant.Array
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum GetPathError {
     /// One of the [`table string keys`] does not exist in the [`table`], or is empty.
     /// Contains the path to the invalid key, or an empty path for the root [`table`]
@@ -145,9 +299,74 @@ pub enum GetPathError {
     ///
     /// [`type`]: enum.ValueType.html
     IncorrectValueType(ValueType),
+    /// Tried to set / remove a value at an empty path (i.e. the root [`table`] / [`array`] itself).
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`array`]: enum.Value.html#variant.Array
+    EmptyPath,
+    /// Failed to [`parse`] the dotted [`config path`] string.
+    /// Contains the parse error.
+    ///
+    /// [`parse`]: struct.ConfigPath.html#impl-FromStr
+    /// [`config path`]: struct.ConfigPath.html
+    InvalidPath(ParseConfigPathError),
+}
+
+impl From<ParseConfigPathError> for GetPathError {
+    fn from(err: ParseConfigPathError) -> Self {
+        GetPathError::InvalidPath(err)
+    }
 }
 
 impl GetPathError {
+    /// Returns the description of the key of the path at which the path lookup failed, and why,
+    /// or `None` if the lookup did not fail at a path key
+    /// (i.e. for [`IncorrectValueType`], [`EmptyPath`] and [`InvalidPath`] errors).
+    ///
+    /// NOTE - empty [`table string keys`] are not recorded in the error path,
+    /// so the key preceding the empty key is reported as the failing key instead.
+    ///
+    /// [`IncorrectValueType`]: #variant.IncorrectValueType
+    /// [`EmptyPath`]: #variant.EmptyPath
+    /// [`InvalidPath`]: #variant.InvalidPath
+    /// [`table string keys`]: enum.ConfigKey.html#variant.Table
+    pub fn segment(&self) -> Option<GetPathSegment<'_>> {
+        use GetPathError::*;
+
+        let (parent, key, reason) = match self {
+            KeyDoesNotExist(path) => {
+                let (key, parent) = path.0.split_last()?;
+                (parent, Some(key), GetPathSegmentReason::KeyDoesNotExist)
+            }
+            IndexOutOfBounds { path, len } => {
+                let (key, parent) = path.0.split_last()?;
+                (
+                    parent,
+                    Some(key),
+                    GetPathSegmentReason::IndexOutOfBounds(*len),
+                )
+            }
+            ValueNotAnArray { path, value_type } => (
+                path.0.as_slice(),
+                None,
+                GetPathSegmentReason::ValueNotAnArray(*value_type),
+            ),
+            ValueNotATable { path, value_type } => (
+                path.0.as_slice(),
+                None,
+                GetPathSegmentReason::ValueNotATable(*value_type),
+            ),
+            IncorrectValueType(_) | EmptyPath | InvalidPath(_) => return None,
+        };
+
+        Some(GetPathSegment {
+            index: parent.len(),
+            parent,
+            key,
+            reason,
+        })
+    }
+
     /// Pushes the table key to the back of the path if the error has one.
     pub(crate) fn push_key(mut self, key: &NonEmptyStr) -> Self {
         use GetPathError::*;
@@ -159,7 +378,7 @@ impl GetPathError {
             ValueNotAnArray { path, .. } => path.0.push(key),
             ValueNotATable { path, .. } => path.0.push(key),
             IndexOutOfBounds { path, .. } => path.0.push(key),
-            IncorrectValueType(_) => {}
+            IncorrectValueType(_) | EmptyPath | InvalidPath(_) => {}
         }
 
         self
@@ -176,7 +395,7 @@ impl GetPathError {
             ValueNotAnArray { path, .. } => path.0.push(index),
             ValueNotATable { path, .. } => path.0.push(index),
             IndexOutOfBounds { path, .. } => path.0.push(index),
-            IncorrectValueType(_) => {}
+            IncorrectValueType(_) | EmptyPath | InvalidPath(_) => {}
         }
 
         self
@@ -194,13 +413,70 @@ impl GetPathError {
             ValueNotAnArray { path, .. } => path.0.reverse(),
             ValueNotATable { path, .. } => path.0.reverse(),
             IndexOutOfBounds { path, .. } => path.0.reverse(),
-            IncorrectValueType(_) => {}
+            IncorrectValueType(_) | EmptyPath | InvalidPath(_) => {}
         };
 
         self
     }
 }
 
+/// Describes the key of a path at which a path lookup failed, and why.
+///
+/// Returned by [`GetPathError::segment`].
+///
+/// [`GetPathError::segment`]: enum.GetPathError.html#method.segment
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GetPathSegment<'e> {
+    /// (`0`-based) index of the failing key in the path.
+    pub index: usize,
+    /// Path to the [`table`] / [`array`] in which the failing key was looked up,
+    /// or an empty path for the root [`table`] / [`array`].
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`array`]: enum.Value.html#variant.Array
+    pub parent: &'e [OwnedConfigKey],
+    /// The failing key, if it is recorded in the error
+    /// (i.e. for missing [`table string keys`] and out of bounds [`array index keys`]).
+    ///
+    /// [`table string keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array index keys`]: enum.ConfigKey.html#variant.Array
+    pub key: Option<&'e OwnedConfigKey>,
+    /// Why the lookup failed at the key.
+    pub reason: GetPathSegmentReason,
+}
+
+/// Why a path lookup failed at a key of the path - see [`GetPathSegment`].
+///
+/// [`GetPathSegment`]: struct.GetPathSegment.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GetPathSegmentReason {
+    /// The [`table string key`] does not exist in the [`table`], or is empty.
+    ///
+    /// [`table string key`]: enum.ConfigKey.html#variant.Table
+    /// [`table`]: enum.Value.html#variant.Table
+    KeyDoesNotExist,
+    /// The [`array index key`] is out of bounds.
+    /// Contains the actual [`array`] length.
+    ///
+    /// [`array index key`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    IndexOutOfBounds(u32),
+    /// The key is an [`array index key`], but the value it was looked up in is not an [`array`].
+    /// Contains the actual value [`type`].
+    ///
+    /// [`array index key`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`type`]: enum.ValueType.html
+    ValueNotAnArray(ValueType),
+    /// The key is a [`table string key`], but the value it was looked up in is not a [`table`].
+    /// Contains the actual value [`type`].
+    ///
+    /// [`table string key`]: enum.ConfigKey.html#variant.Table
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`type`]: enum.ValueType.html
+    ValueNotATable(ValueType),
+}
+
 impl<'a> Error for GetPathError {}
 
 impl<'a> Display for GetPathError {
@@ -208,27 +484,34 @@ impl<'a> Display for GetPathError {
         use GetPathError::*;
 
         match self {
-            KeyDoesNotExist(path) => write!(f, "key {} does not exist in the table", path),
+            KeyDoesNotExist(path) => {
+                write!(f, "key {} does not exist in the table", PathOrRoot(path))
+            }
             IndexOutOfBounds { path, len } => write!(
                 f,
                 "array index in {} out of bounds (length is {})",
-                path, len
+                PathOrRoot(path),
+                len
             ),
             ValueNotAnArray { path, value_type } => write!(
                 f,
                 "value at {} is not an array (but a \"{}\")",
-                path, value_type
+                PathOrRoot(path),
+                value_type
             ),
             ValueNotATable { path, value_type } => write!(
                 f,
                 "value at {} is not an table (but a \"{}\")",
-                path, value_type
+                PathOrRoot(path),
+                value_type
             ),
             IncorrectValueType(actual_type) => write!(
                 f,
                 "value is of incorrect and incompatible type (expected {})",
                 actual_type
             ),
+            EmptyPath => "tried to set / remove a value at an empty path".fmt(f),
+            InvalidPath(err) => write!(f, "invalid config path: {}", err),
         }
     }
 }
@@ -3,6 +3,19 @@ use crate::*;
 /// A trait which represents the config being filled by the [`.ini parser`](struct.IniParser.html)
 /// during the call to [`parse`](struct.IniParser.html#method.parse).
 /// Handles the events generated by the [`.ini parser`](struct.IniParser.html).
+///
+/// The trait is object safe - the [`.ini parser`](struct.IniParser.html) may drive a `&mut dyn IniConfig` event handler,
+/// and `&mut` references to / boxes of event handlers are event handlers themselves,
+/// so adapters (e.g. teeing the events to several configs, filtering or logging them) may be composed at runtime.
+///
+/// Section names / keys / string values which contain no escape sequences are passed to the event handler
+/// [`borrowed`] directly from the `.ini` source, with the lifetime of the source (see [`NonEmptyIniStr::as_borrowed`]).
+/// The parser itself does not allocate when parsing such sources without nested sections,
+/// or any such sources when reusing the [`scratch state`] of a previous parse.
+///
+/// [`borrowed`]: enum.NonEmptyIniStr.html#variant.Borrowed
+/// [`NonEmptyIniStr::as_borrowed`]: enum.NonEmptyIniStr.html#method.as_borrowed
+/// [`scratch state`]: struct.IniParserScratch.html
 pub trait IniConfig<'s> {
     /// Returns `Some(_)` if the current section already contains the `key`.
     /// The returned result value is `true` if the value is a section, `false` otherwise.
@@ -20,6 +33,67 @@ pub trait IniConfig<'s> {
     /// If `overwrite` is `false`, the `key` / `value` pair is added for the first time.
     fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool);
 
+    /// Adds the `key` / `value` pair to the current section, same as [`add_value`](#method.add_value),
+    /// additionally passing the source [`spans`] of the `key` and the `value`.
+    ///
+    /// Called instead of [`add_value`](#method.add_value) if the parser is [`configured`] to report the source spans.
+    /// The default implementation ignores the spans and calls [`add_value`](#method.add_value).
+    ///
+    /// [`spans`]: struct.IniSpan.html
+    /// [`configured`]: struct.IniParser.html#method.spans
+    fn add_value_with_spans(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        value: IniValue<'s, '_>,
+        overwrite: bool,
+        _spans: IniValueSpans,
+    ) {
+        self.add_value(key, value, overwrite)
+    }
+
+    /// Adds the `key` / value `reference` pair to the current section, where `reference` is the path to the referenced value,
+    /// `value` is the unresolved value reference string (e.g. `@{section/key}`)
+    /// and `spans` are the source [`spans`] of the `key` and the `value`.
+    ///
+    /// Called instead of [`add_value`](#method.add_value) for value references if the parser is [`configured`] to support them.
+    /// The event handler is responsible for resolving the references after parsing.
+    /// The default implementation adds the unresolved `value` string by calling [`add_value_with_spans`](#method.add_value_with_spans).
+    ///
+    /// [`spans`]: struct.IniSpan.html
+    /// [`configured`]: struct.IniParser.html#method.value_references
+    fn add_value_reference(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        _reference: &ConfigPath,
+        value: IniStr<'s, '_>,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        self.add_value_with_spans(key, IniValue::String(value), overwrite, spans)
+    }
+
+    /// Adds the `key` / unparsed `value` pair to the current section, where `hint` hints the type of the `value`
+    /// and `spans` are the source [`spans`] of the `key` and the `value`.
+    ///
+    /// Called instead of [`add_value`](#method.add_value) if the parser is [`configured`] to report raw values.
+    /// The event handler may interpret the `value` on demand using the [`hint`] helper methods.
+    /// The default implementation interprets the `value` with [`IniValueHint::to_value`] and calls [`add_value_with_spans`](#method.add_value_with_spans).
+    ///
+    /// [`spans`]: struct.IniSpan.html
+    /// [`configured`]: struct.IniParser.html#method.raw_values
+    /// [`hint`]: enum.IniValueHint.html
+    /// [`IniValueHint::to_value`]: enum.IniValueHint.html#method.to_value
+    fn add_raw_value(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        value: IniStr<'s, '_>,
+        hint: IniValueHint,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        self.add_value_with_spans(key, hint.to_value(value), overwrite, spans)
+    }
+
     /// Adds the `section` to the current section and makes it the current section for the following calls to
     /// [`contains_key`](#method.contains_key), [`add_value`](#method.add_value), [`start_array`](#method.start_array),
     /// [`end_section`](#method.end_section).
@@ -35,6 +109,34 @@ pub trait IniConfig<'s> {
     /// Will be eventually followed by a call to [`end_section`](#method.end_section) with the same `section` name.
     fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool);
 
+    /// Returns `true` if the current section contains an array of sections with the `key`
+    /// (i.e. [`contains_key`](#method.contains_key) returns `Some(false)` for this `key`
+    /// and the value was added by [`start_array_section`](#method.start_array_section)).
+    ///
+    /// Only called if the parser is [`configured`](struct.IniParser.html#method.array_sections) to support arrays of sections.
+    /// The default implementation returns `false`.
+    fn contains_array_section(&self, _key: NonEmptyIniStr<'s, '_>) -> bool {
+        false
+    }
+
+    /// Adds a new empty section to the end of the array of sections `section` in the current section
+    /// (creating the array if [`contains_key`](#method.contains_key) previously returned `None` for this `section`)
+    /// and makes it the current section, same as [`start_section`](#method.start_section).
+    ///
+    /// If `new` is `false`, continues the last section in the array of sections instead
+    /// (i.e. when the current section is restored after an [`isolated`](enum.IniIncludeMerge.html#variant.Isolated) include).
+    ///
+    /// Will be eventually followed by a call to [`end_section`](#method.end_section) with the same `section` name.
+    ///
+    /// Only called if the parser is [`configured`](struct.IniParser.html#method.array_sections) to support arrays of sections.
+    /// The default implementation calls [`start_section`](#method.start_section),
+    /// so all sections in the array of sections after the first one fail to parse with an
+    /// [`InvalidArraySection`](enum.IniErrorKind.html#variant.InvalidArraySection) error,
+    /// unless [`contains_array_section`](#method.contains_array_section) is implemented as well.
+    fn start_array_section(&mut self, section: NonEmptyIniStr<'s, '_>, _new: bool) {
+        self.start_section(section, false)
+    }
+
     /// Finishes the current `section`, started by the preceding call to [`start_section`](#method.start_section) with the same `section` name,
     /// popping it off the LIFO stack of sections,
     /// making the previous section (if any, or the root section) the new current section for the following calls to
@@ -58,4 +160,167 @@ pub trait IniConfig<'s> {
 
     /// Finishes the current `array`, started by the preceding call to [`start_array`](#method.start_array) with the same `array` name.
     fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>);
+
+    /// Returns `Some(_)` error if the event handler failed to handle the preceding event(s)
+    /// (e.g. the config it builds exceeded some limit) and the [`.ini parser`](struct.IniParser.html) must stop parsing.
+    ///
+    /// Checked by the parser after processing each source character which may have generated events.
+    /// If `Some(_)`, the parser returns the error at the current position in the source,
+    /// i.e. at the event which caused it.
+    /// The default implementation returns `None`.
+    fn abort(&self) -> Option<IniErrorKind> {
+        None
+    }
+}
+
+/// Forwards the events to the (possibly [`dynamic`](https://doc.rust-lang.org/std/keyword.dyn.html)) borrowed event handler,
+/// e.g. `&mut dyn IniConfig`, so that handlers may be composed at runtime.
+impl<'s, C: IniConfig<'s> + ?Sized> IniConfig<'s> for &mut C {
+    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        (**self).contains_key(key)
+    }
+
+    fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
+        (**self).add_value(key, value, overwrite)
+    }
+
+    fn add_value_with_spans(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        value: IniValue<'s, '_>,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        (**self).add_value_with_spans(key, value, overwrite, spans)
+    }
+
+    fn add_value_reference(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        reference: &ConfigPath,
+        value: IniStr<'s, '_>,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        (**self).add_value_reference(key, reference, value, overwrite, spans)
+    }
+
+    fn add_raw_value(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        value: IniStr<'s, '_>,
+        hint: IniValueHint,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        (**self).add_raw_value(key, value, hint, overwrite, spans)
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        (**self).start_section(section, overwrite)
+    }
+
+    fn contains_array_section(&self, key: NonEmptyIniStr<'s, '_>) -> bool {
+        (**self).contains_array_section(key)
+    }
+
+    fn start_array_section(&mut self, section: NonEmptyIniStr<'s, '_>, new: bool) {
+        (**self).start_array_section(section, new)
+    }
+
+    fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
+        (**self).end_section(section)
+    }
+
+    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        (**self).start_array(array, overwrite)
+    }
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        (**self).add_array_value(value)
+    }
+
+    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+        (**self).end_array(array)
+    }
+
+    fn abort(&self) -> Option<IniErrorKind> {
+        (**self).abort()
+    }
+}
+
+/// Forwards the events to the (possibly [`dynamic`](https://doc.rust-lang.org/std/keyword.dyn.html)) boxed event handler,
+/// e.g. `Box<dyn IniConfig>`, so that handlers may be composed at runtime.
+impl<'s, C: IniConfig<'s> + ?Sized> IniConfig<'s> for Box<C> {
+    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        (**self).contains_key(key)
+    }
+
+    fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
+        (**self).add_value(key, value, overwrite)
+    }
+
+    fn add_value_with_spans(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        value: IniValue<'s, '_>,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        (**self).add_value_with_spans(key, value, overwrite, spans)
+    }
+
+    fn add_value_reference(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        reference: &ConfigPath,
+        value: IniStr<'s, '_>,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        (**self).add_value_reference(key, reference, value, overwrite, spans)
+    }
+
+    fn add_raw_value(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        value: IniStr<'s, '_>,
+        hint: IniValueHint,
+        overwrite: bool,
+        spans: IniValueSpans,
+    ) {
+        (**self).add_raw_value(key, value, hint, overwrite, spans)
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        (**self).start_section(section, overwrite)
+    }
+
+    fn contains_array_section(&self, key: NonEmptyIniStr<'s, '_>) -> bool {
+        (**self).contains_array_section(key)
+    }
+
+    fn start_array_section(&mut self, section: NonEmptyIniStr<'s, '_>, new: bool) {
+        (**self).start_array_section(section, new)
+    }
+
+    fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
+        (**self).end_section(section)
+    }
+
+    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        (**self).start_array(array, overwrite)
+    }
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        (**self).add_array_value(value)
+    }
+
+    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+        (**self).end_array(array)
+    }
+
+    fn abort(&self) -> Option<IniErrorKind> {
+        (**self).abort()
+    }
 }
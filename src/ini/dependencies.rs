@@ -0,0 +1,253 @@
+use std::fmt::{Display, Formatter};
+
+/// Kind of the dependency recorded in the [`dependency graph`].
+///
+/// [`dependency graph`]: struct.IniDependencyGraph.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniDependencyKind {
+    /// An included `.ini` source (e.g. a file).
+    Include,
+    /// A resolved value reference.
+    Reference,
+    /// A resolved value interpolation.
+    Interpolation,
+}
+
+impl Display for IniDependencyKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use IniDependencyKind::*;
+
+        match self {
+            Include => "include".fmt(f),
+            Reference => "reference".fmt(f),
+            Interpolation => "interpolation".fmt(f),
+        }
+    }
+}
+
+/// A single edge in the [`dependency graph`].
+///
+/// [`dependency graph`]: struct.IniDependencyGraph.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct IniDependency {
+    /// Kind of the dependency.
+    pub kind: IniDependencyKind,
+    /// Name of the dependent node (e.g. the including source name, or the referencing value path),
+    /// or `None` for the root `.ini` source.
+    pub from: Option<String>,
+    /// Name of the dependency node (e.g. the included source name, or the referenced value path).
+    pub to: String,
+}
+
+/// Dependency graph of the `.ini` config, recorded while resolving includes / value references / interpolations.
+///
+/// Nodes of the graph are (user-defined) names of the included sources (e.g. file paths)
+/// or paths of the referencing / referenced and interpolated / interpolating values
+/// (formatted as [`config paths`], e.g. `section.key`).
+/// [`DynConfig::from_ini_with_includes`] records the value references / interpolations after all includes are parsed.
+///
+/// Build systems may use the [`included sources`] for dependency tracking;
+/// the [`dependencies`] are listed in resolution order, which is useful to debug config resolution.
+///
+/// Nodes are resolved recursively, starting from the root `.ini` source:
+/// [`enter`] a node when starting to resolve it and [`exit`] it when done.
+/// Entering a node which is already being resolved is detected as a [`cycle`].
+///
+/// [`config paths`]: struct.ConfigPath.html
+/// [`DynConfig::from_ini_with_includes`]: struct.DynConfig.html#method.from_ini_with_includes
+/// [`included sources`]: #method.includes
+/// [`dependencies`]: #method.dependencies
+/// [`enter`]: #method.enter
+/// [`exit`]: #method.exit
+/// [`cycle`]: #method.cycles
+#[derive(Clone, Default, Debug)]
+pub struct IniDependencyGraph {
+    /// All recorded dependency edges, in resolution order.
+    dependencies: Vec<IniDependency>,
+    /// All detected cycles.
+    /// Each cycle starts and ends with the same node name.
+    cycles: Vec<Vec<String>>,
+    /// Current resolution stack - kinds and names of the nodes being resolved.
+    stack: Vec<(IniDependencyKind, String)>,
+}
+
+impl IniDependencyGraph {
+    /// Creates a new empty [`dependency graph`](struct.IniDependencyGraph.html).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns all recorded [`dependencies`], in resolution order.
+    ///
+    /// [`dependencies`]: struct.IniDependency.html
+    pub fn dependencies(&self) -> &[IniDependency] {
+        &self.dependencies
+    }
+
+    /// Returns an iterator over the unique names of all [`included`] sources, in resolution order.
+    ///
+    /// [`included`]: enum.IniDependencyKind.html#variant.Include
+    pub fn includes(&self) -> impl Iterator<Item = &str> {
+        self.dependencies
+            .iter()
+            .enumerate()
+            .filter(move |(index, dependency)| {
+                dependency.kind == IniDependencyKind::Include
+                    && !self.dependencies[..*index]
+                        .iter()
+                        .any(|other| other.kind == dependency.kind && other.to == dependency.to)
+            })
+            .map(|(_, dependency)| dependency.to.as_str())
+    }
+
+    /// Returns an iterator over all detected dependency cycles.
+    /// Each cycle is a sequence of node names, which starts and ends with the same node name.
+    pub fn cycles(&self) -> impl Iterator<Item = &[String]> {
+        self.cycles.iter().map(Vec::as_slice)
+    }
+
+    /// Returns `true` if any dependency cycles were detected.
+    pub fn has_cycles(&self) -> bool {
+        !self.cycles.is_empty()
+    }
+
+    /// Returns the name of the (innermost) node currently being resolved, if any.
+    ///
+    /// E.g., if resolution failed with an error, this is the name of the node in which the error occured.
+    pub fn current(&self) -> Option<&str> {
+        self.stack.last().map(|(_, name)| name.as_str())
+    }
+
+    /// Returns the number of nodes currently being resolved.
+    pub fn depth(&self) -> u32 {
+        self.stack.len() as u32
+    }
+
+    /// Starts resolving the node of `kind` with `name`, recording the dependency
+    /// from the node currently being resolved (or the root `.ini` source).
+    ///
+    /// Returns `false` if the node is already being resolved (i.e. a dependency cycle was detected) -
+    /// the cycle is recorded, the node is not entered and must not be [`exited`].
+    ///
+    /// [`exited`]: #method.exit
+    pub fn enter<N: Into<String>>(&mut self, kind: IniDependencyKind, name: N) -> bool {
+        let name = name.into();
+
+        if let Some(start) = self
+            .stack
+            .iter()
+            .position(|(other_kind, other_name)| (*other_kind == kind) && (*other_name == name))
+        {
+            let mut cycle: Vec<_> = self.stack[start..]
+                .iter()
+                .map(|(_, name)| name.clone())
+                .collect();
+            cycle.push(name);
+
+            self.cycles.push(cycle);
+
+            return false;
+        }
+
+        self.dependencies.push(IniDependency {
+            kind,
+            from: self.stack.last().map(|(_, name)| name.clone()),
+            to: name.clone(),
+        });
+
+        self.stack.push((kind, name));
+
+        true
+    }
+
+    /// Finishes resolving the node last [`entered`].
+    ///
+    /// [`entered`]: #method.enter
+    pub fn exit(&mut self) {
+        debug_assert!(
+            !self.stack.is_empty(),
+            "exited more dependency graph nodes than entered"
+        );
+        self.stack.pop();
+    }
+
+    /// Clears the [`dependency graph`](struct.IniDependencyGraph.html).
+    pub fn clear(&mut self) {
+        self.dependencies.clear();
+        self.cycles.clear();
+        self.stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dependency_graph() {
+        use IniDependencyKind::*;
+
+        let mut graph = IniDependencyGraph::new();
+
+        assert!(graph.enter(Include, "a.ini"));
+        assert!(graph.enter(Include, "b.ini"));
+        assert_eq!(graph.depth(), 2);
+        assert_eq!(graph.current(), Some("b.ini"));
+
+        // `a.ini` includes `b.ini`, which includes `a.ini`.
+        assert!(!graph.enter(Include, "a.ini"));
+        assert_eq!(graph.depth(), 2);
+
+        // Different kinds of nodes with the same name do not form a cycle.
+        assert!(graph.enter(Reference, "a.ini"));
+        graph.exit();
+
+        graph.exit();
+
+        assert!(graph.enter(Include, "b.ini"));
+        graph.exit();
+
+        graph.exit();
+        assert_eq!(graph.depth(), 0);
+        assert_eq!(graph.current(), None);
+
+        assert_eq!(
+            graph.dependencies(),
+            &[
+                IniDependency {
+                    kind: Include,
+                    from: None,
+                    to: "a.ini".into()
+                },
+                IniDependency {
+                    kind: Include,
+                    from: Some("a.ini".into()),
+                    to: "b.ini".into()
+                },
+                IniDependency {
+                    kind: Reference,
+                    from: Some("b.ini".into()),
+                    to: "a.ini".into()
+                },
+                IniDependency {
+                    kind: Include,
+                    from: Some("a.ini".into()),
+                    to: "b.ini".into()
+                },
+            ]
+        );
+
+        assert_eq!(graph.includes().collect::<Vec<_>>(), vec!["a.ini", "b.ini"]);
+
+        assert!(graph.has_cycles());
+        assert_eq!(
+            graph.cycles().collect::<Vec<_>>(),
+            vec![&["a.ini".to_owned(), "b.ini".to_owned(), "a.ini".to_owned()][..]]
+        );
+
+        graph.clear();
+
+        assert!(graph.dependencies().is_empty());
+        assert!(!graph.has_cycles());
+    }
+}
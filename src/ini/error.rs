@@ -8,6 +8,7 @@ use {
 
 /// An actual concrete error kind returned by the [`.ini parser`](struct.IniParser.html).
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum IniErrorKind {
     /// Invalid character at the start of the line -
     /// expected a key, section name (if supported), or line comment (if supported).
@@ -31,6 +32,8 @@ pub enum IniErrorKind {
     NestedSectionDepthExceeded,
     /// Duplicate section name encountered and is not allowed by options.
     DuplicateSection,
+    /// Array of sections name is already used by a section or a value which is not an array of sections.
+    InvalidArraySection,
     /// Invalid character at the end of the line - expected whitespace or an inline comment (if supported).
     /// Contains the invalid character.
     InvalidCharacterAtLineEnd(char),
@@ -73,6 +76,14 @@ pub enum IniErrorKind {
     UnexpectedEndOfFileInQuotedString,
     /// Encountered an unquoted string value, not allowed by options.
     UnquotedString,
+    /// Encountered an unquoted integer value which overflows `i64`,
+    /// not allowed by the [`number policy`](enum.IniNumberPolicy.html).
+    IntegerOverflow,
+    /// Unknown or empty value type annotation.
+    /// Contains the type annotation.
+    InvalidTypeAnnotation(String),
+    /// The value does not match its type annotation.
+    TypeAnnotationMismatch,
     /// Unexpected new line in an array.
     UnexpectedNewLineInArray,
     /// Mixed value types encountered in an array.
@@ -84,8 +95,69 @@ pub enum IniErrorKind {
     UnexpectedEndOfFileInArray,
     /// Unexpected end of file in a quoted array value.
     UnexpectedEndOfFileInQuotedArrayValue,
+    /// Invalid `!include` directive - expected `!include "<name>"` on its own line.
+    InvalidIncludeDirective,
+    /// The included `.ini` source could not be resolved.
+    /// Contains the name of the included source.
+    IncludeNotFound(String),
+    /// The `.ini` source is (directly or indirectly) included by itself.
+    /// Contains the name of the included source.
+    IncludeCycle(String),
+    /// Case-insensitive section name / key only differs in case from a previously encountered one in the root / section,
+    /// and this is not allowed by options.
+    /// Contains the original spellings of the previous and the current section name / key.
+    KeyCollision(String, String),
+    /// Section name / key was rejected by the user-provided [`validation function`](struct.IniParser.html#method.validate_key).
+    /// Contains the validation error message.
+    InvalidKey(String),
+    /// Section name is not in the [`schema`](struct.IniParser.html#method.schema) of valid section names / keys.
+    UnknownSection,
+    /// Key is not in the [`schema`](struct.IniParser.html#method.schema) of valid section names / keys.
+    UnknownKey,
+    /// Invalid [`value reference`](struct.IniParser.html#method.value_references) - empty section name / key in the referenced path.
+    InvalidValueReference,
+    /// The value referenced by a [`value reference`](struct.IniParser.html#method.value_references) does not exist.
+    /// Contains the referenced path.
+    UnresolvedValueReference(ConfigPath),
+    /// The [`value reference`](struct.IniParser.html#method.value_references) (directly or indirectly) references itself.
+    /// Contains the referenced path.
+    CyclicValueReference(ConfigPath),
+    /// Invalid [`interpolation`](struct.IniParser.html#method.interpolation) syntax -
+    /// e.g. a percent sign (`'%'`) / dollar sign (`'$'`) not followed by a reference or by another one,
+    /// or an empty section name / key in the reference.
+    InvalidInterpolation,
+    /// The value referenced by an [`interpolation`](struct.IniParser.html#method.interpolation) does not exist,
+    /// or is a section / an array.
+    /// Contains the referenced path.
+    UnresolvedInterpolation(ConfigPath),
+    /// The [`interpolations`](struct.IniParser.html#method.interpolation) are nested too deep
+    /// (e.g. the value (directly or indirectly) references itself).
+    InterpolationDepthExceeded,
+    /// Section name / key is longer than the [`maximum length`](struct.IniParser.html#method.max_key_len).
+    /// Contains the maximum length in bytes.
+    KeyTooLong(u32),
+    /// Value is longer than the [`maximum length`](struct.IniParser.html#method.max_value_len).
+    /// Contains the maximum length in bytes.
+    ValueTooLong(u32),
+    /// Array contains more values than the [`maximum`](struct.IniParser.html#method.max_array_len).
+    /// Contains the maximum number of array values.
+    ArrayTooLong(u32),
+    /// Config contains more sections than the [`maximum`](struct.IniParser.html#method.max_sections).
+    /// Contains the maximum number of sections.
+    TooManySections(u32),
+    /// The `.ini` source is longer than the [`maximum size`](struct.IniParser.html#method.max_size).
+    /// Contains the maximum size in bytes.
+    SourceTooLarge(usize),
+    /// The estimated memory usage of the config built by the [`DynConfig::from_ini`] family of methods
+    /// exceeds the [`maximum`](struct.IniParser.html#method.max_memory_usage).
+    /// Contains the maximum memory usage in bytes.
+    ///
+    /// [`DynConfig::from_ini`]: struct.DynConfig.html#method.from_ini
+    MemoryLimitExceeded(usize),
 }
 
+impl Error for IniErrorKind {}
+
 impl Display for IniErrorKind {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         use IniErrorKind::*;
@@ -108,6 +180,7 @@ impl Display for IniErrorKind {
             InvalidParentSection => "invalid (missing or not a section) parent section".fmt(f),
             NestedSectionDepthExceeded => write!(f, "maximum allowed nested section depth exceeded"),
             DuplicateSection => "duplicate section name encountered and is not allowed by options".fmt(f),
+            InvalidArraySection => "array of sections name is already used by a section or a value which is not an array of sections".fmt(f),
             InvalidCharacterAtLineEnd(c) => write!(
                 f,
                 "invalid character ('{}') at the end of the line - expected whitespace or an inline comment (if supported)", c
@@ -144,6 +217,9 @@ impl Display for IniErrorKind {
             UnexpectedNewLineInQuotedValue => "unexpected new line in a quoted string value".fmt(f),
             UnexpectedEndOfFileInQuotedString => "unexpected end of file in a quoted string value".fmt(f),
             UnquotedString => "encountered an unquoted string value, not allowed by options".fmt(f),
+            IntegerOverflow => "encountered an integer value which overflows `i64`, not allowed by options".fmt(f),
+            InvalidTypeAnnotation(name) => write!(f, "unknown or empty value type annotation (\"{}\")", name),
+            TypeAnnotationMismatch => "the value does not match its type annotation".fmt(f),
             UnexpectedNewLineInArray => "unexpected new line in an array".fmt(f),
             MixedArray => "mixed value types encountered in an array".fmt(f),
             InvalidCharacterInArray(c) => write!(
@@ -152,23 +228,98 @@ impl Display for IniErrorKind {
             ),
             UnexpectedEndOfFileInArray => "unexpected end of file in an array".fmt(f),
             UnexpectedEndOfFileInQuotedArrayValue => "unexpected end of file in a quoted array value".fmt(f),
+            InvalidIncludeDirective => "invalid include directive - expected `!include \"<name>\"` on its own line".fmt(f),
+            IncludeNotFound(name) => write!(f, "included `.ini` source \"{}\" could not be resolved", name),
+            IncludeCycle(name) => write!(f, "`.ini` source \"{}\" is included by itself", name),
+            KeyCollision(previous, current) => write!(
+                f,
+                "section name / key \"{}\" only differs in case from \"{}\", which is not allowed by options",
+                current, previous
+            ),
+            InvalidKey(err) => write!(f, "invalid section name / key: {}", err),
+            UnknownSection => "unknown section name not in the schema".fmt(f),
+            UnknownKey => "unknown key not in the schema".fmt(f),
+            InvalidValueReference => "invalid value reference - empty section name / key in the referenced path".fmt(f),
+            UnresolvedValueReference(path) => write!(f, "referenced value at \"{}\" does not exist", path),
+            CyclicValueReference(path) => write!(f, "cyclic value reference to \"{}\"", path),
+            InvalidInterpolation => "invalid interpolation syntax".fmt(f),
+            UnresolvedInterpolation(path) => write!(f, "interpolated value at \"{}\" does not exist", path),
+            InterpolationDepthExceeded => "interpolations are nested too deep".fmt(f),
+            KeyTooLong(max_len) => write!(f, "section name / key is longer than the maximum length ({} bytes)", max_len),
+            ValueTooLong(max_len) => write!(f, "value is longer than the maximum length ({} bytes)", max_len),
+            ArrayTooLong(max_len) => write!(f, "array contains more values than the maximum ({})", max_len),
+            TooManySections(max_sections) => write!(f, "config contains more sections than the maximum ({})", max_sections),
+            SourceTooLarge(max_size) => write!(f, "`.ini` source is larger than the maximum size ({} bytes)", max_size),
+            MemoryLimitExceeded(max_memory_usage) => write!(f, "estimated memory usage of the config exceeds the maximum ({} bytes)", max_memory_usage),
         }
     }
 }
 
 /// An error returned by the [`.ini parser`](struct.IniParser.html).
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub struct IniError {
     /// Line in the source string where the error occured.
     pub line: u32,
-    /// Column in the source string where the error occured.
+    /// Column in the source string where the error occured
+    /// (see [`tab_width`](struct.IniParser.html#method.tab_width)).
     pub column: u32,
+    /// Byte offset in the source string of the character at which the error occured
+    /// (or of the last character of the source string, if the error occured at its end).
+    pub offset: usize,
     /// Path to the key / value in which the error happened, or an empty path for the root section.
     pub path: ConfigPath,
     /// Actual error.
     pub error: IniErrorKind,
 }
 
+impl IniError {
+    /// Returns the line of the `source` string at which the error occured,
+    /// followed by a line with a caret (`^`) pointing at the error [`offset`](#structfield.offset).
+    ///
+    /// Tabs preceding the error in the source line are preserved so that the caret lines up
+    /// regardless of the tab width used to display the snippet.
+    ///
+    /// `source` must be the `.ini` source string the error was returned for.
+    ///
+    /// E.g.:
+    /// ```text
+    /// key = "value
+    ///             ^
+    /// ```
+    pub fn snippet(&self, source: &str) -> String {
+        let mut offset = self.offset.min(source.len());
+
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let is_line_break = |c: char| c == '\n' || c == '\r';
+
+        let line_start = source[..offset]
+            .rfind(is_line_break)
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        let line_end = source[offset..]
+            .find(is_line_break)
+            .map(|idx| offset + idx)
+            .unwrap_or_else(|| source.len());
+
+        let line = &source[line_start..line_end];
+
+        let mut snippet = String::with_capacity(line.len() * 2 + 2);
+        snippet.push_str(line);
+        snippet.push('\n');
+
+        for c in source[line_start..offset].chars() {
+            snippet.push(if c == '\t' { '\t' } else { ' ' });
+        }
+
+        snippet.push('^');
+        snippet
+    }
+}
+
 impl Error for IniError {}
 
 impl Display for IniError {
@@ -176,7 +327,10 @@ impl Display for IniError {
         write!(
             f,
             "`.ini` parse error; line: {}, column: {}, path: {}, error: {}",
-            self.line, self.column, self.path, self.error
+            self.line,
+            self.column,
+            PathOrRoot(&self.path),
+            self.error
         )
     }
 }
@@ -187,6 +341,7 @@ impl Display for IniError {
 /// [`dyn`]: struct.DynConfig.html#method.to_ini_string
 /// [`lua`]: struct.LuaConfig.html#method.to_ini_string
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum ToIniStringError {
     /// Array values are not allowed by options.
     ArraysNotAllowed,
@@ -194,6 +349,8 @@ pub enum ToIniStringError {
     InvalidArrayType,
     /// Maximum allowed nested section depth exceeded.
     NestedSectionDepthExceeded,
+    /// Tables nested in sections are not allowed by options.
+    NestedTablesNotAllowed,
     /// Encountered an escaped character not allowed by options.
     /// Contains the escaped character.
     EscapedCharacterNotAllowed(char),
@@ -223,6 +380,7 @@ impl Display for ToIniStringError {
             ArraysNotAllowed => "array values are not allowed by options".fmt(f),
             InvalidArrayType => "only boolean, number and string arrays are supported".fmt(f),
             NestedSectionDepthExceeded => "maximum allowed nested section depth exceeded".fmt(f),
+            NestedTablesNotAllowed => "tables nested in sections are not allowed by options".fmt(f),
             WriteError => "general write error".fmt(f),
             EscapedCharacterNotAllowed(c) => write!(
                 f,
@@ -232,3 +390,42 @@ impl Display for ToIniStringError {
         }
     }
 }
+
+/// An error returned by the [`.ini` to Lua script event handler](struct.LuaScriptFromIni.html).
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum LuaScriptFromIniError {
+    /// The `.ini` config overwrote a value / section which was already written to the Lua script.
+    /// Contains the path to the overwritten value / section.
+    OverwrittenValue(ConfigPath),
+    /// The `.ini` config continued a section / an array of sections which was already finished in the Lua script.
+    /// Contains the path to the continued section / array of sections.
+    ContinuedSection(ConfigPath),
+    /// General write error.
+    WriteError,
+}
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+impl Error for LuaScriptFromIniError {}
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+impl Display for LuaScriptFromIniError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use LuaScriptFromIniError::*;
+
+        match self {
+            OverwrittenValue(path) => write!(
+                f,
+                "overwrote the value / section at \"{}\" already written to the Lua script",
+                path
+            ),
+            ContinuedSection(path) => write!(
+                f,
+                "continued the section at \"{}\" already finished in the Lua script",
+                path
+            ),
+            WriteError => "general write error".fmt(f),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::{collections::HashMap, hash::BuildHasher};
+
+/// A trait implemented by the user to resolve the `.ini` sources included
+/// via the `!include "<name>"` directive when [`parsing with includes`].
+///
+/// Implemented for closures which take the name of the included source
+/// and return its contents (or `None` if the source could not be resolved),
+/// and for references to hash maps of names to sources.
+///
+/// [`parsing with includes`]: struct.IniParser.html#method.parse_with_includes
+pub trait IniIncludeResolver<'s> {
+    /// Returns the contents of the included `.ini` source with `name`, or `None` if it could not be resolved.
+    ///
+    /// NOTE - the returned source must outlive the [`.ini parser`](struct.IniParser.html),
+    /// as the parsed section names / keys / values are borrowed from it.
+    fn resolve(&mut self, name: &str) -> Option<&'s str>;
+}
+
+impl<'s, F> IniIncludeResolver<'s> for F
+where
+    F: FnMut(&str) -> Option<&'s str>,
+{
+    fn resolve(&mut self, name: &str) -> Option<&'s str> {
+        self(name)
+    }
+}
+
+impl<'s, S: BuildHasher> IniIncludeResolver<'s> for &'s HashMap<String, String, S> {
+    fn resolve(&mut self, name: &str) -> Option<&'s str> {
+        self.get(name).map(String::as_str)
+    }
+}
+
+impl<'s, S: BuildHasher> IniIncludeResolver<'s> for &'s HashMap<&'s str, &'s str, S> {
+    fn resolve(&mut self, name: &str) -> Option<&'s str> {
+        self.get(name).copied()
+    }
+}
@@ -0,0 +1,388 @@
+use {
+    crate::{util::*, *},
+    std::{collections::HashMap, fmt::Write},
+};
+
+/// Kind of the value written to a Lua table by the [`event handler`](struct.LuaScriptFromIni.html).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LuaScriptKey {
+    /// A primitive value or an array.
+    Value,
+    /// A (possibly nested) section.
+    Section,
+    /// An array of sections.
+    SectionArray,
+}
+
+/// A Lua table constructor which was started, but not yet finished, by the [`event handler`](struct.LuaScriptFromIni.html).
+struct LuaScriptTable {
+    /// Key of the table in its parent table.
+    /// `None` for the root table and for the sections in the arrays of sections.
+    key: Option<NonEmptyString>,
+    /// Number of sections in the array if the table is an array of sections, `None` otherwise.
+    section_array_len: Option<u32>,
+    /// Keys of the values written to the table.
+    keys: HashMap<String, LuaScriptKey>,
+}
+
+impl LuaScriptTable {
+    fn new(key: Option<&NonEmptyStr>, section_array: bool) -> Self {
+        Self {
+            key: key.map(NonEmptyStr::to_owned),
+            section_array_len: if section_array { Some(0) } else { None },
+            keys: HashMap::new(),
+        }
+    }
+}
+
+/// An [`event handler`] which writes the `.ini` config as a Lua config script (see [`LuaConfig::from_script`])
+/// to the writer as it is parsed, without building a config in between.
+///
+/// The script is written in the same format as by `to_lua_string_opts`, except that the values are written
+/// in the order they were parsed in, the key-value separators are never aligned and arrays are always written in block form.
+///
+/// As the Lua table constructors are written as soon as possible,
+/// the `.ini` config may not overwrite previously written values / sections
+/// (e.g. with the [`Last`] duplicate key policy), or continue previously finished sections
+/// (e.g. the [`merged`] duplicate sections which are not adjacent) -
+/// an [`OverwrittenValue`] / [`ContinuedSection`] error is returned by [`finish`].
+/// Adjacent sections which share the parent section(s) (e.g. `[a/b]` and `[a/c]`) are always supported.
+///
+/// [`event handler`]: trait.IniConfig.html
+/// [`LuaConfig::from_script`]: struct.LuaConfig.html#method.from_script
+/// [`Last`]: enum.IniDuplicateKeys.html#variant.Last
+/// [`merged`]: enum.IniDuplicateSections.html#variant.Merge
+/// [`OverwrittenValue`]: enum.LuaScriptFromIniError.html#variant.OverwrittenValue
+/// [`ContinuedSection`]: enum.LuaScriptFromIniError.html#variant.ContinuedSection
+/// [`finish`]: #method.finish
+pub struct LuaScriptFromIni<W: Write> {
+    w: W,
+    options: ToLuaStringOptions,
+    /// Started Lua table constructors, starting with the root table.
+    /// The tables past `depth` were ended by the parser, but are only finished when the parser
+    /// adds a value to / starts a different section in one of their parent tables.
+    tables: Vec<LuaScriptTable>,
+    /// Number of the tables currently open in the parser, incl. the root table.
+    depth: usize,
+    /// First error encountered while writing the script, if any.
+    error: Option<LuaScriptFromIniError>,
+}
+
+impl<W: Write> LuaScriptFromIni<W> {
+    /// Creates a new [`event handler`](struct.LuaScriptFromIni.html) which writes the Lua script to the writer `w`,
+    /// using the default [`options`](struct.ToLuaStringOptions.html).
+    pub fn new(w: W) -> Self {
+        Self::with_options(w, Default::default())
+    }
+
+    /// Creates a new [`event handler`](struct.LuaScriptFromIni.html) which writes the Lua script to the writer `w`,
+    /// using the provided [`options`](struct.ToLuaStringOptions.html).
+    pub fn with_options(w: W, options: ToLuaStringOptions) -> Self {
+        let mut script = Self {
+            w,
+            options,
+            tables: vec![LuaScriptTable::new(None, false)],
+            depth: 1,
+            error: None,
+        };
+
+        script.write(|w, _| writeln!(w, "{{"));
+
+        script
+    }
+
+    /// Finishes the Lua script and returns the writer.
+    ///
+    /// NOTE - must only be called after the [`.ini parser`](struct.IniParser.html) successfully finished parsing.
+    pub fn finish(mut self) -> Result<W, LuaScriptFromIniError> {
+        debug_assert_eq!(self.depth, 1, "missing `end_section()` call");
+
+        self.finish_tables(1);
+        self.write(|w, _| w.write_char('}'));
+
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.w),
+        }
+    }
+
+    /// Calls `f` with the writer, unless an error was already encountered.
+    fn write<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut NewLineWriter<'_, W>, &ToLuaStringOptions) -> std::fmt::Result,
+    {
+        if self.error.is_none() {
+            let options = self.options;
+
+            if f(&mut options.formatter.new_lines(&mut self.w), &options).is_err() {
+                self.error.replace(LuaScriptFromIniError::WriteError);
+            }
+        }
+    }
+
+    fn set_error(&mut self, error: LuaScriptFromIniError) {
+        if self.error.is_none() {
+            self.error.replace(error);
+        }
+    }
+
+    /// Returns the path to the `key` in the current table.
+    fn path(&self, key: &NonEmptyStr) -> ConfigPath {
+        let mut path = ConfigPath::new();
+        let mut section_array_len = None;
+
+        for table in self.tables[1..self.depth].iter() {
+            match (&table.key, section_array_len) {
+                (Some(key), _) => path.push(key.as_ne_str()),
+                (None, Some(len)) => path.push(OwnedConfigKey::Array(len - 1)),
+                (None, None) => {}
+            }
+
+            section_array_len = table.section_array_len;
+        }
+
+        path.push(key);
+        path
+    }
+
+    /// Finishes the tables which were ended by the parser, but not yet finished,
+    /// until only `depth` tables are left.
+    fn finish_tables(&mut self, depth: usize) {
+        while self.tables.len() > depth {
+            // Must succeed - the root table is never finished here.
+            let table = unwrap_unchecked(self.tables.pop(), "empty Lua script table stack");
+            let indent = self.tables.len() as u32;
+            let section_array_len = self
+                .tables
+                .last()
+                .and_then(|parent| parent.section_array_len);
+
+            self.write(|w, options| {
+                options.formatter.write_indent(w, indent)?;
+                w.write_str("},")?;
+
+                match (table.key.as_ref(), section_array_len) {
+                    (Some(key), _) => write!(w, " -- {}", key)?,
+                    (None, Some(len)) => write!(w, " -- [{}]", len - 1)?,
+                    (None, None) => {}
+                }
+
+                writeln!(w)
+            });
+        }
+    }
+
+    /// Writes the `key` and starts a new value in the current table, finishing the tables ended by the parser, if any.
+    /// Returns `false` if the `key` already exists in the current table and thus may not be written.
+    fn start_value(&mut self, key: &NonEmptyStr, kind: LuaScriptKey) -> bool {
+        self.finish_tables(self.depth);
+
+        // Must succeed - the root table is always open.
+        let table = unwrap_unchecked(self.tables.last_mut(), "empty Lua script table stack");
+
+        if table.keys.contains_key(key.as_str()) {
+            return false;
+        }
+
+        table.keys.insert(key.as_str().to_owned(), kind);
+
+        let indent = self.depth as u32;
+
+        self.write(|w, options| {
+            options.formatter.write_indent(w, indent)?;
+            write_lua_key(w, key, 0)?;
+            w.write_str(" = ")
+        });
+
+        true
+    }
+
+    /// Returns the kind of the value with the `key` in the current table, if any.
+    fn get_key(&self, key: &NonEmptyStr) -> Option<LuaScriptKey> {
+        self.tables[self.depth - 1].keys.get(key.as_str()).copied()
+    }
+
+    /// Returns `true` if the `index`-th table was ended by the parser, but not yet finished,
+    /// and is the section / array of sections with the `key`.
+    fn is_unfinished(&self, index: usize, key: &NonEmptyStr, section_array: bool) -> bool {
+        matches!(
+            self.tables.get(index),
+            Some(table) if table.key.as_ref().map(NonEmptyString::as_ne_str) == Some(key)
+                && table.section_array_len.is_some() == section_array
+        )
+    }
+
+    /// Starts a new section in the current array of sections.
+    fn start_array_section_element(&mut self) {
+        let indent = self.depth as u32;
+
+        // Must succeed - the array of sections is the current table.
+        let section_array_len = unwrap_unchecked(
+            self.tables[self.depth - 1].section_array_len.as_mut(),
+            "expected an array of sections",
+        );
+        *section_array_len += 1;
+
+        self.write(|w, options| {
+            options.formatter.write_indent(w, indent)?;
+            writeln!(w, "{{")
+        });
+
+        self.tables.push(LuaScriptTable::new(None, false));
+        self.depth += 1;
+    }
+}
+
+impl<'s, W: Write> IniConfig<'s> for LuaScriptFromIni<W> {
+    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        self.get_key(key.as_ne_str())
+            .map(|kind| kind == LuaScriptKey::Section)
+    }
+
+    fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
+        let key = key.as_ne_str();
+
+        if overwrite || !self.start_value(key, LuaScriptKey::Value) {
+            return self.set_error(LuaScriptFromIniError::OverwrittenValue(self.path(key)));
+        }
+
+        self.write(|w, options| {
+            write_lua_ini_value(w, value, options)?;
+            writeln!(w, ",")
+        });
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        let section = section.as_ne_str();
+
+        // Continue the section ended by the parser, but not yet finished.
+        if !overwrite && self.is_unfinished(self.depth, section, false) {
+            self.finish_tables(self.depth + 1);
+            self.depth += 1;
+            return;
+        }
+
+        if !self.start_value(section, LuaScriptKey::Section) {
+            let error = if overwrite || self.get_key(section) != Some(LuaScriptKey::Section) {
+                LuaScriptFromIniError::OverwrittenValue(self.path(section))
+            } else {
+                LuaScriptFromIniError::ContinuedSection(self.path(section))
+            };
+
+            // Keep track of the section anyway - nothing is written after an error.
+            self.set_error(error);
+        }
+
+        self.write(|w, _| writeln!(w, "{{"));
+
+        self.tables.push(LuaScriptTable::new(Some(section), false));
+        self.depth += 1;
+    }
+
+    fn contains_array_section(&self, key: NonEmptyIniStr<'s, '_>) -> bool {
+        self.get_key(key.as_ne_str()) == Some(LuaScriptKey::SectionArray)
+    }
+
+    fn start_array_section(&mut self, section: NonEmptyIniStr<'s, '_>, new: bool) {
+        let section = section.as_ne_str();
+
+        // Continue the array of sections ended by the parser, but not yet finished.
+        if self.is_unfinished(self.depth, section, true) {
+            // Start a new section in the array.
+            if new {
+                self.finish_tables(self.depth + 1);
+                self.depth += 1;
+                self.start_array_section_element();
+
+            // Continue the last section in the array, if it's not yet finished.
+            } else if self.tables.len() > self.depth + 1 {
+                self.finish_tables(self.depth + 2);
+                self.depth += 2;
+            // Keep track of the section anyway - nothing is written after an error.
+            } else {
+                self.set_error(LuaScriptFromIniError::ContinuedSection(self.path(section)));
+                self.finish_tables(self.depth + 1);
+                self.depth += 1;
+                self.start_array_section_element();
+            }
+
+            return;
+        }
+
+        // Keep track of the array of sections anyway - nothing is written after an error.
+        if !self.start_value(section, LuaScriptKey::SectionArray) || !new {
+            self.set_error(LuaScriptFromIniError::ContinuedSection(self.path(section)));
+        }
+
+        self.write(|w, _| writeln!(w, "{{"));
+
+        self.tables.push(LuaScriptTable::new(Some(section), true));
+        self.depth += 1;
+
+        self.start_array_section_element();
+    }
+
+    fn end_section(&mut self, _section: NonEmptyIniStr<'s, '_>) {
+        debug_assert!(
+            self.depth > 1,
+            "`end_section()` call without a matching `start_section()`"
+        );
+
+        // The tables are only finished when the parser continues with their parent table.
+        self.depth -= 1;
+
+        // End the array of sections as well.
+        if self.tables[self.depth - 1].section_array_len.is_some() {
+            self.depth -= 1;
+        }
+    }
+
+    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        let array = array.as_ne_str();
+
+        if overwrite || !self.start_value(array, LuaScriptKey::Value) {
+            return self.set_error(LuaScriptFromIniError::OverwrittenValue(self.path(array)));
+        }
+
+        self.write(|w, _| writeln!(w, "{{"));
+    }
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        let indent = self.depth as u32 + 1;
+
+        self.write(|w, options| {
+            options.formatter.write_indent(w, indent)?;
+            write_lua_ini_value(w, value, options)?;
+            writeln!(w, ",")
+        });
+    }
+
+    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+        let indent = self.depth as u32;
+
+        self.write(|w, options| {
+            options.formatter.write_indent(w, indent)?;
+            writeln!(w, "}}, -- {}", array.as_ne_str())
+        });
+    }
+}
+
+/// Writes the primitive `.ini` `value` to the writer `w` as a Lua value.
+fn write_lua_ini_value<W: Write>(
+    w: &mut W,
+    value: IniValue<'_, '_>,
+    options: &ToLuaStringOptions,
+) -> std::fmt::Result {
+    match value {
+        IniValue::Bool(value) => w.write_str(if value { "true" } else { "false" }),
+        IniValue::I64(value) => write!(w, "{}", value),
+        IniValue::F64(value) => write_lua_f64(w, value, &options.formatter),
+        IniValue::String(value) => write_lua_string(w, value.as_str(), options.formatter.quote()),
+        // Lua has no native date/time type - serialize as a string.
+        #[cfg(feature = "datetime")]
+        IniValue::DateTime(value) => {
+            let quote = options.formatter.quote();
+            write!(w, "{}{}{}", quote, value, quote)
+        }
+    }
+}
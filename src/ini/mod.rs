@@ -1,12 +1,27 @@
 mod config;
+mod dependencies;
 mod error;
+mod include;
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+mod lua_script;
 mod options;
 mod parser;
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+mod replay;
+mod schema;
+mod spans;
+mod spellings;
 mod util;
 mod value;
 
 #[cfg(all(test, feature = "dyn"))]
 mod tests;
 
-pub use {config::*, error::*, options::*, parser::*, util::*, value::*};
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub(crate) use replay::*;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub use lua_script::LuaScriptFromIni;
+
+pub use {config::*, dependencies::*, error::*, include::*, options::*, parser::*, schema::*, spans::*, spellings::*, util::*, value::*};
 
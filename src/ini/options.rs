@@ -1,6 +1,10 @@
 #![allow(non_upper_case_globals)]
 
-use bitflags::bitflags;
+use {
+    crate::{ConfigFormatter, ConfigNewLine},
+    bitflags::bitflags,
+    std::cmp::Ordering,
+};
 
 bitflags! {
     /// Flags which specify which characters are valid `.ini` config comment delimiters.
@@ -64,6 +68,142 @@ pub enum IniDuplicateKeys {
     Last,
 }
 
+/// Controls how section names / keys which only differ in case are handled in the root / sections of the `.ini` config
+/// if section names / keys are [`case-insensitive`](struct.IniParser.html#method.case_insensitive_keys).
+///
+/// NOTE - duplicate section names / keys with the same spelling are handled according to the
+/// [`duplicate section`](enum.IniDuplicateSections.html) / [`duplicate key`](enum.IniDuplicateKeys.html) policies.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniKeyCollisions {
+    /// Do not allow section names / keys which only differ in case.
+    Forbid,
+    /// Use the first encountered spelling of the section / key in the root / section,
+    /// skip all following ones.
+    First,
+    /// Use the last encountered spelling of the section / key in the root / section,
+    /// overwriting all prior, if any.
+    Last,
+}
+
+/// Determines how the contents of the `.ini` sources [`included`](struct.IniParser.html#method.parse_with_includes)
+/// via the `!include` directive are merged into the config.
+///
+/// NOTE - in both cases duplicate sections / keys are handled according to the
+/// [`duplicate section`](enum.IniDuplicateSections.html) / [`duplicate key`](enum.IniDuplicateKeys.html) policies.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniIncludeMerge {
+    /// The included source is parsed as if its contents were inserted in place of the `!include` directive:
+    /// the keys before the first section in the included source are added to the current section,
+    /// and the last section in the included source remains the current section after the `!include` directive.
+    Inline,
+    /// The included source is parsed starting in the root section;
+    /// the current section is restored after the `!include` directive.
+    Isolated,
+}
+
+/// Controls how unquoted integer values which overflow [`i64`] are handled in the `.ini` config.
+///
+/// [`i64`]: enum.Value.html#variant.I64
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniNumberPolicy {
+    /// Do not allow integer values which overflow [`i64`] -
+    /// an [`IntegerOverflow`](enum.IniErrorKind.html#variant.IntegerOverflow) error is returned.
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    Error,
+    /// Clamp the integer value to the [`i64`] range (i.e. to `i64::MIN` / `i64::MAX`).
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    Saturate,
+    /// Parse the integer value as an [`f64`], losing precision.
+    /// Integer values which overflow [`f64`] as well are not allowed -
+    /// an [`IntegerOverflow`](enum.IniErrorKind.html#variant.IntegerOverflow) error is returned.
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    Float,
+    /// Keep the integer value as an (unquoted) [`string`].
+    /// If unquoted strings are not [`supported`], an [`UnquotedString`] error is returned.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`supported`]: struct.IniParser.html#method.unquoted_strings
+    /// [`UnquotedString`]: enum.IniErrorKind.html#variant.UnquotedString
+    String,
+}
+
+/// Interpolation syntax of the string values, compatible with the Python `configparser` module.
+///
+/// See [`interpolation`](struct.IniParser.html#method.interpolation).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniInterpolation {
+    /// String values are not interpolated.
+    None,
+    /// `%(key)s` references to the values in the same section (or in the `DEFAULT` section);
+    /// `%%` is a literal percent sign (`'%'`).
+    /// Same as `configparser.BasicInterpolation`.
+    Basic,
+    /// `${key}` references to the values in the same section (or in the `DEFAULT` section)
+    /// and `${section:key}` references to the values in other sections;
+    /// `$$` is a literal dollar sign (`'$'`).
+    /// Same as `configparser.ExtendedInterpolation`.
+    Extended,
+}
+
+/// Determines how whitespace in unquoted values is handled by the `.ini` parser.
+///
+/// See [`trailing_whitespace_in_values`](struct.IniParser.html#method.trailing_whitespace_in_values).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniValueWhitespace {
+    /// Whitespace terminates the unquoted value; the rest of the line must be empty or a comment.
+    Terminate,
+    /// Unquoted values extend to the inline comment delimiter or the end of the line,
+    /// keeping the internal whitespace; trailing whitespace is trimmed.
+    Trim,
+    /// Unquoted values extend to the inline comment delimiter or the end of the line,
+    /// keeping the internal and the trailing whitespace.
+    /// The type of the value is inferred from the value with trailing whitespace trimmed;
+    /// trailing whitespace is only kept in string values (e.g. `7  ` is the integer `7`).
+    Preserve,
+}
+
+/// Determines how tables nested in sections are serialized to an `.ini` string.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniNestedTables {
+    /// Nested tables are written as nested sections with `/`-separated section paths (e.g. `[parent/child]`),
+    /// up to [`nested_section_depth`](struct.ToIniStringOptions.html#structfield.nested_section_depth).
+    Sections,
+    /// Nested tables are flattened into their parent (root level) section;
+    /// their keys are prefixed with the nested table keys, joined by the separator character
+    /// (e.g. `parent.child.key = 7` with the `'.'` separator).
+    ///
+    /// NOTE - empty nested tables are skipped.
+    /// `nested_section_depth` is ignored.
+    Flatten(char),
+    /// Nested tables are not allowed - an error is returned if any are encountered.
+    /// `nested_section_depth` is ignored.
+    Error,
+}
+
+/// Determines the order of the section names / keys in each section of an `.ini` string.
+///
+/// Values are always written before the nested sections / arrays of sections, regardless of the order.
+#[derive(Clone, Copy, Debug)]
+pub enum IniKeyOrder {
+    /// Section names / keys are sorted in alphabetical (lexicographic byte-wise) order.
+    Alphabetical,
+    /// Section names / keys are written in the table iteration order -
+    /// for [`binary configs`] this is the order the values were written in by the [`writer`]
+    /// (unless it [`sorted`] the keys), for [`dynamic`] and [`Lua`] configs it is unspecified.
+    ///
+    /// [`binary configs`]: struct.BinConfig.html
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`sorted`]: struct.BinConfigWriter.html#method.sort_keys
+    /// [`dynamic`]: struct.DynConfig.html
+    /// [`Lua`]: struct.LuaConfig.html
+    Insertion,
+    /// Section names / keys are sorted using the comparator function.
+    Custom(fn(&str, &str) -> Ordering),
+}
+
 /// Configuration options for the `.ini` parser.
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct IniOptions {
@@ -95,6 +235,11 @@ pub(crate) struct IniOptions {
     ///
     /// Default: `true`.
     pub(crate) unquoted_strings: bool,
+    /// Whether unquoted values are not interpreted as booleans / integers / floats (/ date/times) (strict mode).
+    /// If `true`, all values are strings.
+    ///
+    /// Default: `false`.
+    pub(crate) strict_values: bool,
     /// Whether escape sequences (a character sequence following a backslash ('\'))
     /// in keys, section names and string values are supported.
     /// If `true`, the following escape sequences are supported:
@@ -158,6 +303,98 @@ pub(crate) struct IniOptions {
     ///
     /// Default: `false`.
     pub(crate) implicit_parent_sections: bool,
+    /// Whether section names / keys are case-insensitive.
+    /// If `true`, section names / keys are converted to lowercase.
+    ///
+    /// Default: `false`.
+    pub(crate) case_insensitive_keys: bool,
+    /// Policy for case-insensitive section names / keys which only differ in case.
+    /// If `case_insensitive_keys` is `false`, this value is ignored.
+    ///
+    /// Default: [`Forbid`](enum.IniKeyCollisions.html#variant.Forbid).
+    pub(crate) key_collisions: IniKeyCollisions,
+    /// How the contents of the included `.ini` sources are merged into the config.
+    ///
+    /// Default: [`Inline`](enum.IniIncludeMerge.html#variant.Inline).
+    pub(crate) include_merge: IniIncludeMerge,
+    /// Whether the source spans of the keys / values are reported to the event handler
+    /// via `add_value_with_spans` instead of `add_value`.
+    ///
+    /// Default: `false`.
+    pub(crate) spans: bool,
+    /// Whether arrays of sections are supported.
+    /// If `true`, section names enclosed in double brackets `"[["` \ `"]]"` start a new section
+    /// in the array of sections with this name.
+    ///
+    /// Default: `false`.
+    pub(crate) array_sections: bool,
+    /// Policy for unquoted integer values which overflow `i64`.
+    ///
+    /// Default: [`Float`](enum.IniNumberPolicy.html#variant.Float).
+    pub(crate) numbers: IniNumberPolicy,
+    /// Whether quoted string values may span multiple lines.
+    /// If `true`, new lines in quoted string values are preserved as line feeds (`'\n'`).
+    ///
+    /// Default: `false`.
+    pub(crate) multiline_strings: bool,
+    /// Whether raw string values, enclosed in backticks (`` '`' ``), are supported.
+    /// Backslashes (`'\\'`) in raw strings are not treated as escape sequences.
+    ///
+    /// Default: `false`.
+    pub(crate) raw_strings: bool,
+    /// Whether value type annotations (e.g. `key:int = 7`) are supported.
+    ///
+    /// Default: `false`.
+    pub(crate) type_annotations: bool,
+    /// Whether unquoted values of the form `@{<path>}` are parsed as references to other values.
+    ///
+    /// Default: `false`.
+    pub(crate) value_references: bool,
+    /// Interpolation syntax of the string values.
+    ///
+    /// Default: [`None`](enum.IniInterpolation.html#variant.None).
+    pub(crate) interpolation: IniInterpolation,
+    /// How whitespace in unquoted values is handled.
+    ///
+    /// Default: [`Terminate`](enum.IniValueWhitespace.html#variant.Terminate).
+    pub(crate) value_whitespace: IniValueWhitespace,
+    /// Whether colons (`':'`) are allowed in unquoted values without being escaped.
+    ///
+    /// Default: `false`.
+    pub(crate) colons_in_values: bool,
+    /// Whether (non-array, non-annotated) values are passed to the event handler unparsed, with a value type hint,
+    /// via `add_raw_value` instead of `add_value`.
+    ///
+    /// Default: `false`.
+    pub(crate) raw_values: bool,
+    /// Number of columns between the tab stops, used to report the source positions.
+    ///
+    /// Default: `1`.
+    pub(crate) tab_width: u32,
+    /// Maximum length in bytes of the section names / keys, if any.
+    ///
+    /// Default: `None`.
+    pub(crate) max_key_len: Option<u32>,
+    /// Maximum length in bytes of the values (incl. array values), if any.
+    ///
+    /// Default: `None`.
+    pub(crate) max_value_len: Option<u32>,
+    /// Maximum number of values in the arrays, if any.
+    ///
+    /// Default: `None`.
+    pub(crate) max_array_len: Option<u32>,
+    /// Maximum number of sections, if any.
+    ///
+    /// Default: `None`.
+    pub(crate) max_sections: Option<u32>,
+    /// Maximum total length in bytes of the parsed `.ini` source (incl. the included sources), if any.
+    ///
+    /// Default: `None`.
+    pub(crate) max_size: Option<usize>,
+    /// Maximum estimated memory usage in bytes of the config built by `DynConfig::from_ini()`, if any.
+    ///
+    /// Default: `None`.
+    pub(crate) max_memory_usage: Option<usize>,
 }
 
 impl Default for IniOptions {
@@ -168,6 +405,7 @@ impl Default for IniOptions {
             key_value_separator: IniKeyValueSeparator::Equals,
             string_quotes: IniStringQuote::Double,
             unquoted_strings: true,
+            strict_values: false,
             escape: true,
             line_continuation: false,
             duplicate_sections: IniDuplicateSections::Merge,
@@ -175,6 +413,27 @@ impl Default for IniOptions {
             arrays: false,
             nested_section_depth: 1,
             implicit_parent_sections: false,
+            case_insensitive_keys: false,
+            key_collisions: IniKeyCollisions::Forbid,
+            include_merge: IniIncludeMerge::Inline,
+            spans: false,
+            array_sections: false,
+            numbers: IniNumberPolicy::Float,
+            multiline_strings: false,
+            raw_strings: false,
+            type_annotations: false,
+            value_references: false,
+            interpolation: IniInterpolation::None,
+            value_whitespace: IniValueWhitespace::Terminate,
+            colons_in_values: false,
+            raw_values: false,
+            tab_width: 1,
+            max_key_len: None,
+            max_value_len: None,
+            max_array_len: None,
+            max_sections: None,
+            max_size: None,
+            max_memory_usage: None,
         }
     }
 }
@@ -183,6 +442,8 @@ impl Default for IniOptions {
 pub(super) enum StringQuote {
     Single,
     Double,
+    /// Raw string backticks - no escape sequences.
+    Raw,
 }
 
 impl IniOptions {
@@ -209,12 +470,19 @@ impl IniOptions {
                     .contains(IniKeyValueSeparator::Colon))
     }
 
+    /// Are type annotations enabled and is the character a type annotation delimiter?
+    pub(super) fn is_type_annotation_char(&self, val: char) -> bool {
+        self.type_annotations && (val == ':')
+    }
+
     /// Is the character a supported string quote?
     pub(super) fn is_string_quote_char(&self, val: char) -> Option<StringQuote> {
         if (val == '"') && self.string_quotes.contains(IniStringQuote::Double) {
             Some(StringQuote::Double)
         } else if (val == '\'') && self.string_quotes.contains(IniStringQuote::Single) {
             Some(StringQuote::Single)
+        } else if (val == '`') && self.raw_strings {
+            Some(StringQuote::Raw)
         } else {
             None
         }
@@ -244,6 +512,12 @@ impl IniOptions {
         self.escape && (val == '\\')
     }
 
+    /// Is the character a supported escape character in a string enclosed in `quote`'s?
+    /// Escape sequences are not supported in raw strings.
+    pub(super) fn is_quoted_escape_char(&self, quote: StringQuote, val: char) -> bool {
+        (quote != StringQuote::Raw) && self.is_escape_char(val)
+    }
+
     /// Is the character a section start delimiter?
     pub(super) fn is_section_start(&self, val: char) -> bool {
         val == '['
@@ -295,6 +569,27 @@ impl IniOptions {
         Self::is_key_or_value_char_impl(c, self.escape, self.nested_sections(), in_section, quote)
     }
 
+    /// Returns `true` if the `c` character is a valid character in an unquoted value (after its first character)
+    /// and does not have to be escaped.
+    /// Same as `is_key_or_value_char()`, except that if `"datetime"` feature is enabled,
+    /// colons (`':'`) are also allowed to support unquoted date/time values (e.g. `2024-01-01T10:00:00Z`),
+    /// as well as for the extended interpolation syntax (e.g. `${section:key}`)
+    /// and if colons in values are explicitly allowed.
+    pub(super) fn is_unquoted_value_char(&self, c: char) -> bool {
+        self.is_key_or_value_char(c, false, None)
+            || ((cfg!(feature = "datetime")
+                || (self.interpolation == IniInterpolation::Extended)
+                || self.colons_in_values)
+                && c == ':')
+    }
+
+    /// Returns `true` if the `c` character is a valid first character of an unquoted value
+    /// and does not have to be escaped.
+    /// Same as `is_key_or_value_char()`, except that colons (`':'`) are allowed if colons in values are explicitly allowed.
+    pub(super) fn is_unquoted_value_start_char(&self, c: char) -> bool {
+        self.is_key_or_value_char(c, false, None) || (self.colons_in_values && c == ':')
+    }
+
     /// Returns `true` if the `c` character is a valid key/value/section name character and does not have to be escaped.
     /// Otherwise, `c` must be escaped (preceded by a backslash) when used in keys/values/section names.
     pub(super) fn is_key_or_value_char_impl(
@@ -305,12 +600,13 @@ impl IniOptions {
         quote: Option<StringQuote>,
     ) -> bool {
         match c {
-            // Escape char (backslash) must be escaped if escape sequences are supported.
-            '\\' if escape => false,
+            // Escape char (backslash) must be escaped if escape sequences are supported,
+            // except in raw strings.
+            '\\' if escape => quote == Some(StringQuote::Raw),
 
             // Non-matching quotes don't need to be escaped in quoted strings.
-            '"' => quote == Some(StringQuote::Single),
-            '\'' => quote == Some(StringQuote::Double),
+            '"' => matches!(quote, Some(StringQuote::Single) | Some(StringQuote::Raw)),
+            '\'' => matches!(quote, Some(StringQuote::Double) | Some(StringQuote::Raw)),
 
             // Space and special `.ini` characters in key/value/section strings
             // (except string quotes, handled above) don't need to be escaped in quoted strings.
@@ -343,6 +639,58 @@ pub struct ToIniStringOptions {
     ///
     /// Default: `false`.
     pub implicit_parent_sections: bool,
+    /// How tables nested in sections are serialized.
+    ///
+    /// Default: [`Sections`](enum.IniNestedTables.html#variant.Sections).
+    pub nested_tables: IniNestedTables,
+    /// See [`array_sections`](struct.IniParser.html#method.array_sections).
+    /// If `true`, arrays of tables are serialized as arrays of sections, one `[[section]]` per table.
+    /// Tables in arrays of sections may not contain nested tables, unless they are
+    /// [`flattened`](enum.IniNestedTables.html#variant.Flatten).
+    ///
+    /// Default: `false`.
+    pub array_sections: bool,
+    /// See [`multiline_strings`](struct.IniParser.html#method.multiline_strings).
+    /// If `true`, line feeds (`'\n'`) in string values are not escaped and are written as new lines
+    /// (using the [`configured`](struct.ConfigFormatter.html#structfield.new_line) new line sequence).
+    ///
+    /// Default: `false`.
+    pub multiline_strings: bool,
+    /// See [`raw_strings`](struct.IniParser.html#method.raw_strings).
+    /// If `true`, string values which contain backslashes (`'\\'`) are serialized as raw strings
+    /// enclosed in backticks (`` '`' ``), unless they contain backticks or characters which must be escaped.
+    ///
+    /// Default: `false`.
+    pub raw_strings: bool,
+    /// See [`unquoted_strings`](struct.IniParser.html#method.unquoted_strings).
+    /// If `true`, string values which only contain characters valid in unquoted values
+    /// (e.g. no spaces, quotes or `.ini` special characters) are written unquoted.
+    ///
+    /// NOTE - unquoted string values which look like booleans / numbers (e.g. `true` / `7`)
+    /// are only parsed back as strings by the [`parser`] in [`strict mode`].
+    ///
+    /// Default: `false`.
+    ///
+    /// [`parser`]: struct.IniParser.html
+    /// [`strict mode`]: struct.IniParser.html#method.strict_values
+    pub unquoted_strings: bool,
+    /// Order of the section names / keys in each section.
+    ///
+    /// Default: [`Alphabetical`](enum.IniKeyOrder.html#variant.Alphabetical).
+    pub key_order: IniKeyOrder,
+    /// Number of blank lines written before each section (except at the start of the `.ini` string).
+    ///
+    /// Default: `1`.
+    pub blank_lines_between_sections: u32,
+    /// If `true`, the key-value separators (`'='`) are surrounded by spaces (i.e. `key = value`);
+    /// otherwise they are not (i.e. `key=value`).
+    ///
+    /// Default: `true`.
+    pub space_around_separator: bool,
+    /// Output style options.
+    ///
+    /// Default: default [`formatter`](struct.ConfigFormatter.html).
+    pub formatter: ConfigFormatter,
 }
 
 impl Default for ToIniStringOptions {
@@ -352,13 +700,113 @@ impl Default for ToIniStringOptions {
             arrays: false,
             nested_section_depth: 1,
             implicit_parent_sections: false,
+            nested_tables: IniNestedTables::Sections,
+            array_sections: false,
+            multiline_strings: false,
+            raw_strings: false,
+            unquoted_strings: false,
+            key_order: IniKeyOrder::Alphabetical,
+            blank_lines_between_sections: 1,
+            space_around_separator: true,
+            formatter: ConfigFormatter::default(),
         }
     }
 }
 
 impl ToIniStringOptions {
+    /// Returns the canonical serialization options, used to serialize configs to `.ini` strings reproducibly
+    /// (e.g. to hash the serialized configs): escape sequences, arrays, arrays of sections
+    /// and unlimited nested sections are supported, strings are always quoted and escaped
+    /// (no multi-line / raw strings), with the [`canonical`](struct.ConfigFormatter.html#method.canonical) output style.
+    ///
+    /// Section names / keys are always serialized in sorted order, so logically equal configs
+    /// are always serialized to identical strings with these options, regardless of the config backend.
+    ///
+    /// NOTE - the serialized configs must be parsed with the [`parser`] configured to support
+    /// arrays, arrays of sections and nested sections.
+    ///
+    /// [`parser`]: struct.IniParser.html
+    pub fn canonical() -> Self {
+        Self {
+            escape: true,
+            arrays: true,
+            nested_section_depth: u32::MAX,
+            implicit_parent_sections: false,
+            nested_tables: IniNestedTables::Sections,
+            array_sections: true,
+            multiline_strings: false,
+            raw_strings: false,
+            unquoted_strings: false,
+            key_order: IniKeyOrder::Alphabetical,
+            blank_lines_between_sections: 1,
+            space_around_separator: true,
+            formatter: ConfigFormatter::canonical(),
+        }
+    }
+
+    /// Returns the serialization options matching the `.ini` dialect of the Windows `WritePrivateProfileString` API,
+    /// e.g. to write the legacy `.ini` files in the format expected by it:
+    /// no escape sequences, arrays or nested sections, unquoted string values (where possible),
+    /// no spaces around the key-value separators (i.e. `key=value`), no blank lines between the sections
+    /// and carriage return / line feed (`"\r\n"`) new lines.
+    ///
+    /// NOTE - the serialized configs should be parsed with the [`parser`] [`configured`] for this dialect.
+    ///
+    /// [`parser`]: struct.IniParser.html
+    /// [`configured`]: struct.IniParser.html#method.windows_compat
+    pub fn windows_compat() -> Self {
+        Self {
+            escape: false,
+            arrays: false,
+            nested_section_depth: 1,
+            implicit_parent_sections: false,
+            nested_tables: IniNestedTables::Sections,
+            array_sections: false,
+            multiline_strings: false,
+            raw_strings: false,
+            unquoted_strings: true,
+            key_order: IniKeyOrder::Alphabetical,
+            blank_lines_between_sections: 0,
+            space_around_separator: false,
+            formatter: ConfigFormatter {
+                new_line: ConfigNewLine::CrLf,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
     pub(crate) fn nested_sections(&self) -> bool {
-        self.nested_section_depth > 1
+        (self.nested_tables == IniNestedTables::Sections) && (self.nested_section_depth > 1)
+    }
+
+    /// Compares the section names / keys `l` and `r` according to the [`key order`](enum.IniKeyOrder.html).
+    #[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+    pub(crate) fn cmp_keys(&self, l: &str, r: &str) -> Ordering {
+        match self.key_order {
+            IniKeyOrder::Alphabetical => l.cmp(r),
+            // Sorting is stable.
+            IniKeyOrder::Insertion => Ordering::Equal,
+            IniKeyOrder::Custom(cmp) => cmp(l, r),
+        }
+    }
+
+    /// Returns the key-value separator, with or without the surrounding spaces.
+    #[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+    pub(crate) fn key_value_separator(&self) -> &'static str {
+        if self.space_around_separator {
+            " = "
+        } else {
+            "="
+        }
+    }
+
+    #[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+    pub(crate) fn flatten_separator(&self) -> Option<char> {
+        match self.nested_tables {
+            IniNestedTables::Flatten(separator) => Some(separator),
+            _ => None,
+        }
     }
 }
 
@@ -429,6 +877,22 @@ mod tests {
             /* quote */ Some(StringQuote::Double)
         ));
 
+        // Backslashes must be escaped if escape sequences are supported, except in raw strings.
+        assert!(!IniOptions::is_key_or_value_char_impl(
+            '\\',
+            /* escape */ true,
+            /* nested_sections */ false,
+            /* in_section */ false,
+            /* quote */ Some(StringQuote::Double)
+        ));
+        assert!(IniOptions::is_key_or_value_char_impl(
+            '\\',
+            /* escape */ true,
+            /* nested_sections */ false,
+            /* in_section */ false,
+            /* quote */ Some(StringQuote::Raw)
+        ));
+
         // .ini special chars are only valid when quoted.
         let assert_ini_char = |c| {
             assert!(!IniOptions::is_key_or_value_char_impl(
@@ -13,6 +13,7 @@ pub(super) enum IniParserFSMState {
     StartLine,
     /// We encountered a section start delimiter (or a nested section separator) and started parsing a (nested) section name.
     /// Accept whitespace (except new lines),
+    /// second section start delimiter (`'['`) (if arrays of sections are supported),
     /// string quotes (`'"'` / `'\'`') (if supported) (-> QuotedSection),
     /// escape sequences (if supported) (-> Section),
     /// valid key chars (-> Section).
@@ -37,6 +38,10 @@ pub(super) enum IniParserFSMState {
     /// section end delimiters (`']'`) (-> SkipLineWhitespaceOrComments),
     /// nested section separators (`'/'`) (if supported) -> (BeforeSection),
     AfterSection,
+    /// We finished parsing an array of sections name and its first section end delimiter
+    /// and expect the second section end delimiter.
+    /// Accept section end delimiters (`']'`) (-> SkipLineWhitespaceOrComments).
+    ArraySectionEnd,
     /// We encountered a comment delimiter and skip the rest of the line.
     /// Accept new lines (-> StartLine),
     /// skip everything else.
@@ -49,6 +54,7 @@ pub(super) enum IniParserFSMState {
     /// We started parsing an unquoted key.
     /// Accept valid key chars,
     /// escape sequences (if supported),
+    /// type annotation delimiters (`':'`) (if supported) (-> TypeAnnotation),
     /// key-value separators (`'='` / `':'`) (-> BeforeValue),
     /// whitespace (except new lines) (-> KeyValueSeparator).
     Key,
@@ -61,9 +67,15 @@ pub(super) enum IniParserFSMState {
     /// Contains the opening quote.
     QuotedKey(StringQuote),
     /// We finished parsing a key and expect a key-value separator.
-    /// Accept key-value separators (`'='` / `':'`) (-> BeforeValue),
+    /// Accept type annotation delimiters (`':'`) (if supported and the key has no type annotation yet) (-> TypeAnnotation),
+    /// key-value separators (`'='` / `':'`) (-> BeforeValue),
     /// whitespace (except new lines).
     KeyValueSeparator,
+    /// We finished parsing a key and a type annotation delimiter and expect a type annotation.
+    /// Accept alphanumeric chars,
+    /// key-value separators (`'='` / `':'`) (-> BeforeValue),
+    /// whitespace (except new lines) (-> KeyValueSeparator, if the type annotation is not empty).
+    TypeAnnotation,
     /// We finished parsing a key-value separator and expect a value (or a new line).
     /// Accept whitespace (except new lines (->StartLine)),
     /// inline comment delimiters (`';'` / `'#'`) (if supported) (-> SkipLine),
@@ -87,6 +99,11 @@ pub(super) enum IniParserFSMState {
     /// valid value chars.
     /// Contains the opening quote.
     QuotedValue(StringQuote),
+    /// We parsed a carriage return (`'\r'`) in a multi-line quoted value.
+    /// Accept a line feed (`'\n'`) (-> QuotedValue),
+    /// everything else as in `QuotedValue`.
+    /// Contains the opening quote.
+    QuotedValueCarriageReturn(StringQuote),
     /// We started parsing an array, or finished parsing a previous array value and separator,
     /// and expect the new value or the end of the array.
     /// Accept whitespace (except new lines),
@@ -122,7 +139,7 @@ pub(super) enum IniParserFSMState {
 }
 
 impl IniParserFSMState {
-    /// Processes the next char `c`.
+    /// Processes the next char `c` at byte offset `idx` in the source chunk and at `position` in the source.
     /// May call `next` to request up to 4 new chars if ASCII / Unicode hex escape sequences are supported.
     /// Returns the new parser state or an error.
     /// The error tuple contains a boolean, which, if `true`, indicates the error location column must be offset
@@ -131,6 +148,7 @@ impl IniParserFSMState {
         self,
         c: char,
         idx: usize,
+        position: IniCharPosition,
         next: N,
         substr: S,
         config: &mut C,
@@ -138,7 +156,7 @@ impl IniParserFSMState {
         options: &IniOptions,
     ) -> Result<IniParserFSMState, (IniErrorKind, bool)>
     where
-        C: IniConfig<'s>,
+        C: IniConfig<'s> + ?Sized,
         N: NextChar,
         S: Substr<'s>,
     {
@@ -151,6 +169,10 @@ impl IniParserFSMState {
 
                 state.is_key_unique = true;
                 state.skip_value = false;
+                state.type_annotation = None;
+
+                // The key, if any, starts at the last char processed in this state.
+                state.key_span = position.span();
 
                 // Skip whitespace at the start of the line (including new lines).
                 if c.is_whitespace() {
@@ -167,6 +189,7 @@ impl IniParserFSMState {
                     state.clear_path(config);
 
                     state.skip_section = false;
+                    state.array_section = false;
 
                     IniParserFSMState::BeforeSection
 
@@ -252,6 +275,15 @@ impl IniParserFSMState {
                 } else if options.is_section_end(c) {
                     return Err((EmptySectionName, false));
 
+                // Second section start delimiter (if supported) - parse the array of sections name.
+                } else if options.array_sections
+                    && options.is_section_start(c)
+                    && state.path.is_empty()
+                    && !state.array_section
+                {
+                    state.array_section = true;
+                    self
+
                 // Else an error.
                 } else {
                     return Err((InvalidCharacterInSectionName(c), false));
@@ -267,6 +299,11 @@ impl IniParserFSMState {
 
                 // Nested section separator (if supported) - finish the current section, keep parsing the nested section.
                 } else if options.is_nested_section_separator(c) {
+                    map_section_name(state, &substr);
+                    fold_parent_section_case(state, &substr, options);
+                    validate_key(state, &substr, options, true)
+                        .map_err(|error_kind| (error_kind, false))?;
+
                     // Must succeed.
                     let section = unwrap_unchecked(state.key.key(&substr), "empty section name");
 
@@ -321,15 +358,32 @@ impl IniParserFSMState {
                 } else if options.is_section_end(c) {
                     debug_assert!(state.path.len() <= options.nested_section_depth);
 
+                    map_section_name(state, &substr);
+
+                    let options = &fold_key_case(state, &substr, options)
+                        .map_err(|error_kind| (error_kind, false))?;
+
+                    validate_key(state, &substr, options, true)
+                        .map_err(|error_kind| (error_kind, false))?;
+                    count_section(state, options).map_err(|error_kind| (error_kind, false))?;
+
                     // Must succeed.
                     let section = unwrap_unchecked(state.key.key(&substr), "empty section name");
 
                     // Try to add the section to the config at the current path.
                     state.path.push(section);
-                    state.skip_section = start_section(config, section, options)?;
+
+                    let next_state = if state.array_section {
+                        start_array_section(config, section)?;
+                        IniParserFSMState::ArraySectionEnd
+                    } else {
+                        state.skip_section = start_section(config, section, options)?;
+                        IniParserFSMState::SkipLineWhitespaceOrComments
+                    };
+
                     state.key.clear();
 
-                    IniParserFSMState::SkipLineWhitespaceOrComments
+                    next_state
 
                 // Whitespace after section name (new lines handled above) - skip it,
                 // parse the nested section separator or the section end delimiter.
@@ -353,7 +407,7 @@ impl IniParserFSMState {
                     IniParserFSMState::AfterSection
 
                 // Escaped char (if supported) - keep parsing the section name.
-                } else if options.is_escape_char(c) {
+                } else if options.is_quoted_escape_char(quote, c) {
                     match try_parse_escape_sequence(next, false, options)? {
                         // Parsed an escaped char - keep parsing the (now owned) section name.
                         ParseEscapeSequenceResult::EscapedChar(c) => {
@@ -396,21 +450,46 @@ impl IniParserFSMState {
 
                 // Section end delimiter - skip the rest of the line.
                 } else if options.is_section_end(c) {
+                    map_section_name(state, &substr);
+
+                    let options = &fold_key_case(state, &substr, options)
+                        .map_err(|error_kind| (error_kind, false))?;
+
+                    validate_key(state, &substr, options, true)
+                        .map_err(|error_kind| (error_kind, false))?;
+
                     // Empty section names are not allowed.
-                    let section = state
-                        .key
-                        .key(&substr)
-                        .ok_or_else(|| (EmptySectionName, true))?;
+                    if state.key.is_empty() {
+                        return Err((EmptySectionName, true));
+                    }
+
+                    count_section(state, options).map_err(|error_kind| (error_kind, false))?;
+
+                    // Must succeed.
+                    let section = unwrap_unchecked(state.key.key(&substr), "empty section name");
 
                     // Try to add the section to the config at the current path.
                     state.path.push(section);
-                    state.skip_section = start_section(config, section, options)?;
+
+                    let next_state = if state.array_section {
+                        start_array_section(config, section)?;
+                        IniParserFSMState::ArraySectionEnd
+                    } else {
+                        state.skip_section = start_section(config, section, options)?;
+                        IniParserFSMState::SkipLineWhitespaceOrComments
+                    };
+
                     state.key.clear();
 
-                    IniParserFSMState::SkipLineWhitespaceOrComments
+                    next_state
 
                 // Nested section separator (if supported) - start parsing the nested section name.
                 } else if options.is_nested_section_separator(c) {
+                    map_section_name(state, &substr);
+                    fold_parent_section_case(state, &substr, options);
+                    validate_key(state, &substr, options, true)
+                        .map_err(|error_kind| (error_kind, false))?;
+
                     // Empty section names are not allowed.
                     let section = state
                         .key
@@ -455,6 +534,20 @@ impl IniParserFSMState {
                     return Err((InvalidCharacterAfterSectionName(c), false));
                 }
             }
+            IniParserFSMState::ArraySectionEnd => {
+                // Second section end delimiter - skip the rest of the line.
+                if options.is_section_end(c) {
+                    IniParserFSMState::SkipLineWhitespaceOrComments
+
+                // New line before the second section end delimiter - error.
+                } else if options.is_new_line(c) {
+                    return Err((UnexpectedNewLineInSectionName, true));
+
+                // Else an error.
+                } else {
+                    return Err((InvalidCharacterAfterSectionName(c), false));
+                }
+            }
             IniParserFSMState::SkipLine => {
                 debug_assert!(state.key.is_empty());
                 debug_assert!(state.value.is_empty());
@@ -493,8 +586,17 @@ impl IniParserFSMState {
                 debug_assert!(!state.key.is_empty());
                 debug_assert!(state.value.is_empty());
 
-                // Key-value separator - finish the key, parse the value.
-                if options.is_key_value_separator_char(c) {
+                // Key-value separator or type annotation delimiter (if supported) - finish the key,
+                // parse the value / type annotation.
+                if options.is_type_annotation_char(c) || options.is_key_value_separator_char(c) {
+                    state.key_span.end = position.offset;
+
+                    let options = &fold_key_case(state, &substr, options)
+                        .map_err(|error_kind| (error_kind, true))?;
+
+                    validate_key(state, &substr, options, false)
+                        .map_err(|error_kind| (error_kind, true))?;
+
                     // Must succeed.
                     let key = unwrap_unchecked(state.key.key(&substr), "empty key");
                     state.path.push(key);
@@ -508,7 +610,11 @@ impl IniParserFSMState {
                         options.duplicate_keys,
                     )?;
 
-                    IniParserFSMState::BeforeValue
+                    if options.is_type_annotation_char(c) {
+                        IniParserFSMState::TypeAnnotation
+                    } else {
+                        IniParserFSMState::BeforeValue
+                    }
 
                 // Whitespace between the key and the separator - skip it, finish the key, parse the separator.
                 } else if c.is_whitespace() {
@@ -517,6 +623,14 @@ impl IniParserFSMState {
                         return Err((UnexpectedNewLineInKey, true));
                     }
 
+                    state.key_span.end = position.offset;
+
+                    let options = &fold_key_case(state, &substr, options)
+                        .map_err(|error_kind| (error_kind, true))?;
+
+                    validate_key(state, &substr, options, false)
+                        .map_err(|error_kind| (error_kind, true))?;
+
                     // Must succeed.
                     let key = unwrap_unchecked(state.key.key(&substr), "empty key");
                     state.path.push(key);
@@ -566,6 +680,14 @@ impl IniParserFSMState {
 
                 // Closing quotes - finish the key, parse the separator.
                 } else if options.is_matching_string_quote_char(quote, c) {
+                    state.key_span.end = position.offset + c.len_utf8();
+
+                    let options = &fold_key_case(state, &substr, options)
+                        .map_err(|error_kind| (error_kind, true))?;
+
+                    validate_key(state, &substr, options, false)
+                        .map_err(|error_kind| (error_kind, true))?;
+
                     // Empty keys are not allowed.
                     let key = state.key.key(&substr).ok_or_else(|| (EmptyKey, false))?;
                     state.path.push(key);
@@ -582,7 +704,7 @@ impl IniParserFSMState {
                     IniParserFSMState::KeyValueSeparator
 
                 // Escaped char (if supported) - keep parsing the key.
-                } else if options.is_escape_char(c) {
+                } else if options.is_quoted_escape_char(quote, c) {
                     match try_parse_escape_sequence(next, false, options)? {
                         // Parsed an escaped char - keep parsing the (now owned) key.
                         ParseEscapeSequenceResult::EscapedChar(c) => {
@@ -616,8 +738,12 @@ impl IniParserFSMState {
                 debug_assert!(state.value.is_empty());
                 debug_assert!(!state.path.is_empty());
 
+                // Type annotation delimiter (if supported) - parse the type annotation (key already finished).
+                if options.is_type_annotation_char(c) && state.type_annotation.is_none() {
+                    IniParserFSMState::TypeAnnotation
+
                 // Key-value separator - parse the value (key already finished).
-                if options.is_key_value_separator_char(c) {
+                } else if options.is_key_value_separator_char(c) {
                     IniParserFSMState::BeforeValue
 
                 // Skip the whitespace between the key and the separator.
@@ -634,11 +760,60 @@ impl IniParserFSMState {
                     return Err((InvalidKeyValueSeparator(c), false));
                 }
             }
+            IniParserFSMState::TypeAnnotation => {
+                debug_assert!(!state.key.is_empty());
+                debug_assert!(!state.path.is_empty());
+
+                // Key-value separator - finish the type annotation, parse the value.
+                if options.is_key_value_separator_char(c) {
+                    state.type_annotation = Some(
+                        parse_type_annotation(state.value.value(&substr).as_str())
+                            .map_err(|error_kind| (error_kind, true))?,
+                    );
+                    state.value.clear();
+
+                    IniParserFSMState::BeforeValue
+
+                // Whitespace.
+                } else if c.is_whitespace() {
+                    // Unless it's a new line.
+                    if options.is_new_line(c) {
+                        return Err((UnexpectedNewLineInKey, true));
+
+                    // Skip the whitespace before the type annotation.
+                    } else if state.value.is_empty() {
+                        self
+
+                    // Finish the type annotation, parse the separator.
+                    } else {
+                        state.type_annotation = Some(
+                            parse_type_annotation(state.value.value(&substr).as_str())
+                                .map_err(|error_kind| (error_kind, true))?,
+                        );
+                        state.value.clear();
+
+                        IniParserFSMState::KeyValueSeparator
+                    }
+
+                // Alphanumeric char - keep parsing the type annotation.
+                } else if c.is_alphanumeric() {
+                    state.value.push(c, idx);
+                    self
+
+                // Else an error.
+                } else {
+                    return Err((InvalidCharacterInKey(c), false));
+                }
+            }
             IniParserFSMState::BeforeValue => {
                 debug_assert!(!state.key.is_empty());
                 debug_assert!(state.value.is_empty());
                 debug_assert!(!state.path.is_empty());
 
+                // The value starts at the last char processed in this state
+                // (or is empty at the new line / inline comment delimiter).
+                state.value_span = position.span();
+
                 // Skip the whitespace before the value.
                 if c.is_whitespace() {
                     // Unless it's a new line - the value is empty.
@@ -651,7 +826,9 @@ impl IniParserFSMState {
                             false,
                             state.skip_section | state.skip_value,
                             state.is_key_unique,
-                            options.unquoted_strings,
+                            state.value_spans(),
+                            state.type_annotation,
+                            options,
                         )
                         .map_err(|error_kind| (error_kind, false))?;
 
@@ -673,7 +850,9 @@ impl IniParserFSMState {
                         false,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
-                        options.unquoted_strings,
+                        state.value_spans(),
+                        state.type_annotation,
+                        options,
                     )
                     .map_err(|error_kind| (error_kind, false))?;
 
@@ -709,13 +888,14 @@ impl IniParserFSMState {
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
                     );
+                    state.array_len = 0;
 
                     //state.path.push(array_key);
 
                     IniParserFSMState::BeforeArrayValue(None)
 
                 // Valid value char - start parsing the unquoted value.
-                } else if options.is_key_or_value_char(c, false, None) {
+                } else if options.is_unquoted_value_start_char(c) {
                     state.value.push(c, idx);
                     IniParserFSMState::Value
 
@@ -729,8 +909,19 @@ impl IniParserFSMState {
                 debug_assert!(!state.value.is_empty());
                 debug_assert!(!state.path.is_empty());
 
+                // Whitespace (if it does not terminate the value) - keep parsing the value.
+                if c.is_whitespace()
+                    && !options.is_new_line(c)
+                    && options.value_whitespace != IniValueWhitespace::Terminate
+                {
+                    state.value.push(c, idx);
+                    state.value_trailing_whitespace += c.len_utf8();
+                    self
+
                 // Whitespace - finish the value.
-                if c.is_whitespace() {
+                } else if c.is_whitespace() {
+                    state.end_unquoted_value(position.offset, &substr, options);
+
                     add_value_to_config(
                         config,
                         // Must succeed.
@@ -739,7 +930,9 @@ impl IniParserFSMState {
                         false,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
-                        options.unquoted_strings,
+                        state.value_spans(),
+                        state.type_annotation,
+                        options,
                     )
                     .map_err(|error_kind| (error_kind, false))?;
 
@@ -758,6 +951,8 @@ impl IniParserFSMState {
 
                 // Inline comment (if supported) - finish the value, skip the rest of the line.
                 } else if options.is_inline_comment_char(c) {
+                    state.end_unquoted_value(position.offset, &substr, options);
+
                     add_value_to_config(
                         config,
                         // Must succeed.
@@ -766,7 +961,9 @@ impl IniParserFSMState {
                         false,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
-                        options.unquoted_strings,
+                        state.value_spans(),
+                        state.type_annotation,
+                        options,
                     )
                     .map_err(|error_kind| (error_kind, false))?;
 
@@ -782,6 +979,7 @@ impl IniParserFSMState {
                         // Parsed an escaped char - keep parsing the (now owned) value.
                         ParseEscapeSequenceResult::EscapedChar(c) => {
                             state.value.push_owned(c, substr);
+                            state.value_trailing_whitespace = 0;
                         }
                         // Line continuation - keep parsing the (now owned) value.
                         ParseEscapeSequenceResult::LineContinuation => {
@@ -792,8 +990,9 @@ impl IniParserFSMState {
                     self
 
                 // Valid value char - keep parsing the value.
-                } else if options.is_key_or_value_char(c, false, None) {
+                } else if options.is_unquoted_value_char(c) {
                     state.value.push(c, idx);
+                    state.value_trailing_whitespace = 0;
                     self
 
                 // Else an error.
@@ -805,12 +1004,28 @@ impl IniParserFSMState {
                 debug_assert!(!state.key.is_empty());
                 debug_assert!(!state.path.is_empty());
 
-                // New line before the closing quotes - error.
+                // New line before the closing quotes - error, unless multi-line strings are supported.
                 if options.is_new_line(c) {
-                    return Err((UnexpectedNewLineInQuotedValue, true));
+                    if !options.multiline_strings {
+                        return Err((UnexpectedNewLineInQuotedValue, true));
+                    }
+
+                    // Line feed - keep parsing the quoted value.
+                    if c == '\n' {
+                        state.value.push(c, idx);
+                        self
+
+                    // Carriage return - normalize to a line feed, keep parsing the (now owned) quoted value,
+                    // skipping the following line feed, if any.
+                    } else {
+                        state.value.push_owned('\n', substr);
+                        IniParserFSMState::QuotedValueCarriageReturn(quote)
+                    }
 
                 // Closing quotes - finish the quoted value (which may be empty), skip the rest of the line.
                 } else if options.is_matching_string_quote_char(quote, c) {
+                    state.value_span.end = position.offset + c.len_utf8();
+
                     add_value_to_config(
                         config,
                         // Must succeed.
@@ -819,7 +1034,9 @@ impl IniParserFSMState {
                         true,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
-                        options.unquoted_strings,
+                        state.value_spans(),
+                        state.type_annotation,
+                        options,
                     )
                     .map_err(|error_kind| (error_kind, false))?;
 
@@ -830,7 +1047,7 @@ impl IniParserFSMState {
                     IniParserFSMState::SkipLineWhitespaceOrComments
 
                 // Escaped char (if supported) - parse the escape sequence.
-                } else if options.is_escape_char(c) {
+                } else if options.is_quoted_escape_char(quote, c) {
                     match try_parse_escape_sequence(next, false, options)? {
                         // Parsed an escaped char - keep parsing the (now owned) quoted value.
                         ParseEscapeSequenceResult::EscapedChar(c) => {
@@ -849,8 +1066,11 @@ impl IniParserFSMState {
                     state.value.push(c, idx);
                     self
 
-                // Space or valid value char - keep parsing the quoted value.
-                } else if c == ' ' || options.is_key_or_value_char(c, false, Some(quote)) {
+                // Space, tab (in multi-line strings) or valid value char - keep parsing the quoted value.
+                } else if c == ' '
+                    || (c == '\t' && options.multiline_strings)
+                    || options.is_key_or_value_char(c, false, Some(quote))
+                {
                     state.value.push(c, idx);
                     self
 
@@ -859,6 +1079,17 @@ impl IniParserFSMState {
                     return Err((InvalidCharacterInValue(c), false));
                 }
             }
+            IniParserFSMState::QuotedValueCarriageReturn(quote) => {
+                // Line feed following the carriage return - already normalized, keep parsing the quoted value.
+                if c == '\n' {
+                    IniParserFSMState::QuotedValue(quote)
+
+                // Else process the char as part of the quoted value.
+                } else {
+                    IniParserFSMState::QuotedValue(quote)
+                        .process(c, idx, position, next, substr, config, state, options)?
+                }
+            }
             IniParserFSMState::BeforeArrayValue(array_type) => {
                 debug_assert!(!state.key.is_empty());
                 debug_assert!(state.value.is_empty());
@@ -911,7 +1142,7 @@ impl IniParserFSMState {
                     }
 
                 // Valid value char - start parsing the unquoted array value.
-                } else if options.is_key_or_value_char(c, false, None) {
+                } else if options.is_unquoted_value_start_char(c) {
                     state.value.push(c, idx);
                     IniParserFSMState::ArrayValue(array_type)
 
@@ -940,7 +1171,9 @@ impl IniParserFSMState {
                         false,
                         state.skip_value | state.skip_section,
                         &mut array_type,
-                        options.unquoted_strings,
+                        &mut state.array_len,
+                        state.type_annotation,
+                        options,
                     )?;
 
                     state.value.clear();
@@ -959,7 +1192,9 @@ impl IniParserFSMState {
                         false,
                         state.skip_value | state.skip_section,
                         &mut array_type,
-                        options.unquoted_strings,
+                        &mut state.array_len,
+                        state.type_annotation,
+                        options,
                     )?;
 
                     state.value.clear();
@@ -974,7 +1209,9 @@ impl IniParserFSMState {
                         false,
                         state.skip_value | state.skip_section,
                         &mut array_type,
-                        options.unquoted_strings,
+                        &mut state.array_len,
+                        state.type_annotation,
+                        options,
                     )?;
 
                     state.value.clear();
@@ -1006,7 +1243,7 @@ impl IniParserFSMState {
                     self
 
                 // Valid value char - keep parsing the unquoted array value.
-                } else if options.is_key_or_value_char(c, false, None) {
+                } else if options.is_unquoted_value_char(c) {
                     state.value.push(c, idx);
                     self
 
@@ -1034,7 +1271,9 @@ impl IniParserFSMState {
                         true,
                         state.skip_value | state.skip_section,
                         &mut dummy_array_type,
-                        options.unquoted_strings,
+                        &mut state.array_len,
+                        state.type_annotation,
+                        options,
                     )?;
                     debug_assert_eq!(dummy_array_type, Some(IniValueType::String));
 
@@ -1043,7 +1282,7 @@ impl IniParserFSMState {
                     IniParserFSMState::AfterArrayValue(IniValueType::String)
 
                 // Escaped char (if supported) - parse the escape sequence.
-                } else if options.is_escape_char(c) {
+                } else if options.is_quoted_escape_char(quote, c) {
                     match try_parse_escape_sequence(next, false, options)? {
                         // Parsed an escaped char - keep parsing the (now owned) quoted array value.
                         ParseEscapeSequenceResult::EscapedChar(c) => {
@@ -1112,34 +1351,82 @@ impl IniParserFSMState {
         })
     }
 
-    /// Called after EOF for cleanup and error reporting if the EOF was unexpected for the current parser state.
+    /// Returns `true` if the parser in this state consumes the ASCII byte `b` (as a char) trivially -
+    /// i.e. just appends it to the current key / value, or skips it,
+    /// so that a run of such bytes may be processed at once by `process_run()` (the parser fast path).
+    pub(super) fn is_run_byte(self, b: u8) -> bool {
+        match self {
+            IniParserFSMState::Key
+            | IniParserFSMState::Value
+            | IniParserFSMState::QuotedValue(_) => {
+                b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'.')
+            }
+            IniParserFSMState::SkipLine => !matches!(b, b'\n' | b'\r'),
+            _ => false,
+        }
+    }
+
+    /// Processes the non-empty run of ASCII bytes in the (half-open) byte `range` in the source chunk,
+    /// all of which satisfy `is_run_byte()` for this state.
+    /// Equivalent to calling `process()` for each of the bytes, which would not change the parser state.
+    pub(super) fn process_run<'s, S>(
+        self,
+        range: Range<usize>,
+        substr: S,
+        state: &mut IniParserPersistentState<'s>,
+    ) where
+        S: Substr<'s>,
+    {
+        match self {
+            IniParserFSMState::Key => state.key.push_range(range, substr),
+            IniParserFSMState::Value => {
+                state.value.push_range(range, substr);
+                state.value_trailing_whitespace = 0;
+            }
+            IniParserFSMState::QuotedValue(_) => state.value.push_range(range, substr),
+            IniParserFSMState::SkipLine => {}
+            _ => debug_assert!(false, "no fast path for the `.ini` parser state"),
+        }
+    }
+
+    /// Called after EOF (at `end` position in the source) for cleanup and error reporting
+    /// if the EOF was unexpected for the current parser state.
     pub(super) fn finish<'s, C, S>(
         self,
         substr: S,
+        end: IniCharPosition,
         config: &mut C,
-        state: &mut IniParserPersistentState,
+        state: &mut IniParserPersistentState<'s>,
         options: &IniOptions,
     ) -> Result<(), IniErrorKind>
     where
-        C: IniConfig<'s>,
+        C: IniConfig<'s> + ?Sized,
         S: Substr<'s>,
     {
         use {IniErrorKind::*, IniParserFSMState::*};
 
         match self {
-            BeforeSection | Section | QuotedSection(_) | AfterSection => {
+            BeforeSection | Section | QuotedSection(_) | AfterSection | ArraySectionEnd => {
                 return Err(UnexpectedEndOfFileInSectionName)
             }
-            Key | QuotedKey(_) | KeyValueSeparator => {
+            Key | QuotedKey(_) | KeyValueSeparator | TypeAnnotation => {
                 return Err(UnexpectedEndOfFileBeforeKeyValueSeparator)
             }
-            QuotedValue(_) => return Err(UnexpectedEndOfFileInQuotedString),
+            QuotedValue(_) | QuotedValueCarriageReturn(_) => {
+                return Err(UnexpectedEndOfFileInQuotedString)
+            }
             // Add the last value if we were parsing it right before EOF.
             Value | BeforeValue => {
                 // We have at least one key character already parsed.
                 debug_assert!(!state.key.is_empty());
                 debug_assert!(!state.path.is_empty());
 
+                if self == BeforeValue {
+                    state.value_span = end.span();
+                } else {
+                    state.end_unquoted_value(end.offset, &substr, options);
+                }
+
                 add_value_to_config(
                     config,
                     // Must succeed.
@@ -1148,9 +1435,14 @@ impl IniParserFSMState {
                     false,
                     state.skip_section | state.skip_value,
                     state.is_key_unique,
-                    options.unquoted_strings,
+                    state.value_spans(),
+                    state.type_annotation,
+                    options,
                 )?;
 
+                // Parsing may continue after an included source EOF.
+                state.key.clear();
+                state.value.clear();
                 state.path.pop();
 
                 Ok(())
@@ -1343,7 +1635,7 @@ fn try_parse_escape_sequence<F: FnMut() -> Option<char>>(
 
 /// Returns `Ok(true)` if we need to skip the current section;
 /// else returns `Ok(false)`.
-fn start_section<'s, C: IniConfig<'s>>(
+fn start_section<'s, C: IniConfig<'s> + ?Sized>(
     config: &mut C,
     section: NonEmptyIniStr<'s, '_>,
     options: &IniOptions,
@@ -1396,9 +1688,184 @@ fn start_section<'s, C: IniConfig<'s>>(
     }
 }
 
+/// Adds a new section to the array of sections `section` in the current section,
+/// creating the array if it does not exist.
+fn start_array_section<'s, C: IniConfig<'s> + ?Sized>(
+    config: &mut C,
+    section: NonEmptyIniStr<'s, '_>,
+) -> Result<(), (IniErrorKind, bool)> {
+    match config.contains_key(section) {
+        // Array of sections does not exist - add it.
+        None => {}
+        // Array of sections already exists - add a new section to it.
+        Some(false) if config.contains_array_section(section) => {}
+        // A section or a value which is not an array of sections - error.
+        Some(_) => return Err((IniErrorKind::InvalidArraySection, false)),
+    }
+
+    config.start_array_section(section, true);
+
+    Ok(())
+}
+
+/// If the parser is configured to map the section names, replaces the parsed (non-empty) section name
+/// with the mapped one.
+fn map_section_name<'s, S: Substr<'s>>(state: &mut IniParserPersistentState<'s>, substr: S) {
+    let map_section = match &state.map_section {
+        Some(map_section) => map_section,
+        None => return,
+    };
+
+    // Empty quoted section names are handled by the caller.
+    let section = match state.key.key(&substr) {
+        Some(section) => section,
+        None => return,
+    };
+
+    let mapped = map_section(section.as_ne_str());
+
+    if mapped.as_str() != section.as_str() {
+        state.original_key.clear();
+        state.original_key.push_str(mapped.as_str());
+
+        state.key.replace(&state.original_key);
+    }
+}
+
+/// Makes sure the parsed (non-empty) section name (if `section` is `true`) / key does not exceed the maximum length, if any.
+/// If the parser is configured to validate the section names / keys, validates the parsed section name / key,
+/// and makes sure it is in the schema, if any.
+fn validate_key<'s, S: Substr<'s>>(
+    state: &IniParserPersistentState<'s>,
+    substr: S,
+    options: &IniOptions,
+    section: bool,
+) -> Result<(), IniErrorKind> {
+    // Empty quoted section names / keys are handled by the caller.
+    let key = match state.key.key(&substr) {
+        Some(key) => key,
+        None => return Ok(()),
+    };
+
+    if let Some(max_key_len) = options.max_key_len {
+        if key.as_str().len() > max_key_len as usize {
+            return Err(IniErrorKind::KeyTooLong(max_key_len));
+        }
+    }
+
+    if let Some(validate_key) = &state.validate_key {
+        validate_key(key.as_ne_str()).map_err(IniErrorKind::InvalidKey)?;
+    }
+
+    match &state.schema {
+        Some(schema) => schema.validate(state.path.iter(), key.as_ne_str(), section),
+        None => Ok(()),
+    }
+}
+
+/// Makes sure the number of parsed sections does not exceed the maximum, if any.
+fn count_section(
+    state: &mut IniParserPersistentState<'_>,
+    options: &IniOptions,
+) -> Result<(), IniErrorKind> {
+    state.num_sections = state.num_sections.saturating_add(1);
+
+    match options.max_sections {
+        Some(max_sections) if state.num_sections > max_sections => {
+            Err(IniErrorKind::TooManySections(max_sections))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Makes sure the parsed `value` does not exceed the maximum length, if any.
+fn check_value_len(value: &IniStr<'_, '_>, options: &IniOptions) -> Result<(), IniErrorKind> {
+    match options.max_value_len {
+        Some(max_value_len) if value.as_str().len() > max_value_len as usize => {
+            Err(IniErrorKind::ValueTooLong(max_value_len))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// If section names / keys are case-insensitive, converts the parsed section name / key to lowercase
+/// and records its original spelling in the current section.
+/// Returns the parsing options to use for the section name / key -
+/// if it only differs in case from a previous one in the current section,
+/// the duplicate section / key policies are overridden by the key collision policy.
+fn fold_key_case<'s, S: Substr<'s>>(
+    state: &mut IniParserPersistentState<'s>,
+    substr: S,
+    options: &IniOptions,
+) -> Result<IniOptions, IniErrorKind> {
+    if !options.case_insensitive_keys {
+        return Ok(*options);
+    }
+
+    state.key.fold_case(&substr, &mut state.original_key);
+
+    // Don't record the spellings in skipped sections.
+    if state.skip_section {
+        return Ok(*options);
+    }
+
+    // Empty quoted section names / keys are handled by the caller.
+    let key = match state.key.key(&substr) {
+        Some(key) => key,
+        None => return Ok(*options),
+    };
+
+    let mut spellings = &mut state.spellings;
+
+    for section in state.path.iter() {
+        spellings = spellings.section_mut(section.as_str());
+    }
+
+    let collision = match spellings.get(key.as_str()) {
+        None => {
+            spellings.set(key.as_str(), &state.original_key);
+            return Ok(*options);
+        }
+        Some(previous) if previous.spelling() == state.original_key => return Ok(*options),
+        Some(previous) => previous.spelling(),
+    };
+
+    match options.key_collisions {
+        IniKeyCollisions::Forbid => Err(IniErrorKind::KeyCollision(
+            collision.to_owned(),
+            state.original_key.clone(),
+        )),
+        IniKeyCollisions::First => Ok(IniOptions {
+            duplicate_sections: IniDuplicateSections::First,
+            duplicate_keys: IniDuplicateKeys::First,
+            ..*options
+        }),
+        IniKeyCollisions::Last => {
+            spellings.set(key.as_str(), &state.original_key);
+
+            Ok(IniOptions {
+                duplicate_sections: IniDuplicateSections::Last,
+                duplicate_keys: IniDuplicateKeys::Last,
+                ..*options
+            })
+        }
+    }
+}
+
+/// If section names / keys are case-insensitive, converts the parsed parent section name to lowercase.
+fn fold_parent_section_case<'s, S: Substr<'s>>(
+    state: &mut IniParserPersistentState<'s>,
+    substr: S,
+    options: &IniOptions,
+) {
+    if options.case_insensitive_keys {
+        state.key.fold_case(&substr, &mut state.original_key);
+    }
+}
+
 /// Sets `skip_value` to `true` if we need to skip the current value;
 /// sets `is_key_unique` to `true` if the key is not contained in `config`'s current section.
-fn check_is_key_duplicate<'s, C: IniConfig<'s>>(
+fn check_is_key_duplicate<'s, C: IniConfig<'s> + ?Sized>(
     config: &C,
     key: NonEmptyIniStr<'s, '_>,
     skip_section: bool,
@@ -1445,32 +1912,137 @@ fn check_is_key_duplicate<'s, C: IniConfig<'s>>(
     }
 }
 
-/// Parses a string `value` and adds it to the `config`'s current section at `key`.
-/// If `quoted` is `true`, `value` is always treated as a string,
-/// else it is first interpreted as a bool / integer / float.
+/// Parses a string `value` and adds it to the `config`'s current section at `key`,
+/// passing the source `spans` of the `key` and `value` if the parser is configured to report them.
+/// If the parser is configured to support value references,
+/// unquoted `value`'s of the form `@{<path>}` without a `type_annotation` are added as value references.
+/// If `quoted` is `true` (or in strict mode), `value` is always treated as a string,
+/// else it is first interpreted as a bool / integer / float,
+/// or as the `type_annotation` type, if any.
 /// Empty `value`'s are treated as strings.
-fn add_value_to_config<'s, C: IniConfig<'s>>(
+#[allow(clippy::too_many_arguments)]
+fn add_value_to_config<'s, C: IniConfig<'s> + ?Sized>(
     config: &mut C,
     key: NonEmptyIniStr<'s, '_>,
     value: IniStr<'s, '_>,
     quoted: bool,
     skip: bool,
     is_key_unique: bool,
-    unquoted_strings: bool,
+    spans: IniValueSpans,
+    type_annotation: Option<IniValueType>,
+    options: &IniOptions,
 ) -> Result<(), IniErrorKind> {
+    check_value_len(&value, options)?;
+
     if !skip {
-        config.add_value(
-            key,
-            parse_value_string(value, quoted, unquoted_strings)?,
-            !is_key_unique,
-        );
+        if options.value_references && !quoted && type_annotation.is_none() {
+            if let Some(reference) = parse_value_reference(value.as_str(), options)? {
+                config.add_value_reference(key, &reference, value, !is_key_unique, spans);
+                return Ok(());
+            }
+        }
+
+        if options.raw_values && type_annotation.is_none() {
+            let hint = if value.as_str().is_empty() || quoted {
+                IniValueHint::String
+            } else if options.strict_values {
+                if !options.unquoted_strings {
+                    return Err(IniErrorKind::UnquotedString);
+                }
+
+                IniValueHint::String
+            } else {
+                IniValueHint::Unquoted
+            };
+
+            config.add_raw_value(key, value, hint, !is_key_unique, spans);
+            return Ok(());
+        }
+
+        let value = parse_value_string(value, quoted, type_annotation, options)?;
+
+        if options.spans {
+            config.add_value_with_spans(key, value, !is_key_unique, spans);
+        } else {
+            config.add_value(key, value, !is_key_unique);
+        }
     }
 
     Ok(())
 }
 
+/// Returns `true` if the unquoted `value` with `trailing_whitespace` bytes of trailing whitespace
+/// is a string value when its trailing whitespace is trimmed,
+/// i.e. if its trailing whitespace may be [`preserved`](enum.IniValueWhitespace.html#variant.Preserve).
+pub(super) fn is_unquoted_string_value(
+    value: &str,
+    trailing_whitespace: usize,
+    type_annotation: Option<IniValueType>,
+    options: &IniOptions,
+) -> bool {
+    debug_assert!(value.len() > trailing_whitespace);
+    let value = &value[..value.len() - trailing_whitespace];
+
+    // Value references are never strings.
+    if options.value_references
+        && type_annotation.is_none()
+        && value.starts_with("@{")
+        && value.ends_with('}')
+    {
+        return false;
+    }
+
+    // Must succeed - the value has at least one non-whitespace char.
+    let value = IniStr::Owned(unwrap_unchecked(
+        NonEmptyStr::new(value),
+        "empty unquoted value",
+    ));
+
+    // Values which fail to parse when trimmed are trimmed, so that the error is reported.
+    matches!(
+        parse_value_string(value, false, type_annotation, options),
+        Ok(IniValue::String(_))
+    )
+}
+
+/// If the (unquoted) `value` is of the form `@{<path>}`, parses and returns the referenced `<path>`,
+/// where `<path>` is a list of section names / keys separated by forward slashes (`'/'`).
+/// Section names / keys in the `<path>` are converted to lowercase if they are case-insensitive.
+/// Returns `None` if the `value` is not a value reference.
+fn parse_value_reference(
+    value: &str,
+    options: &IniOptions,
+) -> Result<Option<ConfigPath>, IniErrorKind> {
+    let path = match value
+        .strip_prefix("@{")
+        .and_then(|value| value.strip_suffix('}'))
+    {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    let mut reference = ConfigPath::new();
+
+    for key in path.split('/') {
+        let key = NonEmptyStr::new(key.trim()).ok_or(IniErrorKind::InvalidValueReference)?;
+
+        if options.case_insensitive_keys {
+            let key: String = key.as_str().chars().flat_map(char::to_lowercase).collect();
+            // Must succeed - the key is not empty.
+            reference.push(unwrap_unchecked(
+                NonEmptyStr::new(&key),
+                "empty value reference key",
+            ));
+        } else {
+            reference.push(key);
+        }
+    }
+
+    Ok(Some(reference))
+}
+
 /// Adds an empty array to the `config`'s current section at `key`.
-fn add_array_to_config<'s, C: IniConfig<'s>>(
+fn add_array_to_config<'s, C: IniConfig<'s> + ?Sized>(
     config: &mut C,
     key: NonEmptyIniStr<'s, '_>,
     skip: bool,
@@ -1482,23 +2054,37 @@ fn add_array_to_config<'s, C: IniConfig<'s>>(
 }
 
 /// Parses a string `value` and adds it to the `config`'s current array.
-/// If `quoted` is `true`, `value` is always treated as a string,
-/// else it is first interpreted as a bool / integer / float.
+/// If `quoted` is `true` (or in strict mode), `value` is always treated as a string,
+/// else it is first interpreted as a bool / integer / float,
+/// or as the `type_annotation` type, if any.
 /// Empty `value`'s are treated as strings.
-/// Updates the `array_type`.
-fn add_value_to_array<'s, C: IniConfig<'s>>(
+/// Updates the `array_type` and the `array_len`.
+#[allow(clippy::too_many_arguments)]
+fn add_value_to_array<'s, C: IniConfig<'s> + ?Sized>(
     config: &mut C,
     value: IniStr<'s, '_>,
     quoted: bool,
     skip: bool,
     array_type: &mut Option<IniValueType>,
-    unquoted_strings: bool,
+    array_len: &mut u32,
+    type_annotation: Option<IniValueType>,
+    options: &IniOptions,
 ) -> Result<(), (IniErrorKind, bool)> {
+    check_value_len(&value, options).map_err(|error_kind| (error_kind, false))?;
+
+    *array_len = array_len.saturating_add(1);
+
+    if let Some(max_array_len) = options.max_array_len {
+        if *array_len > max_array_len {
+            return Err((IniErrorKind::ArrayTooLong(max_array_len), false));
+        }
+    }
+
     if skip {
         return Ok(());
     }
 
-    let value = parse_value_string(value, quoted, unquoted_strings)
+    let value = parse_value_string(value, quoted, type_annotation, options)
         .map_err(|error_kind| (error_kind, false))?;
     let value_type = value.get_ini_type();
 
@@ -1516,22 +2102,86 @@ fn add_value_to_array<'s, C: IniConfig<'s>>(
     Ok(())
 }
 
+impl IniValueHint {
+    /// Interprets the raw `value` with this hint the same way the [`.ini parser`] does with default options -
+    /// as a boolean / integer / float (or date/time, if `"datetime"` feature is enabled), in order, if the value is unquoted,
+    /// or else as a string.
+    /// Integers which overflow `i64` are converted to floats.
+    ///
+    /// [`.ini parser`]: struct.IniParser.html
+    pub fn to_value<'s, 'a>(self, value: IniStr<'s, 'a>) -> IniValue<'s, 'a> {
+        match self {
+            IniValueHint::String => IniValue::String(value),
+            IniValueHint::Unquoted => {
+                parse_value_string(value, false, None, &IniOptions::default())
+                    .unwrap_or(IniValue::String(value))
+            }
+        }
+    }
+
+    /// Interprets the raw `value` with this hint as a boolean, if it is an unquoted `true` / `false`.
+    pub fn to_bool(self, value: &str) -> Option<bool> {
+        match (self, value) {
+            (IniValueHint::Unquoted, "true") => Some(true),
+            (IniValueHint::Unquoted, "false") => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Interprets the raw `value` with this hint as an integer, if it is an unquoted integer in `i64` range.
+    pub fn to_i64(self, value: &str) -> Option<i64> {
+        match self {
+            IniValueHint::String => None,
+            IniValueHint::Unquoted => try_parse_integer(value)?.ok(),
+        }
+    }
+
+    /// Interprets the raw `value` with this hint as a float, if it is an unquoted float or integer.
+    pub fn to_f64(self, value: &str) -> Option<f64> {
+        match self {
+            IniValueHint::String => None,
+            IniValueHint::Unquoted => match try_parse_integer(value) {
+                Some(Ok(integer)) => Some(integer as f64),
+                Some(Err(overflow)) => overflow.to_f64(),
+                None => value.parse().ok(),
+            },
+        }
+    }
+}
+
 /// Parses a string `value`.
 /// If `quoted` is `true`, `value` is always treated as a string,
-/// else it is first interpreted as a bool / integer / float.
+/// else it is first interpreted as a bool / integer / float
+/// (and as a date/time if `"datetime"` feature is enabled),
+/// unless in strict mode.
 /// Empty `value`'s are treated as strings.
+/// If the `type_annotation` is not `None`, `value` is interpreted as its type
+/// (quoted values only match string type annotations).
 fn parse_value_string<'s, 'a>(
     value: IniStr<'s, 'a>,
     quoted: bool,
-    unquoted_strings: bool,
+    type_annotation: Option<IniValueType>,
+    options: &IniOptions,
 ) -> Result<IniValue<'s, 'a>, IniErrorKind> {
     use IniErrorKind::*;
     use IniValue::*;
 
+    if let Some(type_annotation) = type_annotation {
+        return parse_annotated_value_string(value, quoted, type_annotation, options);
+    }
+
     // Empty and quoted values are treated as strings.
     let value = if value.as_str().is_empty() || quoted {
         String(value)
 
+    // In strict mode unquoted values are not interpreted - treated as (unquoted) strings.
+    } else if options.strict_values {
+        if !options.unquoted_strings {
+            return Err(UnquotedString);
+        }
+
+        String(value)
+
     // Check if it's a bool.
     } else if value.as_str() == "true" {
         Bool(true)
@@ -1539,17 +2189,36 @@ fn parse_value_string<'s, 'a>(
         Bool(false)
 
     // Check if it's an integer.
-    } else if let Some(value) = try_parse_integer(value.as_str()) {
-        I64(value)
+    } else if let Some(integer) = try_parse_integer(value.as_str()) {
+        match integer {
+            Ok(integer) => I64(integer),
+            // Integer overflowed - handle according to the options.
+            Err(overflow) => match options.numbers {
+                IniNumberPolicy::Error => return Err(IntegerOverflow),
+                IniNumberPolicy::Saturate => I64(overflow.saturate()),
+                IniNumberPolicy::Float => F64(overflow.to_f64().ok_or(IntegerOverflow)?),
+                IniNumberPolicy::String => {
+                    if !options.unquoted_strings {
+                        return Err(UnquotedString);
+                    }
+
+                    String(value)
+                }
+            },
+        }
 
     // Else check if it's a float.
     } else if let Ok(value) = value.as_str().parse::<f64>() {
         F64(value)
 
+    // Else check if it's a date/time.
+    } else if let Some(value) = try_parse_datetime(value.as_str()) {
+        value
+
     // Else we assume it's an unquoted string.
     } else {
         // Unless we don't allow unquoted strings.
-        if !unquoted_strings {
+        if !options.unquoted_strings {
             return Err(UnquotedString);
         }
 
@@ -1559,18 +2228,134 @@ fn parse_value_string<'s, 'a>(
     Ok(value)
 }
 
-fn try_parse_integer(value: &str) -> Option<i64> {
+/// Parses a string `value` as the `type_annotation` type.
+/// Quoted values only match string type annotations;
+/// integer values match floating point type annotations.
+fn parse_annotated_value_string<'s, 'a>(
+    value: IniStr<'s, 'a>,
+    quoted: bool,
+    type_annotation: IniValueType,
+    options: &IniOptions,
+) -> Result<IniValue<'s, 'a>, IniErrorKind> {
+    use IniErrorKind::*;
+    use IniValue::*;
+
+    if quoted && (type_annotation != IniValueType::String) {
+        return Err(TypeAnnotationMismatch);
+    }
+
+    let string = value.as_str();
+
+    Ok(match type_annotation {
+        IniValueType::Bool => match string {
+            "true" => Bool(true),
+            "false" => Bool(false),
+            _ => return Err(TypeAnnotationMismatch),
+        },
+        IniValueType::I64 => match try_parse_integer(string) {
+            Some(Ok(integer)) => I64(integer),
+            // Integer overflowed - saturate if allowed by the options.
+            Some(Err(overflow)) => match options.numbers {
+                IniNumberPolicy::Saturate => I64(overflow.saturate()),
+                _ => return Err(IntegerOverflow),
+            },
+            None => return Err(TypeAnnotationMismatch),
+        },
+        IniValueType::F64 => match try_parse_integer(string) {
+            Some(Ok(integer)) => F64(integer as f64),
+            Some(Err(overflow)) => F64(overflow.to_f64().ok_or(IntegerOverflow)?),
+            None => match string.parse::<f64>() {
+                Ok(value) => F64(value),
+                Err(_) => return Err(TypeAnnotationMismatch),
+            },
+        },
+        IniValueType::String => String(value),
+        #[cfg(feature = "datetime")]
+        IniValueType::DateTime => match try_parse_datetime(string) {
+            Some(value) => value,
+            None => return Err(TypeAnnotationMismatch),
+        },
+    })
+}
+
+/// Parses the `.ini` type annotation `name`.
+fn parse_type_annotation(name: &str) -> Result<IniValueType, IniErrorKind> {
+    Ok(match name {
+        "bool" => IniValueType::Bool,
+        "int" => IniValueType::I64,
+        "float" => IniValueType::F64,
+        "str" => IniValueType::String,
+        #[cfg(feature = "datetime")]
+        "datetime" => IniValueType::DateTime,
+        _ => return Err(IniErrorKind::InvalidTypeAnnotation(name.to_owned())),
+    })
+}
+
+#[cfg(feature = "datetime")]
+fn try_parse_datetime<'s, 'a>(value: &str) -> Option<IniValue<'s, 'a>> {
+    value.parse().ok().map(IniValue::DateTime)
+}
+
+#[cfg(not(feature = "datetime"))]
+fn try_parse_datetime<'s, 'a>(_: &str) -> Option<IniValue<'s, 'a>> {
+    None
+}
+
+/// An unquoted integer value which overflows `i64`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct OverflowedInteger<'a> {
+    negative: bool,
+    radix: u32,
+    /// Digits without the sign / radix prefix.
+    digits: &'a str,
+}
+
+impl<'a> OverflowedInteger<'a> {
+    /// Clamps the value to the `i64` range.
+    fn saturate(self) -> i64 {
+        if self.negative {
+            i64::MIN
+        } else {
+            i64::MAX
+        }
+    }
+
+    /// Converts the value to the nearest `f64`, or returns `None` if it overflows `f64` as well.
+    fn to_f64(self) -> Option<f64> {
+        let value = if self.radix == 10 {
+            // Correctly rounded.
+            self.digits.parse::<f64>().ok()?
+        } else {
+            self.digits.chars().try_fold(0.0, |value: f64, digit| {
+                digit
+                    .to_digit(self.radix)
+                    .map(|digit| value * self.radix as f64 + digit as f64)
+            })?
+        };
+
+        if value.is_finite() {
+            Some(if self.negative { -value } else { value })
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns `None` if the `value` is not an integer,
+/// `Some(Ok(_))` if it is an integer in `i64` range,
+/// or `Some(Err(_))` if it is an integer which overflows `i64`.
+fn try_parse_integer(value: &str) -> Option<Result<i64, OverflowedInteger<'_>>> {
     if value.is_empty() {
         None
     } else {
         // Explicit sign.
-        let (sign, value) = {
+        let (negative, value) = {
             if let Some(value) = value.strip_prefix("+") {
-                (1, value)
+                (false, value)
             } else if let Some(value) = value.strip_prefix("-") {
-                (-1, value)
+                (true, value)
             } else {
-                (1, value)
+                (false, value)
             }
         };
 
@@ -1588,7 +2373,35 @@ fn try_parse_integer(value: &str) -> Option<i64> {
             }
         };
 
-        i64::from_str_radix(value, radix).ok().map(|int| sign * int)
+        // Only digits, so that overflow is the only possible parse error.
+        if value.is_empty() || !value.chars().all(|c| c.is_digit(radix)) {
+            return None;
+        }
+
+        let overflow = OverflowedInteger {
+            negative,
+            radix,
+            digits: value,
+        };
+
+        // Parse the magnitude to support `i64::MIN`.
+        Some(match u64::from_str_radix(value, radix) {
+            Ok(magnitude) if negative => {
+                if magnitude <= (i64::MAX as u64) + 1 {
+                    Ok((magnitude as i64).wrapping_neg())
+                } else {
+                    Err(overflow)
+                }
+            }
+            Ok(magnitude) => {
+                if magnitude <= i64::MAX as u64 {
+                    Ok(magnitude as i64)
+                } else {
+                    Err(overflow)
+                }
+            }
+            Err(_) => Err(overflow),
+        })
     }
 }
 
@@ -1598,17 +2411,17 @@ mod tests {
 
     #[test]
     fn try_parse_integer_test() {
-        assert_eq!(try_parse_integer("7").unwrap(), 7);
-        assert_eq!(try_parse_integer("+7").unwrap(), 7);
-        assert_eq!(try_parse_integer("-7").unwrap(), -7);
+        assert_eq!(try_parse_integer("7").unwrap().unwrap(), 7);
+        assert_eq!(try_parse_integer("+7").unwrap().unwrap(), 7);
+        assert_eq!(try_parse_integer("-7").unwrap().unwrap(), -7);
 
-        assert_eq!(try_parse_integer("0x17").unwrap(), 23);
-        assert_eq!(try_parse_integer("+0x17").unwrap(), 23);
-        assert_eq!(try_parse_integer("-0x17").unwrap(), -23);
+        assert_eq!(try_parse_integer("0x17").unwrap().unwrap(), 23);
+        assert_eq!(try_parse_integer("+0x17").unwrap().unwrap(), 23);
+        assert_eq!(try_parse_integer("-0x17").unwrap().unwrap(), -23);
 
-        assert_eq!(try_parse_integer("0o17").unwrap(), 15);
-        assert_eq!(try_parse_integer("+0o17").unwrap(), 15);
-        assert_eq!(try_parse_integer("-0o17").unwrap(), -15);
+        assert_eq!(try_parse_integer("0o17").unwrap().unwrap(), 15);
+        assert_eq!(try_parse_integer("+0o17").unwrap().unwrap(), 15);
+        assert_eq!(try_parse_integer("-0o17").unwrap().unwrap(), -15);
 
         assert!(try_parse_integer("-").is_none());
         assert!(try_parse_integer("+").is_none());
@@ -1631,6 +2444,31 @@ mod tests {
         assert!(try_parse_integer("7.0e2").is_none());
         assert!(try_parse_integer("7.0e+2").is_none());
         assert!(try_parse_integer("7.0e-2").is_none());
+
+        assert_eq!(
+            try_parse_integer("9223372036854775807").unwrap().unwrap(),
+            i64::MAX
+        );
+        assert_eq!(
+            try_parse_integer("-9223372036854775808").unwrap().unwrap(),
+            i64::MIN
+        );
+
+        let overflow = try_parse_integer("9223372036854775808")
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(overflow.saturate(), i64::MAX);
+        assert_eq!(overflow.to_f64().unwrap(), 9223372036854775808.0);
+
+        let overflow = try_parse_integer("-0x10000000000000000")
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(overflow.saturate(), i64::MIN);
+        assert_eq!(overflow.to_f64().unwrap(), -18446744073709551616.0);
+
+        let digits = "9".repeat(400);
+        let overflow = try_parse_integer(&digits).unwrap().unwrap_err();
+        assert!(overflow.to_f64().is_none());
     }
 
     #[test]
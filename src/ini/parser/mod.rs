@@ -1,19 +1,25 @@
 mod fsm_state;
+mod reader;
+mod scratch;
+mod stream;
+
+pub use {scratch::*, stream::*};
 
 use {
     super::*,
     crate::*,
     fsm_state::*,
-    std::{iter::Iterator, ops::RangeInclusive, str::CharIndices},
+    reader::*,
+    std::{borrow::Cow, fmt::Display, ops::Range, rc::Rc},
 };
 
 //////////////////////////////////////////////////////////
-/// Trait alias for a closure which, given an inclusive byte range in the `.ini` source string,
+/// Trait alias for a closure which, given a (non-empty, half-open) byte range in the `.ini` source string,
 /// returns the non-empty substring corresponding to the byte range.
 /// The caller guarantees the byte range is valid and corresponds to a valid UTF-8 substring.
-pub(crate) trait Substr<'s>: Fn(RangeInclusive<usize>) -> &'s NonEmptyStr {}
+pub(crate) trait Substr<'s>: Fn(Range<usize>) -> &'s NonEmptyStr {}
 
-impl<'s, F> Substr<'s> for F where F: Fn(RangeInclusive<usize>) -> &'s NonEmptyStr {}
+impl<'s, F> Substr<'s> for F where F: Fn(Range<usize>) -> &'s NonEmptyStr {}
 //////////////////////////////////////////////////////////
 
 //////////////////////////////////////////////////////////
@@ -23,6 +29,13 @@ trait NextChar: FnMut() -> Option<char> {}
 impl<F> NextChar for F where F: FnMut() -> Option<char> {}
 //////////////////////////////////////////////////////////
 
+/// User-provided section name mapping function (see `IniParser::map_section()`).
+type MapSection = Rc<dyn for<'a> Fn(&'a NonEmptyStr) -> Cow<'a, NonEmptyStr>>;
+
+/// User-provided section name / key validation function (see `IniParser::validate_key()`),
+/// returning the validation error message.
+type ValidateKey = Rc<dyn Fn(&NonEmptyStr) -> Result<(), String>>;
+
 /// Persistent state used to communicate information between parser FSM states.
 pub(super) struct IniParserPersistentState<'s> {
     // Scratch buffer for parsed section names / keys, and the current key, if any.
@@ -40,21 +53,134 @@ pub(super) struct IniParserPersistentState<'s> {
     // Whether we need to skip the current value
     // (i.e., when we encountered a duplicate key and we use the `First` duplicate key policy).
     pub skip_value: bool,
+    // Scratch buffer for the original spelling of the current section name / key
+    // if section names / keys are case-insensitive.
+    pub original_key: String,
+    // Original spellings of the section names / keys if section names / keys are case-insensitive.
+    pub spellings: IniKeySpellings,
+    // Source span of the current key, if any.
+    pub key_span: IniSpan,
+    // Source span of the current value, if any.
+    pub value_span: IniSpan,
+    // Length in bytes of the trailing whitespace of the current unquoted value, if any.
+    pub value_trailing_whitespace: usize,
+    // Whether the current (last in the path) section is in an array of sections.
+    pub array_section: bool,
+    // Type annotation of the current key, if any.
+    pub type_annotation: Option<IniValueType>,
+    // User-provided section name mapping function, if any.
+    pub map_section: Option<MapSection>,
+    // User-provided section name / key validation function, if any.
+    pub validate_key: Option<ValidateKey>,
+    // User-provided schema of valid section names / keys, if any.
+    pub schema: Option<Rc<IniSchema>>,
+    // Number of values in the current array, if any.
+    pub array_len: u32,
+    // Number of parsed sections.
+    pub num_sections: u32,
+    // Total length in bytes of the parsed source (incl. the included sources / stream chunks).
+    pub parsed_size: usize,
 }
 
 impl<'s> IniParserPersistentState<'s> {
-    fn new() -> Self {
+    /// Takes the helper buffers from the `scratch` state.
+    fn new(scratch: &mut IniParserScratch) -> Self {
         Self {
-            key: ParsedIniKey::new(),
-            value: ParsedIniValue::new(),
-            path: IniPath::new(),
+            key: ParsedIniKey::from_buffer(std::mem::take(&mut scratch.key)),
+            value: ParsedIniValue::from_buffer(std::mem::take(&mut scratch.value)),
+            path: IniPath::from_buffers(std::mem::take(&mut scratch.path)),
             is_key_unique: true,
             skip_section: false,
             skip_value: false,
+            original_key: String::new(),
+            spellings: IniKeySpellings::new(),
+            key_span: IniCharPosition::default().span(),
+            value_span: IniCharPosition::default().span(),
+            value_trailing_whitespace: 0,
+            array_section: false,
+            type_annotation: None,
+            map_section: None,
+            validate_key: None,
+            schema: None,
+            array_len: 0,
+            num_sections: 0,
+            parsed_size: 0,
+        }
+    }
+
+    /// Returns the (cleared) helper buffers to the `scratch` state.
+    fn into_scratch(self, scratch: &mut IniParserScratch) {
+        scratch.key = self.key.into_buffer();
+        scratch.value = self.value.into_buffer();
+        scratch.path = self.path.into_buffers();
+    }
+
+    /// Copies all section names / keys / values borrowed from the `.ini` source string to the helper buffers,
+    /// making the state independent of the lifetime of the `.ini` source string.
+    fn into_owned<'a, S: Substr<'s>>(mut self, substr: S) -> IniParserPersistentState<'a> {
+        self.key.to_owned(&substr);
+        self.value.to_owned(&substr);
+
+        IniParserPersistentState {
+            key: self.key,
+            value: self.value,
+            path: self.path.into_owned(),
+            is_key_unique: self.is_key_unique,
+            skip_section: self.skip_section,
+            skip_value: self.skip_value,
+            original_key: self.original_key,
+            spellings: self.spellings,
+            key_span: self.key_span,
+            value_span: self.value_span,
+            value_trailing_whitespace: self.value_trailing_whitespace,
+            array_section: self.array_section,
+            type_annotation: self.type_annotation,
+            map_section: self.map_section,
+            validate_key: self.validate_key,
+            schema: self.schema,
+            array_len: self.array_len,
+            num_sections: self.num_sections,
+            parsed_size: self.parsed_size,
         }
     }
 
-    fn clear_path<C: IniConfig<'s>>(&mut self, config: &mut C) {
+    /// Returns the source spans of the current key / value.
+    pub fn value_spans(&self) -> IniValueSpans {
+        IniValueSpans {
+            key: self.key_span,
+            value: self.value_span,
+        }
+    }
+
+    /// Ends the current unquoted value at byte `offset` in the source,
+    /// trimming its trailing whitespace, if any, unless it is preserved.
+    /// Trailing whitespace is only preserved in string values -
+    /// the type of the value is inferred from the trimmed value.
+    pub fn end_unquoted_value<S: Substr<'s>>(
+        &mut self,
+        offset: usize,
+        substr: &S,
+        options: &IniOptions,
+    ) {
+        let trailing_whitespace = std::mem::take(&mut self.value_trailing_whitespace);
+
+        if options.value_whitespace == IniValueWhitespace::Preserve
+            && (trailing_whitespace == 0
+                || is_unquoted_string_value(
+                    self.value.value(substr).as_str(),
+                    trailing_whitespace,
+                    self.type_annotation,
+                    options,
+                ))
+        {
+            self.value_span.end = offset;
+        } else {
+            self.value.truncate_end(trailing_whitespace);
+            self.value_span.end = offset - trailing_whitespace;
+        }
+    }
+
+    fn clear_path<C: IniConfig<'s> + ?Sized>(&mut self, config: &mut C) {
         while let Some(section) = self.path.last() {
             // We didn't call `start_section()` if we skipped it, so don't call `end_section`.
             if !self.skip_section {
@@ -67,23 +193,124 @@ impl<'s> IniParserPersistentState<'s> {
     }
 }
 
+/// User-provided state used to resolve the included `.ini` sources.
+struct IniIncludes<'r, 's> {
+    resolver: &'r mut dyn IniIncludeResolver<'s>,
+    dependencies: &'r mut IniDependencyGraph,
+}
+
 /// Current position in the source string.
 /// Used for error reporting.
 struct IniParserSrcPositionState {
     line: u32,
+    // Column of the last read char, accounting for the tab width.
     column: u32,
+    // Column of the next char.
+    next_column: u32,
+    // Column of the char before the last read char.
+    prev_column: u32,
+    // Byte offset of the last read char in the whole source.
+    char_offset: usize,
+    // Byte offset of the char before the last read char in the whole source.
+    prev_char_offset: usize,
     new_line: bool,
     // Set to `true` in order to consume a `\n` following a `\r` as a single newline.
     cr: bool,
+    // Byte offset of the start of the parsed source chunk in the whole source.
+    // Always `0`, unless the source is parsed in chunks by the stream parser.
+    offset: usize,
+    // Number of columns between the tab stops.
+    tab_width: u32,
 }
 
 impl IniParserSrcPositionState {
-    fn new() -> Self {
+    fn new(options: &IniOptions) -> Self {
         Self {
             line: 1,
             column: 0,
+            next_column: 1,
+            prev_column: 0,
+            char_offset: 0,
+            prev_char_offset: 0,
             new_line: false,
             cr: false,
+            offset: 0,
+            tab_width: options.tab_width,
+        }
+    }
+
+    /// Returns the position of the last read char at byte offset `idx` in the parsed source chunk.
+    fn position(&self, idx: usize) -> IniCharPosition {
+        IniCharPosition {
+            line: self.line,
+            column: self.column,
+            offset: self.offset + idx,
+        }
+    }
+
+    /// Starts a new line.
+    fn new_line(&mut self) {
+        self.line = self.line.saturating_add(1);
+        self.column = 0;
+        self.next_column = 1;
+    }
+
+    /// Advances the position past the (non-line feed) char `c` at byte offset `idx` in the parsed source chunk.
+    /// Tabs (`'\t'`) advance the position to the next tab stop.
+    fn advance(&mut self, c: char, idx: usize) {
+        self.prev_column = self.column;
+        self.prev_char_offset = self.char_offset;
+
+        self.column = self.next_column;
+        self.char_offset = self.offset + idx;
+
+        self.next_column = if c == '\t' {
+            // First column after the next tab stop.
+            ((self.column - 1) / self.tab_width + 1)
+                .saturating_mul(self.tab_width)
+                .saturating_add(1)
+        } else {
+            self.column.saturating_add(1)
+        };
+    }
+
+    /// Advances the position past the non-empty run of plain (non-whitespace) ASCII chars
+    /// at (half-open) byte `range` in the parsed source chunk.
+    fn advance_run(&mut self, range: Range<usize>) {
+        debug_assert!(range.end > range.start);
+        let len = (range.end - range.start) as u32;
+
+        self.prev_column = self.column.saturating_add(len - 1);
+        self.prev_char_offset = if len > 1 {
+            self.offset + range.end - 2
+        } else {
+            self.char_offset
+        };
+
+        self.column = self.column.saturating_add(len);
+        self.next_column = self.column.saturating_add(1);
+        self.char_offset = self.offset + range.end - 1;
+    }
+}
+
+/// Position of a char in the source string.
+/// Used for the source spans of the keys / values.
+#[derive(Clone, Copy, Default)]
+pub(super) struct IniCharPosition {
+    line: u32,
+    column: u32,
+    // Byte offset in the source string.
+    pub offset: usize,
+}
+
+impl IniCharPosition {
+    /// Returns the empty span at this position.
+    pub fn span(self) -> IniSpan {
+        IniSpan {
+            line: self.line,
+            column: self.column,
+            start: self.offset,
+            end: self.offset,
         }
     }
 }
@@ -93,10 +320,14 @@ impl IniParserSrcPositionState {
 pub struct IniParser<'s> {
     /// Source `.ini` string.
     source: &'s str,
-    /// Source string reader.
-    reader: CharIndices<'s>,
     /// Parsing options as provided by the user.
     options: IniOptions,
+    /// Section name mapping function as provided by the user, if any.
+    map_section: Option<MapSection>,
+    /// Section name / key validation function as provided by the user, if any.
+    validate_key: Option<ValidateKey>,
+    /// Schema of valid section names / keys as provided by the user, if any.
+    schema: Option<Rc<IniSchema>>,
 }
 
 impl<'s> IniParser<'s> {
@@ -105,11 +336,54 @@ impl<'s> IniParser<'s> {
     pub fn new(string: &'s str) -> Self {
         Self {
             source: string,
-            reader: string.char_indices(),
             options: Default::default(),
+            map_section: None,
+            validate_key: None,
+            schema: None,
         }
     }
 
+    /// Configures the parser to match the semantics of the Windows `GetPrivateProfileString` API,
+    /// e.g. to load the legacy `.ini` files written by it:
+    /// - only semicolon ([`;`](struct.IniCommentDelimiter.html#associatedconstant.Semicolon)) comments
+    ///   at the start of the line are supported,
+    /// - only the equals sign ([`=`](struct.IniKeyValueSeparator.html#associatedconstant.Equals)) key-value separator is supported,
+    /// - escape sequences are not supported - backslashes (`'\'`) are normal characters,
+    /// - values are not interpreted as booleans / numbers ([`strict mode`](#method.strict_values));
+    ///   single or double quotes around the values are optional,
+    /// - unquoted values extend to the end of the line, with [`trailing whitespace trimmed`](#method.trailing_whitespace_in_values),
+    ///   and may [`contain colons`](#method.colons_in_values) (e.g. `Path=C:\Program Files\Game`),
+    /// - section names / keys are [`case-insensitive`](#method.case_insensitive_keys),
+    /// - the last of the duplicate keys (incl. those which only differ in case) wins; duplicate sections are merged,
+    /// - nested sections and arrays are not supported.
+    ///
+    /// Other options are not changed.
+    ///
+    /// NOTE - values which contain other `.ini` special characters (e.g. `'['`) must still be quoted.
+    ///
+    /// NOTE - use [`ToIniStringOptions::windows_compat`] to write the configs back in this dialect.
+    ///
+    /// [`ToIniStringOptions::windows_compat`]: struct.ToIniStringOptions.html#method.windows_compat
+    pub fn windows_compat(self) -> Self {
+        self.comments(IniCommentDelimiter::Semicolon)
+            .inline_comments(false)
+            .key_value_separator(IniKeyValueSeparator::Equals)
+            .string_quotes(IniStringQuote::Single | IniStringQuote::Double)
+            .unquoted_strings(true)
+            .strict_values(true)
+            .trailing_whitespace_in_values(IniValueWhitespace::Trim)
+            .colons_in_values(true)
+            .escape(false)
+            .line_continuation(false)
+            .case_insensitive_keys(true)
+            .key_collisions(IniKeyCollisions::Last)
+            .duplicate_keys(IniDuplicateKeys::Last)
+            .duplicate_sections(IniDuplicateSections::Merge)
+            .nested_section_depth(1)
+            .arrays(false)
+            .array_sections(false)
+    }
+
     /// Sets the valid comment delimiter character(s).
     /// If [`None`](struct.IniCommentDelimiter.html#associatedconstant.None), comments are not supported.
     ///
@@ -139,7 +413,8 @@ impl<'s> IniParser<'s> {
 
     /// Sets the valid string value quote character(s).
     /// If [`None`](struct.IniStringQuote.html#associatedconstant.None), quoted strings are not supported.
-    /// In this case all values will be parsed as booleans / integers / floats / strings, in order.
+    /// In this case all values will be parsed as booleans / integers / floats / strings, in order
+    /// (unless in [`strict mode`](#method.strict_values)).
     /// E.g., the value `true` is always interpreted as a boolean.
     ///
     /// Default: [`Double`](struct.IniStringQuote.html#associatedconstant.Double).
@@ -158,6 +433,57 @@ impl<'s> IniParser<'s> {
         self
     }
 
+    /// Sets how whitespace in unquoted values is handled.
+    /// If not [`Terminate`], unquoted values are not terminated by whitespace, but extend to the
+    /// [`inline comment`] delimiter or the end of the line, so that values like `Program Files/Game`
+    /// do not have to be quoted. [`Trim`] trims the trailing whitespace of such values, while [`Preserve`] keeps it
+    /// in string values. The type of the value is always inferred with its trailing whitespace trimmed,
+    /// so e.g. `7  ` is the integer `7` in both cases.
+    ///
+    /// NOTE - leading whitespace of unquoted values is always skipped.
+    /// Whitespace in array values and in section names / keys is not affected.
+    ///
+    /// Default: [`Terminate`].
+    ///
+    /// [`Terminate`]: enum.IniValueWhitespace.html#variant.Terminate
+    /// [`inline comment`]: #method.inline_comments
+    /// [`Trim`]: enum.IniValueWhitespace.html#variant.Trim
+    /// [`Preserve`]: enum.IniValueWhitespace.html#variant.Preserve
+    pub fn trailing_whitespace_in_values(mut self, value_whitespace: IniValueWhitespace) -> Self {
+        self.options.value_whitespace = value_whitespace;
+        self
+    }
+
+    /// Sets whether colons (`':'`) are allowed in unquoted values without being escaped
+    /// (e.g. `path = C:/Games`).
+    ///
+    /// NOTE - colons are always allowed in unquoted values (except for their first character)
+    /// if `"datetime"` feature is enabled or if the [`extended interpolation`] syntax is used.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`extended interpolation`]: enum.IniInterpolation.html#variant.Extended
+    pub fn colons_in_values(mut self, colons_in_values: bool) -> Self {
+        self.options.colons_in_values = colons_in_values;
+        self
+    }
+
+    /// Sets whether unquoted values are interpreted as strings only (strict mode).
+    /// If `true`, unquoted values are not interpreted as booleans / integers / floats
+    /// (or date/times, if `"datetime"` feature is enabled) and are passed to the [`event handler`] as strings
+    /// (e.g. the value `1.10` is the string `"1.10"`, not the float `1.1`);
+    /// the values may be converted by the typed accessors of the config (see [`Lenient`]).
+    /// If [`unquoted_strings`](#method.unquoted_strings) is `false`, all values must be quoted, or an error will be raised.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`event handler`]: trait.IniConfig.html
+    /// [`Lenient`]: struct.Lenient.html
+    pub fn strict_values(mut self, strict_values: bool) -> Self {
+        self.options.strict_values = strict_values;
+        self
+    }
+
     /// Sets whether escape sequences (a character sequence following a backslash (`'\'`))
     /// in keys, section names and string values are supported.
     /// If `true`, the following escape sequences are supported:
@@ -249,57 +575,794 @@ impl<'s> IniParser<'s> {
         self
     }
 
+    /// Sets whether section names / keys are case-insensitive.
+    /// If `true`, section names / keys are converted to lowercase before they are passed to the [`event handler`].
+    /// Their original spellings may be recorded by [`parse_with_key_spellings`](#method.parse_with_key_spellings).
+    ///
+    /// Default: `false`.
+    ///
+    /// [`event handler`]: trait.IniConfig.html
+    pub fn case_insensitive_keys(mut self, case_insensitive_keys: bool) -> Self {
+        self.options.case_insensitive_keys = case_insensitive_keys;
+        self
+    }
+
+    /// Sets the policy for [`case-insensitive`](#method.case_insensitive_keys) section names / keys which only differ in case.
+    ///
+    /// Default: [`Forbid`](enum.IniKeyCollisions.html#variant.Forbid).
+    pub fn key_collisions(mut self, key_collisions: IniKeyCollisions) -> Self {
+        self.options.key_collisions = key_collisions;
+        self
+    }
+
+    /// Sets how the contents of the included `.ini` sources are merged into the config
+    /// when [`parsing with includes`](#method.parse_with_includes).
+    ///
+    /// Default: [`Inline`](enum.IniIncludeMerge.html#variant.Inline).
+    pub fn include_merge(mut self, include_merge: IniIncludeMerge) -> Self {
+        self.options.include_merge = include_merge;
+        self
+    }
+
+    /// Sets whether the source [`spans`] of the keys and values are reported to the [`event handler`]
+    /// by calling [`add_value_with_spans`] instead of [`add_value`].
+    ///
+    /// Default: `false`.
+    ///
+    /// [`spans`]: struct.IniSpan.html
+    /// [`event handler`]: trait.IniConfig.html
+    /// [`add_value_with_spans`]: trait.IniConfig.html#method.add_value_with_spans
+    /// [`add_value`]: trait.IniConfig.html#method.add_value
+    pub fn spans(mut self, spans: bool) -> Self {
+        self.options.spans = spans;
+        self
+    }
+
+    /// Sets whether arrays of sections are supported.
+    /// If `true`, section names enclosed in double brackets `"[["` \ `"]]"` (e.g. `[[item]]`)
+    /// start a new section in the array of sections with this name, which is created if it does not exist yet.
+    /// Nested sections (if [`supported`](#method.nested_section_depth)) may not be nested in arrays of sections,
+    /// i.e. only the last section name in the nested section path (e.g. `[[parent/item]]`) refers to the array.
+    ///
+    /// Default: `false`.
+    pub fn array_sections(mut self, array_sections: bool) -> Self {
+        self.options.array_sections = array_sections;
+        self
+    }
+
+    /// Sets whether quoted string values may span multiple lines.
+    /// If `true`, new lines in quoted string values are preserved instead of raising an
+    /// [`UnexpectedNewLineInQuotedValue`](enum.IniErrorKind.html#variant.UnexpectedNewLineInQuotedValue) error.
+    /// Carriage returns (`'\r'`) and carriage return / line feed pairs (`"\r\n"`) are normalized to line feeds (`'\n'`).
+    /// Unescaped tabs (`'\t'`) are allowed in quoted string values as well (e.g. for indentation).
+    /// Quoted array values may not span multiple lines.
+    ///
+    /// Default: `false`.
+    pub fn multiline_strings(mut self, multiline_strings: bool) -> Self {
+        self.options.multiline_strings = multiline_strings;
+        self
+    }
+
+    /// Sets whether raw string values, enclosed in backticks (`` '`' ``), are supported
+    /// (e.g. ``path = `C:\Program Files\Foo` ``).
+    /// Backslashes (`'\\'`) in raw strings are not treated as escape sequences even if [`escape`](#method.escape) is `true`,
+    /// so raw strings may not contain backticks.
+    /// Keys and section names may be enclosed in backticks as well.
+    ///
+    /// Default: `false`.
+    pub fn raw_strings(mut self, raw_strings: bool) -> Self {
+        self.options.raw_strings = raw_strings;
+        self
+    }
+
+    /// Sets whether value type annotations are supported.
+    /// If `true`, a key may be followed by a colon (`':'`) and a type annotation (e.g. `key:int = 7`, `key:str = true`),
+    /// which forces the value to be parsed as the annotated type instead of inferring it,
+    /// raising a [`TypeAnnotationMismatch`](enum.IniErrorKind.html#variant.TypeAnnotationMismatch) error if it does not parse.
+    /// Supported type annotations are `bool`, `int`, `float` (which also accepts integer values), `str`
+    /// (and `datetime` if `"datetime"` feature is enabled);
+    /// other type annotations raise an [`InvalidTypeAnnotation`](enum.IniErrorKind.html#variant.InvalidTypeAnnotation) error.
+    /// Quoted values only match `str` type annotations.
+    /// Type annotations of array keys apply to all array values.
+    ///
+    /// NOTE - colons (`':'`) following the key are always parsed as type annotation delimiters,
+    /// even if they are [`supported`](#method.key_value_separator) as key-value separators.
+    ///
+    /// Default: `false`.
+    pub fn type_annotations(mut self, type_annotations: bool) -> Self {
+        self.options.type_annotations = type_annotations;
+        self
+    }
+
+    /// Sets the function which maps the parsed section names before they are passed to the [`event handler`]
+    /// (e.g. to normalize their case or to rename the legacy section names).
+    /// Section names for which the function returns a different name are replaced with the returned name.
+    /// The function is called for each section name in the nested section paths,
+    /// and for the names of the arrays of sections, before the section names are
+    /// [`folded to lowercase`](#method.case_insensitive_keys), if configured.
+    ///
+    /// Sections mapped to the same name are treated as duplicate sections.
+    ///
+    /// Default: none.
+    ///
+    /// [`event handler`]: trait.IniConfig.html
+    pub fn map_section<F>(mut self, map_section: F) -> Self
+    where
+        F: Fn(&NonEmptyStr) -> Cow<'_, NonEmptyStr> + 'static,
+    {
+        self.map_section.replace(Rc::new(map_section));
+        self
+    }
+
+    /// Sets the function which validates the parsed section names / keys before they are passed to the [`event handler`]
+    /// (e.g. to reject the keys with invalid characters, too long or reserved keys).
+    /// If the function returns an error, parsing fails with an [`InvalidKey`] error,
+    /// which contains the error message and the location of the section name / key in the source.
+    /// The function is called for each section name in the nested section paths (after they are [`mapped`], if configured),
+    /// and for the keys of the values / arrays, after they are [`folded to lowercase`](#method.case_insensitive_keys), if configured.
+    ///
+    /// Default: none.
+    ///
+    /// [`event handler`]: trait.IniConfig.html
+    /// [`InvalidKey`]: enum.IniErrorKind.html#variant.InvalidKey
+    /// [`mapped`]: #method.map_section
+    pub fn validate_key<F, E>(mut self, validate_key: F) -> Self
+    where
+        F: Fn(&NonEmptyStr) -> Result<(), E> + 'static,
+        E: Display,
+    {
+        self.validate_key.replace(Rc::new(move |key: &NonEmptyStr| {
+            validate_key(key).map_err(|err| err.to_string())
+        }));
+        self
+    }
+
+    /// Enables strict mode - sets the [`schema`] of valid section names / keys.
+    /// Parsing fails with an [`UnknownSection`] / [`UnknownKey`] error,
+    /// which contains the location of the section name / key in the source,
+    /// as soon as a section name / key not in the [`schema`] is parsed
+    /// (or a section name is used as a key of a value / array and vice versa).
+    /// Section names / keys are validated after they are [`validated`] by the user-provided function, if any.
+    ///
+    /// Default: none.
+    ///
+    /// [`schema`]: struct.IniSchema.html
+    /// [`UnknownSection`]: enum.IniErrorKind.html#variant.UnknownSection
+    /// [`UnknownKey`]: enum.IniErrorKind.html#variant.UnknownKey
+    /// [`validated`]: #method.validate_key
+    pub fn schema(mut self, schema: IniSchema) -> Self {
+        self.schema.replace(Rc::new(schema));
+        self
+    }
+
+    /// Sets the policy for unquoted integer values which overflow [`i64`].
+    ///
+    /// Default: [`Float`](enum.IniNumberPolicy.html#variant.Float).
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn numbers(mut self, numbers: IniNumberPolicy) -> Self {
+        self.options.numbers = numbers;
+        self
+    }
+
+    /// Sets whether unquoted values of the form `@{<path>}` (e.g. `@{section/nested_section/key}`) are parsed as references
+    /// to the values at `<path>`, where `<path>` is a list of (non-empty) section names / keys, separated by forward slashes (`'/'`),
+    /// starting at the root section.
+    /// Whitespace around the section names / keys in `<path>` is ignored.
+    /// Referenced values may be declared before or after the referencing value,
+    /// and may be values, sections (which are copied with all their contents) or other references.
+    ///
+    /// References are reported to the event handler via [`add_value_reference`] and are resolved by the [`DynConfig::from_ini`] family of methods
+    /// after parsing, raising an [`UnresolvedValueReference`] error if the referenced value does not exist,
+    /// or a [`CyclicValueReference`] error if the reference (directly or indirectly) references itself.
+    ///
+    /// NOTE - array values and values with [`type annotations`](#method.type_annotations) are never parsed as references.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`add_value_reference`]: trait.IniConfig.html#method.add_value_reference
+    /// [`DynConfig::from_ini`]: struct.DynConfig.html#method.from_ini
+    /// [`UnresolvedValueReference`]: enum.IniErrorKind.html#variant.UnresolvedValueReference
+    /// [`CyclicValueReference`]: enum.IniErrorKind.html#variant.CyclicValueReference
+    pub fn value_references(mut self, value_references: bool) -> Self {
+        self.options.value_references = value_references;
+        self
+    }
+
+    /// Sets the interpolation syntax of the string values, compatible with the Python `configparser` module,
+    /// so that the configs shared with Python tooling resolve identically.
+    ///
+    /// Interpolated values reference other values in the same section, in the `DEFAULT` section
+    /// or (for [`Extended`] interpolation) in other sections - `<section>` may be a path of nested sections,
+    /// separated by forward slashes (`'/'`). Referenced values are interpolated recursively, up to `10` levels deep.
+    /// Keys (and section names) of the references are converted to lowercase if they are [`case-insensitive`].
+    /// Colons (`':'`) are allowed in unquoted values if the syntax is [`Extended`].
+    ///
+    /// Interpolations are resolved by the [`DynConfig::from_ini`] family of methods after parsing
+    /// (other event handlers receive the uninterpolated string values), raising
+    /// an [`InvalidInterpolation`] error if the interpolation syntax is invalid (e.g. an unescaped `'%'` / `'$'`),
+    /// an [`UnresolvedInterpolation`] error if the referenced value does not exist, or is a section / an array,
+    /// or an [`InterpolationDepthExceeded`] error if the references are nested too deep (e.g. they are cyclic).
+    ///
+    /// NOTE - interpolated values are always strings. Non-string values are formatted in their `.ini` representation
+    /// when referenced, e.g. the value `1.50` is referenced as `1.5`, unless in [`strict mode`].
+    /// Array values are not interpolated.
+    ///
+    /// Default: [`None`](enum.IniInterpolation.html#variant.None).
+    ///
+    /// [`Extended`]: enum.IniInterpolation.html#variant.Extended
+    /// [`case-insensitive`]: #method.case_insensitive_keys
+    /// [`DynConfig::from_ini`]: struct.DynConfig.html#method.from_ini
+    /// [`InvalidInterpolation`]: enum.IniErrorKind.html#variant.InvalidInterpolation
+    /// [`UnresolvedInterpolation`]: enum.IniErrorKind.html#variant.UnresolvedInterpolation
+    /// [`InterpolationDepthExceeded`]: enum.IniErrorKind.html#variant.InterpolationDepthExceeded
+    /// [`strict mode`]: #method.strict_values
+    pub fn interpolation(mut self, interpolation: IniInterpolation) -> Self {
+        self.options.interpolation = interpolation;
+        self
+    }
+
+    /// Sets whether values are passed to the [`event handler`] unparsed.
+    /// If `true`, values are reported via [`add_raw_value`] as (unescaped) strings with a [`value type hint`],
+    /// and are only interpreted as booleans / integers / floats (or date/times, if `"datetime"` feature is enabled)
+    /// on demand by the [`event handler`] (e.g. using the [`value type hint`] helper methods),
+    /// so that event handlers which are only interested in a few values do not pay for parsing all of them.
+    ///
+    /// NOTE - array values and values with [`type annotations`](#method.type_annotations) are always parsed,
+    /// and [`value references`](#method.value_references) are still reported via [`add_value_reference`].
+    /// Unless in [`strict mode`](#method.strict_values), [`unquoted_strings`](#method.unquoted_strings)
+    /// and the [`integer overflow policy`](#method.numbers) are not applied to the raw values.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`event handler`]: trait.IniConfig.html
+    /// [`add_raw_value`]: trait.IniConfig.html#method.add_raw_value
+    /// [`value type hint`]: enum.IniValueHint.html
+    /// [`add_value_reference`]: trait.IniConfig.html#method.add_value_reference
+    pub fn raw_values(mut self, raw_values: bool) -> Self {
+        self.options.raw_values = raw_values;
+        self
+    }
+
+    /// Sets the number of columns between the tab stops, used to report the error / [`span`] columns.
+    /// Tab characters (`'\t'`) advance the column to the next tab stop, as in text editors.
+    /// `0` is treated as `1`.
+    ///
+    /// Default: `1` (tabs are counted as a single column, like all other characters).
+    ///
+    /// [`span`]: struct.IniSpan.html
+    pub fn tab_width(mut self, tab_width: u32) -> Self {
+        self.options.tab_width = tab_width;
+        self
+    }
+
+    /// Sets the maximum length in bytes of the section names / keys (after processing the escape sequences).
+    /// Longer section names / keys result in a [`KeyTooLong`] error.
+    ///
+    /// Default: `None` (no limit).
+    ///
+    /// [`KeyTooLong`]: enum.IniErrorKind.html#variant.KeyTooLong
+    pub fn max_key_len(mut self, max_key_len: Option<u32>) -> Self {
+        self.options.max_key_len = max_key_len;
+        self
+    }
+
+    /// Sets the maximum length in bytes of the values, incl. array values (after processing the escape sequences).
+    /// Longer values result in a [`ValueTooLong`] error.
+    ///
+    /// Default: `None` (no limit).
+    ///
+    /// [`ValueTooLong`]: enum.IniErrorKind.html#variant.ValueTooLong
+    pub fn max_value_len(mut self, max_value_len: Option<u32>) -> Self {
+        self.options.max_value_len = max_value_len;
+        self
+    }
+
+    /// Sets the maximum number of values in the [`arrays`](#method.arrays).
+    /// Longer arrays result in an [`ArrayTooLong`] error.
+    ///
+    /// Default: `None` (no limit).
+    ///
+    /// [`ArrayTooLong`]: enum.IniErrorKind.html#variant.ArrayTooLong
+    pub fn max_array_len(mut self, max_array_len: Option<u32>) -> Self {
+        self.options.max_array_len = max_array_len;
+        self
+    }
+
+    /// Sets the maximum number of sections (incl. duplicate sections and sections in arrays of sections,
+    /// but not the parent sections in nested section paths).
+    /// More sections result in a [`TooManySections`] error.
+    ///
+    /// Default: `None` (no limit).
+    ///
+    /// [`TooManySections`]: enum.IniErrorKind.html#variant.TooManySections
+    pub fn max_sections(mut self, max_sections: Option<u32>) -> Self {
+        self.options.max_sections = max_sections;
+        self
+    }
+
+    /// Sets the maximum total length in bytes of the parsed `.ini` config source,
+    /// incl. the [`included`](#method.parse_with_includes) sources and the chunks fed to the [`stream parser`].
+    /// Larger sources result in a [`SourceTooLarge`] error before they are parsed.
+    ///
+    /// Default: `None` (no limit).
+    ///
+    /// [`stream parser`]: struct.IniStreamParser.html
+    /// [`SourceTooLarge`]: enum.IniErrorKind.html#variant.SourceTooLarge
+    pub fn max_size(mut self, max_size: Option<usize>) -> Self {
+        self.options.max_size = max_size;
+        self
+    }
+
+    /// Sets the maximum estimated [`memory usage`] in bytes of the config built
+    /// by the [`DynConfig::from_ini`] family of methods (other event handlers ignore this option).
+    /// Parsing stops at the value / section / array which exceeds the limit
+    /// and a [`MemoryLimitExceeded`] error is returned at its position in the source.
+    ///
+    /// NOTE - the memory usage of the overwritten values is not subtracted while parsing.
+    /// The resolved [`value references`] / [`interpolations`] are counted as well,
+    /// and the error is returned at the position of the value reference / interpolated value which exceeds the limit.
+    ///
+    /// Default: `None` (no limit).
+    ///
+    /// [`memory usage`]: struct.DynConfig.html#method.memory_usage
+    /// [`DynConfig::from_ini`]: struct.DynConfig.html#method.from_ini
+    /// [`MemoryLimitExceeded`]: enum.IniErrorKind.html#variant.MemoryLimitExceeded
+    /// [`value references`]: #method.value_references
+    /// [`interpolations`]: #method.interpolation
+    pub fn max_memory_usage(mut self, max_memory_usage: Option<usize>) -> Self {
+        self.options.max_memory_usage = max_memory_usage;
+        self
+    }
+
+    /// Returns the source `.ini` config string.
+    #[cfg(feature = "dyn")]
+    pub(crate) fn source(&self) -> &'s str {
+        self.source
+    }
+
+    /// Returns the parsing options.
+    #[cfg(feature = "dyn")]
+    pub(crate) fn options(&self) -> &IniOptions {
+        &self.options
+    }
+
     /// Consumes the parser and tries to parse the `.ini` config string, calling the methods on the passed `config` event handler.
-    pub fn parse<C: IniConfig<'s>>(mut self, config: &mut C) -> Result<(), IniError> {
+    pub fn parse<C: IniConfig<'s> + ?Sized>(self, config: &mut C) -> Result<(), IniError> {
+        self.parse_with_scratch(config, &mut IniParserScratch::new())
+    }
+
+    /// Consumes the parser and tries to parse the `.ini` config string, calling the methods on the passed `config` event handler.
+    /// Uses (and reuses the allocations of) the helper buffers in the passed [`scratch`] state.
+    ///
+    /// [`scratch`]: struct.IniParserScratch.html
+    pub fn parse_with_scratch<C: IniConfig<'s> + ?Sized>(
+        mut self,
+        config: &mut C,
+        scratch: &mut IniParserScratch,
+    ) -> Result<(), IniError> {
         self.validate_options();
 
-        let options = self.options;
+        let mut persistent_state = self.persistent_state(scratch);
 
-        let reader = &mut self.reader;
-        let source = &self.source;
+        let result = self.parse_impl(config, &mut persistent_state, None);
 
-        let substr = |range| Self::substr(source, range);
+        persistent_state.into_scratch(scratch);
+
+        result
+    }
+
+    /// Consumes the parser and tries to parse the `.ini` config string, calling the methods on the passed `config` event handler.
+    /// If section names / keys are [`case-insensitive`](#method.case_insensitive_keys),
+    /// records their original spellings in the passed (cleared) [`key spellings`](struct.IniKeySpellings.html).
+    pub fn parse_with_key_spellings<C: IniConfig<'s> + ?Sized>(
+        mut self,
+        config: &mut C,
+        spellings: &mut IniKeySpellings,
+    ) -> Result<(), IniError> {
+        self.validate_options();
+
+        let mut scratch = IniParserScratch::new();
+        let mut persistent_state = self.persistent_state(&mut scratch);
+
+        spellings.clear();
+        std::mem::swap(spellings, &mut persistent_state.spellings);
+
+        let result = self.parse_impl(config, &mut persistent_state, None);
+
+        std::mem::swap(spellings, &mut persistent_state.spellings);
+
+        result
+    }
+
+    /// Consumes the parser and tries to parse the `.ini` config string, calling the methods on the passed `config` event handler.
+    ///
+    /// Lines of the form `!include "<name>"` (optionally followed by an inline comment, if supported)
+    /// are treated as include directives: the `resolver` is used to get the `.ini` source with `<name>`,
+    /// which is then parsed (using the same parsing options) and merged into the config according to the
+    /// [`include merge`](#method.include_merge) policy.
+    /// Included sources may in turn include other sources.
+    ///
+    /// The included sources are recorded in the passed [`dependency graph`].
+    /// Including a source which is already being parsed is an error.
+    /// If parsing fails, [`current`] node of the [`dependency graph`] is the included source in which the error occured
+    /// (or `None` for the root `.ini` config string).
+    ///
+    /// NOTE - the include directive is only supported by this method;
+    /// otherwise the exclamation mark (`'!'`) is treated as a normal key character.
+    ///
+    /// [`dependency graph`]: struct.IniDependencyGraph.html
+    /// [`current`]: struct.IniDependencyGraph.html#method.current
+    pub fn parse_with_includes<C: IniConfig<'s> + ?Sized, R: IniIncludeResolver<'s>>(
+        mut self,
+        config: &mut C,
+        mut resolver: R,
+        dependencies: &mut IniDependencyGraph,
+    ) -> Result<(), IniError> {
+        self.validate_options();
+
+        let mut scratch = IniParserScratch::new();
+        let mut persistent_state = self.persistent_state(&mut scratch);
+
+        let mut includes = IniIncludes {
+            resolver: &mut resolver,
+            dependencies,
+        };
+
+        self.parse_impl(config, &mut persistent_state, Some(&mut includes))
+    }
+
+    /// Creates the persistent state, taking the helper buffers from the `scratch` state.
+    fn persistent_state<'a>(&self, scratch: &mut IniParserScratch) -> IniParserPersistentState<'a> {
+        let mut persistent_state = IniParserPersistentState::new(scratch);
+        persistent_state.map_section = self.map_section.clone();
+        persistent_state.validate_key = self.validate_key.clone();
+        persistent_state.schema = self.schema.clone();
+        persistent_state
+    }
 
-        let mut persistent_state = IniParserPersistentState::new();
-        let mut src_pos_state = IniParserSrcPositionState::new();
+    fn parse_impl<C: IniConfig<'s> + ?Sized>(
+        &mut self,
+        config: &mut C,
+        persistent_state: &mut IniParserPersistentState<'s>,
+        includes: Option<&mut IniIncludes<'_, 's>>,
+    ) -> Result<(), IniError> {
+        Self::parse_source(
+            self.source,
+            &self.options,
+            config,
+            persistent_state,
+            includes,
+        )?;
+
+        persistent_state.clear_path(config);
+
+        Ok(())
+    }
+
+    /// Parses the (root or included) `.ini` `source` string.
+    /// Does not finish the sections which remain open at the end of the `source`.
+    fn parse_source<C: IniConfig<'s> + ?Sized>(
+        source: &'s str,
+        options: &IniOptions,
+        config: &mut C,
+        persistent_state: &mut IniParserPersistentState<'s>,
+        includes: Option<&mut IniIncludes<'_, 's>>,
+    ) -> Result<(), IniError> {
+        let mut src_pos_state = IniParserSrcPositionState::new(options);
         let mut fsm_state = IniParserFSMState::StartLine;
 
+        Self::parse_chunk(
+            source,
+            options,
+            config,
+            persistent_state,
+            &mut fsm_state,
+            &mut src_pos_state,
+            includes,
+        )?;
+
+        Self::finish_source(
+            source,
+            options,
+            config,
+            persistent_state,
+            fsm_state,
+            &src_pos_state,
+        )
+    }
+
+    /// Processes all chars in the (whole or partial) `.ini` `source` string,
+    /// starting in (and updating) the `fsm_state` and the `src_pos_state`.
+    fn parse_chunk<C: IniConfig<'s> + ?Sized>(
+        source: &'s str,
+        options: &IniOptions,
+        config: &mut C,
+        persistent_state: &mut IniParserPersistentState<'s>,
+        fsm_state: &mut IniParserFSMState,
+        src_pos_state: &mut IniParserSrcPositionState,
+        mut includes: Option<&mut IniIncludes<'_, 's>>,
+    ) -> Result<(), IniError> {
+        // Make sure the source is not too large before parsing it.
+        persistent_state.parsed_size = persistent_state.parsed_size.saturating_add(source.len());
+        Self::check_size(persistent_state, options, src_pos_state)?;
+
+        let mut reader = IniReader::new(source);
+
+        let substr = |range| Self::substr(source, range);
+
         // Read the chars until EOF, process according to current state.
-        while let Some((c, idx)) = Self::next(reader, &mut src_pos_state) {
-            fsm_state = fsm_state
+        while let Some((c, idx)) = Self::next(&mut reader, src_pos_state) {
+            if let Some(includes) = includes.as_mut() {
+                if (*fsm_state == IniParserFSMState::StartLine) && (c == '!') {
+                    let path = || persistent_state.path.to_config_path();
+
+                    let name =
+                        Self::parse_include_directive(source, &mut reader, src_pos_state, options)
+                            .map_err(|(err, offset)| {
+                                Self::error(err, offset, src_pos_state, path())
+                            })?;
+
+                    let included = includes.resolver.resolve(name).ok_or_else(|| {
+                        Self::error(
+                            IniErrorKind::IncludeNotFound(name.into()),
+                            false,
+                            src_pos_state,
+                            path(),
+                        )
+                    })?;
+
+                    if !includes
+                        .dependencies
+                        .enter(IniDependencyKind::Include, name)
+                    {
+                        return Err(Self::error(
+                            IniErrorKind::IncludeCycle(name.into()),
+                            false,
+                            src_pos_state,
+                            path(),
+                        ));
+                    }
+
+                    Self::parse_include(included, options, config, persistent_state, includes)?;
+                    Self::check_config(config, persistent_state, src_pos_state)?;
+
+                    includes.dependencies.exit();
+
+                    continue;
+                }
+            }
+
+            let position = src_pos_state.position(idx);
+
+            *fsm_state = fsm_state
                 .process(
                     c,
                     idx,
-                    || Self::next(reader, &mut src_pos_state).map(|(c, _)| c),
+                    position,
+                    || Self::next(&mut reader, src_pos_state).map(|(c, _)| c),
                     substr,
                     config,
-                    &mut persistent_state,
-                    &options,
+                    persistent_state,
+                    options,
                 )
                 .map_err(|(err, offset)| {
                     Self::error(
                         err,
                         offset,
-                        &src_pos_state,
+                        src_pos_state,
                         persistent_state.path.to_config_path(),
                     )
                 })?;
+
+            Self::check_config(config, persistent_state, src_pos_state)?;
+
+            // Fast path - process the run of plain chars which follow, if any, at once.
+            // Run chars are never new lines, so only the column needs to be updated.
+            if !src_pos_state.new_line {
+                let run_state = *fsm_state;
+                let run = reader.skip_ascii_while(|b| run_state.is_run_byte(b));
+
+                if !run.is_empty() {
+                    src_pos_state.advance_run(run.clone());
+                    run_state.process_run(run, substr, persistent_state);
+                }
+            }
         }
 
+        Ok(())
+    }
+
+    /// Called after the EOF of the `.ini` `source` string, which was parsed up to the `fsm_state`.
+    fn finish_source<C: IniConfig<'s> + ?Sized>(
+        source: &'s str,
+        options: &IniOptions,
+        config: &mut C,
+        persistent_state: &mut IniParserPersistentState<'s>,
+        fsm_state: IniParserFSMState,
+        src_pos_state: &IniParserSrcPositionState,
+    ) -> Result<(), IniError> {
+        let substr = |range| Self::substr(source, range);
+
         fsm_state
-            .finish(substr, config, &mut persistent_state, &options)
+            .finish(
+                substr,
+                src_pos_state.position(source.len()),
+                config,
+                persistent_state,
+                options,
+            )
             .map_err(|err| {
                 Self::error(
                     err,
                     false,
-                    &src_pos_state,
+                    src_pos_state,
                     persistent_state.path.to_config_path(),
                 )
             })?;
 
-        persistent_state.clear_path(config);
+        Self::check_config(config, persistent_state, src_pos_state)
+    }
 
-        Ok(())
+    /// Parses the rest of the include directive after the leading exclamation mark (`'!'`),
+    /// up to and including the new line / EOF.
+    /// Returns the name of the included source.
+    fn parse_include_directive(
+        source: &'s str,
+        reader: &mut IniReader<'s>,
+        src_pos_state: &mut IniParserSrcPositionState,
+        options: &IniOptions,
+    ) -> Result<&'s str, (IniErrorKind, bool)> {
+        let mut next = || Self::next(reader, src_pos_state);
+
+        // Report the error at the offending char (or at the end of the line if it's a new line), or at the EOF.
+        let invalid = |next: Option<(char, _)>| {
+            (
+                IniErrorKind::InvalidIncludeDirective,
+                matches!(next, Some((c, _)) if options.is_new_line(c)),
+            )
+        };
+
+        let is_whitespace = |c: char| c.is_whitespace() && !options.is_new_line(c);
+
+        for expected in "include".chars() {
+            match next() {
+                Some((c, _)) if c == expected => {}
+                next => return Err(invalid(next)),
+            }
+        }
+
+        // At least one whitespace char must follow the directive.
+        match next() {
+            Some((c, _)) if is_whitespace(c) => {}
+            next => return Err(invalid(next)),
+        }
+
+        let start = loop {
+            match next() {
+                Some((c, _)) if is_whitespace(c) => {}
+                Some(('"', idx)) => break idx + 1,
+                next => return Err(invalid(next)),
+            }
+        };
+
+        let end = loop {
+            match next() {
+                Some(('"', idx)) => break idx,
+                Some((c, _)) if !options.is_new_line(c) => {}
+                next => return Err(invalid(next)),
+            }
+        };
+
+        // Empty name.
+        if start == end {
+            return Err((IniErrorKind::InvalidIncludeDirective, false));
+        }
+
+        // Skip the trailing whitespace and the inline comment, if any.
+        let mut comment = false;
+
+        loop {
+            match next() {
+                None => break,
+                Some((c, _)) if options.is_new_line(c) => break,
+                Some(_) if comment => {}
+                Some((c, _)) if is_whitespace(c) => {}
+                Some((c, _)) if options.is_inline_comment_char(c) => comment = true,
+                next => return Err(invalid(next)),
+            }
+        }
+
+        Ok(&source[start..end])
+    }
+
+    /// Parses the included `.ini` `source` string, merging it into the config according to the include merge policy.
+    fn parse_include<C: IniConfig<'s> + ?Sized>(
+        source: &'s str,
+        options: &IniOptions,
+        config: &mut C,
+        persistent_state: &mut IniParserPersistentState<'s>,
+        includes: &mut IniIncludes<'_, 's>,
+    ) -> Result<(), IniError> {
+        match options.include_merge {
+            IniIncludeMerge::Inline => {
+                Self::parse_source(source, options, config, persistent_state, Some(includes))
+            }
+            IniIncludeMerge::Isolated => {
+                let path = std::mem::replace(&mut persistent_state.path, IniPath::new());
+                let skip_section = std::mem::replace(&mut persistent_state.skip_section, false);
+                let array_section = std::mem::replace(&mut persistent_state.array_section, false);
+
+                // We didn't call `start_section()` if we skipped it, so don't call `end_section` / `start_section`.
+                let is_skipped = |index: usize| skip_section && (index + 1 == path.len() as usize);
+
+                // Finish the current sections - the included source is parsed starting in the root section.
+                let sections: Vec<_> = path.iter().collect();
+
+                for (index, section) in sections.into_iter().enumerate().rev() {
+                    if !is_skipped(index) {
+                        config.end_section(section);
+                    }
+                }
+
+                Self::parse_source(source, options, config, persistent_state, Some(includes))?;
+
+                persistent_state.clear_path(config);
+
+                // Restore the current sections, continuing the last section in the array of sections, if any.
+                for (index, section) in path.iter().enumerate() {
+                    if !is_skipped(index) {
+                        if array_section && (index + 1 == path.len() as usize) {
+                            config.start_array_section(section, false);
+                        } else {
+                            config.start_section(section, false);
+                        }
+                    }
+                }
+
+                persistent_state.path = path;
+                persistent_state.skip_section = skip_section;
+                persistent_state.array_section = array_section;
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the error reported by the event handler (see [`IniConfig::abort`](trait.IniConfig.html#method.abort)), if any,
+    /// at the current position in the source.
+    fn check_config<C: IniConfig<'s> + ?Sized>(
+        config: &C,
+        persistent_state: &IniParserPersistentState<'_>,
+        src_pos_state: &IniParserSrcPositionState,
+    ) -> Result<(), IniError> {
+        match config.abort() {
+            Some(err) => Err(Self::error(
+                err,
+                false,
+                src_pos_state,
+                persistent_state.path.to_config_path(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns a [`SourceTooLarge`](enum.IniErrorKind.html#variant.SourceTooLarge) error
+    /// if the total length of the parsed source exceeds the maximum, if any.
+    fn check_size(
+        persistent_state: &IniParserPersistentState<'_>,
+        options: &IniOptions,
+        src_pos_state: &IniParserSrcPositionState,
+    ) -> Result<(), IniError> {
+        match options.max_size {
+            Some(max_size) if persistent_state.parsed_size > max_size => Err(Self::error(
+                IniErrorKind::SourceTooLarge(max_size),
+                false,
+                src_pos_state,
+                persistent_state.path.to_config_path(),
+            )),
+            _ => Ok(()),
+        }
     }
 
     fn validate_options(&mut self) {
@@ -312,42 +1375,43 @@ impl<'s> IniParser<'s> {
         if self.options.string_quotes.is_empty() {
             self.options.unquoted_strings = true;
         }
+
+        // Tab stops must be at least one column apart.
+        self.options.tab_width = self.options.tab_width.max(1);
     }
 
     /// Reads the next character from the source string reader.
     /// Increments the line/column counters.
     fn next(
-        reader: &mut CharIndices<'s>,
+        reader: &mut IniReader<'s>,
         state: &mut IniParserSrcPositionState,
     ) -> Option<(char, usize)> {
         let next = reader.next();
 
         if state.new_line {
-            state.line += 1;
-            state.column = 0;
-
+            state.new_line();
             state.new_line = false;
         }
 
         match next {
-            Some((idx, c)) => {
+            Some((c, idx)) => {
                 match c {
                     // Eat a line feed if the previous char was a carriage return.
                     '\n' if state.cr => {
                         state.cr = false;
                     }
                     '\r' => {
-                        state.column += 1;
+                        state.advance(c, idx);
                         state.new_line = true;
 
                         state.cr = true;
                     }
                     '\n' => {
-                        state.column += 1;
+                        state.advance(c, idx);
                         state.new_line = true;
                     }
                     _ => {
-                        state.column += 1;
+                        state.advance(c, idx);
                     }
                 }
 
@@ -357,10 +1421,11 @@ impl<'s> IniParser<'s> {
         }
     }
 
-    fn substr(src: &'s str, idx: RangeInclusive<usize>) -> &'s NonEmptyStr {
-        debug_assert!(idx.end() >= idx.start());
-        debug_assert!(*idx.start() < src.len());
-        debug_assert!(*idx.end() <= src.len());
+    fn substr(src: &'s str, idx: Range<usize>) -> &'s NonEmptyStr {
+        debug_assert!(idx.end > idx.start);
+        debug_assert!(idx.end <= src.len());
+        debug_assert!(src.is_char_boundary(idx.start));
+        debug_assert!(src.is_char_boundary(idx.end));
 
         unsafe { unwrap_unchecked(NonEmptyStr::new(src.get_unchecked(idx)), "empty substring") }
     }
@@ -379,12 +1444,17 @@ impl<'s> IniParser<'s> {
         IniError {
             line: state.line,
             column: if offset {
-                state.column - 1
+                state.prev_column
             } else {
                 state.column
             },
-            error,
+            offset: if offset {
+                state.prev_char_offset
+            } else {
+                state.char_offset
+            },
             path,
+            error,
         }
     }
 }
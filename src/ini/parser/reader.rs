@@ -0,0 +1,112 @@
+use std::ops::Range;
+
+/// Reads the `.ini` source string byte by byte.
+/// ASCII bytes (the vast majority of chars in a typical `.ini` config) are returned as chars directly;
+/// multibyte UTF-8 sequences are decoded to code points only when a non-ASCII lead byte is encountered.
+pub(super) struct IniReader<'s> {
+    /// Source `.ini` string bytes.
+    /// The user guarantees it is valid UTF-8.
+    source: &'s [u8],
+    /// Byte offset of the next char in the source.
+    offset: usize,
+}
+
+impl<'s> IniReader<'s> {
+    pub(super) fn new(source: &'s str) -> Self {
+        Self {
+            source: source.as_bytes(),
+            offset: 0,
+        }
+    }
+
+    /// Returns the next char in the source string and its byte offset, or `None` if we reached the end of the source.
+    pub(super) fn next(&mut self) -> Option<(char, usize)> {
+        let offset = self.offset;
+        let lead = *self.source.get(offset)?;
+
+        // Fast path - ASCII char.
+        if lead < 0x80 {
+            self.offset += 1;
+            return Some((lead as char, offset));
+        }
+
+        let (width, mut code_point) = match lead {
+            0xC0..=0xDF => (2, (lead & 0x1F) as u32),
+            0xE0..=0xEF => (3, (lead & 0x0F) as u32),
+            _ => (4, (lead & 0x07) as u32),
+        };
+
+        debug_assert!(
+            offset + width <= self.source.len(),
+            "truncated UTF-8 sequence in the `.ini` source string"
+        );
+
+        for &continuation in &self.source[offset + 1..offset + width] {
+            debug_assert!(
+                (continuation & 0xC0) == 0x80,
+                "invalid UTF-8 continuation byte in the `.ini` source string"
+            );
+            code_point = (code_point << 6) | (continuation & 0x3F) as u32;
+        }
+
+        self.offset += width;
+
+        // The source is valid UTF-8, so the decoded code point is a valid char.
+        Some((unsafe { std::char::from_u32_unchecked(code_point) }, offset))
+    }
+
+    /// Skips the run of ASCII bytes for which `f` returns `true`, starting at the next char in the source string.
+    /// Returns the (possibly empty, half-open) byte range of the skipped run in the source.
+    ///
+    /// Used by the parser fast path to consume runs of plain key / value / comment chars,
+    /// scanning the source bytes directly instead of processing them char by char.
+    pub(super) fn skip_ascii_while<F: Fn(u8) -> bool>(&mut self, f: F) -> Range<usize> {
+        let start = self.offset;
+
+        let len = self.source[start..]
+            .iter()
+            .position(|&b| (b >= 0x80) || !f(b))
+            .unwrap_or(self.source.len() - start);
+
+        self.offset += len;
+
+        start..self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next() {
+        let source = "aé€💯\n";
+        let mut reader = IniReader::new(source);
+        let mut chars = source.char_indices();
+
+        loop {
+            let next = reader.next();
+            assert_eq!(next, chars.next().map(|(idx, c)| (c, idx)));
+
+            if next.is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn skip_ascii_while() {
+        let source = "key1 = abc;é";
+        let mut reader = IniReader::new(source);
+
+        assert_eq!(reader.skip_ascii_while(|b| b.is_ascii_alphanumeric()), 0..4);
+        assert_eq!(reader.next(), Some((' ', 4)));
+        assert_eq!(reader.skip_ascii_while(|b| b.is_ascii_alphanumeric()), 5..5);
+
+        // Stops at non-ASCII chars.
+        assert_eq!(reader.skip_ascii_while(|_| true), 5..11);
+        assert_eq!(reader.next(), Some(('é', 11)));
+        assert_eq!(reader.skip_ascii_while(|_| true), 13..13);
+        assert_eq!(reader.next(), None);
+    }
+}
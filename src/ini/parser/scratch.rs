@@ -0,0 +1,37 @@
+use crate::*;
+
+/// Reusable scratch state of the [`.ini parser`] - helper buffers for parsed section names / keys / values
+/// (i.e. those which contain escape sequences and thus could not be borrowed directly from the source string)
+/// and the current nested section path.
+///
+/// Pass the same scratch state to successive [`parse_with_scratch`] calls
+/// (e.g. when parsing many small `.ini` config strings) to avoid reallocating the helper buffers for each parsed string.
+///
+/// [`.ini parser`]: struct.IniParser.html
+/// [`parse_with_scratch`]: struct.IniParser.html#method.parse_with_scratch
+#[derive(Default)]
+pub struct IniParserScratch {
+    /// Helper buffer for parsed section names / keys.
+    pub(super) key: String,
+    /// Helper buffer for parsed values.
+    pub(super) value: String,
+    /// Helper buffers for the current nested section path.
+    pub(super) path: IniPathBuffers,
+}
+
+impl IniParserScratch {
+    /// Creates a new empty [`scratch state`](struct.IniParserScratch.html).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clears the [`scratch state`](struct.IniParserScratch.html), retaining the allocated helper buffers.
+    ///
+    /// NOTE - the [`parser`](struct.IniParser.html) always leaves the scratch state cleared,
+    /// so this is only required to make sure no data is retained, e.g. before returning the scratch state to a pool.
+    pub fn reset(&mut self) {
+        self.key.clear();
+        self.value.clear();
+        self.path.clear();
+    }
+}
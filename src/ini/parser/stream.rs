@@ -0,0 +1,192 @@
+use super::*;
+
+/// Push-based `.ini` parser, which consumes the `.ini` config source in arbitrary chunks
+/// (e.g. as they arrive over a network stream or are read with async IO)
+/// without buffering the whole source, calling the methods on the owned [`event handler`] object.
+///
+/// Created by [`IniParser::into_stream`] (using the parsing options of the [`.ini parser`]) or [`IniStreamParser::new`].
+/// Call [`feed`] for each chunk of the source, then call [`finish`] to finish parsing and get the [`event handler`] back.
+///
+/// Only complete lines are parsed when the chunks are [`fed`] - the incomplete last line of the fed chunks is buffered.
+/// Section names / keys / values are never borrowed from the source and are always passed to the [`event handler`] as
+/// [`owned`](enum.NonEmptyIniStr.html#variant.Owned).
+///
+/// NOTE - [`include directives`](struct.IniParser.html#method.parse_with_includes) are not supported.
+///
+/// [`event handler`]: trait.IniConfig.html
+/// [`.ini parser`]: struct.IniParser.html
+/// [`IniParser::into_stream`]: struct.IniParser.html#method.into_stream
+/// [`IniStreamParser::new`]: #method.new
+/// [`feed`]: #method.feed
+/// [`fed`]: #method.feed
+/// [`finish`]: #method.finish
+pub struct IniStreamParser<C> {
+    /// The event handler object.
+    config: C,
+    /// Parsing options as provided by the user.
+    options: IniOptions,
+    /// Fed source string which was not parsed yet (i.e. the incomplete last line).
+    pending: String,
+    /// Parser FSM state at the end of the parsed source.
+    fsm_state: IniParserFSMState,
+    /// Position at the end of the parsed source.
+    src_pos_state: IniParserSrcPositionState,
+    /// All section names / keys / values in the persistent state are owned between the `feed` calls.
+    persistent_state: IniParserPersistentState<'static>,
+    /// The first encountered error, if any.
+    /// Returned by all `feed` / `finish` calls after the error.
+    error: Option<IniError>,
+}
+
+impl<C> IniStreamParser<C>
+where
+    C: for<'s> IniConfig<'s>,
+{
+    /// Creates a new [`stream parser`](struct.IniStreamParser.html) with the `config` event handler,
+    /// using default [`parsing options`](struct.IniParser.html).
+    pub fn new(config: C) -> Self {
+        IniParser::new("").into_stream(config)
+    }
+
+    /// Parses the `chunk` of the `.ini` config source, up to and including its last new line.
+    /// The rest of the `chunk` is buffered until the next call to [`feed`](#method.feed) / [`finish`](#method.finish).
+    ///
+    /// Once an error is returned, all following calls return the same error.
+    pub fn feed(&mut self, chunk: &str) -> Result<(), IniError> {
+        self.check_error()?;
+
+        // Make sure the source is not too large before buffering the `chunk`
+        // (the incomplete last line might otherwise grow without bounds).
+        if let Some(max_size) = self.options.max_size {
+            let size = self
+                .persistent_state
+                .parsed_size
+                .saturating_add(self.pending.len())
+                .saturating_add(chunk.len());
+
+            if size > max_size {
+                return Err(self.set_error(IniParser::error(
+                    IniErrorKind::SourceTooLarge(max_size),
+                    false,
+                    &self.src_pos_state,
+                    self.persistent_state.path.to_config_path(),
+                )));
+            }
+        }
+
+        self.pending.push_str(chunk);
+
+        // Only parse complete lines.
+        // NOTE - a carriage return at the end of the `chunk` might be followed by a line feed in the next chunk.
+        if let Some(end) = self.pending.rfind('\n') {
+            let end = end + 1;
+
+            self.parse_pending(end, false)
+                .map_err(|err| self.set_error(err))?;
+
+            self.pending.drain(..end);
+            self.src_pos_state.offset += end;
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the parser, parses the rest of the fed `.ini` config source and finishes parsing.
+    /// Returns the `config` event handler.
+    pub fn finish(mut self) -> Result<C, IniError> {
+        self.check_error()?;
+
+        self.parse_pending(self.pending.len(), true)?;
+
+        Ok(self.config)
+    }
+
+    /// Returns the `config` event handler.
+    pub fn config(&self) -> &C {
+        &self.config
+    }
+
+    /// Parses the `pending` source string up to the `end` byte offset.
+    /// If `finish` is `true`, finishes parsing after the `end`;
+    /// otherwise makes all section names / keys / values in the persistent state owned.
+    fn parse_pending(&mut self, end: usize, finish: bool) -> Result<(), IniError> {
+        let Self {
+            config,
+            options,
+            pending,
+            fsm_state,
+            src_pos_state,
+            persistent_state,
+            ..
+        } = self;
+
+        let source = &pending[..end];
+
+        let mut state: IniParserPersistentState<'_> = std::mem::replace(
+            persistent_state,
+            IniParserPersistentState::new(&mut IniParserScratch::new()),
+        );
+
+        IniParser::parse_chunk(
+            source,
+            options,
+            config,
+            &mut state,
+            fsm_state,
+            src_pos_state,
+            None,
+        )?;
+
+        if finish {
+            IniParser::finish_source(
+                source,
+                options,
+                config,
+                &mut state,
+                *fsm_state,
+                src_pos_state,
+            )?;
+
+            state.clear_path(config);
+        } else {
+            *persistent_state = state.into_owned(|range| IniParser::substr(source, range));
+        }
+
+        Ok(())
+    }
+
+    fn check_error(&self) -> Result<(), IniError> {
+        if let Some(error) = &self.error {
+            Err(error.clone())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn set_error(&mut self, error: IniError) -> IniError {
+        self.error.replace(error.clone());
+        error
+    }
+}
+
+impl<'s> IniParser<'s> {
+    /// Consumes the parser and creates a [`stream parser`] with the `config` event handler,
+    /// using the [`parsing options`](#method.comments) of this parser.
+    ///
+    /// The `.ini` config string of this parser, if any, is the first chunk of the `.ini` config source.
+    ///
+    /// [`stream parser`]: struct.IniStreamParser.html
+    pub fn into_stream<C: for<'a> IniConfig<'a>>(mut self, config: C) -> IniStreamParser<C> {
+        self.validate_options();
+
+        IniStreamParser {
+            config,
+            options: self.options,
+            pending: self.source.into(),
+            fsm_state: IniParserFSMState::StartLine,
+            src_pos_state: IniParserSrcPositionState::new(&self.options),
+            persistent_state: self.persistent_state(&mut IniParserScratch::new()),
+            error: None,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{util::*, *};
+
+/// Calls the methods on the `config` event handler with the sequence of events the [`.ini parser`]
+/// would generate for the `.ini` representation of the `table` (i.e. the root table of a config).
+///
+/// Values in a table are reported before the nested tables / arrays of tables, in unspecified order.
+/// Tables are reported as (nested) sections, arrays of tables - as arrays of sections,
+/// other arrays - as arrays.
+/// Section names / keys / string values are reported as [`owned`](enum.NonEmptyIniStr.html#variant.Owned),
+/// i.e. they are only valid for the duration of the call.
+///
+/// Returns an [`InvalidArrayType`] error for arrays of arrays / arrays of mixed tables and other values,
+/// which have no `.ini` representation.
+///
+/// [`.ini parser`]: struct.IniParser.html
+/// [`InvalidArrayType`]: enum.ToIniStringError.html#variant.InvalidArrayType
+pub(crate) fn replay_ini<'s, T: TableAccess, C: IniConfig<'s> + ?Sized>(
+    table: &T,
+    config: &mut C,
+) -> Result<(), ToIniStringError> {
+    // Values first.
+    for (key, value) in table.iter() {
+        let key = ini_key(key.as_ref());
+
+        match value {
+            Value::Table(_) => {}
+            Value::Array(array) => {
+                if !is_array_sections(&array) {
+                    config.start_array(key, false);
+
+                    for value in array.iter() {
+                        config.add_array_value(ini_value(&value)?);
+                    }
+
+                    config.end_array(key);
+                }
+            }
+            value => config.add_value(key, ini_value(&value)?, false),
+        }
+    }
+
+    // Then the sections / arrays of sections.
+    for (key, value) in table.iter() {
+        let key = ini_key(key.as_ref());
+
+        match value {
+            Value::Table(table) => {
+                config.start_section(key, false);
+                replay_ini(&table, config)?;
+                config.end_section(key);
+            }
+            Value::Array(array) if is_array_sections(&array) => {
+                for value in array.iter() {
+                    match value {
+                        Value::Table(table) => {
+                            config.start_array_section(key, true);
+                            replay_ini(&table, config)?;
+                            config.end_section(key);
+                        }
+                        _ => return Err(ToIniStringError::InvalidArrayType),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Arrays of tables are reported as arrays of sections.
+fn is_array_sections<A: ArrayAccess>(array: &A) -> bool {
+    matches!(array.get_val(0), Ok(Value::Table(_)))
+}
+
+fn ini_key<'s, 'a>(key: &'a str) -> NonEmptyIniStr<'s, 'a> {
+    // Must succeed - table keys are non-empty.
+    NonEmptyIniStr::Owned(unwrap_unchecked(
+        NonEmptyStr::new(key),
+        "empty config table key",
+    ))
+}
+
+fn ini_value<'s, 'a, S: AsRef<str>, A, T>(
+    value: &'a Value<S, A, T>,
+) -> Result<IniValue<'s, 'a>, ToIniStringError> {
+    Ok(match value {
+        Value::Bool(value) => IniValue::Bool(*value),
+        Value::I64(value) => IniValue::I64(*value),
+        Value::F64(value) => IniValue::F64(*value),
+        Value::String(value) => {
+            IniValue::String(NonEmptyStr::new(value.as_ref()).map_or(IniStr::Empty, IniStr::Owned))
+        }
+        #[cfg(feature = "datetime")]
+        Value::DateTime(value) => IniValue::DateTime(*value),
+        Value::Array(_) | Value::Table(_) => return Err(ToIniStringError::InvalidArrayType),
+    })
+}
@@ -0,0 +1,131 @@
+use {
+    crate::*,
+    std::collections::{hash_map::Entry, HashMap},
+};
+
+/// Describes the valid section names / keys of an `.ini` config,
+/// used by the [`.ini parser`] in [`strict mode`] to reject the unknown section names / keys
+/// as soon as they are parsed.
+///
+/// A schema describes the keys of the values / arrays and the names of the (nested) sections / arrays of sections
+/// in the root or a section of the `.ini` config.
+///
+/// NOTE - if section names / keys are [`case-insensitive`], section names / keys in the schema must be lowercase.
+///
+/// [`.ini parser`]: struct.IniParser.html
+/// [`strict mode`]: struct.IniParser.html#method.schema
+/// [`case-insensitive`]: struct.IniParser.html#method.case_insensitive_keys
+#[derive(Clone, Default, Debug)]
+pub struct IniSchema {
+    /// Valid section names / keys, and the schemas of the sections (`None` for values / arrays).
+    keys: HashMap<String, Option<IniSchema>>,
+    /// Whether unknown section names / keys are allowed.
+    allow_unknown: bool,
+}
+
+impl IniSchema {
+    /// Creates a new empty [`schema`](struct.IniSchema.html) with no valid section names / keys.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the (non-empty) `key` of a value / array to the [`schema`](struct.IniSchema.html).
+    /// Replaces the section with the same name, if any.
+    pub fn key<K: AsRef<NonEmptyStr>>(mut self, key: K) -> Self {
+        self.keys.insert(key.as_ref().as_str().to_owned(), None);
+        self
+    }
+
+    /// Adds the (non-empty) `section` (or array of sections) name, and the `schema` of its section names / keys,
+    /// to the [`schema`](struct.IniSchema.html).
+    /// Replaces the key of a value / array with the same name, if any.
+    pub fn section<K: AsRef<NonEmptyStr>>(mut self, section: K, schema: IniSchema) -> Self {
+        self.keys
+            .insert(section.as_ref().as_str().to_owned(), Some(schema));
+        self
+    }
+
+    /// Sets whether section names / keys not in the [`schema`](struct.IniSchema.html) are allowed
+    /// (and are not validated, with all their contents).
+    ///
+    /// Default: `false`.
+    pub fn allow_unknown(mut self, allow_unknown: bool) -> Self {
+        self.allow_unknown = allow_unknown;
+        self
+    }
+
+    /// Returns `true` if the (non-empty) section name / `key` is in the [`schema`](struct.IniSchema.html).
+    pub fn contains<K: AsRef<str>>(&self, key: K) -> bool {
+        self.keys.contains_key(key.as_ref())
+    }
+
+    /// Returns the [`schema`](struct.IniSchema.html) of the section with (non-empty) `section` name, if any.
+    pub fn get_section<K: AsRef<str>>(&self, section: K) -> Option<&IniSchema> {
+        self.keys.get(section.as_ref()).and_then(Option::as_ref)
+    }
+
+    /// Returns the number of section names / keys in the [`schema`](struct.IniSchema.html) (not including nested ones).
+    pub fn len(&self) -> u32 {
+        self.keys.len() as _
+    }
+
+    /// Returns `true` if the [`schema`](struct.IniSchema.html) contains no section names / keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Merges the `other` [`schema`](struct.IniSchema.html) into this one, recursively.
+    /// Section names / keys in `other` replace the section names / keys in this schema,
+    /// unless both are sections, in which case their schemas are merged.
+    pub fn merge(&mut self, other: IniSchema) {
+        self.allow_unknown |= other.allow_unknown;
+
+        for (key, schema) in other.keys.into_iter() {
+            match self.keys.entry(key) {
+                Entry::Occupied(mut entry) => match (entry.get_mut(), schema) {
+                    (Some(this), Some(other)) => this.merge(other),
+                    (_, schema) => {
+                        entry.insert(schema);
+                    }
+                },
+                Entry::Vacant(entry) => {
+                    entry.insert(schema);
+                }
+            }
+        }
+    }
+
+    /// Makes sure the section name (if `section` is `true`) / `key` of a value / array
+    /// in the (root or nested) section at `path` is valid.
+    pub(crate) fn validate<'s, 'a, P>(
+        &self,
+        path: P,
+        key: &NonEmptyStr,
+        section: bool,
+    ) -> Result<(), IniErrorKind>
+    where
+        P: Iterator<Item = NonEmptyIniStr<'s, 'a>>,
+    {
+        let mut schema = self;
+
+        for parent in path {
+            match schema.keys.get(parent.as_str()) {
+                Some(Some(parent)) => schema = parent,
+                // Parent sections are validated before their contents,
+                // so the section name may only be unknown if unknown section names / keys are allowed.
+                _ => return Ok(()),
+            }
+        }
+
+        match schema.keys.get(key.as_str()) {
+            Some(Some(_)) if section => Ok(()),
+            Some(None) if !section => Ok(()),
+            None if schema.allow_unknown => Ok(()),
+            _ => Err(if section {
+                IniErrorKind::UnknownSection
+            } else {
+                IniErrorKind::UnknownKey
+            }),
+        }
+    }
+}
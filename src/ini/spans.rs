@@ -0,0 +1,109 @@
+use {
+    crate::*,
+    std::{collections::HashMap, ops::Range},
+};
+
+/// Location of a key / value in the `.ini` source string,
+/// reported by the [`.ini parser`] if it is [`configured`] to do so.
+///
+/// For the keys / values in the [`included`] `.ini` sources the span refers to the included source.
+///
+/// [`.ini parser`]: struct.IniParser.html
+/// [`configured`]: struct.IniParser.html#method.spans
+/// [`included`]: struct.IniParser.html#method.parse_with_includes
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IniSpan {
+    /// Line in the source string where the key / value starts.
+    pub line: u32,
+    /// Column in the source string where the key / value starts.
+    pub column: u32,
+    /// Byte offset in the source string of the first byte of the key / value,
+    /// including the opening quotes, if any.
+    pub start: usize,
+    /// Byte offset in the source string past the last byte of the key / value,
+    /// including the closing quotes, if any.
+    /// Equal to `start` for empty unquoted values.
+    pub end: usize,
+}
+
+impl IniSpan {
+    /// Returns the byte range of the key / value in the source string.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the length in bytes of the key / value in the source string.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the span is empty (i.e. for an empty unquoted value).
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Source [`spans`] of an `.ini` key and its value.
+///
+/// [`spans`]: struct.IniSpan.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IniValueSpans {
+    /// Source span of the key.
+    pub key: IniSpan,
+    /// Source span of the value.
+    pub value: IniSpan,
+}
+
+/// Source [`spans`] of the `.ini` keys / values, recorded by [`DynConfig::from_ini_with_spans`].
+///
+/// Keys of the spans are the [`paths`] of the values in the config.
+///
+/// [`spans`]: struct.IniValueSpans.html
+/// [`DynConfig::from_ini_with_spans`]: struct.DynConfig.html#method.from_ini_with_spans
+/// [`paths`]: struct.ConfigPath.html
+#[derive(Clone, Default, Debug)]
+pub struct IniSpans(HashMap<ConfigPath, IniValueSpans>);
+
+impl IniSpans {
+    /// Creates a new empty [`spans`](struct.IniSpans.html) object.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the source spans of the value at `path` and its key, if any.
+    pub fn get(&self, path: &ConfigPath) -> Option<&IniValueSpans> {
+        self.0.get(path)
+    }
+
+    /// Returns the number of recorded values.
+    pub fn len(&self) -> u32 {
+        self.0.len() as _
+    }
+
+    /// Returns `true` if no values were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the paths of the values and their source spans, in unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = (&ConfigPath, &IniValueSpans)> {
+        self.0.iter()
+    }
+
+    /// Clears the [`spans`](struct.IniSpans.html).
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Records the source `spans` of the value at `path`, overwriting the previous ones, if any.
+    #[cfg(feature = "dyn")]
+    pub(crate) fn set(&mut self, path: ConfigPath, spans: IniValueSpans) {
+        self.0.insert(path, spans);
+    }
+
+    /// Removes the source spans of the value at `path` and of all values nested in it.
+    #[cfg(feature = "dyn")]
+    pub(crate) fn remove(&mut self, path: &ConfigPath) {
+        self.0.retain(|value_path, _| !value_path.starts_with(path));
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::{hash_map::Iter as HashMapIter, HashMap};
+
+/// Original spellings of the [`case-insensitive`] `.ini` section names / keys,
+/// recorded by the [`.ini parser`] in the root / sections of the `.ini` config.
+///
+/// Keys of the spellings are the lowercase section names / keys, as passed to the [`event handler`].
+/// If different spellings of a section name / key were encountered, the one used is determined by the [`key collision policy`].
+///
+/// May be used to restore the preferred case of the section names / keys, e.g. before serializing the config back to `.ini`.
+///
+/// [`case-insensitive`]: struct.IniParser.html#method.case_insensitive_keys
+/// [`.ini parser`]: struct.IniParser.html#method.parse_with_key_spellings
+/// [`event handler`]: trait.IniConfig.html
+/// [`key collision policy`]: enum.IniKeyCollisions.html
+#[derive(Clone, Default, Debug)]
+pub struct IniKeySpellings(HashMap<String, IniKeySpelling>);
+
+/// Original spelling of the [`case-insensitive`] `.ini` section name / key
+/// and, for sections, the original spellings of the section's nested section names / keys.
+///
+/// [`case-insensitive`]: struct.IniParser.html#method.case_insensitive_keys
+#[derive(Clone, Default, Debug)]
+pub struct IniKeySpelling {
+    spelling: String,
+    children: IniKeySpellings,
+}
+
+impl IniKeySpellings {
+    /// Creates a new empty [`key spellings`](struct.IniKeySpellings.html) object.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the original spelling of the lowercase section name / `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&IniKeySpelling> {
+        self.0.get(key)
+    }
+
+    /// Returns the number of recorded section names / keys (not including nested ones).
+    pub fn len(&self) -> u32 {
+        self.0.len() as _
+    }
+
+    /// Returns `true` if no section names / keys were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the lowercase section names / keys and their original spellings, in unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &IniKeySpelling)> {
+        IniKeySpellingsIter(self.0.iter())
+    }
+
+    /// Clears the [`key spellings`](struct.IniKeySpellings.html).
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns the spellings of the (lowercase) section's nested section names / keys,
+    /// inserting the section with the lowercase spelling if it was not recorded (e.g. if it is an implicit parent section).
+    pub(crate) fn section_mut(&mut self, section: &str) -> &mut IniKeySpellings {
+        if self.get(section).is_none() {
+            self.set(section, section);
+        }
+
+        &mut self.0.get_mut(section).unwrap().children
+    }
+
+    /// Records the `spelling` of the lowercase section name / `key`, overwriting the previous one, if any.
+    pub(crate) fn set(&mut self, key: &str, spelling: &str) {
+        if let Some(previous) = self.0.get_mut(key) {
+            previous.spelling.clear();
+            previous.spelling.push_str(spelling);
+        } else {
+            self.0.insert(
+                key.to_owned(),
+                IniKeySpelling {
+                    spelling: spelling.to_owned(),
+                    children: IniKeySpellings::new(),
+                },
+            );
+        }
+    }
+}
+
+impl IniKeySpelling {
+    /// Returns the original spelling of the section name / key.
+    pub fn spelling(&self) -> &str {
+        &self.spelling
+    }
+
+    /// Returns the original spellings of the section's nested section names / keys.
+    /// Empty for keys.
+    pub fn children(&self) -> &IniKeySpellings {
+        &self.children
+    }
+}
+
+struct IniKeySpellingsIter<'a>(HashMapIter<'a, String, IniKeySpelling>);
+
+impl<'a> Iterator for IniKeySpellingsIter<'a> {
+    type Item = (&'a str, &'a IniKeySpelling);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|(key, spelling)| (key.as_str(), spelling))
+    }
+}
@@ -20,6 +20,7 @@ fn InvalidCharacterAtLineStart() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::InvalidCharacterAtLineStart('\''),
             path: ConfigPath::new(),
         }
@@ -30,6 +31,7 @@ fn InvalidCharacterAtLineStart() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::InvalidCharacterAtLineStart(':'),
             path: ConfigPath::new(),
         }
@@ -40,6 +42,7 @@ fn InvalidCharacterAtLineStart() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::InvalidCharacterAtLineStart('#'),
             path: ConfigPath::new(),
         }
@@ -50,6 +53,7 @@ fn InvalidCharacterAtLineStart() {
         IniError {
             line: 2,
             column: 2,
+            offset: 10,
             error: IniErrorKind::InvalidCharacterAtLineStart('#'),
             path: ConfigPath::new(),
         }
@@ -59,6 +63,7 @@ fn InvalidCharacterAtLineStart() {
         IniError {
             line: 2,
             column: 2,
+            offset: 9,
             error: IniErrorKind::InvalidCharacterAtLineStart('#'),
             path: ConfigPath::new(),
         }
@@ -106,6 +111,7 @@ fn InvalidCharacterInSectionName() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::InvalidCharacterInSectionName('='),
             path: ConfigPath::new(),
         }
@@ -118,6 +124,7 @@ fn InvalidCharacterInSectionName() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::InvalidCharacterInSectionName(':'),
             path: ConfigPath::new(),
         }
@@ -128,6 +135,7 @@ fn InvalidCharacterInSectionName() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidCharacterInSectionName('#'),
             path: ConfigPath::new(),
         }
@@ -140,6 +148,7 @@ fn InvalidCharacterInSectionName() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidCharacterInSectionName(';'),
             path: ConfigPath::new(),
         }
@@ -204,6 +213,7 @@ fn InvalidCharacterAfterSectionName() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::InvalidCharacterAfterSectionName('b'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -217,6 +227,7 @@ fn InvalidCharacterAfterSectionName() {
         IniError {
             line: 1,
             column: 6,
+            offset: 5,
             error: IniErrorKind::InvalidCharacterAfterSectionName('b'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -230,6 +241,7 @@ fn InvalidCharacterAfterSectionName() {
         IniError {
             line: 2,
             column: 6,
+            offset: 9,
             error: IniErrorKind::InvalidCharacterAfterSectionName('c'),
             path: vec![nestr!("a").into(), nestr!("b").into()].into(),
         }
@@ -243,6 +255,7 @@ fn InvalidCharacterAfterSectionName() {
         IniError {
             line: 2,
             column: 10,
+            offset: 13,
             error: IniErrorKind::InvalidCharacterAfterSectionName('c'),
             path: vec![nestr!("a").into(), nestr!("b").into()].into(),
         }
@@ -309,6 +322,7 @@ fn UnexpectedNewLineInSectionName() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::UnexpectedNewLineInSectionName,
             path: ConfigPath::new(),
         }
@@ -319,6 +333,7 @@ fn UnexpectedNewLineInSectionName() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::UnexpectedNewLineInSectionName,
             path: ConfigPath::new(),
         }
@@ -329,6 +344,7 @@ fn UnexpectedNewLineInSectionName() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedNewLineInSectionName,
             path: ConfigPath::new(),
         }
@@ -353,6 +369,7 @@ fn UnexpectedEndOfFileInSectionName() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::UnexpectedEndOfFileInSectionName,
             path: ConfigPath::new(),
         }
@@ -362,6 +379,7 @@ fn UnexpectedEndOfFileInSectionName() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedEndOfFileInSectionName,
             path: ConfigPath::new(),
         }
@@ -371,6 +389,7 @@ fn UnexpectedEndOfFileInSectionName() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::UnexpectedEndOfFileInSectionName,
             path: ConfigPath::new(),
         }
@@ -384,6 +403,7 @@ fn EmptySectionName() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::EmptySectionName,
             path: ConfigPath::new(),
         }
@@ -393,6 +413,7 @@ fn EmptySectionName() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::EmptySectionName,
             path: ConfigPath::new(),
         }
@@ -405,6 +426,7 @@ fn EmptySectionName() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::EmptySectionName,
             path: ConfigPath::new(),
         }
@@ -420,6 +442,7 @@ fn EmptySectionName() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::EmptySectionName,
             path: vec![nestr!("b").into()].into(),
         }
@@ -432,6 +455,7 @@ fn EmptySectionName() {
         IniError {
             line: 2,
             column: 4,
+            offset: 7,
             error: IniErrorKind::EmptySectionName,
             path: vec![nestr!("a").into()].into(),
         }
@@ -447,6 +471,7 @@ fn EmptySectionName() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::EmptySectionName,
             path: vec![nestr!("a").into()].into(),
         }
@@ -459,6 +484,7 @@ fn EmptySectionName() {
         IniError {
             line: 3,
             column: 6,
+            offset: 15,
             error: IniErrorKind::EmptySectionName,
             path: vec![nestr!("a").into(), nestr!("b").into()].into(),
         }
@@ -474,6 +500,7 @@ fn EmptySectionName() {
         IniError {
             line: 1,
             column: 6,
+            offset: 5,
             error: IniErrorKind::EmptySectionName,
             path: vec![nestr!("a").into(), nestr!("b").into()].into(),
         }
@@ -503,6 +530,7 @@ fn InvalidParentSection() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::InvalidParentSection,
             path: vec![nestr!("a").into()].into(),
         }
@@ -514,6 +542,7 @@ fn InvalidParentSection() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::InvalidParentSection,
             path: vec![nestr!("a").into()].into(),
         }
@@ -525,6 +554,7 @@ fn InvalidParentSection() {
         IniError {
             line: 2,
             column: 4,
+            offset: 7,
             error: IniErrorKind::InvalidParentSection,
             path: vec![nestr!("a").into(), nestr!("b").into()].into(),
         }
@@ -585,6 +615,7 @@ fn NestedSectionDepthExceeded() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::NestedSectionDepthExceeded,
             path: ConfigPath::new(),
         }
@@ -621,6 +652,7 @@ fn NestedSectionDepthExceeded() {
         IniError {
             line: 3,
             column: 5,
+            offset: 14,
             error: IniErrorKind::NestedSectionDepthExceeded,
             path: vec![nestr!("a").into(), nestr!("b").into()].into(),
         }
@@ -689,6 +721,7 @@ fn DuplicateSection() {
         IniError {
             line: 3,
             column: 3,
+            offset: 10,
             error: IniErrorKind::DuplicateSection,
             path: vec![nestr!("a").into()].into(),
         }
@@ -729,6 +762,7 @@ fn DuplicateSection() {
         IniError {
             line: 4,
             column: 1,
+            offset: 12,
             error: IniErrorKind::DuplicateKey,
             path: vec![nestr!("a").into(), nestr!("a").into()].into(),
         }
@@ -749,6 +783,77 @@ fn DuplicateSection() {
     assert_eq!(ini.root().get_table("b").unwrap().get_i64("b").unwrap(), 43);
 }
 
+#[test]
+fn InvalidArraySection() {
+    let error = |ini: &str| {
+        DynConfig::from_ini(IniParser::new(ini).arrays(true).array_sections(true))
+            .err()
+            .unwrap()
+    };
+
+    // Section with the same name.
+    assert_eq!(
+        error("[a]\n[[a]]"),
+        IniError {
+            line: 2,
+            column: 4,
+            offset: 7,
+            error: IniErrorKind::InvalidArraySection,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+
+    // Value with the same name.
+    assert_eq!(
+        error("a = 7\n[[a]]"),
+        IniError {
+            line: 2,
+            column: 4,
+            offset: 9,
+            error: IniErrorKind::InvalidArraySection,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+
+    // Array with the same name.
+    assert_eq!(
+        error("a = [7]\n[[ a ]]"),
+        IniError {
+            line: 2,
+            column: 6,
+            offset: 13,
+            error: IniErrorKind::InvalidArraySection,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+
+    // Arrays of sections not supported.
+    assert_eq!(
+        dyn_config_error("[[a]]"),
+        IniError {
+            line: 1,
+            column: 2,
+            offset: 1,
+            error: IniErrorKind::InvalidCharacterInSectionName('['),
+            path: ConfigPath::new(),
+        }
+    );
+
+    // Missing second section end delimiter.
+    assert_eq!(
+        error("[[a] ]").error,
+        IniErrorKind::InvalidCharacterAfterSectionName(' ')
+    );
+    assert_eq!(
+        error("[[a]\n").error,
+        IniErrorKind::UnexpectedNewLineInSectionName
+    );
+    assert_eq!(
+        error("[[a]").error,
+        IniErrorKind::UnexpectedEndOfFileInSectionName
+    );
+}
+
 #[test]
 fn InvalidCharacterAtLineEnd() {
     // After section.
@@ -757,6 +862,7 @@ fn InvalidCharacterAtLineEnd() {
         IniError {
             line: 1,
             column: 5,
+            offset: 4,
             error: IniErrorKind::InvalidCharacterAtLineEnd('b'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -767,6 +873,7 @@ fn InvalidCharacterAtLineEnd() {
         IniError {
             line: 1,
             column: 5,
+            offset: 4,
             error: IniErrorKind::InvalidCharacterAtLineEnd('b'),
             path: ConfigPath::new(),
         }
@@ -777,6 +884,7 @@ fn InvalidCharacterAtLineEnd() {
         IniError {
             line: 1,
             column: 5,
+            offset: 4,
             error: IniErrorKind::InvalidCharacterAtLineEnd(';'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -804,6 +912,7 @@ fn InvalidCharacterInKey() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::InvalidCharacterInKey('['),
             path: ConfigPath::new(),
         }
@@ -814,6 +923,7 @@ fn InvalidCharacterInKey() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidCharacterInKey('\''),
             path: ConfigPath::new(),
         }
@@ -853,6 +963,7 @@ fn UnexpectedNewLineInKey() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::UnexpectedNewLineInKey,
             path: ConfigPath::new(),
         }
@@ -865,6 +976,7 @@ fn UnexpectedNewLineInKey() {
         IniError {
             line: 2,
             column: 2,
+            offset: 5,
             error: IniErrorKind::UnexpectedNewLineInKey,
             path: vec![nestr!("b").into()].into(),
         }
@@ -887,6 +999,7 @@ fn EmptyKey() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::EmptyKey,
             path: ConfigPath::new(),
         }
@@ -896,6 +1009,7 @@ fn EmptyKey() {
         IniError {
             line: 2,
             column: 0,
+            offset: 3,
             error: IniErrorKind::EmptyKey,
             path: vec![nestr!("a").into()].into(),
         }
@@ -910,6 +1024,7 @@ fn EmptyKey() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::EmptyKey,
             path: ConfigPath::new(),
         }
@@ -922,6 +1037,7 @@ fn EmptyKey() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::EmptyKey,
             path: ConfigPath::new(),
         }
@@ -938,6 +1054,7 @@ fn EmptyKey() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::EmptyKey,
             path: ConfigPath::new(),
         }
@@ -962,6 +1079,7 @@ fn DuplicateKey() {
         IniError {
             line: 3,
             column: 1,
+            offset: 8,
             error: IniErrorKind::DuplicateKey,
             path: vec![nestr!("a").into()].into(),
         }
@@ -974,6 +1092,7 @@ fn DuplicateKey() {
         IniError {
             line: 4,
             column: 1,
+            offset: 12,
             error: IniErrorKind::DuplicateKey,
             path: vec![nestr!("x").into(), nestr!("a").into()].into(),
         }
@@ -986,6 +1105,7 @@ fn DuplicateKey() {
         IniError {
             line: 5,
             column: 1,
+            offset: 16,
             error: IniErrorKind::DuplicateKey,
             path: vec![nestr!("x").into(), nestr!("a").into()].into()
         }
@@ -998,6 +1118,7 @@ fn DuplicateKey() {
         IniError {
             line: 2,
             column: 2,
+            offset: 5,
             error: IniErrorKind::DuplicateKey,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1010,6 +1131,7 @@ fn DuplicateKey() {
         IniError {
             line: 3,
             column: 6,
+            offset: 13,
             error: IniErrorKind::DuplicateKey,
             path: vec![nestr!("a").into(), nestr!("b").into()].into(),
         }
@@ -1127,6 +1249,7 @@ fn UnexpectedEndOfFileBeforeKeyValueSeparator() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::UnexpectedEndOfFileBeforeKeyValueSeparator,
             path: ConfigPath::new(),
         }
@@ -1137,6 +1260,7 @@ fn UnexpectedEndOfFileBeforeKeyValueSeparator() {
         IniError {
             line: 2,
             column: 4,
+            offset: 11,
             error: IniErrorKind::UnexpectedEndOfFileBeforeKeyValueSeparator,
             path: vec![nestr!("foo").into(), nestr!("a ").into()].into(),
         }
@@ -1164,6 +1288,7 @@ fn InvalidKeyValueSeparator() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidKeyValueSeparator('!'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1173,6 +1298,7 @@ fn InvalidKeyValueSeparator() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidKeyValueSeparator(':'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1185,6 +1311,7 @@ fn InvalidKeyValueSeparator() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidKeyValueSeparator('b'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1197,6 +1324,7 @@ fn InvalidKeyValueSeparator() {
         IniError {
             line: 1,
             column: 5,
+            offset: 4,
             error: IniErrorKind::InvalidKeyValueSeparator('b'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1222,6 +1350,7 @@ fn InvalidCharacterInValue() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidCharacterInValue('='),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1232,6 +1361,7 @@ fn InvalidCharacterInValue() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidCharacterInValue(':'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1244,6 +1374,7 @@ fn InvalidCharacterInValue() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidCharacterInValue('='),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1257,6 +1388,7 @@ fn InvalidCharacterInValue() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidCharacterInValue(':'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1267,6 +1399,7 @@ fn InvalidCharacterInValue() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::InvalidCharacterInValue(';'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1325,6 +1458,7 @@ fn UnexpectedEndOfFileInEscapeSequence() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::UnexpectedEndOfFileInEscapeSequence,
             path: ConfigPath::new(),
         }
@@ -1335,6 +1469,7 @@ fn UnexpectedEndOfFileInEscapeSequence() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedEndOfFileInEscapeSequence,
             path: ConfigPath::new(),
         }
@@ -1345,6 +1480,7 @@ fn UnexpectedEndOfFileInEscapeSequence() {
         IniError {
             line: 1,
             column: 1,
+            offset: 0,
             error: IniErrorKind::UnexpectedEndOfFileInEscapeSequence,
             path: ConfigPath::new(),
         }
@@ -1355,6 +1491,7 @@ fn UnexpectedEndOfFileInEscapeSequence() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::UnexpectedEndOfFileInEscapeSequence,
             path: ConfigPath::new(),
         }
@@ -1365,6 +1502,7 @@ fn UnexpectedEndOfFileInEscapeSequence() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedEndOfFileInEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1375,6 +1513,7 @@ fn UnexpectedEndOfFileInEscapeSequence() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::UnexpectedEndOfFileInEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1409,6 +1548,7 @@ fn UnexpectedNewLineInEscapeSequence() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::UnexpectedNewLineInEscapeSequence,
             path: ConfigPath::new(),
         }
@@ -1419,6 +1559,7 @@ fn UnexpectedNewLineInEscapeSequence() {
         IniError {
             line: 1,
             column: 2,
+            offset: 1,
             error: IniErrorKind::UnexpectedNewLineInEscapeSequence,
             path: ConfigPath::new(),
         }
@@ -1429,6 +1570,7 @@ fn UnexpectedNewLineInEscapeSequence() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedNewLineInEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1461,6 +1603,7 @@ fn InvalidEscapeCharacter() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::InvalidEscapeCharacter('z'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1557,6 +1700,7 @@ fn UnexpectedEndOfFileInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 5,
+            offset: 4,
             error: IniErrorKind::UnexpectedEndOfFileInUnicodeEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1568,6 +1712,7 @@ fn UnexpectedEndOfFileInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 7,
+            offset: 6,
             error: IniErrorKind::UnexpectedEndOfFileInUnicodeEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1579,6 +1724,7 @@ fn UnexpectedEndOfFileInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 8,
+            offset: 7,
             error: IniErrorKind::UnexpectedEndOfFileInUnicodeEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1594,6 +1740,7 @@ fn UnexpectedNewLineInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::UnexpectedNewLineInUnicodeEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1605,6 +1752,7 @@ fn UnexpectedNewLineInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::UnexpectedNewLineInUnicodeEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1616,6 +1764,7 @@ fn UnexpectedNewLineInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 6,
+            offset: 5,
             error: IniErrorKind::UnexpectedNewLineInUnicodeEscapeSequence,
             path: vec![nestr!("b").into()].into(),
         }
@@ -1631,6 +1780,7 @@ fn InvalidCharacterInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 5,
+            offset: 4,
             error: IniErrorKind::InvalidCharacterInUnicodeEscapeSequence('$'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1642,6 +1792,7 @@ fn InvalidCharacterInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 9,
+            offset: 8,
             error: IniErrorKind::InvalidCharacterInUnicodeEscapeSequence('"'),
             path: vec![nestr!("b").into()].into(),
         }
@@ -1653,6 +1804,7 @@ fn InvalidCharacterInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 9,
+            offset: 8,
             error: IniErrorKind::InvalidCharacterInUnicodeEscapeSequence('"'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1664,6 +1816,7 @@ fn InvalidCharacterInUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 10,
+            offset: 9,
             error: IniErrorKind::InvalidCharacterInUnicodeEscapeSequence('"'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1679,6 +1832,7 @@ fn InvalidUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 8,
+            offset: 7,
             error: IniErrorKind::InvalidUnicodeEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1690,6 +1844,7 @@ fn InvalidUnicodeEscapeSequence() {
         IniError {
             line: 1,
             column: 6,
+            offset: 5,
             error: IniErrorKind::InvalidUnicodeEscapeSequence,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1717,6 +1872,7 @@ fn UnexpectedNewLineInQuotedValue() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedNewLineInQuotedValue,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1748,6 +1904,7 @@ fn UnexpectedEndOfFileInQuotedString() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedEndOfFileInQuotedString,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1772,6 +1929,7 @@ fn UnquotedString() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnquotedString,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1783,6 +1941,186 @@ fn UnquotedString() {
     assert_eq!(ini.root().get_string("a").unwrap(), "a");
 }
 
+#[test]
+fn IntegerOverflow() {
+    let ini = "max = 9223372036854775807\nmin = -9223372036854775808\na = 9223372036854775808";
+
+    assert_eq!(
+        DynConfig::from_ini(IniParser::new(ini).numbers(IniNumberPolicy::Error))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 3,
+            column: 23,
+            offset: 75,
+            error: IniErrorKind::IntegerOverflow,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+
+    // Overflows `f64` as well.
+    assert_eq!(
+        dyn_config_error(&format!("a = {}", "9".repeat(400))).error,
+        IniErrorKind::IntegerOverflow
+    );
+
+    // Parsed as a float by default.
+    let ini = dyn_config(ini);
+    assert_eq!(ini.root().get_i64("max").unwrap(), i64::MAX);
+    assert_eq!(ini.root().get_i64("min").unwrap(), i64::MIN);
+    assert!(cmp_f64(
+        ini.root().get_f64("a").unwrap(),
+        9223372036854775808.0
+    ));
+
+    let ini =
+        "a = 0x10000000000000000\nb = -0o1000000000000000000000000\nc = [1, 99999999999999999999]";
+
+    assert!(cmp_f64(
+        DynConfig::from_ini(IniParser::new(ini).arrays(true))
+            .unwrap()
+            .root()
+            .get_f64("a")
+            .unwrap(),
+        18446744073709551616.0
+    ));
+
+    let config = DynConfig::from_ini(
+        IniParser::new(ini)
+            .arrays(true)
+            .numbers(IniNumberPolicy::Saturate),
+    )
+    .unwrap();
+    assert_eq!(config.root().get_i64("a").unwrap(), i64::MAX);
+    assert_eq!(config.root().get_i64("b").unwrap(), i64::MIN);
+    assert_eq!(
+        config.root().get_array("c").unwrap().get_i64(1).unwrap(),
+        i64::MAX
+    );
+
+    let config = DynConfig::from_ini(
+        IniParser::new("a = -0x10000000000000000").numbers(IniNumberPolicy::String),
+    )
+    .unwrap();
+    assert_eq!(
+        config.root().get_string("a").unwrap(),
+        "-0x10000000000000000"
+    );
+
+    assert_eq!(
+        DynConfig::from_ini(
+            IniParser::new("a = 0x10000000000000000")
+                .numbers(IniNumberPolicy::String)
+                .unquoted_strings(false)
+        )
+        .err()
+        .unwrap()
+        .error,
+        IniErrorKind::UnquotedString
+    );
+}
+
+#[test]
+fn InvalidTypeAnnotation() {
+    let parser = |string| IniParser::new(string).type_annotations(true);
+
+    // Unknown type annotation.
+    assert_eq!(
+        DynConfig::from_ini(parser("a:integer = 7")).err().unwrap(),
+        IniError {
+            line: 1,
+            column: 9,
+            offset: 8,
+            error: IniErrorKind::InvalidTypeAnnotation("integer".to_owned()),
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+
+    // Empty type annotation.
+    assert_eq!(
+        DynConfig::from_ini(parser("a: = 7")).err().unwrap().error,
+        IniErrorKind::InvalidTypeAnnotation(String::new())
+    );
+
+    // Invalid character in the type annotation.
+    assert_eq!(
+        DynConfig::from_ini(parser("a:i-nt = 7"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::InvalidCharacterInKey('-')
+    );
+
+    // Only one type annotation is allowed.
+    assert_eq!(
+        DynConfig::from_ini(parser("a:int :str = 7"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::InvalidKeyValueSeparator(':')
+    );
+
+    // Type annotations are not supported by default.
+    assert_eq!(
+        dyn_config_error("a:int = 7").error,
+        IniErrorKind::InvalidCharacterInKey(':')
+    );
+}
+
+#[test]
+fn TypeAnnotationMismatch() {
+    let parser = |string| IniParser::new(string).type_annotations(true);
+
+    assert_eq!(
+        DynConfig::from_ini(parser("a:int = 7\nb:bool = 1"))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 2,
+            column: 10,
+            offset: 19,
+            error: IniErrorKind::TypeAnnotationMismatch,
+            path: vec![nestr!("b").into()].into(),
+        }
+    );
+
+    // Quoted values only match string type annotations.
+    assert_eq!(
+        DynConfig::from_ini(parser("a:int = \"7\""))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::TypeAnnotationMismatch
+    );
+
+    // Empty values only match string type annotations.
+    assert_eq!(
+        DynConfig::from_ini(parser("a:float ="))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::TypeAnnotationMismatch
+    );
+
+    // Array values.
+    assert_eq!(
+        DynConfig::from_ini(parser("a:int = [1, 2.5]").arrays(true))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::TypeAnnotationMismatch
+    );
+
+    // Overflowing integers.
+    assert_eq!(
+        DynConfig::from_ini(parser("a:int = 9223372036854775808"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::IntegerOverflow
+    );
+}
+
 #[test]
 fn UnexpectedNewLineInArray() {
     // Arrays not supported.
@@ -1791,6 +2129,7 @@ fn UnexpectedNewLineInArray() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::InvalidCharacterInValue('['),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1804,6 +2143,7 @@ fn UnexpectedNewLineInArray() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedNewLineInArray,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1825,6 +2165,7 @@ fn MixedArray() {
         IniError {
             line: 1,
             column: 10,
+            offset: 9,
             error: IniErrorKind::MixedArray,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1837,6 +2178,7 @@ fn MixedArray() {
         IniError {
             line: 1,
             column: 9,
+            offset: 8,
             error: IniErrorKind::MixedArray,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1849,6 +2191,7 @@ fn MixedArray() {
         IniError {
             line: 1,
             column: 7,
+            offset: 6,
             error: IniErrorKind::MixedArray,
             path: vec![nestr!("a").into()].into(),
         }
@@ -1893,6 +2236,7 @@ fn InvalidCharacterInArray() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::InvalidCharacterInArray('='),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1905,6 +2249,7 @@ fn InvalidCharacterInArray() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::InvalidCharacterInArray('['),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1917,6 +2262,7 @@ fn InvalidCharacterInArray() {
         IniError {
             line: 1,
             column: 6,
+            offset: 5,
             error: IniErrorKind::InvalidCharacterInArray('b'),
             path: vec![nestr!("a").into()].into(),
         }
@@ -1993,6 +2339,7 @@ fn UnexpectedEndOfFileInArray() {
         IniError {
             line: 1,
             column: 3,
+            offset: 2,
             error: IniErrorKind::UnexpectedEndOfFileInArray,
             path: vec![nestr!("a").into()].into(),
         }
@@ -2004,6 +2351,7 @@ fn UnexpectedEndOfFileInArray() {
         IniError {
             line: 1,
             column: 5,
+            offset: 4,
             error: IniErrorKind::UnexpectedEndOfFileInArray,
             path: vec![nestr!("a").into()].into(),
         }
@@ -2029,6 +2377,7 @@ fn UnexpectedEndOfFileInQuotedArrayValue() {
         IniError {
             line: 1,
             column: 4,
+            offset: 3,
             error: IniErrorKind::UnexpectedEndOfFileInQuotedArrayValue,
             path: vec![nestr!("a").into()].into(),
         }
@@ -2106,299 +2455,2984 @@ string = "bar""#;
 }
 
 #[test]
-fn ArraysNotAllowed() {
-    let mut config = DynConfig::new();
-    assert!(!config.root_mut().set(nestr!("array"), DynArray::new()));
+fn unicode() {
+    // Multibyte UTF-8 chars in unquoted / quoted section names, keys and values.
+    let ini = r#"ключ = значение
+"ключ 2" = "значение 2"
+ñ = ß
+array = [á, "é", íó,]
 
-    assert_eq!(
-        config.to_ini_string().err().unwrap(),
-        ToIniStringError::ArraysNotAllowed
-    );
+[секция]
+ü\ ö = "ää"
+"#;
 
-    // But this succeeds.
-    assert_eq!(
-        config
-            .to_ini_string_opts(ToIniStringOptions {
-                arrays: true,
-                ..Default::default()
-            })
-            .unwrap(),
-        "array = []"
-    );
-}
+    let config = DynConfig::from_ini(IniParser::new(ini).arrays(true)).unwrap();
+    assert_eq!(config.root().len(), 4 + 1);
 
-#[test]
-fn InvalidArrayType() {
-    // Array of tables.
-    {
-        let mut config = DynConfig::new();
-        let mut array = DynArray::new();
-        array.push(Value::Table(DynTable::new())).unwrap();
-        assert!(!config.root_mut().set(nestr!("array"), array));
+    assert_eq!(config.root().get_string("ключ").unwrap(), "значение");
+    assert_eq!(config.root().get_string("ключ 2").unwrap(), "значение 2");
+    assert_eq!(config.root().get_string("ñ").unwrap(), "ß");
 
-        assert_eq!(
-            config
-                .to_ini_string_opts(ToIniStringOptions {
-                    arrays: true,
-                    ..Default::default()
-                })
-                .err()
-                .unwrap(),
-            ToIniStringError::InvalidArrayType
-        );
-    }
+    let array = config.root().get_array("array").unwrap();
+    assert_eq!(array.len(), 3);
 
-    // Array of arrays.
-    {
-        let mut config = DynConfig::new();
-        let mut array = DynArray::new();
-        array.push(Value::Array(DynArray::new())).unwrap();
-        assert!(!config.root_mut().set(nestr!("array"), array));
+    assert_eq!(array.get_string(0).unwrap(), "á");
+    assert_eq!(array.get_string(1).unwrap(), "é");
+    assert_eq!(array.get_string(2).unwrap(), "íó");
 
-        assert_eq!(
-            config
-                .to_ini_string_opts(ToIniStringOptions {
-                    arrays: true,
-                    ..Default::default()
-                })
-                .err()
-                .unwrap(),
-            ToIniStringError::InvalidArrayType
-        );
-    }
-}
+    let section = config.root().get_table("секция").unwrap();
+    assert_eq!(section.len(), 1);
 
-#[test]
-fn NestedSectionsNotAllowed() {
-    let mut config = DynConfig::new();
-    assert!(!config.root_mut().set(nestr!("table"), DynTable::new()));
-    let table = config.root_mut().get_table_mut("table").unwrap();
-    assert!(!table.set(nestr!("nested_table"), DynTable::new()));
+    assert_eq!(section.get_string("ü ö").unwrap(), "ää");
 
+    // Error columns are counted in chars, not bytes.
     assert_eq!(
-        config.to_ini_string(),
-        Err(ToIniStringError::NestedSectionDepthExceeded)
+        dyn_config_error("ключ = значение ="),
+        IniError {
+            line: 1,
+            column: 17,
+            offset: 28,
+            error: IniErrorKind::InvalidCharacterAtLineEnd('='),
+            path: ConfigPath::new(),
+        }
     );
+}
 
-    // But this works.
+#[test]
+fn scratch() {
+    let mut scratch = IniParserScratch::new();
+
+    let parse = |string: &str, scratch: &mut IniParserScratch| {
+        DynConfig::from_ini_with_scratch(
+            IniParser::new(string)
+                .nested_section_depth(3)
+                .implicit_parent_sections(true),
+            scratch,
+        )
+    };
 
+    // Escaped section names / keys / values use the helper buffers.
+    let config = parse("[\\x66oo/b\\x61r]\n\\x62az = \\x62ob", &mut scratch).unwrap();
     assert_eq!(
         config
-            .to_ini_string_opts(ToIniStringOptions {
-                nested_section_depth: 2,
-                ..Default::default()
-            })
+            .root()
+            .get_string_path(&["foo".into(), "bar".into(), "baz".into()])
             .unwrap(),
-        "[table]\n\n[table/nested_table]"
+        "bob"
     );
 
-    // With implicit parent sections.
+    // Scratch state is cleared after an error.
     assert_eq!(
-        config
-            .to_ini_string_opts(ToIniStringOptions {
-                nested_section_depth: 2,
-                implicit_parent_sections: true,
-                ..Default::default()
-            })
+        parse("[\\x66oo/b\\x61r]\n\\x62az = \\x62ob =", &mut scratch)
+            .err()
             .unwrap(),
-        "[table/nested_table]"
+        IniError {
+            line: 2,
+            column: 17,
+            offset: 32,
+            error: IniErrorKind::InvalidCharacterAtLineEnd('='),
+            path: vec![nestr!("foo").into(), nestr!("bar").into()].into(),
+        }
     );
 
-    assert!(!config.root_mut().set(nestr!("baz"), true));
-    assert!(!config.root_mut().set(nestr!("bill"), "bob"));
+    let config = parse("\\x61 = \\x62\n[\\x63]", &mut scratch).unwrap();
+    assert_eq!(config.root().len(), 2);
+    assert_eq!(config.root().get_string("a").unwrap(), "b");
+    assert_eq!(config.root().get_table("c").unwrap().len(), 0);
 
-    let table = config.root_mut().get_table_mut("table").unwrap();
-    assert!(!table.set(nestr!("foo"), 7));
-    assert!(!table.set(nestr!("bar"), 3.14));
+    scratch.reset();
 
-    assert_eq!(
+    let config = parse("a = b", &mut scratch).unwrap();
+    assert_eq!(config.root().get_string("a").unwrap(), "b");
+}
+
+#[test]
+fn stream() {
+    let parser = |string| {
+        IniParser::new(string)
+            .nested_section_depth(3)
+            .arrays(true)
+            .escape(true)
+            .line_continuation(true)
+            .inline_comments(true)
+    };
+
+    let string = "a = 7\r\n\\x62 = \"f\\u00f6ö\" ; comment\n[c]\nd = [1, 2.5]\n[c/e]\r\nf = g\\\nh\n\n[i]\nj = true";
+
+    let to_ini_string = |config: &DynConfig| {
         config
             .to_ini_string_opts(ToIniStringOptions {
-                nested_section_depth: 2,
+                arrays: true,
+                nested_section_depth: 3,
                 ..Default::default()
             })
-            .unwrap(),
-        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table]"
+            .unwrap()
+    };
+
+    let expected = to_ini_string(&DynConfig::from_ini(parser(string)).unwrap());
+
+    // Any chunk size produces the same result.
+    for chunk_size in 1..=string.len() {
+        let mut stream = parser("").into_stream(DynConfigIniConfig::new());
+
+        let mut offset = 0;
+
+        while offset < string.len() {
+            let mut end = (offset + chunk_size).min(string.len());
+
+            while !string.is_char_boundary(end) {
+                end += 1;
+            }
+
+            stream.feed(&string[offset..end]).unwrap();
+            offset = end;
+        }
+
+        let config = stream.finish().unwrap().into_inner();
+
+        assert_eq!(to_ini_string(&config), expected);
+        assert_eq!(
+            config
+                .root()
+                .get_string_path(&["c".into(), "e".into(), "f".into()])
+                .unwrap(),
+            "gh"
+        );
+    }
+
+    // The parser's string is the first chunk.
+    let mut stream = parser("a = 7\n[b").into_stream(DynConfigIniConfig::new());
+    stream.feed("]\nc = ").unwrap();
+    stream.feed("true").unwrap();
+    let config = stream.finish().unwrap().into_inner();
+    assert_eq!(config.root().get_i64("a").unwrap(), 7);
+    assert!(config.root().get_table("b").unwrap().get_bool("c").unwrap());
+
+    // Errors are reported at the same location, and are returned by all the following calls.
+    let string = "a = 7\n[b]\nc = d e";
+
+    let expected = DynConfig::from_ini(parser(string)).err().unwrap();
+    assert_eq!(
+        expected,
+        IniError {
+            line: 3,
+            column: 7,
+            offset: 16,
+            error: IniErrorKind::InvalidCharacterAtLineEnd('e'),
+            path: vec![nestr!("b").into()].into(),
+        }
     );
 
-    let table = config.root_mut().get_table_mut("table").unwrap();
-    assert!(!table
-        .get_table_mut("nested_table")
-        .unwrap()
-        .set(nestr!("another_nested_table"), DynTable::new()));
+    let mut stream = IniStreamParser::new(DynConfigIniConfig::new());
+    stream.feed("a = 7\n[b]\nc").unwrap();
+    stream.feed(" = d e\n").err().unwrap();
+    assert_eq!(stream.feed("f = g\n").err().unwrap(), expected);
+    assert_eq!(stream.finish().err().unwrap(), expected);
+}
 
-    assert_eq!(config
-        .to_ini_string_opts(ToIniStringOptions {
-            nested_section_depth: 3,
-            ..Default::default()
-        })
-        .unwrap(),
-        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table]\n\n[table/nested_table/another_nested_table]"
+#[test]
+fn key_collisions() {
+    let parse = |string, key_collisions| {
+        let mut config = DynConfigIniConfig::new();
+        let mut spellings = IniKeySpellings::new();
+
+        IniParser::new(string)
+            .nested_section_depth(2)
+            .case_insensitive_keys(true)
+            .key_collisions(key_collisions)
+            .parse_with_key_spellings(&mut config, &mut spellings)
+            .map(|_| (config.into_inner(), spellings))
+    };
+
+    // Keys are case-sensitive by default.
+    let config = dyn_config("Key = 7\nKEY = 8");
+    assert_eq!(config.root().get_i64("Key").unwrap(), 7);
+    assert_eq!(config.root().get_i64("KEY").unwrap(), 8);
+
+    // Section names / keys are converted to lowercase, original spellings are recorded.
+    let (mut config, spellings) = parse(
+        "Key = 7\n[Section]\nFoo = true\n[section/Nested]\nbar = 9",
+        IniKeyCollisions::Forbid,
+    )
+    .unwrap();
+
+    assert_eq!(config.root().get_i64("key").unwrap(), 7);
+    assert!(config
+        .root()
+        .get_bool_path(&["section".into(), "foo".into()])
+        .unwrap());
+    assert_eq!(
+        config
+            .root()
+            .get_i64_path(&["section".into(), "nested".into(), "bar".into()])
+            .unwrap(),
+        9
     );
 
-    // With implicit parent sections.
-    assert_eq!(config
-        .to_ini_string_opts(ToIniStringOptions {
-            nested_section_depth: 3,
-            implicit_parent_sections: true,
-            ..Default::default()
-        })
-        .unwrap(),
-        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table/another_nested_table]"
+    assert_eq!(spellings.len(), 2);
+    assert_eq!(spellings.get("key").unwrap().spelling(), "Key");
+    let section = spellings.get("section").unwrap();
+    assert_eq!(section.spelling(), "Section");
+    assert_eq!(section.children().get("foo").unwrap().spelling(), "Foo");
+    assert_eq!(
+        section
+            .children()
+            .get("nested")
+            .unwrap()
+            .children()
+            .get("bar")
+            .unwrap()
+            .spelling(),
+        "bar"
     );
 
-    assert!(!config.root_mut().set(nestr!("test_table"), DynTable::new()));
+    // Original spellings may be restored.
+    config.restore_key_spellings(&spellings);
 
-    assert_eq!(config
-        .to_ini_string_opts(ToIniStringOptions {
-            nested_section_depth: 3,
-            ..Default::default()
-        })
-        .unwrap(),
-        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table]\n\n[table/nested_table/another_nested_table]\n\n[test_table]"
+    assert_eq!(config.root().get_i64("Key").unwrap(), 7);
+    assert!(config
+        .root()
+        .get_bool_path(&["Section".into(), "Foo".into()])
+        .unwrap());
+    assert_eq!(
+        config
+            .root()
+            .get_i64_path(&["Section".into(), "Nested".into(), "bar".into()])
+            .unwrap(),
+        9
     );
 
-    // With implicit parent sections.
-    assert_eq!(config
-        .to_ini_string_opts(ToIniStringOptions {
-            nested_section_depth: 3,
-            implicit_parent_sections: true,
-            ..Default::default()
-        })
-        .unwrap(),
-        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table/another_nested_table]\n\n[test_table]"
+    // Collisions are forbidden.
+    assert_eq!(
+        parse("Key = 7\nKEY = 8", IniKeyCollisions::Forbid)
+            .err()
+            .unwrap(),
+        IniError {
+            line: 2,
+            column: 3,
+            offset: 10,
+            error: IniErrorKind::KeyCollision("Key".into(), "KEY".into()),
+            path: ConfigPath::new(),
+        }
+    );
+    assert_eq!(
+        parse("[Section]\n[SECTION]", IniKeyCollisions::Forbid)
+            .err()
+            .unwrap(),
+        IniError {
+            line: 2,
+            column: 9,
+            offset: 18,
+            error: IniErrorKind::KeyCollision("Section".into(), "SECTION".into()),
+            path: ConfigPath::new(),
+        }
     );
-}
 
-#[test]
-fn from_string_and_back() {
-    let ini = r#"array = ["foo", "bar", "baz"]
-bool = true
-float = 3.14
-int = 7
-string = "foo"
+    // Duplicates with the same spelling are handled by the duplicate key policy.
+    assert_eq!(
+        parse("Key = 7\nKey = 8", IniKeyCollisions::Last)
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::DuplicateKey
+    );
 
-["other 's/e/c/t/i/o/n'"]
-other_bool = true
-other_float = 5.45
-other_int = 9
-other_string = "foo 'bar'\t"
+    // First spelling wins.
+    let (config, spellings) = parse(
+        "Key = 7\nKEY = 8\n[Section]\na = 1\n[SECTION]\nb = 2",
+        IniKeyCollisions::First,
+    )
+    .unwrap();
 
-[section]
-bool = false
-float = 7.62
-int = 11
-string = "bar"
+    assert_eq!(config.root().get_i64("key").unwrap(), 7);
+    let section = config.root().get_table("section").unwrap();
+    assert_eq!(section.len(), 1);
+    assert_eq!(section.get_i64("a").unwrap(), 1);
 
-[section/nested_section]
-bool = false
-float = 5.56
-int = 13
-string = "baz""#;
+    assert_eq!(spellings.get("key").unwrap().spelling(), "Key");
+    assert_eq!(spellings.get("section").unwrap().spelling(), "Section");
 
-    let config = DynConfig::from_ini(
-        IniParser::new(ini)
-            .arrays(true)
-            .nested_section_depth(u32::MAX),
+    // Last spelling wins.
+    let (config, spellings) = parse(
+        "Key = 7\nKEY = 8\n[Section]\na = 1\n[SECTION]\nb = 2",
+        IniKeyCollisions::Last,
     )
     .unwrap();
 
-    let string = config
-        .to_ini_string_opts(ToIniStringOptions {
-            arrays: true,
-            nested_section_depth: 2,
-            ..Default::default()
-        })
-        .unwrap();
+    assert_eq!(config.root().get_i64("key").unwrap(), 8);
+    let section = config.root().get_table("section").unwrap();
+    assert_eq!(section.len(), 1);
+    assert_eq!(section.get_i64("b").unwrap(), 2);
 
-    assert_eq!(ini, string);
+    assert_eq!(spellings.get("key").unwrap().spelling(), "KEY");
+    assert_eq!(spellings.get("section").unwrap().spelling(), "SECTION");
 }
 
 #[test]
-fn escape() {
-    // With escape sequences supported.
-    let ini = DynConfig::from_ini(
-        IniParser::new(
-            r#"[a\ b]
-"c\t" = '\x66\x6f\x6f'"#,
-        )
-        .string_quotes(IniStringQuote::Single | IniStringQuote::Double),
-    )
-    .unwrap();
+fn InvalidKey() {
+    fn validate_key(key: &NonEmptyStr) -> Result<(), String> {
+        if key.len() > 8 {
+            Err(format!("\"{}\" is longer than 8 characters", key))
+        } else if key.as_str() == "reserved" {
+            Err("\"reserved\" is a reserved name".to_owned())
+        } else {
+            Ok(())
+        }
+    }
 
-    let section = ini.root().get_table("a b").unwrap();
+    let parser = |string| {
+        IniParser::new(string)
+            .nested_section_depth(2)
+            .validate_key(validate_key)
+    };
 
-    assert_eq!(section.len(), 1);
-    assert_eq!(section.get_string("c\t").unwrap(), "foo");
+    let config = DynConfig::from_ini(parser("foo = 7\n[bar]\nbaz = true")).unwrap();
+    assert_eq!(config.root().get_i64("foo").unwrap(), 7);
 
-    // Section name enclosed in double quotes when serializing back to string.
+    // Keys.
     assert_eq!(
-        ini.to_ini_string().unwrap(),
-        r#"["a b"]
-"c\t" = "foo""#
+        DynConfig::from_ini(parser("foo = 7\nlong_key_name = 9"))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 2,
+            column: 13,
+            offset: 20,
+            error: IniErrorKind::InvalidKey("\"long_key_name\" is longer than 8 characters".into()),
+            path: ConfigPath::new(),
+        }
     );
 
-    // Attempt to serialize an escaped character with support for escaped characters disabled.
-    let ini = dyn_config("a\\t = 7");
+    // Section names.
+    assert_eq!(
+        DynConfig::from_ini(parser("[reserved]")).err().unwrap(),
+        IniError {
+            line: 1,
+            column: 10,
+            offset: 9,
+            error: IniErrorKind::InvalidKey("\"reserved\" is a reserved name".into()),
+            path: ConfigPath::new(),
+        }
+    );
 
+    // Parent section names.
     assert_eq!(
-        ini.to_ini_string_opts(ToIniStringOptions {
-            escape: false,
-            ..Default::default()
-        })
-        .err()
-        .unwrap(),
-        ToIniStringError::EscapedCharacterNotAllowed('\t')
+        DynConfig::from_ini(parser("[reserved/foo]"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::InvalidKey("\"reserved\" is a reserved name".into())
     );
 
-    // With escape sequences unsupported.
+    // Keys are validated after case folding.
+    assert_eq!(
+        DynConfig::from_ini(parser("RESERVED = 7").case_insensitive_keys(true))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::InvalidKey("\"reserved\" is a reserved name".into())
+    );
+}
+
+#[test]
+fn strict_values() {
+    let ini = "version = 1.10\nbool = true\nint = -7\nquoted = \"1.10\"\narray = [1, 2.5]";
+
+    // Values are interpreted by default.
+    let config = DynConfig::from_ini(IniParser::new(ini).arrays(true)).unwrap();
+
+    assert!(cmp_f64(config.root().get_f64("version").unwrap(), 1.1));
+    assert!(config.root().get_bool("bool").unwrap());
+
+    // All values are strings in strict mode.
+    let config = DynConfig::from_ini(IniParser::new(ini).arrays(true).strict_values(true)).unwrap();
+
+    assert_eq!(config.root().get_string("version").unwrap(), "1.10");
+    assert_eq!(config.root().get_string("bool").unwrap(), "true");
+    assert_eq!(config.root().get_string("int").unwrap(), "-7");
+    assert_eq!(config.root().get_string("quoted").unwrap(), "1.10");
+
+    let array = config.root().get_array("array").unwrap();
+    assert_eq!(array.get_string(0).unwrap(), "1");
+    assert_eq!(array.get_string(1).unwrap(), "2.5");
+
+    // Values are converted by the typed accessors.
+    assert_eq!(config.root().get_or("bool", Lenient(false)), Lenient(true));
+    assert_eq!(config.root().get_or("int", Lenient(0)), Lenient(-7));
+    assert_eq!(config.root().get_or("version", Lenient(0.0)), Lenient(1.1));
+    assert_eq!(config.root().get_or("version", Lenient(0)), Lenient(1));
+    assert_eq!(
+        config.root().get_or("quoted", Lenient(false)),
+        Lenient(false)
+    );
+
+    // Unquoted values are not allowed if unquoted strings are not supported.
     assert_eq!(
         DynConfig::from_ini(
-            IniParser::new(
-                r#"[a\ b]
-"c\t" = '\u0066\u006f\u006f'"#
-            )
-            .escape(false)
+            IniParser::new("int = 7")
+                .strict_values(true)
+                .unquoted_strings(false)
         )
         .err()
         .unwrap(),
         IniError {
             line: 1,
-            column: 5,
-            error: IniErrorKind::InvalidCharacterAfterSectionName('b'),
-            path: vec![nestr!("a\\").into()].into(),
+            column: 7,
+            offset: 6,
+            error: IniErrorKind::UnquotedString,
+            path: vec![nestr!("int").into()].into(),
         }
     );
+}
 
-    let ini = DynConfig::from_ini(
-        IniParser::new(
-            r#"["a\ b"]
-"c\t" = '\u0066\u00f6\u00f6'"#,
-        )
-        .escape(false)
-        .string_quotes(IniStringQuote::Single | IniStringQuote::Double),
-    )
-    .unwrap();
+#[cfg(feature = "datetime")]
+#[test]
+fn datetime() {
+    let ini = r#"datetime = 2024-01-01T10:00:00Z
+quoted = "2024-01-01T10:00:00Z"
+not_a_datetime = 2024-01-01
+array = [1985-04-12T23:20:50.52-05:00, 2024-01-01T10:00:00Z]"#;
+
+    let config = DynConfig::from_ini(IniParser::new(ini).arrays(true)).unwrap();
+
+    let datetime: DateTime = "2024-01-01T10:00:00Z".parse().unwrap();
 
+    assert_eq!(config.root().get_datetime("datetime").unwrap(), datetime);
     assert_eq!(
-        ini.root()
-            .get_table("a\\ b")
-            .unwrap()
-            .get_string("c\\t")
-            .unwrap(),
-        "\\u0066\\u00f6\\u00f6"
+        config.root().get_string("quoted").unwrap(),
+        "2024-01-01T10:00:00Z"
+    );
+    assert_eq!(
+        config.root().get_string("not_a_datetime").unwrap(),
+        "2024-01-01"
     );
 
-    let string = r#"["a\ b"]
-"c\t" = "\u0066\u00f6\u00f6""#;
+    let array = config.root().get_array("array").unwrap();
+    assert_eq!(
+        array.get_datetime(0).unwrap().to_string(),
+        "1985-04-12T23:20:50.52-05:00"
+    );
+    assert_eq!(array.get_datetime(1).unwrap(), datetime);
 
-    assert_ne!(ini.to_ini_string().unwrap(), string);
+    // Date/time values may not be mixed with other value types in arrays.
     assert_eq!(
-        ini.to_ini_string_opts(ToIniStringOptions {
-            escape: false,
-            ..Default::default()
-        })
-        .unwrap(),
-        string
+        DynConfig::from_ini(IniParser::new("array = [2024-01-01T10:00:00Z, \"foo\"]").arrays(true))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 1,
+            column: 32,
+            offset: 31,
+            error: IniErrorKind::MixedArray,
+            path: vec![nestr!("array").into()].into(),
+        }
+    );
+
+    // Date/time values are serialized unquoted and parsed back.
+    let string = config
+        .to_ini_string_opts(ToIniStringOptions {
+            arrays: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(
+        string,
+        r#"array = [1985-04-12T23:20:50.52-05:00, 2024-01-01T10:00:00Z]
+datetime = 2024-01-01T10:00:00Z
+not_a_datetime = "2024-01-01"
+quoted = "2024-01-01T10:00:00Z""#
+    );
+
+    let config = DynConfig::from_ini(IniParser::new(&string).arrays(true)).unwrap();
+    assert_eq!(config.root().get_datetime("datetime").unwrap(), datetime);
+}
+
+#[test]
+fn ArraysNotAllowed() {
+    let mut config = DynConfig::new();
+    assert!(!config.root_mut().set(nestr!("array"), DynArray::new()));
+
+    assert_eq!(
+        config.to_ini_string().err().unwrap(),
+        ToIniStringError::ArraysNotAllowed
+    );
+
+    // But this succeeds.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                arrays: true,
+                ..Default::default()
+            })
+            .unwrap(),
+        "array = []"
+    );
+}
+
+#[test]
+fn InvalidArrayType() {
+    // Array of tables.
+    {
+        let mut config = DynConfig::new();
+        let mut array = DynArray::new();
+        array.push(Value::Table(DynTable::new())).unwrap();
+        assert!(!config.root_mut().set(nestr!("array"), array));
+
+        assert_eq!(
+            config
+                .to_ini_string_opts(ToIniStringOptions {
+                    arrays: true,
+                    ..Default::default()
+                })
+                .err()
+                .unwrap(),
+            ToIniStringError::InvalidArrayType
+        );
+    }
+
+    // Array of arrays.
+    {
+        let mut config = DynConfig::new();
+        let mut array = DynArray::new();
+        array.push(Value::Array(DynArray::new())).unwrap();
+        assert!(!config.root_mut().set(nestr!("array"), array));
+
+        assert_eq!(
+            config
+                .to_ini_string_opts(ToIniStringOptions {
+                    arrays: true,
+                    ..Default::default()
+                })
+                .err()
+                .unwrap(),
+            ToIniStringError::InvalidArrayType
+        );
+    }
+}
+
+#[test]
+fn NestedSectionsNotAllowed() {
+    let mut config = DynConfig::new();
+    assert!(!config.root_mut().set(nestr!("table"), DynTable::new()));
+    let table = config.root_mut().get_table_mut("table").unwrap();
+    assert!(!table.set(nestr!("nested_table"), DynTable::new()));
+
+    assert_eq!(
+        config.to_ini_string(),
+        Err(ToIniStringError::NestedSectionDepthExceeded)
+    );
+
+    // But this works.
+
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_section_depth: 2,
+                ..Default::default()
+            })
+            .unwrap(),
+        "[table]\n\n[table/nested_table]"
+    );
+
+    // With implicit parent sections.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_section_depth: 2,
+                implicit_parent_sections: true,
+                ..Default::default()
+            })
+            .unwrap(),
+        "[table/nested_table]"
+    );
+
+    assert!(!config.root_mut().set(nestr!("baz"), true));
+    assert!(!config.root_mut().set(nestr!("bill"), "bob"));
+
+    let table = config.root_mut().get_table_mut("table").unwrap();
+    assert!(!table.set(nestr!("foo"), 7));
+    assert!(!table.set(nestr!("bar"), 3.14));
+
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_section_depth: 2,
+                ..Default::default()
+            })
+            .unwrap(),
+        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table]"
+    );
+
+    let table = config.root_mut().get_table_mut("table").unwrap();
+    assert!(!table
+        .get_table_mut("nested_table")
+        .unwrap()
+        .set(nestr!("another_nested_table"), DynTable::new()));
+
+    assert_eq!(config
+        .to_ini_string_opts(ToIniStringOptions {
+            nested_section_depth: 3,
+            ..Default::default()
+        })
+        .unwrap(),
+        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table]\n\n[table/nested_table/another_nested_table]"
+    );
+
+    // With implicit parent sections.
+    assert_eq!(config
+        .to_ini_string_opts(ToIniStringOptions {
+            nested_section_depth: 3,
+            implicit_parent_sections: true,
+            ..Default::default()
+        })
+        .unwrap(),
+        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table/another_nested_table]"
+    );
+
+    assert!(!config.root_mut().set(nestr!("test_table"), DynTable::new()));
+
+    assert_eq!(config
+        .to_ini_string_opts(ToIniStringOptions {
+            nested_section_depth: 3,
+            ..Default::default()
+        })
+        .unwrap(),
+        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table]\n\n[table/nested_table/another_nested_table]\n\n[test_table]"
+    );
+
+    // With implicit parent sections.
+    assert_eq!(config
+        .to_ini_string_opts(ToIniStringOptions {
+            nested_section_depth: 3,
+            implicit_parent_sections: true,
+            ..Default::default()
+        })
+        .unwrap(),
+        "baz = true\nbill = \"bob\"\n\n[table]\nbar = 3.14\nfoo = 7\n\n[table/nested_table/another_nested_table]\n\n[test_table]"
+    );
+}
+
+#[test]
+fn nested_tables() {
+    let mut config = DynConfig::new();
+    assert!(!config.root_mut().set(nestr!("baz"), true));
+    assert!(!config.root_mut().set(nestr!("table"), DynTable::new()));
+
+    let table = config.root_mut().get_table_mut("table").unwrap();
+    assert!(!table.set(nestr!("foo"), 7));
+    assert!(!table.set(nestr!("nested_table"), DynTable::new()));
+    assert!(!table.set(nestr!("empty_table"), DynTable::new()));
+
+    let nested_table = table.get_table_mut("nested_table").unwrap();
+    assert!(!nested_table.set(nestr!("bar"), "bob"));
+    assert!(!nested_table.set(nestr!("another_nested_table"), DynTable::new()));
+
+    let another_nested_table = nested_table.get_table_mut("another_nested_table").unwrap();
+    assert!(!another_nested_table.set(nestr!("bill"), 3.14));
+
+    // Flattened with a separator in the key.
+    let string = config
+        .to_ini_string_opts(ToIniStringOptions {
+            nested_tables: IniNestedTables::Flatten('.'),
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(
+        string,
+        "baz = true\n\n[table]\nfoo = 7\nnested_table.bar = \"bob\"\nnested_table.another_nested_table.bill = 3.14"
+    );
+
+    // Flattened keys are parsed back as is.
+    let parsed = DynConfig::from_ini(IniParser::new(&string)).unwrap();
+    let table = parsed.root().get_table("table").unwrap();
+    assert_eq!(table.get_i64("foo").unwrap(), 7);
+    assert_eq!(table.get_string("nested_table.bar").unwrap(), "bob");
+    assert_eq!(
+        table
+            .get_f64("nested_table.another_nested_table.bill")
+            .unwrap(),
+        3.14
+    );
+
+    // Flattened keys are quoted if the separator requires it.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_tables: IniNestedTables::Flatten(':'),
+                ..Default::default()
+            })
+            .unwrap(),
+        "baz = true\n\n[table]\nfoo = 7\n\"nested_table:bar\" = \"bob\"\n\"nested_table:another_nested_table:bill\" = 3.14"
+    );
+
+    // Nested section depth is ignored.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_tables: IniNestedTables::Flatten('_'),
+                nested_section_depth: 3,
+                implicit_parent_sections: true,
+                ..Default::default()
+            })
+            .unwrap(),
+        "baz = true\n\n[table]\nfoo = 7\nnested_table_bar = \"bob\"\nnested_table_another_nested_table_bill = 3.14"
+    );
+
+    // Nested tables not allowed.
+    assert_eq!(
+        config.to_ini_string_opts(ToIniStringOptions {
+            nested_tables: IniNestedTables::Error,
+            nested_section_depth: 3,
+            ..Default::default()
+        }),
+        Err(ToIniStringError::NestedTablesNotAllowed)
+    );
+
+    // But root level tables are.
+    let table = config.root_mut().get_table_mut("table").unwrap();
+    assert!(table.remove("nested_table").is_some());
+    assert!(table.remove("empty_table").is_some());
+
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_tables: IniNestedTables::Error,
+                ..Default::default()
+            })
+            .unwrap(),
+        "baz = true\n\n[table]\nfoo = 7"
+    );
+}
+
+#[test]
+fn from_string_and_back() {
+    let ini = r#"array = ["foo", "bar", "baz"]
+bool = true
+float = 3.14
+int = 7
+string = "foo"
+
+["other 's/e/c/t/i/o/n'"]
+other_bool = true
+other_float = 5.45
+other_int = 9
+other_string = "foo 'bar'\t"
+
+[section]
+bool = false
+float = 7.62
+int = 11
+string = "bar"
+
+[section/nested_section]
+bool = false
+float = 5.56
+int = 13
+string = "baz""#;
+
+    let config = DynConfig::from_ini(
+        IniParser::new(ini)
+            .arrays(true)
+            .nested_section_depth(u32::MAX),
+    )
+    .unwrap();
+
+    let string = config
+        .to_ini_string_opts(ToIniStringOptions {
+            arrays: true,
+            nested_section_depth: 2,
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(ini, string);
+}
+
+#[test]
+fn escape() {
+    // With escape sequences supported.
+    let ini = DynConfig::from_ini(
+        IniParser::new(
+            r#"[a\ b]
+"c\t" = '\x66\x6f\x6f'"#,
+        )
+        .string_quotes(IniStringQuote::Single | IniStringQuote::Double),
+    )
+    .unwrap();
+
+    let section = ini.root().get_table("a b").unwrap();
+
+    assert_eq!(section.len(), 1);
+    assert_eq!(section.get_string("c\t").unwrap(), "foo");
+
+    // Section name enclosed in double quotes when serializing back to string.
+    assert_eq!(
+        ini.to_ini_string().unwrap(),
+        r#"["a b"]
+"c\t" = "foo""#
+    );
+
+    // Attempt to serialize an escaped character with support for escaped characters disabled.
+    let ini = dyn_config("a\\t = 7");
+
+    assert_eq!(
+        ini.to_ini_string_opts(ToIniStringOptions {
+            escape: false,
+            ..Default::default()
+        })
+        .err()
+        .unwrap(),
+        ToIniStringError::EscapedCharacterNotAllowed('\t')
+    );
+
+    // With escape sequences unsupported.
+    assert_eq!(
+        DynConfig::from_ini(
+            IniParser::new(
+                r#"[a\ b]
+"c\t" = '\u0066\u006f\u006f'"#
+            )
+            .escape(false)
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 1,
+            column: 5,
+            offset: 4,
+            error: IniErrorKind::InvalidCharacterAfterSectionName('b'),
+            path: vec![nestr!("a\\").into()].into(),
+        }
+    );
+
+    let ini = DynConfig::from_ini(
+        IniParser::new(
+            r#"["a\ b"]
+"c\t" = '\u0066\u00f6\u00f6'"#,
+        )
+        .escape(false)
+        .string_quotes(IniStringQuote::Single | IniStringQuote::Double),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ini.root()
+            .get_table("a\\ b")
+            .unwrap()
+            .get_string("c\\t")
+            .unwrap(),
+        "\\u0066\\u00f6\\u00f6"
+    );
+
+    let string = r#"["a\ b"]
+"c\t" = "\u0066\u00f6\u00f6""#;
+
+    assert_ne!(ini.to_ini_string().unwrap(), string);
+    assert_eq!(
+        ini.to_ini_string_opts(ToIniStringOptions {
+            escape: false,
+            ..Default::default()
+        })
+        .unwrap(),
+        string
+    );
+}
+
+#[test]
+fn include() {
+    use std::collections::HashMap;
+
+    let mut sources = HashMap::new();
+    sources.insert("a.ini", "a = 7\n[b]\nc = true");
+    sources.insert("d.ini", "e = \"f\" ; comment\n!include \"a.ini\"");
+    sources.insert("cycle.ini", "!include \"d.ini\"\n!include \"cycle.ini\"");
+    sources.insert("error.ini", "g");
+
+    let parse = |string, include_merge, dependencies: &mut IniDependencyGraph| {
+        DynConfig::from_ini_with_includes(
+            IniParser::new(string)
+                .inline_comments(true)
+                .include_merge(include_merge),
+            &sources,
+            dependencies,
+        )
+    };
+
+    // Included keys are added to the current section, the included section remains current.
+    let mut dependencies = IniDependencyGraph::new();
+    let config = parse(
+        "[x]\n  !include \"d.ini\" ; comment\nh = 9",
+        IniIncludeMerge::Inline,
+        &mut dependencies,
+    )
+    .unwrap();
+
+    let x = config.root().get_table("x").unwrap();
+    assert_eq!(x.get_string("e").unwrap(), "f");
+    assert_eq!(x.get_i64("a").unwrap(), 7);
+    assert!(config.root().get_table("b").unwrap().get_bool("c").unwrap());
+    assert_eq!(
+        config.root().get_table("b").unwrap().get_i64("h").unwrap(),
+        9
+    );
+
+    assert_eq!(
+        dependencies.dependencies(),
+        &[
+            IniDependency {
+                kind: IniDependencyKind::Include,
+                from: None,
+                to: "d.ini".into()
+            },
+            IniDependency {
+                kind: IniDependencyKind::Include,
+                from: Some("d.ini".into()),
+                to: "a.ini".into()
+            },
+        ]
+    );
+    assert_eq!(dependencies.current(), None);
+
+    // Included sources are parsed in the root section, the current section is restored.
+    let mut dependencies = IniDependencyGraph::new();
+    let config = parse(
+        "[x]\n!include \"d.ini\"\nh = 9",
+        IniIncludeMerge::Isolated,
+        &mut dependencies,
+    )
+    .unwrap();
+
+    assert_eq!(config.root().get_string("e").unwrap(), "f");
+    assert_eq!(config.root().get_i64("a").unwrap(), 7);
+    assert!(config.root().get_table("b").unwrap().get_bool("c").unwrap());
+    assert_eq!(
+        config.root().get_table("x").unwrap().get_i64("h").unwrap(),
+        9
+    );
+    assert_eq!(
+        dependencies.includes().collect::<Vec<_>>(),
+        vec!["d.ini", "a.ini"]
+    );
+
+    // Duplicate keys are handled according to the duplicate key policy.
+    assert_eq!(
+        parse(
+            "a = 9\n!include \"a.ini\"",
+            IniIncludeMerge::Inline,
+            &mut IniDependencyGraph::new()
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 1,
+            column: 1,
+            offset: 0,
+            error: IniErrorKind::DuplicateKey,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+
+    // Not found.
+    let mut dependencies = IniDependencyGraph::new();
+    assert_eq!(
+        parse(
+            "!include \"foo.ini\"",
+            IniIncludeMerge::Inline,
+            &mut dependencies
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 1,
+            column: 18,
+            offset: 17,
+            error: IniErrorKind::IncludeNotFound("foo.ini".into()),
+            path: ConfigPath::new(),
+        }
+    );
+    assert!(dependencies.dependencies().is_empty());
+
+    // Cycle.
+    let mut dependencies = IniDependencyGraph::new();
+    assert_eq!(
+        parse(
+            "!include \"cycle.ini\"",
+            IniIncludeMerge::Inline,
+            &mut dependencies
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 2,
+            column: 20,
+            offset: 36,
+            error: IniErrorKind::IncludeCycle("cycle.ini".into()),
+            // `d.ini` includes `a.ini`, which ends in section `b`.
+            path: vec![nestr!("b").into()].into(),
+        }
+    );
+    assert_eq!(dependencies.current(), Some("cycle.ini"));
+    assert!(dependencies.has_cycles());
+
+    // Errors in the included sources.
+    let mut dependencies = IniDependencyGraph::new();
+    assert_eq!(
+        parse(
+            "[x]\n!include \"error.ini\"",
+            IniIncludeMerge::Inline,
+            &mut dependencies
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 1,
+            column: 1,
+            offset: 0,
+            error: IniErrorKind::UnexpectedEndOfFileBeforeKeyValueSeparator,
+            path: vec![nestr!("x").into()].into(),
+        }
+    );
+    assert_eq!(dependencies.current(), Some("error.ini"));
+
+    // Invalid directives.
+    for (string, column) in [
+        ("!inclde \"a.ini\"", 6),
+        ("!include\"a.ini\"", 9),
+        ("!include a.ini", 10),
+        ("!include \"\"", 11),
+        ("!include \"a.ini", 15),
+        ("!include \"a.ini\n\"", 15),
+        ("!include \"a.ini\" a", 18),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            parse(
+                string,
+                IniIncludeMerge::Inline,
+                &mut IniDependencyGraph::new()
+            )
+            .err()
+            .unwrap(),
+            IniError {
+                line: 1,
+                column: *column,
+                offset: *column as usize - 1,
+                error: IniErrorKind::InvalidIncludeDirective,
+                path: ConfigPath::new(),
+            }
+        );
+    }
+
+    // Include directives are only supported by `parse_with_includes`.
+    assert_eq!(
+        dyn_config("!include = \"a.ini\"")
+            .root()
+            .get_string("!include")
+            .unwrap(),
+        "a.ini"
+    );
+}
+
+#[test]
+fn spans() {
+    let parser = |string| {
+        IniParser::new(string)
+            .key_value_separator(IniKeyValueSeparator::Equals | IniKeyValueSeparator::Colon)
+            .inline_comments(true)
+    };
+
+    let string = "a = 7\n[b]\n\"ключ\" = \"значение\"\nc =\nd:true ;comment\ne = foo";
+
+    let span = |line, column, start, len| IniSpan {
+        line,
+        column,
+        start,
+        end: start + len,
+    };
+    let offset = |substring| string.find(substring).unwrap();
+
+    let expected = [
+        (
+            "a",
+            IniValueSpans {
+                key: span(1, 1, 0, 1),
+                value: span(1, 5, 4, 1),
+            },
+        ),
+        (
+            "b.ключ",
+            IniValueSpans {
+                key: span(3, 1, offset("\"ключ\""), "\"ключ\"".len()),
+                value: span(3, 10, offset("\"значение\""), "\"значение\"".len()),
+            },
+        ),
+        (
+            "b.c",
+            IniValueSpans {
+                key: span(4, 1, offset("c ="), 1),
+                // Empty value at the new line.
+                value: span(4, 4, offset("c =") + 3, 0),
+            },
+        ),
+        (
+            "b.d",
+            IniValueSpans {
+                key: span(5, 1, offset("d:"), 1),
+                value: span(5, 3, offset("true"), 4),
+            },
+        ),
+        (
+            "b.e",
+            IniValueSpans {
+                key: span(6, 1, offset("e = "), 1),
+                // Value at the EOF.
+                value: span(6, 5, offset("foo"), 3),
+            },
+        ),
+    ];
+
+    let (config, spans) = DynConfig::from_ini_with_spans(parser(string)).unwrap();
+
+    assert_eq!(config.root().get_table("b").unwrap().len(), 4);
+    assert_eq!(spans.len(), expected.len() as u32);
+
+    for (path, value_spans) in expected.iter() {
+        assert_eq!(
+            spans.get(&path.parse().unwrap()).unwrap(),
+            value_spans,
+            "{}",
+            path
+        );
+    }
+
+    assert_eq!(
+        &string[spans.get(&"b.d".parse().unwrap()).unwrap().value.range()],
+        "true"
+    );
+
+    // Stream parser reports the same spans for any chunk size.
+    struct Spans(Vec<IniValueSpans>);
+
+    impl<'s> IniConfig<'s> for Spans {
+        fn contains_key(&self, _: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+            None
+        }
+
+        fn add_value(&mut self, _: NonEmptyIniStr<'s, '_>, _: IniValue<'s, '_>, _: bool) {
+            panic!("expected a call to `add_value_with_spans()`")
+        }
+
+        fn add_value_with_spans(
+            &mut self,
+            _: NonEmptyIniStr<'s, '_>,
+            _: IniValue<'s, '_>,
+            _: bool,
+            spans: IniValueSpans,
+        ) {
+            self.0.push(spans);
+        }
+
+        fn start_section(&mut self, _: NonEmptyIniStr<'s, '_>, _: bool) {}
+        fn end_section(&mut self, _: NonEmptyIniStr<'s, '_>) {}
+        fn start_array(&mut self, _: NonEmptyIniStr<'s, '_>, _: bool) {}
+        fn add_array_value(&mut self, _: IniValue<'s, '_>) {}
+        fn end_array(&mut self, _: NonEmptyIniStr<'s, '_>) {}
+    }
+
+    for chunk_size in 1..=string.len() {
+        let mut stream = parser("").spans(true).into_stream(Spans(Vec::new()));
+
+        let mut offset = 0;
+
+        while offset < string.len() {
+            let mut end = (offset + chunk_size).min(string.len());
+
+            while !string.is_char_boundary(end) {
+                end += 1;
+            }
+
+            stream.feed(&string[offset..end]).unwrap();
+            offset = end;
+        }
+
+        let spans = stream.finish().unwrap().0;
+
+        assert_eq!(spans.len(), expected.len());
+
+        for (spans, (_, expected)) in spans.iter().zip(expected.iter()) {
+            assert_eq!(spans, expected);
+        }
+    }
+
+    // Spans of the overwritten values / sections are removed.
+    let (_, spans) = DynConfig::from_ini_with_spans(
+        IniParser::new("a = 1\na = 22\n[b]\nc = 3\n[b]\nd = 4")
+            .duplicate_keys(IniDuplicateKeys::Last)
+            .duplicate_sections(IniDuplicateSections::Last),
+    )
+    .unwrap();
+
+    assert_eq!(spans.len(), 2);
+    assert_eq!(
+        spans.get(&"a".parse().unwrap()).unwrap().value,
+        span(2, 5, 10, 2)
+    );
+    assert!(spans.get(&"b.c".parse().unwrap()).is_none());
+    assert_eq!(spans.get(&"b.d".parse().unwrap()).unwrap().key.line, 6);
+}
+
+#[test]
+fn array_sections() {
+    let ini = "a = 7
+
+[[item]]
+name = \"foo\"
+value = 1
+
+[[item]] ; comment
+name = \"bar\"
+
+[section]
+b = true
+
+[[item]]
+
+[[section/item]]
+c = [9, 10]";
+
+    let parser = || {
+        IniParser::new(ini)
+            .inline_comments(true)
+            .arrays(true)
+            .nested_section_depth(2)
+            .array_sections(true)
+    };
+
+    let config = DynConfig::from_ini(parser()).unwrap();
+
+    assert_eq!(config.root().get_i64("a").unwrap(), 7);
+
+    let items = config.root().get_array("item").unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(
+        items.get_table(0).unwrap().get_string("name").unwrap(),
+        "foo"
+    );
+    assert_eq!(items.get_table(0).unwrap().get_i64("value").unwrap(), 1);
+    assert_eq!(
+        items.get_table(1).unwrap().get_string("name").unwrap(),
+        "bar"
+    );
+    assert_eq!(items.get_table(2).unwrap().len(), 0);
+
+    let section = config.root().get_table("section").unwrap();
+    assert!(section.get_bool("b").unwrap());
+    assert_eq!(
+        section
+            .get_array("item")
+            .unwrap()
+            .get_table(0)
+            .unwrap()
+            .get_array("c")
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // Spans of the values in the arrays of sections.
+    let (_, spans) = DynConfig::from_ini_with_spans(parser().spans(true)).unwrap();
+    let path = |path: &str| path.parse::<ConfigPath>().unwrap();
+
+    // No spans for the arrays.
+    assert_eq!(spans.len(), 5);
+    assert_eq!(
+        &ini[spans.get(&path("item[1].name")).unwrap().value.range()],
+        "\"bar\""
+    );
+    assert_eq!(
+        &ini[spans.get(&path("item[0].value")).unwrap().value.range()],
+        "1"
+    );
+
+    // Round trip.
+    let options = ToIniStringOptions {
+        arrays: true,
+        nested_section_depth: 2,
+        array_sections: true,
+        ..Default::default()
+    };
+
+    let string = config.to_ini_string_opts(options).unwrap();
+
+    assert_eq!(
+        string,
+        "a = 7
+
+[[item]]
+name = \"foo\"
+value = 1
+
+[[item]]
+name = \"bar\"
+
+[[item]]
+
+[section]
+b = true
+
+[[section/item]]
+c = [9, 10]"
+    );
+
+    assert!(DynConfig::from_ini(parser())
+        .unwrap()
+        .diff(
+            &DynConfig::from_ini(
+                IniParser::new(&string)
+                    .arrays(true)
+                    .nested_section_depth(2)
+                    .array_sections(true)
+            )
+            .unwrap()
+        )
+        .is_empty());
+
+    // Arrays of tables are not supported by default.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                array_sections: false,
+                ..options
+            })
+            .err()
+            .unwrap(),
+        ToIniStringError::InvalidArrayType
+    );
+
+    // Tables in arrays of sections may not contain nested tables.
+    let mut nested = DynTable::new();
+    nested.set(nestr!("x"), 1);
+
+    let mut table = DynTable::new();
+    table.set(nestr!("nested"), nested);
+
+    let mut array = DynArray::new();
+    array.push(table.into()).unwrap();
+
+    let mut config = DynConfig::new();
+    config.root_mut().set(nestr!("item"), array);
+
+    assert_eq!(
+        config.to_ini_string_opts(options).err().unwrap(),
+        ToIniStringError::InvalidArrayType
+    );
+
+    // Unless flattened.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_tables: IniNestedTables::Flatten('.'),
+                ..options
+            })
+            .unwrap(),
+        "[[item]]\nnested.x = 1"
+    );
+
+    // Isolated includes continue the current section in the array of sections.
+    let mut sources = std::collections::HashMap::new();
+    sources.insert("a.ini", "[b]\nc = true");
+
+    let config = DynConfig::from_ini_with_includes(
+        IniParser::new("[[item]]\nd = 1\n!include \"a.ini\"\ne = 2\n[[item]]")
+            .array_sections(true)
+            .include_merge(IniIncludeMerge::Isolated),
+        &sources,
+        &mut IniDependencyGraph::new(),
+    )
+    .unwrap();
+
+    let items = config.root().get_array("item").unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items.get_table(0).unwrap().get_i64("d").unwrap(), 1);
+    assert_eq!(items.get_table(0).unwrap().get_i64("e").unwrap(), 2);
+    assert!(config.root().get_table("b").unwrap().get_bool("c").unwrap());
+}
+
+#[test]
+fn multiline_strings() {
+    let parser = |string| IniParser::new(string).multiline_strings(true);
+
+    let string = "shader = \"void main() {\n\tgl_Position = pos;\r\n}\r\"\ndesc = 'a\n\\\"b\\\"'\nempty = \"\n\"";
+
+    let config = DynConfig::from_ini(parser(string).string_quotes(IniStringQuote::all())).unwrap();
+
+    // New lines are preserved, carriage returns are normalized to line feeds.
+    assert_eq!(
+        config.root().get_string("shader").unwrap(),
+        "void main() {\n\tgl_Position = pos;\n}\n"
+    );
+    assert_eq!(config.root().get_string("desc").unwrap(), "a\n\"b\"");
+    assert_eq!(config.root().get_string("empty").unwrap(), "\n");
+
+    // Error position after a multi-line value.
+    assert_eq!(
+        DynConfig::from_ini(parser("a = \"b\nc\"\nd = \"e"))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 3,
+            column: 6,
+            offset: 15,
+            error: IniErrorKind::UnexpectedEndOfFileInQuotedString,
+            path: vec![nestr!("d").into()].into(),
+        }
+    );
+
+    // Quoted array values may not span multiple lines.
+    assert_eq!(
+        DynConfig::from_ini(parser("a = [\"b\nc\"]").arrays(true))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::UnexpectedNewLineInQuotedValue
+    );
+
+    // Stream parser, any chunk size.
+    for chunk_size in 1..=string.len() {
+        let mut stream = parser("")
+            .string_quotes(IniStringQuote::all())
+            .into_stream(DynConfigIniConfig::new());
+
+        for chunk in string.as_bytes().chunks(chunk_size) {
+            stream.feed(std::str::from_utf8(chunk).unwrap()).unwrap();
+        }
+
+        let config = stream.finish().unwrap().into_inner();
+
+        assert_eq!(
+            config.root().get_string("shader").unwrap(),
+            "void main() {\n\tgl_Position = pos;\n}\n"
+        );
+    }
+
+    // Line feeds are escaped by default.
+    let options = ToIniStringOptions {
+        multiline_strings: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        config.to_ini_string().unwrap(),
+        "desc = \"a\\n\\\"b\\\"\"\nempty = \"\\n\"\nshader = \"void main() {\\n\\tgl_Position = pos;\\n}\\n\""
+    );
+
+    // Serialized as multi-line strings.
+    let string = config.to_ini_string_opts(options).unwrap();
+
+    assert_eq!(
+        string,
+        "desc = \"a\n\\\"b\\\"\"\nempty = \"\n\"\nshader = \"void main() {\n\\tgl_Position = pos;\n}\n\""
+    );
+    assert!(config
+        .diff(&DynConfig::from_ini(parser(&string)).unwrap())
+        .is_empty());
+
+    // With single quotes and CR/LF new lines.
+    let string = config
+        .to_ini_string_opts(ToIniStringOptions {
+            formatter: ConfigFormatter {
+                quotes: ConfigQuotes::Single,
+                new_line: ConfigNewLine::CrLf,
+                ..Default::default()
+            },
+            ..options
+        })
+        .unwrap();
+
+    assert!(string.starts_with("desc = 'a\r\n\"b\"'\r\n"));
+    assert!(config
+        .diff(&DynConfig::from_ini(parser(&string).string_quotes(IniStringQuote::all())).unwrap())
+        .is_empty());
+}
+
+#[test]
+fn raw_strings() {
+    let parser = |string| IniParser::new(string).raw_strings(true);
+
+    let string = "path = `C:\\Program Files\\Foo\\`\nregex = `^\\d+ \"[a-z]\" 'x'$` ; comment\n`raw\\key` = \"C:\\\\foo\"\n[`C:\\bar`]\nbaz = ``";
+
+    let config = DynConfig::from_ini(parser(string).inline_comments(true)).unwrap();
+
+    // Backslashes are literal in raw strings.
+    assert_eq!(
+        config.root().get_string("path").unwrap(),
+        "C:\\Program Files\\Foo\\"
+    );
+    assert_eq!(
+        config.root().get_string("regex").unwrap(),
+        "^\\d+ \"[a-z]\" 'x'$"
+    );
+    assert_eq!(config.root().get_string("raw\\key").unwrap(), "C:\\foo");
+    assert_eq!(
+        config
+            .root()
+            .get_table("C:\\bar")
+            .unwrap()
+            .get_string("baz")
+            .unwrap(),
+        ""
+    );
+
+    // Backticks are not string quotes by default.
+    assert_eq!(dyn_config("a = `b`").root().get_string("a").unwrap(), "`b`");
+
+    // Raw strings may not contain backticks.
+    assert_eq!(
+        DynConfig::from_ini(parser("a = `b`c`")).err().unwrap(),
+        IniError {
+            line: 1,
+            column: 8,
+            offset: 7,
+            error: IniErrorKind::InvalidCharacterAtLineEnd('c'),
+            path: ConfigPath::new(),
+        }
+    );
+
+    // Raw strings are serialized if possible.
+    let options = ToIniStringOptions {
+        raw_strings: true,
+        ..Default::default()
+    };
+
+    let mut config = DynConfig::new();
+    config
+        .root_mut()
+        .set(nestr!("path"), "C:\\Program Files\\Foo");
+    config.root_mut().set(nestr!("tick"), "C:\\`foo`");
+    config.root_mut().set(nestr!("no_backslash"), "foo");
+    config.root_mut().set(nestr!("new_line"), "C:\\foo\n");
+
+    let string = config.to_ini_string_opts(options).unwrap();
+
+    assert_eq!(
+        string,
+        "new_line = \"C:\\\\foo\\n\"\nno_backslash = \"foo\"\npath = `C:\\Program Files\\Foo`\ntick = \"C:\\\\`foo`\""
+    );
+    assert!(config
+        .diff(&DynConfig::from_ini(parser(&string)).unwrap())
+        .is_empty());
+
+    // New lines in raw strings if multi-line strings are supported.
+    let string = config
+        .to_ini_string_opts(ToIniStringOptions {
+            multiline_strings: true,
+            ..options
+        })
+        .unwrap();
+
+    assert!(string.starts_with("new_line = `C:\\foo\n`\n"));
+    assert!(config
+        .diff(&DynConfig::from_ini(parser(&string).multiline_strings(true)).unwrap())
+        .is_empty());
+}
+
+#[test]
+fn type_annotations() {
+    let parser = |string| IniParser::new(string).type_annotations(true);
+
+    let config = DynConfig::from_ini(
+        parser(
+            "a:str = true\nb : int = 7\n\"c\":float = 7\nd:bool=false\ne:str = 0x17\nf:str =\ng:float = [1, 2.5]\nh:str = [true, \"foo\"]\n[section]\ni:int = -0x17",
+        )
+        .arrays(true),
+    )
+    .unwrap();
+
+    let root = config.root();
+
+    assert_eq!(root.get_string("a").unwrap(), "true");
+    assert_eq!(root.get_i64("b").unwrap(), 7);
+    assert_eq!(root.get_val("c").unwrap().get_type(), ValueType::F64);
+    assert!(cmp_f64(root.get_f64("c").unwrap(), 7.0));
+    assert!(!root.get_bool("d").unwrap());
+    assert_eq!(root.get_string("e").unwrap(), "0x17");
+    assert_eq!(root.get_string("f").unwrap(), "");
+
+    let g = root.get_array("g").unwrap();
+    assert_eq!(g.get_val(0).unwrap().get_type(), ValueType::F64);
+    assert!(cmp_f64(g.get_f64(1).unwrap(), 2.5));
+
+    let h = root.get_array("h").unwrap();
+    assert_eq!(h.get_string(0).unwrap(), "true");
+    assert_eq!(h.get_string(1).unwrap(), "foo");
+
+    assert_eq!(
+        root.get_table("section").unwrap().get_i64("i").unwrap(),
+        -23
+    );
+
+    // Type annotations only apply to their key.
+    let config = DynConfig::from_ini(parser("a:str = 7\nb = 7")).unwrap();
+    assert_eq!(config.root().get_string("a").unwrap(), "7");
+    assert_eq!(config.root().get_i64("b").unwrap(), 7);
+
+    // Colon key-value separators.
+    let config =
+        DynConfig::from_ini(parser("a:str:7").key_value_separator(IniKeyValueSeparator::Colon))
+            .unwrap();
+    assert_eq!(config.root().get_string("a").unwrap(), "7");
+}
+
+#[test]
+fn map_section() {
+    use std::borrow::Cow;
+
+    fn map_section(section: &NonEmptyStr) -> Cow<'_, NonEmptyStr> {
+        match section.as_str() {
+            // Legacy section names.
+            "gfx" => Cow::Borrowed(nestr!("graphics")),
+            "Items" => Cow::Owned(nestr!("items").to_owned()),
+            _ => Cow::Borrowed(section),
+        }
+    }
+
+    let parser = |string| {
+        IniParser::new(string)
+            .nested_section_depth(2)
+            .array_sections(true)
+            .map_section(map_section)
+    };
+
+    let string = "[gfx]\na = 7\n[\"gfx\"/sub]\nb = true\n[graphics]\nc = 9\n[[Items]]\nd = 1\n[[items]]\nd = 2";
+
+    let config = DynConfig::from_ini(parser(string)).unwrap();
+
+    // Mapped sections are merged with the sections with the same name.
+    let graphics = config.root().get_table("graphics").unwrap();
+    assert_eq!(graphics.get_i64("a").unwrap(), 7);
+    assert_eq!(graphics.get_i64("c").unwrap(), 9);
+    assert!(graphics.get_table("sub").unwrap().get_bool("b").unwrap());
+    assert!(config.root().get_table("gfx").is_err());
+
+    let items = config.root().get_array("items").unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items.get_table(1).unwrap().get_i64("d").unwrap(), 2);
+
+    // Mapped before case folding.
+    let config = DynConfig::from_ini(
+        IniParser::new("[GFX]\na = 7")
+            .case_insensitive_keys(true)
+            .map_section(|section: &NonEmptyStr| {
+                if section.as_str() == "GFX" {
+                    Cow::Borrowed(nestr!("Graphics"))
+                } else {
+                    Cow::Borrowed(section)
+                }
+            }),
+    )
+    .unwrap();
+    assert_eq!(
+        config
+            .root()
+            .get_table("graphics")
+            .unwrap()
+            .get_i64("a")
+            .unwrap(),
+        7
+    );
+
+    // Duplicate sections after mapping.
+    assert_eq!(
+        DynConfig::from_ini(
+            parser("[gfx]\n[graphics]").duplicate_sections(IniDuplicateSections::Forbid)
+        )
+        .err()
+        .unwrap()
+        .error,
+        IniErrorKind::DuplicateSection
+    );
+
+    // Stream parser.
+    let mut stream = parser("").into_stream(DynConfigIniConfig::new());
+    stream.feed("[gf").unwrap();
+    stream.feed("x]\na = 7\n").unwrap();
+
+    let config = stream.finish().unwrap().into_inner();
+    assert_eq!(
+        config
+            .root()
+            .get_table("graphics")
+            .unwrap()
+            .get_i64("a")
+            .unwrap(),
+        7
+    );
+}
+
+#[test]
+fn limits() {
+    let error = |parser: IniParser<'_>| DynConfig::from_ini(parser).err().unwrap().error;
+
+    // Section names / keys.
+    let parser = |string| IniParser::new(string).max_key_len(Some(3));
+
+    DynConfig::from_ini(parser("[abc]\nabc = 7")).unwrap();
+    assert_eq!(error(parser("abcd = 7")), IniErrorKind::KeyTooLong(3));
+    assert_eq!(error(parser("[abcd]")), IniErrorKind::KeyTooLong(3));
+    // After processing the escape sequences.
+    DynConfig::from_ini(parser("a\\=c = 7")).unwrap();
+
+    // Values.
+    let parser = |string| IniParser::new(string).arrays(true).max_value_len(Some(3));
+
+    DynConfig::from_ini(parser("a = foo\nb = \"bar\"\nc = [baz]")).unwrap();
+    assert_eq!(error(parser("a = fooo")), IniErrorKind::ValueTooLong(3));
+    assert_eq!(error(parser("a = \"fooo\"")), IniErrorKind::ValueTooLong(3));
+    assert_eq!(
+        error(parser("a = [foo, baar]")),
+        IniErrorKind::ValueTooLong(3)
+    );
+
+    // Arrays.
+    let parser = |string| IniParser::new(string).arrays(true).max_array_len(Some(2));
+
+    DynConfig::from_ini(parser("a = [1, 2]\nb = [3, 4]\nc = []")).unwrap();
+    assert_eq!(
+        error(parser("a = [1, 2, 3]")),
+        IniErrorKind::ArrayTooLong(2)
+    );
+    assert_eq!(
+        error(parser("a = [\"1\", \"2\", \"3\"]")),
+        IniErrorKind::ArrayTooLong(2)
+    );
+
+    // Sections.
+    let parser = |string| {
+        IniParser::new(string)
+            .nested_section_depth(2)
+            .max_sections(Some(2))
+    };
+
+    // Parent sections in nested section paths are not counted.
+    DynConfig::from_ini(parser("[a]\n[a/b]")).unwrap();
+    assert_eq!(
+        error(parser("[a]\n[b]\n[c]")),
+        IniErrorKind::TooManySections(2)
+    );
+    // Duplicate sections are counted.
+    assert_eq!(
+        error(parser("[a]\n[a]\n[a]")),
+        IniErrorKind::TooManySections(2)
+    );
+
+    // Source size.
+    let parser = |string| IniParser::new(string).max_size(Some(8));
+
+    DynConfig::from_ini(parser("a=7\nb=9")).unwrap();
+    assert_eq!(
+        DynConfig::from_ini(parser("a = 7\nb = 9")).err().unwrap(),
+        IniError {
+            line: 1,
+            column: 0,
+            offset: 0,
+            error: IniErrorKind::SourceTooLarge(8),
+            path: ConfigPath::new(),
+        }
+    );
+
+    // Included sources are counted.
+    let mut sources = std::collections::HashMap::new();
+    sources.insert("a.ini", "b = 9");
+
+    assert_eq!(
+        DynConfig::from_ini_with_includes(
+            parser("!include \"a.ini\""),
+            &sources,
+            &mut IniDependencyGraph::new(),
+        )
+        .err()
+        .unwrap()
+        .error,
+        IniErrorKind::SourceTooLarge(8)
+    );
+
+    // Stream parser - the incomplete last line is counted.
+    let mut stream = parser("").into_stream(DynConfigIniConfig::new());
+    stream.feed("a = 7\n").unwrap();
+    stream.feed("b=").unwrap();
+    assert_eq!(
+        stream.feed("99").err().unwrap().error,
+        IniErrorKind::SourceTooLarge(8)
+    );
+    // Same error is returned.
+    assert_eq!(
+        stream.finish().err().unwrap().error,
+        IniErrorKind::SourceTooLarge(8)
+    );
+}
+
+#[test]
+fn max_memory_usage() {
+    let source = "a = 7\nb = \"hello\"\n[c]\nd = [\"foo\", \"bar\"]\n[[e]]\nf = true";
+
+    let usage = DynConfig::from_ini(IniParser::new(source).arrays(true).array_sections(true))
+        .unwrap()
+        .memory_usage();
+
+    let parser = |source, max_memory_usage| {
+        IniParser::new(source)
+            .arrays(true)
+            .array_sections(true)
+            .value_references(true)
+            .max_memory_usage(Some(max_memory_usage))
+    };
+
+    // The estimate while parsing matches the memory usage of the built config.
+    assert_eq!(
+        DynConfig::from_ini(parser(source, usage))
+            .unwrap()
+            .memory_usage(),
+        usage
+    );
+    // The error is reported at the event which exceeded the limit.
+    assert_eq!(
+        DynConfig::from_ini(parser(source, usage - 1)).err().unwrap(),
+        IniError {
+            line: 6,
+            column: 8,
+            offset: 54,
+            error: IniErrorKind::MemoryLimitExceeded(usage - 1),
+            path: ConfigPath(vec![nestr!("e").into()]),
+        }
+    );
+    assert_eq!(
+        DynConfig::from_ini_with_spans(parser(source, usage - 1))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::MemoryLimitExceeded(usage - 1)
+    );
+
+    // Sections / arrays are counted, and parsing stops at the first one which exceeds the limit.
+    let usage = DynConfig::from_ini(parser("[a1]\n[a2]", usage))
+        .unwrap()
+        .memory_usage();
+
+    let source: String = (1..=1000).map(|i| format!("[a{}]\n", i)).collect();
+
+    assert_eq!(
+        DynConfig::from_ini(parser(&source, usage)).err().unwrap(),
+        IniError {
+            line: 3,
+            column: 4,
+            offset: 13,
+            error: IniErrorKind::MemoryLimitExceeded(usage),
+            path: ConfigPath(vec![nestr!("a3").into()]),
+        }
+    );
+    assert_eq!(
+        DynConfig::from_ini(parser("[a1]\n[a2]\nb = []", usage)).err().unwrap(),
+        IniError {
+            line: 3,
+            column: 5,
+            offset: 14,
+            error: IniErrorKind::MemoryLimitExceeded(usage),
+            path: ConfigPath(vec![nestr!("a2").into(), nestr!("b").into()]),
+        }
+    );
+
+    // Resolved value references are counted, the error is reported at the value reference.
+    let source = "a = \"hello\"\nb = @{a}";
+    let usage = DynConfig::from_ini(parser(source, usize::MAX))
+        .unwrap()
+        .memory_usage();
+
+    assert_eq!(
+        DynConfig::from_ini(parser(source, usage - 1)).err().unwrap(),
+        IniError {
+            line: 2,
+            column: 5,
+            offset: 16,
+            error: IniErrorKind::MemoryLimitExceeded(usage - 1),
+            path: ConfigPath(vec![nestr!("b").into()]),
+        }
+    );
+
+    // Interpolated values are counted, the error is reported at the interpolated value.
+    let source = "a = \"hello world\"\nb = \"%(a)s%(a)s\"";
+    let parser = |max_memory_usage| {
+        IniParser::new(source)
+            .interpolation(IniInterpolation::Basic)
+            .max_memory_usage(Some(max_memory_usage))
+    };
+    let usage = DynConfig::from_ini(parser(usize::MAX))
+        .unwrap()
+        .memory_usage();
+
+    assert_eq!(
+        DynConfig::from_ini(parser(usage - 1)).err().unwrap(),
+        IniError {
+            line: 2,
+            column: 5,
+            offset: 22,
+            error: IniErrorKind::MemoryLimitExceeded(usage - 1),
+            path: ConfigPath(vec![nestr!("b").into()]),
+        }
+    );
+}
+
+#[test]
+fn dyn_config_handler() {
+    // Tees the events to several (dynamic) event handlers, logging the sections.
+    struct Tee<'a, 's> {
+        handlers: Vec<&'a mut dyn IniConfig<'s>>,
+        log: Vec<String>,
+    }
+
+    impl<'a, 's> IniConfig<'s> for Tee<'a, 's> {
+        fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+            self.handlers[0].contains_key(key)
+        }
+
+        fn add_value(
+            &mut self,
+            key: NonEmptyIniStr<'s, '_>,
+            value: IniValue<'s, '_>,
+            overwrite: bool,
+        ) {
+            for handler in self.handlers.iter_mut() {
+                handler.add_value(key, value, overwrite);
+            }
+        }
+
+        fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+            self.log.push(section.as_str().to_owned());
+
+            for handler in self.handlers.iter_mut() {
+                handler.start_section(section, overwrite);
+            }
+        }
+
+        fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
+            for handler in self.handlers.iter_mut() {
+                handler.end_section(section);
+            }
+        }
+
+        fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+            for handler in self.handlers.iter_mut() {
+                handler.start_array(array, overwrite);
+            }
+        }
+
+        fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+            for handler in self.handlers.iter_mut() {
+                handler.add_array_value(value);
+            }
+        }
+
+        fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+            for handler in self.handlers.iter_mut() {
+                handler.end_array(array);
+            }
+        }
+    }
+
+    let string = "a = 7\n[b]\nc = [true, false]\n[d]\ne = \"f\"";
+
+    let mut first = DynConfigIniConfig::new();
+    let mut second: Box<dyn IniConfig<'_>> = Box::new(DynConfigIniConfig::new());
+
+    let mut tee = Tee {
+        handlers: vec![&mut first, &mut second],
+        log: Vec::new(),
+    };
+
+    // Driven through a trait object.
+    let handler: &mut dyn IniConfig<'_> = &mut tee;
+    IniParser::new(string).arrays(true).parse(handler).unwrap();
+
+    assert_eq!(tee.log, vec!["b", "d"]);
+
+    let first = first.into_inner();
+    assert_eq!(first.root().get_i64("a").unwrap(), 7);
+    assert!(!first
+        .root()
+        .get_table("b")
+        .unwrap()
+        .get_array("c")
+        .unwrap()
+        .get_bool(1)
+        .unwrap());
+    assert_eq!(
+        first
+            .root()
+            .get_table("d")
+            .unwrap()
+            .get_string("e")
+            .unwrap(),
+        "f"
+    );
+
+    // The second handler received the same events.
+    assert_eq!(
+        second.contains_key(NonEmptyIniStr::Borrowed(nestr!("a"))),
+        Some(false)
+    );
+    assert_eq!(
+        second.contains_key(NonEmptyIniStr::Borrowed(nestr!("d"))),
+        Some(true)
+    );
+
+    // Stream parser with a boxed dynamic event handler.
+    let mut stream =
+        IniStreamParser::new(Box::new(DynConfigIniConfig::new()) as Box<dyn for<'s> IniConfig<'s>>);
+    stream.feed("a = 7\n[b]").unwrap();
+    let handler = stream.finish().unwrap();
+    assert_eq!(
+        handler.contains_key(NonEmptyIniStr::Borrowed(nestr!("b"))),
+        Some(true)
+    );
+}
+
+#[test]
+fn value_references() {
+    let parser = |string| {
+        IniParser::new(string)
+            .nested_section_depth(2)
+            .value_references(true)
+    };
+
+    let string = "a = @{section/b}\nc = \"@{section/b}\"\nd = @{section/nested}\n[section]\nb = 7\ne = @{a}\n[section/nested]\nf = true\ng = @{c}";
+
+    let config = DynConfig::from_ini(parser(string)).unwrap();
+    let root = config.root();
+
+    // Forward references.
+    assert_eq!(root.get_i64("a").unwrap(), 7);
+    // Quoted values are not references.
+    assert_eq!(root.get_string("c").unwrap(), "@{section/b}");
+    // Referenced sections are copied, with their resolved references.
+    let d = root.get_table("d").unwrap();
+    assert!(d.get_bool("f").unwrap());
+    assert_eq!(d.get_string("g").unwrap(), "@{section/b}");
+    // References to references.
+    assert_eq!(root.get_table("section").unwrap().get_i64("e").unwrap(), 7);
+
+    // Not enabled.
+    let config = DynConfig::from_ini(IniParser::new("a = @{b}\nb = 7")).unwrap();
+    assert_eq!(config.root().get_string("a").unwrap(), "@{b}");
+
+    // Overwritten references are not resolved.
+    let config =
+        DynConfig::from_ini(parser("a = @{b}\na = 9").duplicate_keys(IniDuplicateKeys::Last))
+            .unwrap();
+    assert_eq!(config.root().get_i64("a").unwrap(), 9);
+
+    // Case-insensitive keys.
+    let config = DynConfig::from_ini(
+        parser("a = @{Section/B}\n[SECTION]\nb = 7").case_insensitive_keys(true),
+    )
+    .unwrap();
+    assert_eq!(config.root().get_i64("a").unwrap(), 7);
+
+    // Errors.
+    assert_eq!(
+        DynConfig::from_ini(parser("a = 7\n[section]\nb = @{c}"))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 3,
+            column: 5,
+            offset: 20,
+            error: IniErrorKind::UnresolvedValueReference(ConfigPath(vec![nestr!("c").into()])),
+            path: ConfigPath(vec![nestr!("section").into(), nestr!("b").into()]),
+        }
+    );
+    assert_eq!(
+        DynConfig::from_ini(parser("a = @{b}\nb = @{c}\nc = @{a}"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::CyclicValueReference(ConfigPath(vec![nestr!("b").into()]))
+    );
+    assert!(matches!(
+        DynConfig::from_ini(parser("[a]\nb = @{a}"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::CyclicValueReference(_)
+    ));
+    assert_eq!(
+        DynConfig::from_ini(parser("a = @{b//c}"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::InvalidValueReference
+    );
+
+    // Spans.
+    let (config, spans) = DynConfig::from_ini_with_spans(parser("a = @{b}\nb = 7")).unwrap();
+    assert_eq!(config.root().get_i64("a").unwrap(), 7);
+    assert_eq!(
+        spans
+            .get(&ConfigPath(vec![nestr!("a").into()]))
+            .unwrap()
+            .value
+            .column,
+        5
+    );
+
+    // Other event handlers receive the unresolved references.
+    let mut config = DynConfigIniConfig::new();
+    parser("a = @{b}\nb = 7").parse(&mut config).unwrap();
+    assert_eq!(config.into_inner().root().get_string("a").unwrap(), "@{b}");
+}
+
+#[test]
+fn interpolation() {
+    fn basic(string: &str) -> Result<DynConfig, IniError> {
+        DynConfig::from_ini(
+            IniParser::new(string)
+                .strict_values(true)
+                .interpolation(IniInterpolation::Basic),
+        )
+    }
+
+    let config = basic(
+        "[DEFAULT]\nhome = /home/user\n\
+        [paths]\ndata = %(home)s/data\ncache = %(data)s/cache\nlog = %(cache)s.log\npercent = 100%%\ndollar = $5",
+    )
+    .unwrap();
+    let paths = config.root().get_table("paths").unwrap();
+
+    // References to the default section / same section, nested references, escaped delimiters.
+    assert_eq!(paths.get_string("data").unwrap(), "/home/user/data");
+    assert_eq!(paths.get_string("cache").unwrap(), "/home/user/data/cache");
+    assert_eq!(
+        paths.get_string("log").unwrap(),
+        "/home/user/data/cache.log"
+    );
+    assert_eq!(paths.get_string("percent").unwrap(), "100%");
+    assert_eq!(paths.get_string("dollar").unwrap(), "$5");
+
+    // Non-string values are referenced in their `.ini` representation.
+    let config = DynConfig::from_ini(
+        IniParser::new("port = 8080\nurl = \"localhost:%(port)s\"")
+            .interpolation(IniInterpolation::Basic),
+    )
+    .unwrap();
+    assert_eq!(config.root().get_i64("port").unwrap(), 8080);
+    assert_eq!(config.root().get_string("url").unwrap(), "localhost:8080");
+
+    // Extended interpolation, case-insensitive keys.
+    let config = DynConfig::from_ini(
+        IniParser::new(
+            "[Common]\nRoot = /srv\n\
+            [App]\nData = ${Common:Root}/app\nLog = ${data}/log\nPrice = $$5\nPercent = 100%",
+        )
+        .strict_values(true)
+        .case_insensitive_keys(true)
+        .interpolation(IniInterpolation::Extended),
+    )
+    .unwrap();
+    let app = config.root().get_table("app").unwrap();
+    assert_eq!(app.get_string("data").unwrap(), "/srv/app");
+    assert_eq!(app.get_string("log").unwrap(), "/srv/app/log");
+    assert_eq!(app.get_string("price").unwrap(), "$5");
+    assert_eq!(app.get_string("percent").unwrap(), "100%");
+
+    // Errors.
+    assert_eq!(
+        basic("a = 1\n[section]\nb = %(c)s").err().unwrap(),
+        IniError {
+            line: 3,
+            column: 5,
+            offset: 20,
+            error: IniErrorKind::UnresolvedInterpolation(ConfigPath(vec![
+                nestr!("section").into(),
+                nestr!("c").into()
+            ])),
+            path: ConfigPath(vec![nestr!("section").into(), nestr!("b").into()]),
+        }
+    );
+    for string in &["a = 100%", "a = %(b)", "a = %()s", "a = %b"] {
+        assert_eq!(
+            basic(string).err().unwrap().error,
+            IniErrorKind::InvalidInterpolation
+        );
+    }
+    assert_eq!(
+        basic("a = %(b)s\nb = %(a)s").err().unwrap().error,
+        IniErrorKind::InterpolationDepthExceeded
+    );
+    assert_eq!(
+        DynConfig::from_ini(
+            IniParser::new("a = ${b:c:d}").interpolation(IniInterpolation::Extended)
+        )
+        .err()
+        .unwrap()
+        .error,
+        IniErrorKind::InvalidInterpolation
+    );
+
+    // Up to 10 levels of nested references.
+    let mut string = String::from("a0 = x");
+    for i in 1..=10 {
+        string.push_str(&format!("\na{} = %(a{})s", i, i - 1));
+    }
+    assert_eq!(
+        basic(&string).unwrap().root().get_string("a10").unwrap(),
+        "x"
+    );
+    string.push_str("\na11 = %(a10)s");
+    assert_eq!(
+        basic(&string).err().unwrap().error,
+        IniErrorKind::InterpolationDepthExceeded
+    );
+
+    // Other event handlers receive the uninterpolated values.
+    let mut config = DynConfigIniConfig::new();
+    IniParser::new("a = %(b)s\nb = c")
+        .interpolation(IniInterpolation::Basic)
+        .parse(&mut config)
+        .unwrap();
+    assert_eq!(config.into_inner().root().get_string("a").unwrap(), "%(b)s");
+}
+
+#[test]
+fn value_dependencies() {
+    use {std::collections::HashMap, IniDependencyKind::*};
+
+    let dependency = |kind, from: Option<&str>, to: &str| IniDependency {
+        kind,
+        from: from.map(str::to_owned),
+        to: to.into(),
+    };
+
+    let mut sources = HashMap::new();
+    sources.insert("a.ini", "b = @{c}");
+
+    let parse = |string, interpolation, dependencies: &mut IniDependencyGraph| {
+        DynConfig::from_ini_with_includes(
+            IniParser::new(string)
+                .value_references(true)
+                .interpolation(interpolation),
+            &sources,
+            dependencies,
+        )
+    };
+
+    // Value references are recorded after the includes, starting from the root source.
+    let mut dependencies = IniDependencyGraph::new();
+    let config = parse(
+        "c = 7\n!include \"a.ini\"\nd = @{b}\n[s]\ne = @{d}",
+        IniInterpolation::None,
+        &mut dependencies,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.root().get_table("s").unwrap().get_i64("e").unwrap(),
+        7
+    );
+    assert_eq!(
+        dependencies.dependencies(),
+        &[
+            dependency(Include, None, "a.ini"),
+            dependency(Reference, None, "b"),
+            dependency(Reference, Some("b"), "c"),
+            dependency(Reference, None, "d"),
+            dependency(Reference, Some("d"), "b"),
+            dependency(Reference, None, "s.e"),
+            dependency(Reference, Some("s.e"), "d"),
+        ]
+    );
+    assert_eq!(dependencies.current(), None);
+    assert!(!dependencies.has_cycles());
+
+    // Value references to sections depend on the value references within them.
+    let mut dependencies = IniDependencyGraph::new();
+    parse(
+        "a = @{s}\nc = 7\n[s]\nb = @{c}",
+        IniInterpolation::None,
+        &mut dependencies,
+    )
+    .unwrap();
+
+    assert_eq!(
+        dependencies.dependencies(),
+        &[
+            dependency(Reference, None, "a"),
+            dependency(Reference, Some("a"), "s"),
+            dependency(Reference, Some("s"), "s.b"),
+            dependency(Reference, Some("s.b"), "c"),
+        ]
+    );
+
+    // Cyclic value references.
+    let mut dependencies = IniDependencyGraph::new();
+    assert_eq!(
+        parse(
+            "a = @{b}\nb = @{c}\nc = @{a}",
+            IniInterpolation::None,
+            &mut dependencies
+        )
+        .err()
+        .unwrap()
+        .error,
+        IniErrorKind::CyclicValueReference(ConfigPath(vec![nestr!("b").into()]))
+    );
+    assert_eq!(
+        dependencies.cycles().collect::<Vec<_>>(),
+        vec![&["a".to_owned(), "b".to_owned(), "c".to_owned(), "a".to_owned()][..]]
+    );
+    assert_eq!(dependencies.current(), Some("c"));
+
+    // Interpolated values are recorded after the value references, with the values they reference.
+    let mut dependencies = IniDependencyGraph::new();
+    let config = parse(
+        "a = \"%(b)s/%(c)s\"\nb = @{c}\nc = 7\n[DEFAULT]\nd = \"%(f)s\"\nf = 8\n[s]\ne = \"%(d)s\"",
+        IniInterpolation::Basic,
+        &mut dependencies,
+    )
+    .unwrap();
+
+    assert_eq!(config.root().get_string("a").unwrap(), "7/7");
+    assert_eq!(
+        config.root().get_table("s").unwrap().get_string("e").unwrap(),
+        "8"
+    );
+
+    let dependencies = dependencies.dependencies();
+
+    assert_eq!(dependencies.len(), 10);
+    assert_eq!(dependencies[0], dependency(Reference, None, "b"));
+    assert_eq!(dependencies[1], dependency(Reference, Some("b"), "c"));
+
+    // Interpolated values are resolved in unspecified order.
+    for expected in [
+        dependency(Interpolation, None, "a"),
+        dependency(Interpolation, Some("a"), "b"),
+        dependency(Interpolation, Some("a"), "c"),
+        dependency(Interpolation, None, "DEFAULT.d"),
+        dependency(Interpolation, Some("DEFAULT.d"), "DEFAULT.f"),
+        dependency(Interpolation, None, "s.e"),
+        dependency(Interpolation, Some("s.e"), "DEFAULT.d"),
+    ]
+    .iter()
+    {
+        assert!(dependencies[2..].contains(expected), "{:?}", expected);
+    }
+
+    // Cyclic interpolations.
+    let mut dependencies = IniDependencyGraph::new();
+    assert_eq!(
+        parse(
+            "a = \"%(b)s\"\nb = \"x%(a)s\"",
+            IniInterpolation::Basic,
+            &mut dependencies
+        )
+        .err()
+        .unwrap()
+        .error,
+        IniErrorKind::InterpolationDepthExceeded
+    );
+
+    let cycle = dependencies.cycles().next().unwrap();
+    assert_eq!(cycle.len(), 3);
+    assert_eq!(cycle.first(), cycle.last());
+}
+
+#[test]
+fn schema() {
+    let schema = || {
+        IniSchema::new()
+            .key(nestr!("name"))
+            .section(
+                nestr!("window"),
+                IniSchema::new()
+                    .key(nestr!("width"))
+                    .key(nestr!("height"))
+                    .section(nestr!("title"), IniSchema::new().key(nestr!("text"))),
+            )
+            .section(nestr!("levels"), IniSchema::new().key(nestr!("file")))
+            .section(nestr!("mods"), IniSchema::new().allow_unknown(true))
+    };
+
+    let parser = |string| {
+        IniParser::new(string)
+            .nested_section_depth(3)
+            .array_sections(true)
+            .schema(schema())
+    };
+
+    let config = DynConfig::from_ini(parser(
+        "name = foo\n[window]\nwidth = 7\n[window/title]\ntext = bar\n[[levels]]\nfile = a\n[[levels]]\nfile = b\n[mods]\nanything = 9\n[mods/nested]\nfoo = true",
+    ))
+    .unwrap();
+    assert_eq!(
+        config
+            .root()
+            .get_i64_path(&["window".into(), "width".into()])
+            .unwrap(),
+        7
+    );
+
+    // Unknown keys.
+    assert_eq!(
+        DynConfig::from_ini(parser("name = foo\n[window]\nwidht = 7"))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 3,
+            column: 5,
+            offset: 24,
+            error: IniErrorKind::UnknownKey,
+            path: ConfigPath(vec![nestr!("window").into()]),
+        }
+    );
+
+    // Unknown sections.
+    assert_eq!(
+        DynConfig::from_ini(parser("name = foo\n[windows]\nwidth = 7"))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 2,
+            column: 9,
+            offset: 19,
+            error: IniErrorKind::UnknownSection,
+            path: ConfigPath::new(),
+        }
+    );
+
+    // Unknown parent / nested sections.
+    assert_eq!(
+        DynConfig::from_ini(parser("[windows/title]"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::UnknownSection
+    );
+    assert_eq!(
+        DynConfig::from_ini(parser("[window]\n[window/titles]"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::UnknownSection
+    );
+
+    // Sections used as keys and vice versa.
+    assert_eq!(
+        DynConfig::from_ini(parser("window = 7"))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::UnknownKey
+    );
+    assert_eq!(
+        DynConfig::from_ini(parser("[name]")).err().unwrap().error,
+        IniErrorKind::UnknownSection
+    );
+
+    // Case-insensitive keys are validated after case folding.
+    let config =
+        DynConfig::from_ini(parser("NAME = foo\n[Window]\nWidth = 7").case_insensitive_keys(true))
+            .unwrap();
+    assert_eq!(config.root().get_string("name").unwrap(), "foo");
+
+    // Schemas may be merged.
+    let mut merged = schema();
+    merged.merge(IniSchema::new().section(nestr!("window"), IniSchema::new().key(nestr!("vsync"))));
+    assert!(merged.get_section("window").unwrap().contains("vsync"));
+    assert!(merged.get_section("window").unwrap().contains("width"));
+
+    DynConfig::from_ini(IniParser::new("[window]\nvsync = true").schema(merged)).unwrap();
+}
+
+#[test]
+fn deprecations() {
+    let path = |path: &str| -> ConfigPath { path.parse().unwrap() };
+
+    let mut deprecations = ConfigDeprecations::new();
+    deprecations.add(path("window.fullscreen"), Some(path("window.mode")));
+    deprecations.add(path("audio"), None);
+    deprecations.add(path("vsync"), None);
+
+    let (config, warnings) = DynConfig::from_ini_with_warnings(
+        IniParser::new("[window]\nwidth = 7\n  fullscreen = true\n[audio]\nvolume = 0.5"),
+        &deprecations,
+    )
+    .unwrap();
+
+    // Deprecated values still work.
+    assert!(config
+        .root()
+        .get_table("window")
+        .unwrap()
+        .get_bool("fullscreen")
+        .unwrap());
+
+    assert_eq!(
+        warnings,
+        vec![
+            ConfigWarning {
+                path: path("window.fullscreen"),
+                kind: ConfigWarningKind::Deprecated {
+                    replacement: Some(path("window.mode")),
+                },
+                location: Some((3, 3)),
+            },
+            ConfigWarning {
+                path: path("audio"),
+                kind: ConfigWarningKind::Deprecated { replacement: None },
+                location: None,
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "window.fullscreen (line 3, column 3): value is deprecated, use \"window.mode\" instead"
+    );
+}
+
+#[test]
+fn plain_char_runs() {
+    // Runs of plain key / value / comment chars are processed at once;
+    // make sure they mix correctly with escape sequences, quotes and multibyte chars.
+    let ini = dyn_config(
+        "; comment_with_plain_chars and more\n\
+        key_1 = value_1.0-beta\n\
+        key\\ 2 = val\\tue_2\n\
+        key_3 = \"quoted_value_3 with spaces\"\n\
+        key_4 = abcé_def\n\
+        [section_1]\n\
+        key_5 = 5.25",
+    );
+
+    assert_eq!(ini.root().get_string("key_1").unwrap(), "value_1.0-beta");
+    assert_eq!(ini.root().get_string("key 2").unwrap(), "val\tue_2");
+    assert_eq!(
+        ini.root().get_string("key_3").unwrap(),
+        "quoted_value_3 with spaces"
+    );
+    assert_eq!(ini.root().get_string("key_4").unwrap(), "abcé_def");
+    assert_eq!(
+        ini.root()
+            .get_table("section_1")
+            .unwrap()
+            .get_f64("key_5")
+            .unwrap(),
+        5.25
+    );
+
+    // Error locations after the runs.
+    assert_eq!(
+        dyn_config_error("; comment\nkey_1 = value_1\nkey_2 = value_2 value_3"),
+        IniError {
+            line: 3,
+            column: 17,
+            offset: 42,
+            error: IniErrorKind::InvalidCharacterAtLineEnd('v'),
+            path: ConfigPath::new(),
+        }
+    );
+    assert_eq!(
+        dyn_config_error("key_1é_\"_1 = 7"),
+        IniError {
+            line: 1,
+            column: 8,
+            offset: 8,
+            error: IniErrorKind::InvalidCharacterInKey('"'),
+            path: ConfigPath::new(),
+        }
+    );
+}
+
+#[test]
+fn error_positions() {
+    let ini = "key = a\r\n\tkey_2 = b c";
+
+    // Tabs are counted as a single column by default.
+    let error = dyn_config_error(ini);
+    assert_eq!(
+        error,
+        IniError {
+            line: 2,
+            column: 12,
+            offset: 20,
+            error: IniErrorKind::InvalidCharacterAtLineEnd('c'),
+            path: ConfigPath::new(),
+        }
+    );
+    assert_eq!(&ini[error.offset..], "c");
+    assert_eq!(error.snippet(ini), "\tkey_2 = b c\n\t          ^");
+
+    // Tabs advance the column to the next tab stop.
+    let error = DynConfig::from_ini(IniParser::new(ini).tab_width(4))
+        .err()
+        .unwrap();
+    assert_eq!(
+        error,
+        IniError {
+            line: 2,
+            column: 15,
+            offset: 20,
+            error: IniErrorKind::InvalidCharacterAtLineEnd('c'),
+            path: ConfigPath::new(),
+        }
+    );
+    assert_eq!(
+        DynConfig::from_ini(IniParser::new("a\tb = 7").tab_width(4))
+            .err()
+            .unwrap()
+            .column,
+        5
+    );
+
+    // Multibyte chars.
+    let ini = "ключ = значение значение";
+    let error = dyn_config_error(ini);
+    assert_eq!(error.column, 17);
+    assert_eq!(error.offset, 28);
+    assert_eq!(
+        error.snippet(ini),
+        "ключ = значение значение\n                ^"
+    );
+
+    // Errors at new lines / at the end of the source point at the preceding character.
+    let ini = "[section\n";
+    let error = dyn_config_error(ini);
+    assert_eq!(error.error, IniErrorKind::UnexpectedNewLineInSectionName);
+    assert_eq!(error.snippet(ini), "[section\n       ^");
+
+    let ini = "key = \"value";
+    let error = dyn_config_error(ini);
+    assert_eq!(error.error, IniErrorKind::UnexpectedEndOfFileInQuotedString);
+    assert_eq!(error.snippet(ini), "key = \"value\n           ^");
+}
+
+#[test]
+fn windows_compat() {
+    let ini = "[Game]\r\n\
+        Path=Games\\Title\r\n\
+        Install=C:\\Program Files\\Game  \r\n\
+        Volume=050\r\n\
+        ; Comment.\r\n\
+        Title=\"Player One\"\r\n\
+        Name='Player'\r\n\
+        volume=75\r\n\
+        \r\n\
+        [Video]\r\n\
+        Width=1920\r\n\
+        [video]\r\n\
+        Width=1280\r\n";
+
+    let config = DynConfig::from_ini(IniParser::new(ini).windows_compat()).unwrap();
+
+    // Section names / keys are case-insensitive, values are strings without escape sequences,
+    // last duplicate wins.
+    let game = config.root().get_table("game").unwrap();
+    assert_eq!(game.get_string("path").unwrap(), "Games\\Title");
+    // Unquoted values may contain spaces and colons; trailing whitespace is trimmed.
+    assert_eq!(
+        game.get_string("install").unwrap(),
+        "C:\\Program Files\\Game"
+    );
+    assert_eq!(game.get_string("volume").unwrap(), "75");
+    assert_eq!(game.get_string("title").unwrap(), "Player One");
+    assert_eq!(game.get_string("name").unwrap(), "Player");
+    assert_eq!(
+        config
+            .root()
+            .get_string_path(&["video".into(), "width".into()])
+            .unwrap(),
+        "1280"
+    );
+
+    // Strings are unquoted where possible.
+    let string = config
+        .to_ini_string_opts(ToIniStringOptions::windows_compat())
+        .unwrap();
+    assert_eq!(
+        string,
+        "[game]\r\n\
+        install=\"C:\\Program Files\\Game\"\r\n\
+        name=Player\r\n\
+        path=Games\\Title\r\n\
+        title=\"Player One\"\r\n\
+        volume=75\r\n\
+        [video]\r\n\
+        width=1280"
+    );
+
+    assert!(
+        DynConfig::from_ini(IniParser::new(&string).windows_compat())
+            .unwrap()
+            .diff(&config)
+            .is_empty()
+    );
+}
+
+#[test]
+fn raw_values() {
+    /// Records the raw values and their hints, parsing only the value with the `port` key.
+    #[derive(Default)]
+    struct RawValues {
+        values: Vec<(String, String, IniValueHint)>,
+        port: Option<i64>,
+    }
+
+    impl<'s> IniConfig<'s> for RawValues {
+        fn contains_key(&self, _key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+            None
+        }
+
+        fn add_value(
+            &mut self,
+            _key: NonEmptyIniStr<'s, '_>,
+            _value: IniValue<'s, '_>,
+            _overwrite: bool,
+        ) {
+            panic!("expected a raw value");
+        }
+
+        fn add_raw_value(
+            &mut self,
+            key: NonEmptyIniStr<'s, '_>,
+            value: IniStr<'s, '_>,
+            hint: IniValueHint,
+            _overwrite: bool,
+            _spans: IniValueSpans,
+        ) {
+            if key.as_str() == "port" {
+                self.port = hint.to_i64(value.as_str());
+            }
+
+            self.values
+                .push((key.as_str().into(), value.as_str().into(), hint));
+        }
+
+        fn start_section(&mut self, _section: NonEmptyIniStr<'s, '_>, _overwrite: bool) {}
+
+        fn end_section(&mut self, _section: NonEmptyIniStr<'s, '_>) {}
+
+        fn start_array(&mut self, _array: NonEmptyIniStr<'s, '_>, _overwrite: bool) {}
+
+        fn add_array_value(&mut self, _value: IniValue<'s, '_>) {}
+
+        fn end_array(&mut self, _array: NonEmptyIniStr<'s, '_>) {}
+    }
+
+    let ini = "port = 0x1F90\nname = \"server\"\nratio = 1.5\nenabled = true\nempty =\nescaped = a\\tb\ntext = 007";
+
+    let mut config = RawValues::default();
+    IniParser::new(ini)
+        .raw_values(true)
+        .parse(&mut config)
+        .unwrap();
+
+    assert_eq!(config.port, Some(8080));
+
+    let values: Vec<_> = config
+        .values
+        .iter()
+        .map(|(key, value, hint)| (key.as_str(), value.as_str(), *hint))
+        .collect();
+
+    assert_eq!(
+        values,
+        vec![
+            ("port", "0x1F90", IniValueHint::Unquoted),
+            ("name", "server", IniValueHint::String),
+            ("ratio", "1.5", IniValueHint::Unquoted),
+            ("enabled", "true", IniValueHint::Unquoted),
+            ("empty", "", IniValueHint::String),
+            ("escaped", "a\tb", IniValueHint::Unquoted),
+            ("text", "007", IniValueHint::Unquoted),
+        ]
+    );
+
+    // Helpers.
+    assert_eq!(IniValueHint::Unquoted.to_bool("true"), Some(true));
+    assert_eq!(IniValueHint::Unquoted.to_bool("1"), None);
+    assert_eq!(IniValueHint::String.to_bool("true"), None);
+    assert_eq!(IniValueHint::Unquoted.to_i64("-7"), Some(-7));
+    assert_eq!(IniValueHint::Unquoted.to_i64("1.5"), None);
+    assert_eq!(IniValueHint::Unquoted.to_i64("9223372036854775808"), None);
+    assert_eq!(IniValueHint::String.to_i64("7"), None);
+    assert_eq!(IniValueHint::Unquoted.to_f64("1.5"), Some(1.5));
+    assert_eq!(IniValueHint::Unquoted.to_f64("7"), Some(7.0));
+    assert_eq!(
+        IniValueHint::Unquoted.to_f64("9223372036854775808"),
+        Some(9223372036854775808.0)
+    );
+    assert_eq!(IniValueHint::Unquoted.to_f64("foo"), None);
+
+    // Default implementation interprets the values as usual.
+    let config = DynConfig::from_ini(IniParser::new(ini).raw_values(true)).unwrap();
+    assert_eq!(config.root().get_i64("port").unwrap(), 8080);
+    assert_eq!(config.root().get_string("name").unwrap(), "server");
+    assert_eq!(config.root().get_f64("ratio").unwrap(), 1.5);
+    assert!(config.root().get_bool("enabled").unwrap());
+    assert_eq!(config.root().get_string("empty").unwrap(), "");
+    assert_eq!(config.root().get_string("escaped").unwrap(), "a\tb");
+    assert_eq!(config.root().get_i64("text").unwrap(), 7);
+
+    // Strict mode.
+    let mut config = RawValues::default();
+    IniParser::new("port = 8080")
+        .strict_values(true)
+        .raw_values(true)
+        .parse(&mut config)
+        .unwrap();
+    assert_eq!(config.port, None);
+    assert_eq!(config.values[0].2, IniValueHint::String);
+}
+
+#[test]
+fn trailing_whitespace_in_values() {
+    fn parse(string: &str, value_whitespace: IniValueWhitespace) -> Result<DynConfig, IniError> {
+        DynConfig::from_ini(
+            IniParser::new(string)
+                .escape(true)
+                .inline_comments(true)
+                .trailing_whitespace_in_values(value_whitespace),
+        )
+    }
+
+    let ini = "path = /Program Files/My Game  ;comment\n\
+        [section]\nname = a  b\\ \t\nnumber = 7 \nlast = é é  ";
+
+    // Whitespace terminates unquoted values by default.
+    assert!(parse(ini, IniValueWhitespace::Terminate).is_err());
+
+    // Internal whitespace is kept, trailing whitespace is trimmed.
+    let config = parse(ini, IniValueWhitespace::Trim).unwrap();
+    assert_eq!(
+        config.root().get_string("path").unwrap(),
+        "/Program Files/My Game"
+    );
+    let section = config.root().get_table("section").unwrap();
+    // Escaped whitespace is not trimmed.
+    assert_eq!(section.get_string("name").unwrap(), "a  b ");
+    assert_eq!(section.get_i64("number").unwrap(), 7);
+    assert_eq!(section.get_string("last").unwrap(), "é é");
+
+    // Internal and trailing whitespace is kept.
+    let config = parse(ini, IniValueWhitespace::Preserve).unwrap();
+    assert_eq!(
+        config.root().get_string("path").unwrap(),
+        "/Program Files/My Game  "
+    );
+    let section = config.root().get_table("section").unwrap();
+    assert_eq!(section.get_string("name").unwrap(), "a  b \t");
+    // Non-string values are inferred from the trimmed value.
+    assert_eq!(section.get_i64("number").unwrap(), 7);
+    assert_eq!(section.get_string("last").unwrap(), "é é  ");
+
+    let config = parse(
+        "int = 7  \nfloat = 3.5\t\nbool = true ;comment\nstring = 7 a  \nescaped = 7\\ ",
+        IniValueWhitespace::Preserve,
+    )
+    .unwrap();
+    assert_eq!(config.root().get_i64("int").unwrap(), 7);
+    assert_eq!(config.root().get_f64("float").unwrap(), 3.5);
+    assert!(config.root().get_bool("bool").unwrap());
+    assert_eq!(config.root().get_string("string").unwrap(), "7 a  ");
+    // Escaped whitespace is not trailing whitespace.
+    assert_eq!(config.root().get_string("escaped").unwrap(), "7 ");
+
+    // Values which fail to parse are reported as if the trailing whitespace was trimmed.
+    let err = DynConfig::from_ini(
+        IniParser::new("int = 9223372036854775808  ")
+            .numbers(IniNumberPolicy::Error)
+            .trailing_whitespace_in_values(IniValueWhitespace::Preserve),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.error, IniErrorKind::IntegerOverflow);
+
+    // Leading whitespace is skipped, quoted values are not affected.
+    let config = parse(
+        "a =   b c\nd = \" e \"  ;comment",
+        IniValueWhitespace::Preserve,
+    )
+    .unwrap();
+    assert_eq!(config.root().get_string("a").unwrap(), "b c");
+    assert_eq!(config.root().get_string("d").unwrap(), " e ");
+}
+
+#[test]
+fn lua_script_from_ini() {
+    fn lua_script(string: &str) -> Result<String, LuaScriptFromIniError> {
+        let mut script = LuaScriptFromIni::new(String::new());
+        IniParser::new(string)
+            .arrays(true)
+            .array_sections(true)
+            .nested_section_depth(u32::MAX)
+            .parse(&mut script)
+            .unwrap();
+        script.finish()
+    }
+
+    let ini = "a = 7\nb = [true, false]\n\
+        [c]\nd = \"foo\"\n\
+        [c/e]\nf = 0.5\n\
+        [c/g]\nh = -1\n\
+        [[i]]\nj = 1\n\
+        [[i]]\nk = 2";
+
+    assert_eq!(
+        lua_script(ini).unwrap(),
+        r#"{
+    a = 7,
+    b = {
+        true,
+        false,
+    }, -- b
+    c = {
+        d = "foo",
+        e = {
+            f = 0.5,
+        }, -- e
+        g = {
+            h = -1,
+        }, -- g
+    }, -- c
+    i = {
+        {
+            j = 1,
+        }, -- [0]
+        {
+            k = 2,
+        }, -- [1]
+    }, -- i
+}"#
+        .replace("    ", "\t")
+    );
+
+    // Same as serializing the dynamic config, if the keys are in alphabetical order.
+    assert_eq!(
+        lua_script(ini).unwrap(),
+        DynConfig::from_ini(
+            IniParser::new(ini)
+                .arrays(true)
+                .array_sections(true)
+                .nested_section_depth(u32::MAX)
+        )
+        .unwrap()
+        .to_lua_string()
+        .unwrap()
+    );
+
+    // Previously finished sections may not be continued.
+    assert_eq!(
+        lua_script("[a]\nb = 7\n[c]\n[a]\nd = 9").err().unwrap(),
+        LuaScriptFromIniError::ContinuedSection("a".parse().unwrap())
     );
 }
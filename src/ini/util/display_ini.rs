@@ -1,13 +1,21 @@
 use {
     crate::{
-        util::{write_char, WriteCharError},
+        ini::StringQuote,
+        util::{write_char, write_padding, written_width, WriteCharError},
         *,
     },
-    std::fmt::Write,
+    std::{borrow::Borrow, fmt::Write, iter::Iterator},
 };
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
-use std::{borrow::Borrow, iter::Iterator};
+/// Adapts the `std::io::Write` writer to `std::fmt::Write`,
+/// so that configs may be serialized to an `.ini` string directly to files / network buffers.
+pub(crate) struct IoWriter<'w, W: std::io::Write>(pub(crate) &'w mut W);
+
+impl<'w, W: std::io::Write> Write for IoWriter<'w, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.write_all(s.as_bytes()).map_err(|_| std::fmt::Error)
+    }
+}
 
 /// A trait implemented by configs serializable to an `.ini` string.
 pub(crate) trait DisplayIni {
@@ -41,17 +49,44 @@ where
                 write!(writer, "{}", if *value { "true" } else { "false" })?;
             }
             Value::I64(value) => write!(writer, "{}", value)?,
-            Value::F64(value) => write!(writer, "{}", value)?,
+            Value::F64(value) => options.formatter.write_f64(writer, *value)?,
+            // Date/time values are written unquoted so that they are parsed back as date/time values.
+            #[cfg(feature = "datetime")]
+            Value::DateTime(value) => write!(writer, "{}", value)?,
+            Value::String(value)
+                if options.unquoted_strings
+                    && is_ini_unquoted_string(value.as_ref(), options.escape) =>
+            {
+                writer.write_str(value.as_ref())?;
+            }
+            Value::String(value)
+                if options.raw_strings
+                    && is_ini_raw_string(value.as_ref(), options.multiline_strings) =>
+            {
+                write!(writer, "`{}`", value.as_ref())?;
+            }
             Value::String(value) => {
-                write!(writer, "\"")?;
-                write_ini_string(writer, value.as_ref(), true, options.escape)?;
-                write!(writer, "\"")?;
+                let quote = options.formatter.quote();
+
+                writer.write_char(quote)?;
+                write_ini_quoted_string(
+                    writer,
+                    value.as_ref(),
+                    quote,
+                    options.escape,
+                    options.multiline_strings,
+                )?;
+                writer.write_char(quote)?;
             }
             Value::Table(value) => {
                 if array {
                     return Err(InvalidArrayType);
                 } else {
-                    debug_assert!(options.nested_sections() || level < 2);
+                    debug_assert!(
+                        options.nested_sections()
+                            || options.flatten_separator().is_some()
+                            || level < 2
+                    );
                     value.fmt_ini(writer, level, false, path, options)?;
                 }
             }
@@ -81,21 +116,82 @@ pub(crate) fn write_ini_string<W: Write>(
     escape: bool,
 ) -> Result<(), ToIniStringError> {
     for c in string.chars() {
-        write_char(w, c, true, quoted, escape).map_err(|err| match err {
-            WriteCharError::WriteError => ToIniStringError::WriteError,
-            WriteCharError::EscapedCharacter(c) => ToIniStringError::EscapedCharacterNotAllowed(c),
-        })?;
+        write_ini_char(w, c, quoted, escape)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the `string` enclosed in `quote`'s (`'"'` or `'\''`) to the writer `w`, like [`write_ini_string`],
+/// except if `quote` is `'\''`, single quotes ('\'') are escaped and double quotes ('"') are not,
+/// and if `multiline` is `true`, line feeds ('\n') are not escaped.
+fn write_ini_quoted_string<W: Write>(
+    w: &mut W,
+    string: &str,
+    quote: char,
+    escape: bool,
+    multiline: bool,
+) -> Result<(), ToIniStringError> {
+    if quote == '"' && !multiline {
+        return write_ini_string(w, string, true, escape);
+    }
+
+    for c in string.chars() {
+        match c {
+            '\'' if quote == '\'' && escape => w.write_str(r#"\'"#)?,
+            '\'' if quote == '\'' => return Err(ToIniStringError::EscapedCharacterNotAllowed(c)),
+            '"' if quote == '\'' => w.write_char(c)?,
+            '\n' if multiline => w.write_char(c)?,
+            c => write_ini_char(w, c, true, escape)?,
+        }
     }
 
     Ok(())
 }
 
+/// Returns `true` if the `string` contains backslashes ('\\') and may be written as a raw string enclosed in backticks ('`'),
+/// i.e. does not contain backticks or characters which must be escaped
+/// (except line feeds ('\n') and tabs ('\t') if `multiline` is `true`).
+fn is_ini_raw_string(string: &str, multiline: bool) -> bool {
+    string.contains('\\')
+        && string.chars().all(|c| match c {
+            '`' => false,
+            '\n' | '\t' => multiline,
+            c => {
+                IniOptions::is_key_or_value_char_impl(c, true, false, false, Some(StringQuote::Raw))
+            }
+        })
+}
+
+/// Returns `true` if the (non-empty) `string` may be written as an unquoted value,
+/// i.e. only contains characters which don't have to be escaped or quoted,
+/// and does not start with a raw string backtick (`'`'`) or a value reference (`"@{"`).
+fn is_ini_unquoted_string(string: &str, escape: bool) -> bool {
+    !string.is_empty()
+        && !string.starts_with('`')
+        && !string.starts_with("@{")
+        && string
+            .chars()
+            .all(|c| IniOptions::is_key_or_value_char_impl(c, escape, false, false, None))
+}
+
+fn write_ini_char<W: Write>(
+    w: &mut W,
+    c: char,
+    quoted: bool,
+    escape: bool,
+) -> Result<(), ToIniStringError> {
+    write_char(w, c, true, quoted, escape).map_err(|err| match err {
+        WriteCharError::WriteError => ToIniStringError::WriteError,
+        WriteCharError::EscapedCharacter(c) => ToIniStringError::EscapedCharacterNotAllowed(c),
+    })
+}
+
 /// Returns `true` if the string contains special characters
 /// ('\\', '\0', '\a', '\b', '\t', '\n', '\v', '\f', '\r'),
 /// string quotes ('\'', '"'),
 /// `.ini` special characters ('[', ']', ';', '#', '=', ':') or spaces (' '),
 /// and if `escape_nested_section_separators` is `true`, nested section separators ('/').
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
 fn string_needs_quotes(string: &str, escape_nested_section_separators: bool) -> bool {
     for c in string.chars() {
         match c {
@@ -123,7 +219,6 @@ fn string_needs_quotes(string: &str, escape_nested_section_separators: bool) ->
 /// spaces (' '),
 /// or if `nested_sections` is `true`, nested section separators ('/'),
 /// they are additionally enclosed in double quotes ('"').
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
 fn write_ini_sections<W: Write>(
     w: &mut W,
     path: &IniPath,
@@ -162,10 +257,11 @@ fn write_ini_sections<W: Write>(
     Ok(())
 }
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
 pub(crate) fn write_ini_array<W: Write, A: Iterator<Item = I>, I: Borrow<V>, V: DisplayIni>(
     w: &mut W,
     key: &NonEmptyStr,
+    comment: Option<&str>,
+    key_width: usize,
     array: A,
     array_len: usize,
     last: bool,
@@ -176,9 +272,10 @@ pub(crate) fn write_ini_array<W: Write, A: Iterator<Item = I>, I: Borrow<V>, V:
     use ToIniStringError::*;
 
     if options.arrays {
-        write_ini_key(w, key, options.escape)?;
+        write_ini_comment(w, comment)?;
+        write_ini_key(w, key, key_width, path, options)?;
 
-        write!(w, " = [")?;
+        write!(w, "{}[", options.key_value_separator())?;
 
         for (array_index, array_value) in array.enumerate() {
             let last = array_index == array_len - 1;
@@ -204,32 +301,140 @@ pub(crate) fn write_ini_array<W: Write, A: Iterator<Item = I>, I: Borrow<V>, V:
     Ok(())
 }
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+/// Returns `true` if the array with values of `value_type` (or `None` if the array is empty)
+/// must be serialized as an array of sections, according to `options`.
+pub(crate) fn is_ini_array_sections(
+    value_type: Option<ValueType>,
+    options: ToIniStringOptions,
+) -> bool {
+    options.array_sections && (value_type == Some(ValueType::Table))
+}
+
+/// Writes the array of `tables` (of length `array_len`, each with its length) as an array of sections
+/// (one `[[section]]` per table) to the writer `w`.
+/// `array_element` is `true` if the array is in a table which is itself in an array of sections.
+/// The `comment`, if any, is written above the first section header.
+pub(crate) fn write_ini_array_sections<W: Write, A: Iterator<Item = (V, u32)>, V: DisplayIni>(
+    w: &mut W,
+    key: &NonEmptyStr,
+    comment: Option<&str>,
+    key_index: u32,
+    tables: A,
+    array_len: usize,
+    last: bool,
+    level: u32,
+    array_element: bool,
+    path: &mut IniPath,
+    options: ToIniStringOptions,
+) -> Result<(), ToIniStringError> {
+    use ToIniStringError::*;
+
+    // Arrays of sections may not be nested in arrays of sections, or flattened.
+    if array_element || ((level > 0) && options.flatten_separator().is_some()) {
+        return Err(InvalidArrayType);
+    }
+
+    if (level > 0) && (options.nested_tables == IniNestedTables::Error) {
+        return Err(NestedTablesNotAllowed);
+    }
+
+    if level >= options.nested_section_depth {
+        return Err(NestedSectionDepthExceeded);
+    }
+
+    path.push(NonEmptyIniStr::Owned(key));
+
+    for (array_index, (table, table_len)) in tables.enumerate() {
+        let last = last && (array_index == array_len - 1);
+
+        if (key_index > 0) || (array_index > 0) {
+            write_ini_blank_lines(w, options)?;
+        }
+
+        if array_index == 0 {
+            write_ini_comment(w, comment)?;
+        }
+
+        w.write_char('[')?;
+        write_ini_sections(w, path, options.escape, options.nested_sections())?;
+        w.write_char(']')?;
+
+        if table_len > 0 {
+            writeln!(w)?;
+            table.fmt_ini(w, level + 1, true, path, options)?;
+        }
+
+        if !last {
+            writeln!(w)?;
+        }
+    }
+
+    path.pop();
+
+    Ok(())
+}
+
 pub(crate) fn write_ini_table<W: Write, V: DisplayIni>(
     w: &mut W,
     key: &NonEmptyStr,
+    comment: Option<&str>,
     key_index: u32,
     table: &V,
     table_len: u32,
     has_non_tables: bool,
     last: bool,
     level: u32,
+    array_element: bool,
     path: &mut IniPath,
     options: ToIniStringOptions,
 ) -> Result<(), ToIniStringError> {
     use ToIniStringError::*;
 
+    match options.nested_tables {
+        // Keys of the flattened nested table are prefixed with its `path` (sans the section name).
+        IniNestedTables::Flatten(_) if level > 0 => {
+            if table_len > 0 {
+                write_ini_comment(w, comment)?;
+
+                path.push(NonEmptyIniStr::Owned(key));
+
+                table.fmt_ini(w, level + 1, false, path, options)?;
+
+                path.pop();
+
+                if !last {
+                    writeln!(w)?;
+                }
+            }
+
+            return Ok(());
+        }
+        IniNestedTables::Error if level > 0 => {
+            return Err(NestedTablesNotAllowed);
+        }
+        _ => {}
+    }
+
+    // Tables in arrays of sections may not contain nested (non-flattened) tables.
+    if array_element {
+        return Err(InvalidArrayType);
+    }
+
     if level >= options.nested_section_depth {
         return Err(NestedSectionDepthExceeded);
     }
 
+    // Sections with flattened nested tables always have non-table values.
+    let has_non_tables = has_non_tables || options.flatten_separator().is_some();
+
     if key_index > 0 {
-        writeln!(w)?;
+        write_ini_blank_lines(w, options)?;
     }
 
     path.push(NonEmptyIniStr::Owned(key));
 
     if has_non_tables || !options.implicit_parent_sections || (table_len == 0) {
+        write_ini_comment(w, comment)?;
         write_ini_sections(w, path, options.escape, options.nested_sections())?;
 
         if table_len > 0 {
@@ -237,7 +442,7 @@ pub(crate) fn write_ini_table<W: Write, V: DisplayIni>(
         }
 
         if !has_non_tables && (table_len > 0) {
-            writeln!(w)?;
+            write_ini_blank_lines(w, options)?;
         }
     }
 
@@ -254,10 +459,23 @@ pub(crate) fn write_ini_table<W: Write, V: DisplayIni>(
     Ok(())
 }
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+/// Writes the configured number of blank lines between the sections to the writer `w`.
+fn write_ini_blank_lines<W: Write>(
+    w: &mut W,
+    options: ToIniStringOptions,
+) -> Result<(), ToIniStringError> {
+    for _ in 0..options.blank_lines_between_sections {
+        writeln!(w)?;
+    }
+
+    Ok(())
+}
+
 pub(crate) fn write_ini_value<W: Write, V: DisplayIni>(
     w: &mut W,
     key: &NonEmptyStr,
+    comment: Option<&str>,
+    key_width: usize,
     value: &V,
     last: bool,
     level: u32,
@@ -265,9 +483,10 @@ pub(crate) fn write_ini_value<W: Write, V: DisplayIni>(
     path: &mut IniPath,
     options: ToIniStringOptions,
 ) -> Result<(), ToIniStringError> {
-    write_ini_key(w, key, options.escape)?;
+    write_ini_comment(w, comment)?;
+    write_ini_key(w, key, key_width, path, options)?;
 
-    write!(w, " = ")?;
+    w.write_str(options.key_value_separator())?;
 
     value.fmt_ini(w, level + 1, array, path, options)?;
 
@@ -278,25 +497,76 @@ pub(crate) fn write_ini_value<W: Write, V: DisplayIni>(
     Ok(())
 }
 
-/// Writes the `key` to the writer `w`.
-/// If the `key` contains special characters
+/// Writes the (possibly multiline) description `comment`, if any, to the writer `w`,
+/// as one `;` comment line per line of the `comment`.
+pub(crate) fn write_ini_comment<W: Write>(
+    w: &mut W,
+    comment: Option<&str>,
+) -> Result<(), ToIniStringError> {
+    if let Some(comment) = comment {
+        for line in comment.lines() {
+            if line.is_empty() {
+                writeln!(w, ";")?;
+            } else {
+                writeln!(w, "; {}", line)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the `key` to the writer `w`, padded with spaces to `width` chars.
+/// If nested tables are [`flattened`](enum.IniNestedTables.html#variant.Flatten),
+/// the `key` is prefixed with the nested table keys in the `path` (sans the section name), joined by the separator.
+/// If the (prefixed) `key` contains special characters
 /// ('\\', '\0', '\a', '\b', '\t', '\n', '\v', '\f', '\r'),
 /// string quotes ('\'', '"'),
 /// `.ini` special characters ('[', ']', ';', '#', '=', ':') or spaces (' '),
 /// it is additionally enclosed in double quotes ('"').
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
 fn write_ini_key<W: Write>(
     w: &mut W,
     key: &NonEmptyStr,
-    escape: bool,
+    width: usize,
+    path: &IniPath,
+    options: ToIniStringOptions,
 ) -> Result<(), ToIniStringError> {
-    let needs_quotes = string_needs_quotes(key.as_ref(), false);
+    if width > 0 {
+        let mut string = String::new();
+        write_ini_key(&mut string, key, 0, path, options)?;
+        w.write_str(&string)?;
+        write_padding(w, width.saturating_sub(string.chars().count()))?;
+
+        return Ok(());
+    }
+
+    let mut separator = [0; 4];
+
+    let (separator, prefix_len) = match options.flatten_separator() {
+        Some(c) => (
+            &*c.encode_utf8(&mut separator),
+            path.len().saturating_sub(1),
+        ),
+        None => ("", 0),
+    };
+
+    let prefix = || path.iter().skip(1).take(prefix_len as usize);
+
+    let needs_quotes = string_needs_quotes(key.as_ref(), false)
+        || ((prefix_len > 0)
+            && (string_needs_quotes(separator, false)
+                || prefix().any(|key| string_needs_quotes(key.as_ne_str(), false))));
 
     if needs_quotes {
         write!(w, "\"")?;
     }
 
-    write_ini_string(w, key.as_ref(), needs_quotes, escape)?;
+    for key in prefix() {
+        write_ini_string(w, key.as_ne_str(), needs_quotes, options.escape)?;
+        write_ini_string(w, separator, needs_quotes, options.escape)?;
+    }
+
+    write_ini_string(w, key.as_ref(), needs_quotes, options.escape)?;
 
     if needs_quotes {
         write!(w, "\"")?;
@@ -304,3 +574,22 @@ fn write_ini_key<W: Write>(
 
     Ok(())
 }
+
+/// Returns the width in chars to pad the `keys` of the values in the current section / table at `path` to,
+/// if the key-value separators are aligned according to `options`; otherwise returns `0`.
+pub(crate) fn ini_keys_width<I, K>(keys: I, path: &IniPath, options: ToIniStringOptions) -> usize
+where
+    I: Iterator<Item = K>,
+    K: AsRef<str>,
+{
+    if !options.formatter.align_values {
+        return 0;
+    }
+
+    keys.filter_map(|key| {
+        let key = NonEmptyStr::new(key.as_ref())?;
+        written_width(|string| write_ini_key(string, key, 0, path, options))
+    })
+    .max()
+    .unwrap_or(0)
+}
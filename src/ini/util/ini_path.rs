@@ -11,50 +11,131 @@ enum IniPathPart<'s> {
     Owned(std::ops::Range<usize>),
 }
 
+/// Placeholder for the unused inline path parts.
+impl<'s> Default for IniPathPart<'s> {
+    fn default() -> Self {
+        IniPathPart::Owned(0..0)
+    }
+}
+
+/// Number of nested section path parts stored inline in the `IniPath`, without allocating -
+/// enough for a section name and a key (or an array name), i.e. the path of any value if we don't support nested sections.
+const INLINE_PARTS: usize = 2;
+
 /// A simple wrapper around the nested `.ini` section path, used to minimize the number of allocations.
 /// Stores owned section names in the contiguous local buffer.
+/// Does not allocate if all section names are borrowed and the path is no longer than `INLINE_PARTS`.
 pub(crate) struct IniPath<'s> {
     /// Helper buffer for owned section names.
     buffer: String,
-    /// Nested section path parts. Contains at most one entry if we don't support nested sections.
+    /// First `INLINE_PARTS` nested section path parts, if any.
+    inline: [IniPathPart<'s>; INLINE_PARTS],
+    /// Number of nested section path parts, incl. the inline ones.
+    len: u32,
+    /// Nested section path parts after the first `INLINE_PARTS`, if any.
     parts: Vec<IniPathPart<'s>>,
 }
 
+/// Helper buffers of the `IniPath`, not tied to the lifetime of any `.ini` source string.
+/// Used to reuse the allocations across multiple parser invocations.
+#[derive(Default)]
+pub(crate) struct IniPathBuffers {
+    buffer: String,
+    parts: Vec<IniPathPart<'static>>,
+}
+
+impl IniPathBuffers {
+    pub(crate) fn clear(&mut self) {
+        self.buffer.clear();
+        self.parts.clear();
+    }
+}
+
 impl<'s> IniPath<'s> {
     pub(crate) fn new() -> Self {
         Self {
             buffer: String::new(),
+            inline: Default::default(),
+            len: 0,
             parts: Vec::new(),
         }
     }
 
+    /// Creates an empty path, reusing the allocations of the (possibly non-empty) helper `buffers`.
+    pub(crate) fn from_buffers(buffers: IniPathBuffers) -> Self {
+        let IniPathBuffers { mut buffer, parts } = buffers;
+        buffer.clear();
+
+        Self {
+            buffer,
+            inline: Default::default(),
+            len: 0,
+            parts: reuse_parts(parts),
+        }
+    }
+
+    /// Clears the path, returning its helper buffers for later reuse.
+    pub(crate) fn into_buffers(self) -> IniPathBuffers {
+        let IniPath {
+            mut buffer, parts, ..
+        } = self;
+        buffer.clear();
+
+        IniPathBuffers {
+            buffer,
+            parts: reuse_parts(parts),
+        }
+    }
+
+    /// Copies all borrowed section names to the local buffer,
+    /// making the path independent of the lifetime of the `.ini` source string.
+    pub(crate) fn into_owned<'a>(self) -> IniPath<'a> {
+        let mut path = IniPath::new();
+
+        for section in self.iter() {
+            path.push(NonEmptyIniStr::Owned(section.as_ne_str()));
+        }
+
+        path
+    }
+
     /// Pushes a new section name to the end of the path.
     /// If the section name is owned, appends it to the local buffer.
     pub(crate) fn push(&mut self, section: NonEmptyIniStr<'s, '_>) {
         use NonEmptyIniStr::*;
 
-        match section {
-            Borrowed(section) => {
-                self.parts.push(IniPathPart::Borrowed(section));
-            }
+        let part = match section {
+            Borrowed(section) => IniPathPart::Borrowed(section),
             Owned(section) => {
                 let current_len = self.buffer.len();
                 let offset = current_len + section.as_str().len();
 
                 self.buffer.push_str(section.as_str());
-                self.parts.push(IniPathPart::Owned(current_len..offset));
+                IniPathPart::Owned(current_len..offset)
             }
+        };
+
+        if (self.len as usize) < INLINE_PARTS {
+            self.inline[self.len as usize] = part;
+        } else {
+            self.parts.push(part);
         }
+
+        self.len += 1;
     }
 
     /// Pops a section name off the end of the path.
     /// NOTE - the caller guarantees that the path is not empty.
     pub(crate) fn pop(&mut self) {
-        debug_assert!(!self.parts.is_empty(), "tried to pop an empty `.ini` path");
+        debug_assert!(!self.is_empty(), "tried to pop an empty `.ini` path");
 
-        self.parts.pop();
+        if (self.len as usize) > INLINE_PARTS {
+            self.parts.pop();
+        }
 
-        if let Some(last) = self.parts.last() {
+        self.len -= 1;
+
+        if let Some(last) = self.last_part() {
             if let IniPathPart::Owned(offset) = last {
                 debug_assert!(offset.end > 0);
                 self.buffer.truncate(offset.end);
@@ -73,7 +154,7 @@ impl<'s> IniPath<'s> {
 
     /// Returns the number of section names in the path.
     pub(crate) fn len(&self) -> u32 {
-        self.parts.len() as _
+        self.len
     }
 
     pub(crate) fn is_empty(&self) -> bool {
@@ -103,7 +184,7 @@ impl<'s> IniPath<'s> {
 
         debug_assert!(index < self.len());
 
-        match self.parts.get_unchecked(index as usize) {
+        match self.part(index) {
             Owned(range) => {
                 debug_assert!(range.end > 0);
                 debug_assert!(range.start < range.end);
@@ -118,6 +199,38 @@ impl<'s> IniPath<'s> {
     }
 }
 
+impl<'s> IniPath<'s> {
+    /// Returns the path part at `index`.
+    /// NOTE - the caller guarantees `index` is valid.
+    unsafe fn part(&self, index: u32) -> &IniPathPart<'s> {
+        if (index as usize) < INLINE_PARTS {
+            self.inline.get_unchecked(index as usize)
+        } else {
+            self.parts.get_unchecked(index as usize - INLINE_PARTS)
+        }
+    }
+
+    fn last_part(&self) -> Option<&IniPathPart<'s>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(unsafe { self.part(self.len - 1) })
+        }
+    }
+}
+
+/// Clears the `parts` and reinterprets them as path parts with a different source string lifetime,
+/// reusing the allocation.
+fn reuse_parts<'a, 'b>(mut parts: Vec<IniPathPart<'a>>) -> Vec<IniPathPart<'b>> {
+    parts.clear();
+
+    let mut parts = std::mem::ManuallyDrop::new(parts);
+    let (ptr, capacity) = (parts.as_mut_ptr(), parts.capacity());
+
+    // The vector is empty, and the lifetime does not affect the layout of the path parts.
+    unsafe { Vec::from_raw_parts(ptr.cast(), 0, capacity) }
+}
+
 /// Iterates over the `IniPath` nested section path parts, parent to child.
 struct IniPathIter<'a, 's> {
     path: &'a IniPath<'s>,
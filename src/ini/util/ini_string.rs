@@ -15,6 +15,16 @@ pub enum IniStr<'s, 'a> {
 }
 
 impl<'s, 'a> IniStr<'s, 'a> {
+    /// Returns the (non-empty) string if it is borrowed directly from the `.ini` source,
+    /// with the lifetime of the `.ini` source.
+    /// Returns `None` if the string is empty or contained in a temporary helper buffer in the [`.ini parser`](struct.IniParser.html).
+    pub fn as_borrowed(&self) -> Option<&'s NonEmptyStr> {
+        match self {
+            IniStr::Borrowed(_str) => Some(_str),
+            _ => None,
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             IniStr::Borrowed(_str) => _str.as_str(),
@@ -49,6 +59,16 @@ pub enum NonEmptyIniStr<'s, 'a> {
 }
 
 impl<'s, 'a> NonEmptyIniStr<'s, 'a> {
+    /// Returns the string if it is borrowed directly from the `.ini` source,
+    /// with the lifetime of the `.ini` source.
+    /// Returns `None` if the string is contained in a temporary helper buffer in the parser.
+    pub fn as_borrowed(&self) -> Option<&'s NonEmptyStr> {
+        match self {
+            NonEmptyIniStr::Borrowed(_str) => Some(_str),
+            NonEmptyIniStr::Owned(_) => None,
+        }
+    }
+
     pub fn as_ne_str(&self) -> &NonEmptyStr {
         match self {
             NonEmptyIniStr::Borrowed(_str) => _str,
@@ -1,3 +1,4 @@
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 mod display_ini;
 mod ini_path;
 mod ini_string;
@@ -5,4 +6,7 @@ mod parsed_ini_string;
 
 pub use ini_string::*;
 
-pub(crate) use {display_ini::*, ini_path::*, parsed_ini_string::*};
+pub(crate) use {ini_path::*, parsed_ini_string::*};
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub(crate) use display_ini::*;
@@ -1,6 +1,7 @@
 use {
     crate::{ini::Substr, *},
     ministr::NonEmptyStr,
+    std::ops::Range,
 };
 
 /// Type of the string (non-empty key/section name, or potentially empty value) parsed from the `.ini` source.
@@ -9,7 +10,7 @@ enum ParsedIniStringKind {
     /// The string is in uninitialized state.
     Cleared,
     /// Borrowed directly from the `.ini` source string.
-    /// Contains the (inclusive) byte range of the string in the source.
+    /// Contains the (non-empty, half-open) byte range of the string in the source.
     /// The user guarantees the range is valid and corresponds to a valid UTF-8 substring.
     Borrowed((usize, usize)),
     /// The string is owned and is contained in a helper buffer
@@ -26,35 +27,44 @@ pub(crate) struct ParsedIniString {
 }
 
 impl ParsedIniString {
-    fn new() -> Self {
+    /// Creates a cleared string, reusing the allocation of the (possibly non-empty) helper `buffer`.
+    fn from_buffer(mut buffer: String) -> Self {
+        buffer.clear();
+
         Self {
             kind: ParsedIniStringKind::Cleared,
-            buffer: String::new(),
+            buffer,
         }
     }
 
+    /// Clears the string, returning its helper buffer for later reuse.
+    fn into_buffer(mut self) -> String {
+        self.clear();
+        self.buffer
+    }
+
     /// Pushes the char `c` at byte index `i` in the source string to this string.
     /// Copies `c` to the inner buffer if it's an onwed string,
-    /// or updates (increments) the byte range with `i` if it's a cleared or borrowed string.
+    /// or updates (extends) the byte range with `c` at `i` if it's a cleared or borrowed string.
     fn push(&mut self, c: char, i: usize) {
         use ParsedIniStringKind::*;
 
         match &mut self.kind {
             Cleared => {
                 debug_assert!(self.buffer.is_empty());
-                self.kind = Borrowed((i, i));
+                self.kind = Borrowed((i, i + c.len_utf8()));
             }
             Borrowed(range) => {
                 debug_assert!(self.buffer.is_empty());
                 debug_assert!(
-                    range.1 >= range.0,
+                    range.1 > range.0,
                     "byte ranges for borrowed strings must be non-empty"
                 );
                 debug_assert!(
-                    i == range.1 + 1,
+                    i == range.1,
                     "byte ranges for borrowed strings must be contiguous"
                 );
-                range.1 = i;
+                range.1 = i + c.len_utf8();
             }
             Owned => {
                 debug_assert!(!self.buffer.is_empty());
@@ -63,6 +73,34 @@ impl ParsedIniString {
         }
     }
 
+    /// Pushes the run of chars in the non-empty (half-open) byte `range` in the source string to this string.
+    /// Copies the run to the inner buffer via `substr` if it's an owned string,
+    /// or updates (extends) the byte range with the run if it's a cleared or borrowed string.
+    fn push_range<'s, S: Substr<'s>>(&mut self, range: Range<usize>, substr: S) {
+        use ParsedIniStringKind::*;
+
+        debug_assert!(range.end > range.start);
+
+        match &mut self.kind {
+            Cleared => {
+                debug_assert!(self.buffer.is_empty());
+                self.kind = Borrowed((range.start, range.end));
+            }
+            Borrowed(borrowed) => {
+                debug_assert!(self.buffer.is_empty());
+                debug_assert!(
+                    range.start == borrowed.1,
+                    "byte ranges for borrowed strings must be contiguous"
+                );
+                borrowed.1 = range.end;
+            }
+            Owned => {
+                debug_assert!(!self.buffer.is_empty());
+                self.buffer.push_str(substr(range));
+            }
+        }
+    }
+
     /// Converts the string to the `Owned` variant.
     /// Copies the existing borrowed string range, if any, to the inner buffer via `substr`.
     /// Then pushes the char `c` to the inner buffer.
@@ -90,15 +128,41 @@ impl ParsedIniString {
             Borrowed(range) => {
                 debug_assert!(self.buffer.is_empty());
                 debug_assert!(
-                    range.1 >= range.0,
+                    range.1 > range.0,
                     "byte ranges for borrowed strings must be non-empty"
                 );
-                self.buffer.push_str(substr(range.0..=range.1));
+                self.buffer.push_str(substr(range.0..range.1));
                 self.kind = Owned;
             }
         }
     }
 
+    /// Replaces the string with the non-empty `string`, converting it to the `Owned` variant.
+    fn replace(&mut self, string: &str) {
+        debug_assert!(!string.is_empty());
+
+        self.buffer.clear();
+        self.buffer.push_str(string);
+        self.kind = ParsedIniStringKind::Owned;
+    }
+
+    /// Converts the string to lowercase, copying the original string to the `original` buffer.
+    /// Converts the string to the `Owned` variant only if it contains uppercase chars.
+    fn fold_case<'s, S: Substr<'s>>(&mut self, substr: S, original: &mut String) {
+        original.clear();
+
+        if let Some(key) = self.key(&substr) {
+            original.push_str(key.as_str());
+        }
+
+        if original.chars().any(char::is_uppercase) {
+            self.buffer.clear();
+            self.buffer
+                .extend(original.chars().flat_map(char::to_lowercase));
+            self.kind = ParsedIniStringKind::Owned;
+        }
+    }
+
     fn key<'s, S: Substr<'s>>(&self, substr: S) -> Option<NonEmptyIniStr<'s, '_>> {
         use ParsedIniStringKind::*;
 
@@ -109,7 +173,7 @@ impl ParsedIniString {
             }
             Borrowed(range) => {
                 debug_assert!(self.buffer.is_empty());
-                Some(NonEmptyIniStr::Borrowed(substr(range.0..=range.1)))
+                Some(NonEmptyIniStr::Borrowed(substr(range.0..range.1)))
             }
             Owned => {
                 debug_assert!(
@@ -134,7 +198,7 @@ impl ParsedIniString {
             }
             Borrowed(range) => {
                 debug_assert!(self.buffer.is_empty());
-                IniStr::Borrowed(substr(range.0..=range.1))
+                IniStr::Borrowed(substr(range.0..range.1))
             }
             Owned => {
                 debug_assert!(
@@ -149,6 +213,34 @@ impl ParsedIniString {
         }
     }
 
+    /// Removes the last `len` bytes of the string.
+    /// The caller guarantees the string remains non-empty and the new end is at a char boundary.
+    fn truncate_end(&mut self, len: usize) {
+        use ParsedIniStringKind::*;
+
+        if len == 0 {
+            return;
+        }
+
+        match &mut self.kind {
+            Cleared => debug_assert!(false, "cannot truncate an empty `.ini` string"),
+            Borrowed(range) => {
+                debug_assert!(
+                    range.1 > range.0 + len,
+                    "byte ranges for borrowed `.ini` strings must be non-empty"
+                );
+                range.1 -= len;
+            }
+            Owned => {
+                debug_assert!(
+                    self.buffer.len() > len,
+                    "owned `.ini` strings may not be empty"
+                );
+                self.buffer.truncate(self.buffer.len() - len);
+            }
+        }
+    }
+
     fn clear(&mut self) {
         self.kind = ParsedIniStringKind::Cleared;
         self.buffer.clear();
@@ -161,7 +253,7 @@ impl ParsedIniString {
             Cleared => true,
             Borrowed(range) => {
                 debug_assert!(
-                    range.1 >= range.0,
+                    range.1 > range.0,
                     "byte ranges for borrowed `.ini` strings must be non-empty"
                 );
                 false
@@ -181,8 +273,14 @@ impl ParsedIniString {
 pub(crate) struct ParsedIniKey(ParsedIniString);
 
 impl ParsedIniKey {
-    pub(crate) fn new() -> Self {
-        Self(ParsedIniString::new())
+    /// See `ParsedIniString::from_buffer()`.
+    pub(crate) fn from_buffer(buffer: String) -> Self {
+        Self(ParsedIniString::from_buffer(buffer))
+    }
+
+    /// See `ParsedIniString::into_buffer()`.
+    pub(crate) fn into_buffer(self) -> String {
+        self.0.into_buffer()
     }
 
     /// See `ParsedIniString::push()`.
@@ -190,6 +288,11 @@ impl ParsedIniKey {
         self.0.push(c, idx)
     }
 
+    /// See `ParsedIniString::push_range()`.
+    pub(crate) fn push_range<'s, S: Substr<'s>>(&mut self, range: Range<usize>, substr: S) {
+        self.0.push_range(range, substr)
+    }
+
     /// See `ParsedIniString::push_owned()`.
     pub(crate) fn push_owned<'s, S: Substr<'s>>(&mut self, c: char, substr: S) {
         self.0.push_owned(c, substr)
@@ -200,6 +303,16 @@ impl ParsedIniKey {
         self.0.to_owned(substr)
     }
 
+    /// See `ParsedIniString::replace()`.
+    pub(crate) fn replace(&mut self, string: &str) {
+        self.0.replace(string)
+    }
+
+    /// See `ParsedIniString::fold_case()`.
+    pub(crate) fn fold_case<'s, S: Substr<'s>>(&mut self, substr: S, original: &mut String) {
+        self.0.fold_case(substr, original)
+    }
+
     /// See `ParsedIniString::key()`.
     pub(crate) fn key<'s, S: Substr<'s>>(&self, substr: S) -> Option<NonEmptyIniStr<'s, '_>> {
         self.0.key(substr)
@@ -218,8 +331,14 @@ impl ParsedIniKey {
 pub(crate) struct ParsedIniValue(ParsedIniString);
 
 impl ParsedIniValue {
-    pub(crate) fn new() -> Self {
-        Self(ParsedIniString::new())
+    /// See `ParsedIniString::from_buffer()`.
+    pub(crate) fn from_buffer(buffer: String) -> Self {
+        Self(ParsedIniString::from_buffer(buffer))
+    }
+
+    /// See `ParsedIniString::into_buffer()`.
+    pub(crate) fn into_buffer(self) -> String {
+        self.0.into_buffer()
     }
 
     /// See `ParsedIniString::push()`.
@@ -227,6 +346,11 @@ impl ParsedIniValue {
         self.0.push(c, idx)
     }
 
+    /// See `ParsedIniString::push_range()`.
+    pub(crate) fn push_range<'s, S: Substr<'s>>(&mut self, range: Range<usize>, substr: S) {
+        self.0.push_range(range, substr)
+    }
+
     /// See `ParsedIniString::push_owned()`.
     pub(crate) fn push_owned<'s, S: Substr<'s>>(&mut self, c: char, substr: S) {
         self.0.push_owned(c, substr)
@@ -242,6 +366,11 @@ impl ParsedIniValue {
         self.0.value(substr)
     }
 
+    /// See `ParsedIniString::truncate_end()`.
+    pub(crate) fn truncate_end(&mut self, len: usize) {
+        self.0.truncate_end(len)
+    }
+
     pub(crate) fn clear(&mut self) {
         self.0.clear()
     }
@@ -8,6 +8,9 @@ pub enum IniValue<'s, 'a> {
     I64(i64),
     F64(f64),
     String(IniStr<'s, 'a>),
+    /// NOTE - requires `"datetime"` feature.
+    #[cfg(feature = "datetime")]
+    DateTime(crate::DateTime),
 }
 
 impl<'s, 'a> IniValue<'s, 'a> {
@@ -17,16 +20,36 @@ impl<'s, 'a> IniValue<'s, 'a> {
             IniValue::I64(_) => IniValueType::I64,
             IniValue::F64(_) => IniValueType::F64,
             IniValue::String(_) => IniValueType::String,
+            #[cfg(feature = "datetime")]
+            IniValue::DateTime(_) => IniValueType::DateTime,
         }
     }
 }
 
+/// Hints the type of the unparsed `.ini` config value
+/// passed to the [`event handler`] by the [`.ini parser`] configured to report [`raw values`].
+///
+/// [`event handler`]: trait.IniConfig.html#method.add_raw_value
+/// [`.ini parser`]: struct.IniParser.html
+/// [`raw values`]: struct.IniParser.html#method.raw_values
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniValueHint {
+    /// The value is a string - it was quoted or empty
+    /// (or the parser is in [`strict mode`](struct.IniParser.html#method.strict_values)).
+    String,
+    /// The value was unquoted and may be a boolean / integer / float
+    /// (or date/time, if `"datetime"` feature is enabled), or else an (unquoted) string.
+    Unquoted,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub(crate) enum IniValueType {
     Bool,
     I64,
     F64,
     String,
+    #[cfg(feature = "datetime")]
+    DateTime,
 }
 
 impl IniValueType {
@@ -38,6 +61,8 @@ impl IniValueType {
             I64 => (other == I64) || (other == F64),
             F64 => (other == I64) || (other == F64),
             String => other == String,
+            #[cfg(feature = "datetime")]
+            DateTime => other == DateTime,
         }
     }
 }
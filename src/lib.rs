@@ -4,33 +4,58 @@ mod bin_config;
 #[cfg(feature = "dyn")]
 mod dyn_config;
 
-#[cfg(feature = "lua")]
+#[cfg(feature = "lua_runtime")]
 mod lua_config;
 
+#[cfg(all(
+    feature = "lua_runtime",
+    not(any(feature = "lua51", feature = "lua53", feature = "lua54"))
+))]
+compile_error!("\"lua_runtime\" feature must not be enabled directly - enable \"lua\" (Lua 5.3) or one of \"lua51\" / \"lua53\" / \"lua54\" features to select the Lua runtime version");
+
+#[cfg(any(
+    all(feature = "lua51", feature = "lua53"),
+    all(feature = "lua51", feature = "lua54"),
+    all(feature = "lua53", feature = "lua54")
+))]
+compile_error!("only one of \"lua51\" / \"lua53\" / \"lua54\" features may be enabled (\"lua\" feature enables \"lua53\")");
+
 #[cfg(feature = "ini")]
 mod ini;
 
+#[cfg(all(feature = "watch", any(feature = "ini", feature = "lua_runtime")))]
+mod watch;
+
+#[cfg(all(feature = "embed", any(feature = "ini", feature = "lua_runtime")))]
+mod embed;
+
 #[cfg(any(
     feature = "bin",
     feature = "dyn",
     feature = "ini",
-    feature = "lua",
+    feature = "lua_runtime",
     feature = "str_hash"
 ))]
 #[macro_use]
 mod util;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 pub(crate) use util::debug_unreachable_impl;
 
 mod value;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+#[cfg(feature = "datetime")]
+mod datetime;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 mod error;
 
 pub use value::*;
 
-#[cfg(all(test, any(feature = "bin", feature = "dyn", feature = "lua")))]
+#[cfg(feature = "datetime")]
+pub use datetime::*;
+
+#[cfg(all(test, any(feature = "bin", feature = "dyn", feature = "lua_runtime")))]
 pub(crate) use util::cmp_f64;
 
 #[cfg(feature = "bin")]
@@ -39,20 +64,29 @@ pub use bin_config::*;
 #[cfg(feature = "dyn")]
 pub use dyn_config::*;
 
-#[cfg(feature = "lua")]
+#[cfg(feature = "lua_runtime")]
 pub use lua_config::*;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 pub use error::*;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 pub use util::*;
 
 #[cfg(feature = "ini")]
 pub use ini::*;
 
-#[cfg(all(feature = "bin", feature = "str_hash"))]
+#[cfg(all(feature = "watch", any(feature = "ini", feature = "lua_runtime")))]
+pub use watch::*;
+
+#[cfg(all(feature = "embed", any(feature = "ini", feature = "lua_runtime")))]
+pub use embed::*;
+
+#[cfg(all(
+    feature = "str_hash",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
 pub use util::StringAndHash;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 pub use ministr::{NonEmptyStr, NonEmptyString};
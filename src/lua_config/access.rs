@@ -0,0 +1,48 @@
+use crate::*;
+
+impl<'lua> Config for LuaConfig<'lua> {
+    type Table<'a>
+        = LuaTable<'lua>
+    where
+        Self: 'a;
+
+    fn root(&self) -> Self::Table<'_> {
+        LuaConfig::root(self)
+    }
+}
+
+impl<'lua> TableAccess for LuaTable<'lua> {
+    type String = LuaString<'lua>;
+    type Array = LuaArray<'lua>;
+
+    fn len(&self) -> u32 {
+        LuaTable::len(self)
+    }
+
+    fn get_val(&self, key: &str) -> Option<LuaConfigValue<'lua>> {
+        LuaTable::get_val(self, key)
+    }
+
+    fn iter<'i>(
+        &'i self,
+    ) -> Box<dyn Iterator<Item = (LuaString<'lua>, LuaConfigValue<'lua>)> + 'i> {
+        Box::new(LuaTable::iter(self))
+    }
+}
+
+impl<'lua> ArrayAccess for LuaArray<'lua> {
+    type String = LuaString<'lua>;
+    type Table = LuaTable<'lua>;
+
+    fn len(&self) -> u32 {
+        LuaArray::len(self)
+    }
+
+    fn get_val(&self, index: u32) -> Result<LuaConfigValue<'lua>, ArrayError> {
+        LuaArray::get_val(self, index)
+    }
+
+    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = LuaConfigValue<'lua>> + 'i> {
+        Box::new(LuaArray::iter(self))
+    }
+}
@@ -194,6 +194,74 @@ impl<'lua> LuaArray<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u32`] [`value`] in the [`array`] at `0`-based `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u32`].
+    ///
+    /// [`u32`]: https://doc.rust-lang.org/std/primitive.u32.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.ArrayError.html#variant.ValueOutOfRange
+    pub fn get_u32(&self, index: u32) -> Result<u32, ArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(ArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get an [`i32`] [`value`] in the [`array`] at `0`-based `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of an [`i32`].
+    ///
+    /// [`i32`]: https://doc.rust-lang.org/std/primitive.i32.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.ArrayError.html#variant.ValueOutOfRange
+    pub fn get_i32(&self, index: u32) -> Result<i32, ArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(ArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u16`] [`value`] in the [`array`] at `0`-based `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u16`].
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/std/primitive.u16.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.ArrayError.html#variant.ValueOutOfRange
+    pub fn get_u16(&self, index: u32) -> Result<u16, ArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(ArrayError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u8`] [`value`] in the [`array`] at `0`-based `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u8`].
+    ///
+    /// [`u8`]: https://doc.rust-lang.org/std/primitive.u8.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.ArrayError.html#variant.ValueOutOfRange
+    pub fn get_u8(&self, index: u32) -> Result<u8, ArrayError> {
+        self.get_i64(index)
+            .and_then(|value| narrow_i64(value).map_err(ArrayError::ValueOutOfRange))
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`array`] at `0`-based `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds or if value is not an [`f64`] / [`i64`].
@@ -416,6 +484,15 @@ impl<'lua> LuaArray<'lua> {
         use ArrayError::*;
 
         let value_type = value.get_type();
+
+        // Date/time values are stored as strings in Lua configs.
+        #[cfg(feature = "datetime")]
+        let value_type = if value_type == ValueType::DateTime {
+            ValueType::String
+        } else {
+            value_type
+        };
+
         let array_value_type = get_array_value_type(&self.0);
 
         // If the array is non-empty and has a value type, ensure the provided value type is compatible.
@@ -451,6 +528,9 @@ impl<'lua> LuaArray<'lua> {
                 Value::String(value) => array.raw_set(index, value),
                 Value::Array(value) => array.raw_set(index, value.0),
                 Value::Table(value) => array.raw_set(index, value.0),
+                // Lua has no native date/time type - store as a string.
+                #[cfg(feature = "datetime")]
+                Value::DateTime(value) => array.raw_set(index, value.to_string()),
             },
             "failed to set a value in the Lua array table",
         );
@@ -538,14 +618,24 @@ impl<'lua> LuaArray<'lua> {
         }
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        // Arrays of `bool`'s / numbers may be serialized in compact form.
+        if is_compact_lua_array(self.iter().next().map(|value| value.get_type()), options) {
+            return write_compact_lua_array(w, self.iter(), self.len(), indent, options);
+        }
+
         writeln!(w, "{{")?;
 
         // Iterate the array.
         for (index, value) in self.iter().enumerate() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            options.formatter.write_indent(w, indent + 1)?;
 
-            value.fmt_lua(w, indent + 1)?;
+            value.fmt_lua(w, indent + 1, options)?;
 
             write!(w, ",")?;
 
@@ -558,7 +648,7 @@ impl<'lua> LuaArray<'lua> {
             writeln!(w)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
+        options.formatter.write_indent(w, indent)?;
         write!(w, "}}")?;
 
         Ok(())
@@ -593,14 +683,19 @@ impl<'lua> std::iter::Iterator for LuaArrayIter<'lua> {
 }
 
 impl<'lua> DisplayLua for LuaArray<'lua> {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl<'lua> Display for LuaArray<'lua> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, Default::default())
     }
 }
 
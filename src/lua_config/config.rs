@@ -1,10 +1,14 @@
 use {
-    super::util::*,
+    super::{references::resolve_references, util::*},
     crate::{util::*, *},
-    rlua::{Context, RegistryKey},
+    rlua::{Context, HookTriggers, RegistryKey},
     std::{
         fmt::{Display, Formatter, Write},
         num::NonZeroU32,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
     },
 };
 
@@ -31,10 +35,33 @@ impl<'lua> LuaConfig<'lua> {
     ///
     /// [`Lua config`]: struct.LuaConfig.html
     pub fn from_script(lua: Context<'lua>, script: &str) -> Result<Self, LuaConfigError> {
+        Self::from_script_impl(lua, script, LuaConfigOptions::default())
+    }
+
+    /// Creates a new [`Lua config`] from the Lua `script`,
+    /// making the [`globals`] of the Lua state available to the script
+    /// and optionally resolving the deferred values ([`references`]).
+    ///
+    /// [`Lua config`]: struct.LuaConfig.html
+    /// [`globals`]: struct.LuaConfigOptions.html#structfield.globals
+    /// [`references`]: struct.LuaConfigOptions.html#structfield.references
+    fn from_script_impl(
+        lua: Context<'lua>,
+        script: &str,
+        options: LuaConfigOptions<'_>,
+    ) -> Result<Self, LuaConfigError> {
         use LuaConfigError::*;
 
         let root = lua.create_table().map_err(LuaScriptError)?;
 
+        // The script is executed in the isolated environment - only expose the requested globals.
+        let lua_globals = lua.globals();
+
+        for global in options.globals {
+            let value: rlua::Value<'_> = lua_globals.raw_get(*global).map_err(LuaScriptError)?;
+            root.raw_set(*global, value).map_err(LuaScriptError)?;
+        }
+
         // Must prepend the global "root" value assignment because of the way we define the config root Lua table
         // (similar to JSON).
         let script =
@@ -47,26 +74,68 @@ impl<'lua> LuaConfig<'lua> {
             .map_err(LuaScriptError)?;
 
         // Must succeed.
-        let root = unwrap_unchecked(
+        let config_root = unwrap_unchecked(
             root.raw_get("root"),
             "failed to get the Lua config root table from the environment",
         );
 
-        Self::from_table(lua, root)
+        if options.references {
+            resolve_references(lua, &root, &config_root)?;
+        }
+
+        Self::from_table_impl(lua, config_root, options.max_depth)
     }
 
-    /// Creates a new [`config`] from the Lua `table`.
+    /// Creates a new [`config`] from the existing Lua `table` (e.g. returned by a user Lua script)
+    /// without copying it.
+    ///
+    /// The `table` and all its nested tables are validated to be a valid config
+    /// (see [`error`] for the requirements), nested at most [`DEFAULT_MAX_DEPTH`] levels deep.
+    /// The `table` becomes the root [`table`] of the [`config`] - the changes made to it by the [`config`]
+    /// are visible to the Lua scripts which hold a reference to it, and vice versa.
     ///
     /// [`config`]: struct.LuaConfig.html
+    /// [`error`]: enum.LuaConfigError.html
+    /// [`DEFAULT_MAX_DEPTH`]: constant.DEFAULT_MAX_DEPTH.html
+    /// [`table`]: struct.LuaTable.html
     pub fn from_table(
         lua: Context<'lua>,
         table: rlua::Table<'lua>,
     ) -> Result<Self, LuaConfigError> {
-        validate_lua_config_table(lua, &table)?;
+        Self::from_table_impl(lua, table, DEFAULT_MAX_DEPTH)
+    }
+
+    fn from_table_impl(
+        lua: Context<'lua>,
+        table: rlua::Table<'lua>,
+        max_depth: u32,
+    ) -> Result<Self, LuaConfigError> {
+        validate_lua_config_table(lua, &table, max_depth)?;
 
         Ok(LuaConfig(LuaTable::from_valid_table(table)))
     }
 
+    /// Creates a new [`config`] from the existing Lua table stored in the [`Lua registry`] under the `key`,
+    /// without copying it.
+    ///
+    /// See [`from_table`].
+    ///
+    /// Returns a [`LuaScriptError`] if the `key` does not refer to a table,
+    /// or if it was created by a different [`Lua state`].
+    ///
+    /// [`config`]: struct.LuaConfig.html
+    /// [`Lua registry`]: https://docs.rs/rlua/*/rlua/struct.Context.html#method.create_registry_value
+    /// [`from_table`]: #method.from_table
+    /// [`LuaScriptError`]: enum.LuaConfigError.html#variant.LuaScriptError
+    /// [`Lua state`]: https://docs.rs/rlua/*/rlua/struct.Lua.html
+    pub fn from_ref(lua: Context<'lua>, key: &RegistryKey) -> Result<Self, LuaConfigError> {
+        let table = lua
+            .registry_value(key)
+            .map_err(LuaConfigError::LuaScriptError)?;
+
+        Self::from_table(lua, table)
+    }
+
     /// Returns the reference to the root [`table`] of the [`config`].
     ///
     /// [`table`]: struct.LuaTable.html
@@ -113,7 +182,58 @@ impl<'lua> LuaConfig<'lua> {
     ///
     /// [`config`]: struct.LuaConfig.html
     pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), std::fmt::Error> {
-        self.root().fmt_lua(w, 0)
+        self.fmt_lua_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string using provided [`options`].
+    ///
+    /// [`config`]: struct.LuaConfig.html
+    /// [`options`]: struct.ToLuaStringOptions.html
+    pub fn to_lua_string_opts(
+        &self,
+        options: ToLuaStringOptions,
+    ) -> Result<String, std::fmt::Error> {
+        let mut result = String::new();
+
+        self.fmt_lua_opts(options, &mut result)?;
+
+        result.shrink_to_fit();
+
+        Ok(result)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using provided [`options`].
+    ///
+    /// [`config`]: struct.LuaConfig.html
+    /// [`options`]: struct.ToLuaStringOptions.html
+    pub fn fmt_lua_opts<W: Write>(
+        &self,
+        options: ToLuaStringOptions,
+        w: &mut W,
+    ) -> Result<(), std::fmt::Error> {
+        self.root()
+            .fmt_lua(&mut options.formatter.new_lines(w), 0, options)
+    }
+
+    /// Returns the 64-bit hash of the contents of this [`config`], calculated over its [`canonical`] serialization
+    /// to a Lua script string, so that logically equal configs (regardless of the config backend,
+    /// or the order in which their values were added) always have the same hash,
+    /// e.g. to be used as cache keys by build systems.
+    ///
+    /// NOTE - the hash is not a cryptographic hash, and is not guaranteed to be stable across versions of this crate.
+    ///
+    /// [`config`]: struct.LuaConfig.html
+    /// [`canonical`]: struct.ToLuaStringOptions.html#method.canonical
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = ContentHasher::new();
+
+        // Must succeed - the hasher never fails.
+        unwrap_unchecked(
+            self.fmt_lua_opts(ToLuaStringOptions::canonical(), &mut hasher),
+            "failed to hash a config",
+        );
+
+        hasher.finish()
     }
 
     /// Serializes this [`config`] to a [`dynamic config`].
@@ -131,8 +251,12 @@ impl<'lua> LuaConfig<'lua> {
 
     /// Tries to serialize this [`config`] to a [`binary config`].
     ///
+    /// Walks the Lua tables / arrays of the config and drives the binary config [`writer`] directly,
+    /// without serializing the config to an intermediate Lua script / `.ini` string.
+    ///
     /// [`config`]: struct.LuaConfig.html
     /// [`binary config`]: struct.BinConfig.html
+    /// [`writer`]: struct.BinConfigWriter.html
     #[cfg(feature = "bin")]
     pub fn to_bin_config(&self) -> Result<Box<[u8]>, BinConfigWriterError> {
         use BinConfigWriterError::*;
@@ -199,7 +323,41 @@ impl<'lua> LuaConfig<'lua> {
     ) -> Result<(), ToIniStringError> {
         let mut path = IniPath::new();
 
-        self.root().fmt_ini(w, 0, false, &mut path, options)
+        self.root().fmt_ini(
+            &mut options.formatter.new_lines(w),
+            0,
+            false,
+            &mut path,
+            options,
+        )
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string to the `std::io::Write` writer `w` (e.g. a file)
+    /// using default [`options`], without an intermediate string.
+    ///
+    /// NOTE - writes to `w` are not buffered - consider wrapping unbuffered writers in a `std::io::BufWriter`.
+    ///
+    /// [`config`]: struct.LuaConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn write_ini<W: std::io::Write>(&self, w: &mut W) -> Result<(), ToIniStringError> {
+        self.write_ini_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string to the `std::io::Write` writer `w` (e.g. a file)
+    /// using provided [`options`], without an intermediate string.
+    ///
+    /// NOTE - writes to `w` are not buffered - consider wrapping unbuffered writers in a `std::io::BufWriter`.
+    ///
+    /// [`config`]: struct.LuaConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn write_ini_opts<W: std::io::Write>(
+        &self,
+        options: ToIniStringOptions,
+        w: &mut W,
+    ) -> Result<(), ToIniStringError> {
+        self.fmt_ini_opts(options, &mut IoWriter(w))
     }
 
     #[cfg(feature = "dyn")]
@@ -244,6 +402,8 @@ impl<'lua> LuaConfig<'lua> {
                 Self::table_to_dyn_table(value, &mut table);
                 dyn_table.set(key, table)
             }
+            #[cfg(feature = "datetime")]
+            DateTime(value) => dyn_table.set(key, value),
         };
 
         // Must succeed - we are only adding values to the dyn table.
@@ -276,6 +436,8 @@ impl<'lua> LuaConfig<'lua> {
                 Self::table_to_dyn_table(value, &mut table);
                 dyn_array.push(Value::Table(table))
             }
+            #[cfg(feature = "datetime")]
+            DateTime(value) => dyn_array.push(Value::DateTime(value)),
         } {
             debug_unreachable!("pushing a value to the array failed")
         }
@@ -335,6 +497,89 @@ impl LuaConfigKey {
         LuaConfig::from_script(lua, script).map(|config| config.key(lua))
     }
 
+    /// Creates a new [`Lua config`] from the Lua `script`, using the provided sandboxing [`options`].
+    ///
+    /// NOTE - the instruction / memory limits are implemented via the Lua state hook / memory limit,
+    /// which are overridden while the `script` is executed and removed afterwards.
+    ///
+    /// [`Lua config`]: struct.LuaConfigKey.html
+    /// [`options`]: struct.LuaConfigOptions.html
+    pub fn from_script_opts(
+        lua: &rlua::Lua,
+        script: &str,
+        options: LuaConfigOptions<'_>,
+    ) -> Result<Self, LuaConfigError> {
+        use LuaConfigError::*;
+
+        let instruction_limit_exceeded = Arc::new(AtomicBool::new(false));
+
+        if let Some(instruction_limit) = options.instruction_limit {
+            let instruction_limit_exceeded = instruction_limit_exceeded.clone();
+
+            lua.set_hook(
+                HookTriggers {
+                    every_nth_instruction: Some(instruction_limit.get()),
+                    ..Default::default()
+                },
+                move |_, _| {
+                    instruction_limit_exceeded.store(true, Ordering::Relaxed);
+                    Err(rlua::Error::RuntimeError(
+                        "instruction limit exceeded".to_owned(),
+                    ))
+                },
+            );
+        }
+
+        if let Some(memory_limit) = options.memory_limit {
+            lua.set_memory_limit(Some(lua.used_memory() + memory_limit));
+        }
+
+        lua.context(|context| {
+            let result = LuaConfig::from_script_impl(context, script, options);
+
+            // Remove the limits before creating the registry value.
+            if options.instruction_limit.is_some() {
+                lua.remove_hook();
+            }
+
+            if options.memory_limit.is_some() {
+                lua.set_memory_limit(None);
+            }
+
+            result.map(|config| config.key(context))
+        })
+        .map_err(|err| match err {
+            _ if instruction_limit_exceeded.load(Ordering::Relaxed) => InstructionLimitExceeded,
+            LuaScriptError(rlua::Error::MemoryError(_)) => MemoryLimitExceeded,
+            err => err,
+        })
+    }
+
+    /// Creates a new [`Lua config`] from the existing Lua `table` without copying it.
+    ///
+    /// See [`LuaConfig::from_table`].
+    ///
+    /// [`Lua config`]: struct.LuaConfigKey.html
+    /// [`LuaConfig::from_table`]: struct.LuaConfig.html#method.from_table
+    pub fn from_table<'lua>(
+        lua: Context<'lua>,
+        table: rlua::Table<'lua>,
+    ) -> Result<Self, LuaConfigError> {
+        LuaConfig::from_table(lua, table).map(|config| config.key(lua))
+    }
+
+    /// Creates a new [`Lua config`] from the existing Lua table stored in the [`Lua registry`] under the `key`,
+    /// without copying it.
+    ///
+    /// See [`LuaConfig::from_ref`].
+    ///
+    /// [`Lua config`]: struct.LuaConfigKey.html
+    /// [`Lua registry`]: https://docs.rs/rlua/*/rlua/struct.Context.html#method.create_registry_value
+    /// [`LuaConfig::from_ref`]: struct.LuaConfig.html#method.from_ref
+    pub fn from_ref(lua: Context<'_>, key: &RegistryKey) -> Result<Self, LuaConfigError> {
+        LuaConfig::from_ref(lua, key).map(|config| config.key(lua))
+    }
+
     /// Returns the root [`Lua table`] of the config.
     ///
     /// [`Lua table`]: struct.LuaTable.html
@@ -348,24 +593,19 @@ fn table_to_bin_config(
     table: LuaTable<'_>,
     writer: &mut BinConfigWriter,
 ) -> Result<(), BinConfigWriterError> {
-    // Gather the keys.
-    let mut keys: Vec<_> = table.iter().map(|(key, _)| key).collect();
+    // Gather the keys and values in a single pass over the Lua table,
+    // instead of looking up each value by its key again.
+    let mut entries: Vec<_> = table.iter().collect();
 
-    // Sort the keys in alphabetical order.
-    keys.sort_by(|l, r| l.as_ref().cmp(r.as_ref()));
+    // Sort the entries by key in alphabetical order.
+    entries.sort_by(|(l, _), (r, _)| l.as_ref().cmp(r.as_ref()));
 
-    // Iterate the table using the sorted keys.
-    for key in keys.into_iter() {
+    // Iterate the table using the sorted entries.
+    for (key, value) in entries.into_iter() {
         // Must succeed - we don't allow empty table keys.
         let key_str =
             unwrap_unchecked(NonEmptyStr::new(key.as_ref()), "empty Lua config table key");
 
-        // Must succeed - all keys are valid.
-        let value = unwrap_unchecked(
-            table.get_val(key_str),
-            "failed to get a value from a Lua config table with a valid key",
-        );
-
         value_to_bin_config(Some(key_str), value, writer)?;
     }
 
@@ -416,6 +656,10 @@ fn value_to_bin_config(
             table_to_bin_config(value, writer)?;
             writer.end()?;
         }
+        #[cfg(feature = "datetime")]
+        DateTime(value) => {
+            writer.datetime(key, value)?;
+        }
     }
 
     Ok(())
@@ -423,7 +667,7 @@ fn value_to_bin_config(
 
 impl<'lua> Display for LuaConfig<'lua> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.root().fmt_lua(f, 0)
+        self.root().fmt_lua(f, 0, Default::default())
     }
 }
 
@@ -431,7 +675,9 @@ impl<'lua> Display for LuaConfig<'lua> {
 mod tests {
     #![allow(non_snake_case)]
 
-    use {crate::*, ministr_macro::nestr, rlua_ext::ValueType as LuaValueType};
+    use {
+        crate::*, ministr_macro::nestr, rlua_ext::ValueType as LuaValueType, std::num::NonZeroU32,
+    };
 
     fn lua_config(script: &str) -> Result<(), LuaConfigError> {
         let lua = rlua::Lua::new();
@@ -731,6 +977,45 @@ mod tests {
 \t}, -- table_value
 }";
 
+    #[test]
+    fn LuaConfigError_MaxDepthExceeded() {
+        let lua = rlua::Lua::new();
+
+        let max_depth = |max_depth| {
+            LuaConfigKey::from_script_opts(
+                &lua,
+                "{ foo = { bar = { 7 } } }",
+                LuaConfigOptions {
+                    max_depth,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert!(max_depth(2).is_ok());
+
+        match max_depth(1).err().unwrap() {
+            LuaConfigError::MaxDepthExceeded(path) => assert_eq!(
+                path,
+                ConfigPath(vec![nestr!("foo").into(), nestr!("bar").into()])
+            ),
+            _ => panic!("expected an error"),
+        }
+
+        // Self-containing tables.
+        lua.context(|lua| {
+            let table = lua.create_table().unwrap();
+            table.set("foo", table.clone()).unwrap();
+
+            match LuaConfig::from_table(lua, table).err().unwrap() {
+                LuaConfigError::MaxDepthExceeded(path) => {
+                    assert_eq!(path.0.len() as u32, DEFAULT_MAX_DEPTH + 1)
+                }
+                _ => panic!("expected an error"),
+            }
+        });
+    }
+
     #[test]
     fn from_script_and_back() {
         let lua = rlua::Lua::new();
@@ -746,6 +1031,191 @@ mod tests {
         });
     }
 
+    #[test]
+    fn from_script_opts() {
+        let lua = rlua::Lua::new();
+
+        // Globals are not available by default.
+        assert!(matches!(
+            LuaConfigKey::from_script_opts(&lua, "{ foo = os.time() }", Default::default())
+                .err()
+                .unwrap(),
+            LuaConfigError::LuaScriptError(_)
+        ));
+
+        let config = LuaConfigKey::from_script_opts(
+            &lua,
+            "{ foo = math.floor(7.5), bar = (os == nil) }",
+            LuaConfigOptions {
+                globals: &["math"],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        lua.context(|lua| {
+            let root = config.root(lua).unwrap();
+            assert_eq!(root.get_i64("foo").unwrap(), 7);
+            assert!(root.get_bool("bar").unwrap());
+        });
+
+        assert!(matches!(
+            LuaConfigKey::from_script_opts(
+                &lua,
+                "(function() while true do end end)()",
+                LuaConfigOptions {
+                    instruction_limit: NonZeroU32::new(10_000),
+                    ..Default::default()
+                },
+            )
+            .err()
+            .unwrap(),
+            LuaConfigError::InstructionLimitExceeded
+        ));
+
+        assert!(matches!(
+            LuaConfigKey::from_script_opts(
+                &lua,
+                "{ foo = string.rep(\"x\", 1024 * 1024) }",
+                LuaConfigOptions {
+                    globals: &["string"],
+                    memory_limit: Some(64 * 1024),
+                    ..Default::default()
+                },
+            )
+            .err()
+            .unwrap(),
+            LuaConfigError::MemoryLimitExceeded
+        ));
+
+        // The limits are removed.
+        assert!(LuaConfigKey::from_script_opts(
+            &lua,
+            "{ foo = string.rep(\"x\", 1024 * 1024) }",
+            LuaConfigOptions {
+                globals: &["string"],
+                ..Default::default()
+            },
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn from_script_references() {
+        let lua = rlua::Lua::new();
+
+        const SCRIPT: &str = "{
+            width = 1920,
+            height = function() return width * 0.5625 end,
+            area = function() return width * height end,
+            window = {
+                title = function() return \"w\" .. width end,
+                sizes = { function() return height / 2 end, 64.0 },
+            },
+        }";
+
+        // Function values are not allowed by default.
+        assert!(matches!(
+            LuaConfigKey::from_script_opts(&lua, SCRIPT, Default::default())
+                .err()
+                .unwrap(),
+            LuaConfigError::InvalidValueType { .. }
+        ));
+
+        let options = LuaConfigOptions {
+            globals: &["math"],
+            references: true,
+            ..Default::default()
+        };
+
+        let config = LuaConfigKey::from_script_opts(&lua, SCRIPT, options).unwrap();
+
+        lua.context(|lua| {
+            let root = config.root(lua).unwrap();
+            assert_eq!(root.get_f64("height").unwrap(), 1080.0);
+            assert_eq!(root.get_f64("area").unwrap(), 1920.0 * 1080.0);
+            assert_eq!(
+                root.get_string_path(&["window".into(), "title".into()])
+                    .unwrap(),
+                "w1920"
+            );
+            assert_eq!(
+                root.get_f64_path(&["window".into(), "sizes".into(), 0.into()])
+                    .unwrap(),
+                540.0
+            );
+        });
+
+        // Globals are still available.
+        let config = LuaConfigKey::from_script_opts(
+            &lua,
+            "{ foo = 7.5, bar = function() return math.floor(foo) end }",
+            options,
+        )
+        .unwrap();
+
+        lua.context(|lua| {
+            assert_eq!(config.root(lua).unwrap().get_i64("bar").unwrap(), 7);
+        });
+
+        match LuaConfigKey::from_script_opts(
+            &lua,
+            "{ table = { foo = function() return bar end, bar = function() return foo end } }",
+            options,
+        )
+        .err()
+        .unwrap()
+        {
+            LuaConfigError::ReferenceCycle(path) => {
+                assert_eq!(path.0.len(), 2);
+                assert_eq!(path.0[0], OwnedConfigKey::Table(nestr!("table").into()));
+            }
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn from_table_and_ref() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            // Table produced by a user script.
+            let table: rlua::Table = lua
+                .load("return { foo = 7, bar = { true, false } }")
+                .eval()
+                .unwrap();
+
+            let config = LuaConfig::from_table(lua, table.clone()).unwrap();
+            assert_eq!(config.root().get_i64("foo").unwrap(), 7);
+
+            // Not copied - changes are visible to the script.
+            config.root().set(nestr!("foo"), 9);
+            assert_eq!(table.get::<_, i64>("foo").unwrap(), 9);
+
+            // From the registry.
+            let key = lua.create_registry_value(table).unwrap();
+            let config = LuaConfig::from_ref(lua, &key).unwrap();
+            assert_eq!(config.root().get_i64("foo").unwrap(), 9);
+
+            let config_key = LuaConfigKey::from_ref(lua, &key).unwrap();
+            assert_eq!(config_key.root(lua).unwrap().get_i64("foo").unwrap(), 9);
+
+            // Invalid config tables.
+            let table: rlua::Table = lua.load("return { [true] = 7 }").eval().unwrap();
+            assert!(matches!(
+                LuaConfig::from_table(lua, table).err().unwrap(),
+                LuaConfigError::InvalidKeyType { .. }
+            ));
+
+            // Not a table.
+            let key = lua.create_registry_value(7).unwrap();
+            assert!(matches!(
+                LuaConfig::from_ref(lua, &key).err().unwrap(),
+                LuaConfigError::LuaScriptError(_)
+            ));
+        });
+    }
+
     #[cfg(feature = "bin")]
     #[test]
     fn to_bin_config() {
@@ -761,7 +1231,7 @@ mod tests {
             // Load the binary config.
             let bin_config = BinConfig::new(bin_data).unwrap();
 
-            let array_value = bin_config.root().get_array("array_value".into()).unwrap();
+            let array_value = bin_config.root().get_array("array_value").unwrap();
 
             assert_eq!(array_value.len(), 3);
             assert_eq!(array_value.get_i64(0).unwrap(), 54);
@@ -771,38 +1241,32 @@ mod tests {
             assert_eq!(array_value.get_i64(2).unwrap(), 78);
             assert!(cmp_f64(array_value.get_f64(2).unwrap(), 78.9));
 
-            assert_eq!(
-                bin_config.root().get_bool("bool_value".into()).unwrap(),
-                true
-            );
+            assert_eq!(bin_config.root().get_bool("bool_value").unwrap(), true);
 
             assert_eq!(
-                bin_config
-                    .root()
-                    .get_string("fancy 'value'".into())
-                    .unwrap(),
+                bin_config.root().get_string("fancy 'value'").unwrap(),
                 "\t'\""
             );
 
             assert!(cmp_f64(
-                bin_config.root().get_f64("float_value".into()).unwrap(),
+                bin_config.root().get_f64("float_value").unwrap(),
                 3.14
             ));
 
-            assert_eq!(bin_config.root().get_i64("int_value".into()).unwrap(), 7);
+            assert_eq!(bin_config.root().get_i64("int_value").unwrap(), 7);
 
             assert_eq!(
-                bin_config.root().get_string("string_value".into()).unwrap(),
+                bin_config.root().get_string("string_value").unwrap(),
                 "foo{}[];#:="
             );
 
-            let table_value = bin_config.root().get_table("table_value".into()).unwrap();
+            let table_value = bin_config.root().get_table("table_value").unwrap();
 
             assert_eq!(table_value.len(), 3);
-            assert_eq!(table_value.get_i64("bar".into()).unwrap(), 2020);
-            assert!(cmp_f64(table_value.get_f64("bar".into()).unwrap(), 2020.0));
-            assert_eq!(table_value.get_string("baz".into()).unwrap(), "hello");
-            assert_eq!(table_value.get_bool("foo".into()).unwrap(), false);
+            assert_eq!(table_value.get_i64("bar").unwrap(), 2020);
+            assert!(cmp_f64(table_value.get_f64("bar").unwrap(), 2020.0));
+            assert_eq!(table_value.get_string("baz").unwrap(), "hello");
+            assert_eq!(table_value.get_bool("foo").unwrap(), false);
         });
     }
 
@@ -7,11 +7,18 @@ use {
     },
 };
 
-/// An error returned by [`LuaConfig::from_script`], [`LuaConfigKey::from_script`].
+/// An error returned by [`LuaConfig::from_script`], [`LuaConfigKey::from_script`],
+/// [`LuaConfig::from_table`], [`LuaConfig::from_ref`], their [`LuaConfigKey`] counterparts
+/// and [`LuaConfigKey::from_script_opts`].
 ///
 /// [`LuaConfig::from_script`]: struct.LuaConfig.html#method.from_script
 /// [`LuaConfigKey::from_script`]: struct.LuaConfigKey.html#method.from_script
+/// [`LuaConfig::from_table`]: struct.LuaConfig.html#method.from_table
+/// [`LuaConfig::from_ref`]: struct.LuaConfig.html#method.from_ref
+/// [`LuaConfigKey`]: struct.LuaConfigKey.html
+/// [`LuaConfigKey::from_script_opts`]: struct.LuaConfigKey.html#method.from_script_opts
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum LuaConfigError {
     /// Error loading the Lua config script.
     /// Contains the actual Lua error.
@@ -92,6 +99,30 @@ pub enum LuaConfigError {
         /// UTF-8 parse error.
         error: rlua::Error,
     },
+    /// The Lua config script exceeded the [`instruction limit`].
+    ///
+    /// [`instruction limit`]: struct.LuaConfigOptions.html#structfield.instruction_limit
+    InstructionLimitExceeded,
+    /// The Lua config script exceeded the [`memory limit`].
+    ///
+    /// [`memory limit`]: struct.LuaConfigOptions.html#structfield.memory_limit
+    MemoryLimitExceeded,
+    /// A deferred Lua config value (indirectly) references itself
+    /// (see [`references`]).
+    /// Contains the path to the deferred value.
+    ///
+    /// [`references`]: struct.LuaConfigOptions.html#structfield.references
+    ReferenceCycle(ConfigPath),
+    /// Lua config [`tables`] / [`arrays`] are nested deeper than the [`maximum nesting depth`]
+    /// (e.g. a table which (indirectly) contains itself).
+    /// Contains the path to the first [`table`] / [`array`] nested too deep.
+    ///
+    /// [`tables`]: struct.LuaTable.html
+    /// [`arrays`]: struct.LuaArray.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`maximum nesting depth`]: struct.LuaConfigOptions.html#structfield.max_depth
+    MaxDepthExceeded(ConfigPath),
 }
 
 impl LuaConfigError {
@@ -108,8 +139,10 @@ impl LuaConfigError {
             InvalidArrayIndex(path) => path.0.push(key),
             InvalidValueType { path, .. } => path.0.push(key),
             InvalidValueUTF8 { path, .. } => path.0.push(key),
+            ReferenceCycle(path) => path.0.push(key),
+            MaxDepthExceeded(path) => path.0.push(key),
 
-            LuaScriptError(_) => {}
+            LuaScriptError(_) | InstructionLimitExceeded | MemoryLimitExceeded => {}
         };
 
         self
@@ -131,15 +164,28 @@ impl LuaConfigError {
             InvalidArrayIndex(path) => path.0.reverse(),
             InvalidValueType { path, .. } => path.0.reverse(),
             InvalidValueUTF8 { path, .. } => path.0.reverse(),
+            ReferenceCycle(path) => path.0.reverse(),
+            MaxDepthExceeded(path) => path.0.reverse(),
 
-            LuaScriptError(_) => {}
+            LuaScriptError(_) | InstructionLimitExceeded | MemoryLimitExceeded => {}
         };
 
         self
     }
 }
 
-impl Error for LuaConfigError {}
+impl Error for LuaConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use LuaConfigError::*;
+
+        match self {
+            LuaScriptError(err) => Some(err),
+            InvalidKeyUTF8 { error, .. } => Some(error),
+            InvalidValueUTF8 { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
 
 impl Display for LuaConfigError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
@@ -162,11 +208,19 @@ impl Display for LuaConfigError {
             InvalidArrayIndex(path) => write!(f, "invalid index in Lua config array {}", path),
             InvalidValueType{ path, invalid_type } => write!(f, "invalid Lua value type (\"{}\") for a Lua config value at {}", invalid_type, path),
             InvalidValueUTF8{ path, error } => write!(f, "invalid string value UTF-8 at {}: {}", path, error),
+            InstructionLimitExceeded => "Lua config script exceeded the instruction limit".fmt(f),
+            MemoryLimitExceeded => "Lua config script exceeded the memory limit".fmt(f),
+            ReferenceCycle(path) => write!(f, "cyclic reference in Lua config value {}", path),
+            MaxDepthExceeded(path) => write!(f, "Lua config table {} is nested deeper than the maximum nesting depth", path),
         }
     }
 }
 
+/// An error returned by [`LuaConfigKey`] accessors.
+///
+/// [`LuaConfigKey`]: struct.LuaConfigKey.html
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum LuaConfigKeyError {
     /// Lua state mismatch - tried to call [`config()`] / [`root()`] with the [`Lua context`]
     /// the [`config key`] is not associated with.
@@ -0,0 +1,149 @@
+use crate::{util::*, *};
+
+impl<'lua> LuaTable<'lua> {
+    /// Tries to get a 2-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `2` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec2<K: AsRef<str>>(&self, key: K) -> Result<[f32; 2], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get a 3-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `3` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec3<K: AsRef<str>>(&self, key: K) -> Result<[f32; 3], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get a 4-element vector from a numeric [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `4` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec4<K: AsRef<str>>(&self, key: K) -> Result<[f32; 4], GetVecError> {
+        self.get_vec(key)
+    }
+
+    /// Tries to get an RGBA color [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// The color is either a `#rrggbb` / `#rrggbbaa` hex [`string`],
+    /// or a numeric [`array`] with `3` (RGB, alpha is `1.0`) or `4` (RGBA) elements.
+    /// Channels of hex [`strings`] are converted to `[0.0, 1.0]` range; [`array`] elements are returned as is.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`] or an [`array`],
+    /// or if it is not a valid color.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`strings`]: enum.Value.html#variant.String
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_color<K: AsRef<str>>(&self, key: K) -> Result<[f32; 4], GetVecError> {
+        match self.get_val(key).ok_or(TableError::KeyDoesNotExist)? {
+            Value::String(color) => parse_color(color.as_ref()),
+            Value::Array(array) => color_from_values(array.len(), array.iter()),
+            value => Err(TableError::IncorrectValueType(value.get_type()).into()),
+        }
+    }
+
+    fn get_vec<K: AsRef<str>, const N: usize>(&self, key: K) -> Result<[f32; N], GetVecError> {
+        let array = self.get_array(key)?;
+        vec_from_values(array.len(), array.iter())
+    }
+}
+
+impl<'lua> LuaArray<'lua> {
+    /// Tries to get a 2-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `2` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.LuaArray.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec2(&self, index: u32) -> Result<[f32; 2], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get a 3-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `3` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.LuaArray.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec3(&self, index: u32) -> Result<[f32; 3], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get a 4-element vector from a numeric [`array`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not an [`array`],
+    /// or if the [`array`] does not contain exactly `4` numeric elements.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`array`]: struct.LuaArray.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_vec4(&self, index: u32) -> Result<[f32; 4], GetVecError> {
+        self.get_vec(index)
+    }
+
+    /// Tries to get an RGBA color [`value`] in the [`array`] at `index`.
+    ///
+    /// See [`LuaTable::get_color`] for supported color representations.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`] or an [`array`],
+    /// or if it is not a valid color.
+    ///
+    /// NOTE - requires `"math"` feature.
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`LuaTable::get_color`]: struct.LuaTable.html#method.get_color
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`error`]: enum.GetVecError.html
+    pub fn get_color(&self, index: u32) -> Result<[f32; 4], GetVecError> {
+        match self.get_val(index)? {
+            Value::String(color) => parse_color(color.as_ref()),
+            Value::Array(array) => color_from_values(array.len(), array.iter()),
+            value => Err(ArrayError::IncorrectValueType(value.get_type()).into()),
+        }
+    }
+
+    fn get_vec<const N: usize>(&self, index: u32) -> Result<[f32; N], GetVecError> {
+        let array = self.get_array(index)?;
+        vec_from_values(array.len(), array.iter())
+    }
+}
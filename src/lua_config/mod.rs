@@ -1,8 +1,15 @@
+mod access;
 mod array;
 mod config;
 mod error;
+#[cfg(feature = "math")]
+mod math;
+mod options;
+mod references;
 mod table;
+mod units;
 mod util;
 mod value;
+mod version;
 
-pub use {array::*, config::*, error::*, table::*, value::*};
+pub use {array::*, config::*, error::*, options::*, table::*, value::*, version::*};
@@ -0,0 +1,66 @@
+use std::num::NonZeroU32;
+
+/// Sandboxing options used when evaluating a Lua config script with [`LuaConfigKey::from_script_opts`].
+///
+/// Lua config scripts are always executed in an isolated environment,
+/// which by default does not have access to any of the Lua state globals (e.g. `os`, `io`, `require`).
+///
+/// [`LuaConfigKey::from_script_opts`]: struct.LuaConfigKey.html#method.from_script_opts
+#[derive(Clone, Copy, Debug)]
+pub struct LuaConfigOptions<'a> {
+    /// Names of the Lua state globals (e.g. `"math"`, `"string"`) made available to the script.
+    /// Missing globals are ignored.
+    ///
+    /// Default: none.
+    pub globals: &'a [&'a str],
+    /// If `Some`, the script is aborted with an [`InstructionLimitExceeded`] error
+    /// after executing the given number of Lua VM instructions.
+    ///
+    /// Default: `None`.
+    ///
+    /// [`InstructionLimitExceeded`]: enum.LuaConfigError.html#variant.InstructionLimitExceeded
+    pub instruction_limit: Option<NonZeroU32>,
+    /// If `Some`, the script is aborted with a [`MemoryLimitExceeded`] error
+    /// if it allocates more than the given number of bytes (in addition to the memory already used by the Lua state).
+    ///
+    /// Default: `None`.
+    ///
+    /// [`MemoryLimitExceeded`]: enum.LuaConfigError.html#variant.MemoryLimitExceeded
+    pub memory_limit: Option<usize>,
+    /// If `true`, function values in the config are treated as deferred values (e.g. `height = function() return width * 0.5625 end`)
+    /// and are replaced with their return values after the script is executed.
+    ///
+    /// Deferred values may reference the other keys by name - first in the table which contains the deferred value,
+    /// then in all its enclosing tables, innermost first.
+    /// Referenced deferred values are evaluated first, on demand; otherwise the evaluation order is unspecified.
+    /// Deferred values which (indirectly) reference themselves result in a [`ReferenceCycle`] error.
+    ///
+    /// NOTE - referencing an enclosing table by name (e.g. `window.width`) returns the table as is,
+    /// with its deferred values possibly not yet evaluated - reference the enclosing table's keys directly instead.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`ReferenceCycle`]: enum.LuaConfigError.html#variant.ReferenceCycle
+    pub references: bool,
+    /// Maximum nesting depth of the config tables / arrays (the tables / arrays in the root table are at depth `1`).
+    /// The config is rejected with a [`MaxDepthExceeded`] error if its tables / arrays are nested deeper
+    /// (or if a table (indirectly) contains itself).
+    ///
+    /// Default: [`DEFAULT_MAX_DEPTH`].
+    ///
+    /// [`MaxDepthExceeded`]: enum.LuaConfigError.html#variant.MaxDepthExceeded
+    /// [`DEFAULT_MAX_DEPTH`]: constant.DEFAULT_MAX_DEPTH.html
+    pub max_depth: u32,
+}
+
+impl<'a> Default for LuaConfigOptions<'a> {
+    fn default() -> Self {
+        Self {
+            globals: &[],
+            instruction_limit: None,
+            memory_limit: None,
+            references: false,
+            max_depth: crate::DEFAULT_MAX_DEPTH,
+        }
+    }
+}
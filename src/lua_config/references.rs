@@ -0,0 +1,147 @@
+use {
+    crate::*,
+    rlua::{Context, Value as LuaValue},
+};
+
+/// Lua chunk which resolves the deferred values (functions) in the Lua config root table.
+///
+/// Called with the script environment table and the Lua config root table.
+/// Replaces each function value in the root table and all its nested tables with the value it returns,
+/// evaluating the functions on demand, so that they may reference the (resolved) values of the other keys.
+/// Free names in the functions are resolved w.r.t. the table which contains the function,
+/// then all its enclosing tables, innermost first.
+///
+/// Returns `nil` on success, or the keys of the path to the cyclic value in reverse order.
+const RESOLVE_REFERENCES: &str = r#"
+local env, root = ...
+
+-- Enclosing table and key of each visited nested table.
+local parents = {}
+-- Keys of the values being resolved, per table.
+local resolving = {}
+-- Tables containing the values being resolved, innermost last.
+local scopes = {}
+-- The value which (indirectly) references itself, if any.
+local cycle_table, cycle_key
+
+local resolve_table
+
+local function resolve(t, key)
+    local value = rawget(t, key)
+
+    if type(value) == "function" then
+        local keys = resolving[t]
+
+        if not keys then
+            keys = {}
+            resolving[t] = keys
+        end
+
+        if keys[key] then
+            cycle_table, cycle_key = t, key
+            error(resolving)
+        end
+
+        keys[key] = true
+        scopes[#scopes + 1] = t
+
+        value = value()
+
+        scopes[#scopes] = nil
+        keys[key] = nil
+
+        rawset(t, key, value)
+    end
+
+    -- Resolve the nested tables when they are first visited.
+    if type(value) == "table" and value ~= root and not parents[value] then
+        parents[value] = { t, key }
+        resolve_table(value)
+    end
+
+    return value
+end
+
+resolve_table = function(t)
+    for key in pairs(t) do
+        resolve(t, key)
+    end
+end
+
+setmetatable(env, {
+    __index = function(_, name)
+        local t = scopes[#scopes]
+
+        while t do
+            if rawget(t, name) ~= nil then
+                return resolve(t, name)
+            end
+
+            local parent = parents[t]
+            t = parent and parent[1]
+        end
+    end
+})
+
+local ok, err = pcall(resolve_table, root)
+
+setmetatable(env, nil)
+
+if ok then
+    return nil
+end
+
+if err ~= resolving then
+    error(err, 0)
+end
+
+local path = { cycle_key }
+local t = cycle_table
+
+while parents[t] do
+    path[#path + 1] = parents[t][2]
+    t = parents[t][1]
+end
+
+return path
+"#;
+
+/// Resolves the deferred values in the Lua config `root` table
+/// (see [`LuaConfigOptions::references`](struct.LuaConfigOptions.html#structfield.references)),
+/// executed in the script environment table `env`.
+pub(super) fn resolve_references<'lua>(
+    lua: Context<'lua>,
+    env: &rlua::Table<'lua>,
+    root: &rlua::Table<'lua>,
+) -> Result<(), LuaConfigError> {
+    use LuaConfigError::*;
+
+    let cycle: Option<rlua::Table<'lua>> = lua
+        .load(RESOLVE_REFERENCES)
+        .into_function()
+        .map_err(LuaScriptError)?
+        .call((env.clone(), root.clone()))
+        .map_err(LuaScriptError)?;
+
+    if let Some(cycle) = cycle {
+        let mut path = cycle
+            .sequence_values::<LuaValue<'_>>()
+            .filter_map(|key| match key.ok()? {
+                LuaValue::String(key) => {
+                    NonEmptyStr::new(key.to_str().ok()?).map(OwnedConfigKey::from)
+                }
+                // Lua array indices are `1`-based.
+                LuaValue::Integer(index) if index > 0 => {
+                    Some(OwnedConfigKey::Array((index - 1) as _))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        path.reverse();
+
+        return Err(ReferenceCycle(path.into()));
+    }
+
+    Ok(())
+}
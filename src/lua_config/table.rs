@@ -6,6 +6,7 @@ use {
         borrow::Borrow,
         convert::TryInto,
         fmt::{Display, Formatter, Write},
+        str::FromStr,
     },
 };
 
@@ -81,6 +82,92 @@ impl<'lua> LuaTable<'lua> {
         V::try_from(self.get_val(key).ok_or_else(|| KeyDoesNotExist)?).map_err(IncorrectValueType)
     }
 
+    /// Returns `true` if the [`table`] contains a [`value`] with the string `key` and its precomputed hash
+    /// (created via the [`key!`] macro).
+    ///
+    /// Provided for parity with the [`binary`] and [`dynamic`] config tables.
+    /// NOTE - Lua hashes (and interns) the key string internally, so this does not avoid runtime string hashing.
+    ///
+    /// NOTE - requires `"str_hash"` feature.
+    ///
+    /// [`table`]: struct.LuaTable.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`key!`]: macro.key.html
+    /// [`binary`]: struct.BinTable.html
+    /// [`dynamic`]: struct.DynTable.html#method.contains_by_hash
+    #[cfg(feature = "str_hash")]
+    pub fn contains_by_hash(&self, key: StringAndHash) -> bool {
+        self.get_val_by_hash(key).is_some()
+    }
+
+    /// Tries to get a reference to a [`value`] in the [`table`] with the string `key` and its precomputed hash
+    /// (created via the [`key!`] macro).
+    ///
+    /// Provided for parity with the [`binary`] and [`dynamic`] config tables.
+    /// NOTE - Lua hashes (and interns) the key string internally, so this does not avoid runtime string hashing.
+    ///
+    /// NOTE - requires `"str_hash"` feature.
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`key!`]: macro.key.html
+    /// [`binary`]: struct.BinTable.html
+    /// [`dynamic`]: struct.DynTable.html#method.get_val_by_hash
+    #[cfg(feature = "str_hash")]
+    pub fn get_val_by_hash(&self, key: StringAndHash) -> Option<LuaConfigValue<'lua>> {
+        self.get_impl(key.string)
+    }
+
+    /// Tries to get a reference to a [`value`] in the [`table`] with the string `key` and its precomputed hash
+    /// (created via the [`key!`] macro),
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// Provided for parity with the [`binary`] and [`dynamic`] config tables.
+    /// NOTE - Lua hashes (and interns) the key string internally, so this does not avoid runtime string hashing.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`,
+    /// or if the [`value`] is of incorrect and incompatible type.
+    ///
+    /// NOTE - requires `"str_hash"` feature.
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`key!`]: macro.key.html
+    /// [`binary`]: struct.BinTable.html
+    /// [`dynamic`]: struct.DynTable.html#method.get_by_hash
+    /// [`error`]: enum.TableError.html
+    #[cfg(feature = "str_hash")]
+    pub fn get_by_hash<V: TryFromValue<LuaString<'lua>, LuaArray<'lua>, LuaTable<'lua>>>(
+        &self,
+        key: StringAndHash,
+    ) -> Result<V, TableError> {
+        use TableError::*;
+        V::try_from(self.get_val_by_hash(key).ok_or(KeyDoesNotExist)?).map_err(IncorrectValueType)
+    }
+
+    /// Tries to get a reference to a [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key`,
+    /// or if the [`value`] is of incorrect and incompatible [`type`].
+    ///
+    /// NOTE - use [`Lenient`] as the requested type to apply lenient type coercion rules.
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`type`]: enum.ValueType.html
+    /// [`Lenient`]: struct.Lenient.html
+    pub fn get_or<
+        K: AsRef<str>,
+        V: TryFromValue<LuaString<'lua>, LuaArray<'lua>, LuaTable<'lua>>,
+    >(
+        &self,
+        key: K,
+        default: V,
+    ) -> V {
+        self.get(key).unwrap_or(default)
+    }
+
     /// Tries to get a reference to a [`value`] in the [`table`] at `path`.
     ///
     /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
@@ -133,6 +220,33 @@ impl<'lua> LuaTable<'lua> {
         V::try_from(self.get_val_path(path)?).map_err(GetPathError::IncorrectValueType)
     }
 
+    /// Tries to get a reference to a [`value`] in the [`table`] at `path`,
+    /// and convert it to the user-requested type [`convertible`](TryFromValue) from a [`value`].
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    ///
+    /// Returns the `default` value if the `path` is invalid,
+    /// or if the [`value`] is of incorrect and incompatible [`type`].
+    ///
+    /// NOTE - use [`Lenient`] as the requested type to apply lenient type coercion rules.
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`type`]: enum.ValueType.html
+    /// [`Lenient`]: struct.Lenient.html
+    pub fn get_path_or<'k, K, P, V>(&self, path: P, default: V) -> V
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+        V: TryFromValue<LuaString<'lua>, LuaArray<'lua>, LuaTable<'lua>>,
+    {
+        self.get_path(path).unwrap_or(default)
+    }
+
     /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`bool`].
@@ -167,6 +281,39 @@ impl<'lua> LuaTable<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not a [`bool`].
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    pub fn get_bool_or<K: AsRef<str>>(&self, key: K, default: bool) -> bool {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`bool`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_bool<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<bool> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::Bool,
+            self.get(key.as_ref()),
+        )
+    }
+
     /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`].
@@ -203,6 +350,109 @@ impl<'lua> LuaTable<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`].
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_i64_or<K: AsRef<str>>(&self, key: K, default: i64) -> i64 {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`i64`] / [`f64`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_i64<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<i64> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::I64,
+            self.get(key.as_ref()),
+        )
+    }
+
+    /// Tries to get a [`u32`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u32`].
+    ///
+    /// [`u32`]: https://doc.rust-lang.org/std/primitive.u32.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u32<K: AsRef<str>>(&self, key: K) -> Result<u32, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get an [`i32`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of an [`i32`].
+    ///
+    /// [`i32`]: https://doc.rust-lang.org/std/primitive.i32.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_i32<K: AsRef<str>>(&self, key: K) -> Result<i32, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u16`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u16`].
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/std/primitive.u16.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u16<K: AsRef<str>>(&self, key: K) -> Result<u16, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
+    /// Tries to get a [`u8`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`f64`],
+    /// or if it is [`out of range`] of a [`u8`].
+    ///
+    /// [`u8`]: https://doc.rust-lang.org/std/primitive.u8.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`out of range`]: enum.TableError.html#variant.ValueOutOfRange
+    pub fn get_u8<K: AsRef<str>>(&self, key: K) -> Result<u8, TableError> {
+        self.get_i64(key)
+            .and_then(|value| narrow_i64(value).map_err(TableError::ValueOutOfRange))
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
@@ -239,6 +489,41 @@ impl<'lua> LuaTable<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns the `default` value if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn get_f64_or<K: AsRef<str>>(&self, key: K, default: f64) -> f64 {
+        self.get(key).unwrap_or(default)
+    }
+
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_f64<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<f64> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::F64,
+            self.get(key.as_ref()),
+        )
+    }
+
     /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`string`].
@@ -273,6 +558,70 @@ impl<'lua> LuaTable<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`string`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_string<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<LuaString<'lua>> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::String,
+            self.get(key.as_ref()),
+        )
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as an enum value of type `T` via its [`FromStr`] implementation.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    /// [`error`]: enum.GetEnumError.html
+    pub fn get_enum<K: AsRef<str>, T: FromStr>(&self, key: K) -> Result<T, GetEnumError> {
+        let value = self.get_string(key.as_ref());
+        GetEnumError::parse(
+            key.as_ref(),
+            value.as_ref().map(AsRef::as_ref).map_err(|err| *err),
+        )
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and map it to an enum value of type `T` using the valid `variants` - pairs of strings and corresponding enum values.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it does not match any of the `variants` (the error then lists all valid `variants`).
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.GetEnumError.html
+    pub fn get_enum_with<K: AsRef<str>, T: Clone>(
+        &self,
+        key: K,
+        variants: &[(&str, T)],
+    ) -> Result<T, GetEnumError> {
+        let value = self.get_string(key.as_ref());
+        GetEnumError::map(
+            key.as_ref(),
+            value.as_ref().map(AsRef::as_ref).map_err(|err| *err),
+            variants,
+        )
+    }
+
     /// Tries to get an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`array`].
@@ -306,6 +655,28 @@ impl<'lua> LuaTable<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not an [`array`],
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_array<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<LuaArray<'lua>> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::Array,
+            self.get(key.as_ref()),
+        )
+    }
+
     /// Tries to get a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`table`](enum.Value.html#variant.Table).
@@ -338,6 +709,27 @@ impl<'lua> LuaTable<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns `None` if the [`table`] does not contain the `key` or if value is not a [`table`](enum.Value.html#variant.Table),
+    /// recording the [`diagnostic`] in the `diagnostics` sink, if any.
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`diagnostic`]: struct.ConfigDiagnostic.html
+    pub fn try_get_table<'d, K: AsRef<str>, D: Into<Option<&'d ConfigDiagnostics>>>(
+        &self,
+        key: K,
+        diagnostics: D,
+    ) -> Option<LuaTable<'lua>> {
+        ConfigDiagnostics::try_get(
+            diagnostics.into(),
+            key.as_ref(),
+            ValueType::Table,
+            self.get(key.as_ref()),
+        )
+    }
+
     /// Returns an iterator over ([`key`], [`value`]) pairs of the [`table`], in unspecified order.
     ///
     /// [`key`]: struct.LuaString.html
@@ -360,6 +752,22 @@ impl<'lua> LuaTable<'lua> {
         self.set_impl(key.as_ref(), value.into())
     }
 
+    /// Inserts or changes the [`value`] at string `key`, which may be a (non-empty) `&NonEmptyStr` or a plain `&str`.
+    /// Returns `true` if the [`value`] at `key` already existed and was modified.
+    /// Returns `false` if the [`value`] at `key` did not exist and was added.
+    ///
+    /// Returns an [`error`] if the `key` is empty.
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`error`]: struct.EmptyKeyError.html
+    pub fn try_set<'s, 'k, K, V>(&mut self, key: K, value: V) -> Result<bool, EmptyKeyError>
+    where
+        K: IntoTableKey<'k>,
+        V: Into<Value<&'s str, LuaArray<'lua>, LuaTable<'lua>>>,
+    {
+        Ok(self.set_impl(key.into_table_key()?, value.into()))
+    }
+
     /// Tries to remove the [`value`] at (non-empty) string `key`.
     /// Returns the now-removed [`value`] at `key` if it existed,
     /// otherwise returns `None`.
@@ -369,6 +777,31 @@ impl<'lua> LuaTable<'lua> {
         self.remove_impl(key.as_ref().try_into().ok()?)
     }
 
+    /// Inserts or changes the [`values`] in the [`table`] to the [`values`] in the [`dynamic config table`], recursively.
+    ///
+    /// Nested [`tables`] present in both are updated in place,
+    /// all other [`values`] (including [`arrays`]) in the [`dynamic config table`] replace the values in the [`table`], if any.
+    /// [`values`] only present in the [`table`] are left unchanged.
+    ///
+    /// [`values`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`dynamic config table`]: struct.DynTable.html
+    /// [`tables`]: struct.LuaTable.html
+    /// [`arrays`]: struct.LuaArray.html
+    #[cfg(feature = "dyn")]
+    pub fn update_from_dyn(&mut self, lua: Context<'lua>, table: &DynTable) {
+        for (key, value) in table.iter() {
+            if let Value::Table(table) = value {
+                if let Some(Value::Table(mut lua_table)) = self.get_impl(key) {
+                    lua_table.update_from_dyn(lua, table);
+                    continue;
+                }
+            }
+
+            self.set_impl(key, value_from_dyn_value(lua, value));
+        }
+    }
+
     pub(super) fn from_valid_table(table: rlua::Table<'lua>) -> Self {
         Self(table)
     }
@@ -408,6 +841,9 @@ impl<'lua> LuaTable<'lua> {
                 Value::String(value) => table.raw_set(key, value),
                 Value::Array(value) => table.raw_set(key, value.0),
                 Value::Table(value) => table.raw_set(key, value.0),
+                // Lua has no native date/time type - store as a string.
+                #[cfg(feature = "datetime")]
+                Value::DateTime(value) => table.raw_set(key, value.to_string()),
             },
             "failed to set a value in the Lua table",
         );
@@ -464,7 +900,12 @@ impl<'lua> LuaTable<'lua> {
         }
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
         writeln!(w, "{{")?;
 
         // Gather the keys.
@@ -473,13 +914,16 @@ impl<'lua> LuaTable<'lua> {
         // Sort the keys in alphabetical order.
         keys.sort_by(|l, r| l.as_ref().cmp(r.as_ref()));
 
+        // Width to pad the keys to, if aligned.
+        let key_width = lua_keys_width(keys.iter(), options);
+
         // Iterate the table using the sorted keys.
         for key in keys.into_iter() {
             let key = unwrap_unchecked(NonEmptyStr::new(key.as_ref()), "empty key");
 
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            options.formatter.write_indent(w, indent + 1)?;
 
-            write_lua_key(w, key)?;
+            write_lua_key(w, key, key_width)?;
             write!(w, " = ")?;
 
             // Must succeed - all keys are valid.
@@ -490,7 +934,7 @@ impl<'lua> LuaTable<'lua> {
 
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            value.fmt_lua(w, indent + 1)?;
+            value.fmt_lua(w, indent + 1, options)?;
 
             write!(w, ",")?;
 
@@ -501,7 +945,7 @@ impl<'lua> LuaTable<'lua> {
             writeln!(w)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
+        options.formatter.write_indent(w, indent)?;
         write!(w, "}}")?;
 
         Ok(())
@@ -516,12 +960,23 @@ impl<'lua> LuaTable<'lua> {
         path: &mut IniPath,
         options: ToIniStringOptions,
     ) -> Result<(), ToIniStringError> {
-        debug_assert!(options.nested_sections() || level < 2);
+        debug_assert!(
+            options.nested_sections() || options.flatten_separator().is_some() || level < 2
+        );
+
+        // Tables and arrays of tables serialized as arrays of sections are written after all other values.
+        let is_section = |value: &LuaConfigValue<'_>| match value {
+            Value::Table(_) => true,
+            Value::Array(value) => {
+                is_ini_array_sections(value.get_val(0).ok().map(|value| value.get_type()), options)
+            }
+            _ => false,
+        };
 
         // Gather the keys.
         let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
 
-        // Sort the keys in alphabetical order, non-tables first.
+        // Sort the keys in the configured order, non-tables first.
         keys.sort_by(|l, r| {
             // Must succeed - all keys are valid.
             let l_val = unwrap_unchecked(
@@ -533,18 +988,27 @@ impl<'lua> LuaTable<'lua> {
                 "failed to get a value from a Lua config table with a valid key",
             );
 
-            let l_is_a_table = l_val.get_type() == ValueType::Table;
-            let r_is_a_table = r_val.get_type() == ValueType::Table;
+            let l_is_a_table = is_section(&l_val);
+            let r_is_a_table = is_section(&r_val);
 
             if !l_is_a_table && r_is_a_table {
                 std::cmp::Ordering::Less
             } else if l_is_a_table && !r_is_a_table {
                 std::cmp::Ordering::Greater
             } else {
-                l.as_ref().cmp(r.as_ref())
+                options.cmp_keys(l.as_ref(), r.as_ref())
             }
         });
 
+        // Width to pad the keys of the non-table values to, if aligned.
+        let key_width = ini_keys_width(
+            self.iter()
+                .filter(|(_, value)| !is_section(value))
+                .map(|(key, _)| key),
+            path,
+            options,
+        );
+
         let len = self.len() as usize;
 
         // Iterate the table using the sorted keys.
@@ -560,10 +1024,35 @@ impl<'lua> LuaTable<'lua> {
             );
 
             match value {
+                Value::Array(value)
+                    if is_ini_array_sections(
+                        value.get_val(0).ok().map(|value| value.get_type()),
+                        options,
+                    ) =>
+                {
+                    write_ini_array_sections(
+                        w,
+                        key,
+                        None,
+                        key_index as u32,
+                        value.iter().filter_map(Value::table).map(|table| {
+                            let len = table.len();
+                            (table, len)
+                        }),
+                        value.len() as usize,
+                        last,
+                        level,
+                        array,
+                        path,
+                        options,
+                    )?;
+                }
                 Value::Array(value) => {
                     write_ini_array(
                         w,
                         key,
+                        None,
+                        key_width,
                         value.iter(),
                         value.len() as usize,
                         last,
@@ -573,25 +1062,27 @@ impl<'lua> LuaTable<'lua> {
                     )?;
                 }
                 Value::Table(value) => {
-                    let has_non_tables = value
-                        .iter()
-                        .any(|(_, val)| val.get_type() != ValueType::Table);
+                    let has_non_tables = value.iter().any(|(_, val)| !is_section(&val));
 
                     write_ini_table(
                         w,
                         key,
+                        None,
                         key_index as u32,
                         &value,
                         value.len(),
                         has_non_tables,
                         last,
                         level,
+                        array,
                         path,
                         options,
                     )?;
                 }
                 value => {
-                    write_ini_value(w, key, &value, last, level, array, path, options)?;
+                    write_ini_value(
+                        w, key, None, key_width, &value, last, level, array, path, options,
+                    )?;
                 }
             }
         }
@@ -638,8 +1129,13 @@ impl<'lua> std::iter::Iterator for LuaTableIter<'lua> {
 }
 
 impl<'lua> DisplayLua for LuaTable<'lua> {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
@@ -659,7 +1155,7 @@ impl<'lua> DisplayIni for LuaTable<'lua> {
 
 impl<'lua> Display for LuaTable<'lua> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, Default::default())
     }
 }
 
@@ -1032,4 +1528,106 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn get_or() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let mut table = LuaTable::new(lua);
+
+            assert!(!table.set(nestr!("bool"), true));
+            assert!(!table.set(nestr!("int"), 7));
+            assert!(!table.set(nestr!("string"), "false"));
+
+            let mut nested = LuaTable::new(lua);
+            assert!(!nested.set(nestr!("float"), 3.14));
+            assert!(!table.set(nestr!("table"), nested));
+
+            assert_eq!(table.get_bool_or("bool", false), true);
+            assert_eq!(table.get_bool_or("missing", true), true);
+            assert_eq!(table.get_bool_or("int", true), true);
+
+            assert_eq!(table.get_i64_or("int", 9), 7);
+            assert!(cmp_f64(table.get_f64_or("int", 9.0), 7.0));
+            assert_eq!(table.get_i64_or("string", 9), 9);
+
+            assert_eq!(table.get_or("int", 9), 7);
+            assert_eq!(table.get_or("missing", 9), 9);
+
+            assert!(cmp_f64(
+                table.get_path_or(&["table".into(), "float".into()], 7.62),
+                3.14
+            ));
+            assert!(cmp_f64(
+                table.get_path_or(&["table".into(), "missing".into()], 7.62),
+                7.62
+            ));
+
+            // Lenient coercion.
+            assert_eq!(table.get_bool_or("string", true), true);
+            assert_eq!(table.get_or("string", Lenient(true)), Lenient(false));
+            assert_eq!(table.get_or("int", Lenient(false)), Lenient(false));
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "dyn")]
+    fn update_from_dyn() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let config = dyn_config! {
+                bool: true,
+                array: [1, 2, 3],
+                table: { string: "foo", nested: { float: 3.14 } },
+            };
+
+            let mut table = config.to_lua(lua);
+
+            assert_eq!(table.len(), 3);
+            assert_eq!(table.get_bool("bool").unwrap(), true);
+            assert_eq!(table.get_array("array").unwrap().len(), 3);
+            assert_eq!(
+                table
+                    .get_string_path(&["table".into(), "string".into()])
+                    .unwrap()
+                    .as_ref(),
+                "foo"
+            );
+            assert!(cmp_f64(
+                table
+                    .get_f64_path(&["table".into(), "nested".into(), "float".into()])
+                    .unwrap(),
+                3.14
+            ));
+
+            let mut nested = table.get_table("table").unwrap();
+            assert!(!nested.set(nestr!("int"), 7));
+
+            table.update_from_dyn(
+                lua,
+                dyn_config! {
+                    bool: false,
+                    array: [4],
+                    table: { string: "bar" },
+                }
+                .root(),
+            );
+
+            assert_eq!(table.len(), 3);
+            assert_eq!(table.get_bool("bool").unwrap(), false);
+            assert_eq!(table.get_array("array").unwrap().len(), 1);
+
+            // Nested tables are updated in place.
+            assert_eq!(nested.len(), 3);
+            assert_eq!(nested.get_string("string").unwrap().as_ref(), "bar");
+            assert_eq!(nested.get_i64("int").unwrap(), 7);
+            assert!(nested.contains("nested"));
+
+            // The Lua table is a valid Lua config table.
+            let config = LuaConfig::from_table(lua, table.0.clone()).unwrap();
+            assert_eq!(config.root().get_bool("bool").unwrap(), false);
+        });
+    }
 }
@@ -0,0 +1,68 @@
+use {
+    crate::{util::*, *},
+    std::time::Duration,
+};
+
+impl<'lua> LuaTable<'lua> {
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as a duration - a non-negative integer or fractional number followed by a unit suffix
+    /// (`ns`, `us` / `µs`, `ms`, `s`, `m` / `min`, `h` or `d`), e.g. `"5s"`, `"250ms"`, `"1.5h"`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_duration<K: AsRef<str>>(&self, key: K) -> Result<Duration, GetUnitError> {
+        parse_duration(self.get_string(key)?.as_ref())
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as a byte size - a non-negative integer or fractional number followed by an optional unit suffix
+    /// (`B`, decimal `kB` / `KB`, `MB`, `GB`, `TB`, or binary `KiB`, `MiB`, `GiB`, `TiB`), e.g. `"512"`, `"64MiB"`, `"1.5GB"`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_bytes<K: AsRef<str>>(&self, key: K) -> Result<u64, GetUnitError> {
+        parse_bytes(self.get_string(key)?.as_ref())
+    }
+}
+
+impl<'lua> LuaArray<'lua> {
+    /// Tries to get a [`string`] [`value`] in the [`array`] at `index` and parse it as a duration.
+    ///
+    /// See [`LuaTable::get_duration`] for the supported format.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`], or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`LuaTable::get_duration`]: struct.LuaTable.html#method.get_duration
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_duration(&self, index: u32) -> Result<Duration, GetUnitError> {
+        parse_duration(self.get_string(index)?.as_ref())
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`array`] at `index` and parse it as a byte size.
+    ///
+    /// See [`LuaTable::get_bytes`] for the supported format.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds, if value is not a [`string`], or if it failed to parse.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`LuaTable::get_bytes`]: struct.LuaTable.html#method.get_bytes
+    /// [`error`]: enum.GetUnitError.html
+    pub fn get_bytes(&self, index: u32) -> Result<u64, GetUnitError> {
+        parse_bytes(self.get_string(index)?.as_ref())
+    }
+}
@@ -57,18 +57,23 @@ pub(super) fn are_lua_types_compatible(l: rlua_ext::ValueType, r: rlua_ext::Valu
     }
 }
 
+/// Validates the Lua config `table` and all its nested tables / arrays,
+/// nested at most `max_depth` levels deep.
 pub(super) fn validate_lua_config_table<'lua>(
     lua: rlua::Context<'lua>,
     table: &rlua::Table<'lua>,
+    max_depth: u32,
 ) -> Result<(), LuaConfigError> {
-    validate_lua_config_table_impl(lua, table)
+    validate_lua_config_table_impl(lua, table, max_depth)
         .map(|_| ())
         .map_err(LuaConfigError::reverse)
 }
 
+/// `max_depth` is the maximum nesting depth of the `table`'s nested tables / arrays.
 fn validate_lua_config_table_impl<'lua>(
     lua: rlua::Context<'lua>,
     table: &rlua::Table<'lua>,
+    max_depth: u32,
 ) -> Result<LuaTableType, LuaConfigError> {
     use LuaConfigError::*;
 
@@ -204,14 +209,21 @@ fn validate_lua_config_table_impl<'lua>(
 
                 ValueType::String
             }
-            LuaValue::Table(value) => validate_lua_config_table_impl(lua, &value)
-                .map(|table_type| match table_type {
-                    LuaTableType::Array => ValueType::Array,
-                    LuaTableType::Table => ValueType::Table,
-                })
-                // Push the current table / array key to the end of the path on error.
-                // The path will be reversed at the end.
-                .map_err(|err| err.push_key(key.into()))?,
+            LuaValue::Table(value) => {
+                // Also protects against (indirectly) self-containing tables.
+                if max_depth == 0 {
+                    return Err(MaxDepthExceeded(vec![key.into()].into()));
+                }
+
+                validate_lua_config_table_impl(lua, &value, max_depth - 1)
+                    .map(|table_type| match table_type {
+                        LuaTableType::Array => ValueType::Array,
+                        LuaTableType::Table => ValueType::Table,
+                    })
+                    // Push the current table / array key to the end of the path on error.
+                    // The path will be reversed at the end.
+                    .map_err(|err| err.push_key(key.into()))?
+            }
             // Only valid Lua value types allowed.
             invalid_value => {
                 return Err(InvalidValueType {
@@ -407,3 +419,41 @@ pub(super) fn clear_array(table: &rlua::Table<'_>) {
         let _ = table.raw_set(index + 1, rlua::Value::Nil);
     }
 }
+
+/// Creates the Lua [`value`] equivalent to the [`dynamic config value`], creating new Lua [`tables`] / [`arrays`] as necessary.
+///
+/// [`value`]: type.LuaConfigValue.html
+/// [`dynamic config value`]: type.DynConfigValueRef.html
+/// [`tables`]: struct.LuaTable.html
+/// [`arrays`]: struct.LuaArray.html
+#[cfg(feature = "dyn")]
+pub(super) fn value_from_dyn_value<'s, 'lua>(
+    lua: rlua::Context<'lua>,
+    value: DynConfigValueRef<'s>,
+) -> Value<&'s str, LuaArray<'lua>, LuaTable<'lua>> {
+    match value {
+        Value::Bool(value) => Value::Bool(value),
+        Value::I64(value) => Value::I64(value),
+        Value::F64(value) => Value::F64(value),
+        Value::String(value) => Value::String(value),
+        Value::Array(value) => {
+            let mut array = LuaArray::new(lua);
+
+            for value in value.iter() {
+                // Must succeed - we are pushing values of the same type to the Lua array.
+                if array.push(value_from_dyn_value(lua, value)).is_err() {
+                    debug_unreachable!("pushing a value to the array failed")
+                }
+            }
+
+            Value::Array(array)
+        }
+        Value::Table(value) => {
+            let mut table = LuaTable::new(lua);
+            table.update_from_dyn(lua, value);
+            Value::Table(table)
+        }
+        #[cfg(feature = "datetime")]
+        Value::DateTime(value) => Value::DateTime(value),
+    }
+}
@@ -68,8 +68,10 @@ impl<'lua> LuaConfigValue<'lua> {
                                 path: vec![(*index).into()].into(),
                                 len,
                             },
-                            ArrayError::ArrayEmpty | ArrayError::IncorrectValueType(_) => {
-                                debug_unreachable!("`get()` does not return `ArrayEmpty` or `IncorrectValueType(_)`")
+                            ArrayError::ArrayEmpty
+                            | ArrayError::IncorrectValueType(_)
+                            | ArrayError::ValueOutOfRange(_) => {
+                                debug_unreachable!("`get()` does not return `ArrayEmpty`, `IncorrectValueType(_)` or `ValueOutOfRange(_)`")
                             }
                         })?;
 
@@ -103,7 +105,7 @@ impl<'lua> LuaConfigValue<'lua> {
 
 impl<'lua> Display for LuaConfigValue<'lua> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, Default::default())
     }
 }
 
@@ -0,0 +1,75 @@
+/// Version of the Lua runtime used by [`Lua configs`], selected by the `"lua51"` / `"lua53"` / `"lua54"` features.
+///
+/// [`Lua configs`]: struct.LuaConfig.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LuaVersion {
+    /// Lua 5.1 (`"lua51"` feature).
+    ///
+    /// NOTE - Lua 5.1 has no integer number subtype.
+    /// Numbers with integral values (including floating-point values like `1.0`) are read as [`integers`].
+    ///
+    /// [`integers`]: enum.Value.html#variant.I64
+    Lua51,
+    /// Lua 5.3 (`"lua53"` feature).
+    Lua53,
+    /// Lua 5.4 (`"lua54"` feature).
+    Lua54,
+}
+
+impl LuaVersion {
+    /// Returns `true` if the Lua runtime distinguishes between integer and floating-point numbers.
+    pub fn has_integers(self) -> bool {
+        match self {
+            LuaVersion::Lua51 => false,
+            LuaVersion::Lua53 | LuaVersion::Lua54 => true,
+        }
+    }
+}
+
+/// Version of the Lua runtime [`Lua configs`] were compiled against.
+///
+/// [`Lua configs`]: struct.LuaConfig.html
+#[cfg(feature = "lua51")]
+pub const LUA_VERSION: LuaVersion = LuaVersion::Lua51;
+
+/// Version of the Lua runtime [`Lua configs`] were compiled against.
+///
+/// [`Lua configs`]: struct.LuaConfig.html
+#[cfg(feature = "lua53")]
+pub const LUA_VERSION: LuaVersion = LuaVersion::Lua53;
+
+/// Version of the Lua runtime [`Lua configs`] were compiled against.
+///
+/// [`Lua configs`]: struct.LuaConfig.html
+#[cfg(feature = "lua54")]
+pub const LUA_VERSION: LuaVersion = LuaVersion::Lua54;
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn lua_version() {
+        assert_eq!(LUA_VERSION.has_integers(), !cfg!(feature = "lua51"));
+
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let config =
+                LuaConfig::from_script(lua, "{ int = 7, integral = 1.0, float = 0.5 }").unwrap();
+            let root = config.root();
+
+            assert!(matches!(root.get_val("int"), Some(Value::I64(7))));
+            assert!(matches!(root.get_val("float"), Some(Value::F64(val)) if cmp_f64(val, 0.5)));
+
+            // Lua 5.1 has no integer number subtype - integral floating-point values are read as integers.
+            if LUA_VERSION == LuaVersion::Lua51 {
+                assert!(matches!(root.get_val("integral"), Some(Value::I64(1))));
+            } else {
+                assert!(
+                    matches!(root.get_val("integral"), Some(Value::F64(val)) if cmp_f64(val, 1.0))
+                );
+            }
+        });
+    }
+}
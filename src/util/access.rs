@@ -0,0 +1,580 @@
+use {crate::*, std::borrow::Cow};
+
+/// A config with a root [`table`], accessed via the backend-agnostic [`TableAccess`] / [`ArrayAccess`] traits.
+///
+/// Implemented by [`BinConfig`], [`DynConfig`] and [`LuaConfig`],
+/// so that read-only config access code may be written once and used with any config backend.
+///
+/// [`table`]: trait.TableAccess.html
+/// [`TableAccess`]: trait.TableAccess.html
+/// [`ArrayAccess`]: trait.ArrayAccess.html
+/// [`BinConfig`]: struct.BinConfig.html
+/// [`DynConfig`]: struct.DynConfig.html
+/// [`LuaConfig`]: struct.LuaConfig.html
+pub trait Config {
+    /// The root [`table`] type of the config.
+    ///
+    /// [`table`]: trait.TableAccess.html
+    type Table<'a>: TableAccess
+    where
+        Self: 'a;
+
+    /// Returns the root [`table`] of the config.
+    ///
+    /// [`table`]: trait.TableAccess.html
+    fn root(&self) -> Self::Table<'_>;
+}
+
+/// Read-only access to a config [`table`], implemented by [`BinTable`], `&`[`DynTable`] and [`LuaTable`].
+///
+/// Only [`len`] / [`get_val`] / [`iter`] are required; the typed accessors are implemented in terms of [`get_val`]
+/// and follow the semantics of the backends' own accessors.
+///
+/// [`table`]: enum.Value.html#variant.Table
+/// [`BinTable`]: struct.BinTable.html
+/// [`DynTable`]: struct.DynTable.html
+/// [`LuaTable`]: struct.LuaTable.html
+/// [`len`]: #tymethod.len
+/// [`get_val`]: #tymethod.get_val
+/// [`iter`]: #tymethod.iter
+pub trait TableAccess: Sized {
+    /// The [`string`] value type of the config.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    type String: AsRef<str>;
+
+    /// The [`array`] type of the config.
+    ///
+    /// [`array`]: trait.ArrayAccess.html
+    type Array: ArrayAccess<String = Self::String, Table = Self>;
+
+    /// Returns the number of entries in the [`table`].
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    fn len(&self) -> u32;
+
+    /// Returns `true` if the [`table`] is empty.
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tries to get a [`value`] in the [`table`] with the (non-empty) string `key`.
+    /// Returns `None` if the [`table`] does not contain the `key` or if the `key` is empty.
+    ///
+    /// [`value`]: enum.Value.html
+    /// [`table`]: enum.Value.html#variant.Table
+    fn get_val(&self, key: &str) -> Option<Value<Self::String, Self::Array, Self>>;
+
+    /// Returns an iterator over (`key`, [`value`]) pairs of the [`table`], in unspecified order.
+    ///
+    /// [`value`]: enum.Value.html
+    /// [`table`]: enum.Value.html#variant.Table
+    #[allow(clippy::type_complexity)]
+    fn iter<'i>(
+        &'i self,
+    ) -> Box<dyn Iterator<Item = (Self::String, Value<Self::String, Self::Array, Self>)> + 'i>;
+
+    /// Returns `true` if the [`table`] contains a [`value`] with the (non-empty) string `key`.
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`value`]: enum.Value.html
+    fn contains(&self, key: &str) -> bool {
+        self.get_val(key).is_some()
+    }
+
+    /// Tries to get a `bool` [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, or if value is not a `bool`.
+    ///
+    /// [`value`]: enum.Value.html#variant.Bool
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`error`]: enum.TableError.html
+    fn get_bool(&self, key: &str) -> Result<bool, TableError> {
+        let value = self.get_val(key).ok_or(TableError::KeyDoesNotExist)?;
+        value
+            .bool()
+            .ok_or_else(|| TableError::IncorrectValueType(value.get_type()))
+    }
+
+    /// Tries to get an `i64` [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, or if value is not an `i64` / `f64`.
+    ///
+    /// [`value`]: enum.Value.html#variant.I64
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`error`]: enum.TableError.html
+    fn get_i64(&self, key: &str) -> Result<i64, TableError> {
+        let value = self.get_val(key).ok_or(TableError::KeyDoesNotExist)?;
+        value
+            .i64()
+            .ok_or_else(|| TableError::IncorrectValueType(value.get_type()))
+    }
+
+    /// Tries to get an `f64` [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, or if value is not an `f64` / `i64`.
+    ///
+    /// [`value`]: enum.Value.html#variant.F64
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`error`]: enum.TableError.html
+    fn get_f64(&self, key: &str) -> Result<f64, TableError> {
+        let value = self.get_val(key).ok_or(TableError::KeyDoesNotExist)?;
+        value
+            .f64()
+            .ok_or_else(|| TableError::IncorrectValueType(value.get_type()))
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, or if value is not a [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: enum.Value.html
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`error`]: enum.TableError.html
+    fn get_string(&self, key: &str) -> Result<Self::String, TableError> {
+        match self.get_val(key).ok_or(TableError::KeyDoesNotExist)? {
+            Value::String(value) => Ok(value),
+            value => Err(TableError::IncorrectValueType(value.get_type())),
+        }
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// as a [`Cow`] string - borrowed if the config backend allows it (binary / dynamic configs),
+    /// owned otherwise (Lua configs).
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, or if value is not a [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: enum.Value.html
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`Cow`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html
+    /// [`error`]: enum.TableError.html
+    fn get_str_cow(&self, key: &str) -> Result<Cow<'_, str>, TableError> {
+        self.get_string(key)
+            .map(|value| Cow::Owned(value.as_ref().to_owned()))
+    }
+
+    /// Tries to get an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, or if value is not an [`array`].
+    ///
+    /// [`array`]: trait.ArrayAccess.html
+    /// [`value`]: enum.Value.html
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`error`]: enum.TableError.html
+    fn get_array(&self, key: &str) -> Result<Self::Array, TableError> {
+        match self.get_val(key).ok_or(TableError::KeyDoesNotExist)? {
+            Value::Array(value) => Ok(value),
+            value => Err(TableError::IncorrectValueType(value.get_type())),
+        }
+    }
+
+    /// Tries to get a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, or if value is not a [`table`](enum.Value.html#variant.Table).
+    ///
+    /// [`value`]: enum.Value.html
+    /// [`table`]: trait.TableAccess.html
+    /// [`error`]: enum.TableError.html
+    fn get_table(&self, key: &str) -> Result<Self, TableError> {
+        match self.get_val(key).ok_or(TableError::KeyDoesNotExist)? {
+            Value::Table(value) => Ok(value),
+            value => Err(TableError::IncorrectValueType(value.get_type())),
+        }
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, or if value is not a [`date/time`].
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// [`date/time`]: struct.DateTime.html
+    /// [`value`]: enum.Value.html#variant.DateTime
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`error`]: enum.TableError.html
+    #[cfg(feature = "datetime")]
+    fn get_datetime(&self, key: &str) -> Result<DateTime, TableError> {
+        let value = self.get_val(key).ok_or(TableError::KeyDoesNotExist)?;
+        value
+            .datetime()
+            .ok_or_else(|| TableError::IncorrectValueType(value.get_type()))
+    }
+}
+
+/// Read-only access to a config [`array`], implemented by [`BinArray`], `&`[`DynArray`] and [`LuaArray`].
+///
+/// Only [`len`] / [`get_val`] / [`iter`] are required; the typed accessors are implemented in terms of [`get_val`]
+/// and follow the semantics of the backends' own accessors.
+///
+/// [`array`]: enum.Value.html#variant.Array
+/// [`BinArray`]: struct.BinArray.html
+/// [`DynArray`]: struct.DynArray.html
+/// [`LuaArray`]: struct.LuaArray.html
+/// [`len`]: #tymethod.len
+/// [`get_val`]: #tymethod.get_val
+/// [`iter`]: #tymethod.iter
+pub trait ArrayAccess: Sized {
+    /// The [`string`] value type of the config.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    type String: AsRef<str>;
+
+    /// The [`table`] type of the config.
+    ///
+    /// [`table`]: trait.TableAccess.html
+    type Table: TableAccess<String = Self::String, Array = Self>;
+
+    /// Returns the number of elements in the [`array`].
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    fn len(&self) -> u32;
+
+    /// Returns `true` if the [`array`] is empty.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tries to get a [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds.
+    ///
+    /// [`value`]: enum.Value.html
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.ArrayError.html
+    fn get_val(&self, index: u32) -> Result<Value<Self::String, Self, Self::Table>, ArrayError>;
+
+    /// Returns an in-order iterator over the [`values`] in the [`array`].
+    ///
+    /// [`values`]: enum.Value.html
+    /// [`array`]: enum.Value.html#variant.Array
+    fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = Value<Self::String, Self, Self::Table>> + 'i>;
+
+    /// Tries to get a `bool` [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a `bool`.
+    ///
+    /// [`value`]: enum.Value.html#variant.Bool
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.ArrayError.html
+    fn get_bool(&self, index: u32) -> Result<bool, ArrayError> {
+        let value = self.get_val(index)?;
+        value
+            .bool()
+            .ok_or_else(|| ArrayError::IncorrectValueType(value.get_type()))
+    }
+
+    /// Tries to get an `i64` [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not an `i64` / `f64`.
+    ///
+    /// [`value`]: enum.Value.html#variant.I64
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.ArrayError.html
+    fn get_i64(&self, index: u32) -> Result<i64, ArrayError> {
+        let value = self.get_val(index)?;
+        value
+            .i64()
+            .ok_or_else(|| ArrayError::IncorrectValueType(value.get_type()))
+    }
+
+    /// Tries to get an `f64` [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not an `f64` / `i64`.
+    ///
+    /// [`value`]: enum.Value.html#variant.F64
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.ArrayError.html
+    fn get_f64(&self, index: u32) -> Result<f64, ArrayError> {
+        let value = self.get_val(index)?;
+        value
+            .f64()
+            .ok_or_else(|| ArrayError::IncorrectValueType(value.get_type()))
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: enum.Value.html
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.ArrayError.html
+    fn get_string(&self, index: u32) -> Result<Self::String, ArrayError> {
+        match self.get_val(index)? {
+            Value::String(value) => Ok(value),
+            value => Err(ArrayError::IncorrectValueType(value.get_type())),
+        }
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`array`] at `index`
+    /// as a [`Cow`] string - borrowed if the config backend allows it (binary / dynamic configs),
+    /// owned otherwise (Lua configs).
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: enum.Value.html
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`Cow`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html
+    /// [`error`]: enum.ArrayError.html
+    fn get_str_cow(&self, index: u32) -> Result<Cow<'_, str>, ArrayError> {
+        self.get_string(index)
+            .map(|value| Cow::Owned(value.as_ref().to_owned()))
+    }
+
+    /// Tries to get an [`array`](enum.Value.html#variant.Array) [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not an [`array`](enum.Value.html#variant.Array).
+    ///
+    /// [`value`]: enum.Value.html
+    /// [`array`]: trait.ArrayAccess.html
+    /// [`error`]: enum.ArrayError.html
+    fn get_array(&self, index: u32) -> Result<Self, ArrayError> {
+        match self.get_val(index)? {
+            Value::Array(value) => Ok(value),
+            value => Err(ArrayError::IncorrectValueType(value.get_type())),
+        }
+    }
+
+    /// Tries to get a [`table`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`table`].
+    ///
+    /// [`table`]: trait.TableAccess.html
+    /// [`value`]: enum.Value.html
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.ArrayError.html
+    fn get_table(&self, index: u32) -> Result<Self::Table, ArrayError> {
+        match self.get_val(index)? {
+            Value::Table(value) => Ok(value),
+            value => Err(ArrayError::IncorrectValueType(value.get_type())),
+        }
+    }
+
+    /// Tries to get a [`date/time`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`date/time`].
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// [`date/time`]: struct.DateTime.html
+    /// [`value`]: enum.Value.html#variant.DateTime
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`error`]: enum.ArrayError.html
+    #[cfg(feature = "datetime")]
+    fn get_datetime(&self, index: u32) -> Result<DateTime, ArrayError> {
+        let value = self.get_val(index)?;
+        value
+            .datetime()
+            .ok_or_else(|| ArrayError::IncorrectValueType(value.get_type()))
+    }
+}
+
+#[cfg(all(test, feature = "dyn", feature = "bin"))]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr, std::borrow::Cow};
+
+    /// Backend-agnostic config access.
+    fn window_area<C: Config>(config: &C) -> Result<i64, TableError> {
+        let window = config.root().get_table("window")?;
+        let size = window
+            .get_array("size")
+            .map_err(|_| TableError::KeyDoesNotExist)?;
+
+        let mut area = 1;
+
+        for value in size.iter() {
+            area *= value
+                .i64()
+                .ok_or(TableError::IncorrectValueType(value.get_type()))?;
+        }
+
+        Ok(area)
+    }
+
+    fn title<C: Config>(config: &C) -> Option<String> {
+        config
+            .root()
+            .get_table("window")
+            .ok()?
+            .get_string("title")
+            .ok()
+            .map(|title| title.as_ref().to_owned())
+    }
+
+    #[test]
+    fn access() {
+        let mut size = DynArray::new();
+        size.push(1920.into()).unwrap();
+        size.push(1080.into()).unwrap();
+
+        let mut window = DynTable::new();
+        window.set(nestr!("size"), Value::Array(size));
+        window.set(nestr!("title"), "Game");
+
+        let mut config = DynConfig::new();
+        config
+            .root_mut()
+            .set(nestr!("window"), Value::Table(window));
+
+        let bin_config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+
+        assert_eq!(window_area(&config).unwrap(), 1920 * 1080);
+        assert_eq!(window_area(&bin_config).unwrap(), 1920 * 1080);
+
+        assert_eq!(title(&config).unwrap(), "Game");
+        assert_eq!(title(&bin_config).unwrap(), "Game");
+
+        let root = bin_config.root();
+        assert_eq!(TableAccess::len(&root), 1);
+        assert_eq!(
+            TableAccess::get_bool(&root, "window").err().unwrap(),
+            TableError::IncorrectValueType(ValueType::Table)
+        );
+        assert_eq!(
+            TableAccess::get_i64(&root, "missing").err().unwrap(),
+            TableError::KeyDoesNotExist
+        );
+
+        let window = TableAccess::get_table(&root, "window").unwrap();
+        assert!(matches!(
+            window.get_str_cow("title").unwrap(),
+            Cow::Borrowed("Game")
+        ));
+        let window = TableAccess::get_table(&config.root(), "window").unwrap();
+        assert!(matches!(
+            window.get_str_cow("title").unwrap(),
+            Cow::Borrowed("Game")
+        ));
+        assert_eq!(
+            window.get_str_cow("size").err().unwrap(),
+            TableError::IncorrectValueType(ValueType::Array)
+        );
+
+        let keys: Vec<_> = TableAccess::iter(&config.root())
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(keys, vec!["window"]);
+    }
+
+    /// Config access wrapper which does not override `get_str_cow`.
+    struct Wrapper<T>(T);
+
+    fn wrap<'a>(value: DynConfigValueRef<'a>) -> WrapperValue<'a> {
+        match value {
+            Value::Bool(value) => Value::Bool(value),
+            Value::I64(value) => Value::I64(value),
+            Value::F64(value) => Value::F64(value),
+            Value::String(value) => Value::String(value),
+            Value::Array(value) => Value::Array(Wrapper(value)),
+            Value::Table(value) => Value::Table(Wrapper(value)),
+            #[cfg(feature = "datetime")]
+            Value::DateTime(value) => Value::DateTime(value),
+        }
+    }
+
+    type WrapperValue<'a> = Value<&'a str, Wrapper<&'a DynArray>, Wrapper<&'a DynTable>>;
+
+    impl<'a> TableAccess for Wrapper<&'a DynTable> {
+        type String = &'a str;
+        type Array = Wrapper<&'a DynArray>;
+
+        fn len(&self) -> u32 {
+            self.0.len()
+        }
+
+        fn get_val(&self, key: &str) -> Option<WrapperValue<'a>> {
+            self.0.get_val(key).map(wrap)
+        }
+
+        fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = (&'a str, WrapperValue<'a>)> + 'i> {
+            Box::new(
+                self.0
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), wrap(value))),
+            )
+        }
+    }
+
+    impl<'a> ArrayAccess for Wrapper<&'a DynArray> {
+        type String = &'a str;
+        type Table = Wrapper<&'a DynTable>;
+
+        fn len(&self) -> u32 {
+            self.0.len()
+        }
+
+        fn get_val(&self, index: u32) -> Result<WrapperValue<'a>, ArrayError> {
+            self.0.get_val(index).map(wrap)
+        }
+
+        fn iter<'i>(&'i self) -> Box<dyn Iterator<Item = WrapperValue<'a>> + 'i> {
+            Box::new(self.0.iter().map(wrap))
+        }
+    }
+
+    #[test]
+    fn get_str_cow() {
+        let mut array = DynArray::new();
+        array.push("foo".into()).unwrap();
+
+        let mut config = DynConfig::new();
+        config.root_mut().set(nestr!("array"), Value::Array(array));
+        config.root_mut().set(nestr!("string"), "bar");
+
+        let bin_config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+
+        // Binary / dynamic config strings are borrowed.
+        let dyn_array = TableAccess::get_array(&config.root(), "array").unwrap();
+        let bin_root = bin_config.root();
+        let bin_array = TableAccess::get_array(&bin_root, "array").unwrap();
+
+        assert!(matches!(
+            dyn_array.get_str_cow(0).unwrap(),
+            Cow::Borrowed("foo")
+        ));
+        assert!(matches!(
+            bin_array.get_str_cow(0).unwrap(),
+            Cow::Borrowed("foo")
+        ));
+
+        assert_eq!(
+            dyn_array.get_str_cow(1).err().unwrap(),
+            ArrayError::IndexOutOfBounds(1)
+        );
+        assert_eq!(
+            bin_array.get_str_cow(1).err().unwrap(),
+            ArrayError::IndexOutOfBounds(1)
+        );
+        assert_eq!(
+            TableAccess::get_str_cow(&bin_root, "missing")
+                .err()
+                .unwrap(),
+            TableError::KeyDoesNotExist
+        );
+
+        // Strings are owned by default.
+        let root = Wrapper(config.root());
+
+        assert!(matches!(
+            root.get_str_cow("string").unwrap(),
+            Cow::Owned(string) if string == "bar"
+        ));
+        assert!(matches!(
+            root.get_array("array").unwrap().get_str_cow(0).unwrap(),
+            Cow::Owned(string) if string == "foo"
+        ));
+        assert_eq!(
+            root.get_str_cow("array").err().unwrap(),
+            TableError::IncorrectValueType(ValueType::Array)
+        );
+    }
+}
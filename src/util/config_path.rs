@@ -2,20 +2,31 @@ use {
     crate::*,
     std::{
         borrow::Cow,
+        convert::TryFrom,
+        error::Error,
         fmt::{Display, Formatter},
+        iter::Map,
+        str::FromStr,
     },
 };
 
-#[cfg(feature = "bin")]
-use crate::bin_config::string_hash_fnv1a;
+#[cfg(any(
+    feature = "bin",
+    feature = "dyn",
+    all(feature = "lua_runtime", feature = "str_hash")
+))]
+use crate::util::string_hash_fnv1a;
 
-#[cfg(all(feature = "bin", feature = "str_hash"))]
+#[cfg(all(
+    feature = "str_hash",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
 mod string_and_hash {
     use super::*;
 
     /// A non-empty string literal and its compile-time hash (created via `str_hash_fnv1a!`).
-    /// Used as an optimization for string literals used as binary config table keys to avoid runtime string hashing.
-    /// Requires "bin" and "str_hash" features.
+    /// Used as an optimization for string literals used as config table keys to avoid runtime string hashing.
+    /// Requires "str_hash" feature.
     #[derive(Clone, Copy, PartialEq, Eq, Debug)]
     pub struct StringAndHash {
         pub string: &'static NonEmptyStr,
@@ -32,6 +43,30 @@ mod string_and_hash {
             );
             Self { string, hash }
         }
+
+        /// Returns the FNV1-a hash of the `string` literal,
+        /// computed at compile time when called in a const context (used by the [`key_hash!`] macro).
+        ///
+        /// [`key_hash!`]: macro.key_hash.html
+        #[doc(hidden)]
+        pub const fn string_hash(string: &str) -> u32 {
+            string_hash_fnv1a(string)
+        }
+
+        /// Returns the expected FNV1-a `hash` of the `string` literal,
+        /// verified at compile time when called in a const context (used by the [`key_hash!`] macro).
+        ///
+        /// Panics if `hash` is not the correct FNV1-a hash of the `string` literal.
+        ///
+        /// [`key_hash!`]: macro.key_hash.html
+        #[doc(hidden)]
+        pub const fn verify_hash(string: &str, hash: u32) -> u32 {
+            assert!(
+                string_hash_fnv1a(string) == hash,
+                "string and hash mismatch"
+            );
+            hash
+        }
     }
 
     impl Display for StringAndHash {
@@ -40,31 +75,74 @@ mod string_and_hash {
         }
     }
 
-    /// Creates a [`binary config`] [`table`] [`key`] and its hash from a non-empty string literal.
+    /// Creates a config [`table`] [`key`] and its hash from a non-empty string literal.
     /// This is slightly more efficient then using a [`normal`] string key,
-    /// as this avoids runtime string hashing used internally by the binary config [`table`] accessor.
+    /// as this avoids runtime string hashing used internally by the [`binary`] and [`dynamic`] config [`table`] accessors.
     ///
-    /// [`binary config`]: struct.BinConfig.html
-    /// [`table`]: struct.BinTable.html
-    /// [`key`]: enum.TableKey.html
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`binary`]: struct.BinTable.html
+    /// [`dynamic`]: struct.DynTable.html
+    /// [`key`]: struct.StringAndHash.html
     /// [`normal`]: enum.TableKey.html#variant.String
     #[macro_export]
     macro_rules! key {
         ($string:literal) => {
-            $crate::TableKey::StringAndHash($crate::StringAndHash::new(
+            $crate::StringAndHash::new(
                 ministr_macro::nestr!($string),
                 ministr_macro::str_hash_fnv1a!($string),
-            ))
+            )
         };
     }
+
+    /// Creates a config [`table`] [`key`] and its hash from a non-empty string literal, like the [`key!`] macro,
+    /// but the hash is computed by a `const fn` at compile time instead of verified at runtime (in debug builds),
+    /// so no string hashing is ever done at runtime, e.g. when the key is used in hot loops.
+    ///
+    /// If the expected FNV1-a hash of the string literal is also provided
+    /// (e.g. when the hash is recorded in some external data), it is verified at compile time:
+    ///
+    /// ```
+    /// let speed = miniconfig::key_hash!("player/speed", 0xe737_b848);
+    /// assert_eq!(speed, miniconfig::key_hash!("player/speed"));
+    /// ```
+    ///
+    /// A mismatched hash fails to compile:
+    ///
+    /// ```compile_fail
+    /// let speed = miniconfig::key_hash!("player/speed", 0xe737_b849);
+    /// ```
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`key`]: struct.StringAndHash.html
+    /// [`key!`]: macro.key.html
+    #[macro_export]
+    macro_rules! key_hash {
+        ($string:literal) => {{
+            const HASH: u32 = $crate::StringAndHash::string_hash($string);
+            $crate::StringAndHash {
+                string: ministr_macro::nestr!($string),
+                hash: HASH,
+            }
+        }};
+        ($string:literal, $hash:expr) => {{
+            const HASH: u32 = $crate::StringAndHash::verify_hash($string, $hash);
+            $crate::StringAndHash {
+                string: ministr_macro::nestr!($string),
+                hash: HASH,
+            }
+        }};
+    }
 }
 
-#[cfg(all(feature = "bin", feature = "str_hash"))]
+#[cfg(all(
+    feature = "str_hash",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
 pub use string_and_hash::*;
 
 /// A config [`table`] string key.
 /// Borrowed, owned, or a compile-time hashed string literal
-/// (created via `key!` macro for a binary config table, requires `"bin"` and `"str_hash"` features).
+/// (created via `key!` macro, requires `"str_hash"` feature).
 ///
 /// Valid [`table`] keys are non-empty, but we allow creating keys from empty strings,
 /// instead handling the error in the config accessors to make using the code simpler.
@@ -75,7 +153,10 @@ pub enum TableKey<'a> {
     /// A normal table string key, borrowed or owned.
     String(Cow<'a, str>),
     /// A string literal + its compile time hash created via the [`key!`] macro.
-    #[cfg(all(feature = "bin", feature = "str_hash"))]
+    #[cfg(all(
+        feature = "str_hash",
+        any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+    ))]
     StringAndHash(StringAndHash),
 }
 
@@ -83,16 +164,19 @@ impl<'a> TableKey<'a> {
     pub fn as_str(&self) -> &str {
         match self {
             TableKey::String(string) => string.as_ref(),
-            #[cfg(all(feature = "bin", feature = "str_hash"))]
+            #[cfg(all(
+                feature = "str_hash",
+                any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+            ))]
             TableKey::StringAndHash(StringAndHash { string, .. }) => string.as_ref(),
         }
     }
 }
 
-#[cfg(feature = "bin")]
+#[cfg(any(feature = "bin", feature = "dyn"))]
 impl<'a> TableKey<'a> {
     /// Returns the FNV1-a hash of the key string.
-    /// Used by binary config tables (requires `"bin"` feature).
+    /// Used by binary and dynamic config tables (requires `"bin"` or `"dyn"` feature).
     /// Computed on the fly for non-string-literal keys, or just returns the compile-time hash for
     /// keys created by the [`key!`] macro from a string literal (requires `"str_hash"` feature).
     pub(crate) fn key_hash(&self) -> u32 {
@@ -115,7 +199,10 @@ impl<'a> Display for TableKey<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
             TableKey::String(string) => write!(f, "\"{}\"", string),
-            #[cfg(all(feature = "bin", feature = "str_hash"))]
+            #[cfg(all(
+                feature = "str_hash",
+                any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+            ))]
             TableKey::StringAndHash(string_and_hash) => string_and_hash.fmt(f),
         }
     }
@@ -145,6 +232,16 @@ impl<'a> From<NonEmptyString> for TableKey<'a> {
     }
 }
 
+#[cfg(all(
+    feature = "str_hash",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
+impl<'a> From<StringAndHash> for TableKey<'a> {
+    fn from(other: StringAndHash) -> Self {
+        Self::StringAndHash(other)
+    }
+}
+
 /// String key (in the [`table`]) or integer index (in the [`array`]) of a config element.
 ///
 /// [`table`]: enum.Value.html#variant.Table
@@ -191,6 +288,16 @@ impl<'a> From<TableKey<'a>> for ConfigKey<'a> {
     }
 }
 
+#[cfg(all(
+    feature = "str_hash",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
+impl<'a> From<StringAndHash> for ConfigKey<'a> {
+    fn from(key: StringAndHash) -> Self {
+        ConfigKey::Table(key.into())
+    }
+}
+
 impl<'a> From<u32> for ConfigKey<'a> {
     fn from(index: u32) -> Self {
         ConfigKey::Array(index)
@@ -211,7 +318,7 @@ impl<'a> Display for ConfigKey<'a> {
 ///
 /// [`table`]: enum.Value.html#variant.Table
 /// [`array`]: enum.Value.html#variant.Array
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum OwnedConfigKey {
     /// A string [`table`] key.
     ///
@@ -241,6 +348,160 @@ impl From<u32> for OwnedConfigKey {
     }
 }
 
+impl TryFrom<&str> for OwnedConfigKey {
+    type Error = EmptyKeyError;
+
+    fn try_from(key: &str) -> Result<Self, Self::Error> {
+        Ok(key.into_table_key()?.into())
+    }
+}
+
+impl TryFrom<String> for OwnedConfigKey {
+    type Error = EmptyKeyError;
+
+    fn try_from(key: String) -> Result<Self, Self::Error> {
+        Self::try_from(key.as_str())
+    }
+}
+
+/// An error returned when an empty string is used as a [`table`] key.
+///
+/// [`table`]: enum.Value.html#variant.Table
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EmptyKeyError;
+
+impl Error for EmptyKeyError {}
+
+impl Display for EmptyKeyError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        "table key is empty".fmt(f)
+    }
+}
+
+/// A (non-empty) string [`table`] key argument of the methods which access / add values in config [`tables`].
+///
+/// Implemented for `&NonEmptyStr` / `&NonEmptyString`, which are always valid [`table`] keys,
+/// for `&str` / `&String` / `&TableKey`, which are checked at runtime (empty strings result in an [`error`]),
+/// and for string literals with precomputed hashes created via the [`key!`] macro (requires `"str_hash"` feature).
+///
+/// [`table`]: enum.Value.html#variant.Table
+/// [`tables`]: enum.Value.html#variant.Table
+/// [`error`]: struct.EmptyKeyError.html
+/// [`key!`]: macro.key.html
+pub trait IntoTableKey<'a> {
+    /// Returns the (non-empty) string [`table`](enum.Value.html#variant.Table) key,
+    /// or an [`error`](struct.EmptyKeyError.html) if the key is empty.
+    fn into_table_key(self) -> Result<&'a NonEmptyStr, EmptyKeyError>;
+
+    /// Returns the precomputed FNV1-a hash of the key string, if any.
+    #[doc(hidden)]
+    fn precomputed_hash(&self) -> Option<u32> {
+        None
+    }
+}
+
+impl<'a> IntoTableKey<'a> for &'a NonEmptyStr {
+    fn into_table_key(self) -> Result<&'a NonEmptyStr, EmptyKeyError> {
+        Ok(self)
+    }
+}
+
+impl<'a> IntoTableKey<'a> for &'a NonEmptyString {
+    fn into_table_key(self) -> Result<&'a NonEmptyStr, EmptyKeyError> {
+        Ok(self.as_ref())
+    }
+}
+
+impl<'a> IntoTableKey<'a> for &'a str {
+    fn into_table_key(self) -> Result<&'a NonEmptyStr, EmptyKeyError> {
+        NonEmptyStr::new(self).ok_or(EmptyKeyError)
+    }
+}
+
+impl<'a> IntoTableKey<'a> for &'a String {
+    fn into_table_key(self) -> Result<&'a NonEmptyStr, EmptyKeyError> {
+        self.as_str().into_table_key()
+    }
+}
+
+impl<'a> IntoTableKey<'a> for &'a TableKey<'_> {
+    fn into_table_key(self) -> Result<&'a NonEmptyStr, EmptyKeyError> {
+        self.as_str().into_table_key()
+    }
+
+    fn precomputed_hash(&self) -> Option<u32> {
+        match self {
+            TableKey::String(_) => None,
+            #[cfg(all(
+                feature = "str_hash",
+                any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+            ))]
+            TableKey::StringAndHash(StringAndHash { hash, .. }) => Some(*hash),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "str_hash",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
+impl<'a> IntoTableKey<'a> for StringAndHash {
+    fn into_table_key(self) -> Result<&'a NonEmptyStr, EmptyKeyError> {
+        Ok(self.string)
+    }
+
+    fn precomputed_hash(&self) -> Option<u32> {
+        Some(self.hash)
+    }
+}
+
+/// An optional (non-empty) string [`table`] key argument of the methods which add values to config [`arrays`] / [`tables`]
+/// (e.g. the [`binary config writer`] methods).
+///
+/// Implemented for all [`table key`] arguments and for `Option<&NonEmptyStr>` (`None` for [`array`] elements).
+///
+/// [`table`]: enum.Value.html#variant.Table
+/// [`arrays`]: enum.Value.html#variant.Array
+/// [`tables`]: enum.Value.html#variant.Table
+/// [`binary config writer`]: struct.BinConfigWriter.html
+/// [`table key`]: trait.IntoTableKey.html
+/// [`array`]: enum.Value.html#variant.Array
+pub trait IntoOptionalTableKey<'a> {
+    /// Returns the optional (non-empty) string [`table`](enum.Value.html#variant.Table) key,
+    /// or an [`error`](struct.EmptyKeyError.html) if the key is empty.
+    fn into_optional_table_key(self) -> Result<Option<&'a NonEmptyStr>, EmptyKeyError>;
+}
+
+impl<'a> IntoOptionalTableKey<'a> for Option<&'a NonEmptyStr> {
+    fn into_optional_table_key(self) -> Result<Option<&'a NonEmptyStr>, EmptyKeyError> {
+        Ok(self)
+    }
+}
+
+impl<'a> IntoOptionalTableKey<'a> for &'a NonEmptyStr {
+    fn into_optional_table_key(self) -> Result<Option<&'a NonEmptyStr>, EmptyKeyError> {
+        Ok(Some(self))
+    }
+}
+
+impl<'a> IntoOptionalTableKey<'a> for &'a NonEmptyString {
+    fn into_optional_table_key(self) -> Result<Option<&'a NonEmptyStr>, EmptyKeyError> {
+        self.into_table_key().map(Some)
+    }
+}
+
+impl<'a> IntoOptionalTableKey<'a> for &'a str {
+    fn into_optional_table_key(self) -> Result<Option<&'a NonEmptyStr>, EmptyKeyError> {
+        self.into_table_key().map(Some)
+    }
+}
+
+impl<'a> IntoOptionalTableKey<'a> for &'a String {
+    fn into_optional_table_key(self) -> Result<Option<&'a NonEmptyStr>, EmptyKeyError> {
+        self.into_table_key().map(Some)
+    }
+}
+
 impl Display for OwnedConfigKey {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
@@ -253,13 +514,99 @@ impl Display for OwnedConfigKey {
 /// Describes the full path to a config element.
 /// Empty path means the root table.
 /// Used in error reporting by config accessors and parsers.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
 pub struct ConfigPath(pub Vec<OwnedConfigKey>);
 
 impl ConfigPath {
-    pub(crate) fn new() -> Self {
+    /// Creates a new empty [`config path`] (i.e. the path to the root [`table`] / [`array`]).
+    ///
+    /// [`config path`]: struct.ConfigPath.html
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`array`]: enum.Value.html#variant.Array
+    pub fn new() -> Self {
         Self(Vec::new())
     }
+
+    /// Returns the number of [`config keys`](enum.OwnedConfigKey.html) in the path.
+    pub fn len(&self) -> u32 {
+        self.0.len() as _
+    }
+
+    /// Returns `true` if the path is empty (i.e. it is the path to the root [`table`] / [`array`]).
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`array`]: enum.Value.html#variant.Array
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the last [`config key`](enum.OwnedConfigKey.html) in the path, if any.
+    pub fn last(&self) -> Option<&OwnedConfigKey> {
+        self.0.last()
+    }
+
+    /// Appends the [`config key`](enum.OwnedConfigKey.html) to the path.
+    pub fn push<K: Into<OwnedConfigKey>>(&mut self, key: K) {
+        self.0.push(key.into())
+    }
+
+    /// Removes and returns the last [`config key`](enum.OwnedConfigKey.html) in the path, if any.
+    pub fn pop(&mut self) -> Option<OwnedConfigKey> {
+        self.0.pop()
+    }
+
+    /// Returns a new path with the (relative) `path` appended to this path.
+    pub fn join(&self, path: &ConfigPath) -> ConfigPath {
+        let mut result = self.clone();
+        result.0.extend(path.0.iter().cloned());
+        result
+    }
+
+    /// Returns the path to the parent [`table`] / [`array`] of the element at this path,
+    /// or `None` if the path is empty (i.e. it is the path to the root [`table`] / [`array`]).
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`array`]: enum.Value.html#variant.Array
+    pub fn parent(&self) -> Option<ConfigPath> {
+        self.0.split_last().map(|(_, parent)| Self(parent.to_vec()))
+    }
+
+    /// Returns `true` if the `prefix` path is a prefix of (or is equal to) this path,
+    /// i.e. if the element at this path is (nested in) the element at the `prefix` path.
+    pub fn starts_with(&self, prefix: &ConfigPath) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+
+    /// Returns an iterator over the [`config keys`] in the path,
+    /// suitable for passing to the config `*_path` accessors.
+    ///
+    /// [`config keys`]: enum.ConfigKey.html
+    pub fn iter(&self) -> ConfigPathIter<'_> {
+        self.0.iter().map(config_key as _)
+    }
+}
+
+fn config_key(key: &OwnedConfigKey) -> ConfigKey<'_> {
+    match key {
+        OwnedConfigKey::Table(key) => key.as_str().into(),
+        OwnedConfigKey::Array(index) => (*index).into(),
+    }
+}
+
+/// Iterator over the [`config keys`] in the [`config path`].
+///
+/// [`config keys`]: enum.ConfigKey.html
+/// [`config path`]: struct.ConfigPath.html
+pub type ConfigPathIter<'a> =
+    Map<std::slice::Iter<'a, OwnedConfigKey>, fn(&'a OwnedConfigKey) -> ConfigKey<'a>>;
+
+impl<'a> IntoIterator for &'a ConfigPath {
+    type Item = ConfigKey<'a>;
+    type IntoIter = ConfigPathIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl From<Vec<OwnedConfigKey>> for ConfigPath {
@@ -268,20 +615,681 @@ impl From<Vec<OwnedConfigKey>> for ConfigPath {
     }
 }
 
+/// Formats the [`config path`] as a dotted path string (e.g. `"render.window.size[2]"`),
+/// which may be [`parsed`] back to the same [`config path`].
+///
+/// Periods, square brackets and backslashes in the [`table keys`] are escaped with a backslash.
+/// Empty path (i.e. the root [`table`] / [`array`]) is formatted as an empty string.
+///
+/// [`config path`]: struct.ConfigPath.html
+/// [`parsed`]: struct.ConfigPath.html#impl-FromStr
+/// [`table keys`]: enum.ConfigKey.html#variant.Table
+/// [`table`]: enum.Value.html#variant.Table
+/// [`array`]: enum.Value.html#variant.Array
 impl Display for ConfigPath {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        for (key_index, key) in self.0.iter().enumerate() {
+            match key {
+                OwnedConfigKey::Table(key) => {
+                    if key_index > 0 {
+                        '.'.fmt(f)?;
+                    }
+
+                    for c in key.as_str().chars() {
+                        if is_path_special_char(c) {
+                            '\\'.fmt(f)?;
+                        }
+
+                        c.fmt(f)?;
+                    }
+                }
+                OwnedConfigKey::Array(index) => write!(f, "[{}]", index)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the [`config path`] for error messages - as `<root>` if it is empty.
+///
+/// [`config path`]: struct.ConfigPath.html
+pub(crate) struct PathOrRoot<'p>(pub(crate) &'p ConfigPath);
+
+impl<'p> Display for PathOrRoot<'p> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         if self.0.is_empty() {
             "<root>".fmt(f)
         } else {
-            for (key_index, key) in self.0.iter().enumerate() {
-                key.fmt(f)?;
+            self.0.fmt(f)
+        }
+    }
+}
+
+/// An error returned when [`parsing`] a [`config path`] from a string.
+///
+/// [`parsing`]: struct.ConfigPath.html#impl-FromStr
+/// [`config path`]: struct.ConfigPath.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ParseConfigPathError {
+    /// Encountered an empty [`table key`] (e.g. `"foo..bar"`).
+    /// Contains the byte offset of the empty key in the string.
+    ///
+    /// [`table key`]: enum.ConfigKey.html#variant.Table
+    EmptyKey(usize),
+    /// Encountered an unterminated or invalid [`array index`] (e.g. `"foo[bar]"`).
+    /// Contains the byte offset of the opening bracket in the string.
+    ///
+    /// [`array index`]: enum.ConfigKey.html#variant.Array
+    InvalidIndex(usize),
+    /// Encountered an unexpected character (e.g. `"foo[0]bar"`).
+    /// Contains the byte offset of the character in the string and the character itself.
+    UnexpectedCharacter(usize, char),
+    /// Encountered an unterminated or invalid escape sequence in a [`table key`] (e.g. `"foo\\bar"`).
+    /// Only `.`, `[`, `]` and `\\` may be escaped with a backslash (`\\`).
+    /// Contains the byte offset of the backslash in the string.
+    ///
+    /// [`table key`]: enum.ConfigKey.html#variant.Table
+    InvalidEscapeSequence(usize),
+}
+
+impl Error for ParseConfigPathError {}
+
+impl Display for ParseConfigPathError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use ParseConfigPathError::*;
 
-                if !key_index == (self.0.len() - 1) {
-                    '/'.fmt(f)?;
+        match self {
+            EmptyKey(offset) => write!(f, "empty table key at offset {}", offset),
+            InvalidIndex(offset) => write!(f, "invalid array index at offset {}", offset),
+            UnexpectedCharacter(offset, c) => {
+                write!(f, "unexpected character '{}' at offset {}", c, offset)
+            }
+            InvalidEscapeSequence(offset) => {
+                write!(f, "invalid escape sequence at offset {}", offset)
+            }
+        }
+    }
+}
+
+/// Parses a dotted [`config path`] string, e.g. `"render.window.size[2]"`.
+///
+/// (Non-empty) string [`table keys`] are separated by periods (`.`),
+/// (`0`-based) [`array indices`] are enclosed in square brackets (`[`, `]`) and follow the key of the array (if any).
+/// Periods, square brackets and backslashes in [`table keys`] are escaped with a backslash (e.g. `"foo\\.bar"` for the `"foo.bar"` key).
+/// The path may start with an array index (i.e. `"[0].foo"`) if it is used to access an [`array`].
+/// Empty string is parsed as an empty path (i.e. the root [`table`] / [`array`]).
+///
+/// This is the inverse of the [`config path`] `Display` implementation.
+///
+/// [`config path`]: struct.ConfigPath.html
+/// [`table keys`]: enum.ConfigKey.html#variant.Table
+/// [`array indices`]: enum.ConfigKey.html#variant.Array
+/// [`table`]: enum.Value.html#variant.Table
+/// [`array`]: enum.Value.html#variant.Array
+impl FromStr for ConfigPath {
+    type Err = ParseConfigPathError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut path = Vec::new();
+
+        parse_dotted_path(string, |part| {
+            match part {
+                DottedPathPart::Key(key) => path.push(key.into()),
+                DottedPathPart::Index(index) => path.push(parse_index(index)?.into()),
+            }
+
+            Some(())
+        })?;
+
+        Ok(Self(path))
+    }
+}
+
+/// Part of the dotted path string.
+enum DottedPathPart<'s> {
+    /// A (non-empty) unescaped table key.
+    Key(&'s NonEmptyStr),
+    /// Contents of the square brackets of the array index.
+    Index(&'s str),
+}
+
+/// Parses the (`0`-based) array `index` string, which must only contain ASCII digits.
+fn parse_index(index: &str) -> Option<u32> {
+    if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+        None
+    } else {
+        index.parse::<u32>().ok()
+    }
+}
+
+/// Parses the dotted path `string`, calling `f` for each of its parts.
+/// `f` returns `None` if the array index is invalid.
+fn parse_dotted_path<F>(string: &str, mut f: F) -> Result<(), ParseConfigPathError>
+where
+    F: FnMut(DottedPathPart<'_>) -> Option<()>,
+{
+    use ParseConfigPathError::*;
+
+    if string.is_empty() {
+        return Ok(());
+    }
+
+    let mut chars = string.char_indices().peekable();
+
+    // Unescaped current key, if it contains any escape sequences.
+    let mut unescaped = String::new();
+
+    for part_index in 0.. {
+        // Byte offset of the current part in the string.
+        let offset = chars.peek().map_or(string.len(), |&(offset, _)| offset);
+
+        let mut escaped = false;
+        unescaped.clear();
+
+        while let Some(&(char_offset, c)) = chars.peek() {
+            match c {
+                '.' | '[' => break,
+                ']' => return Err(UnexpectedCharacter(char_offset, c)),
+                '\\' => {
+                    chars.next();
+
+                    match chars.next() {
+                        Some((_, c)) if is_path_special_char(c) => {
+                            if !escaped {
+                                unescaped.push_str(&string[offset..char_offset]);
+                                escaped = true;
+                            }
+                            unescaped.push(c);
+                        }
+                        _ => return Err(InvalidEscapeSequence(char_offset)),
+                    }
+                }
+                _ => {
+                    chars.next();
+
+                    if escaped {
+                        unescaped.push(c);
+                    }
+                }
+            }
+        }
+
+        let key_end = chars.peek().map_or(string.len(), |&(offset, _)| offset);
+        let key = if escaped {
+            unescaped.as_str()
+        } else {
+            &string[offset..key_end]
+        };
+        let has_indices = matches!(chars.peek(), Some((_, '[')));
+
+        if let Some(key) = NonEmptyStr::new(key) {
+            // Keys are always valid.
+            let _ = f(DottedPathPart::Key(key));
+
+        // Only the first key may be empty, and only if it is followed by an array index.
+        } else if part_index > 0 || !has_indices {
+            return Err(EmptyKey(offset));
+        }
+
+        while let Some(&(index_offset, '[')) = chars.peek() {
+            let end = index_offset
+                + string[index_offset..]
+                    .find(']')
+                    .ok_or(InvalidIndex(index_offset))?;
+
+            f(DottedPathPart::Index(&string[index_offset + 1..end]))
+                .ok_or(InvalidIndex(index_offset))?;
+
+            // Skip the array index.
+            while let Some(&(offset, _)) = chars.peek() {
+                if offset > end {
+                    break;
+                }
+
+                chars.next();
+            }
+        }
+
+        match chars.next() {
+            None => break,
+            // Skip the separator.
+            Some((_, '.')) => {}
+            Some((offset, c)) => return Err(UnexpectedCharacter(offset, c)),
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the character `c` must be escaped with a backslash (`\\`) in the dotted path table keys.
+fn is_path_special_char(c: char) -> bool {
+    matches!(c, '.' | '[' | ']' | '\\')
+}
+
+/// String key pattern (in the [`table`]) or integer index pattern (in the [`array`]) of a [`config path pattern`].
+///
+/// [`table`]: enum.Value.html#variant.Table
+/// [`array`]: enum.Value.html#variant.Array
+/// [`config path pattern`]: struct.ConfigPathPattern.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ConfigPathPatternKey {
+    /// A string [`table`] key glob pattern.
+    /// `*` matches any (possibly empty) sequence of characters, `?` matches any single character;
+    /// all other characters match themselves.
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    Table(NonEmptyString),
+    /// A (`0`-based) integer [`array`] index, or `None` to match any index.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    Array(Option<u32>),
+}
+
+impl ConfigPathPatternKey {
+    /// Returns `true` if the pattern matches the [`table`] `key`.
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    pub fn matches_key(&self, key: &str) -> bool {
+        match self {
+            Self::Table(pattern) => glob_match(pattern.as_str(), key),
+            Self::Array(_) => false,
+        }
+    }
+
+    /// Returns `true` if the pattern matches the [`array`] `index`.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    pub fn matches_index(&self, index: u32) -> bool {
+        match self {
+            Self::Table(_) => false,
+            Self::Array(pattern) => pattern.is_none() || (*pattern == Some(index)),
+        }
+    }
+}
+
+/// A key of a path / path pattern used to select config elements,
+/// implemented by [`config path pattern`] keys (matching via wildcards) and [`config path`] keys (matching exactly).
+///
+/// [`config path pattern`]: struct.ConfigPathPattern.html
+/// [`config path`]: struct.ConfigPath.html
+#[cfg(any(feature = "bin", feature = "dyn"))]
+pub(crate) trait ConfigPathMatch {
+    /// Returns `true` if the key matches the [`table`] `key`.
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    fn matches_key(&self, key: &str) -> bool;
+
+    /// Returns `true` if the key matches the [`array`] `index`.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    fn matches_index(&self, index: u32) -> bool;
+}
+
+#[cfg(any(feature = "bin", feature = "dyn"))]
+impl ConfigPathMatch for ConfigPathPatternKey {
+    fn matches_key(&self, key: &str) -> bool {
+        ConfigPathPatternKey::matches_key(self, key)
+    }
+
+    fn matches_index(&self, index: u32) -> bool {
+        ConfigPathPatternKey::matches_index(self, index)
+    }
+}
+
+#[cfg(any(feature = "bin", feature = "dyn"))]
+impl ConfigPathMatch for OwnedConfigKey {
+    fn matches_key(&self, key: &str) -> bool {
+        match self {
+            Self::Table(table_key) => table_key.as_str() == key,
+            Self::Array(_) => false,
+        }
+    }
+
+    fn matches_index(&self, index: u32) -> bool {
+        match self {
+            Self::Table(_) => false,
+            Self::Array(array_index) => *array_index == index,
+        }
+    }
+}
+
+/// Returns the remaining suffixes of the `paths` / path patterns whose first key `matches`.
+#[cfg(any(feature = "bin", feature = "dyn"))]
+pub(crate) fn sub_patterns<'p, K, F>(paths: &[&'p [K]], matches: F) -> Vec<&'p [K]>
+where
+    F: Fn(&K) -> bool,
+{
+    paths
+        .iter()
+        .filter_map(|path| {
+            path.split_first()
+                .filter(|(first, _)| matches(first))
+                .map(|(_, rest)| rest)
+        })
+        .collect()
+}
+
+/// Returns `true` if the glob `pattern` matches the `string`.
+/// `*` in the `pattern` matches any (possibly empty) sequence of characters, `?` matches any single character.
+fn glob_match(pattern: &str, string: &str) -> bool {
+    let pattern: Vec<_> = pattern.chars().collect();
+    let string: Vec<_> = string.chars().collect();
+
+    let (mut p, mut s) = (0, 0);
+
+    // Pattern / string positions to backtrack to after the last encountered `*`.
+    let mut backtrack = None;
+
+    while s < string.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, s));
+                p += 1;
+            }
+            Some(&c) if (c == '?') || (c == string[s]) => {
+                p += 1;
+                s += 1;
+            }
+            _ => {
+                if let Some((star_p, star_s)) = backtrack {
+                    // Let the last `*` match one more character.
+                    p = star_p + 1;
+                    s = star_s + 1;
+                    backtrack = Some((star_p, star_s + 1));
+                } else {
+                    return false;
                 }
             }
+        }
+    }
+
+    // Any trailing `*`'s match the empty string.
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A [`config path`] pattern, used to select multiple config elements.
+///
+/// Parsed from the same dotted path strings as the [`config path`] (e.g. `"render.*.size[*]"`),
+/// where [`table key patterns`] may contain glob wildcards (`*`, `?`),
+/// and [`array index patterns`] may be `*` to match any index.
+///
+/// [`config path`]: struct.ConfigPath.html
+/// [`table key patterns`]: enum.ConfigPathPatternKey.html#variant.Table
+/// [`array index patterns`]: enum.ConfigPathPatternKey.html#variant.Array
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConfigPathPattern(pub Vec<ConfigPathPatternKey>);
+
+impl From<Vec<ConfigPathPatternKey>> for ConfigPathPattern {
+    fn from(pattern: Vec<ConfigPathPatternKey>) -> Self {
+        Self(pattern)
+    }
+}
+
+impl FromStr for ConfigPathPattern {
+    type Err = ParseConfigPathError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut pattern = Vec::new();
+
+        parse_dotted_path(string, |part| {
+            pattern.push(match part {
+                DottedPathPart::Key(key) => ConfigPathPatternKey::Table(key.into()),
+                DottedPathPart::Index("*") => ConfigPathPatternKey::Array(None),
+                DottedPathPart::Index(index) => {
+                    ConfigPathPatternKey::Array(Some(parse_index(index)?))
+                }
+            });
 
-            Ok(())
+            Some(())
+        })?;
+
+        Ok(Self(pattern))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    #[cfg(all(feature = "str_hash", feature = "bin", feature = "dyn"))]
+    #[test]
+    fn key_hash() {
+        let speed = key_hash!("player/speed");
+
+        assert_eq!(speed, key!("player/speed"));
+        assert_eq!(speed, key_hash!("player/speed", 0xe737_b848));
+        assert_eq!(speed.string.as_str(), "player/speed");
+
+        let mut config = DynConfig::new();
+        config.root_mut().set(nestr!("player/speed"), 7.62);
+
+        let config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(config.root().get_f64(speed).unwrap(), 7.62);
         }
     }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("".parse::<ConfigPath>().unwrap(), ConfigPath::new());
+        assert_eq!(
+            "foo".parse::<ConfigPath>().unwrap(),
+            vec![nestr!("foo").into()].into()
+        );
+        assert_eq!(
+            "render.window.size[2]".parse::<ConfigPath>().unwrap(),
+            vec![
+                nestr!("render").into(),
+                nestr!("window").into(),
+                nestr!("size").into(),
+                2.into()
+            ]
+            .into()
+        );
+        assert_eq!(
+            "[0][1].foo[7]".parse::<ConfigPath>().unwrap(),
+            vec![0.into(), 1.into(), nestr!("foo").into(), 7.into()].into()
+        );
+
+        assert_eq!(
+            ".foo".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::EmptyKey(0)
+        );
+        assert_eq!(
+            "foo..bar".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::EmptyKey(4)
+        );
+        assert_eq!(
+            "foo.".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::EmptyKey(4)
+        );
+        assert_eq!(
+            "foo.[0]".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::EmptyKey(4)
+        );
+        assert_eq!(
+            "foo[bar]".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::InvalidIndex(3)
+        );
+        assert_eq!(
+            "foo.bar[0".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::InvalidIndex(7)
+        );
+        assert_eq!(
+            "foo[]".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::InvalidIndex(3)
+        );
+        assert_eq!(
+            "foo[4294967296]".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::InvalidIndex(3)
+        );
+        assert_eq!(
+            "foo[0]bar".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::UnexpectedCharacter(6, 'b')
+        );
+        assert_eq!(
+            "foo]".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::UnexpectedCharacter(3, ']')
+        );
+
+        // Escaped keys.
+        assert_eq!(
+            r"foo\.bar.b\[a\]z\\".parse::<ConfigPath>().unwrap(),
+            vec![nestr!("foo.bar").into(), nestr!("b[a]z\\").into()].into()
+        );
+        assert_eq!(
+            r"\.[0]".parse::<ConfigPath>().unwrap(),
+            vec![nestr!(".").into(), 0.into()].into()
+        );
+        assert_eq!(
+            r"foo.b\ar".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::InvalidEscapeSequence(5)
+        );
+        assert_eq!(
+            r"foo\".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::InvalidEscapeSequence(3)
+        );
+    }
+
+    #[test]
+    fn display() {
+        let round_trip = |string: &str| {
+            assert_eq!(string.parse::<ConfigPath>().unwrap().to_string(), string);
+        };
+
+        round_trip("");
+        round_trip("foo");
+        round_trip("render.window.size[2]");
+        round_trip("[0][1].foo[7]");
+        round_trip(r"foo\.bar.b\[a\]z\\[3]");
+        round_trip("foo/bar.ёж");
+
+        let path: ConfigPath = vec![
+            nestr!("a.b").into(),
+            0.into(),
+            nestr!("[c]").into(),
+            nestr!("d\\").into(),
+        ]
+        .into();
+
+        assert_eq!(path.to_string(), r"a\.b[0].\[c\].d\\");
+        assert_eq!(path.to_string().parse::<ConfigPath>().unwrap(), path);
+    }
+
+    #[test]
+    fn join_parent_starts_with() {
+        let path: ConfigPath = "render.window".parse().unwrap();
+        let size: ConfigPath = "size[1]".parse().unwrap();
+
+        let joined = path.join(&size);
+
+        assert_eq!(joined, "render.window.size[1]".parse().unwrap());
+        assert_eq!(joined.len(), 4);
+        assert_eq!(joined.last(), Some(&1.into()));
+
+        assert!(joined.starts_with(&path));
+        assert!(joined.starts_with(&joined));
+        assert!(joined.starts_with(&ConfigPath::new()));
+        assert!(!path.starts_with(&joined));
+        assert!(!joined.starts_with(&"render.win".parse().unwrap()));
+
+        assert_eq!(
+            joined.parent().unwrap(),
+            "render.window.size".parse().unwrap()
+        );
+        assert_eq!(path.parent().unwrap().parent().unwrap(), ConfigPath::new());
+        assert!(ConfigPath::new().parent().is_none());
+        assert!(ConfigPath::new().is_empty());
+
+        let mut path = ConfigPath::new();
+        path.push(nestr!("foo"));
+        path.push(3);
+        assert_eq!(path, "foo[3]".parse().unwrap());
+        assert_eq!(path.pop(), Some(3.into()));
+        assert_eq!(path, "foo".parse().unwrap());
+    }
+
+    #[test]
+    fn glob_match() {
+        use super::glob_match;
+
+        assert!(glob_match("foo", "foo"));
+        assert!(!glob_match("foo", "fo"));
+        assert!(!glob_match("foo", "fooo"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "foo"));
+        assert!(glob_match("f*", "foo"));
+        assert!(glob_match("*o", "foo"));
+        assert!(glob_match("f*o", "fo"));
+        assert!(glob_match("f*o*r", "foobar"));
+        assert!(!glob_match("f*o*z", "foobar"));
+        assert!(glob_match("f?o", "foo"));
+        assert!(!glob_match("f?o", "fo"));
+        assert!(glob_match("??", "ёж"));
+        assert!(glob_match("*.ini", "foo.bar.ini"));
+    }
+
+    #[test]
+    fn ConfigPathPattern_from_str() {
+        assert_eq!(
+            "render.*.size[*][1]".parse::<ConfigPathPattern>().unwrap(),
+            vec![
+                ConfigPathPatternKey::Table(nestr!("render").into()),
+                ConfigPathPatternKey::Table(nestr!("*").into()),
+                ConfigPathPatternKey::Table(nestr!("size").into()),
+                ConfigPathPatternKey::Array(None),
+                ConfigPathPatternKey::Array(Some(1)),
+            ]
+            .into()
+        );
+        assert_eq!(
+            "foo[?]".parse::<ConfigPathPattern>().err().unwrap(),
+            ParseConfigPathError::InvalidIndex(3)
+        );
+        assert_eq!(
+            "*[*]".parse::<ConfigPath>().err().unwrap(),
+            ParseConfigPathError::InvalidIndex(1)
+        );
+
+        let pattern: ConfigPathPattern = "f?o*[*]".parse().unwrap();
+
+        assert!(pattern.0[0].matches_key("foobar"));
+        assert!(!pattern.0[0].matches_key("bar"));
+        assert!(!pattern.0[0].matches_index(0));
+        assert!(pattern.0[1].matches_index(7));
+        assert!(!pattern.0[1].matches_key("foo"));
+    }
+
+    #[test]
+    fn OwnedConfigKey_try_from() {
+        use std::convert::TryFrom;
+
+        assert_eq!(
+            OwnedConfigKey::try_from("foo").unwrap(),
+            nestr!("foo").into()
+        );
+        assert_eq!(
+            OwnedConfigKey::try_from(String::from("bar")).unwrap(),
+            nestr!("bar").into()
+        );
+        assert_eq!(OwnedConfigKey::try_from("").err().unwrap(), EmptyKeyError);
+
+        assert_eq!("foo".into_table_key().unwrap(), nestr!("foo"));
+        assert_eq!("".into_table_key().err().unwrap(), EmptyKeyError);
+    }
+
+    #[test]
+    fn iter() {
+        let path: ConfigPath = "foo[1].bar".parse().unwrap();
+
+        assert_eq!(
+            path.iter().collect::<Vec<_>>(),
+            vec!["foo".into(), 1.into(), "bar".into()]
+        );
+    }
 }
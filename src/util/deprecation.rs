@@ -0,0 +1,222 @@
+use {
+    crate::*,
+    std::fmt::{Display, Formatter},
+};
+
+/// A deprecated config value [`path`] and the [`path`] of its replacement, if any,
+/// registered in the [`deprecation registry`].
+///
+/// [`path`]: struct.ConfigPath.html
+/// [`deprecation registry`]: struct.ConfigDeprecations.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConfigDeprecation {
+    /// Path to the deprecated value (or [`table`] / [`array`]), relative to the root [`table`].
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`array`]: enum.Value.html#variant.Array
+    pub path: ConfigPath,
+    /// Path to the value which replaces the deprecated value, if any, relative to the root [`table`].
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    pub replacement: Option<ConfigPath>,
+}
+
+/// A registry of deprecated config value [`paths`], used to emit [`warnings`] (not errors)
+/// for the deprecated values present in the configs,
+/// e.g. during a migration period in which the old values are still supported, but the users are told to move.
+///
+/// [`paths`]: struct.ConfigPath.html
+/// [`warnings`]: struct.ConfigWarning.html
+#[derive(Clone, Default, Debug)]
+pub struct ConfigDeprecations(Vec<ConfigDeprecation>);
+
+impl ConfigDeprecations {
+    /// Creates a new empty [`deprecation registry`](struct.ConfigDeprecations.html).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the deprecated value at `path`, optionally replaced by the value at `replacement` path.
+    pub fn add(&mut self, path: ConfigPath, replacement: Option<ConfigPath>) {
+        self.0.push(ConfigDeprecation { path, replacement });
+    }
+
+    /// Returns the number of registered [`deprecations`](struct.ConfigDeprecation.html).
+    pub fn len(&self) -> u32 {
+        self.0.len() as _
+    }
+
+    /// Returns `true` if no [`deprecations`](struct.ConfigDeprecation.html) were registered.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the registered [`deprecations`](struct.ConfigDeprecation.html), in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &ConfigDeprecation> {
+        self.0.iter()
+    }
+
+    /// Calls the `warn` callback with a [`warning`] for each registered deprecated value present in the `config`,
+    /// in registration order.
+    ///
+    /// The [`warnings`] have no source location.
+    ///
+    /// [`warning`]: struct.ConfigWarning.html
+    /// [`warnings`]: struct.ConfigWarning.html
+    pub fn check_with<C: Config, F: FnMut(ConfigWarning)>(&self, config: &C, mut warn: F) {
+        let root = config.root();
+
+        for deprecation in self.0.iter() {
+            if contains_path(&root, &deprecation.path.0) {
+                warn(ConfigWarning {
+                    path: deprecation.path.clone(),
+                    kind: ConfigWarningKind::Deprecated {
+                        replacement: deprecation.replacement.clone(),
+                    },
+                    location: None,
+                });
+            }
+        }
+    }
+
+    /// Returns the [`warnings`] for the registered deprecated values present in the `config`, in registration order.
+    ///
+    /// The [`warnings`] have no source location.
+    ///
+    /// [`warnings`]: struct.ConfigWarning.html
+    pub fn check<C: Config>(&self, config: &C) -> Vec<ConfigWarning> {
+        let mut warnings = Vec::new();
+        self.check_with(config, |warning| warnings.push(warning));
+        warnings
+    }
+}
+
+/// Describes the cause of a [`config warning`](struct.ConfigWarning.html).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ConfigWarningKind {
+    /// The value is [`deprecated`](struct.ConfigDeprecations.html).
+    Deprecated {
+        /// Path to the value which replaces the deprecated value, if any, relative to the root [`table`].
+        ///
+        /// [`table`]: enum.Value.html#variant.Table
+        replacement: Option<ConfigPath>,
+    },
+}
+
+/// A structured warning about a config value which, unlike errors, does not prevent the config from being used,
+/// e.g. for the values registered as [`deprecated`].
+///
+/// [`deprecated`]: struct.ConfigDeprecations.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConfigWarning {
+    /// Path to the value, relative to the root [`table`].
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    pub path: ConfigPath,
+    /// Cause of the warning.
+    pub kind: ConfigWarningKind,
+    /// (`1`-based) line and column of the value's key in the source the config was parsed from, if known.
+    pub location: Option<(u32, u32)>,
+}
+
+impl Display for ConfigWarning {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", PathOrRoot(&self.path))?;
+
+        if let Some((line, column)) = self.location {
+            write!(f, " (line {}, column {})", line, column)?;
+        }
+
+        ": ".fmt(f)?;
+
+        match &self.kind {
+            ConfigWarningKind::Deprecated { replacement } => {
+                "value is deprecated".fmt(f)?;
+
+                if let Some(replacement) = replacement {
+                    write!(f, ", use \"{}\" instead", replacement)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns `true` if the `table` contains a value at `path`.
+fn contains_path<T: TableAccess>(table: &T, path: &[OwnedConfigKey]) -> bool {
+    match path.split_first() {
+        None => true,
+        Some((OwnedConfigKey::Table(key), path)) => match table.get_val(key.as_str()) {
+            Some(value) => value_contains_path(value, path),
+            None => false,
+        },
+        Some((OwnedConfigKey::Array(_), _)) => false,
+    }
+}
+
+fn value_contains_path<S, A: ArrayAccess<Table = T>, T: TableAccess>(
+    value: Value<S, A, T>,
+    path: &[OwnedConfigKey],
+) -> bool {
+    match (value, path.first()) {
+        (_, None) => true,
+        (Value::Table(table), Some(OwnedConfigKey::Table(_))) => contains_path(&table, path),
+        (Value::Array(array), Some(OwnedConfigKey::Array(index))) => match array.get_val(*index) {
+            Ok(value) => value_contains_path(value, &path[1..]),
+            Err(_) => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(all(test, feature = "dyn"))]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::*;
+
+    #[test]
+    fn check() {
+        let path = |path: &str| -> ConfigPath { path.parse().unwrap() };
+
+        let mut deprecations = ConfigDeprecations::new();
+        deprecations.add(path("width"), Some(path("window.width")));
+        deprecations.add(path("levels[1].music"), None);
+        deprecations.add(path("fullscreen"), None);
+        assert_eq!(deprecations.len(), 3);
+
+        let config = dyn_config! {
+            width: 1920,
+            window: { width: 1920 },
+            levels: [{ name: "a" }, { name: "b", music: "b.ogg" }],
+        };
+
+        let warnings = deprecations.check(&config);
+        assert_eq!(
+            warnings,
+            vec![
+                ConfigWarning {
+                    path: path("width"),
+                    kind: ConfigWarningKind::Deprecated {
+                        replacement: Some(path("window.width"))
+                    },
+                    location: None,
+                },
+                ConfigWarning {
+                    path: path("levels[1].music"),
+                    kind: ConfigWarningKind::Deprecated { replacement: None },
+                    location: None,
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "width: value is deprecated, use \"window.width\" instead"
+        );
+
+        let mut num_warnings = 0;
+        deprecations.check_with(&config, |_| num_warnings += 1);
+        assert_eq!(num_warnings, 2);
+    }
+}
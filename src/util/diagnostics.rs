@@ -0,0 +1,115 @@
+use {
+    crate::*,
+    std::{
+        cell::RefCell,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// A soft failure of a config value access, recorded by the table `try_get_*` accessors
+/// in the [`diagnostics sink`].
+///
+/// [`diagnostics sink`]: struct.ConfigDiagnostics.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConfigDiagnostic {
+    /// Path to the accessed value, relative to the accessed table.
+    pub path: ConfigPath,
+    /// Requested value [`type`](enum.ValueType.html).
+    pub expected: ValueType,
+    /// Actual value [`type`](enum.ValueType.html), or `None` if the value does not exist.
+    pub found: Option<ValueType>,
+}
+
+impl Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if let Some(found) = self.found {
+            write!(
+                f,
+                "{}: value is of incorrect and incompatible type (expected {}, found {})",
+                PathOrRoot(&self.path),
+                self.expected,
+                found
+            )
+        } else {
+            write!(
+                f,
+                "{}: value does not exist (expected {})",
+                PathOrRoot(&self.path),
+                self.expected
+            )
+        }
+    }
+}
+
+/// A sink for [`diagnostics`] recorded by the table `try_get_*` accessors
+/// (e.g. [`DynTable::try_get_i64`]), which return `None` instead of an error on failure.
+///
+/// Intended to be created once per config and passed to all its `try_get_*` accessors,
+/// so that the accessing code may use default values for missing / invalid config values
+/// and log the recorded diagnostics later.
+///
+/// Uses interior mutability, so the same sink may be passed to accessors
+/// of multiple tables borrowed from the config at the same time.
+///
+/// [`diagnostics`]: struct.ConfigDiagnostic.html
+/// [`DynTable::try_get_i64`]: struct.DynTable.html#method.try_get_i64
+#[derive(Default, Debug)]
+pub struct ConfigDiagnostics(RefCell<Vec<ConfigDiagnostic>>);
+
+impl ConfigDiagnostics {
+    /// Creates a new empty [`diagnostics sink`](struct.ConfigDiagnostics.html).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of recorded [`diagnostics`](struct.ConfigDiagnostic.html).
+    pub fn len(&self) -> u32 {
+        self.0.borrow().len() as _
+    }
+
+    /// Returns `true` if no [`diagnostics`](struct.ConfigDiagnostic.html) were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().is_empty()
+    }
+
+    /// Removes and returns the recorded [`diagnostics`](struct.ConfigDiagnostic.html), in order.
+    pub fn take(&self) -> Vec<ConfigDiagnostic> {
+        self.0.replace(Vec::new())
+    }
+
+    /// Clears the recorded [`diagnostics`](struct.ConfigDiagnostic.html).
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+
+    /// Converts the `result` of the table value access with the `key` of the `expected` type to an `Option`,
+    /// recording the diagnostic in the `diagnostics` sink, if any, on failure.
+    pub(crate) fn try_get<V>(
+        diagnostics: Option<&Self>,
+        key: &str,
+        expected: ValueType,
+        result: Result<V, TableError>,
+    ) -> Option<V> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                if let Some(diagnostics) = diagnostics {
+                    diagnostics.0.borrow_mut().push(ConfigDiagnostic {
+                        path: NonEmptyStr::new(key)
+                            .map(|key| vec![key.into()])
+                            .unwrap_or_default()
+                            .into(),
+                        expected,
+                        found: match err {
+                            TableError::KeyDoesNotExist => None,
+                            TableError::IncorrectValueType(found) => Some(found),
+                            TableError::ValueOutOfRange(_) => Some(ValueType::I64),
+                        },
+                    });
+                }
+
+                None
+            }
+        }
+    }
+}
@@ -1,23 +1,196 @@
 use {crate::*, std::fmt::Write};
 
-pub(crate) trait DisplayLua {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result;
+/// Configuration options for serializing a config to a Lua script string.
+#[derive(Clone, Copy, Debug)]
+pub struct ToLuaStringOptions {
+    /// If `true`, arrays of [`bool`]'s / [`i64`]'s / [`f64`]'s are serialized in compact form,
+    /// i.e. multiple comma-separated values per line (see [`compact_array_wrap`](#structfield.compact_array_wrap)).
+    /// Arrays of other values and tables are always serialized in block form, one value per line.
+    ///
+    /// If `false`, all arrays are serialized in block form.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub compact_arrays: bool,
+    /// Maximum number of values per line in arrays serialized in compact form.
+    /// Arrays with fewer values are serialized on a single line;
+    /// longer arrays are wrapped over multiple lines.
+    ///
+    /// If `0`, compact arrays are never wrapped.
+    ///
+    /// Default: `16`.
+    pub compact_array_wrap: u32,
+    /// Output style options.
+    ///
+    /// Default: default [`formatter`](struct.ConfigFormatter.html).
+    pub formatter: ConfigFormatter,
+}
+
+impl Default for ToLuaStringOptions {
+    fn default() -> Self {
+        Self {
+            compact_arrays: false,
+            compact_array_wrap: 16,
+            formatter: ConfigFormatter::default(),
+        }
+    }
+}
+
+impl ToLuaStringOptions {
+    /// Returns the canonical serialization options, used to serialize configs to Lua script strings reproducibly
+    /// (e.g. to hash the serialized configs): all arrays are serialized in block form
+    /// with the [`canonical`](struct.ConfigFormatter.html#method.canonical) output style.
+    ///
+    /// Table keys are always serialized in sorted order, so logically equal configs
+    /// are always serialized to identical strings with these options, regardless of the config backend.
+    pub fn canonical() -> Self {
+        Self {
+            compact_arrays: false,
+            compact_array_wrap: 16,
+            formatter: ConfigFormatter::canonical(),
+        }
+    }
+}
+
+/// A `std::fmt::Write` writer which calculates the 64-bit FNV-1a hash of the written string,
+/// used to hash the configs serialized to Lua script strings without an intermediate string.
+pub(crate) struct ContentHasher(u64);
+
+impl ContentHasher {
+    pub(crate) fn new() -> Self {
+        Self(0xCBF2_9CE4_8422_2325)
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
 
-    fn do_indent<W: Write>(w: &mut W, indent: u32) -> std::fmt::Result {
-        for _ in 0..indent {
-            w.write_char('\t')?;
+impl Write for ContentHasher {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        const FNV1A_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+        for byte in s.as_bytes() {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(FNV1A_PRIME);
         }
 
         Ok(())
     }
 }
 
-/// Writes the `string` to the writer `w`, enclosing it in quotes and escaping special characters
-/// ('\\', '\0', '\a', '\b', '\t', '\n', '\r', '\v', '\f') and double quotes ('"').
-pub(crate) fn write_lua_string<W: Write>(w: &mut W, string: &str) -> std::fmt::Result {
-    w.write_char('"')?;
+pub(crate) trait DisplayLua {
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result;
+}
+
+/// Returns `true` if the array with values of `value_type` (or `None` if the array is empty)
+/// must be serialized in compact form, according to `options`.
+pub(crate) fn is_compact_lua_array(
+    value_type: Option<ValueType>,
+    options: ToLuaStringOptions,
+) -> bool {
+    options.compact_arrays
+        && matches!(
+            value_type,
+            Some(ValueType::Bool) | Some(ValueType::I64) | Some(ValueType::F64)
+        )
+}
+
+/// Writes the array `values` (of length `len`) in compact form to the writer `w`.
+/// The caller guarantees `values` are [`bool`]'s / [`i64`]'s / [`f64`]'s.
+///
+/// [`bool`]: enum.Value.html#variant.Bool
+/// [`i64`]: enum.Value.html#variant.I64
+/// [`f64`]: enum.Value.html#variant.F64
+pub(crate) fn write_compact_lua_array<W, V, I>(
+    w: &mut W,
+    values: I,
+    len: u32,
+    indent: u32,
+    options: ToLuaStringOptions,
+) -> std::fmt::Result
+where
+    W: Write,
+    V: DisplayLua,
+    I: Iterator<Item = V>,
+{
+    let wrap = options.compact_array_wrap;
+
+    // Short array - write on a single line.
+    if wrap == 0 || len <= wrap {
+        w.write_str("{ ")?;
+
+        for (index, value) in values.enumerate() {
+            if index > 0 {
+                w.write_str(", ")?;
+            }
+
+            value.fmt_lua(w, indent + 1, options)?;
+        }
+
+        w.write_str(" }")
+
+    // Long array - wrap every `wrap` values.
+    } else {
+        writeln!(w, "{{")?;
+
+        for (index, value) in values.enumerate() {
+            if index % (wrap as usize) == 0 {
+                if index > 0 {
+                    writeln!(w)?;
+                }
+
+                options.formatter.write_indent(w, indent + 1)?;
+            } else {
+                w.write_char(' ')?;
+            }
+
+            value.fmt_lua(w, indent + 1, options)?;
+
+            w.write_char(',')?;
+        }
+
+        writeln!(w)?;
+
+        options.formatter.write_indent(w, indent)?;
+        w.write_char('}')
+    }
+}
+
+/// Writes the floating point `value` to the writer `w` in the format configured by the `formatter`.
+/// Lua has no literals for non-finite values - they are written as `(1/0)`, `(-1/0)` and `(0/0)`.
+pub(crate) fn write_lua_f64<W: Write>(
+    w: &mut W,
+    value: f64,
+    formatter: &ConfigFormatter,
+) -> std::fmt::Result {
+    if value.is_nan() {
+        w.write_str("(0/0)")
+    } else if value.is_infinite() {
+        w.write_str(if value > 0.0 { "(1/0)" } else { "(-1/0)" })
+    } else {
+        formatter.write_f64(w, value)
+    }
+}
+
+/// Writes the `string` to the writer `w`, enclosing it in `quote`'s (`'"'` or `'\''`) and escaping special characters
+/// ('\\', '\0', '\a', '\b', '\t', '\n', '\r', '\v', '\f'), double quotes ('"') and, if `quote` is `'\''`, single quotes.
+pub(crate) fn write_lua_string<W: Write>(w: &mut W, string: &str, quote: char) -> std::fmt::Result {
+    w.write_char(quote)?;
 
     for c in string.chars() {
+        if (c == '\'') && (quote == '\'') {
+            w.write_str(r#"\'"#)?;
+            continue;
+        }
+
         write_char(w, c, false, true, true).map_err(|err| match err {
             WriteCharError::WriteError => std::fmt::Error,
             WriteCharError::EscapedCharacter(_) => debug_unreachable!(
@@ -26,20 +199,57 @@ pub(crate) fn write_lua_string<W: Write>(w: &mut W, string: &str) -> std::fmt::R
         })?;
     }
 
-    w.write_char('"')
+    w.write_char(quote)
 }
 
-/// Writes the Lua table `key` to the writer `w`.
+/// Writes the Lua table `key` to the writer `w`, padded with spaces to `width` chars.
 /// Writes the string as-is if it's a valid Lua identifier,
 /// otherwise encloses it in brackets and quotes, and escapes special characters
 /// ('\\', '\0', '\a', '\b', '\t', '\n', '\r', '\v', '\f') and quotes ('"').
-pub(crate) fn write_lua_key<W: Write>(w: &mut W, key: &NonEmptyStr) -> std::fmt::Result {
+pub(crate) fn write_lua_key<W: Write>(
+    w: &mut W,
+    key: &NonEmptyStr,
+    width: usize,
+) -> std::fmt::Result {
     if is_lua_identifier_key(key) {
-        write!(w, "{}", key)
+        write!(w, "{}", key)?;
     } else {
         w.write_char('[')?;
-        write_lua_string(w, key.as_str())?;
-        w.write_char(']')
+        write_lua_string(w, key.as_str(), '"')?;
+        w.write_char(']')?;
+    }
+
+    write_padding(w, width.saturating_sub(lua_key_width(key)))
+}
+
+/// Returns the width in chars to pad the table `keys` to, if the key-value separators are aligned according to `options`;
+/// otherwise returns `0`.
+pub(crate) fn lua_keys_width<I, K>(keys: I, options: ToLuaStringOptions) -> usize
+where
+    I: Iterator<Item = K>,
+    K: AsRef<str>,
+{
+    if !options.formatter.align_values {
+        return 0;
+    }
+
+    keys.filter_map(|key| NonEmptyStr::new(key.as_ref()).map(lua_key_width))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the width in chars of the written Lua table `key`.
+fn lua_key_width(key: &NonEmptyStr) -> usize {
+    if is_lua_identifier_key(key) {
+        key.as_str().chars().count()
+    } else {
+        written_width(|string| {
+            string.push('[');
+            write_lua_string(string, key.as_str(), '"')?;
+            string.push(']');
+            Ok::<_, std::fmt::Error>(())
+        })
+        .unwrap_or(0)
     }
 }
 
@@ -0,0 +1,204 @@
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+use std::fmt::Write;
+
+/// Indentation style of the nested tables / arrays in the serialized config.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigIndent {
+    /// One horizontal tab (`'\t'`) per nesting level.
+    Tabs,
+    /// The given number of spaces (`' '`) per nesting level.
+    Spaces(u32),
+}
+
+/// Quotes used for string values in the serialized config.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigQuotes {
+    /// Double quotes (`'"'`).
+    Double,
+    /// Single quotes (`'\''`).
+    ///
+    /// NOTE - `.ini` configs serialized with single quotes must be parsed with the [`parser`]
+    /// [`configured`] to support them.
+    ///
+    /// [`parser`]: struct.IniParser.html
+    /// [`configured`]: struct.IniParser.html#method.string_quotes
+    Single,
+}
+
+/// Formatting of the floating point values in the serialized config.
+///
+/// Floating point values are always serialized with a decimal point or an exponent,
+/// so that they are parsed back as floating point values rather than integers.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigFloatFormat {
+    /// Shortest representation which is parsed back to exactly the same value
+    /// (e.g. `0.1`, `1.0`, `1e300`, `5e-324`).
+    Shortest,
+    /// Fixed number of digits after the decimal point (e.g. `0.10` for `Fixed(2)`).
+    ///
+    /// NOTE - the values are rounded to the given precision and are not guaranteed to be parsed back to the same value.
+    /// `Fixed(0)` still serializes a zero fractional part (e.g. `1.0`).
+    Fixed(u32),
+}
+
+/// New line sequence used in the serialized config.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigNewLine {
+    /// Line feed (`"\n"`).
+    Lf,
+    /// Carriage return followed by line feed (`"\r\n"`).
+    CrLf,
+}
+
+/// Output style options shared by the `.ini` / Lua script config serializers
+/// (see [`ToIniStringOptions`] / [`ToLuaStringOptions`]).
+///
+/// [`ToIniStringOptions`]: struct.ToIniStringOptions.html
+/// [`ToLuaStringOptions`]: struct.ToLuaStringOptions.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ConfigFormatter {
+    /// Indentation style of the nested tables / arrays.
+    /// `.ini` configs are not indented.
+    ///
+    /// Default: [`Tabs`](enum.ConfigIndent.html#variant.Tabs).
+    pub indent: ConfigIndent,
+    /// Quotes used for string values.
+    /// Keys / section names which need quotes are always enclosed in double quotes.
+    ///
+    /// Default: [`Double`](enum.ConfigQuotes.html#variant.Double).
+    pub quotes: ConfigQuotes,
+    /// Formatting of the floating point values.
+    ///
+    /// Default: [`Shortest`](enum.ConfigFloatFormat.html#variant.Shortest).
+    pub float_format: ConfigFloatFormat,
+    /// New line sequence.
+    ///
+    /// Default: [`Lf`](enum.ConfigNewLine.html#variant.Lf).
+    pub new_line: ConfigNewLine,
+    /// If `true`, the key-value separators (`'='`) of the values in the same table / section
+    /// are aligned by padding the keys with spaces.
+    ///
+    /// Default: `false`.
+    pub align_values: bool,
+}
+
+impl Default for ConfigFormatter {
+    fn default() -> Self {
+        Self {
+            indent: ConfigIndent::Tabs,
+            quotes: ConfigQuotes::Double,
+            float_format: ConfigFloatFormat::Shortest,
+            new_line: ConfigNewLine::Lf,
+            align_values: false,
+        }
+    }
+}
+
+impl ConfigFormatter {
+    /// Returns the canonical output style options, used to serialize configs reproducibly
+    /// (e.g. to hash the serialized configs): no indentation, double quotes,
+    /// [`shortest`](enum.ConfigFloatFormat.html#variant.Shortest) floating point values,
+    /// line feeds and no alignment.
+    pub fn canonical() -> Self {
+        Self {
+            indent: ConfigIndent::Spaces(0),
+            quotes: ConfigQuotes::Double,
+            float_format: ConfigFloatFormat::Shortest,
+            new_line: ConfigNewLine::Lf,
+            align_values: false,
+        }
+    }
+}
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+impl ConfigFormatter {
+    /// Writes the indentation for the `indent` nesting level to the writer `w`.
+    pub(crate) fn write_indent<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+        for _ in 0..indent {
+            match self.indent {
+                ConfigIndent::Tabs => w.write_char('\t')?,
+                ConfigIndent::Spaces(spaces) => write_padding(w, spaces as usize)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the floating point `value` to the writer `w` in the configured format.
+    ///
+    /// Non-finite values are written as `inf`, `-inf` and `NaN`.
+    pub(crate) fn write_f64<W: Write>(&self, w: &mut W, value: f64) -> std::fmt::Result {
+        match self.float_format {
+            // `Debug` formatting is the shortest round-trip representation,
+            // always with a decimal point or an exponent, switching to the exponent for very large / small values.
+            ConfigFloatFormat::Shortest => write!(w, "{:?}", value),
+            ConfigFloatFormat::Fixed(0) if value.is_finite() => write!(w, "{:.0}.0", value),
+            ConfigFloatFormat::Fixed(precision) => write!(w, "{:.*}", precision as usize, value),
+        }
+    }
+
+    /// Returns the quote character used for string values.
+    pub(crate) fn quote(&self) -> char {
+        match self.quotes {
+            ConfigQuotes::Double => '"',
+            ConfigQuotes::Single => '\'',
+        }
+    }
+
+    /// Wraps the writer `w`, replacing the line feeds (`'\n'`) written to it with the configured new line sequence.
+    pub(crate) fn new_lines<'w, W: Write>(&self, w: &'w mut W) -> NewLineWriter<'w, W> {
+        NewLineWriter {
+            w,
+            crlf: self.new_line == ConfigNewLine::CrLf,
+        }
+    }
+}
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+/// Writes `width` spaces to the writer `w`.
+pub(crate) fn write_padding<W: Write>(w: &mut W, width: usize) -> std::fmt::Result {
+    for _ in 0..width {
+        w.write_char(' ')?;
+    }
+
+    Ok(())
+}
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+/// Returns the width in chars of the string written by the `write` closure,
+/// or `None` if it failed.
+pub(crate) fn written_width<F: FnOnce(&mut String) -> Result<(), E>, E>(write: F) -> Option<usize> {
+    let mut string = String::new();
+    write(&mut string).ok()?;
+    Some(string.chars().count())
+}
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+/// Writer adapter which replaces the line feeds (`'\n'`) with the configured new line sequence.
+///
+/// NOTE - serialized string values never contain unescaped line feeds,
+/// unless serialized as [`multi-line strings`](struct.ToIniStringOptions.html#structfield.multiline_strings),
+/// which are normalized back to line feeds when parsed.
+pub(crate) struct NewLineWriter<'w, W> {
+    w: &'w mut W,
+    crlf: bool,
+}
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+impl<'w, W: Write> Write for NewLineWriter<'w, W> {
+    fn write_str(&mut self, string: &str) -> std::fmt::Result {
+        if !self.crlf {
+            return self.w.write_str(string);
+        }
+
+        for (index, line) in string.split('\n').enumerate() {
+            if index > 0 {
+                self.w.write_str("\r\n")?;
+            }
+
+            self.w.write_str(line)?;
+        }
+
+        Ok(())
+    }
+}
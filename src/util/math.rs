@@ -0,0 +1,173 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// An error returned by [`table`] / [`array`] vector and color accessors (e.g. [`DynTable::get_vec3`], [`DynTable::get_color`]).
+///
+/// NOTE - requires `"math"` feature.
+///
+/// [`table`]: enum.Value.html#variant.Table
+/// [`array`]: enum.Value.html#variant.Array
+/// [`DynTable::get_vec3`]: struct.DynTable.html#method.get_vec3
+/// [`DynTable::get_color`]: struct.DynTable.html#method.get_color
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum GetVecError {
+    /// Failed to get the value from the [`table`].
+    /// Contains the [`table error`].
+    ///
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`table error`]: enum.TableError.html
+    Table(TableError),
+    /// Failed to get the value from the [`array`].
+    /// Contains the [`array error`].
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`array error`]: enum.ArrayError.html
+    Array(ArrayError),
+    /// The vector / color [`array`] has an invalid number of elements.
+    /// Contains the actual [`array`] length.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    InvalidLength(u32),
+    /// The vector / color [`array`] element is not a number.
+    ///
+    /// [`array`]: enum.Value.html#variant.Array
+    IncorrectElementType {
+        /// Index of the element in the [`array`](enum.Value.html#variant.Array).
+        index: u32,
+        /// Actual element value [`type`](enum.ValueType.html).
+        value_type: ValueType,
+    },
+    /// The color [`string`] is not of `#rrggbb` / `#rrggbbaa` form.
+    /// Contains the actual [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    InvalidColor(String),
+}
+
+impl Error for GetVecError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use GetVecError::*;
+
+        match self {
+            // Transparent - `Display` is forwarded to the table / array error.
+            Table(err) => err.source(),
+            Array(err) => err.source(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for GetVecError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use GetVecError::*;
+
+        match self {
+            Table(err) => err.fmt(f),
+            Array(err) => err.fmt(f),
+            InvalidLength(len) => write!(
+                f,
+                "vector / color array has an invalid number of elements ({})",
+                len
+            ),
+            IncorrectElementType { index, value_type } => write!(
+                f,
+                "vector / color array element {} is not a number (found {})",
+                index, value_type
+            ),
+            InvalidColor(color) => write!(
+                f,
+                "invalid color string \"{}\" (expected \"#rrggbb\" or \"#rrggbbaa\")",
+                color
+            ),
+        }
+    }
+}
+
+impl From<TableError> for GetVecError {
+    fn from(err: TableError) -> Self {
+        Self::Table(err)
+    }
+}
+
+impl From<ArrayError> for GetVecError {
+    fn from(err: ArrayError) -> Self {
+        Self::Array(err)
+    }
+}
+
+/// Converts the `len` numeric array `values` to a vector of `N` elements.
+pub(crate) fn vec_from_values<S, A, T, I, const N: usize>(
+    len: u32,
+    values: I,
+) -> Result<[f32; N], GetVecError>
+where
+    I: Iterator<Item = Value<S, A, T>>,
+{
+    if len as usize != N {
+        return Err(GetVecError::InvalidLength(len));
+    }
+
+    let mut result = [0.0; N];
+
+    for (index, value) in values.enumerate() {
+        result[index] = number(index as u32, value)?;
+    }
+
+    Ok(result)
+}
+
+/// Converts the `len` numeric array `values` to an RGBA color.
+/// The array must have `3` (RGB, alpha is `1.0`) or `4` (RGBA) elements.
+pub(crate) fn color_from_values<S, A, T, I>(len: u32, values: I) -> Result<[f32; 4], GetVecError>
+where
+    I: Iterator<Item = Value<S, A, T>>,
+{
+    if len != 3 && len != 4 {
+        return Err(GetVecError::InvalidLength(len));
+    }
+
+    let mut result = [1.0; 4];
+
+    for (index, value) in values.enumerate() {
+        result[index] = number(index as u32, value)?;
+    }
+
+    Ok(result)
+}
+
+/// Parses the `#rrggbb` / `#rrggbbaa` hex `color` string to an RGBA color with channels in `[0.0, 1.0]` range.
+pub(crate) fn parse_color(color: &str) -> Result<[f32; 4], GetVecError> {
+    let invalid_color = || GetVecError::InvalidColor(color.to_owned());
+
+    let digits = color.strip_prefix('#').ok_or_else(invalid_color)?;
+
+    if (digits.len() != 6 && digits.len() != 8) || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid_color());
+    }
+
+    let mut result = [1.0; 4];
+
+    for (index, channel) in result.iter_mut().take(digits.len() / 2).enumerate() {
+        let channel_digits = &digits[index * 2..index * 2 + 2];
+        *channel =
+            u8::from_str_radix(channel_digits, 16).map_err(|_| invalid_color())? as f32 / 255.0;
+    }
+
+    Ok(result)
+}
+
+fn number<S, A, T>(index: u32, value: Value<S, A, T>) -> Result<f32, GetVecError> {
+    value
+        .f64()
+        .map(|value| value as f32)
+        .ok_or_else(|| GetVecError::IncorrectElementType {
+            index,
+            value_type: value.get_type(),
+        })
+}
@@ -1,27 +1,134 @@
-#[cfg(any(feature = "bin", feature = "dyn", feature = "ini", feature = "lua"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+mod access;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 mod display;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 mod display_lua;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "ini", feature = "lua_runtime"))]
+mod formatter;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 mod config_path;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "ini", feature = "lua"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+mod deprecation;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+mod diagnostics;
+
+#[cfg(any(feature = "bin", feature = "dyn"))]
+mod stats;
+
+#[cfg(all(
+    feature = "math",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
+mod math;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+mod units;
+
+#[cfg(any(
+    feature = "bin",
+    feature = "dyn",
+    all(feature = "lua_runtime", feature = "str_hash")
+))]
+mod string_hash;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub use access::{ArrayAccess, Config, TableAccess};
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 pub(crate) use display::*;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 pub(crate) use display_lua::*;
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub use display_lua::ToLuaStringOptions;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "ini", feature = "lua_runtime"))]
+pub use formatter::{
+    ConfigFloatFormat, ConfigFormatter, ConfigIndent, ConfigNewLine, ConfigQuotes,
+};
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub(crate) use formatter::{write_padding, written_width};
+
+#[cfg(all(
+    feature = "ini",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
+pub(crate) use formatter::NewLineWriter;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 pub use config_path::*;
 
-#[cfg(all(test, any(feature = "bin", feature = "dyn", feature = "lua")))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub use deprecation::*;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub use diagnostics::*;
+
+#[cfg(any(feature = "bin", feature = "dyn"))]
+pub use stats::ConfigStats;
+
+#[cfg(all(
+    feature = "math",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
+pub use math::GetVecError;
+
+#[cfg(all(
+    feature = "math",
+    any(feature = "bin", feature = "dyn", feature = "lua_runtime")
+))]
+pub(crate) use math::{color_from_values, parse_color, vec_from_values};
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub use units::GetUnitError;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub(crate) use units::{parse_bytes, parse_duration};
+
+#[cfg(any(
+    feature = "bin",
+    feature = "dyn",
+    all(feature = "lua_runtime", feature = "str_hash")
+))]
+pub(crate) use string_hash::*;
+
+/// Default maximum nesting depth of arrays / tables in the binary configs [`loaded`] from data blobs
+/// or [`written`] by the binary config writer (requires `"bin"` feature),
+/// and in the [`validated`] Lua config tables (requires `"lua"` feature).
+///
+/// The arrays / tables in the root table are at depth `1`.
+///
+/// Limits the recursion depth when processing the configs, so that hostile or broken inputs
+/// with thousands of nesting levels fail with an error instead of overflowing the stack.
+///
+/// [`loaded`]: struct.BinConfig.html#method.with_max_depth
+/// [`written`]: struct.BinConfigWriter.html#method.max_depth
+/// [`validated`]: struct.LuaConfigOptions.html#structfield.max_depth
+#[cfg(any(feature = "bin", feature = "lua_runtime"))]
+pub const DEFAULT_MAX_DEPTH: u32 = 128;
+
+/// Converts the [`i64`] `value` to the (narrower) integer type `T`.
+/// Returns the `value` if it is out of range of `T`.
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
+pub(crate) fn narrow_i64<T: std::convert::TryFrom<i64>>(value: i64) -> Result<T, i64> {
+    T::try_from(value).map_err(|_| value)
+}
+
+#[cfg(all(test, any(feature = "bin", feature = "dyn", feature = "lua_runtime")))]
 pub(crate) fn cmp_f64(l: f64, r: f64) -> bool {
     (l - r).abs() < 0.000_001
 }
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 pub(crate) fn debug_unreachable_impl(msg: &'static str) -> ! {
     if cfg!(debug_assertions) {
         unreachable!(msg)
@@ -32,7 +139,7 @@ pub(crate) fn debug_unreachable_impl(msg: &'static str) -> ! {
 
 /// `unreachable!()` in debug to `panic!()` and catch the logic error,
 /// `std::hint::unreachable_unchecked()` in release to avoid unnecessary `panic!()` codegen.
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 #[macro_export]
 macro_rules! debug_unreachable {
     () => {{
@@ -47,12 +154,12 @@ macro_rules! debug_unreachable {
 /// which are known to be `Some` / `Ok`.
 /// Unlike the (currently unstable) `.unwrap_unchecked()` method on `Option`'s / `Result`'s,
 /// this uses `unreachable!()` in debug configuration and `std::hint::unreachable_unchecked()` in release configuration.
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 pub(crate) trait UnwrapUnchecked<T> {
     fn unwrap_unchecked(self, msg: &'static str) -> T;
 }
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 impl<T> UnwrapUnchecked<T> for Option<T> {
     fn unwrap_unchecked(self, msg: &'static str) -> T {
         if let Some(val) = self {
@@ -63,7 +170,7 @@ impl<T> UnwrapUnchecked<T> for Option<T> {
     }
 }
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 impl<T, E> UnwrapUnchecked<T> for Result<T, E> {
     fn unwrap_unchecked(self, msg: &'static str) -> T {
         if let Ok(val) = self {
@@ -74,7 +181,7 @@ impl<T, E> UnwrapUnchecked<T> for Result<T, E> {
     }
 }
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime", feature = "ini"))]
 pub(crate) fn unwrap_unchecked<U: UnwrapUnchecked<T>, T>(
     option_or_result: U,
     msg: &'static str,
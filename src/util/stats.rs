@@ -0,0 +1,127 @@
+use crate::*;
+
+/// Statistics of the contents of a config - counts of tables / arrays / values per [`type`],
+/// maximum nesting depth and total string sizes (e.g. to track config bloat across releases).
+///
+/// Returned by [`DynConfig::stats`] and [`BinConfig::stats`].
+///
+/// [`type`]: enum.ValueType.html
+/// [`DynConfig::stats`]: struct.DynConfig.html#method.stats
+/// [`BinConfig::stats`]: struct.BinConfig.html#method.stats
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ConfigStats {
+    /// Number of tables, including the root table.
+    pub num_tables: u32,
+    /// Number of arrays.
+    pub num_arrays: u32,
+    /// Number of [`bool`](enum.Value.html#variant.Bool) values.
+    pub num_bools: u32,
+    /// Number of [`i64`](enum.Value.html#variant.I64) values.
+    pub num_i64s: u32,
+    /// Number of [`f64`](enum.Value.html#variant.F64) values.
+    pub num_f64s: u32,
+    /// Number of [`string`](enum.Value.html#variant.String) values.
+    pub num_strings: u32,
+    /// Number of [`date/time`](enum.Value.html#variant.DateTime) values.
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    #[cfg(feature = "datetime")]
+    pub num_datetimes: u32,
+    /// Maximum nesting depth of the values in the config:
+    /// `1` for the elements of the root table, `2` for the elements of its nested tables / arrays, etc.
+    /// `0` if the root table is empty.
+    pub max_depth: u32,
+    /// Total length in bytes of all table keys (duplicate keys are counted each time they are used).
+    pub key_bytes: u64,
+    /// Total length in bytes of all [`string`](enum.Value.html#variant.String) values
+    /// (duplicate strings are counted each time they are used).
+    pub string_bytes: u64,
+}
+
+impl ConfigStats {
+    /// Returns the number of values of `value_type` in the config
+    /// (including the root table for [`tables`](enum.ValueType.html#variant.Table)).
+    pub fn num_values(&self, value_type: ValueType) -> u32 {
+        match value_type {
+            ValueType::Bool => self.num_bools,
+            ValueType::I64 => self.num_i64s,
+            ValueType::F64 => self.num_f64s,
+            ValueType::String => self.num_strings,
+            ValueType::Array => self.num_arrays,
+            ValueType::Table => self.num_tables,
+            #[cfg(feature = "datetime")]
+            ValueType::DateTime => self.num_datetimes,
+        }
+    }
+
+    /// Returns the total number of values in the config, including nested tables / arrays
+    /// (but not the root table).
+    pub fn total_values(&self) -> u32 {
+        #[allow(unused_mut)]
+        let mut total = self.num_tables - 1
+            + self.num_arrays
+            + self.num_bools
+            + self.num_i64s
+            + self.num_f64s
+            + self.num_strings;
+
+        #[cfg(feature = "datetime")]
+        {
+            total += self.num_datetimes;
+        }
+
+        total
+    }
+
+    /// Creates the statistics of a config with an empty root table.
+    pub(crate) fn new() -> Self {
+        Self {
+            num_tables: 1,
+            num_arrays: 0,
+            num_bools: 0,
+            num_i64s: 0,
+            num_f64s: 0,
+            num_strings: 0,
+            #[cfg(feature = "datetime")]
+            num_datetimes: 0,
+            max_depth: 0,
+            key_bytes: 0,
+            string_bytes: 0,
+        }
+    }
+
+    /// Records the `value` at `depth` (`1` for the elements of the root table)
+    /// with the `key` if it is a table element.
+    pub(crate) fn add<S: AsRef<str>, A, T>(
+        &mut self,
+        key: Option<&str>,
+        value: &Value<S, A, T>,
+        depth: u32,
+    ) {
+        match value {
+            Value::Bool(_) => self.num_bools += 1,
+            Value::I64(_) => self.num_i64s += 1,
+            Value::F64(_) => self.num_f64s += 1,
+            Value::String(string) => {
+                self.num_strings += 1;
+                self.string_bytes += string.as_ref().len() as u64;
+            }
+            Value::Array(_) => self.num_arrays += 1,
+            Value::Table(_) => self.num_tables += 1,
+            #[cfg(feature = "datetime")]
+            Value::DateTime(_) => self.num_datetimes += 1,
+        }
+
+        if let Some(key) = key {
+            self.key_bytes += key.len() as u64;
+        }
+
+        self.max_depth = self.max_depth.max(depth);
+    }
+}
+
+impl Default for ConfigStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,41 @@
+pub(crate) type StringHash = u32;
+
+pub(crate) const fn string_hash_fnv1a(string: &str) -> StringHash {
+    const FNV1A_PRIME: StringHash = 0x0100_0193;
+    const FNV1A_SEED: StringHash = 0x811C_9DC5;
+
+    let bytes = string.as_bytes();
+    let mut hash = FNV1A_SEED;
+    let mut index = 0;
+
+    // `for` loops are not allowed in `const fn`'s.
+    while index < bytes.len() {
+        hash = (hash ^ bytes[index] as StringHash).wrapping_mul(FNV1A_PRIME);
+        index += 1;
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_hash_collisions() {
+        // https://softwareengineering.stackexchange.com/questions/49550/which-hashing-algorithm-is-best-for-uniqueness-and-speed
+
+        assert_eq!(string_hash_fnv1a("costarring"), string_hash_fnv1a("liquid"),);
+
+        assert_eq!(
+            string_hash_fnv1a("declinate"),
+            string_hash_fnv1a("macallums"),
+        );
+
+        assert_eq!(string_hash_fnv1a("altarage"), string_hash_fnv1a("zinke"),);
+
+        assert_eq!(string_hash_fnv1a("altarages"), string_hash_fnv1a("zinkes"),);
+
+        assert_ne!(string_hash_fnv1a("foo"), string_hash_fnv1a("bar"),);
+    }
+}
@@ -0,0 +1,204 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+        time::Duration,
+    },
+};
+
+/// An error returned by [`table`] / [`array`] duration and byte size accessors (e.g. [`DynTable::get_duration`], [`DynTable::get_bytes`]).
+///
+/// [`table`]: enum.Value.html#variant.Table
+/// [`array`]: enum.Value.html#variant.Array
+/// [`DynTable::get_duration`]: struct.DynTable.html#method.get_duration
+/// [`DynTable::get_bytes`]: struct.DynTable.html#method.get_bytes
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum GetUnitError {
+    /// Failed to get the [`string`] value from the [`table`].
+    /// Contains the [`table error`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`table`]: enum.Value.html#variant.Table
+    /// [`table error`]: enum.TableError.html
+    Table(TableError),
+    /// Failed to get the [`string`] value from the [`array`].
+    /// Contains the [`array error`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`array error`]: enum.ArrayError.html
+    Array(ArrayError),
+    /// The [`string`] value does not start with a valid non-negative number.
+    /// Contains the [`string`] value.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    InvalidNumber(String),
+    /// The [`string`] value has a missing or unknown unit suffix.
+    /// Contains the suffix (empty if missing).
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    InvalidSuffix(String),
+    /// The [`string`] value is out of range of the duration / byte size type.
+    /// Contains the [`string`] value.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    ValueOutOfRange(String),
+}
+
+impl Error for GetUnitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use GetUnitError::*;
+
+        match self {
+            // Transparent - `Display` is forwarded to the table / array error.
+            Table(err) => err.source(),
+            Array(err) => err.source(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for GetUnitError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use GetUnitError::*;
+
+        match self {
+            Table(err) => err.fmt(f),
+            Array(err) => err.fmt(f),
+            InvalidNumber(value) => write!(f, "\"{}\" is not a valid non-negative number", value),
+            InvalidSuffix(suffix) => {
+                if suffix.is_empty() {
+                    "missing unit suffix".fmt(f)
+                } else {
+                    write!(f, "unknown unit suffix \"{}\"", suffix)
+                }
+            }
+            ValueOutOfRange(value) => write!(f, "\"{}\" is out of range", value),
+        }
+    }
+}
+
+impl From<TableError> for GetUnitError {
+    fn from(err: TableError) -> Self {
+        Self::Table(err)
+    }
+}
+
+impl From<ArrayError> for GetUnitError {
+    fn from(err: ArrayError) -> Self {
+        Self::Array(err)
+    }
+}
+
+/// Parses the `value` string as a duration - a non-negative integer or fractional number,
+/// followed by an (optionally whitespace-separated) unit suffix:
+/// `ns`, `us` / `µs`, `ms`, `s`, `m` / `min`, `h` or `d`.
+pub(crate) fn parse_duration(value: &str) -> Result<Duration, GetUnitError> {
+    use GetUnitError::*;
+
+    let (number, suffix) = split_number(value)?;
+
+    // Nanoseconds per unit.
+    let unit: u64 = match suffix {
+        "ns" => 1,
+        "us" | "µs" => 1_000,
+        "ms" => 1_000_000,
+        "s" => 1_000_000_000,
+        "m" | "min" => 60 * 1_000_000_000,
+        "h" => 60 * 60 * 1_000_000_000,
+        "d" => 24 * 60 * 60 * 1_000_000_000,
+        _ => return Err(InvalidSuffix(suffix.to_owned())),
+    };
+
+    let nanos = match number {
+        Number::Integer(number) => (number as u128) * (unit as u128),
+        Number::Float(number) => {
+            let nanos = number * unit as f64;
+
+            if nanos >= u128::MAX as f64 {
+                return Err(ValueOutOfRange(value.to_owned()));
+            }
+
+            nanos as u128
+        }
+    };
+
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+    let secs = nanos / NANOS_PER_SEC;
+
+    if secs > u64::MAX as u128 {
+        return Err(ValueOutOfRange(value.to_owned()));
+    }
+
+    Ok(Duration::new(secs as u64, (nanos % NANOS_PER_SEC) as u32))
+}
+
+/// Parses the `value` string as a byte size - a non-negative integer or fractional number,
+/// followed by an optional (optionally whitespace-separated) unit suffix:
+/// `B` (or no suffix), decimal `kB` / `KB`, `MB`, `GB`, `TB`, or binary `KiB`, `MiB`, `GiB`, `TiB`.
+/// Fractional byte sizes are truncated.
+pub(crate) fn parse_bytes(value: &str) -> Result<u64, GetUnitError> {
+    use GetUnitError::*;
+
+    let (number, suffix) = split_number(value)?;
+
+    let unit: u64 = match suffix {
+        "" | "B" => 1,
+        "kB" | "KB" => 1_000,
+        "MB" => 1_000_000,
+        "GB" => 1_000_000_000,
+        "TB" => 1_000_000_000_000,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        "TiB" => 1 << 40,
+        _ => return Err(InvalidSuffix(suffix.to_owned())),
+    };
+
+    match number {
+        Number::Integer(number) => number.checked_mul(unit),
+        Number::Float(number) => {
+            let bytes = number * unit as f64;
+
+            if bytes < u64::MAX as f64 {
+                Some(bytes as u64)
+            } else {
+                None
+            }
+        }
+    }
+    .ok_or_else(|| ValueOutOfRange(value.to_owned()))
+}
+
+enum Number {
+    Integer(u64),
+    Float(f64),
+}
+
+/// Splits the `value` string into the leading non-negative number and the trimmed unit suffix.
+fn split_number(value: &str) -> Result<(Number, &str), GetUnitError> {
+    use GetUnitError::*;
+
+    let trimmed = value.trim();
+
+    let number_len = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(trimmed.len());
+
+    let (number, suffix) = trimmed.split_at(number_len);
+    let suffix = suffix.trim_start();
+
+    let number = if let Ok(number) = number.parse::<u64>() {
+        Number::Integer(number)
+    } else {
+        match number.parse::<f64>() {
+            Ok(number) if number.is_finite() => Number::Float(number),
+            _ => return Err(InvalidNumber(value.to_owned())),
+        }
+    };
+
+    Ok((number, suffix))
+}
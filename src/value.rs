@@ -3,7 +3,7 @@ use std::{
     fmt::{Display, Formatter},
 };
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 use {crate::util::*, std::fmt::Write};
 
 /// Represents a config value.
@@ -17,6 +17,9 @@ pub enum Value<S, A, T> {
     String(S),
     Array(A),
     Table(T),
+    /// NOTE - requires `"datetime"` feature.
+    #[cfg(feature = "datetime")]
+    DateTime(crate::DateTime),
 }
 
 impl<S, A, T> From<bool> for Value<S, A, T> {
@@ -31,27 +34,87 @@ impl<S, A, T> From<i64> for Value<S, A, T> {
     }
 }
 
+impl<S, A, T> From<i32> for Value<S, A, T> {
+    fn from(val: i32) -> Self {
+        Value::I64(i64::from(val))
+    }
+}
+
+impl<S, A, T> From<u32> for Value<S, A, T> {
+    fn from(val: u32) -> Self {
+        Value::I64(i64::from(val))
+    }
+}
+
+impl<S, A, T> From<i16> for Value<S, A, T> {
+    fn from(val: i16) -> Self {
+        Value::I64(i64::from(val))
+    }
+}
+
+impl<S, A, T> From<u16> for Value<S, A, T> {
+    fn from(val: u16) -> Self {
+        Value::I64(i64::from(val))
+    }
+}
+
+impl<S, A, T> From<i8> for Value<S, A, T> {
+    fn from(val: i8) -> Self {
+        Value::I64(i64::from(val))
+    }
+}
+
+impl<S, A, T> From<u8> for Value<S, A, T> {
+    fn from(val: u8) -> Self {
+        Value::I64(i64::from(val))
+    }
+}
+
 impl<S, A, T> From<f64> for Value<S, A, T> {
     fn from(val: f64) -> Self {
         Value::F64(val)
     }
 }
 
-#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+impl<'s, A, T> From<&'s str> for Value<&'s str, A, T> {
+    fn from(val: &'s str) -> Self {
+        Value::String(val)
+    }
+}
+
+#[cfg(feature = "datetime")]
+impl<S, A, T> From<crate::DateTime> for Value<S, A, T> {
+    fn from(val: crate::DateTime) -> Self {
+        Value::DateTime(val)
+    }
+}
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
 impl<S, A, T> DisplayLua for Value<S, A, T>
 where
     S: AsRef<str>,
     A: DisplayLua,
     T: DisplayLua,
 {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToLuaStringOptions,
+    ) -> std::fmt::Result {
         match self {
             Value::Bool(value) => write!(w, "{}", if *value { "true" } else { "false" }),
             Value::I64(value) => write!(w, "{}", value),
-            Value::F64(value) => write!(w, "{}", value),
-            Value::String(value) => write_lua_string(w, value.as_ref()),
-            Value::Array(value) => value.fmt_lua(w, indent),
-            Value::Table(value) => value.fmt_lua(w, indent),
+            Value::F64(value) => write_lua_f64(w, *value, &options.formatter),
+            Value::String(value) => write_lua_string(w, value.as_ref(), options.formatter.quote()),
+            Value::Array(value) => value.fmt_lua(w, indent, options),
+            Value::Table(value) => value.fmt_lua(w, indent, options),
+            // Lua has no native date/time type - serialize as a string.
+            #[cfg(feature = "datetime")]
+            Value::DateTime(value) => {
+                let quote = options.formatter.quote();
+                write!(w, "{}{}{}", quote, value, quote)
+            }
         }
     }
 }
@@ -67,6 +130,9 @@ pub enum ValueType {
     String,
     Array,
     Table,
+    /// NOTE - requires `"datetime"` feature.
+    #[cfg(feature = "datetime")]
+    DateTime,
 }
 
 impl<S, A, T> Value<S, A, T> {
@@ -81,6 +147,8 @@ impl<S, A, T> Value<S, A, T> {
             Value::String(_) => String,
             Value::Array(_) => Array,
             Value::Table(_) => Table,
+            #[cfg(feature = "datetime")]
+            Value::DateTime(_) => DateTime,
         }
     }
 
@@ -153,6 +221,20 @@ impl<S, A, T> Value<S, A, T> {
             _ => None,
         }
     }
+
+    /// Extracts the [`date/time`] value from the config value.
+    /// Returns `None` if the value is not a [`date/time`].
+    ///
+    /// NOTE - requires `"datetime"` feature.
+    ///
+    /// [`date/time`]: enum.Value.html#variant.DateTime
+    #[cfg(feature = "datetime")]
+    pub fn datetime(&self) -> Option<crate::DateTime> {
+        match self {
+            Value::DateTime(val) => Some(*val),
+            _ => None,
+        }
+    }
 }
 
 impl<S: AsRef<str>, A, T> Value<S, A, T> {
@@ -165,7 +247,7 @@ impl<S: AsRef<str>, A, T> Value<S, A, T> {
 }
 
 impl ValueType {
-    #[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+    #[cfg(any(feature = "bin", feature = "dyn", feature = "lua_runtime"))]
     pub(crate) fn is_compatible(self, other: ValueType) -> bool {
         use ValueType::*;
 
@@ -176,6 +258,8 @@ impl ValueType {
             String => other == String,
             Array => other == Array,
             Table => other == Table,
+            #[cfg(feature = "datetime")]
+            DateTime => other == DateTime,
         }
     }
 }
@@ -191,11 +275,13 @@ impl Display for ValueType {
             String => "String".fmt(f),
             Array => "Array".fmt(f),
             Table => "Table".fmt(f),
+            #[cfg(feature = "datetime")]
+            DateTime => "DateTime".fmt(f),
         }
     }
 }
 
-#[cfg(any(feature = "bin", feature = "lua"))]
+#[cfg(any(feature = "bin", feature = "lua_runtime"))]
 pub(crate) fn value_type_to_u32<V: Into<Option<ValueType>>>(val: V) -> u32 {
     use ValueType::*;
 
@@ -209,13 +295,15 @@ pub(crate) fn value_type_to_u32<V: Into<Option<ValueType>>>(val: V) -> u32 {
             String => 4,
             Array => 5,
             Table => 6,
+            #[cfg(feature = "datetime")]
+            DateTime => 7,
         }
     } else {
         0
     }
 }
 
-#[cfg(any(feature = "bin", feature = "lua"))]
+#[cfg(any(feature = "bin", feature = "lua_runtime"))]
 pub(crate) fn value_type_from_u32(val: u32) -> Option<ValueType> {
     use ValueType::*;
 
@@ -227,6 +315,8 @@ pub(crate) fn value_type_from_u32(val: u32) -> Option<ValueType> {
         4 => Some(String),
         5 => Some(Array),
         6 => Some(Table),
+        #[cfg(feature = "datetime")]
+        7 => Some(DateTime),
         _ => None,
     }
 }
@@ -255,12 +345,80 @@ impl<S, A, T> TryFromValue<S, A, T> for f64 {
     }
 }
 
+#[cfg(feature = "datetime")]
+impl<S, A, T> TryFromValue<S, A, T> for crate::DateTime {
+    fn try_from(val: Value<S, A, T>) -> Result<Self, ValueType> {
+        val.datetime().ok_or_else(|| val.get_type())
+    }
+}
+
+/// A wrapper around a [`bool`] / [`i64`] / [`f64`] value
+/// [`converted`](trait.TryFromValue.html) from a [`config value`] using lenient type coercion rules:
+/// - [`strings`] `"true"` / `"false"` (case-sensitive) are coerced to [`bool`]'s,
+/// - [`i64`]'s and [`f64`]'s are coerced to each other (same as for unwrapped [`i64`]'s and [`f64`]'s),
+/// - [`strings`] which parse as integers / floats are coerced to [`i64`]'s / [`f64`]'s
+///   (e.g. values of `.ini` configs parsed in [`strict mode`]).
+///
+/// [`bool`]: enum.Value.html#variant.Bool
+/// [`i64`]: enum.Value.html#variant.I64
+/// [`f64`]: enum.Value.html#variant.F64
+/// [`config value`]: enum.Value.html
+/// [`strings`]: enum.Value.html#variant.String
+/// [`strict mode`]: struct.IniParser.html#method.strict_values
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Lenient<V>(pub V);
+
+impl<S: AsRef<str>, A, T> TryFromValue<S, A, T> for Lenient<bool> {
+    fn try_from(val: Value<S, A, T>) -> Result<Self, ValueType> {
+        match &val {
+            Value::Bool(val) => Some(*val),
+            Value::String(val) => match val.as_ref() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+        .map(Lenient)
+        .ok_or_else(|| val.get_type())
+    }
+}
+
+impl<S: AsRef<str>, A, T> TryFromValue<S, A, T> for Lenient<i64> {
+    fn try_from(val: Value<S, A, T>) -> Result<Self, ValueType> {
+        match &val {
+            Value::String(string) => {
+                let string = string.as_ref();
+
+                string
+                    .parse::<i64>()
+                    .ok()
+                    .or_else(|| string.parse::<f64>().ok().map(|val| val as i64))
+            }
+            _ => val.i64(),
+        }
+        .map(Lenient)
+        .ok_or_else(|| val.get_type())
+    }
+}
+
+impl<S: AsRef<str>, A, T> TryFromValue<S, A, T> for Lenient<f64> {
+    fn try_from(val: Value<S, A, T>) -> Result<Self, ValueType> {
+        match &val {
+            Value::String(string) => string.as_ref().parse::<f64>().ok(),
+            _ => val.f64(),
+        }
+        .map(Lenient)
+        .ok_or_else(|| val.get_type())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    #[cfg(any(feature = "bin", feature = "lua"))]
+    #[cfg(any(feature = "bin", feature = "lua_runtime"))]
     use super::*;
 
-    #[cfg(any(feature = "bin", feature = "lua"))]
+    #[cfg(any(feature = "bin", feature = "lua_runtime"))]
     #[test]
     fn value_type_to_u32_and_back() {
         assert_eq!(value_type_from_u32(value_type_to_u32(None)), None);
@@ -288,5 +446,10 @@ mod tests {
             value_type_from_u32(value_type_to_u32(ValueType::Table)),
             Some(ValueType::Table)
         );
+        #[cfg(feature = "datetime")]
+        assert_eq!(
+            value_type_from_u32(value_type_to_u32(ValueType::DateTime)),
+            Some(ValueType::DateTime)
+        );
     }
 }
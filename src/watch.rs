@@ -0,0 +1,335 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+        fs, io,
+        path::{Path, PathBuf},
+        time::SystemTime,
+    },
+};
+
+/// Source format of the config file watched by the [`config watcher`](struct.ConfigWatcher.html).
+#[derive(Clone, Copy)]
+pub enum ConfigWatchFormat {
+    /// `.ini` config, parsed with default [`parser`] options.
+    ///
+    /// NOTE - requires `"ini"` feature.
+    ///
+    /// [`parser`]: struct.IniParser.html
+    #[cfg(feature = "ini")]
+    Ini,
+    /// `.ini` config, parsed with the [`parser`] options set up by the user-provided function.
+    ///
+    /// NOTE - requires `"ini"` feature.
+    ///
+    /// [`parser`]: struct.IniParser.html
+    #[cfg(feature = "ini")]
+    IniWithOptions(fn(IniParser<'_>) -> IniParser<'_>),
+    /// Lua script config, executed in a new Lua state.
+    ///
+    /// NOTE - requires `"lua"` feature.
+    #[cfg(feature = "lua_runtime")]
+    Lua,
+}
+
+/// An error returned by the [`config watcher`](struct.ConfigWatcher.html).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigWatcherError {
+    /// Failed to access or read the config file.
+    /// Contains the I/O error.
+    Io(io::Error),
+    /// Failed to parse the `.ini` config file.
+    /// Contains the `.ini` [`parser error`](struct.IniError.html).
+    #[cfg(feature = "ini")]
+    Ini(IniError),
+    /// Failed to execute the Lua config script.
+    /// Contains the Lua [`config error`](enum.LuaConfigError.html).
+    #[cfg(feature = "lua_runtime")]
+    Lua(LuaConfigError),
+}
+
+impl Error for ConfigWatcherError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use ConfigWatcherError::*;
+
+        match self {
+            Io(err) => Some(err),
+            #[cfg(feature = "ini")]
+            Ini(err) => Some(err),
+            #[cfg(feature = "lua_runtime")]
+            Lua(err) => Some(err),
+        }
+    }
+}
+
+impl Display for ConfigWatcherError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use ConfigWatcherError::*;
+
+        match self {
+            Io(err) => write!(f, "failed to read the config file: {}", err),
+            #[cfg(feature = "ini")]
+            Ini(err) => write!(f, "failed to parse the `.ini` config file: {}", err),
+            #[cfg(feature = "lua_runtime")]
+            Lua(err) => write!(f, "failed to execute the Lua config script: {}", err),
+        }
+    }
+}
+
+impl From<io::Error> for ConfigWatcherError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Monitors a config file on disk and hot-reloads it into a [`dynamic config`] when it changes.
+///
+/// NOTE - requires `"watch"` feature and `"ini"` and / or `"lua"` features.
+///
+/// The watcher does not spawn any threads - the user calls [`poll`] periodically (e.g. once per frame),
+/// which checks the config file modification time / size and, if it changed, re-parses the file,
+/// [`diffs`] the new config against the previous one and calls the user callback with the [`differences`]
+/// and the new config if there are any.
+///
+/// If the changed config file fails to parse, the error is returned and the previous config is kept,
+/// so the user may fix the error and save the file again.
+///
+/// [`dynamic config`]: struct.DynConfig.html
+/// [`poll`]: #method.poll
+/// [`diffs`]: struct.DynConfig.html#method.diff
+/// [`differences`]: struct.ConfigDiff.html
+pub struct ConfigWatcher<F> {
+    path: PathBuf,
+    format: ConfigWatchFormat,
+    stamp: Option<FileStamp>,
+    config: DynConfig,
+    on_change: F,
+}
+
+impl<F: FnMut(&ConfigDiff, &DynConfig)> ConfigWatcher<F> {
+    /// Creates a new watcher for the config file at `path` in the source `format`,
+    /// loading the config file.
+    ///
+    /// `on_change` is called with the [`differences`] and the new [`config`]
+    /// when the config file changes; it is not called for the initial load.
+    ///
+    /// Returns an [`error`] if the config file could not be read or parsed.
+    ///
+    /// [`differences`]: struct.ConfigDiff.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`error`]: enum.ConfigWatcherError.html
+    pub fn new<P: Into<PathBuf>>(
+        path: P,
+        format: ConfigWatchFormat,
+        on_change: F,
+    ) -> Result<Self, ConfigWatcherError> {
+        let path = path.into();
+
+        let stamp = FileStamp::read(&path)?;
+        let config = load_config(&path, format)?;
+
+        Ok(Self {
+            path,
+            format,
+            stamp: Some(stamp),
+            config,
+            on_change,
+        })
+    }
+
+    /// Returns the path to the watched config file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the current (last successfully loaded) [`config`](struct.DynConfig.html).
+    pub fn config(&self) -> &DynConfig {
+        &self.config
+    }
+
+    /// Consumes the watcher and returns the current (last successfully loaded) [`config`](struct.DynConfig.html).
+    pub fn into_config(self) -> DynConfig {
+        self.config
+    }
+
+    /// Checks whether the config file changed since the last call and [`reloads`] it if it did.
+    ///
+    /// Returns `true` if the config changed and the user callback was called.
+    ///
+    /// Returns an [`error`] if the config file could not be accessed, read or parsed.
+    /// The previous config is kept in this case.
+    /// A config file which failed to parse is not reloaded again until it changes.
+    ///
+    /// [`reloads`]: #method.reload
+    /// [`error`]: enum.ConfigWatcherError.html
+    pub fn poll(&mut self) -> Result<bool, ConfigWatcherError> {
+        let stamp = FileStamp::read(&self.path)?;
+
+        if self.stamp == Some(stamp) {
+            return Ok(false);
+        }
+
+        self.stamp = Some(stamp);
+
+        self.reload()
+    }
+
+    /// Unconditionally reloads the config file, diffs the new config against the current one
+    /// and calls the user callback if there are any differences.
+    ///
+    /// Returns `true` if the config changed and the user callback was called.
+    ///
+    /// Returns an [`error`] if the config file could not be read or parsed.
+    /// The previous config is kept in this case.
+    ///
+    /// [`error`]: enum.ConfigWatcherError.html
+    pub fn reload(&mut self) -> Result<bool, ConfigWatcherError> {
+        let config = load_config(&self.path, self.format)?;
+
+        let diff = self.config.diff(&config);
+
+        if diff.is_empty() {
+            return Ok(false);
+        }
+
+        self.config = config;
+        (self.on_change)(&diff, &self.config);
+
+        Ok(true)
+    }
+}
+
+/// Modification time and size of the watched config file.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Result<Self, io::Error> {
+        let metadata = fs::metadata(path)?;
+
+        Ok(Self {
+            // Not available on all platforms - fall back to comparing the file size.
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+fn load_config(path: &Path, format: ConfigWatchFormat) -> Result<DynConfig, ConfigWatcherError> {
+    let source = fs::read_to_string(path)?;
+
+    match format {
+        #[cfg(feature = "ini")]
+        ConfigWatchFormat::Ini => {
+            DynConfig::from_ini(IniParser::new(&source)).map_err(ConfigWatcherError::Ini)
+        }
+        #[cfg(feature = "ini")]
+        ConfigWatchFormat::IniWithOptions(options) => {
+            DynConfig::from_ini(options(IniParser::new(&source))).map_err(ConfigWatcherError::Ini)
+        }
+        #[cfg(feature = "lua_runtime")]
+        ConfigWatchFormat::Lua => {
+            let lua = rlua::Lua::new();
+
+            lua.context(|lua| {
+                LuaConfig::from_script(lua, &source)
+                    .map(|config| config.to_dyn_config())
+                    .map_err(ConfigWatcherError::Lua)
+            })
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ini"))]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {
+        crate::*,
+        std::{cell::RefCell, fs, rc::Rc},
+    };
+
+    #[test]
+    fn watch() {
+        let path =
+            std::env::temp_dir().join(format!("miniconfig_watch_test_{}.ini", std::process::id()));
+
+        fs::write(&path, "foo = 7\nbar = true").unwrap();
+
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let changes_clone = changes.clone();
+
+        let mut watcher = ConfigWatcher::new(
+            &path,
+            ConfigWatchFormat::IniWithOptions(|parser| parser.unquoted_strings(true)),
+            move |diff: &ConfigDiff, config: &DynConfig| {
+                assert_eq!(config.root().len(), 2);
+                changes_clone
+                    .borrow_mut()
+                    .extend(diff.iter().map(|change| change.to_string()));
+            },
+        )
+        .unwrap();
+
+        assert_eq!(watcher.config().root().get_i64("foo").unwrap(), 7);
+
+        // Unchanged.
+        assert!(!watcher.poll().unwrap());
+        assert!(changes.borrow().is_empty());
+
+        // Changed.
+        fs::write(&path, "foo = 9\nbaz = qux").unwrap();
+
+        assert!(watcher.poll().unwrap());
+        assert_eq!(
+            *changes.borrow(),
+            vec![
+                "- bar: true".to_owned(),
+                "+ baz: \"qux\"".to_owned(),
+                "~ foo: 7 -> 9".to_owned(),
+            ]
+        );
+        assert_eq!(watcher.config().root().get_string("baz").unwrap(), "qux");
+
+        assert!(!watcher.poll().unwrap());
+
+        // Invalid config is reported, previous config is kept.
+        fs::write(&path, "foo = 9\nbaz = qux\n[").unwrap();
+
+        let err = watcher.poll().err().unwrap();
+        assert!(matches!(err, ConfigWatcherError::Ini(_)));
+        // The parse error is exposed as the error source.
+        assert_eq!(
+            std::error::Error::source(&err)
+                .unwrap()
+                .downcast_ref::<IniError>()
+                .unwrap()
+                .error,
+            IniErrorKind::UnexpectedEndOfFileInSectionName
+        );
+        assert_eq!(watcher.config().root().get_i64("foo").unwrap(), 9);
+
+        // Not reloaded until changed.
+        assert!(!watcher.poll().unwrap());
+
+        // Missing file is reported.
+        fs::remove_file(&path).unwrap();
+
+        let err = watcher.poll().err().unwrap();
+        assert!(matches!(err, ConfigWatcherError::Io(_)));
+        assert_eq!(
+            std::error::Error::source(&err)
+                .unwrap()
+                .downcast_ref::<std::io::Error>()
+                .unwrap()
+                .kind(),
+            std::io::ErrorKind::NotFound
+        );
+        assert_eq!(changes.borrow().len(), 3);
+    }
+}
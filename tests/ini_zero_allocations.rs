@@ -0,0 +1,151 @@
+//! Checks that parsing escape-free `.ini` sources does not allocate.
+//!
+//! Installs a counting global allocator, so it runs in its own test binary without the test harness
+//! (i.e. single-threaded) and does not affect the allocations made by the other tests.
+
+use {
+    miniconfig::*,
+    std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// Counts the heap allocations made by the process.
+struct CountingAllocator;
+
+static NUM_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the number of heap allocations made while calling `f`.
+fn num_allocations<F: FnOnce()>(f: F) -> usize {
+    let start = NUM_ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    NUM_ALLOCATIONS.load(Ordering::Relaxed) - start
+}
+
+/// Event handler which requires all section names / keys / (non-empty) string values to be borrowed from the source.
+struct BorrowedIniConfig {
+    num_values: u32,
+}
+
+impl<'s> IniConfig<'s> for BorrowedIniConfig {
+    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        assert!(key.as_borrowed().is_some());
+        None
+    }
+
+    fn add_value(
+        &mut self,
+        key: NonEmptyIniStr<'s, '_>,
+        value: IniValue<'s, '_>,
+        _overwrite: bool,
+    ) {
+        assert!(key.as_borrowed().is_some());
+
+        if let IniValue::String(value) = value {
+            assert!(!matches!(value, IniStr::Owned(_)));
+        }
+
+        self.num_values += 1;
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, _overwrite: bool) {
+        assert!(section.as_borrowed().is_some());
+    }
+
+    fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
+        assert!(section.as_borrowed().is_some());
+    }
+
+    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, _overwrite: bool) {
+        assert!(array.as_borrowed().is_some());
+    }
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        if let IniValue::String(value) = value {
+            assert!(!matches!(value, IniStr::Owned(_)));
+        }
+    }
+
+    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+        assert!(array.as_borrowed().is_some());
+    }
+}
+
+fn zero_allocations() {
+    let ini = "; Comment\n\
+        key = value\n\
+        \"quoted key\" = \"quoted value\"\n\
+        int = 7\n\
+        float = 3.14\n\
+        bool = true\n\
+        empty = \"\"\n\
+        array = [\"foo\", \"bar\", baz]\n\
+        \n\
+        [section]\n\
+        key = value ; Inline comment\n\
+        \n\
+        [section/nested]\n\
+        key = value\n";
+
+    let parser = || {
+        IniParser::new(ini)
+            .arrays(true)
+            .inline_comments(true)
+            .nested_section_depth(2)
+            .implicit_parent_sections(true)
+    };
+
+    let mut config = BorrowedIniConfig { num_values: 0 };
+    let mut scratch = IniParserScratch::new();
+
+    // The first parse may allocate the scratch state buffers (i.e. the nested section path).
+    parser()
+        .parse_with_scratch(&mut config, &mut scratch)
+        .unwrap();
+    assert_eq!(config.num_values, 8);
+
+    // Subsequent parses of escape-free sources do not allocate.
+    assert_eq!(
+        num_allocations(|| parser()
+            .parse_with_scratch(&mut config, &mut scratch)
+            .unwrap()),
+        0
+    );
+    assert_eq!(config.num_values, 16);
+
+    // No scratch state buffers are required for sources without nested sections.
+    let ini = "key = value\n[section]\nkey = \"quoted value\" ; Inline comment\nint = 7";
+
+    assert_eq!(
+        num_allocations(|| IniParser::new(ini)
+            .inline_comments(true)
+            .parse(&mut config)
+            .unwrap()),
+        0
+    );
+    assert_eq!(config.num_values, 19);
+}
+
+fn main() {
+    zero_allocations();
+}